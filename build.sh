@@ -19,6 +19,9 @@ function subcommand_pack_map {
 
     mkdir -p ftp
     cp -r --preserve=timestamps assets/maps.txt build/maps ftp/
+    if [ -d build/textures ]; then
+        cp -r --preserve=timestamps build/textures ftp/
+    fi
 }
 
 function subcommand_pack_all_maps {
@@ -35,6 +38,9 @@ function subcommand_pack_all_maps {
 
     mkdir -p ftp
     cp -r --preserve=timestamps assets/maps.txt build/maps ftp/
+    if [ -d build/textures ]; then
+        cp -r --preserve=timestamps build/textures ftp/
+    fi
 }
 
 function subcommand_pack_model {
@@ -132,6 +138,9 @@ function subcommand_build_gcm {
     mkdir -p disc_root
     rm -rf disc_root/*
     cp -r --preserve=timestamps ../assets/{opening.bnr,maps.txt} maps disc_root/
+    if [ -d textures ]; then
+        cp -r --preserve=timestamps textures disc_root/
+    fi
 
     popd >/dev/null
     pushd pc >/dev/null
@@ -0,0 +1,344 @@
+//! A drop-down debug console.
+//!
+//! There's no keyboard, so text is entered a character at a time with the menu actions while the
+//! console is open:
+//!
+//! - `MenuUp` and `MenuDown` cycle the character under the cursor.
+//! - `MenuRight` accepts it and moves on. `MenuLeft` deletes the previous character.
+//! - `Ascend` completes the command or map name being typed.
+//! - `Descend` recalls earlier commands, oldest last.
+//! - `Confirm` runs the line and `Back` closes the console.
+//!
+//! Commands are parsed here against [`COMMANDS`] and handed back to the game loop to run.
+
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use font_gx::TextRenderer;
+use gamecube_gx::{apply_vertex_format, DebugColorVertex, Gx};
+use gamecube_math::Mtx34;
+use inception_render_common::vertex_format::VertexFormat;
+use ogc_sys::*;
+
+use crate::input::{Action, Bindings, Input};
+use crate::shaders::flat_vertex_color::FLAT_VERTEX_COLOR_SHADER;
+
+/// Characters the cursor cycles through, starting from a space.
+const CHARSET: &[u8] = b" abcdefghijklmnopqrstuvwxyz0123456789_-.";
+
+const OUTPUT_LINES: usize = 8;
+const HISTORY_LEN: usize = 32;
+
+/// A command parsed from the console, ready for the game loop to run.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    Noclip,
+    Teleport([f32; 3]),
+    Map(String),
+    LightmapStyle(usize),
+    Stats,
+    Occlusion,
+    Flash,
+    Glow,
+    ClearLights,
+}
+
+struct CommandSpec {
+    name: &'static str,
+    usage: &'static str,
+    /// Returns `None` if the arguments are malformed.
+    parse: fn(&[&str]) -> Option<Command>,
+}
+
+/// Every command the console knows, in the order `help` lists them.
+const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "help",
+        usage: "help",
+        // Handled by the console itself.
+        parse: |_| None,
+    },
+    CommandSpec {
+        name: "noclip",
+        usage: "noclip",
+        parse: |args| args.is_empty().then_some(Command::Noclip),
+    },
+    CommandSpec {
+        name: "teleport",
+        usage: "teleport x y z",
+        parse: |args| match args {
+            [x, y, z] => Some(Command::Teleport([
+                x.parse().ok()?,
+                y.parse().ok()?,
+                z.parse().ok()?,
+            ])),
+            _ => None,
+        },
+    },
+    CommandSpec {
+        name: "map",
+        usage: "map name",
+        parse: |args| match args {
+            [name] => Some(Command::Map(name.to_string())),
+            _ => None,
+        },
+    },
+    CommandSpec {
+        name: "lightmap_style",
+        usage: "lightmap_style n",
+        parse: |args| match args {
+            [style] => Some(Command::LightmapStyle(style.parse().ok()?)),
+            _ => None,
+        },
+    },
+    CommandSpec {
+        name: "stats",
+        usage: "stats",
+        parse: |args| args.is_empty().then_some(Command::Stats),
+    },
+    CommandSpec {
+        name: "occlusion",
+        usage: "occlusion",
+        parse: |args| args.is_empty().then_some(Command::Occlusion),
+    },
+    CommandSpec {
+        name: "flash",
+        usage: "flash",
+        parse: |args| args.is_empty().then_some(Command::Flash),
+    },
+    CommandSpec {
+        name: "glow",
+        usage: "glow",
+        parse: |args| args.is_empty().then_some(Command::Glow),
+    },
+    CommandSpec {
+        name: "clear_lights",
+        usage: "clear_lights",
+        parse: |args| args.is_empty().then_some(Command::ClearLights),
+    },
+];
+
+pub struct Console {
+    open: bool,
+    line: String,
+    /// Index into `CHARSET` of the character under the cursor, not yet part of `line`.
+    pending: usize,
+    /// Most recent last.
+    history: VecDeque<String>,
+    /// How far back `Descend` has stepped into `history`, if at all.
+    history_cursor: Option<usize>,
+    output: VecDeque<String>,
+    /// Candidates for completing the argument to `map`.
+    map_names: Vec<String>,
+}
+
+impl Console {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            line: String::new(),
+            pending: 0,
+            history: VecDeque::new(),
+            history_cursor: None,
+            output: VecDeque::new(),
+            map_names: Vec::new(),
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn toggle(&mut self) {
+        self.open ^= true;
+    }
+
+    pub fn set_map_names(&mut self, map_names: Vec<String>) {
+        self.map_names = map_names;
+    }
+
+    /// Appends a line to the output area, scrolling off the oldest.
+    pub fn print(&mut self, line: impl Into<String>) {
+        if self.output.len() == OUTPUT_LINES {
+            self.output.pop_front();
+        }
+        self.output.push_back(line.into());
+    }
+
+    /// Handles one frame of input while open. Returns a command when one is entered.
+    pub fn update(&mut self, input: &Input, bindings: &Bindings) -> Option<Command> {
+        if input.is_pressed(bindings, Action::Back) {
+            self.open = false;
+            return None;
+        }
+        if input.is_pressed(bindings, Action::MenuUp) {
+            self.pending = (self.pending + 1) % CHARSET.len();
+        }
+        if input.is_pressed(bindings, Action::MenuDown) {
+            self.pending = self.pending.checked_sub(1).unwrap_or(CHARSET.len() - 1);
+        }
+        if input.is_pressed(bindings, Action::MenuRight) {
+            self.accept_pending();
+        }
+        if input.is_pressed(bindings, Action::MenuLeft) {
+            self.pending = 0;
+            self.line.pop();
+        }
+        if input.is_pressed(bindings, Action::Ascend) {
+            if self.pending != 0 {
+                self.accept_pending();
+            }
+            self.complete();
+        }
+        if input.is_pressed(bindings, Action::Descend) {
+            self.recall();
+        }
+        if input.is_pressed(bindings, Action::Confirm) {
+            if self.pending != 0 {
+                self.accept_pending();
+            }
+            let line = core::mem::take(&mut self.line);
+            return self.submit(line.trim());
+        }
+        None
+    }
+
+    fn accept_pending(&mut self) {
+        self.line.push(CHARSET[self.pending] as char);
+        self.pending = 0;
+        self.history_cursor = None;
+    }
+
+    fn submit(&mut self, line: &str) -> Option<Command> {
+        self.history_cursor = None;
+        if line.is_empty() {
+            return None;
+        }
+        self.print(format!("] {}", line));
+        if self.history.back().map(String::as_str) != Some(line) {
+            if self.history.len() == HISTORY_LEN {
+                self.history.pop_front();
+            }
+            self.history.push_back(line.to_string());
+        }
+
+        let mut words = line.split_ascii_whitespace();
+        let name = words.next()?;
+        let args: Vec<&str> = words.collect();
+        let Some(spec) = COMMANDS.iter().find(|spec| spec.name == name) else {
+            self.print(format!("unknown command: {}", name));
+            return None;
+        };
+        if spec.name == "help" {
+            for spec in COMMANDS {
+                self.print(spec.usage);
+            }
+            return None;
+        }
+        let command = (spec.parse)(&args);
+        match &command {
+            None => self.print(format!("usage: {}", spec.usage)),
+            Some(Command::Map(name)) if !self.map_names.contains(name) => {
+                self.print(format!("no such map: {}", name));
+                return None;
+            }
+            _ => (),
+        }
+        command
+    }
+
+    /// Completes the word being typed from command names, or from map names after `map`. An
+    /// ambiguous word is extended as far as the candidates agree and the candidates are listed.
+    fn complete(&mut self) {
+        let (prefix, word) = match self.line.rfind(' ') {
+            Some(space) => self.line.split_at(space + 1),
+            None => ("", self.line.as_str()),
+        };
+        let candidates: Vec<&str> = match prefix.trim_end() {
+            "" => COMMANDS.iter().map(|spec| spec.name).collect(),
+            "map" => self.map_names.iter().map(String::as_str).collect(),
+            _ => return,
+        };
+        let matches: Vec<&str> = candidates
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(word))
+            .collect();
+
+        let completed = match matches.as_slice() {
+            [] => return,
+            [only] => format!("{}{} ", prefix, only),
+            [first, rest @ ..] => {
+                let common = rest.iter().fold(first.len(), |len, candidate| {
+                    first
+                        .bytes()
+                        .zip(candidate.bytes())
+                        .take(len)
+                        .take_while(|(a, b)| a == b)
+                        .count()
+                });
+                let listing = matches.join(" ");
+                let completed = format!("{}{}", prefix, &first[..common]);
+                self.print(listing);
+                completed
+            }
+        };
+        self.line = completed;
+    }
+
+    /// Replaces the line with the next older history entry, wrapping back to an empty line.
+    fn recall(&mut self) {
+        let cursor = match self.history_cursor {
+            None => 0,
+            Some(cursor) => cursor + 1,
+        };
+        self.pending = 0;
+        if cursor < self.history.len() {
+            self.line = self.history[self.history.len() - 1 - cursor].clone();
+            self.history_cursor = Some(cursor);
+        } else {
+            self.line.clear();
+            self.history_cursor = None;
+        }
+    }
+
+    /// Draws the console over the top of the screen. Expects the screen-space projection that
+    /// the debug overlay uses.
+    pub fn draw(&self, gx: &mut Gx, width: u16, ui_font: &GXTexObj) {
+        if !self.open {
+            return;
+        }
+        let height = 16 * (OUTPUT_LINES as u16 + 1) + 16;
+        unsafe {
+            apply_vertex_format(&VertexFormat::DebugColor.descriptor());
+            FLAT_VERTEX_COLOR_SHADER.apply();
+            GX_SetZMode(GX_FALSE as u8, GX_ALWAYS as u8, GX_FALSE as u8);
+
+            let mut view = Mtx34::identity();
+            GX_LoadPosMtxImm(view.as_mut_ptr(), GX_PNMTX0);
+
+            gx.quad(
+                [(0, 0), (width, 0), (width, height), (0, height)].map(|(x, y)| DebugColorVertex {
+                    pos: [x, y],
+                    clr0: [0, 0, 48],
+                }),
+            );
+        }
+
+        TextRenderer::prepare(ui_font);
+        let mut r = TextRenderer {
+            x: 16,
+            y: 8 + 16 * (OUTPUT_LINES - self.output.len()) as u16,
+            left_margin: 16,
+        };
+        for line in &self.output {
+            r.draw_str(gx, line.as_bytes());
+            r.new_line();
+        }
+        r.draw_str(gx, b"] ");
+        r.draw_str(gx, self.line.as_bytes());
+        r.draw_char(gx, b'[');
+        r.draw_char(gx, CHARSET[self.pending]);
+        r.draw_char(gx, b']');
+    }
+}
@@ -0,0 +1,310 @@
+//! A panic handler that draws a crash report straight into the external framebuffer.
+//!
+//! This deliberately avoids the libogc console and GX. Either may be in an arbitrary state when a
+//! panic happens, and a report that never reaches the screen is no help when debugging on
+//! hardware.
+
+use core::fmt::{self, Display, Write};
+use core::panic::PanicInfo;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use gamecube_cpu::registers::msr::mfmsr;
+use gamecube_cpu::registers::{dar, dsisr, hid0, hid2, link_register, srr0, srr1, stack_pointer};
+use ogc_sys::*;
+
+use crate::input::{Action, Bindings, Input};
+use crate::XFB_FRONT;
+
+extern "C" {
+    /// The 8x16 console font built into libogc. Each glyph is 16 bytes, one per row, with the
+    /// leftmost pixel in the most significant bit.
+    static console_font_8x16: [u8; 256 * 16];
+}
+
+const SCREEN_WIDTH: usize = 640;
+const SCREEN_HEIGHT: usize = 480;
+const MARGIN: usize = 32;
+const CELL_WIDTH: usize = 8;
+const CELL_HEIGHT: usize = 16;
+
+// Y'CbCr values. Chroma is shared by every pixel so that each glyph row only affects luma.
+const BACKGROUND_Y: u32 = 0x28;
+const FOREGROUND_Y: u32 = 0xeb;
+const CHROMA_U: u32 = 0x6a;
+const CHROMA_V: u32 = 0xc8;
+
+const MAX_FRAMES: usize = 16;
+
+static PANICKING: AtomicBool = AtomicBool::new(false);
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    // Capture registers before doing anything that might disturb them.
+    let registers = Registers::capture();
+    let backtrace = Backtrace::capture(registers.sp);
+
+    if PANICKING.swap(true, Ordering::AcqRel) {
+        // A second panic while reporting the first. Leave whatever made it to the screen alone.
+        wait_for_start_and_exit();
+    }
+
+    // Keep the retrace callback from flipping to the back buffer, which GX may still be drawing
+    // into.
+    unsafe { drop(VIDEO_SetPreRetraceCallback(None)) };
+
+    let mut screen = CrashScreen::new();
+    write_report(&mut screen, info, &registers, &backtrace).ok();
+
+    #[cfg(feature = "ftp_loader")]
+    {
+        use crate::loader::ftp_loader::{ftp_put, SERVER_ADDR, SERVER_PORT};
+        use crate::net::SocketAddr;
+
+        let mut report = ReportBuffer::new();
+        write_report(&mut report, info, &registers, &backtrace).ok();
+        let addr = SocketAddr::new(SERVER_ADDR, SERVER_PORT);
+        match ftp_put(&addr, "crash.txt", report.as_bytes()) {
+            Ok(()) => writeln!(screen, "Report uploaded to crash.txt.").ok(),
+            Err(e) => writeln!(screen, "Report upload failed: {e:?}").ok(),
+        };
+    }
+
+    writeln!(screen, "Press Start to exit to the loader.").ok();
+    wait_for_start_and_exit();
+}
+
+fn write_report(
+    w: &mut impl Write,
+    info: &PanicInfo,
+    registers: &Registers,
+    backtrace: &Backtrace,
+) -> fmt::Result {
+    writeln!(w, "{info}")?;
+    writeln!(w)?;
+    writeln!(w, "{registers}")?;
+    writeln!(w, "{backtrace}")
+}
+
+fn wait_for_start_and_exit() -> ! {
+    unsafe {
+        loop {
+            if Input::scan().is_pressed(&Bindings::default(), Action::Menu) {
+                libc::exit(0);
+            }
+
+            VIDEO_WaitVSync();
+        }
+    }
+}
+
+struct Registers {
+    lr: u32,
+    sp: u32,
+    msr: u32,
+    srr0: u32,
+    srr1: u32,
+    dsisr: u32,
+    dar: u32,
+    hid0: u32,
+    hid2: u32,
+}
+
+impl Registers {
+    #[inline(always)]
+    fn capture() -> Self {
+        Self {
+            lr: link_register(),
+            sp: stack_pointer(),
+            msr: mfmsr().as_u32(),
+            srr0: srr0(),
+            srr1: srr1(),
+            dsisr: dsisr(),
+            dar: dar(),
+            hid0: hid0(),
+            hid2: hid2(),
+        }
+    }
+}
+
+impl Display for Registers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "LR   0x{:08x}  SP    0x{:08x}  MSR  0x{:08x}",
+            self.lr, self.sp, self.msr,
+        )?;
+        writeln!(f, "SRR0 0x{:08x}  SRR1  0x{:08x}", self.srr0, self.srr1)?;
+        writeln!(f, "DAR  0x{:08x}  DSISR 0x{:08x}", self.dar, self.dsisr)?;
+        writeln!(f, "HID0 0x{:08x}  HID2  0x{:08x}", self.hid0, self.hid2)
+    }
+}
+
+/// Return addresses recovered by following the stack's back chain.
+///
+/// Under the PowerPC EABI, the word at the stack pointer is the caller's stack pointer and the word
+/// after that in the caller's frame is where the callee saves the link register. The walk stops at
+/// the first pointer that doesn't look like a stack frame in MEM1.
+struct Backtrace {
+    frames: [u32; MAX_FRAMES],
+    len: usize,
+}
+
+impl Backtrace {
+    fn capture(sp: u32) -> Self {
+        let mut frames = [0; MAX_FRAMES];
+        let mut len = 0;
+        let mut sp = sp;
+        while len < MAX_FRAMES && is_plausible_frame(sp) {
+            // SAFETY: The address was checked to lie within MEM1 and be suitably aligned.
+            let next_sp = unsafe { (sp as *const u32).read_volatile() };
+            if next_sp <= sp || !is_plausible_frame(next_sp) {
+                break;
+            }
+            // SAFETY: As above.
+            frames[len] = unsafe { ((next_sp + 4) as *const u32).read_volatile() };
+            len += 1;
+            sp = next_sp;
+        }
+        Self { frames, len }
+    }
+}
+
+fn is_plausible_frame(sp: u32) -> bool {
+    (0x8000_0000..0x8180_0000 - 8).contains(&sp) && (sp & 7) == 0
+}
+
+impl Display for Backtrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Backtrace (saved link registers, innermost first):")?;
+        for (i, lr) in self.frames[..self.len].iter().enumerate() {
+            writeln!(f, "  #{i:<2} 0x{lr:08x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders text into the displayed external framebuffer.
+struct CrashScreen {
+    xfb: *mut u32,
+    x: usize,
+    y: usize,
+}
+
+impl CrashScreen {
+    const WORDS_PER_ROW: usize = SCREEN_WIDTH / 2;
+
+    fn new() -> Self {
+        let mut xfb = XFB_FRONT.load(Ordering::Acquire);
+        if xfb.is_null() {
+            // The panic happened before video was initialized.
+            crate::init_for_console();
+            xfb = XFB_FRONT.load(Ordering::Acquire);
+        }
+
+        let mut screen = Self {
+            xfb: xfb.cast(),
+            x: MARGIN,
+            y: MARGIN,
+        };
+        screen.clear();
+        unsafe {
+            VIDEO_SetNextFramebuffer(xfb);
+            VIDEO_SetBlack(false);
+            VIDEO_Flush();
+        }
+        screen
+    }
+
+    fn clear(&mut self) {
+        let word = pixel_pair(false, false);
+        for i in 0..Self::WORDS_PER_ROW * SCREEN_HEIGHT {
+            // SAFETY: The XFB holds a full screen of pixel pairs. It is an uncached mapping, so
+            // the writes are visible to the video interface without a flush.
+            unsafe { self.xfb.add(i).write_volatile(word) };
+        }
+    }
+
+    fn new_line(&mut self) {
+        self.x = MARGIN;
+        self.y += CELL_HEIGHT;
+    }
+
+    fn draw_char(&mut self, c: u8) {
+        if c == b'\n' {
+            self.new_line();
+            return;
+        }
+        if self.x + CELL_WIDTH > SCREEN_WIDTH - MARGIN {
+            self.new_line();
+        }
+        if self.y + CELL_HEIGHT > SCREEN_HEIGHT - MARGIN {
+            // Out of room. The FTP report, if any, has the rest.
+            return;
+        }
+
+        // SAFETY: The font is an immutable table provided by libogc.
+        let glyph = unsafe { &console_font_8x16[c as usize * CELL_HEIGHT..][..CELL_HEIGHT] };
+        for (row, &bits) in glyph.iter().enumerate() {
+            let base = (self.y + row) * Self::WORDS_PER_ROW + self.x / 2;
+            for pair in 0..CELL_WIDTH / 2 {
+                let shift = 6 - 2 * pair;
+                let word = pixel_pair((bits >> (shift + 1)) & 1 != 0, (bits >> shift) & 1 != 0);
+                // SAFETY: The bounds checks above keep the glyph on screen.
+                unsafe { self.xfb.add(base + pair).write_volatile(word) };
+            }
+        }
+        self.x += CELL_WIDTH;
+    }
+}
+
+impl Write for CrashScreen {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.bytes() {
+            self.draw_char(c);
+        }
+        Ok(())
+    }
+}
+
+/// Packs two horizontally adjacent pixels into the XFB's Y'CbY'Cr format.
+const fn pixel_pair(left: bool, right: bool) -> u32 {
+    const fn luma(lit: bool) -> u32 {
+        if lit {
+            FOREGROUND_Y
+        } else {
+            BACKGROUND_Y
+        }
+    }
+    luma(left) << 24 | CHROMA_U << 16 | luma(right) << 8 | CHROMA_V
+}
+
+/// A fixed-size text buffer that silently truncates, so formatting a report never allocates.
+#[cfg(feature = "ftp_loader")]
+struct ReportBuffer {
+    data: [u8; 4096],
+    len: usize,
+}
+
+#[cfg(feature = "ftp_loader")]
+impl ReportBuffer {
+    fn new() -> Self {
+        Self {
+            data: [0; 4096],
+            len: 0,
+        }
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+}
+
+#[cfg(feature = "ftp_loader")]
+impl Write for ReportBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let n = s.len().min(self.data.len() - self.len);
+        self.data[self.len..self.len + n].copy_from_slice(&s.as_bytes()[..n]);
+        self.len += n;
+        Ok(())
+    }
+}
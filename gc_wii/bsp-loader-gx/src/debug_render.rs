@@ -0,0 +1,76 @@
+use derive_try_from_primitive::TryFromPrimitive;
+use inception_render_common::pipeline_state::{BlendMode, DepthMode, PipelineState};
+
+use crate::pipeline_state::PassShader;
+
+/// Pass indices for draws outside the six BSP cluster passes, used to pick tint colors.
+pub const DISPLACEMENT_PASS_BASE: usize = 6;
+pub const SKYBOX_PASS: usize = 8;
+pub const OVERLAY_PASS_BASE: usize = 9;
+pub const PROP_PASS_BASE: usize = 11;
+
+const PASS_TINTS: [[u8; 3]; 13] = [
+    [255, 0, 0],
+    [0, 255, 0],
+    [0, 0, 255],
+    [255, 255, 0],
+    [255, 0, 255],
+    [0, 255, 255],
+    [255, 128, 0],
+    [128, 0, 255],
+    [64, 64, 64],
+    [255, 255, 255],
+    [128, 128, 128],
+    [0, 128, 64],
+    [128, 64, 0],
+];
+
+/// Each overdraw layer adds this much to every color channel, so eight layers saturate to white.
+const OVERDRAW_INCREMENT: u8 = 32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, TryFromPrimitive)]
+#[repr(u32)]
+pub enum DebugRenderMode {
+    Normal = 0,
+    /// Every rasterized fragment adds a constant amount of brightness, ignoring depth, so the
+    /// image shows how many times each pixel was filled.
+    Overdraw = 1,
+    /// Every pass draws in a flat color that identifies it.
+    PassTint = 2,
+}
+
+impl DebugRenderMode {
+    pub fn prev(self) -> Self {
+        if let Ok(result) = Self::try_from((self as u32).wrapping_sub(1)) {
+            result
+        } else {
+            Self::PassTint
+        }
+    }
+
+    pub fn next(self) -> Self {
+        if let Ok(result) = Self::try_from(self as u32 + 1) {
+            result
+        } else {
+            Self::Normal
+        }
+    }
+
+    /// Replaces parts of a pass's state with the debug mode's.
+    pub fn override_state(self, pass: usize, state: &mut PipelineState<PassShader>) {
+        match self {
+            Self::Normal => (),
+            Self::Overdraw => {
+                state.shader = PassShader::DebugConstant([OVERDRAW_INCREMENT; 3]);
+                state.blend = BlendMode::Additive;
+                state.depth = DepthMode::DISABLED;
+            }
+            Self::PassTint => state.shader = PassShader::DebugConstant(PASS_TINTS[pass]),
+        }
+    }
+
+    /// Whether this mode replaces blending and depth, in which case every layer should be drawn.
+    pub fn overrides_blend(self) -> bool {
+        self == Self::Overdraw
+    }
+}
@@ -0,0 +1,70 @@
+use core::cell::{Cell, RefCell};
+
+use aligned::{Aligned, A32};
+use gamecube_dvd_driver::{DvdDriver, SECTOR_SIZE};
+use no_std_io::{NetError, Read, Seek};
+
+/// A byte range of the disc that can be read at any offset and length, despite the drive only
+/// transferring whole multiples of 32 bytes from 4-byte aligned offsets. Reads go through a
+/// one-sector buffer, so small reads near each other only reach the drive once.
+pub struct DvdFile<'a> {
+    dvd: RefCell<&'a mut DvdDriver>,
+    /// Disc offset of the first byte.
+    start: usize,
+    len: usize,
+    pos: Cell<usize>,
+    sector: RefCell<Aligned<A32, [u8; SECTOR_SIZE]>>,
+    /// Disc offset of the sector held in `sector`, if any.
+    buffered_sector: Cell<Option<usize>>,
+}
+
+impl<'a> DvdFile<'a> {
+    pub fn new(dvd: &'a mut DvdDriver, start: usize, len: usize) -> Self {
+        Self {
+            dvd: RefCell::new(dvd),
+            start,
+            len,
+            pos: Cell::new(0),
+            sector: RefCell::new(Aligned([0; SECTOR_SIZE])),
+            buffered_sector: Cell::new(None),
+        }
+    }
+}
+
+impl Read for DvdFile<'_> {
+    fn read(&self, buf: &mut [u8]) -> Result<usize, NetError> {
+        let pos = self.pos.get();
+        let len = buf.len().min(self.len.saturating_sub(pos));
+        if len == 0 {
+            return Ok(0);
+        }
+
+        let disc_offset = self.start + pos;
+        let sector_start = disc_offset & !(SECTOR_SIZE - 1);
+        let mut sector = self.sector.borrow_mut();
+        if self.buffered_sector.get() != Some(sector_start) {
+            self.buffered_sector.set(None);
+            self.dvd
+                .borrow_mut()
+                .read(sector_start, &mut **sector)
+                .map_err(|_| NetError::Unexpected {
+                    function: "DvdDriver::read",
+                    ret: -1,
+                })?;
+            self.buffered_sector.set(Some(sector_start));
+        }
+
+        let sector_offset = disc_offset - sector_start;
+        let n = len.min(SECTOR_SIZE - sector_offset);
+        buf[..n].copy_from_slice(&sector[sector_offset..sector_offset + n]);
+        self.pos.set(pos + n);
+        Ok(n)
+    }
+}
+
+impl Seek for DvdFile<'_> {
+    fn seek(&self, pos: usize) -> Result<(), NetError> {
+        self.pos.set(pos);
+        Ok(())
+    }
+}
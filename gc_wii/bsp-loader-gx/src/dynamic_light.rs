@@ -0,0 +1,208 @@
+//! Short-lived point lights for effects like muzzle flashes and pickup glows.
+//!
+//! The world's lighting is baked, so these are layered on top of it: GX channel lighting evaluates
+//! the lights per vertex and a TEV stage adds the result to the lightmap sample. Only brush
+//! geometry has normals, so displacements, props, and overlays keep their baked lighting.
+
+use core::mem::zeroed;
+
+use gamecube_math::{Mtx34, Vec3};
+use ogc_sys::*;
+
+use crate::texture_animation::RETRACE_RATE;
+
+/// GX can light a channel with eight lights, but each costs vertex transform time.
+pub const MAX_DYNAMIC_LIGHTS: usize = 4;
+
+#[derive(Clone, Copy, Debug)]
+pub struct PointLight {
+    pub pos: Vec3,
+    pub color: [u8; 3],
+    /// Distance at which the light falls off to half brightness.
+    pub radius: f32,
+    pub effect: LightEffect,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LightEffect {
+    /// Fades out over `duration` seconds, then goes away.
+    Flash { duration: f32 },
+    /// Pulses between half and full brightness every `period` seconds until it's replaced.
+    Glow { period: f32 },
+}
+
+impl PointLight {
+    pub fn muzzle_flash(pos: Vec3) -> Self {
+        Self {
+            pos,
+            color: [255, 192, 96],
+            radius: 192.0,
+            effect: LightEffect::Flash { duration: 0.1 },
+        }
+    }
+
+    pub fn pickup_glow(pos: Vec3) -> Self {
+        Self {
+            pos,
+            color: [96, 160, 255],
+            radius: 96.0,
+            effect: LightEffect::Glow { period: 1.5 },
+        }
+    }
+
+    /// Returns the light's brightness `age` seconds after it was spawned, or `None` once it has
+    /// expired.
+    fn intensity(&self, age: f32) -> Option<f32> {
+        match self.effect {
+            LightEffect::Flash { duration } => (age < duration).then(|| 1.0 - age / duration),
+            LightEffect::Glow { period } => {
+                Some(0.75 + 0.25 * libm::cosf(2.0 * core::f32::consts::PI * age / period))
+            }
+        }
+    }
+}
+
+struct ActiveLight {
+    light: PointLight,
+    spawn_retrace_count: u32,
+}
+
+impl ActiveLight {
+    fn intensity(&self, retrace_count: u32) -> Option<f32> {
+        let retraces = retrace_count.wrapping_sub(self.spawn_retrace_count);
+        self.light.intensity(retraces as f32 / RETRACE_RATE)
+    }
+}
+
+/// The dynamic lights in the scene, loaded into GX hardware lights for the brush passes.
+#[derive(Default)]
+pub struct DynamicLights {
+    lights: [Option<ActiveLight>; MAX_DYNAMIC_LIGHTS],
+}
+
+impl DynamicLights {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a light, replacing the oldest one if every slot is taken.
+    pub fn spawn(&mut self, light: PointLight) {
+        let now = unsafe { VIDEO_GetRetraceCount() };
+        let slot = match self.lights.iter().position(Option::is_none) {
+            Some(slot) => slot,
+            None => (0..MAX_DYNAMIC_LIGHTS)
+                .max_by_key(|&slot| {
+                    now.wrapping_sub(self.lights[slot].as_ref().unwrap().spawn_retrace_count)
+                })
+                .unwrap(),
+        };
+        self.lights[slot] = Some(ActiveLight {
+            light,
+            spawn_retrace_count: now,
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.lights = Default::default();
+    }
+
+    /// Drops lights that have finished fading out.
+    pub fn update(&mut self) {
+        let now = unsafe { VIDEO_GetRetraceCount() };
+        for slot in &mut self.lights {
+            if slot
+                .as_ref()
+                .map_or(false, |active| active.intensity(now).is_none())
+            {
+                *slot = None;
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lights.iter().all(Option::is_none)
+    }
+
+    /// Loads the lights into GX and enables lighting on color channel 0, which the dynamic
+    /// lightmapped shaders add in.
+    ///
+    /// GX lights in view space, so light positions are transformed by `view` and its rotation is
+    /// loaded as the normal matrix. Undo this with [`Self::unload`] before drawing anything that
+    /// takes channel 0 from vertex colors.
+    pub unsafe fn load(&self, view: &Mtx34) {
+        let now = unsafe { VIDEO_GetRetraceCount() };
+        let mut light_mask = 0;
+        for (index, active) in self.lights.iter().enumerate() {
+            let Some(active) = active else {
+                continue;
+            };
+            let Some(intensity) = active.intensity(now) else {
+                continue;
+            };
+            let pos = view.transform_point(active.light.pos);
+            let [r, g, b] = active.light.color.map(|c| (c as f32 * intensity) as u8);
+            let id = GX_LIGHT0 << index;
+            unsafe {
+                let mut light = zeroed::<GXLightObj>();
+                GX_InitLightPos(&mut light, pos.x, pos.y, pos.z);
+                GX_InitLightColor(&mut light, GXColor { r, g, b, a: 255 });
+                GX_InitLightSpot(&mut light, 0.0, GX_SP_OFF as u8);
+                GX_InitLightDistAttn(&mut light, active.light.radius, 0.5, GX_DA_MEDIUM as u8);
+                GX_LoadLightObj(&mut light, id as u8);
+            }
+            light_mask |= id;
+        }
+
+        unsafe {
+            // The view matrix is a rotation and a translation, so its upper 3x3 is its own
+            // inverse transpose.
+            let mut view = *view;
+            GX_LoadNrmMtxImm(view.as_mut_ptr(), GX_PNMTX0);
+
+            GX_SetChanAmbColor(
+                GX_COLOR0 as i32,
+                GXColor {
+                    r: 0,
+                    g: 0,
+                    b: 0,
+                    a: 0,
+                },
+            );
+            GX_SetChanMatColor(
+                GX_COLOR0 as i32,
+                GXColor {
+                    r: 255,
+                    g: 255,
+                    b: 255,
+                    a: 255,
+                },
+            );
+            GX_SetChanCtrl(
+                GX_COLOR0 as i32,
+                GX_ENABLE as u8,
+                GX_SRC_REG as u8,
+                GX_SRC_REG as u8,
+                light_mask as u8,
+                GX_DF_CLAMP as u8,
+                GX_AF_SPOT as u8,
+            );
+        }
+    }
+
+    /// Turns lighting back off, passing vertex colors through channel 0 as GX does by default.
+    pub unsafe fn unload() {
+        unsafe {
+            GX_SetChanCtrl(
+                GX_COLOR0 as i32,
+                GX_DISABLE as u8,
+                GX_SRC_REG as u8,
+                GX_SRC_VTX as u8,
+                GX_LIGHTNULL as u8,
+                GX_DF_NONE as u8,
+                GX_AF_NONE as u8,
+            );
+            let mut identity = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+            GX_LoadNrmMtxImm3x3(identity.as_mut_ptr(), GX_PNMTX0);
+        }
+    }
+}
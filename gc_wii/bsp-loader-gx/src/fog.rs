@@ -0,0 +1,92 @@
+//! Distance fog from the map's Source fog parameters, drawn with GX's hardware fog.
+//!
+//! Source fades linearly with eye depth from `start` to `end`, where the fog reaches
+//! `max_density` and stops thickening. GX fog always ramps all the way to opaque, so a thin fog's
+//! ramp is stretched to pass through `max_density` at `end` instead, and keeps thickening past it.
+//!
+//! The skybox is drawn around the camera at a distance of a few units, so depth fog would leave it
+//! clear. It stands in for everything past the world, so it gets the `sky_camera`'s fog at a flat
+//! `max_density` instead, or no fog if the map has none.
+
+use core::ops::Deref;
+
+use gamecube_gx::Gx;
+use inception_render_common::map_data::{FogTableEntry, MapData};
+use ogc_sys::*;
+
+/// The skybox's fog ramp is pushed this far away so it's flat across the skybox's faces.
+const SKYBOX_RAMP_LENGTH: f32 = 1_000_000.0;
+
+pub struct MapFog {
+    world: Option<FogTableEntry>,
+    skybox: Option<FogTableEntry>,
+}
+
+impl MapFog {
+    pub fn new<Data: Deref<Target = [u8]>>(map_data: &MapData<Data>) -> Self {
+        let usable = |entry: &&FogTableEntry| entry.max_density > 0.0 && entry.end > entry.start;
+        Self {
+            world: map_data.fog(FogTableEntry::WORLD).filter(usable).copied(),
+            skybox: map_data
+                .fog(FogTableEntry::SKYBOX)
+                .filter(|entry| entry.max_density > 0.0)
+                .copied(),
+        }
+    }
+
+    /// Fogs what's drawn next as world geometry, with the camera's near and far planes.
+    pub fn apply_world(&self, gx: &Gx, near_z: f32, far_z: f32) {
+        match self.world {
+            Some(fog) => {
+                let end = fog.start + (fog.end - fog.start) / fog.max_density;
+                set_fog(gx, &fog, fog.start, end, near_z, far_z);
+            }
+            None => disable(gx),
+        }
+    }
+
+    /// Fogs what's drawn next as the skybox, with the camera's near and far planes.
+    pub fn apply_skybox(&self, gx: &Gx, near_z: f32, far_z: f32) {
+        match self.skybox {
+            Some(fog) => {
+                // Depth is a rounding error next to the ramp, so every face sits at max_density.
+                let start = -fog.max_density * SKYBOX_RAMP_LENGTH;
+                set_fog(gx, &fog, start, start + SKYBOX_RAMP_LENGTH, near_z, far_z);
+            }
+            None => disable(gx),
+        }
+    }
+}
+
+/// Turns fog off for what's drawn next, like the debug overlays.
+pub fn disable(_gx: &Gx) {
+    unsafe {
+        GX_SetFog(
+            GX_FOG_NONE as u8,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            GXColor {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 0,
+            },
+        );
+    }
+}
+
+fn set_fog(_gx: &Gx, fog: &FogTableEntry, start: f32, end: f32, near_z: f32, far_z: f32) {
+    let [r, g, b] = fog.color;
+    unsafe {
+        GX_SetFog(
+            GX_FOG_PERSP_LIN as u8,
+            start,
+            end,
+            near_z,
+            far_z,
+            GXColor { r, g, b, a: 255 },
+        );
+    }
+}
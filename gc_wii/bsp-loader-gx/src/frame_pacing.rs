@@ -0,0 +1,137 @@
+//! Frame pacing.
+//!
+//! A frame that misses vsync is shown one retrace late, and if the next frame makes it the two
+//! are shown for uneven times. A run of borderline frames turns into visible judder. The governor
+//! here instead picks how many retraces every frame is shown for (1, 2, or 3, for 60, 30, or 20
+//! Hz) from recent frame costs, and the retrace callback holds each flip until its slot comes
+//! around.
+//!
+//! A frame's cost is the larger of its CPU time and the time until the GPU finished it, which is
+//! timestamped by a PE token at the end of the frame's commands.
+
+use core::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+
+use gamecube_cpu::registers::TIME_BASE_TICKS_PER_MS;
+use ogc_sys::*;
+
+/// Time base ticks per NTSC field.
+const TICKS_PER_RETRACE: u32 = (TIME_BASE_TICKS_PER_MS * 1001 / 60) as u32;
+
+const MAX_INTERVAL: usize = 3;
+
+/// How many recent frame costs are kept.
+const HISTORY_LEN: usize = 16;
+
+/// How many of the recent frames may miss their budget before slowing down.
+const MAX_MISSES: usize = 1;
+
+/// How many frames in a row must fit a faster interval before switching to it.
+const SPEED_UP_FRAMES: u32 = 90;
+
+/// The share of a faster interval's budget a frame may use and still count as fitting, in percent,
+/// so pacing doesn't flap on frames near the boundary.
+const SPEED_UP_HEADROOM_PERCENT: u32 = 80;
+
+const FRAME_END_TOKEN: u16 = 0xf9ac;
+
+static GPU_DONE_TICK: AtomicU32 = AtomicU32::new(0);
+static FLIP_INTERVAL: AtomicUsize = AtomicUsize::new(1);
+
+extern "C" fn on_draw_sync(token: u16) {
+    if token == FRAME_END_TOKEN {
+        GPU_DONE_TICK.store(unsafe { gettick() }, Ordering::Release);
+    }
+}
+
+/// The number of retraces each frame is currently shown for. The retrace callback flips only once
+/// this many have passed since the last flip.
+pub fn flip_interval() -> usize {
+    FLIP_INTERVAL.load(Ordering::Acquire)
+}
+
+pub struct FramePacer {
+    interval: usize,
+    frame_start: u32,
+    cpu_ticks: u32,
+    gpu_ticks: u32,
+    costs: [u32; HISTORY_LEN],
+    next_cost: usize,
+    /// Consecutive frames that would have fit the next faster interval.
+    fits_faster: u32,
+}
+
+impl FramePacer {
+    pub fn new() -> Self {
+        unsafe {
+            drop(GX_SetDrawSyncCallback(Some(on_draw_sync)));
+        }
+        FLIP_INTERVAL.store(1, Ordering::Release);
+        Self {
+            interval: 1,
+            frame_start: 0,
+            cpu_ticks: 0,
+            gpu_ticks: 0,
+            costs: [0; HISTORY_LEN],
+            next_cost: 0,
+            fits_faster: 0,
+        }
+    }
+
+    /// Starts timing a frame. Call before any of its GX commands.
+    pub fn begin_frame(&mut self) {
+        self.frame_start = unsafe { gettick() };
+    }
+
+    /// Ends the frame's CPU time and queues the token that ends its GPU time. Call after the
+    /// frame's last GX command and before `GX_DrawDone()`.
+    pub fn end_submit(&mut self) {
+        self.cpu_ticks = unsafe { gettick() }.wrapping_sub(self.frame_start);
+        unsafe {
+            GX_SetDrawSync(FRAME_END_TOKEN);
+        }
+    }
+
+    /// Records the frame's cost and updates the interval. Call after `GX_DrawDone()` returns, so
+    /// the token has been reached.
+    pub fn end_frame(&mut self) {
+        self.gpu_ticks = GPU_DONE_TICK
+            .load(Ordering::Acquire)
+            .wrapping_sub(self.frame_start);
+        let cost = self.cpu_ticks.max(self.gpu_ticks);
+        self.costs[self.next_cost] = cost;
+        self.next_cost = (self.next_cost + 1) % HISTORY_LEN;
+
+        let budget = self.interval as u32 * TICKS_PER_RETRACE;
+        let misses = self.costs.iter().filter(|&&cost| cost > budget).count();
+        if misses > MAX_MISSES && self.interval < MAX_INTERVAL {
+            // Jump straight to an interval the worst recent frame fits.
+            let worst = self.costs.iter().copied().max().unwrap();
+            self.set_interval(
+                (1..=MAX_INTERVAL)
+                    .find(|&interval| worst <= interval as u32 * TICKS_PER_RETRACE)
+                    .unwrap_or(MAX_INTERVAL),
+            );
+            return;
+        }
+
+        if self.interval > 1 {
+            let faster_budget = (self.interval - 1) as u32 * TICKS_PER_RETRACE;
+            if cost <= faster_budget / 100 * SPEED_UP_HEADROOM_PERCENT {
+                self.fits_faster += 1;
+                if self.fits_faster >= SPEED_UP_FRAMES {
+                    self.set_interval(self.interval - 1);
+                }
+            } else {
+                self.fits_faster = 0;
+            }
+        }
+    }
+
+    fn set_interval(&mut self, interval: usize) {
+        self.interval = interval;
+        self.fits_faster = 0;
+        // Judge the new interval only by frames drawn under it.
+        self.costs = [0; HISTORY_LEN];
+        FLIP_INTERVAL.store(interval, Ordering::Release);
+    }
+}
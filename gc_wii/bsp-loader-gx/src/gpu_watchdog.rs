@@ -0,0 +1,115 @@
+//! Detects GPU hangs instead of waiting on them forever.
+//!
+//! `GX_DrawDone` blocks until the pixel engine reaches the draw done token, which never happens if
+//! a malformed display list wedges the command processor. That looks exactly like a CPU hang. The
+//! watchdog here waits for the token with a deadline, and on a miss logs the CP and PE status
+//! registers over Gecko and resets the FIFO so the next frame gets a chance.
+
+use core::ptr;
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use gamecube_cpu::registers::{time_base, TIME_BASE_TICKS_PER_MS};
+use gamecube_gx::Gx;
+use gamecube_mmio::command_processor::CommandProcessor;
+use log::error;
+use ogc_sys::*;
+
+/// How long a frame's commands may take to drain before the GPU is declared hung. Real frames take
+/// a few tens of milliseconds at worst.
+const TIMEOUT_MS: u64 = 500;
+
+const CP_BASE: usize = 0xcc00_0000;
+const PE_BASE: usize = 0xcc00_1000;
+
+static DRAW_DONE: AtomicBool = AtomicBool::new(false);
+static RECOVERY_COUNT: AtomicU32 = AtomicU32::new(0);
+
+extern "C" fn on_draw_done() {
+    DRAW_DONE.store(true, Ordering::Release);
+}
+
+/// Installs the draw done callback the watchdog waits on. `GX_DrawDone` keeps working as before.
+pub fn init() {
+    unsafe {
+        drop(GX_SetDrawDoneCallback(Some(on_draw_done)));
+    }
+}
+
+/// How many hangs have been recovered from since boot.
+pub fn recovery_count() -> u32 {
+    RECOVERY_COUNT.load(Ordering::Relaxed)
+}
+
+/// Like `GX_DrawDone`, but gives up after [`TIMEOUT_MS`]. On a timeout, this logs the GPU's status,
+/// aborts whatever is left of the frame, and returns false.
+pub fn draw_done(gx: &mut Gx) -> bool {
+    DRAW_DONE.store(false, Ordering::Relaxed);
+    unsafe { GX_SetDrawDone() };
+
+    let start = time_base();
+    while !DRAW_DONE.load(Ordering::Acquire) {
+        if time_base().wrapping_sub(start) > TIMEOUT_MS * TIME_BASE_TICKS_PER_MS {
+            error!("GPU hang: no draw done token after {} ms", TIMEOUT_MS);
+            log_status();
+            recover(gx);
+            return false;
+        }
+    }
+    true
+}
+
+fn log_status() {
+    // SAFETY: Only reads the CP registers. Nothing else holds a handle to them.
+    let cp = unsafe { CommandProcessor::new_unchecked() };
+    let status = cp.read_status();
+    let control = cp.read_control();
+    error!(
+        "CP status {:#06x}: overflow={} underflow={} read_idle={} command_idle={} breakpoint={}",
+        status.as_u16(),
+        status.fifo_overflow(),
+        status.fifo_underflow(),
+        status.is_read_idle(),
+        status.is_command_idle(),
+        status.breakpoint_interrupt(),
+    );
+    error!(
+        "CP control {:#06x}: read_enable={} link_enable={}",
+        control.as_u16(),
+        control.fifo_read_enable(),
+        control.fifo_link_enable(),
+    );
+    error!(
+        "CP FIFO base={:#010x} end={:#010x} write={:#010x} read={:#010x} distance={:#010x}",
+        read_cp_u32(0x20),
+        read_cp_u32(0x24),
+        read_cp_u32(0x34),
+        read_cp_u32(0x38),
+        read_cp_u32(0x30),
+    );
+    error!(
+        "PE interrupt status={:#06x} token={:#06x}",
+        read_u16(PE_BASE + 0x0a),
+        read_u16(PE_BASE + 0x0e),
+    );
+}
+
+/// Resets the GP and empties its FIFO, dropping every command that hadn't been read yet.
+fn recover(_gx: &mut Gx) {
+    unsafe {
+        GX_AbortFrame();
+        GX_Flush();
+    }
+    RECOVERY_COUNT.fetch_add(1, Ordering::Relaxed);
+    error!("GPU FIFO reset ({} so far)", recovery_count());
+}
+
+/// Reads a CP register pair that holds a 32-bit value as low and high halves.
+fn read_cp_u32(offset: usize) -> u32 {
+    let lo = read_u16(CP_BASE + offset) as u32;
+    let hi = read_u16(CP_BASE + offset + 2) as u32;
+    hi << 16 | lo
+}
+
+fn read_u16(addr: usize) -> u16 {
+    unsafe { ptr::read_volatile(addr as *const u16) }
+}
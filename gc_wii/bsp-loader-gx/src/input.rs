@@ -0,0 +1,239 @@
+//! Controller input, mapped from physical buttons to logical actions.
+//!
+//! GameCube controllers are always read. On Wii, the first Wii Remote is read too, along with a
+//! Classic Controller if one is plugged into it. Everything is merged into one GameCube-shaped
+//! button set so that a single set of bindings covers every controller.
+
+use ogc_sys::*;
+
+/// A physical button, named after its place on a GameCube controller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u16)]
+pub enum Button {
+    Left = 0x0001,
+    Right = 0x0002,
+    Down = 0x0004,
+    Up = 0x0008,
+    Z = 0x0010,
+    R = 0x0020,
+    L = 0x0040,
+    A = 0x0100,
+    B = 0x0200,
+    X = 0x0400,
+    Y = 0x0800,
+    Start = 0x1000,
+}
+
+/// Something the player can do, independent of which button does it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Opens the map selection menu, or exits from it.
+    Menu,
+    Confirm,
+    Back,
+    MenuUp,
+    MenuDown,
+    MenuLeft,
+    MenuRight,
+    /// Moves faster while held.
+    Speed,
+    Ascend,
+    Descend,
+    InvertPitch,
+    /// Opens or closes the debug console.
+    Console,
+}
+
+const ACTION_COUNT: usize = Action::Console as usize + 1;
+
+/// Which button triggers each action. Lives in `GameState` so it survives map changes.
+#[derive(Clone, Copy, Debug)]
+pub struct Bindings {
+    buttons: [Button; ACTION_COUNT],
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        // In `Action` order.
+        Self {
+            buttons: [
+                Button::Start,
+                Button::A,
+                Button::B,
+                Button::Up,
+                Button::Down,
+                Button::Left,
+                Button::Right,
+                Button::R,
+                Button::Y,
+                Button::X,
+                Button::Z,
+                Button::L,
+            ],
+        }
+    }
+}
+
+impl Bindings {
+    pub fn button(&self, action: Action) -> Button {
+        self.buttons[action as usize]
+    }
+
+    pub fn rebind(&mut self, action: Action, button: Button) {
+        self.buttons[action as usize] = button;
+    }
+}
+
+/// One frame's worth of controller state.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Input {
+    held: u16,
+    down: u16,
+    move_stick: (f32, f32),
+    look_stick: (f32, f32),
+}
+
+impl Input {
+    /// Initializes every controller library the platform has.
+    pub fn init() {
+        unsafe {
+            PAD_Init();
+            #[cfg(feature = "wii")]
+            WPAD_Init();
+        }
+    }
+
+    /// Polls the controllers. Call once per frame.
+    pub fn scan() -> Self {
+        let mut input = Self::default();
+        unsafe {
+            PAD_ScanPads();
+            input.merge_buttons(PAD_ButtonsHeld(0), PAD_ButtonsDown(0));
+            // The analog R trigger counts as held once it's halfway in.
+            if PAD_TriggerR(0) >= 128 {
+                input.held |= Button::R as u16;
+            }
+            input.move_stick = process_stick(PAD_StickX(0), PAD_StickY(0));
+            input.look_stick = process_stick(PAD_SubStickX(0), PAD_SubStickY(0));
+        }
+        #[cfg(feature = "wii")]
+        input.scan_wii_remote();
+        input
+    }
+
+    pub fn is_held(&self, bindings: &Bindings, action: Action) -> bool {
+        self.held & bindings.button(action) as u16 != 0
+    }
+
+    /// Whether the action's button went down this frame.
+    pub fn is_pressed(&self, bindings: &Bindings, action: Action) -> bool {
+        self.down & bindings.button(action) as u16 != 0
+    }
+
+    /// Strafe and walk, each in -1.0..=1.0 with a dead zone applied.
+    pub fn move_axes(&self) -> (f32, f32) {
+        self.move_stick
+    }
+
+    /// Yaw and pitch, each in -1.0..=1.0 with a dead zone applied.
+    pub fn look_axes(&self) -> (f32, f32) {
+        self.look_stick
+    }
+
+    fn merge_buttons(&mut self, held: u16, down: u16) {
+        self.held |= held;
+        self.down |= down;
+    }
+
+    #[cfg(feature = "wii")]
+    fn scan_wii_remote(&mut self) {
+        const WII_REMOTE_BUTTONS: [(u32, Button); 11] = [
+            (WPAD_BUTTON_LEFT, Button::Left),
+            (WPAD_BUTTON_RIGHT, Button::Right),
+            (WPAD_BUTTON_DOWN, Button::Down),
+            (WPAD_BUTTON_UP, Button::Up),
+            (WPAD_BUTTON_A, Button::A),
+            (WPAD_BUTTON_B, Button::B),
+            (WPAD_BUTTON_1, Button::X),
+            (WPAD_BUTTON_2, Button::Y),
+            (WPAD_BUTTON_MINUS, Button::Z),
+            (WPAD_BUTTON_PLUS, Button::Start),
+            (WPAD_BUTTON_HOME, Button::Start),
+        ];
+        const CLASSIC_BUTTONS: [(u32, Button); 12] = [
+            (WPAD_CLASSIC_BUTTON_LEFT, Button::Left),
+            (WPAD_CLASSIC_BUTTON_RIGHT, Button::Right),
+            (WPAD_CLASSIC_BUTTON_DOWN, Button::Down),
+            (WPAD_CLASSIC_BUTTON_UP, Button::Up),
+            (WPAD_CLASSIC_BUTTON_ZR, Button::Z),
+            (WPAD_CLASSIC_BUTTON_FULL_R, Button::R),
+            (WPAD_CLASSIC_BUTTON_FULL_L, Button::L),
+            (WPAD_CLASSIC_BUTTON_A, Button::A),
+            (WPAD_CLASSIC_BUTTON_B, Button::B),
+            (WPAD_CLASSIC_BUTTON_X, Button::X),
+            (WPAD_CLASSIC_BUTTON_Y, Button::Y),
+            (WPAD_CLASSIC_BUTTON_PLUS, Button::Start),
+        ];
+
+        fn translate(bits: u32, table: &[(u32, Button)]) -> u16 {
+            table
+                .iter()
+                .filter(|&&(mask, _)| bits & mask != 0)
+                .fold(0, |acc, &(_, button)| acc | button as u16)
+        }
+
+        unsafe {
+            WPAD_ScanPads();
+            let mut device_type = 0;
+            if WPAD_Probe(0, &mut device_type) != WPAD_ERR_NONE as i32 {
+                return;
+            }
+            let held = WPAD_ButtonsHeld(0);
+            let down = WPAD_ButtonsDown(0);
+            self.merge_buttons(
+                translate(held, &WII_REMOTE_BUTTONS),
+                translate(down, &WII_REMOTE_BUTTONS),
+            );
+
+            if device_type == WPAD_EXP_CLASSIC {
+                self.merge_buttons(
+                    translate(held, &CLASSIC_BUTTONS),
+                    translate(down, &CLASSIC_BUTTONS),
+                );
+                let classic = &(*WPAD_Data(0)).exp.__bindgen_anon_1.classic;
+                if self.move_stick == (0.0, 0.0) {
+                    self.move_stick = process_classic_stick(&classic.ljs);
+                }
+                if self.look_stick == (0.0, 0.0) {
+                    self.look_stick = process_classic_stick(&classic.rjs);
+                }
+            }
+        }
+    }
+}
+
+/// Applies a dead zone and a ramp to a raw GameCube stick reading.
+fn process_stick(x: i8, y: i8) -> (f32, f32) {
+    shape_stick(x as f32 / 127.0, y as f32 / 127.0)
+}
+
+/// Converts a Classic Controller stick's polar reading and shapes it like a GameCube stick.
+#[cfg(feature = "wii")]
+fn process_classic_stick(stick: &joystick_t) -> (f32, f32) {
+    let angle = stick.ang.to_radians();
+    shape_stick(stick.mag * libm::sinf(angle), stick.mag * libm::cosf(angle))
+}
+
+fn shape_stick(dx: f32, dy: f32) -> (f32, f32) {
+    let d = libm::sqrtf(dx * dx + dy * dy);
+    if d < 0.2 {
+        (0.0, 0.0)
+    } else if d < 0.9 {
+        let goal = (d - 0.1) * (1.0 / 0.8);
+        let scale = goal / d;
+        (dx * scale, dy * scale)
+    } else {
+        let scale = 1.0 / d;
+        (dx * scale, dy * scale)
+    }
+}
@@ -3,11 +3,14 @@
 use core::mem::size_of;
 use core::ops::{ControlFlow, Range};
 
-use aligned::{Aligned, A32};
+use crate::dvd_file::DvdFile;
+
+use aligned::{Aligned, A2, A32};
 use alloc::format;
 use alloc::vec::Vec;
 use bytemuck::{from_bytes, Pod, Zeroable};
 use gamecube_dvd_driver::DvdDriver;
+use no_std_io::SeekExt;
 use ogc_sys::GlobalAlign32;
 
 pub struct DiscReader {
@@ -54,31 +57,22 @@ impl DiscReader {
         &mut self,
         mut f: impl FnMut(u16, &BePathTableEntry) -> ControlFlow<R>,
     ) -> Option<R> {
-        let mut offset = self.path_table_offset;
-        let mut size_remaining = self.path_table_size;
-        let mut buf: Aligned<A32, _> = Aligned([0; 1024]);
+        // Entries are packed back to back at 2-byte alignment, so read through a file that takes
+        // care of the drive's alignment rules.
+        let path_table = DvdFile::new(&mut self.dvd, self.path_table_offset, self.path_table_size);
+        let mut offset = 0;
+        let mut buf: Aligned<A2, _> = Aligned([0; BePathTableEntry::MAX_SIZE]);
         let mut index = 0;
-        while size_remaining > 0 {
-            let read_offset = offset & !3;
-            let struct_offset = offset - read_offset;
-            let size = (buf
-                .len()
-                .min(BePathTableEntry::MAX_SIZE)
-                .min(size_remaining)
-                + 31)
-                & !31;
-
-            self.dvd.read(read_offset, &mut (*buf)[..size]).unwrap();
-            let entry: &BePathTableEntry =
-                from_bytes(&(*buf)[struct_offset..struct_offset + size_of::<BePathTableEntry>()]);
+        while offset < self.path_table_size {
+            let size = BePathTableEntry::MAX_SIZE.min(self.path_table_size - offset);
+            path_table.read_at(offset, &mut (*buf)[..size]).unwrap();
+            let entry: &BePathTableEntry = from_bytes(&(*buf)[..size_of::<BePathTableEntry>()]);
             match f(index, entry) {
                 ControlFlow::Continue(()) => (),
                 ControlFlow::Break(result) => return Some(result),
             }
 
-            let size = entry.size();
-            offset += size;
-            size_remaining -= size;
+            offset += entry.size();
             index += 1;
         }
 
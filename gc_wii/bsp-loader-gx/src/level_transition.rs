@@ -0,0 +1,95 @@
+use core::ops::Deref;
+
+use alloc::string::{String, ToString};
+use inception_render_common::map_data::MapData;
+use ogc_sys::guVector;
+
+/// A level transition that has been triggered but whose destination map hasn't been loaded yet.
+pub struct PendingTransition {
+    pub map: String,
+    landmark: String,
+    /// The camera's position relative to the landmark in the source map.
+    offset: [f32; 3],
+    yaw: f32,
+    pitch: f32,
+}
+
+impl PendingTransition {
+    /// A transition to a map with no shared landmark, keeping the camera's orientation.
+    pub fn to_map(map: String, yaw: f32, pitch: f32) -> Self {
+        Self {
+            map,
+            landmark: String::new(),
+            offset: [0.0; 3],
+            yaw,
+            pitch,
+        }
+    }
+
+    /// Places the camera in the destination map at the same offset from the shared landmark. If
+    /// the destination map lacks the landmark, the camera is left where it is.
+    pub fn place_camera<Data: Deref<Target = [u8]>>(
+        &self,
+        map_data: &MapData<Data>,
+        pos: &mut guVector,
+        yaw: &mut f32,
+        pitch: &mut f32,
+    ) {
+        if let Some(landmark) = map_data.find_landmark(&self.landmark) {
+            pos.x = landmark.origin[0] + self.offset[0];
+            pos.y = landmark.origin[1] + self.offset[1];
+            pos.z = landmark.origin[2] + self.offset[2];
+        }
+        *yaw = self.yaw;
+        *pitch = self.pitch;
+    }
+}
+
+/// Watches for the camera entering a level transition volume.
+///
+/// A map's spawn point is often inside the volume leading back to the previous map, so volumes
+/// only fire after the camera has been outside all of them.
+pub struct LevelTransitionDetector {
+    armed: bool,
+}
+
+impl LevelTransitionDetector {
+    pub fn new() -> Self {
+        Self { armed: false }
+    }
+
+    pub fn update<Data: Deref<Target = [u8]>>(
+        &mut self,
+        map_data: &MapData<Data>,
+        pos: &guVector,
+        yaw: f32,
+        pitch: f32,
+    ) -> Option<PendingTransition> {
+        let pos = [pos.x, pos.y, pos.z];
+        let Some(transition) = map_data
+            .level_transition_table()
+            .iter()
+            .find(|transition| transition.contains(&pos))
+        else {
+            self.armed = true;
+            return None;
+        };
+        if !self.armed {
+            return None;
+        }
+
+        let map = map_data.name(&transition.map_name)?;
+        let landmark = map_data.name(&transition.landmark_name)?;
+        let origin = map_data
+            .find_landmark(landmark)
+            .map_or([0.0; 3], |landmark| landmark.origin);
+        self.armed = false;
+        Some(PendingTransition {
+            map: map.to_string(),
+            landmark: landmark.to_string(),
+            offset: [0, 1, 2].map(|i| pos[i] - origin[i]),
+            yaw,
+            pitch,
+        })
+    }
+}
@@ -1,8 +1,12 @@
-use core::ops::Deref;
+use core::ops::DerefMut;
 
 use alloc::string::String;
 use alloc::vec::Vec;
-use inception_render_common::map_data::MapData;
+use gamecube_alloc::DmaBuffer;
+use gamecube_dvd_driver::DvdError;
+use inception_render_common::map_data::{MapData, SharedTextureTableEntry};
+use no_std_ftp::Timestamp;
+use no_std_io::NetError;
 
 pub mod dvd_gcm_loader;
 pub mod dvd_iso_loader;
@@ -10,9 +14,50 @@ pub mod dvd_iso_loader;
 pub mod embedded_loader;
 pub mod ftp_loader;
 
+/// How far a load has gotten.
+pub enum LoadStatus<T> {
+    /// `done` of `total` bytes have arrived.
+    Loading {
+        done: usize,
+        total: usize,
+    },
+    Done(T),
+}
+
+/// Why a shared texture couldn't be loaded.
+#[derive(Debug)]
+pub enum LoadError {
+    /// The texture's file isn't on the disc or server.
+    NotFound { path: String },
+    /// The texture's file isn't the size the map's shared texture table gives.
+    WrongSize {
+        path: String,
+        size: usize,
+        expected: usize,
+    },
+    /// The texture's file doesn't match the checksum the map's shared texture table gives.
+    Corrupt { path: String },
+    /// The disc drive failed a read.
+    Dvd(DvdError),
+    /// The connection kept dropping, even after resuming.
+    Net(NetError),
+}
+
+/// A map as shown in the selector, with whatever the loader could find out about its file.
+pub struct MapListing {
+    pub name: String,
+    pub size: Option<usize>,
+    pub modified: Option<Timestamp>,
+}
+
 pub trait Loader: Sized {
     type Params<'a>;
-    type Data: Deref<Target = [u8]>;
+    /// Mutable so that the map can be relocated in place.
+    type Data: DerefMut<Target = [u8]>;
+    /// A map load in progress. Dropping it cancels the load.
+    type MapLoad;
+    /// A shared texture load in progress. Dropping it cancels the load.
+    type TextureLoad;
 
     /// This might do a lot of I/O.
     fn new(params: Self::Params<'_>) -> Self;
@@ -20,6 +65,43 @@ pub trait Loader: Sized {
     /// This might do a lot of I/O.
     fn maps(&mut self) -> Vec<String>;
 
-    /// This might do a lot of I/O.
-    fn load_map(&mut self, map: &str) -> MapData<Self::Data>;
+    /// Like [`Loader::maps`], with each map's file size and modification time where the loader
+    /// knows them. This might do a lot of I/O.
+    fn map_listings(&mut self) -> Vec<MapListing> {
+        self.maps()
+            .into_iter()
+            .map(|name| MapListing {
+                name,
+                size: None,
+                modified: None,
+            })
+            .collect()
+    }
+
+    /// Begins loading a map. This does only as much I/O as it takes to find the map.
+    fn start_load_map(&mut self, map: &str) -> Self::MapLoad;
+
+    /// Advances a map load by a bounded amount of I/O, so that the caller can keep the screen and
+    /// input alive in between. Call until it returns [`LoadStatus::Done`].
+    fn poll_load_map(&mut self, load: &mut Self::MapLoad) -> LoadStatus<MapData<Self::Data>>;
+
+    /// Returns true if the underlying media was swapped since the last call, invalidating the map
+    /// list. Must not block.
+    fn media_changed(&mut self) -> bool {
+        false
+    }
+
+    /// Begins loading a shared texture's image. This does only as much I/O as it takes to find it.
+    fn start_load_shared_texture(
+        &mut self,
+        entry: &SharedTextureTableEntry,
+    ) -> Result<Self::TextureLoad, LoadError>;
+
+    /// Advances a shared texture load by a bounded amount of I/O, like [`Loader::poll_load_map`].
+    /// The image is `entry.size` bytes.
+    fn poll_load_shared_texture(
+        &mut self,
+        load: &mut Self::TextureLoad,
+    ) -> Result<LoadStatus<DmaBuffer<u8>>, LoadError>;
 }
+
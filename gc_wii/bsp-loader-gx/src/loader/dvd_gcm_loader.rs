@@ -1,24 +1,53 @@
 use core::mem::size_of;
 
-use crate::loader::Loader;
+use crate::loader::{LoadError, LoadStatus, Loader};
 
-use aligned::{Aligned, A32};
 use alloc::format;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use bytemuck::{from_bytes, Pod, Zeroable};
-use gamecube_dvd_driver::DvdDriver;
+use gamecube_alloc::DmaBuffer;
+use gamecube_dvd_driver::{DvdDriver, DvdError, DvdEvent};
 use gamecube_mmio::processor_interface::ProcessorInterface;
-use inception_render_common::map_data::MapData;
-use ogc_sys::GlobalAlign32;
+use inception_render_common::map_data::{shared_texture_path, MapData, SharedTextureTableEntry};
+use ogc_sys::VIDEO_WaitVSync;
+
+/// How much of a file each poll reads. About a tenth of a second at the drive's transfer rate.
+const LOAD_CHUNK_SIZE: usize = 256 * 1024;
 
 pub struct DvdGcmLoader {
     dvd: DvdDriver,
-    table_data: Vec<u8, GlobalAlign32>,
+    pi: ProcessorInterface,
+    table_data: DmaBuffer<u8>,
     string_table_start: usize,
 }
 
 impl DvdGcmLoader {
+    /// Reads the file system table if the Inception disc is in the drive. Returns false otherwise.
+    fn mount(&mut self) -> bool {
+        let header = match self.dvd.read_disc_id() {
+            Ok(header)
+                if header.game_code == *b"GGME"
+                    && header.maker_code == *b"MV"
+                    && header.disc_number == 0
+                    && header.version == 0 =>
+            {
+                header
+            }
+            _ => return false,
+        };
+        let table_data = match self.dvd.read_fst(&header) {
+            Ok(table_data) => table_data,
+            Err(_) => return false,
+        };
+
+        let root_entry: &FileTableEntry = from_bytes(&table_data[..size_of::<FileTableEntry>()]);
+        self.string_table_start =
+            size_of::<FileTableEntry>() * root_entry.file_length_or_next_index;
+        self.table_data = table_data;
+        true
+    }
+
     fn string(&self, offset: usize) -> &str {
         let start = self.string_table_start + offset;
         let end = start
@@ -30,8 +59,17 @@ impl DvdGcmLoader {
         core::str::from_utf8(&self.table_data[start..end]).unwrap()
     }
 
-    fn read_file(&mut self, path: &str) -> Vec<u8, GlobalAlign32> {
-        let orig_path = path;
+    fn read_file(&mut self, path: &str) -> DmaBuffer<u8> {
+        let (file_offset, file_size) = self
+            .find_file(path)
+            .unwrap_or_else(|| panic!("File not found: {:?}", path));
+        let mut data = DmaBuffer::zeroed(file_size);
+        self.dvd.read_into(file_offset, &mut data).unwrap();
+        data
+    }
+
+    /// Returns the disc offset and size of a file, if it exists.
+    fn find_file(&self, path: &str) -> Option<(usize, usize)> {
         let mut path = path;
 
         let mut dir_offset = size_of::<FileTableEntry>();
@@ -43,15 +81,8 @@ impl DvdGcmLoader {
 
             if entry.is_file() {
                 if name.eq_ignore_ascii_case(path) {
-                    // Found the file. Read it.
-                    let file_offset = entry.data_or_parent_index;
-                    let file_size = entry.file_length_or_next_index;
-                    let mut data = Vec::with_capacity_in((file_size + 31) & !31, GlobalAlign32);
-                    self.dvd
-                        .read_maybe_uninit(file_offset, data.spare_capacity_mut())
-                        .unwrap();
-                    unsafe { data.set_len(file_size) }
-                    return data;
+                    // Found the file.
+                    return Some((entry.data_or_parent_index, entry.file_length_or_next_index));
                 } else {
                     // Fall through and try the next entry.
 
@@ -78,54 +109,40 @@ impl DvdGcmLoader {
             }
         }
 
-        panic!("File not found: {:?}", orig_path);
+        None
     }
 }
 
 impl Loader for DvdGcmLoader {
     type Params<'a> = (DvdDriver, ProcessorInterface);
-    type Data = Vec<u8, GlobalAlign32>;
+    type Data = DmaBuffer<u8>;
+    type MapLoad = DvdFileLoad;
+    type TextureLoad = DvdFileLoad;
 
     fn new((mut dvd, pi): Self::Params<'_>) -> Self {
-        // Check for the expected disc.
-        loop {
-            unsafe {
-                libc::printf(b"Resetting the disc drive...\n\0".as_ptr());
-                dvd.reset(pi);
-                match dvd.read_disc_id() {
-                    Ok(disc_id) => {
-                        if &disc_id[..8] == b"GGMEMV\x00\x00" {
-                            break;
-                        }
-                    }
-                    Err(_) => (),
-                }
-
-                libc::printf(b"Unrecognized disc. Open the disc cover.\n\0".as_ptr());
-                dvd.wait_for_cover(true);
+        dvd.enable_cover_events();
+        let mut loader = Self {
+            dvd,
+            pi,
+            table_data: DmaBuffer::default(),
+            string_table_start: 0,
+        };
 
-                libc::printf(b"Insert the Inception disc and close the cover.\n\0".as_ptr());
-                dvd.wait_for_cover(false);
+        unsafe {
+            libc::printf(b"Resetting the disc drive...\n\0".as_ptr());
+            loader.dvd.reset(&loader.pi);
+            if !loader.mount() {
+                libc::printf(
+                    b"Unrecognized disc. Open the cover and insert the Inception disc.\n\0"
+                        .as_ptr(),
+                );
+                while !loader.media_changed() {
+                    VIDEO_WaitVSync();
+                }
             }
         }
 
-        let mut metadata: Aligned<A32, _> = Aligned(DiscHeader0x420::zeroed());
-        dvd.read(0x420, bytemuck::bytes_of_mut(&mut *metadata))
-            .unwrap();
-
-        let mut table_data = Vec::with_capacity_in((metadata.fst_size + 31) & !31, GlobalAlign32);
-        dvd.read_maybe_uninit(metadata.fst_offset, table_data.spare_capacity_mut())
-            .unwrap();
-        unsafe { table_data.set_len(metadata.fst_size) }
-
-        let root_entry: &FileTableEntry = from_bytes(&table_data[..size_of::<FileTableEntry>()]);
-        let string_table_start = size_of::<FileTableEntry>() * root_entry.file_length_or_next_index;
-
-        Self {
-            dvd,
-            table_data,
-            string_table_start,
-        }
+        loader
     }
 
     fn maps(&mut self) -> Vec<String> {
@@ -138,19 +155,116 @@ impl Loader for DvdGcmLoader {
         maps
     }
 
-    fn load_map(&mut self, map: &str) -> MapData<Self::Data> {
-        let data = self.read_file(&format!("maps/{}.dat", map));
-        unsafe { MapData::new(data) }
+    fn start_load_map(&mut self, map: &str) -> DvdFileLoad {
+        let path = format!("maps/{}.dat", map);
+        let (offset, size) = self
+            .find_file(&path)
+            .unwrap_or_else(|| panic!("File not found: {:?}", path));
+        DvdFileLoad::new(offset, size)
+    }
+
+    fn poll_load_map(&mut self, load: &mut DvdFileLoad) -> LoadStatus<MapData<Self::Data>> {
+        match load.poll(&mut self.dvd).unwrap() {
+            LoadStatus::Loading { done, total } => LoadStatus::Loading { done, total },
+            LoadStatus::Done(data) => unsafe { LoadStatus::Done(MapData::new(data)) },
+        }
+    }
+
+    fn start_load_shared_texture(
+        &mut self,
+        entry: &SharedTextureTableEntry,
+    ) -> Result<DvdFileLoad, LoadError> {
+        let path = shared_texture_path(&entry.hash);
+        let (offset, size) = match self.find_file(&path) {
+            Some(file) => file,
+            None => return Err(LoadError::NotFound { path }),
+        };
+        let expected = entry.size as usize;
+        if size != expected {
+            return Err(LoadError::WrongSize {
+                path,
+                size,
+                expected,
+            });
+        }
+        Ok(DvdFileLoad::new(offset, size))
+    }
+
+    fn poll_load_shared_texture(
+        &mut self,
+        load: &mut DvdFileLoad,
+    ) -> Result<LoadStatus<DmaBuffer<u8>>, LoadError> {
+        load.poll(&mut self.dvd).map_err(LoadError::Dvd)
+    }
+
+    fn media_changed(&mut self) -> bool {
+        let mut changed = false;
+        while let Some(event) = self.dvd.poll_event() {
+            unsafe {
+                match event {
+                    DvdEvent::CoverOpened => {
+                        libc::printf(b"Disc cover opened.\n\0".as_ptr());
+                    }
+                    DvdEvent::CoverClosed => {
+                        libc::printf(
+                            b"Disc cover closed. Resetting the disc drive...\n\0".as_ptr(),
+                        );
+                        self.dvd.start_reset(&self.pi);
+                    }
+                    DvdEvent::ResetComplete => {
+                        if self.mount() {
+                            changed = true;
+                        } else {
+                            libc::printf(
+                                b"Unrecognized disc. Open the cover and insert the Inception disc.\n\0"
+                                    .as_ptr(),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        changed
     }
 }
 
-#[derive(Clone, Copy, Pod, Zeroable)]
-#[repr(C)]
-struct DiscHeader0x420 {
-    _unused1: usize,
-    fst_offset: usize,
-    fst_size: usize,
-    _unused2: [usize; 5],
+/// A file being read a chunk per poll.
+pub struct DvdFileLoad {
+    offset: usize,
+    size: usize,
+    /// Filled directly, padding and all.
+    data: DmaBuffer<u8>,
+    /// Bytes read so far, including any padding.
+    read: usize,
+}
+
+impl DvdFileLoad {
+    fn new(offset: usize, size: usize) -> Self {
+        Self {
+            offset,
+            size,
+            data: DmaBuffer::zeroed(size),
+            read: 0,
+        }
+    }
+
+    fn poll(&mut self, dvd: &mut DvdDriver) -> Result<LoadStatus<DmaBuffer<u8>>, DvdError> {
+        // Reads stay whole multiples of 32 bytes by running into the padding at the end.
+        let buf = self.data.dma_bytes_mut();
+        if self.read < buf.len() {
+            let end = (self.read + LOAD_CHUNK_SIZE).min(buf.len());
+            dvd.read(self.offset + self.read, &mut buf[self.read..end])?;
+            self.read = end;
+        }
+        if self.read < self.size {
+            return Ok(LoadStatus::Loading {
+                done: self.read,
+                total: self.size,
+            });
+        }
+
+        Ok(LoadStatus::Done(core::mem::take(&mut self.data)))
+    }
 }
 
 #[derive(Clone, Copy, Pod, Zeroable)]
@@ -1,11 +1,12 @@
 use crate::iso9660::DiscReader;
-use crate::loader::Loader;
+use crate::loader::{LoadError, LoadStatus, Loader};
 
 use alloc::format;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
+use gamecube_alloc::DmaBuffer;
 use gamecube_dvd_driver::DvdDriver;
-use inception_render_common::map_data::MapData;
+use inception_render_common::map_data::{shared_texture_path, MapData, SharedTextureTableEntry};
 use ogc_sys::GlobalAlign32;
 
 pub struct DvdIsoLoader {
@@ -15,6 +16,11 @@ pub struct DvdIsoLoader {
 impl Loader for DvdIsoLoader {
     type Params<'a> = DvdDriver;
     type Data = Vec<u8, GlobalAlign32>;
+    /// The map's path. The ISO 9660 reader can only read whole files, so the load happens in a
+    /// single poll.
+    type MapLoad = String;
+    /// Likewise read in a single poll.
+    type TextureLoad = SharedTextureTableEntry;
 
     fn new(dvd: Self::Params<'_>) -> Self {
         Self {
@@ -32,8 +38,38 @@ impl Loader for DvdIsoLoader {
         maps
     }
 
-    fn load_map(&mut self, map: &str) -> MapData<Self::Data> {
-        let data = self.disc_reader.read_file(&format!("maps/{}", map));
-        unsafe { MapData::new(data) }
+    fn start_load_map(&mut self, map: &str) -> String {
+        format!("maps/{}", map)
+    }
+
+    fn poll_load_map(&mut self, path: &mut String) -> LoadStatus<MapData<Self::Data>> {
+        let data = self.disc_reader.read_file(path);
+        LoadStatus::Done(unsafe { MapData::new(data) })
+    }
+
+    fn start_load_shared_texture(
+        &mut self,
+        entry: &SharedTextureTableEntry,
+    ) -> Result<SharedTextureTableEntry, LoadError> {
+        Ok(*entry)
+    }
+
+    fn poll_load_shared_texture(
+        &mut self,
+        entry: &mut SharedTextureTableEntry,
+    ) -> Result<LoadStatus<DmaBuffer<u8>>, LoadError> {
+        let path = shared_texture_path(&entry.hash);
+        let data = self.disc_reader.read_file(&path);
+        let expected = entry.size as usize;
+        if data.len() != expected {
+            return Err(LoadError::WrongSize {
+                path,
+                size: data.len(),
+                expected,
+            });
+        }
+        let mut buf = DmaBuffer::zeroed(expected);
+        buf.copy_from_slice(&data);
+        Ok(LoadStatus::Done(buf))
     }
 }
@@ -1,9 +1,13 @@
+use core::convert::Infallible;
+
 use alloc::string::{String, ToString};
 use alloc::vec;
 use alloc::vec::Vec;
-use inception_render_common::map_data::MapData;
+use gamecube_alloc::DmaBuffer;
+use inception_render_common::map_data::{shared_texture_path, MapData, SharedTextureTableEntry};
+use ogc_sys::GlobalAlign32;
 
-use crate::loader::Loader;
+use crate::loader::{LoadError, LoadStatus, Loader};
 
 #[repr(align(32))]
 struct Align32Bytes;
@@ -14,7 +18,10 @@ pub struct EmbeddedLoader;
 
 impl Loader for EmbeddedLoader {
     type Params<'a> = ();
-    type Data = &'static [u8];
+    type Data = Vec<u8, GlobalAlign32>;
+    type MapLoad = ();
+    /// The embedded map carries all of its textures.
+    type TextureLoad = Infallible;
 
     fn new((): Self::Params<'_>) -> Self {
         Self
@@ -24,7 +31,27 @@ impl Loader for EmbeddedLoader {
         vec!["embedded".to_string()]
     }
 
-    fn load_map(&mut self, _map: &str) -> MapData<Self::Data> {
-        unsafe { MapData::new(MAP_DATA) }
+    fn start_load_map(&mut self, _map: &str) {}
+
+    fn poll_load_map(&mut self, (): &mut ()) -> LoadStatus<MapData<Self::Data>> {
+        // Copy out of the read-only image so the map can be relocated.
+        let mut data = Vec::with_capacity_in(MAP_DATA.len(), GlobalAlign32);
+        data.extend_from_slice(MAP_DATA);
+        LoadStatus::Done(unsafe { MapData::new(data) })
+    }
+    fn start_load_shared_texture(
+        &mut self,
+        entry: &SharedTextureTableEntry,
+    ) -> Result<Infallible, LoadError> {
+        Err(LoadError::NotFound {
+            path: shared_texture_path(&entry.hash),
+        })
+    }
+
+    fn poll_load_shared_texture(
+        &mut self,
+        load: &mut Infallible,
+    ) -> Result<LoadStatus<DmaBuffer<u8>>, LoadError> {
+        match *load {}
     }
 }
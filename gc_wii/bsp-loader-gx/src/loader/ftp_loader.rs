@@ -1,17 +1,30 @@
 use core::alloc::Allocator;
+use core::cell::{Cell, RefCell};
+use core::ops::DerefMut;
 
-use crate::loader::Loader;
+use crate::loader::{LoadError, LoadStatus, Loader, MapListing};
 use crate::net::{self, SocketAddr, TcpStream};
 
 use alloc::alloc::Global;
 use alloc::format;
-use alloc::string::String;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
-use inception_render_common::map_data::MapData;
-use no_std_ftp::{FtpClient, FtpResponse};
-use no_std_io::{NetError, ReadExt};
+use gamecube_alloc::DmaBuffer;
+use inception_render_common::map_data::{shared_texture_path, MapData, SharedTextureTableEntry};
+use no_std_ftp::{crc32, FtpClient, FtpResponse};
+use no_std_io::{NetError, Read, Seek, WriteExt};
 use ogc_sys::GlobalAlign32;
 
+/// The FTP server that maps are fetched from. Crash reports are uploaded here too.
+pub const SERVER_ADDR: [u8; 4] = [10, 0, 1, 104];
+pub const SERVER_PORT: u16 = 21;
+
+/// How many times an interrupted download is resumed before giving up.
+const MAX_RESUMES: usize = 8;
+
+/// How much of a file each poll waits for.
+const LOAD_CHUNK_SIZE: usize = 64 * 1024;
+
 pub struct FtpLoader {
     addr: SocketAddr,
 }
@@ -19,6 +32,8 @@ pub struct FtpLoader {
 impl Loader for FtpLoader {
     type Params<'a> = SocketAddr;
     type Data = Vec<u8, GlobalAlign32>;
+    type MapLoad = FtpMapLoad;
+    type TextureLoad = FtpTransfer<DmaBuffer<u8>>;
 
     fn new(addr: Self::Params<'_>) -> Self {
         unsafe {
@@ -40,9 +55,279 @@ impl Loader for FtpLoader {
         maps
     }
 
-    fn load_map(&mut self, map: &str) -> MapData<Self::Data> {
-        let data = ftp_get_in(&self.addr, &format!("maps/{}.dat", map), GlobalAlign32).unwrap();
-        unsafe { MapData::new(data) }
+    fn map_listings(&mut self) -> Vec<MapListing> {
+        let maps = self.maps();
+        // The details are only for show, so a server that can't list the directory still gets a
+        // working map selector.
+        let listing = match ftp_list(&self.addr, "maps") {
+            Ok(listing) => listing,
+            Err(e) => {
+                unsafe {
+                    let buf = format!("Couldn't list maps: {:?}\n\0", e);
+                    libc::printf(b"%s\0".as_ptr(), buf.as_ptr());
+                }
+                Vec::new()
+            }
+        };
+        maps.into_iter()
+            .map(|name| {
+                let file_name = format!("{}.dat", name);
+                let entry = no_std_ftp::list(&listing).find(|entry| entry.name == file_name);
+                MapListing {
+                    size: entry.and_then(|entry| entry.size).map(|size| size as usize),
+                    modified: entry.and_then(|entry| entry.modified),
+                    name,
+                }
+            })
+            .collect()
+    }
+
+    fn start_load_map(&mut self, map: &str) -> FtpMapLoad {
+        let manifest = ftp_get(&self.addr, &format!("maps/{}.manifest", map)).unwrap();
+        let expected = ManifestEntry::find(&manifest, "*").expect("manifest lacks a file entry");
+
+        let mut data = Vec::with_capacity_in(expected.size, GlobalAlign32);
+        data.resize(expected.size, 0);
+        FtpMapLoad {
+            transfer: FtpTransfer::new(
+                FtpFile::new(self.addr, format!("maps/{}.dat", map), expected.size),
+                data,
+            ),
+            expected_crc32: expected.crc32,
+        }
+    }
+
+    fn poll_load_map(&mut self, load: &mut FtpMapLoad) -> LoadStatus<MapData<Self::Data>> {
+        let transfer = &mut load.transfer;
+        if !transfer.is_done() {
+            if let Err(e) = transfer.poll() {
+                panic!("Transfer of {} failed: {:?}", transfer.file.path, e);
+            }
+            return LoadStatus::Loading {
+                done: transfer.received,
+                total: transfer.data.len(),
+            };
+        }
+
+        let data = core::mem::replace(&mut transfer.data, Vec::new_in(GlobalAlign32));
+        let crc = crc32(&data);
+        if crc != load.expected_crc32 {
+            panic!(
+                "{} has CRC-32 {:08x}, but the manifest says {:08x}",
+                transfer.file.path, crc, load.expected_crc32,
+            );
+        }
+        LoadStatus::Done(unsafe { MapData::new(data) })
+    }
+
+    fn start_load_shared_texture(
+        &mut self,
+        entry: &SharedTextureTableEntry,
+    ) -> Result<FtpTransfer<DmaBuffer<u8>>, LoadError> {
+        let len = entry.size as usize;
+        let file = FtpFile::new(self.addr, shared_texture_path(&entry.hash), len);
+        Ok(FtpTransfer::new(file, DmaBuffer::zeroed(len)))
+    }
+
+    fn poll_load_shared_texture(
+        &mut self,
+        transfer: &mut FtpTransfer<DmaBuffer<u8>>,
+    ) -> Result<LoadStatus<DmaBuffer<u8>>, LoadError> {
+        if !transfer.is_done() {
+            transfer.poll().map_err(LoadError::Net)?;
+            return Ok(LoadStatus::Loading {
+                done: transfer.received,
+                total: transfer.data.len(),
+            });
+        }
+        Ok(LoadStatus::Done(core::mem::take(&mut transfer.data)))
+    }
+}
+
+/// A map download, sized from the map's manifest.
+pub struct FtpMapLoad {
+    transfer: FtpTransfer<Vec<u8, GlobalAlign32>>,
+    expected_crc32: u32,
+}
+
+/// A download into `data`, which is as long as the file.
+pub struct FtpTransfer<Data> {
+    file: FtpFile,
+    data: Data,
+    received: usize,
+    resumes: usize,
+}
+
+impl<Data: DerefMut<Target = [u8]>> FtpTransfer<Data> {
+    fn new(file: FtpFile, data: Data) -> Self {
+        Self {
+            file,
+            data,
+            received: 0,
+            resumes: 0,
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.received == self.data.len()
+    }
+
+    /// Receives up to a chunk of the file. If the connection drops, the next poll picks up where
+    /// this one left off, up to [`MAX_RESUMES`] times.
+    fn poll(&mut self) -> Result<(), NetError> {
+        if let Err(e) = self.receive_chunk() {
+            if self.resumes == MAX_RESUMES {
+                return Err(e);
+            }
+            self.resumes += 1;
+            unsafe {
+                let buf = format!(
+                    "Transfer of {} interrupted at {}/{} bytes, resuming\n\0",
+                    self.file.path,
+                    self.received,
+                    self.data.len(),
+                );
+                libc::printf(b"%s\0".as_ptr(), buf.as_ptr());
+            }
+        }
+        Ok(())
+    }
+
+    /// Receives up to a chunk of the file, connecting first if necessary.
+    fn receive_chunk(&mut self) -> Result<(), NetError> {
+        let end = (self.received + LOAD_CHUNK_SIZE).min(self.data.len());
+        while self.received < end {
+            match self.file.read(&mut self.data[self.received..end])? {
+                0 => return Err(NetError::Disconnected),
+                n => self.received += n,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A file on the FTP server, read over a data connection that is opened on demand.
+///
+/// Seeking drops the connections, and the next read retrieves the file again with `REST` to start
+/// at the new position. A dropped connection is handled the same way, so a failed read can simply
+/// be retried.
+pub struct FtpFile {
+    addr: SocketAddr,
+    path: String,
+    len: usize,
+    pos: Cell<usize>,
+    /// A logged-in control connection that hasn't started a transfer yet.
+    idle_client: RefCell<Option<FtpClient<TcpStream>>>,
+    /// The control and data connections, once a transfer is underway.
+    transfer: RefCell<Option<(FtpClient<TcpStream>, TcpStream)>>,
+}
+
+impl FtpFile {
+    /// Refers to a file whose size is already known. This does no I/O.
+    fn new(addr: SocketAddr, path: String, len: usize) -> Self {
+        Self {
+            addr,
+            path,
+            len,
+            pos: Cell::new(0),
+            idle_client: RefCell::new(None),
+            transfer: RefCell::new(None),
+        }
+    }
+
+    /// Logs in and asks the server for the file's size.
+    fn open(addr: &SocketAddr, path: &str) -> Result<Self, NetError> {
+        let mut client = ftp_login(addr)?;
+
+        // NOTE: This makes no attempt to encode the path correctly. Interesting characters will
+        // cause this to fail.
+        let command = format!("SIZE {}\r\n", path);
+        let len = match client.send(command.as_bytes())? {
+            FtpResponse::FileSize { size } => size,
+            resp => panic!("Unexpected response to SIZE: {:?}", resp),
+        };
+
+        let file = Self::new(*addr, path.to_string(), len);
+        *file.idle_client.borrow_mut() = Some(client);
+        Ok(file)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn position(&self) -> usize {
+        self.pos.get()
+    }
+}
+
+impl Read for FtpFile {
+    fn read(&self, buf: &mut [u8]) -> Result<usize, NetError> {
+        let len = buf.len().min(self.len.saturating_sub(self.pos.get()));
+        if len == 0 {
+            return Ok(0);
+        }
+
+        let mut transfer = self.transfer.borrow_mut();
+        if transfer.is_none() {
+            let mut client = match self.idle_client.take() {
+                Some(client) => client,
+                None => ftp_login(&self.addr)?,
+            };
+            let data_stream = ftp_open_retrieve(&mut client, &self.path, self.pos.get())?;
+            *transfer = Some((client, data_stream));
+        }
+        let (_, data_stream) = transfer.as_ref().unwrap();
+
+        // The server closing the data connection early means the transfer was cut short.
+        match data_stream.read(&mut buf[..len]) {
+            Ok(0) => {
+                *transfer = None;
+                Err(NetError::Disconnected)
+            }
+            Ok(n) => {
+                self.pos.set(self.pos.get() + n);
+                if self.pos.get() == self.len {
+                    // There should be a response confirming the transfer is complete, but at this
+                    // point we can just close both connections.
+                    *transfer = None;
+                }
+                Ok(n)
+            }
+            Err(e) => {
+                *transfer = None;
+                Err(e)
+            }
+        }
+    }
+}
+
+impl Seek for FtpFile {
+    fn seek(&self, pos: usize) -> Result<(), NetError> {
+        if pos != self.pos.get() {
+            *self.transfer.borrow_mut() = None;
+            self.pos.set(pos);
+        }
+        Ok(())
+    }
+}
+
+/// One line of a map manifest, as written by inception-pack: `<sha256> <size> <crc32> <name>`.
+struct ManifestEntry {
+    size: usize,
+    crc32: u32,
+}
+
+impl ManifestEntry {
+    fn find(manifest: &[u8], name: &str) -> Option<Self> {
+        let manifest = core::str::from_utf8(manifest).ok()?;
+        manifest.lines().find_map(|line| {
+            let mut fields = line.split(' ');
+            let _sha256 = fields.next()?;
+            let size = fields.next()?.parse().ok()?;
+            let crc32 = u32::from_str_radix(fields.next()?, 16).ok()?;
+            (fields.next()? == name).then_some(Self { size, crc32 })
+        })
     }
 }
 
@@ -55,6 +340,40 @@ fn ftp_get_in<A: Allocator>(
     path: &str,
     alloc: A,
 ) -> Result<Vec<u8, A>, NetError> {
+    let file = FtpFile::open(addr, path)?;
+    let mut data = Vec::with_capacity_in(file.len(), alloc);
+    data.resize(file.len(), 0);
+    read_with_resumes(&file, &mut data)?;
+    Ok(data)
+}
+
+/// Retrieves the file into `data`, picking up where the last attempt left off if the connection
+/// drops.
+fn read_with_resumes(file: &FtpFile, data: &mut [u8]) -> Result<(), NetError> {
+    let mut resumes = 0;
+    while file.position() < data.len() {
+        let pos = file.position();
+        if let Err(e) = file.read(&mut data[pos..]) {
+            if resumes == MAX_RESUMES {
+                return Err(e);
+            }
+            resumes += 1;
+            unsafe {
+                let buf = format!(
+                    "Transfer of {} interrupted at {}/{} bytes, resuming\n\0",
+                    file.path,
+                    file.position(),
+                    file.len(),
+                );
+                libc::printf(b"%s\0".as_ptr(), buf.as_ptr());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Opens a control connection, logs in anonymously, and sets binary image mode.
+fn ftp_login(addr: &SocketAddr) -> Result<FtpClient<TcpStream>, NetError> {
     let stream = TcpStream::connect(addr)?;
     stream.socket().set_no_delay()?;
     let mut client = FtpClient::new(stream)?;
@@ -71,38 +390,132 @@ fn ftp_get_in<A: Allocator>(
         resp => panic!("Unexpected response to TYPE: {:?}", resp),
     }
 
-    // Get the file's size.
+    Ok(client)
+}
+
+/// Starts retrieving a file from byte `offset` and returns the data connection it arrives on.
+fn ftp_open_retrieve(
+    client: &mut FtpClient<TcpStream>,
+    path: &str,
+    offset: usize,
+) -> Result<TcpStream, NetError> {
+    let data_stream = ftp_open_data(client)?;
+
+    // Skip whatever was already received.
+    if offset > 0 {
+        let command = format!("REST {}\r\n", offset);
+        match client.send(command.as_bytes())? {
+            FtpResponse::Code(350) => (), // Requested file action pending further information.
+            resp => panic!("Unexpected response to REST: {:?}", resp),
+        }
+    }
+
     // NOTE: This makes no attempt to encode the path correctly. Interesting characters will cause
     // this to fail.
-    let command = format!("SIZE {}\r\n", path);
-    let size = match client.send(command.as_bytes())? {
-        FtpResponse::FileSize { size } => size,
-        resp => panic!("Unexpected response to SIZE: {:?}", resp),
+    let command = format!("RETR {}\r\n", path);
+    match client.send(command.as_bytes())? {
+        FtpResponse::Code(150) => (), // File status okay; about to open data connection.
+        resp => panic!("Unexpected response to RETR: {:?}", resp),
+    }
+
+    Ok(data_stream)
+}
+
+/// Retrieves the listing of a directory, preferring `MLSD` and falling back to `LIST` for servers
+/// that don't implement it. Parse the result with [`no_std_ftp::list`].
+fn ftp_list(addr: &SocketAddr, path: &str) -> Result<Vec<u8>, NetError> {
+    let mut client = ftp_login(addr)?;
+    for verb in ["MLSD", "LIST"] {
+        let data_stream = ftp_open_data(&mut client)?;
+
+        // NOTE: This makes no attempt to encode the path correctly. Interesting characters will
+        // cause this to fail.
+        let command = format!("{} {}\r\n", verb, path);
+        match client.send(command.as_bytes())? {
+            FtpResponse::Code(125 | 150) => (), // Data connection open; transfer starting.
+            FtpResponse::Code(500 | 502) => continue, // Command unrecognized or not implemented.
+            resp => panic!("Unexpected response to {}: {:?}", verb, resp),
+        }
+
+        // The server closes the data connection once the whole listing is sent.
+        let mut data = Vec::new();
+        let mut buf = [0; 1024];
+        loop {
+            match data_stream.read(&mut buf)? {
+                0 => return Ok(data),
+                n => data.extend_from_slice(&buf[..n]),
+            }
+        }
+    }
+    panic!("Server can't list directories");
+}
+
+/// Switches to passive mode and establishes the data connection for the next transfer.
+fn ftp_open_data(client: &mut FtpClient<TcpStream>) -> Result<TcpStream, NetError> {
+    let addr = match client.send(b"PASV\r\n")? {
+        FtpResponse::EnteringPassiveMode { addr, port } => SocketAddr::new(addr, port),
+        resp => panic!("Unexpected response to PASV: {:?}", resp),
     };
+    TcpStream::connect(&addr)
+}
+
+/// Uploads `data` to the server as `path`.
+///
+/// Unlike the download path, unexpected responses are reported as errors rather than panics so that
+/// this can be used from the panic handler.
+pub fn ftp_put(addr: &SocketAddr, path: &str, data: &[u8]) -> Result<(), NetError> {
+    fn expect(resp: FtpResponse, code: u32, function: &'static str) -> Result<(), NetError> {
+        match resp {
+            FtpResponse::Code(c) if c == code => Ok(()),
+            FtpResponse::Code(c) => Err(NetError::Unexpected {
+                function,
+                ret: c as i32,
+            }),
+            _ => Err(NetError::Unexpected { function, ret: -1 }),
+        }
+    }
+
+    let stream = TcpStream::connect(addr)?;
+    stream.socket().set_no_delay()?;
+    let mut client = FtpClient::new(stream)?;
+
+    // Log in anonymously and set binary image mode.
+    expect(client.send(b"USER anonymous\r\n")?, 230, "USER")?;
+    expect(client.send(b"TYPE I\r\n")?, 200, "TYPE")?;
 
     // Switch to passive mode and establish the data connection.
     let addr = match client.send(b"PASV\r\n")? {
         FtpResponse::EnteringPassiveMode { addr, port } => SocketAddr::new(addr, port),
-        resp => panic!("Unexpected response to PASV: {:?}", resp),
+        _ => {
+            return Err(NetError::Unexpected {
+                function: "PASV",
+                ret: -1,
+            })
+        }
     };
     let data_stream = TcpStream::connect(&addr)?;
 
-    // Retrieve the file.
+    // The command is assembled on the stack, since the heap may be what the panic was about.
+    //
     // NOTE: This makes no attempt to encode the path correctly. Interesting characters will cause
     // this to fail.
-    let command = format!("RETR {}\r\n", path);
-    match client.send(command.as_bytes())? {
-        FtpResponse::Code(150) => (), // File status okay; about to open data connection.
-        resp => panic!("Unexpected response to RETR: {:?}", resp),
+    let mut command = [0; 128];
+    let mut len = 0;
+    for part in [b"STOR ", path.as_bytes(), b"\r\n"] {
+        let Some(dst) = command.get_mut(len..len + part.len()) else {
+            return Err(NetError::Unexpected {
+                function: "STOR",
+                ret: -1,
+            });
+        };
+        dst.copy_from_slice(part);
+        len += part.len();
     }
+    expect(client.send(&command[..len])?, 150, "STOR")?;
 
-    // Read the file from the data connection.
-    let mut data = Vec::with_capacity_in(size, alloc);
-    data.resize(size, 0);
-    data_stream.read_all(&mut data)?;
-
-    // There should be a response confirming the transfer is complete, but at this point we can just
-    // close both connections and declare success.
-
-    Ok(data)
+    // Closing the data connection marks the end of the file. The server confirms it has the whole
+    // file once it sees that.
+    data_stream.write_all(data)?;
+    drop(data_stream);
+    expect(client.read_response()?, 226, "STOR")
 }
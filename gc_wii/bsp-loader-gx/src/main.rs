@@ -8,9 +8,10 @@ extern crate alloc;
 #[macro_use(include_bytes_align_as)]
 extern crate include_bytes_align_as;
 
+use core::cell::Cell;
 use core::ffi::c_void;
-use core::mem::zeroed;
-use core::ops::Deref;
+use core::mem::{take, zeroed};
+use core::ops::{Deref, DerefMut};
 use core::ptr::null_mut;
 use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicU32, AtomicUsize, Ordering};
 
@@ -21,36 +22,78 @@ use alloc::string::String;
 use alloc::vec::Vec;
 use derive_try_from_primitive::TryFromPrimitive;
 use font_gx::TextRenderer;
+use gamecube_gecko::{Gecko, Slot};
+use gamecube_gx::{apply_vertex_format, DebugColorVertex, DebugTextureVertex, Gx, SkyboxVertex};
+use gamecube_math::{Mtx34, Mtx44, Vec3};
 use gamecube_mmio::dvd_interface::DvdInterface;
 use gamecube_mmio::processor_interface::ProcessorInterface;
+use gamecube_mmio::Peripherals;
 use gamecube_shader::FLAT_TEXTURED_SHADER;
 use inception_render_common::bytecode::{BytecodeOp, BytecodeReader};
 use inception_render_common::map_data::{MapData, TextureTableEntry};
+use inception_render_common::pipeline_state::{
+    BlendMode, DepthCompare, DepthMode, PipelineState, PipelineStateTracker, StateGroups,
+};
+use inception_render_common::vertex_format::VertexFormat;
+use log::{info, LevelFilter};
 use num_traits::float::FloatCore;
 use ogc_sys::*;
-
+use visibility::{ClusterIndex, PackedVisibility};
+
+use crate::console::{Command, Console};
+use crate::debug_render::{
+    DebugRenderMode, DISPLACEMENT_PASS_BASE, OVERLAY_PASS_BASE, PROP_PASS_BASE, SKYBOX_PASS,
+};
+use crate::dynamic_light::{DynamicLights, PointLight};
+use crate::fog::MapFog;
+use crate::frame_pacing::FramePacer;
+use crate::input::{Action, Bindings, Input};
+use crate::level_transition::{LevelTransitionDetector, PendingTransition};
 use crate::lightmap::Lightmap;
-use crate::loader::Loader;
+use crate::loader::{LoadError, LoadStatus, Loader};
+use crate::occlusion::OcclusionCuller;
+use crate::pipeline_state::{GxPipelineState, PassShader};
+use crate::shaders::dynamic_light::{LIGHTMAPPED_BAAA_DYNAMIC_SHADER, LIGHTMAPPED_DYNAMIC_SHADER};
 use crate::shaders::flat_vertex_color::FLAT_VERTEX_COLOR_SHADER;
-use crate::shaders::lightmapped::LIGHTMAPPED_SHADER;
-use crate::shaders::lightmapped_baaa::LIGHTMAPPED_BAAA_SHADER;
+use crate::shaders::material::{
+    LIGHTMAPPED_BAAA_SHADER, LIGHTMAPPED_SHADER, UNLIT_GENERIC_SHADER,
+    WORLD_VERTEX_TRANSITION_SHADER,
+};
+use crate::shaders::overlay::{OVERLAY_BAAA_SHADER, OVERLAY_SHADER};
 use crate::shaders::self_illum::SELF_ILLUM_SHADER;
-use crate::shaders::unlit_generic::UNLIT_GENERIC_SHADER;
-use crate::shaders::world_vertex_transition::WORLD_VERTEX_TRANSITION_SHADER;
-use crate::visibility::{ClusterIndex, Visibility};
-
+use crate::stereo::{Eye, StereoMode, StereoRenderer};
+use crate::texture_animation::TextureAnimator;
+use crate::texture_cache::SharedTextureCache;
+use crate::z_prepass::{PixelCounts, WorldDepthMode, WorldFillMetrics};
+
+mod console;
+mod crash_screen;
+mod debug_render;
+mod dvd_file;
+mod dynamic_light;
+mod fog;
+mod frame_pacing;
+mod gpu_watchdog;
+mod input;
 mod iso9660;
+mod level_transition;
 mod lightmap;
 mod loader;
 mod net;
+mod occlusion;
+mod pipeline_state;
 mod shaders;
-mod visibility;
+mod stereo;
+mod texture_animation;
+mod texture_cache;
+mod z_prepass;
 
 static UI_FONT: &[u8] = include_bytes_align_as!(A32, "../../../build/ui_font.dat");
 
+const GP_FIFO_SIZE: usize = 512 * 1024;
+
 static XFB_FRONT: AtomicPtr<c_void> = AtomicPtr::new(null_mut());
 static XFB_BACK: AtomicPtr<c_void> = AtomicPtr::new(null_mut());
-static GP_FIFO: AtomicPtr<c_void> = AtomicPtr::new(null_mut());
 static DO_COPY: AtomicBool = AtomicBool::new(false);
 static FRAMES: AtomicUsize = AtomicUsize::new(0);
 static LAST_FRAME_FRAMES: AtomicUsize = AtomicUsize::new(0);
@@ -65,21 +108,21 @@ fn get_widescreen_setting() -> bool {
     true // Probably a bad default, but that's what my test setup wants.
 }
 
-fn configure_loader() -> impl Loader {
+#[cfg_attr(not(feature = "dvd_loader"), allow(unused_variables))]
+fn configure_loader(di: DvdInterface, pi: ProcessorInterface) -> impl Loader {
     #[cfg(feature = "dvd_loader")]
     {
         return crate::loader::dvd_gcm_loader::DvdGcmLoader::new((
-            gamecube_dvd_driver::DvdDriver::new(DvdInterface::new()),
-            ProcessorInterface::new(),
+            gamecube_dvd_driver::DvdDriver::new(di),
+            pi,
         ));
     }
 
     #[cfg(feature = "ftp_loader")]
     {
-        return crate::loader::ftp_loader::FtpLoader::new(crate::net::SocketAddr::new(
-            [10, 0, 1, 104],
-            21,
-        ));
+        use crate::loader::ftp_loader::{FtpLoader, SERVER_ADDR, SERVER_PORT};
+
+        return FtpLoader::new(crate::net::SocketAddr::new(SERVER_ADDR, SERVER_PORT));
     }
 
     #[cfg(feature = "embedded_loader")]
@@ -93,17 +136,20 @@ fn configure_loader() -> impl Loader {
     }
 }
 
-fn select_map(loader: &mut impl Loader) -> String {
+fn select_map(loader: &mut impl Loader, bindings: &Bindings) -> String {
     unsafe {
         loop {
             libc::printf(b"\x1b[2JFetching map list...\n\0".as_ptr());
-            let mut maps = loader.maps();
+            let mut maps = loader.map_listings();
+            // Newest first, since that's usually the map that was just packed. The sort is stable,
+            // so maps without a modification time stay in list order at the end.
+            maps.sort_by(|a, b| b.modified.cmp(&a.modified));
 
             if maps.is_empty() {
                 libc::printf(b"Map list was empty!\0".as_ptr());
                 loop {}
             } else if maps.len() == 1 {
-                return maps.swap_remove(0);
+                return maps.swap_remove(0).name;
             }
 
             libc::printf(
@@ -116,21 +162,41 @@ fn select_map(loader: &mut impl Loader) -> String {
             );
             let mut index = 0usize;
             'select: loop {
+                let map = &maps[index];
+                let mut details = String::new();
+                if let Some(size) = map.size {
+                    details += &format!("  {} KiB", size / 1024);
+                }
+                if let Some(modified) = map.modified {
+                    if let Some(year) = modified.year {
+                        details += &format!("  {:04}-", year);
+                    } else {
+                        details += "  ";
+                    }
+                    details += &format!(
+                        "{:02}-{:02} {:02}:{:02}",
+                        modified.month, modified.day, modified.hour, modified.minute,
+                    );
+                }
                 let buf = format!(
-                    "\x1b[u\x1b[K    ({}/{}) {}\n\0",
+                    "\x1b[u\x1b[K    ({}/{}) {}{}\n\0",
                     index + 1,
                     maps.len(),
-                    maps[index],
+                    map.name,
+                    details,
                 );
                 libc::printf(b"%s\0".as_ptr(), buf.as_ptr());
 
                 loop {
                     VIDEO_WaitVSync();
-                    PAD_ScanPads();
-                    if (PAD_ButtonsDown(0) & PAD_BUTTON_START as u16) != 0 {
+                    if loader.media_changed() {
+                        break 'select;
+                    }
+                    let input = Input::scan();
+                    if input.is_pressed(bindings, Action::Menu) {
                         libc::exit(0);
                     }
-                    if (PAD_ButtonsDown(0) & PAD_BUTTON_UP as u16) != 0 {
+                    if input.is_pressed(bindings, Action::MenuUp) {
                         if index < maps.len() - 1 {
                             index = (index + 10).min(maps.len() - 1);
                         } else {
@@ -138,7 +204,7 @@ fn select_map(loader: &mut impl Loader) -> String {
                         }
                         break;
                     }
-                    if (PAD_ButtonsDown(0) & PAD_BUTTON_DOWN as u16) != 0 {
+                    if input.is_pressed(bindings, Action::MenuDown) {
                         if index > 0 {
                             index = index.saturating_sub(10);
                         } else {
@@ -146,7 +212,7 @@ fn select_map(loader: &mut impl Loader) -> String {
                         }
                         break;
                     }
-                    if (PAD_ButtonsDown(0) & PAD_BUTTON_LEFT as u16) != 0 {
+                    if input.is_pressed(bindings, Action::MenuLeft) {
                         if index > 0 {
                             index -= 1;
                         } else {
@@ -154,7 +220,7 @@ fn select_map(loader: &mut impl Loader) -> String {
                         }
                         break;
                     }
-                    if (PAD_ButtonsDown(0) & PAD_BUTTON_RIGHT as u16) != 0 {
+                    if input.is_pressed(bindings, Action::MenuRight) {
                         if index < maps.len() - 1 {
                             index += 1;
                         } else {
@@ -162,10 +228,10 @@ fn select_map(loader: &mut impl Loader) -> String {
                         }
                         break;
                     }
-                    if (PAD_ButtonsDown(0) & PAD_BUTTON_A as u16) != 0 {
-                        return maps.swap_remove(index);
+                    if input.is_pressed(bindings, Action::Confirm) {
+                        return maps.swap_remove(index).name;
                     }
-                    if (PAD_ButtonsDown(0) & PAD_BUTTON_B as u16) != 0 {
+                    if input.is_pressed(bindings, Action::Back) {
                         break 'select;
                     }
                 }
@@ -174,50 +240,110 @@ fn select_map(loader: &mut impl Loader) -> String {
     }
 }
 
-/// # SAFETY
-///
-/// This function writes to memory that aliases `map_data` and so is fundamentally unsound. That
-/// said, the memory in question is a GX display list that the CPU will never read. Let's hope the
-/// optimizer doesn't strip out these writes.
-unsafe fn relocate_references<Data: Deref<Target = [u8]>>(map_data: &MapData<Data>) {
-    unsafe {
-        for entry in map_data.cluster_geometry_references() {
-            let display_list_ptr: *mut u32 = map_data
-                .cluster_geometry_display_lists()
-                .as_ptr()
-                .cast_mut()
-                .offset(entry.display_list_offset as isize)
-                .cast();
-            let image_ptr = map_data
-                .texture_data()
-                .as_ptr()
-                .offset(map_data.texture_table()[entry.texture_id as usize].start_offset as isize);
-            let image_reg_value = ((image_ptr as u32) >> 5) & 0x00ffffff;
-            display_list_ptr.write(display_list_ptr.read() & 0xff000000 | image_reg_value);
+/// Pumps a map load, redrawing a progress bar between polls. Returns `None` if the load was
+/// cancelled.
+fn load_map_with_progress<L: Loader>(
+    loader: &mut L,
+    map: &str,
+    bindings: &Bindings,
+) -> Option<MapData<L::Data>> {
+    begin_progress(&format!("Loading {}...", map));
+    let mut load = loader.start_load_map(map);
+    loop {
+        match loader.poll_load_map(&mut load) {
+            LoadStatus::Done(map_data) => return Some(map_data),
+            LoadStatus::Loading { done, total } => {
+                if !update_progress(bindings, done, total) {
+                    return None;
+                }
+            }
         }
-        DCFlushRange(
-            map_data.cluster_geometry_display_lists().as_ptr() as _,
-            map_data.cluster_geometry_display_lists().len() as u32,
-        );
+    }
+}
 
-        for entry in map_data.displacement_references() {
-            let display_list_ptr: *mut u32 = map_data
-                .displacement_display_lists()
-                .as_ptr()
-                .cast_mut()
-                .offset(entry.display_list_offset as isize)
-                .cast();
-            let image_ptr = map_data
-                .texture_data()
-                .as_ptr()
-                .offset(map_data.texture_table()[entry.texture_id as usize].start_offset as isize);
-            let image_reg_value = ((image_ptr as u32) >> 5) & 0x00ffffff;
-            display_list_ptr.write(display_list_ptr.read() & 0xff000000 | image_reg_value);
+/// Pumps a fetch of the shared textures `map_data` is missing, redrawing a progress bar between
+/// polls. Returns false if the fetch was cancelled.
+fn load_shared_textures_with_progress<L: Loader, Data: Deref<Target = [u8]>>(
+    loader: &mut L,
+    texture_cache: &mut SharedTextureCache,
+    map_data: &MapData<Data>,
+    bindings: &Bindings,
+) -> Result<bool, LoadError> {
+    let mut load = texture_cache.start_load_for_map(map_data);
+    if load.is_empty() {
+        return Ok(true);
+    }
+
+    begin_progress(&format!("Fetching {} shared textures...", load.len()));
+    loop {
+        match texture_cache.poll_load(loader, &mut load)? {
+            LoadStatus::Done(()) => return Ok(true),
+            LoadStatus::Loading { done, total } => {
+                if !update_progress(bindings, done, total) {
+                    return Ok(false);
+                }
+            }
         }
-        DCFlushRange(
-            map_data.displacement_display_lists().as_ptr() as _,
-            map_data.displacement_display_lists().len() as u32,
+    }
+}
+
+/// Clears the screen for a load, leaving room for [`update_progress`] to draw the bar.
+fn begin_progress(title: &str) {
+    unsafe {
+        let buf = format!(
+            "\x1b[2J{}\n\n\x1b[s\n\n\
+            B: Cancel\n\0",
+            title,
+        );
+        libc::printf(b"%s\0".as_ptr(), buf.as_ptr());
+    }
+}
+
+/// Redraws the progress bar for `done` of `total` bytes. Returns false if the player cancelled.
+fn update_progress(bindings: &Bindings, done: usize, total: usize) -> bool {
+    const SPINNER: &[u8] = b"|/-\\";
+    const BAR_WIDTH: usize = 40;
+
+    if Input::scan().is_pressed(bindings, Action::Back) {
+        return false;
+    }
+
+    unsafe {
+        // Widen to avoid overflow on large maps.
+        let fraction = |scale: usize| (done as u64 * scale as u64 / total.max(1) as u64) as usize;
+        let filled = fraction(BAR_WIDTH);
+        let spinner = SPINNER[(VIDEO_GetRetraceCount() / 8) as usize % SPINNER.len()] as char;
+        let buf = format!(
+            "\x1b[u\x1b[K    {} [{}{}] {:3}% ({}/{} KiB)\0",
+            spinner,
+            "#".repeat(filled),
+            " ".repeat(BAR_WIDTH - filled),
+            fraction(100),
+            done / 1024,
+            total / 1024,
         );
+        libc::printf(b"%s\0".as_ptr(), buf.as_ptr());
+    }
+    true
+}
+
+/// Points the display lists' texture references at the loaded texture data, then pushes the
+/// patched display lists out of the CPU cache so that GX sees them.
+fn relocate<Data: DerefMut<Target = [u8]>>(
+    map: &str,
+    map_data: &mut MapData<Data>,
+    texture_cache: &SharedTextureCache,
+) {
+    if let Err(e) = map_data.relocate_with_shared_textures(|entry| texture_cache.address(entry)) {
+        panic!("map {} can't be relocated: {:?}", map, e);
+    }
+    for display_lists in [
+        map_data.cluster_geometry_display_lists(),
+        map_data.displacement_display_lists(),
+        map_data.overlay_display_lists(),
+        map_data.prop_display_lists(),
+    ] {
+        unsafe { DCFlushRange(display_lists.as_ptr() as _, display_lists.len() as u32) };
     }
 }
 
@@ -225,8 +351,19 @@ unsafe fn relocate_references<Data: Deref<Target = [u8]>>(map_data: &MapData<Dat
 fn main(_argc: isize, _argv: *const *const u8) -> isize {
     unsafe {
         init_for_console();
+        let mut gx = Gx::init(GP_FIFO_SIZE);
+        let peripherals = Peripherals::take().unwrap();
+        let _ = gamecube_gecko::logger::init(
+            Gecko::probe(peripherals.external_interface, Slot::B),
+            LevelFilter::Info,
+        );
 
-        let mut loader = configure_loader();
+        let mut loader =
+            configure_loader(peripherals.dvd_interface, peripherals.processor_interface);
+        let mut pending_transition: Option<PendingTransition> = None;
+        let mut bindings = Bindings::default();
+        let mut console = Console::new();
+        let mut texture_cache = SharedTextureCache::new();
 
         loop {
             PENDING_GAME_STATE_CHANGE.store(GameStateChange::None as u32, Ordering::SeqCst);
@@ -236,11 +373,49 @@ fn main(_argc: isize, _argv: *const *const u8) -> isize {
             // Compute logical height.
             let height = if (*rmode).aa != 0 { 2 * height } else { height };
 
-            let map = select_map(&mut loader);
-            libc::printf(b"Loading map...\n\0".as_ptr());
-            let map_data = loader.load_map(&map);
+            // Fall back to the map selection menu if the destination map wasn't packed.
+            let transition = pending_transition
+                .take()
+                .filter(|transition| loader.maps().contains(&transition.map));
+            let map = match &transition {
+                Some(transition) => transition.map.clone(),
+                None => select_map(&mut loader, &bindings),
+            };
+            console.set_map_names(loader.maps());
+            info!("loading map {}", map);
+            let mut map_data = match load_map_with_progress(&mut loader, &map, &bindings) {
+                Some(map_data) => map_data,
+                // Back to map selection.
+                None => continue,
+            };
+            if let Err(e) = map_data.verify() {
+                panic!("map {} is corrupt: {:?}", map, e);
+            }
 
-            relocate_references(&map_data);
+            match load_shared_textures_with_progress(
+                &mut loader,
+                &mut texture_cache,
+                &map_data,
+                &bindings,
+            ) {
+                Ok(true) => (),
+                // Back to map selection.
+                Ok(false) => continue,
+                Err(e) => {
+                    let buf = format!(
+                        "\n\nCouldn't fetch shared textures: {:?}\n\n\
+                        B: Return to map selection\n\0",
+                        e,
+                    );
+                    libc::printf(b"%s\0".as_ptr(), buf.as_ptr());
+                    while !Input::scan().is_pressed(&bindings, Action::Back) {
+                        VIDEO_WaitVSync();
+                    }
+                    continue;
+                }
+            }
+            relocate(&map, &mut map_data, &texture_cache);
+            let mut texture_animator = TextureAnimator::new(&map_data);
 
             init_for_3d(&*rmode);
 
@@ -333,7 +508,8 @@ fn main(_argc: isize, _argv: *const *const u8) -> isize {
                 ui_font
             };
 
-            let visibility = Visibility::new(map_data.visibility().as_ptr());
+            let visibility = PackedVisibility::new(map_data.visibility());
+            let mut occlusion_culler = OcclusionCuller::new(visibility.num_clusters());
 
             let mut game_state = GameState {
                 // // d1_trainstation_01 classic view
@@ -355,20 +531,45 @@ fn main(_argc: isize, _argv: *const *const u8) -> isize {
                 pitch: 0.0155,
 
                 inverted_pitch_control: false,
+                bindings,
                 msaa: false,
                 copy_filter: false,
                 widescreen: get_widescreen_setting(),
                 lightmap_style: 0,
+                debug_render_mode: DebugRenderMode::Normal,
+                stereo_mode: StereoMode::Off,
+                noclip: false,
+                occlusion_culling: true,
+                z_prepass: false,
+                show_stats: true,
+                requested_map: None,
+                dynamic_lights: DynamicLights::new(),
+                fog: MapFog::new(&map_data),
 
                 ui_item: 0,
+                console,
 
                 gp_perf_metric0: GpPerfMetric0::NONE,
                 gp_perf_metric1: GpPerfMetric1::NONE,
             };
+            if let Some(transition) = &transition {
+                transition.place_camera(
+                    &map_data,
+                    &mut game_state.pos,
+                    &mut game_state.yaw,
+                    &mut game_state.pitch,
+                );
+            }
+            let mut level_transition_detector = LevelTransitionDetector::new();
+            // Allocated the first time stereo is turned on, since the eye textures are large.
+            let mut stereo_renderer: Option<StereoRenderer> = None;
 
             let mut performance_metrics = PerformanceMetrics::default();
+            let mut fill_metrics = WorldFillMetrics::default();
             let mut last_frame_timers = zeroed::<FrameTimers>();
             let mut last_frame_frames = 0;
+            let mut frame_pacer = FramePacer::new();
+            gpu_watchdog::init();
             loop {
                 match PENDING_GAME_STATE_CHANGE.load(Ordering::SeqCst) {
                     x if x == GameStateChange::Reset as u32 => {
@@ -383,6 +584,7 @@ fn main(_argc: isize, _argv: *const *const u8) -> isize {
                     _ => (),
                 }
 
+                frame_pacer.begin_frame();
                 let game_logic_elapsed = Timer::time(|| {
                     do_game_logic(
                         &map_data,
@@ -390,22 +592,85 @@ fn main(_argc: isize, _argv: *const *const u8) -> isize {
                         &mut cluster_lightmaps,
                         &mut displacement_lightmaps,
                     );
+                    // The previous frame's GX_DrawDone has returned, so GX is idle.
+                    texture_animator.update(&mut map_data);
                 });
+                if let Some(map) = game_state.requested_map.take() {
+                    pending_transition = Some(PendingTransition::to_map(
+                        map,
+                        game_state.yaw,
+                        game_state.pitch,
+                    ));
+                    libc::printf(b"Changing level...\n\0".as_ptr());
+                    break;
+                }
+                if !game_state.noclip {
+                    pending_transition = level_transition_detector.update(
+                        &map_data,
+                        &game_state.pos,
+                        game_state.yaw,
+                        game_state.pitch,
+                    );
+                }
+                if pending_transition.is_some() {
+                    libc::printf(b"Changing level...\n\0".as_ptr());
+                    break;
+                }
                 let main_draw_elapsed = Timer::time(|| {
                     GX_ClearGPMetric();
                     GX_ClearVCacheMetric();
 
-                    if game_state.msaa {
+                    if game_state.stereo_mode != StereoMode::Off {
+                        let stereo_renderer =
+                            stereo_renderer.get_or_insert_with(StereoRenderer::new);
+                        let center = game_state.pos;
+                        let mut view_cluster = -1;
+                        for eye in [Eye::Left, Eye::Right] {
+                            game_state.pos = eye.offset(center, game_state.yaw);
+                            prepare_main_draw(width, height, &game_state, None);
+                            view_cluster = do_main_draw(
+                                &mut gx,
+                                &map_data,
+                                &game_state,
+                                visibility,
+                                &mut occlusion_culler,
+                                &skybox_texobjs,
+                                &cluster_lightmaps,
+                                &displacement_lightmaps,
+                                &mut fill_metrics,
+                            );
+                            stereo_renderer.copy_eye(eye);
+                        }
+                        game_state.pos = center;
+                        stereo_renderer.composite(&mut gx, game_state.stereo_mode);
+                        do_debug_draw(
+                            &mut gx,
+                            width,
+                            height,
+                            &game_state,
+                            &last_frame_timers,
+                            view_cluster,
+                            &cluster_lightmaps,
+                            &ui_font,
+                            &performance_metrics,
+                            last_frame_frames,
+                        );
+                        copy_disp(None);
+                    } else if game_state.msaa {
                         prepare_main_draw(width, height, &game_state, Some(false));
                         let view_cluster = do_main_draw(
+                            &mut gx,
                             &map_data,
                             &game_state,
                             visibility,
+                            &mut occlusion_culler,
                             &skybox_texobjs,
                             &cluster_lightmaps,
                             &displacement_lightmaps,
+                            &mut fill_metrics,
                         );
                         do_debug_draw(
+                            &mut gx,
                             width,
                             height,
                             &game_state,
@@ -420,14 +685,18 @@ fn main(_argc: isize, _argv: *const *const u8) -> isize {
 
                         prepare_main_draw(width, height, &game_state, Some(true));
                         let view_cluster = do_main_draw(
+                            &mut gx,
                             &map_data,
                             &game_state,
                             visibility,
+                            &mut occlusion_culler,
                             &skybox_texobjs,
                             &cluster_lightmaps,
                             &displacement_lightmaps,
+                            &mut fill_metrics,
                         );
                         do_debug_draw(
+                            &mut gx,
                             width,
                             height,
                             &game_state,
@@ -442,14 +711,18 @@ fn main(_argc: isize, _argv: *const *const u8) -> isize {
                     } else {
                         prepare_main_draw(width, height, &game_state, None);
                         let view_cluster = do_main_draw(
+                            &mut gx,
                             &map_data,
                             &game_state,
                             visibility,
+                            &mut occlusion_culler,
                             &skybox_texobjs,
                             &cluster_lightmaps,
                             &displacement_lightmaps,
+                            &mut fill_metrics,
                         );
                         do_debug_draw(
+                            &mut gx,
                             width,
                             height,
                             &game_state,
@@ -462,16 +735,30 @@ fn main(_argc: isize, _argv: *const *const u8) -> isize {
                         );
                         copy_disp(None);
                     }
+
+                    if game_state.occlusion_culling {
+                        occlusion_culler.end_frame();
+                    } else {
+                        occlusion_culler.reset();
+                    }
                 });
                 let copy_to_texture_elapsed = 0;
                 let debug_draw_elapsed = 0;
                 let draw_done_elapsed = Timer::time(|| {
-                    GX_DrawDone();
-                    performance_metrics = PerformanceMetrics::read();
+                    frame_pacer.end_submit();
+                    // A hung frame never reached the pacer's token, so it has no GPU time to
+                    // record.
+                    if gpu_watchdog::draw_done(&mut gx) {
+                        frame_pacer.end_frame();
+                    }
+                    performance_metrics = PerformanceMetrics::read(take(&mut fill_metrics));
                     DO_COPY.store(true, Ordering::Release);
                 });
                 let idle_elapsed = Timer::time(|| {
-                    VIDEO_WaitVSync();
+                    // The flip may be held for more than one retrace by frame pacing.
+                    while DO_COPY.load(Ordering::Acquire) {
+                        VIDEO_WaitVSync();
+                    }
                 });
                 last_frame_frames = LAST_FRAME_FRAMES.load(Ordering::Acquire);
 
@@ -484,6 +771,8 @@ fn main(_argc: isize, _argv: *const *const u8) -> isize {
                     idle: idle_elapsed,
                 };
             }
+            bindings = game_state.bindings;
+            console = game_state.console;
         }
     }
 }
@@ -493,18 +782,42 @@ struct GameState {
     yaw: f32,
     pitch: f32,
     inverted_pitch_control: bool,
+    bindings: Bindings,
     msaa: bool,
     copy_filter: bool,
     widescreen: bool,
     lightmap_style: usize,
+    debug_render_mode: DebugRenderMode,
+    /// Stereo renders at full resolution without MSAA, whatever `msaa` says.
+    stereo_mode: StereoMode,
+    /// There's no collision, so this only stops level transition volumes from firing.
+    noclip: bool,
+    occlusion_culling: bool,
+    /// Draws opaque world geometry depth-only first, then shades it with an equal depth test.
+    z_prepass: bool,
+    /// Also gathers pixel counts for the world passes, at the cost of a GX stall per pass.
+    show_stats: bool,
+    /// Set by the console to leave for another map at the end of the frame.
+    requested_map: Option<String>,
+    dynamic_lights: DynamicLights,
+    fog: MapFog,
 
     ui_item: usize,
+    console: Console,
 
     gp_perf_metric0: GpPerfMetric0,
     gp_perf_metric1: GpPerfMetric1,
 }
 
 impl GameState {
+    fn eye(&self) -> Vec3 {
+        Vec3::new(self.pos.x, self.pos.y, self.pos.z)
+    }
+
+    fn msaa_enabled(&self) -> bool {
+        self.msaa && self.stereo_mode == StereoMode::Off
+    }
+
     fn widescreen_factor(&self) -> f32 {
         if self.widescreen {
             4.0 / 3.0
@@ -549,124 +862,248 @@ fn do_game_logic<Data: Deref<Target = [u8]>>(
     displacement_lightmaps: &mut BTreeMap<u16, Lightmap>,
 ) {
     unsafe {
-        PAD_ScanPads();
+        let input = Input::scan();
+        let bindings = &game_state.bindings;
 
-        if (PAD_ButtonsDown(0) & PAD_BUTTON_START as u16) != 0 {
+        if input.is_pressed(bindings, Action::Menu) {
             PENDING_GAME_STATE_CHANGE.store(GameStateChange::MapSelect as u32, Ordering::SeqCst);
         }
-        if (PAD_ButtonsDown(0) & PAD_TRIGGER_Z as u16) != 0 {
+        if input.is_pressed(bindings, Action::InvertPitch) {
             game_state.inverted_pitch_control ^= true;
         }
 
-        let right = [libm::sinf(game_state.yaw), -libm::cosf(game_state.yaw), 0.0];
-        let forward = [libm::cosf(game_state.yaw), libm::sinf(game_state.yaw), 0.0];
-        let speed = if PAD_TriggerR(0) >= 128 { 100.0 } else { 10.0 };
-        let angspeed = 0.1;
-        let (dx, dy) = get_processed_stick(0, false);
-        let (cx, cy) = get_processed_stick(0, true);
-        let cy = if game_state.inverted_pitch_control {
-            -cy
+        if input.is_pressed(bindings, Action::Console) {
+            game_state.console.toggle();
+        } else if game_state.console.is_open() {
+            if let Some(command) = game_state.console.update(&input, bindings) {
+                run_console_command(
+                    map_data,
+                    game_state,
+                    cluster_lightmaps,
+                    displacement_lightmaps,
+                    command,
+                );
+            }
         } else {
-            cy
-        };
-
-        game_state.pos.x += speed * (right[0] * dx + forward[0] * dy);
-        game_state.pos.y += speed * (right[1] * dx + forward[1] * dy);
-        game_state.pos.z += speed * (right[2] * dx + forward[2] * dy);
-        if (PAD_ButtonsHeld(0) & PAD_BUTTON_Y as u16) != 0 {
-            game_state.pos.z += speed;
-        }
-        if (PAD_ButtonsHeld(0) & PAD_BUTTON_X as u16) != 0 {
-            game_state.pos.z -= speed;
+            update_camera_and_menu(
+                map_data,
+                game_state,
+                cluster_lightmaps,
+                displacement_lightmaps,
+                &input,
+            );
         }
 
-        game_state.yaw -= angspeed * cx;
-        game_state.pitch = (game_state.pitch + angspeed * cy).clamp(
-            -89.0 / 180.0 * core::f32::consts::PI,
-            89.0 / 180.0 * core::f32::consts::PI,
+        game_state.dynamic_lights.update();
+
+        GX_SetGPMetric(
+            game_state.gp_perf_metric0 as u32,
+            game_state.gp_perf_metric1 as u32,
         );
+        GX_SetVCacheMetric(GX_VC_ALL);
+    }
+}
+
+fn update_camera_and_menu<Data: Deref<Target = [u8]>>(
+    map_data: &MapData<Data>,
+    game_state: &mut GameState,
+    cluster_lightmaps: &mut [Lightmap],
+    displacement_lightmaps: &mut BTreeMap<u16, Lightmap>,
+    input: &Input,
+) {
+    let bindings = &game_state.bindings;
 
-        if (PAD_ButtonsDown(0) & PAD_BUTTON_UP as u16) != 0 {
-            game_state.ui_item = game_state.ui_item.checked_sub(1).unwrap_or(4);
+    let right = [libm::sinf(game_state.yaw), -libm::cosf(game_state.yaw), 0.0];
+    let forward = [libm::cosf(game_state.yaw), libm::sinf(game_state.yaw), 0.0];
+    let speed = if input.is_held(bindings, Action::Speed) {
+        100.0
+    } else {
+        10.0
+    };
+    let angspeed = 0.1;
+    let (dx, dy) = input.move_axes();
+    let (cx, cy) = input.look_axes();
+    let cy = if game_state.inverted_pitch_control {
+        -cy
+    } else {
+        cy
+    };
+
+    game_state.pos.x += speed * (right[0] * dx + forward[0] * dy);
+    game_state.pos.y += speed * (right[1] * dx + forward[1] * dy);
+    game_state.pos.z += speed * (right[2] * dx + forward[2] * dy);
+    if input.is_held(bindings, Action::Ascend) {
+        game_state.pos.z += speed;
+    }
+    if input.is_held(bindings, Action::Descend) {
+        game_state.pos.z -= speed;
+    }
+
+    game_state.yaw -= angspeed * cx;
+    game_state.pitch = (game_state.pitch + angspeed * cy).clamp(
+        -89.0 / 180.0 * core::f32::consts::PI,
+        89.0 / 180.0 * core::f32::consts::PI,
+    );
+
+    if input.is_pressed(bindings, Action::MenuUp) {
+        game_state.ui_item = game_state.ui_item.checked_sub(1).unwrap_or(7);
+    }
+    if input.is_pressed(bindings, Action::MenuDown) {
+        game_state.ui_item = (game_state.ui_item + 1) % 8;
+    }
+
+    let ui_increment: i32 = if input.is_pressed(bindings, Action::MenuLeft) {
+        -1
+    } else {
+        0
+    } + if input.is_pressed(bindings, Action::MenuRight) {
+        1
+    } else {
+        0
+    };
+
+    match game_state.ui_item {
+        0 => {
+            game_state.msaa ^= ui_increment != 0;
         }
-        if (PAD_ButtonsDown(0) & PAD_BUTTON_DOWN as u16) != 0 {
-            game_state.ui_item = (game_state.ui_item + 1) % 5;
+
+        1 => {
+            game_state.copy_filter ^= ui_increment != 0;
         }
 
-        let ui_increment: i32 = if (PAD_ButtonsDown(0) & PAD_BUTTON_LEFT as u16) != 0 {
-            -1
-        } else {
-            0
-        } + if (PAD_ButtonsDown(0) & PAD_BUTTON_RIGHT as u16) != 0 {
-            1
-        } else {
-            0
-        };
+        2 => {
+            // Change lightmap styles.
+            let new_lightmap_style = game_state
+                .lightmap_style
+                .wrapping_add(ui_increment as usize)
+                % 4;
+            set_lightmap_style(
+                map_data,
+                game_state,
+                cluster_lightmaps,
+                displacement_lightmaps,
+                new_lightmap_style,
+            );
+        }
 
-        match game_state.ui_item {
-            0 => {
-                game_state.msaa ^= ui_increment != 0;
-            }
+        3 => {
+            // Change GP perf metric 0.
+            match ui_increment {
+                -1 => game_state.gp_perf_metric0 = game_state.gp_perf_metric0.prev(),
+                1 => game_state.gp_perf_metric0 = game_state.gp_perf_metric0.next(),
+                _ => (),
+            };
+        }
 
-            1 => {
-                game_state.copy_filter ^= ui_increment != 0;
-            }
+        4 => {
+            // Change GP perf metric 1.
+            match ui_increment {
+                -1 => game_state.gp_perf_metric1 = game_state.gp_perf_metric1.prev(),
+                1 => game_state.gp_perf_metric1 = game_state.gp_perf_metric1.next(),
+                _ => (),
+            };
+        }
 
-            2 => {
-                // Change lightmap styles.
-                let new_lightmap_style = game_state
-                    .lightmap_style
-                    .wrapping_add(ui_increment as usize)
-                    % 4;
-
-                if game_state.lightmap_style != new_lightmap_style {
-                    game_state.lightmap_style = new_lightmap_style;
-                    for (cluster_index, lightmap) in cluster_lightmaps.iter_mut().enumerate() {
-                        let entry = &map_data.lightmap_cluster_table()[cluster_index];
-                        lightmap.update(map_data, &entry.common, game_state.lightmap_style);
-                    }
-                    for entry in map_data.lightmap_displacement_table() {
-                        displacement_lightmaps
-                            .get_mut(&entry.face_index)
-                            .unwrap()
-                            .update(map_data, &entry.common, game_state.lightmap_style);
-                    }
-                }
-            }
+        5 => {
+            // Change debug render mode.
+            match ui_increment {
+                -1 => game_state.debug_render_mode = game_state.debug_render_mode.prev(),
+                1 => game_state.debug_render_mode = game_state.debug_render_mode.next(),
+                _ => (),
+            };
+        }
 
-            3 => {
-                // Change GP perf metric 0.
-                match ui_increment {
-                    -1 => game_state.gp_perf_metric0 = game_state.gp_perf_metric0.prev(),
-                    1 => game_state.gp_perf_metric0 = game_state.gp_perf_metric0.next(),
-                    _ => (),
-                };
-            }
+        6 => {
+            // Change stereo mode.
+            match ui_increment {
+                -1 => game_state.stereo_mode = game_state.stereo_mode.prev(),
+                1 => game_state.stereo_mode = game_state.stereo_mode.next(),
+                _ => (),
+            };
+        }
 
-            4 => {
-                // Change GP perf metric 1.
-                match ui_increment {
-                    -1 => game_state.gp_perf_metric1 = game_state.gp_perf_metric1.prev(),
-                    1 => game_state.gp_perf_metric1 = game_state.gp_perf_metric1.next(),
-                    _ => (),
-                };
-            }
+        7 => {
+            game_state.z_prepass ^= ui_increment != 0;
+        }
 
-            _ => unreachable!(),
+        _ => unreachable!(),
+    }
+}
+
+fn run_console_command<Data: Deref<Target = [u8]>>(
+    map_data: &MapData<Data>,
+    game_state: &mut GameState,
+    cluster_lightmaps: &mut [Lightmap],
+    displacement_lightmaps: &mut BTreeMap<u16, Lightmap>,
+    command: Command,
+) {
+    match command {
+        Command::Noclip => {
+            game_state.noclip ^= true;
+            let message = format!("noclip {}", if game_state.noclip { "on" } else { "off" });
+            game_state.console.print(message);
+        }
+        Command::Teleport([x, y, z]) => game_state.pos = guVector { x, y, z },
+        Command::Map(map) => game_state.requested_map = Some(map),
+        Command::LightmapStyle(style) if style < 4 => set_lightmap_style(
+            map_data,
+            game_state,
+            cluster_lightmaps,
+            displacement_lightmaps,
+            style,
+        ),
+        Command::LightmapStyle(_) => game_state.console.print("lightmap styles are 0 to 3"),
+        Command::Stats => game_state.show_stats ^= true,
+        Command::Occlusion => {
+            game_state.occlusion_culling ^= true;
+            let message = format!(
+                "occlusion culling {}",
+                if game_state.occlusion_culling {
+                    "on"
+                } else {
+                    "off"
+                },
+            );
+            game_state.console.print(message);
         }
+        Command::Flash => game_state
+            .dynamic_lights
+            .spawn(PointLight::muzzle_flash(game_state.eye())),
+        Command::Glow => game_state
+            .dynamic_lights
+            .spawn(PointLight::pickup_glow(game_state.eye())),
+        Command::ClearLights => game_state.dynamic_lights.clear(),
+    }
+}
 
-        GX_SetGPMetric(
-            game_state.gp_perf_metric0 as u32,
-            game_state.gp_perf_metric1 as u32,
-        );
-        GX_SetVCacheMetric(GX_VC_ALL);
+/// Switches every lightmap to one of the four styles baked into the map.
+fn set_lightmap_style<Data: Deref<Target = [u8]>>(
+    map_data: &MapData<Data>,
+    game_state: &mut GameState,
+    cluster_lightmaps: &mut [Lightmap],
+    displacement_lightmaps: &mut BTreeMap<u16, Lightmap>,
+    lightmap_style: usize,
+) {
+    if game_state.lightmap_style == lightmap_style {
+        return;
+    }
+    game_state.lightmap_style = lightmap_style;
+    for (cluster_index, lightmap) in cluster_lightmaps.iter_mut().enumerate() {
+        let entry = &map_data.lightmap_cluster_table()[cluster_index];
+        lightmap.update(map_data, &entry.common, lightmap_style);
+    }
+    for entry in map_data.lightmap_displacement_table() {
+        displacement_lightmaps
+            .get_mut(&entry.face_index)
+            .unwrap()
+            .update(map_data, &entry.common, lightmap_style);
     }
 }
 
 fn prepare_main_draw(width: u16, height: u16, game_state: &GameState, half: Option<bool>) {
     unsafe {
         GX_SetPixelFmt(
-            if game_state.msaa {
+            if game_state.msaa_enabled() {
                 GX_PF_RGB565_Z16
             } else {
                 GX_PF_RGB8_Z24
@@ -674,7 +1111,7 @@ fn prepare_main_draw(width: u16, height: u16, game_state: &GameState, half: Opti
             GX_ZC_LINEAR as u8,
         );
         GX_SetCopyFilter(
-            game_state.msaa as u8,
+            game_state.msaa_enabled() as u8,
             TVNtsc480ProgAa.sample_pattern.as_ptr() as *mut [u8; 2],
             game_state.copy_filter as u8,
             TVNtsc480ProgSoft.vfilter.as_ptr() as *mut u8,
@@ -687,13 +1124,7 @@ fn prepare_main_draw(width: u16, height: u16, game_state: &GameState, half: Opti
 
         load_camera_proj_matrix(width, height, game_state, half);
 
-        let mut eye_offset = zeroed::<Mtx>();
-        c_guMtxTrans(
-            eye_offset.as_mut_ptr(),
-            -game_state.pos.x,
-            -game_state.pos.y,
-            -game_state.pos.z,
-        );
+        let mut eye_offset = Mtx34::translation(-game_state.eye());
         GX_LoadTexMtxImm(eye_offset.as_mut_ptr(), GX_TEXMTX0, GX_MTX3x4 as u8);
 
         let mut scale_and_bias = [[0.5, 0.0, 0.0, 0.5], [0.0, 0.5, 0.0, 0.5]];
@@ -704,15 +1135,17 @@ fn prepare_main_draw(width: u16, height: u16, game_state: &GameState, half: Opti
     }
 }
 
+/// The camera's near and far clip planes.
+const NEAR_Z: f32 = 1.0;
+const FAR_Z: f32 = 16384.0;
+
 fn load_camera_proj_matrix(width: u16, height: u16, game_state: &GameState, half: Option<bool>) {
     unsafe {
-        let mut proj = zeroed::<Mtx44>();
-        guPerspective(
-            proj.as_mut_ptr(),
+        let mut proj = Mtx44::perspective(
             90.0,
             width as f32 / height as f32 * game_state.widescreen_factor(),
-            1.0,
-            16384.0,
+            NEAR_Z,
+            FAR_Z,
         );
         GX_LoadProjectionMtx(proj.as_mut_ptr(), GX_PERSPECTIVE as u8);
 
@@ -734,118 +1167,130 @@ fn load_camera_proj_matrix(width: u16, height: u16, game_state: &GameState, half
     }
 }
 
+fn camera_view_matrix(game_state: &GameState) -> Mtx34 {
+    let eye = game_state.eye();
+    orient_view(
+        game_state,
+        Mtx34::look_at(
+            eye,
+            Vec3::new(0.0, 0.0, 1.0),
+            eye + Vec3::new(1.0, 0.0, 0.0),
+        ),
+    )
+}
+
 fn load_camera_view_matrix(game_state: &GameState) {
+    let mut view = camera_view_matrix(game_state);
     unsafe {
-        let mut look_at = zeroed::<Mtx>();
-        let mut yaw_rotation = zeroed::<Mtx>();
-        let mut pitch_rotation = zeroed::<Mtx>();
-        let mut tmp = zeroed::<Mtx>();
-        guLookAt(
-            look_at.as_mut_ptr(),
-            &mut guVector {
-                x: game_state.pos.x,
-                y: game_state.pos.y,
-                z: game_state.pos.z,
-            },
-            &mut guVector {
-                x: 0.0,
-                y: 0.0,
-                z: 1.0,
-            },
-            &mut guVector {
-                x: game_state.pos.x + 1.0,
-                y: game_state.pos.y,
-                z: game_state.pos.z,
-            },
-        );
-        c_guMtxRotRad(yaw_rotation.as_mut_ptr(), b'y', -game_state.yaw);
-        c_guMtxRotRad(pitch_rotation.as_mut_ptr(), b'x', -game_state.pitch);
-        c_guMtxConcat(
-            yaw_rotation.as_mut_ptr(),
-            look_at.as_mut_ptr(),
-            tmp.as_mut_ptr(),
-        );
-        let mut view = zeroed::<Mtx>();
-        c_guMtxConcat(
-            pitch_rotation.as_mut_ptr(),
-            tmp.as_mut_ptr(),
-            view.as_mut_ptr(),
-        );
         GX_LoadPosMtxImm(view.as_mut_ptr(), GX_PNMTX0);
     }
 }
 
 fn load_skybox_view_matrix(game_state: &GameState) {
+    let mut view = orient_view(
+        game_state,
+        Mtx34::look_at(
+            Vec3::zero(),
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(1.0, 0.0, 0.0),
+        ),
+    );
     unsafe {
-        let mut look_at = zeroed::<Mtx>();
-        let mut yaw_rotation = zeroed::<Mtx>();
-        let mut pitch_rotation = zeroed::<Mtx>();
-        let mut tmp = zeroed::<Mtx>();
-        guLookAt(
-            look_at.as_mut_ptr(),
-            &mut guVector {
-                x: 0.0,
-                y: 0.0,
-                z: 0.0,
-            },
-            &mut guVector {
-                x: 0.0,
-                y: 0.0,
-                z: 1.0,
-            },
-            &mut guVector {
-                x: 1.0,
-                y: 0.0,
-                z: 0.0,
-            },
-        );
-        c_guMtxRotRad(yaw_rotation.as_mut_ptr(), b'y', -game_state.yaw);
-        c_guMtxRotRad(pitch_rotation.as_mut_ptr(), b'x', -game_state.pitch);
-        c_guMtxConcat(
-            yaw_rotation.as_mut_ptr(),
-            look_at.as_mut_ptr(),
-            tmp.as_mut_ptr(),
-        );
-        let mut view = zeroed::<Mtx>();
-        c_guMtxConcat(
-            pitch_rotation.as_mut_ptr(),
-            tmp.as_mut_ptr(),
-            view.as_mut_ptr(),
-        );
         GX_LoadPosMtxImm(view.as_mut_ptr(), GX_PNMTX0);
     }
 }
 
+/// Applies the camera's yaw and pitch to a view matrix looking down +X.
+fn orient_view(game_state: &GameState, look_at: Mtx34) -> Mtx34 {
+    Mtx34::rotation_x(-game_state.pitch) * (Mtx34::rotation_y(-game_state.yaw) * look_at)
+}
+
 fn do_main_draw<Data: Deref<Target = [u8]>>(
+    gx: &mut Gx,
     map_data: &MapData<Data>,
     game_state: &GameState,
-    visibility: Visibility,
+    visibility: PackedVisibility,
+    occlusion_culler: &mut OcclusionCuller,
     skybox_texobjs: &[GXTexObj],
     cluster_lightmaps: &[Lightmap],
     displacement_lightmaps: &BTreeMap<u16, Lightmap>,
+    fill_metrics: &mut WorldFillMetrics,
 ) -> i16 {
-    draw_skybox(game_state, skybox_texobjs);
-    draw_displacements(map_data, game_state, displacement_lightmaps);
-    let view_cluster = draw_visible_clusters(map_data, game_state, cluster_lightmaps, visibility);
+    // Whatever is drawn before and after this sets its own blend and depth state, so the tracker
+    // only lives for the passes here.
+    let mut tracker = PipelineStateTracker::new();
+    game_state.fog.apply_skybox(gx, NEAR_Z, FAR_Z);
+    draw_skybox(gx, &mut tracker, game_state, skybox_texobjs);
+    game_state.fog.apply_world(gx, NEAR_Z, FAR_Z);
+    draw_displacements(
+        gx,
+        &mut tracker,
+        map_data,
+        game_state,
+        displacement_lightmaps,
+    );
+    let view_cluster = draw_visible_clusters(
+        gx,
+        &mut tracker,
+        map_data,
+        game_state,
+        cluster_lightmaps,
+        visibility,
+        occlusion_culler,
+        fill_metrics,
+    );
+    draw_props(
+        gx,
+        &mut tracker,
+        map_data,
+        game_state,
+        visibility,
+        occlusion_culler,
+        view_cluster,
+    );
+    draw_overlays(
+        gx,
+        &mut tracker,
+        map_data,
+        game_state,
+        visibility,
+        occlusion_culler,
+        view_cluster,
+    );
+    fog::disable(gx);
+    if game_state.occlusion_culling {
+        unsafe {
+            occlusion_culler.query(
+                gx,
+                map_data,
+                visibility,
+                view_cluster,
+                [game_state.pos.x, game_state.pos.y, game_state.pos.z],
+            );
+        }
+    }
     view_cluster
 }
 
+/// Clusters whose bounds are farther than this from the camera are drawn with their LOD geometry.
+const CLUSTER_LOD_DISTANCE: f32 = 2048.0;
+
 fn draw_visible_clusters<Data: Deref<Target = [u8]>>(
+    gx: &Gx,
+    tracker: &mut PipelineStateTracker<PassShader>,
     map_data: &MapData<Data>,
     game_state: &GameState,
     cluster_lightmaps: &[Lightmap],
-    visibility: Visibility,
+    visibility: PackedVisibility,
+    occlusion_culler: &OcclusionCuller,
+    fill_metrics: &mut WorldFillMetrics,
 ) -> i16 {
     unsafe {
-        GX_ClearVtxDesc();
-        GX_SetVtxDesc(GX_VA_POS as u8, GX_INDEX16 as u8);
-        GX_SetVtxDesc(GX_VA_NRM as u8, GX_INDEX16 as u8);
-        GX_SetVtxDesc(GX_VA_TEX0 as u8, GX_DIRECT as u8);
-        GX_SetVtxDesc(GX_VA_TEX1 as u8, GX_INDEX16 as u8);
-        GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_POS, GX_POS_XYZ, GX_F32, 0);
-        GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_NRM, GX_NRM_XYZ, GX_S8, 0);
-        GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_TEX0, GX_TEX_ST, GX_U16, 15);
-        GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_TEX1, GX_TEX_ST, GX_U16, 8);
+        apply_vertex_format(
+            map_data
+                .vertex_format_descriptor(VertexFormat::Brush)
+                .expect("map data lacks the brush vertex format"),
+        );
         GX_SetArray(GX_VA_POS, map_data.position_data().as_ptr() as *mut _, 12);
         GX_SetArray(GX_VA_NRM, map_data.normal_data().as_ptr() as *mut _, 3);
         GX_SetArray(
@@ -853,9 +1298,6 @@ fn draw_visible_clusters<Data: Deref<Target = [u8]>>(
             map_data.texture_coord_data().as_ptr() as *mut _,
             4,
         );
-        GX_InvVtxCache();
-
-        GX_SetZMode(GX_TRUE as u8, GX_LEQUAL as u8, GX_TRUE as u8);
 
         let view_leaf =
             map_data.traverse_bsp(&[game_state.pos.x, game_state.pos.y, game_state.pos.z]);
@@ -863,16 +1305,35 @@ fn draw_visible_clusters<Data: Deref<Target = [u8]>>(
 
         // Memoize some map data sections.
         let cluster_geometry_table = map_data.cluster_geometry_table();
+        let cluster_lod_table = map_data.cluster_lod_table();
         let cluster_geometry_byte_code = map_data.cluster_geometry_byte_code();
         let cluster_geometry_display_lists = map_data.cluster_geometry_display_lists();
 
-        let draw_cluster = move |cluster: u16, pass: usize| {
-            let cluster_geometry = cluster_geometry_table[cluster as usize];
+        let view_pos = [game_state.pos.x, game_state.pos.y, game_state.pos.z];
+
+        // Whether the prepass is skipping alpha tested draws. Like GX's alpha compare in the shaded
+        // passes, this carries over from one cluster to the next.
+        let skipping = &Cell::new(false);
+        let draw_cluster = move |cluster: u16, pass: usize, depth_mode: WorldDepthMode| {
+            if occlusion_culler.is_occluded(cluster) {
+                return;
+            }
+            // Swap in the simplified geometry for clusters that are far enough away.
+            let cluster_geometry = match cluster_lod_table.get(cluster as usize) {
+                Some(lod)
+                    if lod.distance_squared(view_pos)
+                        > CLUSTER_LOD_DISTANCE * CLUSTER_LOD_DISTANCE =>
+                {
+                    lod.geometry
+                }
+                _ => cluster_geometry_table[cluster as usize],
+            };
             // Bind the lightmap, but only if there's rendering to be done.
             if cluster_geometry.byte_code_index_ranges[pass][0]
                 != cluster_geometry.byte_code_index_ranges[pass][1]
             {
                 match cluster_lightmaps.get(cluster as usize) {
+                    Some(_) if depth_mode == WorldDepthMode::Prepass => (),
                     Some(lightmap) => {
                         GX_LoadTexObj(lightmap.texobj(), GX_TEXMAP0 as u8);
                     }
@@ -885,6 +1346,9 @@ fn draw_visible_clusters<Data: Deref<Target = [u8]>>(
                         display_list_offset,
                         display_list_size,
                     } => {
+                        if skipping.get() {
+                            continue;
+                        }
                         GX_CallDispList(
                             (cluster_geometry_display_lists.as_ptr() as *mut c_void)
                                 .offset(display_list_offset as isize),
@@ -901,6 +1365,11 @@ fn draw_visible_clusters<Data: Deref<Target = [u8]>>(
                         compare_type,
                         reference,
                     } => {
+                        let alpha_tested = compare_type != GX_ALWAYS as u8;
+                        if depth_mode == WorldDepthMode::Prepass {
+                            skipping.set(alpha_tested);
+                            continue;
+                        }
                         GX_SetZCompLoc(z_comp_before_tex);
                         GX_SetAlphaCompare(
                             compare_type,
@@ -909,260 +1378,378 @@ fn draw_visible_clusters<Data: Deref<Target = [u8]>>(
                             GX_ALWAYS as u8,
                             0,
                         );
+                        depth_mode.apply_depth_state(gx, alpha_tested);
                     }
                     BytecodeOp::SetFaceIndex { .. } => unreachable!(),
                 }
             }
         };
 
-        for pass in 0..6 {
-            if pass < 4 {
-                match pass & 0x1 {
-                    0 => LIGHTMAPPED_SHADER.apply(),
-                    1 => LIGHTMAPPED_BAAA_SHADER.apply(),
-                    _ => unreachable!(),
-                }
-            } else if pass == 4 {
-                UNLIT_GENERIC_SHADER.apply();
-            } else if pass == 5 {
-                SELF_ILLUM_SHADER.apply();
-            }
-
-            let blend = pass < 4 && (pass & 2) == 2;
-            if blend {
-                // Alpha blending.
-                GX_SetBlendMode(
-                    GX_BM_BLEND as u8,
-                    GX_BL_SRCALPHA as u8,
-                    GX_BL_INVSRCALPHA as u8,
-                    0,
-                );
-                GX_SetZMode(GX_TRUE as u8, GX_LEQUAL as u8, GX_FALSE as u8);
-            } else {
-                // Blending off.
-                GX_SetBlendMode(GX_BM_NONE as u8, 0, 0, 0);
-                GX_SetZMode(GX_TRUE as u8, GX_LEQUAL as u8, GX_TRUE as u8);
-            }
+        // Skip the extra TEV stage and vertex lighting when there's nothing to light.
+        let dynamic_lights = !game_state.dynamic_lights.is_empty();
+        if dynamic_lights {
+            game_state
+                .dynamic_lights
+                .load(&camera_view_matrix(game_state));
+        }
 
-            if view_cluster != -1 {
-                for cluster in visibility
-                    .get_cluster(ClusterIndex(view_cluster as usize))
+        let view_bitmap = usize::try_from(view_cluster)
+            .ok()
+            .and_then(|view_cluster| visibility.get_cluster(ClusterIndex(view_cluster)));
+        let draw_pass = |pass: usize, depth_mode: WorldDepthMode| {
+            if let Some(view_bitmap) = view_bitmap {
+                for cluster in view_bitmap
                     .iter_visible_clusters()
                     .map(|cluster| cluster.0 as u16)
                 {
-                    draw_cluster(cluster, pass);
+                    draw_cluster(cluster, pass, depth_mode);
                 }
             } else {
                 for cluster in 0..visibility.num_clusters() as u16 {
-                    draw_cluster(cluster, pass);
+                    draw_cluster(cluster, pass, depth_mode);
                 }
             }
+        };
+        let is_blended = |pass: usize| pass < 4 && (pass & 2) == 2;
+
+        // Debug render modes that override blending want to see every layer of overdraw.
+        let z_prepass = game_state.z_prepass && !game_state.debug_render_mode.overrides_blend();
+        if game_state.show_stats {
+            PixelCounts::clear(gx);
+        }
+        if z_prepass {
+            z_prepass::begin_prepass(gx);
+            tracker.invalidate();
+            for pass in (0..6).filter(|&pass| !is_blended(pass)) {
+                skipping.set(false);
+                draw_pass(pass, WorldDepthMode::Prepass);
+            }
+            z_prepass::end_prepass(gx);
+            if game_state.show_stats {
+                fill_metrics.add_prepass(PixelCounts::read(gx));
+                PixelCounts::clear(gx);
+            }
         }
 
-        GX_SetBlendMode(GX_BM_NONE as u8, 0, 0, 0);
-        GX_SetZMode(GX_TRUE as u8, GX_LEQUAL as u8, GX_TRUE as u8);
+        for pass in 0..6 {
+            let shader = if pass < 4 {
+                match (pass & 0x1, dynamic_lights) {
+                    (0, false) => &LIGHTMAPPED_SHADER,
+                    (1, false) => &LIGHTMAPPED_BAAA_SHADER,
+                    (0, true) => &LIGHTMAPPED_DYNAMIC_SHADER,
+                    (1, true) => &LIGHTMAPPED_BAAA_DYNAMIC_SHADER,
+                    _ => unreachable!(),
+                }
+            } else if pass == 4 {
+                &UNLIT_GENERIC_SHADER
+            } else {
+                &SELF_ILLUM_SHADER
+            };
+
+            let blend = is_blended(pass);
+            let after_prepass = z_prepass && !blend;
+            let mut state = if blend {
+                PipelineState::new(
+                    PassShader::Static(shader),
+                    BlendMode::Alpha,
+                    DepthMode::TRANSLUCENT,
+                )
+            } else if after_prepass {
+                PipelineState::new(
+                    PassShader::Static(shader),
+                    BlendMode::Opaque,
+                    DepthMode {
+                        compare: Some(DepthCompare::Equal),
+                        write: false,
+                    },
+                )
+            } else {
+                PipelineState::new(
+                    PassShader::Static(shader),
+                    BlendMode::Opaque,
+                    DepthMode::OPAQUE,
+                )
+            };
+            game_state
+                .debug_render_mode
+                .override_state(pass, &mut state);
+            tracker.submit(&state, &mut GxPipelineState::new(gx));
+
+            if after_prepass {
+                // Start from the same alpha compare the prepass did, so both skip the same draws.
+                GX_SetAlphaCompare(GX_ALWAYS as u8, 0, GX_AOP_OR as u8, GX_ALWAYS as u8, 0);
+                GX_SetZCompLoc(GX_TRUE as u8);
+                draw_pass(pass, WorldDepthMode::AfterPrepass);
+                // Alpha tested draws switch the depth test behind the tracker's back.
+                tracker.invalidate_groups(StateGroups {
+                    depth: true,
+                    ..StateGroups::default()
+                });
+            } else {
+                draw_pass(pass, WorldDepthMode::Normal);
+            }
+        }
+
+        if dynamic_lights {
+            DynamicLights::unload();
+        }
         GX_SetZCompLoc(GX_TRUE as u8);
         GX_SetAlphaCompare(GX_ALWAYS as u8, 0, GX_AOP_OR as u8, GX_ALWAYS as u8, 0);
+        if game_state.show_stats {
+            fill_metrics.add_shaded(PixelCounts::read(gx));
+        }
 
         view_cluster
     }
 }
 
-fn draw_skybox(game_state: &GameState, skybox_texobjs: &[GXTexObj]) {
+/// Corners of each skybox face in the order the 2D skybox textures are loaded, wound to match
+/// texture coordinates (0, 0), (1, 0), (1, 1), (0, 1). This is `SkyboxFace::DRAWN` from
+/// source-reader, whose `corners()` this scales up. There's no -Z face.
+const SKYBOX_FACE_POSITIONS: [[[i8; 3]; 4]; 5] = [
+    // +X face.
+    [[10, 10, 10], [10, -10, 10], [10, -10, -10], [10, 10, -10]],
+    // -X face.
+    [
+        [-10, -10, 10],
+        [-10, 10, 10],
+        [-10, 10, -10],
+        [-10, -10, -10],
+    ],
+    // +Y face.
+    [[-10, 10, 10], [10, 10, 10], [10, 10, -10], [-10, 10, -10]],
+    // -Y face.
+    [
+        [10, -10, 10],
+        [-10, -10, 10],
+        [-10, -10, -10],
+        [10, -10, -10],
+    ],
+    // +Z face.
+    [[-10, 10, 10], [-10, -10, 10], [10, -10, 10], [10, 10, 10]],
+];
+
+fn draw_skybox(
+    gx: &mut Gx,
+    tracker: &mut PipelineStateTracker<PassShader>,
+    game_state: &GameState,
+    skybox_texobjs: &[GXTexObj],
+) {
     unsafe {
-        GX_ClearVtxDesc();
-        GX_SetVtxDesc(GX_VA_POS as u8, GX_DIRECT as u8);
-        GX_SetVtxDesc(GX_VA_TEX0 as u8, GX_DIRECT as u8);
-        GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_POS, GX_POS_XYZ, GX_S8, 0);
-        GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_TEX0, GX_TEX_ST, GX_U8, 0);
-        GX_InvVtxCache();
+        apply_vertex_format(&VertexFormat::Skybox.descriptor());
 
         load_skybox_view_matrix(game_state);
 
-        GX_SetZMode(GX_FALSE as u8, GX_ALWAYS as u8, GX_FALSE as u8);
         GX_SetColorUpdate(GX_TRUE as u8);
 
-        FLAT_TEXTURED_SHADER.apply();
-
-        // +X face.
-        GX_LoadTexObj(
-            &skybox_texobjs[0] as *const GXTexObj as *mut GXTexObj,
-            GX_TEXMAP0 as u8,
+        let mut state = PipelineState::new(
+            PassShader::Static(&FLAT_TEXTURED_SHADER),
+            BlendMode::Opaque,
+            DepthMode::DISABLED,
         );
-        GX_Begin(GX_QUADS as u8, GX_VTXFMT0 as u8, 4);
-        {
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).U8 = 0;
-            (*wgPipe).U8 = 0;
-
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).U8 = 1;
-            (*wgPipe).U8 = 0;
-
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).U8 = 1;
-            (*wgPipe).U8 = 1;
-
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).U8 = 0;
-            (*wgPipe).U8 = 1;
+        game_state
+            .debug_render_mode
+            .override_state(SKYBOX_PASS, &mut state);
+        tracker.submit(&state, &mut GxPipelineState::new(gx));
+
+        for (texobj, positions) in skybox_texobjs.iter().zip(SKYBOX_FACE_POSITIONS) {
+            GX_LoadTexObj(texobj as *const GXTexObj as *mut GXTexObj, GX_TEXMAP0 as u8);
+            let tex0 = [[0, 0], [1, 0], [1, 1], [0, 1]];
+            gx.quad([0, 1, 2, 3].map(|i| SkyboxVertex {
+                pos: positions[i],
+                tex0: tex0[i],
+            }));
         }
+    }
+}
 
-        // -X face.
-        GX_LoadTexObj(
-            &skybox_texobjs[1] as *const GXTexObj as *mut GXTexObj,
-            GX_TEXMAP0 as u8,
+/// Draws static props for the visible clusters. Props are baked into world space with their
+/// lighting in vertex colors, so they share the overlay vertex format and shaders but draw opaque.
+fn draw_props<Data: Deref<Target = [u8]>>(
+    gx: &Gx,
+    tracker: &mut PipelineStateTracker<PassShader>,
+    map_data: &MapData<Data>,
+    game_state: &GameState,
+    visibility: PackedVisibility,
+    occlusion_culler: &OcclusionCuller,
+    view_cluster: i16,
+) {
+    unsafe {
+        apply_vertex_format(
+            map_data
+                .vertex_format_descriptor(VertexFormat::Overlay)
+                .expect("map data lacks the overlay vertex format"),
         );
-        GX_Begin(GX_QUADS as u8, GX_VTXFMT0 as u8, 4);
-        {
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).U8 = 0;
-            (*wgPipe).U8 = 0;
-
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).U8 = 1;
-            (*wgPipe).U8 = 0;
-
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).U8 = 1;
-            (*wgPipe).U8 = 1;
-
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).U8 = 0;
-            (*wgPipe).U8 = 1;
-        }
 
-        // +Y face.
-        GX_LoadTexObj(
-            &skybox_texobjs[2] as *const GXTexObj as *mut GXTexObj,
-            GX_TEXMAP0 as u8,
-        );
-        GX_Begin(GX_QUADS as u8, GX_VTXFMT0 as u8, 4);
-        {
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).U8 = 0;
-            (*wgPipe).U8 = 0;
-
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).U8 = 1;
-            (*wgPipe).U8 = 0;
-
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).U8 = 1;
-            (*wgPipe).U8 = 1;
-
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).U8 = 0;
-            (*wgPipe).U8 = 1;
-        }
+        let prop_table = map_data.prop_table();
+        let prop_byte_code = map_data.prop_byte_code();
+        let prop_display_lists = map_data.prop_display_lists();
 
-        // -Y face.
-        GX_LoadTexObj(
-            &skybox_texobjs[3] as *const GXTexObj as *mut GXTexObj,
-            GX_TEXMAP0 as u8,
-        );
-        GX_Begin(GX_QUADS as u8, GX_VTXFMT0 as u8, 4);
-        {
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).U8 = 0;
-            (*wgPipe).U8 = 0;
-
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).U8 = 1;
-            (*wgPipe).U8 = 0;
-
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).U8 = 1;
-            (*wgPipe).U8 = 1;
-
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).U8 = 0;
-            (*wgPipe).U8 = 1;
+        let draw_cluster = |cluster: u16, mode: usize| {
+            if occlusion_culler.is_occluded(cluster) {
+                return;
+            }
+            let entry = match prop_table.get(cluster as usize) {
+                Some(entry) => entry,
+                None => return,
+            };
+            for op in entry.iter_display_lists(prop_byte_code, mode) {
+                match op {
+                    BytecodeOp::Draw {
+                        display_list_offset,
+                        display_list_size,
+                    } => {
+                        GX_CallDispList(
+                            (prop_display_lists.as_ptr() as *mut c_void)
+                                .offset(display_list_offset as isize),
+                            display_list_size,
+                        );
+                    }
+                    _ => unreachable!(),
+                }
+            }
+        };
+
+        for mode in 0..2 {
+            let shader = match mode {
+                0 => &OVERLAY_SHADER,
+                1 => &OVERLAY_BAAA_SHADER,
+                _ => unreachable!(),
+            };
+            let mut state = PipelineState::new(
+                PassShader::Static(shader),
+                BlendMode::Opaque,
+                DepthMode::OPAQUE,
+            );
+            game_state
+                .debug_render_mode
+                .override_state(PROP_PASS_BASE + mode, &mut state);
+            tracker.submit(&state, &mut GxPipelineState::new(gx));
+
+            let view_bitmap = usize::try_from(view_cluster)
+                .ok()
+                .and_then(|view_cluster| visibility.get_cluster(ClusterIndex(view_cluster)));
+            if let Some(view_bitmap) = view_bitmap {
+                for cluster in view_bitmap
+                    .iter_visible_clusters()
+                    .map(|cluster| cluster.0 as u16)
+                {
+                    draw_cluster(cluster, mode);
+                }
+            } else {
+                for cluster in 0..visibility.num_clusters() as u16 {
+                    draw_cluster(cluster, mode);
+                }
+            }
         }
+    }
+}
+
+/// Overlays are pulled toward the camera by this fraction of the depth range so they win the depth
+/// test against the faces they were projected onto. GX has no polygon offset, so this narrows the
+/// viewport's depth range instead.
+const OVERLAY_DEPTH_BIAS: f32 = 1.0 / 65536.0;
 
-        // +Z face.
-        GX_LoadTexObj(
-            &skybox_texobjs[4] as *const GXTexObj as *mut GXTexObj,
-            GX_TEXMAP0 as u8,
+/// Draws overlays and decals for the visible clusters, after all of the world geometry they sit on.
+fn draw_overlays<Data: Deref<Target = [u8]>>(
+    gx: &Gx,
+    tracker: &mut PipelineStateTracker<PassShader>,
+    map_data: &MapData<Data>,
+    game_state: &GameState,
+    visibility: PackedVisibility,
+    occlusion_culler: &OcclusionCuller,
+    view_cluster: i16,
+) {
+    unsafe {
+        apply_vertex_format(
+            map_data
+                .vertex_format_descriptor(VertexFormat::Overlay)
+                .expect("map data lacks the overlay vertex format"),
         );
-        GX_Begin(GX_QUADS as u8, GX_VTXFMT0 as u8, 4);
-        {
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).U8 = 0;
-            (*wgPipe).U8 = 0;
-
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).U8 = 1;
-            (*wgPipe).U8 = 0;
-
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = -10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).U8 = 1;
-            (*wgPipe).U8 = 1;
-
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).S8 = 10;
-            (*wgPipe).U8 = 0;
-            (*wgPipe).U8 = 1;
+
+        GX_SetViewport(0.0, 0.0, 640.0, 480.0, 0.0, 1.0 - OVERLAY_DEPTH_BIAS);
+
+        let overlay_table = map_data.overlay_table();
+        let overlay_byte_code = map_data.overlay_byte_code();
+        let overlay_display_lists = map_data.overlay_display_lists();
+
+        let draw_cluster = |cluster: u16, mode: usize| {
+            if occlusion_culler.is_occluded(cluster) {
+                return;
+            }
+            let entry = match overlay_table.get(cluster as usize) {
+                Some(entry) => entry,
+                None => return,
+            };
+            for op in entry.iter_display_lists(overlay_byte_code, mode) {
+                match op {
+                    BytecodeOp::Draw {
+                        display_list_offset,
+                        display_list_size,
+                    } => {
+                        GX_CallDispList(
+                            (overlay_display_lists.as_ptr() as *mut c_void)
+                                .offset(display_list_offset as isize),
+                            display_list_size,
+                        );
+                    }
+                    _ => unreachable!(),
+                }
+            }
+        };
+
+        for mode in 0..2 {
+            let shader = match mode {
+                0 => &OVERLAY_SHADER,
+                1 => &OVERLAY_BAAA_SHADER,
+                _ => unreachable!(),
+            };
+            let mut state = PipelineState::new(
+                PassShader::Static(shader),
+                BlendMode::Alpha,
+                DepthMode::TRANSLUCENT,
+            );
+            game_state
+                .debug_render_mode
+                .override_state(OVERLAY_PASS_BASE + mode, &mut state);
+            tracker.submit(&state, &mut GxPipelineState::new(gx));
+
+            let view_bitmap = usize::try_from(view_cluster)
+                .ok()
+                .and_then(|view_cluster| visibility.get_cluster(ClusterIndex(view_cluster)));
+            if let Some(view_bitmap) = view_bitmap {
+                for cluster in view_bitmap
+                    .iter_visible_clusters()
+                    .map(|cluster| cluster.0 as u16)
+                {
+                    draw_cluster(cluster, mode);
+                }
+            } else {
+                for cluster in 0..visibility.num_clusters() as u16 {
+                    draw_cluster(cluster, mode);
+                }
+            }
         }
+
+        GX_SetViewport(0.0, 0.0, 640.0, 480.0, 0.0, 1.0);
+        GX_SetBlendMode(GX_BM_NONE as u8, 0, 0, 0);
+        GX_SetZMode(GX_TRUE as u8, GX_LEQUAL as u8, GX_TRUE as u8);
     }
 }
 
 fn draw_displacements<Data: Deref<Target = [u8]>>(
+    gx: &Gx,
+    tracker: &mut PipelineStateTracker<PassShader>,
     map_data: &MapData<Data>,
     game_state: &GameState,
     displacement_lightmaps: &BTreeMap<u16, Lightmap>,
 ) {
     unsafe {
-        GX_ClearVtxDesc();
-        GX_SetVtxDesc(GX_VA_POS as u8, GX_INDEX16 as u8);
-        GX_SetVtxDesc(GX_VA_CLR0 as u8, GX_INDEX16 as u8);
-        GX_SetVtxDesc(GX_VA_TEX0 as u8, GX_DIRECT as u8);
-        GX_SetVtxDesc(GX_VA_TEX1 as u8, GX_INDEX16 as u8);
-        GX_SetVtxDesc(GX_VA_TEX2 as u8, GX_INDEX16 as u8);
-        GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_POS, GX_POS_XYZ, GX_F32, 0);
-        GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_CLR0, GX_CLR_RGB, GX_RGB8, 0);
-        GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_TEX0, GX_TEX_ST, GX_U16, 15);
-        GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_TEX1, GX_TEX_ST, GX_U16, 8);
-        GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_TEX2, GX_TEX_ST, GX_U16, 8);
+        apply_vertex_format(
+            map_data
+                .vertex_format_descriptor(VertexFormat::Displacement)
+                .expect("map data lacks the displacement vertex format"),
+        );
         GX_SetArray(
             GX_VA_POS,
             map_data.displacement_position_data().as_ptr() as *mut _,
@@ -1183,13 +1770,9 @@ fn draw_displacements<Data: Deref<Target = [u8]>>(
             map_data.displacement_texture_coordinate_data().as_ptr() as *mut _,
             4,
         );
-        GX_InvVtxCache();
 
         load_camera_view_matrix(game_state);
 
-        GX_SetBlendMode(GX_BM_NONE as u8, 0, 0, 0);
-        GX_SetZMode(GX_TRUE as u8, GX_LEQUAL as u8, GX_TRUE as u8);
-
         let displacement_byte_code = map_data.displacement_byte_code();
         let displacement_display_lists = map_data.displacement_display_lists();
 
@@ -1197,11 +1780,20 @@ fn draw_displacements<Data: Deref<Target = [u8]>>(
         for (mode, entry) in map_data.displacement_table().iter().enumerate() {
             if prev_mode != Some(mode) {
                 prev_mode = Some(mode);
-                match mode {
-                    0 => LIGHTMAPPED_SHADER.apply(),
-                    1 => WORLD_VERTEX_TRANSITION_SHADER.apply(),
+                let shader = match mode {
+                    0 => &LIGHTMAPPED_SHADER,
+                    1 => &WORLD_VERTEX_TRANSITION_SHADER,
                     _ => unreachable!(),
-                }
+                };
+                let mut state = PipelineState::new(
+                    PassShader::Static(shader),
+                    BlendMode::Opaque,
+                    DepthMode::OPAQUE,
+                );
+                game_state
+                    .debug_render_mode
+                    .override_state(DISPLACEMENT_PASS_BASE + mode, &mut state);
+                tracker.submit(&state, &mut GxPipelineState::new(gx));
             }
 
             for op in BytecodeReader::new(
@@ -1232,7 +1824,7 @@ fn draw_displacements<Data: Deref<Target = [u8]>>(
     }
 }
 
-fn _do_copy_to_texture(screen_texture_color_data: &Vec<u8, GlobalAlign32>) {
+fn _do_copy_to_texture(gx: &mut Gx, screen_texture_color_data: &Vec<u8, GlobalAlign32>) {
     unsafe {
         // Copy the color buffer to a texture in main memory.
         GX_SetTexCopySrc(0, 0, 640, 480); // TODO: Use the current mode.
@@ -1254,44 +1846,23 @@ fn _do_copy_to_texture(screen_texture_color_data: &Vec<u8, GlobalAlign32>) {
 
         GX_InvalidateTexAll();
 
-        GX_ClearVtxDesc();
-        GX_SetVtxDesc(GX_VA_POS as u8, GX_DIRECT as u8);
-        GX_SetVtxDesc(GX_VA_TEX0 as u8, GX_DIRECT as u8);
-        GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_POS, GX_POS_XY, GX_U16, 0);
-        GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_TEX0, GX_TEX_ST, GX_U8, 0);
+        apply_vertex_format(&VertexFormat::DebugTexture.descriptor());
 
         GX_SetZMode(GX_FALSE as u8, GX_ALWAYS as u8, GX_FALSE as u8);
         GX_SetColorUpdate(GX_TRUE as u8);
 
-        let mut proj = zeroed::<Mtx44>();
-        guOrtho(proj.as_mut_ptr(), 0.0, 1.0, 0.0, 1.0, -1.0, 1.0);
+        let mut proj = Mtx44::ortho(0.0, 1.0, 0.0, 1.0, -1.0, 1.0);
         GX_LoadProjectionMtx(proj.as_mut_ptr(), GX_ORTHOGRAPHIC as u8);
 
-        let mut view = zeroed::<Mtx>();
-        c_guMtxIdentity(view.as_mut_ptr());
+        let mut view = Mtx34::identity();
         GX_LoadPosMtxImm(view.as_mut_ptr(), GX_PNMTX0);
 
-        GX_Begin(GX_QUADS as u8, GX_VTXFMT0 as u8, 4);
-
-        (*wgPipe).U16 = 0;
-        (*wgPipe).U16 = 0;
-        (*wgPipe).U8 = 0;
-        (*wgPipe).U8 = 0;
-
-        (*wgPipe).U16 = 1;
-        (*wgPipe).U16 = 0;
-        (*wgPipe).U8 = 1;
-        (*wgPipe).U8 = 0;
-
-        (*wgPipe).U16 = 1;
-        (*wgPipe).U16 = 1;
-        (*wgPipe).U8 = 1;
-        (*wgPipe).U8 = 1;
-
-        (*wgPipe).U16 = 0;
-        (*wgPipe).U16 = 1;
-        (*wgPipe).U8 = 0;
-        (*wgPipe).U8 = 1;
+        gx.quad(
+            [(0, 0), (1, 0), (1, 1), (0, 1)].map(|(x, y)| DebugTextureVertex {
+                pos: [x, y],
+                tex0: [x as u8, y as u8],
+            }),
+        );
     }
 }
 
@@ -1411,10 +1982,11 @@ struct PerformanceMetrics {
     vcache_metric_check: u32,
     vcache_metric_miss: u32,
     vcache_metric_stall: u32,
+    world_fill: WorldFillMetrics,
 }
 
 impl PerformanceMetrics {
-    fn read() -> Self {
+    fn read(world_fill: WorldFillMetrics) -> Self {
         unsafe {
             let cp = gamecube_peripheral_access::CP::PTR;
             let gp_a = ((*cp).xf_rasbusy_h.read().bits() as u32) << 16
@@ -1442,6 +2014,7 @@ impl PerformanceMetrics {
                 vcache_metric_check,
                 vcache_metric_miss,
                 vcache_metric_stall,
+                world_fill,
                 // clks_per_vtx_in: 0,
                 // clks_per_vtx_out: 0,
             }
@@ -1450,6 +2023,7 @@ impl PerformanceMetrics {
 }
 
 fn do_debug_draw(
+    gx: &mut Gx,
     width: u16,
     height: u16,
     game_state: &GameState,
@@ -1461,65 +2035,36 @@ fn do_debug_draw(
     last_frame_frames: usize,
 ) {
     unsafe {
-        GX_ClearVtxDesc();
-        GX_SetVtxDesc(GX_VA_POS as u8, GX_DIRECT as u8);
-        GX_SetVtxDesc(GX_VA_CLR0 as u8, GX_DIRECT as u8);
-        GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_POS, GX_POS_XY, GX_U16, 0);
-        GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_CLR0, GX_CLR_RGB, GX_RGB8, 0);
-        GX_InvVtxCache();
+        apply_vertex_format(&VertexFormat::DebugColor.descriptor());
 
         FLAT_VERTEX_COLOR_SHADER.apply();
 
         GX_SetZMode(GX_FALSE as u8, GX_ALWAYS as u8, GX_FALSE as u8);
         GX_SetColorUpdate(GX_TRUE as u8);
 
-        let mut proj = zeroed::<Mtx44>();
-        guOrtho(
-            proj.as_mut_ptr(),
-            0.0,
-            height as f32,
-            0.0,
-            width as f32,
-            -1.0,
-            1.0,
-        );
+        let mut proj = Mtx44::ortho(0.0, height as f32, 0.0, width as f32, -1.0, 1.0);
         GX_LoadProjectionMtx(proj.as_mut_ptr(), GX_ORTHOGRAPHIC as u8);
 
-        let mut view = zeroed::<Mtx>();
-        c_guMtxIdentity(view.as_mut_ptr());
+        let mut view = Mtx34::identity();
         GX_LoadPosMtxImm(view.as_mut_ptr(), GX_PNMTX0);
 
         let to_y = height - 16;
         let from_y = to_y - 16;
-        let emit_debug_quad = |from_x, to_x, max_x, r, g, b| {
+        let mut emit_debug_quad = |from_x, to_x, max_x, r, g, b| {
             let from_x = (from_x as f32 * width as f32 / max_x as f32) as u16;
             let to_x = (to_x as f32 * width as f32 / max_x as f32) as u16;
-
-            GX_Begin(GX_QUADS as u8, GX_VTXFMT0 as u8, 4);
-
-            (*wgPipe).U16 = from_x;
-            (*wgPipe).U16 = from_y;
-            (*wgPipe).U8 = r;
-            (*wgPipe).U8 = g;
-            (*wgPipe).U8 = b;
-
-            (*wgPipe).U16 = to_x;
-            (*wgPipe).U16 = from_y;
-            (*wgPipe).U8 = r;
-            (*wgPipe).U8 = g;
-            (*wgPipe).U8 = b;
-
-            (*wgPipe).U16 = to_x;
-            (*wgPipe).U16 = to_y;
-            (*wgPipe).U8 = r;
-            (*wgPipe).U8 = g;
-            (*wgPipe).U8 = b;
-
-            (*wgPipe).U16 = from_x;
-            (*wgPipe).U16 = to_y;
-            (*wgPipe).U8 = r;
-            (*wgPipe).U8 = g;
-            (*wgPipe).U8 = b;
+            gx.quad(
+                [
+                    (from_x, from_y),
+                    (to_x, from_y),
+                    (to_x, to_y),
+                    (from_x, to_y),
+                ]
+                .map(|(x, y)| DebugColorVertex {
+                    pos: [x, y],
+                    clr0: [r, g, b],
+                }),
+            );
         };
 
         let x0 = 0;
@@ -1537,36 +2082,14 @@ fn do_debug_draw(
         emit_debug_quad(x4, x5, x6, 0, 0, 255);
         emit_debug_quad(x5, x6, x6, 255, 0, 255);
 
-        let draw_bit = |x0, y0, bit| {
+        let mut draw_bit = |x0, y0, bit| {
             let x1 = x0 + 16;
             let y1 = y0 + 16;
-            let (r, g, b) = if bit { (0, 255, 0) } else { (0, 64, 0) };
-
-            GX_Begin(GX_QUADS as u8, GX_VTXFMT0 as u8, 4);
-
-            (*wgPipe).U16 = x0;
-            (*wgPipe).U16 = y0;
-            (*wgPipe).U8 = r;
-            (*wgPipe).U8 = g;
-            (*wgPipe).U8 = b;
-
-            (*wgPipe).U16 = x1;
-            (*wgPipe).U16 = y0;
-            (*wgPipe).U8 = r;
-            (*wgPipe).U8 = g;
-            (*wgPipe).U8 = b;
-
-            (*wgPipe).U16 = x1;
-            (*wgPipe).U16 = y1;
-            (*wgPipe).U8 = r;
-            (*wgPipe).U8 = g;
-            (*wgPipe).U8 = b;
-
-            (*wgPipe).U16 = x0;
-            (*wgPipe).U16 = y1;
-            (*wgPipe).U8 = r;
-            (*wgPipe).U8 = g;
-            (*wgPipe).U8 = b;
+            let clr0 = if bit { [0, 255, 0] } else { [0, 64, 0] };
+            gx.quad(
+                [(x0, y0), (x1, y0), (x1, y1), (x0, y1)]
+                    .map(|(x, y)| DebugColorVertex { pos: [x, y], clr0 }),
+            );
         };
         draw_bit(16, 16, view_cluster != -1);
         draw_bit(16, 0, (game_state.lightmap_style & 2) != 0);
@@ -1577,12 +2100,7 @@ fn do_debug_draw(
             let w = GX_GetTexObjWidth(lightmap.texobj());
             let h = GX_GetTexObjHeight(lightmap.texobj());
 
-            GX_ClearVtxDesc();
-            GX_SetVtxDesc(GX_VA_POS as u8, GX_DIRECT as u8);
-            GX_SetVtxDesc(GX_VA_TEX0 as u8, GX_DIRECT as u8);
-            GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_POS, GX_POS_XY, GX_U16, 0);
-            GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_TEX0, GX_TEX_ST, GX_U8, 0);
-            GX_InvVtxCache();
+            apply_vertex_format(&VertexFormat::DebugTexture.descriptor());
 
             FLAT_TEXTURED_SHADER.apply();
 
@@ -1606,85 +2124,92 @@ fn do_debug_draw(
                 GX_LoadTexObj(&mut dst, GX_TEXMAP0 as u8);
             }
 
-            GX_Begin(GX_QUADS as u8, GX_VTXFMT0 as u8, 4);
-
-            (*wgPipe).U16 = 16;
-            (*wgPipe).U16 = 16;
-            (*wgPipe).U8 = 0;
-            (*wgPipe).U8 = 0;
-
-            (*wgPipe).U16 = 16 + w;
-            (*wgPipe).U16 = 16;
-            (*wgPipe).U8 = 1;
-            (*wgPipe).U8 = 0;
-
-            (*wgPipe).U16 = 16 + w;
-            (*wgPipe).U16 = 16 + h;
-            (*wgPipe).U8 = 1;
-            (*wgPipe).U8 = 1;
-
-            (*wgPipe).U16 = 16;
-            (*wgPipe).U16 = 16 + h;
-            (*wgPipe).U8 = 0;
-            (*wgPipe).U8 = 1;
+            gx.quad(
+                [(0, 0), (1, 0), (1, 1), (0, 1)].map(|(s, t)| DebugTextureVertex {
+                    pos: [16 + s * w, 16 + t * h],
+                    tex0: [s as u8, t as u8],
+                }),
+            );
         }
 
         // Draw some  T E X T
         TextRenderer::prepare(ui_font);
         let mut r = TextRenderer {
             x: 16,
-            y: 480 - 15 * 16,
+            y: 480 - 20 * 16,
             left_margin: 16,
         };
-        let buf = format!(
-            "At ({}, {}, {}) yaw={} pitch={}\n\
-             {} MSAA: {}\n\
-             {} Copy filter: {}\n\
-             {} Lightmap style: {}\n\
-             {} GP perf metric 0: {:?}\n\
-             {} GP perf metric 1: {:?}\n\
-             gp_a: {}\n\
-             gp_b: {}\n\
-             gp_c: {}\n\
-             gp_d: {}\n\
-             vcache_metric_check: {}\n\
-             vcache_metric_miss: {}\n\
-             vcache_metric_stall: {}\n",
-            game_state.pos.x.round(),
-            game_state.pos.y.round(),
-            game_state.pos.z.round(),
-            game_state.yaw,
-            game_state.pitch,
-            if game_state.ui_item == 0 { "->" } else { "  " },
-            game_state.msaa,
-            if game_state.ui_item == 1 { "->" } else { "  " },
-            game_state.copy_filter,
-            if game_state.ui_item == 2 { "->" } else { "  " },
-            game_state.lightmap_style,
-            if game_state.ui_item == 3 { "->" } else { "  " },
-            game_state.gp_perf_metric0,
-            if game_state.ui_item == 4 { "->" } else { "  " },
-            game_state.gp_perf_metric1,
-            performance_metrics.gp_a,
-            performance_metrics.gp_b,
-            performance_metrics.gp_c,
-            performance_metrics.gp_d,
-            performance_metrics.vcache_metric_check,
-            performance_metrics.vcache_metric_miss,
-            performance_metrics.vcache_metric_stall,
-        );
-        r.draw_str(buf.as_bytes());
+        if game_state.show_stats {
+            let buf = format!(
+                "At ({}, {}, {}) yaw={} pitch={}\n\
+                 {} MSAA: {}\n\
+                 {} Copy filter: {}\n\
+                 {} Lightmap style: {}\n\
+                 {} GP perf metric 0: {:?}\n\
+                 {} GP perf metric 1: {:?}\n\
+                 {} Debug render mode: {:?}\n\
+                 {} Stereo: {:?}\n\
+                 {} Z prepass: {}\n\
+                 gp_a: {}\n\
+                 gp_b: {}\n\
+                 gp_c: {}\n\
+                 gp_d: {}\n\
+                 vcache_metric_check: {}\n\
+                 vcache_metric_miss: {}\n\
+                 vcache_metric_stall: {}\n\
+                 prepass pixels: z {} -> {}\n\
+                 shaded pixels: z {} -> {}, color {}\n\
+                 GPU hangs recovered: {}\n",
+                game_state.pos.x.round(),
+                game_state.pos.y.round(),
+                game_state.pos.z.round(),
+                game_state.yaw,
+                game_state.pitch,
+                if game_state.ui_item == 0 { "->" } else { "  " },
+                game_state.msaa,
+                if game_state.ui_item == 1 { "->" } else { "  " },
+                game_state.copy_filter,
+                if game_state.ui_item == 2 { "->" } else { "  " },
+                game_state.lightmap_style,
+                if game_state.ui_item == 3 { "->" } else { "  " },
+                game_state.gp_perf_metric0,
+                if game_state.ui_item == 4 { "->" } else { "  " },
+                game_state.gp_perf_metric1,
+                if game_state.ui_item == 5 { "->" } else { "  " },
+                game_state.debug_render_mode,
+                if game_state.ui_item == 6 { "->" } else { "  " },
+                game_state.stereo_mode,
+                if game_state.ui_item == 7 { "->" } else { "  " },
+                game_state.z_prepass,
+                performance_metrics.gp_a,
+                performance_metrics.gp_b,
+                performance_metrics.gp_c,
+                performance_metrics.gp_d,
+                performance_metrics.vcache_metric_check,
+                performance_metrics.vcache_metric_miss,
+                performance_metrics.vcache_metric_stall,
+                performance_metrics.world_fill.prepass.z_in,
+                performance_metrics.world_fill.prepass.z_out,
+                performance_metrics.world_fill.shaded.z_in,
+                performance_metrics.world_fill.shaded.z_out,
+                performance_metrics.world_fill.shaded.color_in,
+                gpu_watchdog::recovery_count(),
+            );
+            r.draw_str(gx, buf.as_bytes());
+        }
         r.x = 640 - 24;
         r.y = 480 - 28;
         let buf = format!("{}", last_frame_frames);
-        r.draw_str(buf.as_bytes());
+        r.draw_str(gx, buf.as_bytes());
+
+        game_state.console.draw(gx, width, ui_font);
     }
 }
 
 fn init_for_console() -> (*mut GXRModeObj, u16, u16) {
     unsafe {
         VIDEO_Init();
-        PAD_Init();
+        Input::init();
 
         // Configure the preferred video mode.
         // let rmode = VIDEO_GetPreferredMode(null_mut());
@@ -1735,16 +2260,6 @@ fn init_for_3d(rmode: &GXRModeObj) {
         drop(VIDEO_SetPreRetraceCallback(Some(pre_retrace_callback)));
         drop(VIDEO_SetPostRetraceCallback(None));
 
-        // Allocate a FIFO for sending commands to the GPU.
-        let gp_fifo = GP_FIFO.load(Ordering::Acquire);
-        if gp_fifo.is_null() {
-            const FIFO_SIZE: usize = 512 * 1024;
-            let gp_fifo = MEM_K0_TO_K1(libc::memalign(32, FIFO_SIZE));
-            GP_FIFO.store(gp_fifo, Ordering::Release);
-            libc::memset(gp_fifo, 0, FIFO_SIZE);
-            GX_Init(gp_fifo, FIFO_SIZE as u32);
-        }
-
         GX_SetCopyClear(
             GXColor {
                 r: 0x80,
@@ -1841,11 +2356,12 @@ struct FrameTimers {
 }
 
 extern "C" fn pre_retrace_callback(_count: u32) {
-    FRAMES.fetch_add(1, Ordering::AcqRel);
+    let frames = FRAMES.fetch_add(1, Ordering::AcqRel) + 1;
 
-    if DO_COPY
-        .compare_exchange(true, false, Ordering::AcqRel, Ordering::Acquire)
-        .is_ok()
+    if frames >= frame_pacing::flip_interval()
+        && DO_COPY
+            .compare_exchange(true, false, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
     {
         // Swap buffers.
         let next_xfb_back = XFB_FRONT.load(Ordering::Acquire);
@@ -1888,29 +2404,3 @@ fn copy_disp(half: Option<bool>) {
         GX_Flush();
     }
 }
-
-fn get_processed_stick(pad: i32, c: bool) -> (f32, f32) {
-    let dx = if c {
-        unsafe { PAD_SubStickX(pad) }
-    } else {
-        unsafe { PAD_StickX(0) }
-    } as f32
-        / 127.0;
-    let dy = if c {
-        unsafe { PAD_SubStickY(pad) }
-    } else {
-        unsafe { PAD_StickY(0) }
-    } as f32
-        / 127.0;
-    let d = libm::sqrtf(dx * dx + dy * dy);
-    if d < 0.2 {
-        (0.0, 0.0)
-    } else if d < 0.9 {
-        let goal = (d - 0.1) * (1.0 / 0.8);
-        let scale = goal / d;
-        (dx * scale, dy * scale)
-    } else {
-        let scale = 1.0 / d;
-        (dx * scale, dy * scale)
-    }
-}
@@ -64,6 +64,7 @@ impl Drop for Socket {
     }
 }
 
+#[derive(Clone, Copy)]
 #[repr(transparent)]
 pub struct SocketAddr(sockaddr_in);
 
@@ -0,0 +1,186 @@
+//! Coarse per-cluster occlusion culling.
+//!
+//! After the main draw, each cluster the PVS let through has its bounding box drawn against the
+//! depth buffer with color, alpha, and depth writes off. The PE counts the pixels that pass the
+//! early depth test, and a box with none is hidden behind what was already drawn. Those clusters
+//! are skipped on the next frame.
+//!
+//! Reading the counter means waiting for GX to go idle, so every query costs a round trip. Results
+//! from a frame drawn in several passes (MSAA halves, stereo eyes) are combined, and a cluster seen
+//! by one pass isn't queried again by the next.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+use gamecube_gx::{apply_vertex_format, Gx, OverlayVertex, Primitive};
+use inception_render_common::map_data::MapData;
+use inception_render_common::vertex_format::VertexFormat;
+use ogc_sys::*;
+use visibility::{ClusterIndex, PackedVisibility};
+
+use crate::shaders::flat_vertex_color::FLAT_VERTEX_COLOR_SHADER;
+
+/// Clusters whose bounds come within this distance of the camera are never queried, since their
+/// boxes may be clipped by the near plane.
+const NEAR_MARGIN: f32 = 16.0;
+
+pub struct OcclusionCuller {
+    /// Clusters found hidden by the previous frame's queries.
+    occluded: Vec<bool>,
+    /// Clusters queried so far this frame.
+    queried: Vec<bool>,
+    /// Clusters with at least one visible pixel so far this frame.
+    visible: Vec<bool>,
+}
+
+impl OcclusionCuller {
+    pub fn new(num_clusters: usize) -> Self {
+        Self {
+            occluded: vec![false; num_clusters],
+            queried: vec![false; num_clusters],
+            visible: vec![false; num_clusters],
+        }
+    }
+
+    pub fn is_occluded(&self, cluster: u16) -> bool {
+        self.occluded
+            .get(cluster as usize)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Forgets every result, so nothing is culled until the next queries come in.
+    pub fn reset(&mut self) {
+        self.occluded.fill(false);
+        self.queried.fill(false);
+        self.visible.fill(false);
+    }
+
+    /// Queries each cluster the PVS allows from `view_cluster`, using the current view matrix and
+    /// depth buffer.
+    ///
+    /// # Safety
+    ///
+    /// Call this after the pass's world geometry is drawn and before its EFB copy clears the depth
+    /// buffer.
+    pub unsafe fn query<Data: Deref<Target = [u8]>>(
+        &mut self,
+        gx: &mut Gx,
+        map_data: &MapData<Data>,
+        visibility: PackedVisibility,
+        view_cluster: i16,
+        eye: [f32; 3],
+    ) {
+        let view_bitmap = match usize::try_from(view_cluster)
+            .ok()
+            .and_then(|view_cluster| visibility.get_cluster(ClusterIndex(view_cluster)))
+        {
+            Some(view_bitmap) => view_bitmap,
+            // Outside the world everything is drawn anyway.
+            None => return,
+        };
+        let cluster_lod_table = map_data.cluster_lod_table();
+
+        unsafe {
+            apply_vertex_format(&VertexFormat::Overlay.descriptor());
+            FLAT_VERTEX_COLOR_SHADER.apply();
+            GX_SetColorUpdate(GX_FALSE as u8);
+            GX_SetAlphaUpdate(GX_FALSE as u8);
+            GX_SetBlendMode(GX_BM_NONE as u8, 0, 0, 0);
+            GX_SetZMode(GX_TRUE as u8, GX_LEQUAL as u8, GX_FALSE as u8);
+            GX_SetZCompLoc(GX_TRUE as u8);
+            GX_SetCullMode(GX_CULL_NONE as u8);
+        }
+
+        for cluster in view_bitmap.iter_visible_clusters().map(|cluster| cluster.0) {
+            if cluster >= self.queried.len() || self.visible[cluster] {
+                continue;
+            }
+            self.queried[cluster] = true;
+            let bounds = match cluster_lod_table.get(cluster) {
+                Some(bounds) => bounds,
+                None => {
+                    self.visible[cluster] = true;
+                    continue;
+                }
+            };
+            if bounds.distance_squared(eye) <= NEAR_MARGIN * NEAR_MARGIN {
+                self.visible[cluster] = true;
+                continue;
+            }
+
+            let mut top_z_out = 0;
+            unsafe {
+                GX_ClearPixMetric();
+                draw_box(gx, bounds.mins, bounds.maxs);
+                GX_DrawDone();
+                let (mut top_z_in, mut bot_z_in, mut bot_z_out, mut clr_in, mut copy_clks) =
+                    (0, 0, 0, 0, 0);
+                GX_ReadPixMetric(
+                    &mut top_z_in,
+                    &mut top_z_out,
+                    &mut bot_z_in,
+                    &mut bot_z_out,
+                    &mut clr_in,
+                    &mut copy_clks,
+                );
+            }
+            if top_z_out != 0 {
+                self.visible[cluster] = true;
+            }
+        }
+
+        unsafe {
+            GX_SetColorUpdate(GX_TRUE as u8);
+            GX_SetAlphaUpdate(GX_TRUE as u8);
+            GX_SetZMode(GX_TRUE as u8, GX_LEQUAL as u8, GX_TRUE as u8);
+            GX_SetCullMode(GX_CULL_BACK as u8);
+        }
+    }
+
+    /// Makes this frame's results the ones the next frame culls with.
+    pub fn end_frame(&mut self) {
+        for ((occluded, queried), visible) in self
+            .occluded
+            .iter_mut()
+            .zip(&mut self.queried)
+            .zip(&mut self.visible)
+        {
+            *occluded = *queried && !*visible;
+            *queried = false;
+            *visible = false;
+        }
+    }
+}
+
+/// Draws the six faces of an axis-aligned box.
+fn draw_box(gx: &mut Gx, mins: [f32; 3], maxs: [f32; 3]) {
+    let corner = |i: usize| {
+        [0, 1, 2].map(|axis| {
+            if i & (1 << axis) != 0 {
+                maxs[axis]
+            } else {
+                mins[axis]
+            }
+        })
+    };
+    const FACES: [[usize; 4]; 6] = [
+        [0, 2, 6, 4],
+        [1, 5, 7, 3],
+        [0, 4, 5, 1],
+        [2, 3, 7, 6],
+        [0, 1, 3, 2],
+        [4, 6, 7, 5],
+    ];
+    let mut draw = gx.begin(Primitive::Quads, 24);
+    for face in FACES {
+        for i in face {
+            draw.vertex(OverlayVertex {
+                pos: corner(i),
+                clr0: [0; 3],
+                tex0: [0.0; 2],
+            });
+        }
+    }
+}
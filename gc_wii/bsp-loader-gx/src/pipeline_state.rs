@@ -0,0 +1,145 @@
+use core::ptr;
+
+use gamecube_gx::Gx;
+use gamecube_shader::Shader as GxShader;
+use inception_render_common::hashable_float::HashableMat3x4;
+use inception_render_common::pipeline_state::{
+    ApplyPipelineState, BlendMode, DepthCompare, DepthMode, TextureSlot, VertexDesc,
+};
+use inception_render_common::shader::{Shader, ShaderBaseAlpha};
+use ogc_sys::*;
+
+use crate::shaders::debug_constant::DEBUG_CONSTANT_SHADER;
+use crate::shaders::material::{
+    LIGHTMAPPED_BAAA_SHADER, LIGHTMAPPED_SHADER, UNLIT_GENERIC_SHADER,
+    WORLD_VERTEX_TRANSITION_SHADER,
+};
+
+/// The TEV configuration for a pass.
+#[derive(Clone, Copy, Debug)]
+pub enum PassShader {
+    Static(&'static GxShader),
+    /// Draws a flat color from konstant register 0, for the debug render modes.
+    DebugConstant([u8; 3]),
+}
+
+impl PartialEq for PassShader {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Static(a), Self::Static(b)) => ptr::eq(*a, *b),
+            (Self::DebugConstant(a), Self::DebugConstant(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl From<Shader> for PassShader {
+    fn from(shader: Shader) -> Self {
+        Self::Static(gx_shader(shader))
+    }
+}
+
+/// Submits pipeline state straight to GX.
+///
+/// Pair this with a `PipelineStateTracker` to skip state that didn't change since the last batch.
+pub struct GxPipelineState<'a> {
+    /// Only held to show that GX is ours to configure.
+    _gx: &'a Gx,
+    /// Texture objects, indexed by texture ID. Empty for passes whose display lists load their own.
+    pub texobjs: &'a [GXTexObj],
+}
+
+impl<'a> GxPipelineState<'a> {
+    pub fn new(gx: &'a Gx) -> Self {
+        Self {
+            _gx: gx,
+            texobjs: &[],
+        }
+    }
+}
+
+impl<'a> ApplyPipelineState<PassShader> for GxPipelineState<'a> {
+    fn apply_shader(&mut self, shader: &PassShader) {
+        match *shader {
+            PassShader::Static(shader) => shader.apply(),
+            PassShader::DebugConstant(tint) => {
+                self.apply_env_map_tint(tint);
+                DEBUG_CONSTANT_SHADER.apply();
+            }
+        }
+    }
+
+    fn apply_blend(&mut self, blend: BlendMode) {
+        let (src, dst) = match blend {
+            BlendMode::Opaque => {
+                unsafe { GX_SetBlendMode(GX_BM_NONE as u8, 0, 0, 0) };
+                return;
+            }
+            BlendMode::Alpha => (GX_BL_SRCALPHA, GX_BL_INVSRCALPHA),
+            BlendMode::Additive => (GX_BL_ONE, GX_BL_ONE),
+        };
+        unsafe { GX_SetBlendMode(GX_BM_BLEND as u8, src as u8, dst as u8, 0) };
+    }
+
+    fn apply_depth(&mut self, depth: DepthMode) {
+        let (enable, func) = match depth.compare {
+            None => (GX_FALSE, GX_ALWAYS),
+            Some(DepthCompare::LessEqual) => (GX_TRUE, GX_LEQUAL),
+            Some(DepthCompare::Equal) => (GX_TRUE, GX_EQUAL),
+        };
+        let write = if depth.write { GX_TRUE } else { GX_FALSE };
+        unsafe { GX_SetZMode(enable as u8, func as u8, write as u8) };
+    }
+
+    fn apply_reflection_matrix(&mut self, matrix: &HashableMat3x4) {
+        let matrix = matrix.as_matrix();
+        let mut mtx = [[0.0; 4]; 3];
+        for (r, row) in mtx.iter_mut().enumerate() {
+            for (c, value) in row.iter_mut().enumerate() {
+                *value = matrix[(r, c)];
+            }
+        }
+        unsafe { GX_LoadTexMtxImm(mtx.as_mut_ptr(), GX_TEXMTX2, GX_MTX3x4 as u8) };
+    }
+
+    fn apply_vertex_desc(&mut self, vertex_desc: &VertexDesc) {
+        unsafe {
+            GX_ClearVtxDesc();
+            for (attribute, input) in vertex_desc.iter() {
+                GX_SetVtxDesc(attribute as u8, input as u8);
+            }
+            GX_InvVtxCache();
+        }
+    }
+
+    fn bind_texture(&mut self, slot: TextureSlot, texture_id: u16) {
+        let texmap = match slot {
+            TextureSlot::Base => GX_TEXMAP1,
+            TextureSlot::Aux => GX_TEXMAP2,
+            TextureSlot::Env => GX_TEXMAP3,
+        };
+        let texobj = &self.texobjs[texture_id as usize];
+        unsafe { GX_LoadTexObj(texobj as *const GXTexObj as *mut GXTexObj, texmap as u8) };
+    }
+
+    fn apply_env_map_tint(&mut self, tint: [u8; 3]) {
+        let [r, g, b] = tint;
+        unsafe { GX_SetTevKColor(GX_KCOLOR0 as u8, GXColor { r, g, b, a: 255 }) };
+    }
+}
+
+/// Picks the compiled TEV configuration for a shader, matching [`Shader::program`].
+fn gx_shader(shader: Shader) -> &'static GxShader {
+    match shader {
+        Shader::LightmappedGeneric {
+            base_alpha: ShaderBaseAlpha::BaseTextureAlpha,
+            ..
+        } => &LIGHTMAPPED_SHADER,
+        Shader::LightmappedGeneric {
+            base_alpha: ShaderBaseAlpha::AuxTextureAlpha,
+            ..
+        } => &LIGHTMAPPED_BAAA_SHADER,
+        Shader::UnlitGeneric => &UNLIT_GENERIC_SHADER,
+        Shader::WorldVertexTransition => &WORLD_VERTEX_TRANSITION_SHADER,
+    }
+}
@@ -0,0 +1,16 @@
+use gamecube_shader::gx::*;
+use gamecube_shader::*;
+
+/// Outputs konst color register 0 without sampling anything.
+pub static DEBUG_CONSTANT_SHADER: Shader = Shader {
+    tev_stages: tev_builder()
+        .add_stage(TevStage::new(
+            TevStageColor::just(TevColorIn::Konst).with_konst_sel(Some(TevColorKonst::K0Rgb)),
+            TevStageAlpha::just(TevAlphaIn::Konst).with_konst_sel(Some(TevAlphaKonst::K0A)),
+        ))
+        .build(),
+    ind_tex_stages: [None; 4],
+    num_chans: 0,
+    tex_gens: [None; 8],
+    swap_table: [[0, 1, 2, 3]; 4],
+};
@@ -0,0 +1,112 @@
+use gamecube_shader::gx::*;
+use gamecube_shader::*;
+
+/// LightmappedGeneric with the dynamic lights in color channel 0 added to the lightmap.
+pub static LIGHTMAPPED_DYNAMIC_SHADER: Shader = Shader {
+    tev_stages: tev_builder()
+        // Sample the lightmap.
+        .add_stage(
+            TevStage::color_only(TevStageColor::just(TevColorIn::TexColor))
+                .with_tex(TevTexCoord::TexCoord0, TevTexMap::TEXMAP0),
+        )
+        // Add the dynamic lights.
+        .add_stage(
+            TevStage::color_only(TevStageColor::add(
+                TevColorIn::PrevColor,
+                TevColorIn::RasColor,
+            ))
+            .with_channel(TevChannel::Color0),
+        )
+        // Sample the base map and multiply it by the lighting.
+        .add_stage(
+            TevStage::new(
+                TevStageColor::mul(TevColorIn::PrevColor, TevColorIn::TexColor)
+                    // Scale to allow the lightmap to over-brighten to some degree.
+                    .with_scale(TevScale::K2),
+                TevStageAlpha::just(TevAlphaIn::TexAlpha),
+            )
+            .with_tex(TevTexCoord::TexCoord1, TevTexMap::TEXMAP1),
+        )
+        .build(),
+    ind_tex_stages: [None; 4],
+    num_chans: 1,
+    tex_gens: [
+        // Lightmap coord.
+        Some(TexGen::new(
+            TexGenType::Mtx2x4,
+            TexGenSrc::Tex0,
+            TexMtxIndex::IDENTITY,
+        )),
+        // Base map coord.
+        Some(TexGen::new(
+            TexGenType::Mtx2x4,
+            TexGenSrc::Tex1,
+            TexMtxIndex::IDENTITY,
+        )),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    ],
+    swap_table: [[0, 1, 2, 3]; 4],
+};
+
+/// LightmappedGeneric with dynamic lights, base alpha packed as aux alpha.
+pub static LIGHTMAPPED_BAAA_DYNAMIC_SHADER: Shader = Shader {
+    tev_stages: tev_builder()
+        // Sample the lightmap.
+        .add_stage(
+            TevStage::color_only(TevStageColor::just(TevColorIn::TexColor))
+                .with_tex(TevTexCoord::TexCoord0, TevTexMap::TEXMAP0),
+        )
+        // Add the dynamic lights.
+        .add_stage(
+            TevStage::color_only(TevStageColor::add(
+                TevColorIn::PrevColor,
+                TevColorIn::RasColor,
+            ))
+            .with_channel(TevChannel::Color0),
+        )
+        // Sample the base map and multiply it by the lighting.
+        .add_stage(
+            TevStage::color_only(
+                TevStageColor::mul(TevColorIn::PrevColor, TevColorIn::TexColor)
+                    .with_scale(TevScale::K2),
+            )
+            .with_tex(TevTexCoord::TexCoord1, TevTexMap::TEXMAP1),
+        )
+        // Sample the aux map for alpha.
+        .add_stage(
+            TevStage::new(
+                TevStageColor::just(TevColorIn::PrevColor),
+                TevStageAlpha::just(TevAlphaIn::TexAlpha),
+            )
+            .with_tex(TevTexCoord::TexCoord1, TevTexMap::TEXMAP2),
+        )
+        .build(),
+    ind_tex_stages: [None; 4],
+    num_chans: 1,
+    tex_gens: [
+        // Lightmap coord.
+        Some(TexGen::new(
+            TexGenType::Mtx2x4,
+            TexGenSrc::Tex0,
+            TexMtxIndex::IDENTITY,
+        )),
+        // Base map coord.
+        Some(TexGen::new(
+            TexGenType::Mtx2x4,
+            TexGenSrc::Tex1,
+            TexMtxIndex::IDENTITY,
+        )),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    ],
+    swap_table: [[0, 1, 2, 3]; 4],
+};
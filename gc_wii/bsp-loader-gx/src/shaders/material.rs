@@ -0,0 +1,17 @@
+//! GX shaders compiled from the packer's portable material programs.
+
+use gamecube_shader::Shader;
+use inception_render_common::shader::{
+    LIGHTMAPPED_BAAA_PROGRAM, LIGHTMAPPED_PROGRAM, UNLIT_GENERIC_PROGRAM,
+    WORLD_VERTEX_TRANSITION_PROGRAM,
+};
+
+pub static LIGHTMAPPED_SHADER: Shader = Shader::from_program(&LIGHTMAPPED_PROGRAM);
+
+/// LightmappedGeneric, base alpha packed as aux alpha.
+pub static LIGHTMAPPED_BAAA_SHADER: Shader = Shader::from_program(&LIGHTMAPPED_BAAA_PROGRAM);
+
+pub static UNLIT_GENERIC_SHADER: Shader = Shader::from_program(&UNLIT_GENERIC_PROGRAM);
+
+pub static WORLD_VERTEX_TRANSITION_SHADER: Shader =
+    Shader::from_program(&WORLD_VERTEX_TRANSITION_PROGRAM);
@@ -1,7 +1,8 @@
+pub mod debug_constant;
+pub mod dynamic_light;
 pub mod flat_vertex_color;
-pub mod lightmapped;
-pub mod lightmapped_baaa;
+pub mod material;
+pub mod overlay;
 pub mod self_illum;
-pub mod unlit_generic;
+pub mod stereo;
 pub mod vertex_color;
-pub mod world_vertex_transition;
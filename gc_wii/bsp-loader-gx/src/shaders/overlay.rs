@@ -0,0 +1,78 @@
+use gamecube_shader::gx::*;
+use gamecube_shader::*;
+
+/// Overlays and decals, lit by vertex color, alpha from the base map.
+pub static OVERLAY_SHADER: Shader = Shader {
+    tev_stages: tev_builder()
+        // Sample the base map and multiply it by the lighting.
+        .add_stage(
+            TevStage::new(
+                TevStageColor::mul(TevColorIn::RasColor, TevColorIn::TexColor)
+                    // Scale to match the lightmapped shaders' over-brightening.
+                    .with_scale(TevScale::K2),
+                TevStageAlpha::just(TevAlphaIn::TexAlpha),
+            )
+            .with_tex(TevTexCoord::TexCoord0, TevTexMap::TEXMAP0)
+            .with_channel(TevChannel::Color0),
+        )
+        .build(),
+    ind_tex_stages: [None; 4],
+    num_chans: 1,
+    tex_gens: [
+        // Base map coord.
+        Some(TexGen::new(
+            TexGenType::Mtx2x4,
+            TexGenSrc::Tex0,
+            TexMtxIndex::IDENTITY,
+        )),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    ],
+    swap_table: [[0, 1, 2, 3]; 4],
+};
+
+/// Overlays and decals, lit by vertex color, base alpha packed as aux alpha.
+pub static OVERLAY_BAAA_SHADER: Shader = Shader {
+    tev_stages: tev_builder()
+        // Sample the base map and multiply it by the lighting.
+        .add_stage(
+            TevStage::color_only(
+                TevStageColor::mul(TevColorIn::RasColor, TevColorIn::TexColor)
+                    .with_scale(TevScale::K2),
+            )
+            .with_tex(TevTexCoord::TexCoord0, TevTexMap::TEXMAP0)
+            .with_channel(TevChannel::Color0),
+        )
+        // Sample the aux map for alpha.
+        .add_stage(
+            TevStage::new(
+                TevStageColor::just(TevColorIn::PrevColor),
+                TevStageAlpha::just(TevAlphaIn::TexAlpha),
+            )
+            .with_tex(TevTexCoord::TexCoord0, TevTexMap::TEXMAP1),
+        )
+        .build(),
+    ind_tex_stages: [None; 4],
+    num_chans: 1,
+    tex_gens: [
+        // Base map coord.
+        Some(TexGen::new(
+            TexGenType::Mtx2x4,
+            TexGenSrc::Tex0,
+            TexMtxIndex::IDENTITY,
+        )),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    ],
+    swap_table: [[0, 1, 2, 3]; 4],
+};
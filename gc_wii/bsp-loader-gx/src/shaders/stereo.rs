@@ -0,0 +1,94 @@
+use gamecube_shader::gx::*;
+use gamecube_shader::*;
+
+/// Combines the left eye (map 0) and right eye (map 1) per channel, taking konst color 0 of the
+/// right eye and the rest of the left.
+pub static STEREO_ANAGLYPH_SHADER: Shader = Shader {
+    tev_stages: tev_builder()
+        // Sample the left eye.
+        .add_stage(
+            TevStage::color_only(TevStageColor::just(TevColorIn::TexColor))
+                .with_tex(TevTexCoord::TexCoord0, TevTexMap::TEXMAP0),
+        )
+        // Sample the right eye and mix it in by channel.
+        .add_stage(
+            TevStage::color_only(
+                TevStageColor::mix(
+                    TevColorIn::PrevColor,
+                    TevColorIn::TexColor,
+                    TevColorIn::Konst,
+                )
+                .with_konst_sel(Some(TevColorKonst::K0Rgb)),
+            )
+            .with_tex(TevTexCoord::TexCoord0, TevTexMap::TEXMAP1),
+        )
+        .build(),
+    ind_tex_stages: [None; 4],
+    num_chans: 0,
+    tex_gens: [
+        // Screen coord.
+        Some(TexGen::new(
+            TexGenType::Mtx2x4,
+            TexGenSrc::Tex0,
+            TexMtxIndex::IDENTITY,
+        )),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    ],
+    swap_table: [[0, 1, 2, 3]; 4],
+};
+
+/// Selects between the left eye (map 0) and right eye (map 1) by an intensity mask (map 2) that
+/// repeats every few lines.
+pub static STEREO_INTERLACED_SHADER: Shader = Shader {
+    tev_stages: tev_builder()
+        // Sample the left eye.
+        .add_stage(
+            TevStage::color_only(TevStageColor::just(TevColorIn::TexColor))
+                .with_tex(TevTexCoord::TexCoord0, TevTexMap::TEXMAP0),
+        )
+        // Sample the right eye.
+        .add_stage(
+            TevStage::color_only(TevStageColor::just(TevColorIn::TexColor))
+                .with_color_dst(TevReg::Reg0)
+                .with_tex(TevTexCoord::TexCoord0, TevTexMap::TEXMAP1),
+        )
+        // Sample the mask and select.
+        .add_stage(
+            TevStage::color_only(TevStageColor::mix(
+                TevColorIn::PrevColor,
+                TevColorIn::Reg0Color,
+                TevColorIn::TexColor,
+            ))
+            .with_tex(TevTexCoord::TexCoord1, TevTexMap::TEXMAP2),
+        )
+        .build(),
+    ind_tex_stages: [None; 4],
+    num_chans: 0,
+    tex_gens: [
+        // Screen coord.
+        Some(TexGen::new(
+            TexGenType::Mtx2x4,
+            TexGenSrc::Tex0,
+            TexMtxIndex::IDENTITY,
+        )),
+        // Mask coord, scaled to repeat down the screen.
+        Some(TexGen::new(
+            TexGenType::Mtx2x4,
+            TexGenSrc::Tex0,
+            TexMtxIndex::TEXMTX2,
+        )),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    ],
+    swap_table: [[0, 1, 2, 3]; 4],
+};
@@ -0,0 +1,211 @@
+use core::mem::zeroed;
+
+use alloc::vec::Vec;
+use derive_try_from_primitive::TryFromPrimitive;
+use gamecube_gx::{apply_vertex_format, DebugTextureVertex, Gx};
+use gamecube_math::{Mtx34, Mtx44};
+use inception_render_common::vertex_format::VertexFormat;
+use ogc_sys::*;
+
+use crate::shaders::stereo::{STEREO_ANAGLYPH_SHADER, STEREO_INTERLACED_SHADER};
+
+/// Distance between the eyes in world units. Roughly human at Source's scale, exaggerated a
+/// little so the effect is obvious.
+const EYE_SEPARATION: f32 = 4.0;
+
+/// Eye textures are copied at the full EFB size.
+const WIDTH: u16 = 640;
+const HEIGHT: u16 = 480;
+
+/// The interlace mask is a single I8 tile, alternating left and right eye rows.
+const MASK_WIDTH: u16 = 8;
+const MASK_HEIGHT: u16 = 4;
+
+/// Which color channels the right eye contributes to in anaglyph mode. Red comes from the left
+/// eye for red/cyan glasses.
+const ANAGLYPH_RIGHT_CHANNELS: GXColor = GXColor {
+    r: 0,
+    g: 255,
+    b: 255,
+    a: 255,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, TryFromPrimitive)]
+#[repr(u32)]
+pub enum StereoMode {
+    Off = 0,
+    /// Red/cyan, for glasses.
+    Anaglyph = 1,
+    /// Alternating lines, for line-interleaved 3D displays.
+    Interlaced = 2,
+}
+
+impl StereoMode {
+    pub fn prev(self) -> Self {
+        if let Ok(result) = Self::try_from((self as u32).wrapping_sub(1)) {
+            result
+        } else {
+            Self::Interlaced
+        }
+    }
+
+    pub fn next(self) -> Self {
+        if let Ok(result) = Self::try_from(self as u32 + 1) {
+            result
+        } else {
+            Self::Off
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Eye {
+    Left,
+    Right,
+}
+
+impl Eye {
+    /// Offsets a camera position toward this eye. `yaw` is the camera's, as in `GameState`.
+    pub fn offset(self, pos: guVector, yaw: f32) -> guVector {
+        let sign = match self {
+            Self::Left => -0.5,
+            Self::Right => 0.5,
+        };
+        let distance = sign * EYE_SEPARATION;
+        guVector {
+            x: pos.x + distance * libm::sinf(yaw),
+            y: pos.y - distance * libm::cosf(yaw),
+            z: pos.z,
+        }
+    }
+}
+
+/// Holds each eye's rendering between the two main draws and combines them in the EFB.
+pub struct StereoRenderer {
+    left_data: Vec<u8, GlobalAlign32>,
+    right_data: Vec<u8, GlobalAlign32>,
+    /// Only referenced through `mask`.
+    _mask_data: Vec<u8, GlobalAlign32>,
+    left: GXTexObj,
+    right: GXTexObj,
+    mask: GXTexObj,
+}
+
+impl StereoRenderer {
+    pub fn new() -> Self {
+        let eye_size = 2 * WIDTH as usize * HEIGHT as usize;
+        let left_data = alloc_texture(eye_size);
+        let right_data = alloc_texture(eye_size);
+
+        let mut mask_data = Vec::with_capacity_in(32, GlobalAlign32);
+        for row in 0..MASK_HEIGHT {
+            let value = if row % 2 == 0 { 0 } else { 255 };
+            mask_data.extend((0..MASK_WIDTH).map(|_| value));
+        }
+        unsafe {
+            DCFlushRange(mask_data.as_ptr() as _, mask_data.len() as u32);
+        }
+
+        let left = init_texobj(&left_data, WIDTH, HEIGHT, GX_TF_RGB565, GX_CLAMP);
+        let right = init_texobj(&right_data, WIDTH, HEIGHT, GX_TF_RGB565, GX_CLAMP);
+        let mask = init_texobj(&mask_data, MASK_WIDTH, MASK_HEIGHT, GX_TF_I8, GX_REPEAT);
+
+        Self {
+            left_data,
+            right_data,
+            _mask_data: mask_data,
+            left,
+            right,
+            mask,
+        }
+    }
+
+    /// Copies the EFB to an eye's texture and clears it for the next draw.
+    pub fn copy_eye(&mut self, eye: Eye) {
+        let data = match eye {
+            Eye::Left => &self.left_data,
+            Eye::Right => &self.right_data,
+        };
+        unsafe {
+            GX_SetTexCopySrc(0, 0, WIDTH, HEIGHT);
+            GX_SetTexCopyDst(WIDTH, HEIGHT, GX_TF_RGB565, GX_FALSE as u8);
+            DCInvalidateRange(data.as_ptr() as _, data.len() as u32);
+            GX_CopyTex(data.as_ptr() as _, GX_TRUE as u8);
+            GX_PixModeSync();
+        }
+    }
+
+    /// Draws both eyes over the whole EFB, combined for `mode`.
+    pub fn composite(&mut self, gx: &mut Gx, mode: StereoMode) {
+        unsafe {
+            GX_InvalidateTexAll();
+            GX_LoadTexObj(&mut self.left, GX_TEXMAP0 as u8);
+            GX_LoadTexObj(&mut self.right, GX_TEXMAP1 as u8);
+            match mode {
+                StereoMode::Off => unreachable!(),
+                StereoMode::Anaglyph => {
+                    GX_SetTevKColor(GX_KCOLOR0 as u8, ANAGLYPH_RIGHT_CHANNELS);
+                    STEREO_ANAGLYPH_SHADER.apply();
+                }
+                StereoMode::Interlaced => {
+                    GX_LoadTexObj(&mut self.mask, GX_TEXMAP2 as u8);
+                    let mut mask_scale = [
+                        [1.0, 0.0, 0.0, 0.0],
+                        [0.0, (HEIGHT / MASK_HEIGHT) as f32, 0.0, 0.0],
+                    ];
+                    GX_LoadTexMtxImm(mask_scale.as_mut_ptr(), GX_TEXMTX2, GX_MTX2x4 as u8);
+                    STEREO_INTERLACED_SHADER.apply();
+                }
+            }
+
+            apply_vertex_format(&VertexFormat::DebugTexture.descriptor());
+
+            GX_SetBlendMode(GX_BM_NONE as u8, 0, 0, 0);
+            GX_SetZMode(GX_FALSE as u8, GX_ALWAYS as u8, GX_FALSE as u8);
+            GX_SetColorUpdate(GX_TRUE as u8);
+
+            let mut proj = Mtx44::ortho(0.0, 1.0, 0.0, 1.0, -1.0, 1.0);
+            GX_LoadProjectionMtx(proj.as_mut_ptr(), GX_ORTHOGRAPHIC as u8);
+
+            let mut view = Mtx34::identity();
+            GX_LoadPosMtxImm(view.as_mut_ptr(), GX_PNMTX0);
+
+            gx.quad(
+                [(0, 0), (1, 0), (1, 1), (0, 1)].map(|(x, y)| DebugTextureVertex {
+                    pos: [x, y],
+                    tex0: [x as u8, y as u8],
+                }),
+            );
+        }
+    }
+}
+
+fn alloc_texture(size: usize) -> Vec<u8, GlobalAlign32> {
+    let mut data = Vec::with_capacity_in(size, GlobalAlign32);
+    data.resize(size, 0);
+    data
+}
+
+fn init_texobj(
+    data: &Vec<u8, GlobalAlign32>,
+    width: u16,
+    height: u16,
+    format: u32,
+    wrap: u32,
+) -> GXTexObj {
+    unsafe {
+        let mut texobj = zeroed::<GXTexObj>();
+        GX_InitTexObj(
+            &mut texobj,
+            data.as_ptr() as _,
+            width,
+            height,
+            format as u8,
+            wrap as u8,
+            wrap as u8,
+            GX_FALSE as u8,
+        );
+        GX_InitTexObjFilterMode(&mut texobj, GX_NEAR as u8, GX_NEAR as u8);
+        texobj
+    }
+}
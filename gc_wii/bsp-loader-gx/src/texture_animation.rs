@@ -0,0 +1,78 @@
+use core::ops::{Deref, DerefMut};
+
+use alloc::vec::Vec;
+use inception_render_common::map_data::{
+    AnimatedTextureTableEntry, MapData, TextureRelocationSites,
+};
+use ogc_sys::*;
+
+/// Vertical retraces per second. The video mode is always NTSC 480p.
+pub const RETRACE_RATE: f32 = 60.0;
+
+/// Cycles animated textures by repointing the display list texture binds that reference them.
+pub struct TextureAnimator {
+    animations: Vec<Animation>,
+    start_retrace_count: u32,
+}
+
+struct Animation {
+    entry: AnimatedTextureTableEntry,
+    sites: TextureRelocationSites,
+    /// The start offset of the frame the display lists currently point at.
+    frame_start_offset: u32,
+}
+
+impl TextureAnimator {
+    /// Collects the references to each animated texture. Every animation starts on its first
+    /// frame, which is where [`MapData::relocate`] leaves them.
+    pub fn new<Data: Deref<Target = [u8]>>(map_data: &MapData<Data>) -> Self {
+        let animations = map_data
+            .animated_texture_table()
+            .iter()
+            .map(|&entry| Animation {
+                entry,
+                sites: match map_data.texture_relocation_sites(entry.texture_id) {
+                    Ok(sites) => sites,
+                    Err(e) => panic!(
+                        "animated texture {} can't be relocated: {:?}",
+                        entry.texture_id, e
+                    ),
+                },
+                frame_start_offset: map_data.texture_table()[entry.texture_id as usize]
+                    .start_offset,
+            })
+            .collect();
+        Self {
+            animations,
+            start_retrace_count: unsafe { VIDEO_GetRetraceCount() },
+        }
+    }
+
+    /// Advances every animation to the current time, patching only those whose frame changed.
+    /// GX must not be reading the map's display lists, so call this between `GX_DrawDone` and the
+    /// next frame's draws.
+    pub fn update<Data: DerefMut<Target = [u8]>>(&mut self, map_data: &mut MapData<Data>) {
+        let retraces = unsafe { VIDEO_GetRetraceCount() }.wrapping_sub(self.start_retrace_count);
+        let seconds = retraces as f32 / RETRACE_RATE;
+        for animation in &mut self.animations {
+            let frame_start_offset = animation
+                .entry
+                .frame_start_offset(map_data.texture_table(), seconds);
+            if frame_start_offset == animation.frame_start_offset {
+                continue;
+            }
+            animation.frame_start_offset = frame_start_offset;
+
+            let result =
+                map_data.relocate_texture_frame(&animation.sites, frame_start_offset, |bytes| {
+                    unsafe { DCFlushRange(bytes.as_ptr() as _, bytes.len() as u32) };
+                });
+            if let Err(e) = result {
+                panic!(
+                    "animated texture {} can't be relocated: {:?}",
+                    animation.entry.texture_id, e
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Shared textures, kept across map changes.
+//!
+//! Maps packed with `--shared-textures` leave most of their images out and name each one by a hash
+//! of its contents instead. The cache keeps whatever the current map uses, so moving between maps
+//! in the same chapter only transfers the textures the new map doesn't have in common with the
+//! last one.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+use gamecube_alloc::DmaBuffer;
+use inception_render_common::map_data::{shared_texture_path, MapData, SharedTextureTableEntry};
+
+use crate::loader::{LoadError, LoadStatus, Loader};
+
+#[derive(Default)]
+pub struct SharedTextureCache {
+    textures: BTreeMap<[u8; 16], DmaBuffer<u8>>,
+}
+
+impl SharedTextureCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drops the textures `map_data` doesn't use, then starts fetching the ones it uses that aren't
+    /// cached yet. Dropping first keeps the previous map's leftovers from crowding out the new map.
+    ///
+    /// Nothing may still be drawing with the previous map's textures.
+    pub fn start_load_for_map<L: Loader, Data: Deref<Target = [u8]>>(
+        &mut self,
+        map_data: &MapData<Data>,
+    ) -> SharedTextureLoad<L> {
+        let table = map_data.shared_texture_table();
+        self.textures
+            .retain(|hash, _| table.iter().any(|entry| entry.hash == *hash));
+
+        // Several textures in a map can share an image.
+        let mut missing: BTreeMap<[u8; 16], SharedTextureTableEntry> = BTreeMap::new();
+        for entry in table {
+            if !self.textures.contains_key(&entry.hash) {
+                missing.insert(entry.hash, *entry);
+            }
+        }
+        let missing: Vec<_> = missing.into_values().collect();
+        SharedTextureLoad {
+            total: missing.iter().map(|entry| entry.size as usize).sum(),
+            missing,
+            current: None,
+            done: 0,
+        }
+    }
+
+    /// Advances a fetch a texture at a time, each by a bounded amount of I/O. Call until it returns
+    /// [`LoadStatus::Done`]. Textures that finished before an error stay cached.
+    pub fn poll_load<L: Loader>(
+        &mut self,
+        loader: &mut L,
+        load: &mut SharedTextureLoad<L>,
+    ) -> Result<LoadStatus<()>, LoadError> {
+        if load.current.is_none() {
+            let Some(entry) = load.missing.pop() else {
+                return Ok(LoadStatus::Done(()));
+            };
+            load.current = Some((entry, loader.start_load_shared_texture(&entry)?));
+        }
+        let (entry, texture_load) = load.current.as_mut().unwrap();
+
+        match loader.poll_load_shared_texture(texture_load)? {
+            LoadStatus::Loading { done, .. } => Ok(LoadStatus::Loading {
+                done: load.done + done,
+                total: load.total,
+            }),
+            LoadStatus::Done(mut data) => {
+                if !entry.matches(&data) {
+                    return Err(LoadError::Corrupt {
+                        path: shared_texture_path(&entry.hash),
+                    });
+                }
+                // GX reads textures straight from memory.
+                data.prepare_for_device_read();
+                self.textures.insert(entry.hash, data);
+                load.done += entry.size as usize;
+                load.current = None;
+                Ok(LoadStatus::Loading {
+                    done: load.done,
+                    total: load.total,
+                })
+            }
+        }
+    }
+
+    /// The address of a shared texture's image, if it's loaded. Images are checked against their
+    /// entries as they arrive.
+    pub fn address(&self, entry: &SharedTextureTableEntry) -> Option<usize> {
+        self.textures
+            .get(&entry.hash)
+            .filter(|data| data.len() == entry.size as usize)
+            .map(|data| data.as_ptr() as usize)
+    }
+}
+
+/// A fetch of the shared textures a map is missing. Dropping it cancels the fetch.
+pub struct SharedTextureLoad<L: Loader> {
+    /// Not started yet.
+    missing: Vec<SharedTextureTableEntry>,
+    current: Option<(SharedTextureTableEntry, L::TextureLoad)>,
+    /// Bytes of the textures that finished.
+    done: usize,
+    total: usize,
+}
+
+impl<L: Loader> SharedTextureLoad<L> {
+    /// How many textures are still to be fetched.
+    pub fn len(&self) -> usize {
+        self.missing.len() + self.current.is_some() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
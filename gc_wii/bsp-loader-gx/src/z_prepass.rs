@@ -0,0 +1,130 @@
+//! An optional depth-only prepass for opaque world geometry.
+//!
+//! The opaque world passes are drawn twice. The first pass writes depth alone, with color and
+//! alpha writes off and a single constant TEV stage, no texture coordinates, and no color
+//! channels, so the transform unit outputs positions only. The display lists still carry the brush
+//! vertex format's other attributes, which are fetched but go nowhere. The second pass shades with
+//! an early depth test of equal and no depth writes, so TEV and texturing only run once per pixel
+//! however much overdraw the PVS lets through.
+//!
+//! Alpha tested draws are left out of the prepass, since their cutouts aren't known without
+//! texturing. They are shaded with the usual less-or-equal test and depth writes instead.
+
+use gamecube_gx::Gx;
+use ogc_sys::*;
+
+use crate::shaders::debug_constant::DEBUG_CONSTANT_SHADER;
+
+/// How a world pass's display lists are drawn.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum WorldDepthMode {
+    /// Shaded, testing and writing depth as usual.
+    Normal,
+    /// Depth only.
+    Prepass,
+    /// Shaded against the prepass's depth.
+    AfterPrepass,
+}
+
+impl WorldDepthMode {
+    /// Sets the depth test for the draws that follow a change of alpha compare.
+    pub fn apply_depth_state(self, _gx: &Gx, alpha_tested: bool) {
+        match (self, alpha_tested) {
+            (Self::AfterPrepass, false) => unsafe {
+                GX_SetZMode(GX_TRUE as u8, GX_EQUAL as u8, GX_FALSE as u8);
+                GX_SetZCompLoc(GX_TRUE as u8);
+            },
+            (Self::AfterPrepass, true) => unsafe {
+                GX_SetZMode(GX_TRUE as u8, GX_LEQUAL as u8, GX_TRUE as u8);
+            },
+            _ => (),
+        }
+    }
+}
+
+/// Sets up for drawing depth only. The constant color is never written, so it doesn't matter.
+pub fn begin_prepass(_gx: &Gx) {
+    unsafe {
+        DEBUG_CONSTANT_SHADER.apply();
+        GX_SetColorUpdate(GX_FALSE as u8);
+        GX_SetAlphaUpdate(GX_FALSE as u8);
+        GX_SetBlendMode(GX_BM_NONE as u8, 0, 0, 0);
+        GX_SetZMode(GX_TRUE as u8, GX_LEQUAL as u8, GX_TRUE as u8);
+        GX_SetZCompLoc(GX_TRUE as u8);
+        GX_SetAlphaCompare(GX_ALWAYS as u8, 0, GX_AOP_OR as u8, GX_ALWAYS as u8, 0);
+    }
+}
+
+/// Turns color and alpha writes back on.
+pub fn end_prepass(_gx: &Gx) {
+    unsafe {
+        GX_SetColorUpdate(GX_TRUE as u8);
+        GX_SetAlphaUpdate(GX_TRUE as u8);
+    }
+}
+
+/// Pixel engine counts for one stretch of drawing.
+#[derive(Clone, Copy, Default)]
+pub struct PixelCounts {
+    /// Pixels that reached the depth test.
+    pub z_in: u32,
+    /// Pixels that passed the depth test.
+    pub z_out: u32,
+    /// Pixels that reached the blender, each of which was textured and shaded.
+    pub color_in: u32,
+}
+
+impl PixelCounts {
+    /// Starts counting from zero.
+    pub fn clear(_gx: &Gx) {
+        unsafe { GX_ClearPixMetric() };
+    }
+
+    /// Waits for GX to go idle and reads the counts since the last `clear()`.
+    pub fn read(_gx: &Gx) -> Self {
+        let (mut top_z_in, mut top_z_out, mut bot_z_in, mut bot_z_out, mut clr_in, mut copy_clks) =
+            (0, 0, 0, 0, 0, 0);
+        unsafe {
+            GX_DrawDone();
+            GX_ReadPixMetric(
+                &mut top_z_in,
+                &mut top_z_out,
+                &mut bot_z_in,
+                &mut bot_z_out,
+                &mut clr_in,
+                &mut copy_clks,
+            );
+        }
+        Self {
+            z_in: top_z_in + bot_z_in,
+            z_out: top_z_out + bot_z_out,
+            color_in: clr_in,
+        }
+    }
+
+    fn add(&mut self, other: Self) {
+        self.z_in += other.z_in;
+        self.z_out += other.z_out;
+        self.color_in += other.color_in;
+    }
+}
+
+/// The world passes' pixel counts, summed over every pass of a frame.
+///
+/// Reading the counters waits for GX to go idle, so collecting these costs a stall per pass and
+/// they should only be gathered while they're on screen.
+#[derive(Clone, Copy, Default)]
+pub struct WorldFillMetrics {
+    pub prepass: PixelCounts,
+    pub shaded: PixelCounts,
+}
+
+impl WorldFillMetrics {
+    pub fn add_prepass(&mut self, counts: PixelCounts) {
+        self.prepass.add(counts);
+    }
+
+    pub fn add_shaded(&mut self, counts: PixelCounts) {
+        self.shaded.add(counts);
+    }
+}
@@ -1,6 +1,8 @@
 #![no_std]
 
+use gamecube_gx::{apply_vertex_format, Gx, TextVertex};
 use gamecube_shader::FLAT_TEXTURED_SHADER;
+use inception_render_common::vertex_format::VertexFormat;
 use ogc_sys::*;
 
 pub struct TextRenderer {
@@ -12,12 +14,7 @@ pub struct TextRenderer {
 impl TextRenderer {
     pub fn prepare(ui_font: &GXTexObj) {
         unsafe {
-            GX_ClearVtxDesc();
-            GX_SetVtxDesc(GX_VA_POS as u8, GX_DIRECT as u8);
-            GX_SetVtxDesc(GX_VA_TEX0 as u8, GX_DIRECT as u8);
-            GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_POS, GX_POS_XY, GX_U16, 0);
-            GX_SetVtxAttrFmt(GX_VTXFMT0 as u8, GX_VA_TEX0, GX_TEX_ST, GX_U8, 6);
-            GX_InvVtxCache();
+            apply_vertex_format(&VertexFormat::Text.descriptor());
 
             FLAT_TEXTURED_SHADER.apply();
             GX_LoadTexObj(
@@ -32,7 +29,7 @@ impl TextRenderer {
         self.y += 16;
     }
 
-    pub fn draw_char(&mut self, c: u8) {
+    pub fn draw_char(&mut self, gx: &mut Gx, c: u8) {
         if c == b'\n' {
             self.new_line();
             return;
@@ -48,29 +45,18 @@ impl TextRenderer {
         let t0 = (c >> 4) << 2;
         let t1 = t0 + 4;
 
-        unsafe {
-            GX_Begin(GX_QUADS as u8, GX_VTXFMT0 as u8, 4);
-
-            (*wgPipe).U16 = x0;
-            (*wgPipe).U16 = y0;
-            (*wgPipe).U8 = s0;
-            (*wgPipe).U8 = t0;
-
-            (*wgPipe).U16 = x1;
-            (*wgPipe).U16 = y0;
-            (*wgPipe).U8 = s1;
-            (*wgPipe).U8 = t0;
-
-            (*wgPipe).U16 = x1;
-            (*wgPipe).U16 = y1;
-            (*wgPipe).U8 = s1;
-            (*wgPipe).U8 = t1;
-
-            (*wgPipe).U16 = x0;
-            (*wgPipe).U16 = y1;
-            (*wgPipe).U8 = s0;
-            (*wgPipe).U8 = t1;
-        }
+        gx.quad(
+            [
+                (x0, y0, s0, t0),
+                (x1, y0, s1, t0),
+                (x1, y1, s1, t1),
+                (x0, y1, s0, t1),
+            ]
+            .map(|(x, y, s, t)| TextVertex {
+                pos: [x, y],
+                tex0: [s, t],
+            }),
+        );
 
         self.x += 8;
         if self.x + 8 > 640 {
@@ -78,9 +64,9 @@ impl TextRenderer {
         }
     }
 
-    pub fn draw_str(&mut self, s: &[u8]) {
+    pub fn draw_str(&mut self, gx: &mut Gx, s: &[u8]) {
         for &c in s {
-            self.draw_char(c);
+            self.draw_char(gx, c);
         }
     }
 }
@@ -0,0 +1,162 @@
+//! Buffers shared with peripherals that access main memory directly.
+//!
+//! DMA bypasses the CPU's data cache. Before a device reads a buffer, the CPU's writes have to be
+//! flushed out to memory, and before a device writes one, its cached lines have to be invalidated
+//! so the CPU doesn't read stale data afterward or write it back over the device's data. Both work
+//! on whole cache lines, so a DMA buffer must start on a line boundary and own every line it
+//! touches.
+
+use alloc::alloc::{alloc_zeroed, dealloc, handle_alloc_error};
+use core::mem::{align_of, size_of, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+use core::slice;
+
+use bytemuck::Pod;
+use gamecube_cpu::cache::{flush_data_cache_blocks, invalidate_data_cache_blocks};
+
+use crate::{dma_layout, DMA_ALIGN};
+
+/// Main memory is mirrored at several virtual addresses, cached and uncached. Devices see the
+/// physical address underneath.
+const PHYSICAL_ADDRESS_MASK: usize = 0x3fff_ffff;
+
+/// Returns the physical address that a device should be given to reach `ptr`.
+pub fn physical_address<T>(ptr: *const T) -> u32 {
+    (ptr as usize & PHYSICAL_ADDRESS_MASK) as u32
+}
+
+/// Flushes `buf` from the data cache so a device reads what the CPU wrote, and returns its
+/// physical address.
+///
+/// # Panics
+///
+/// Panics if `buf` doesn't start and end on a cache line boundary.
+pub fn prepare_for_device_read(buf: &[u8]) -> u32 {
+    assert_dma_aligned(buf.as_ptr(), buf.len());
+    flush_data_cache_blocks(buf.as_ptr() as _, buf.len() / DMA_ALIGN);
+    physical_address(buf.as_ptr())
+}
+
+/// Invalidates `buf` in the data cache so the CPU sees what a device writes, and returns its
+/// physical address. The buffer's contents are unspecified until the transfer completes.
+///
+/// # Panics
+///
+/// Panics if `buf` doesn't start and end on a cache line boundary.
+pub fn prepare_for_device_write(buf: &mut [MaybeUninit<u8>]) -> u32 {
+    assert_dma_aligned(buf.as_ptr(), buf.len());
+    // SAFETY: The buffer is exclusively borrowed, covers whole cache lines, and may hold anything.
+    unsafe { invalidate_data_cache_blocks(buf.as_ptr() as _, buf.len() / DMA_ALIGN) };
+    physical_address(buf.as_ptr())
+}
+
+fn assert_dma_aligned<T>(ptr: *const T, len: usize) {
+    assert_eq!(ptr as usize % DMA_ALIGN, 0, "DMA buffer is misaligned");
+    assert_eq!(
+        len % DMA_ALIGN,
+        0,
+        "DMA buffer length is not a whole number of cache lines"
+    );
+}
+
+/// A heap allocation of `len` elements that is always safe to hand to a device.
+///
+/// The allocation is aligned to [`DMA_ALIGN`] and padded out to a whole number of cache lines, so
+/// transfers can cover [`DmaBuffer::dma_len`] bytes without the caller rounding anything. The
+/// elements are [`Pod`], so whatever a device writes is a valid value.
+pub struct DmaBuffer<T: Pod> {
+    ptr: NonNull<T>,
+    len: usize,
+}
+
+// SAFETY: The buffer exclusively owns its allocation.
+unsafe impl<T: Pod + Send> Send for DmaBuffer<T> {}
+unsafe impl<T: Pod + Sync> Sync for DmaBuffer<T> {}
+
+impl<T: Pod> DmaBuffer<T> {
+    /// Allocates `len` zeroed elements.
+    pub fn zeroed(len: usize) -> Self {
+        assert!(align_of::<T>() <= DMA_ALIGN);
+        let size = len
+            .checked_mul(size_of::<T>())
+            .expect("DMA buffer size overflows");
+        let ptr = if size == 0 {
+            // Nothing to transfer and nothing to free. Any well aligned address will do.
+            NonNull::new(DMA_ALIGN as *mut T).unwrap()
+        } else {
+            let layout = dma_layout(size);
+            // SAFETY: The layout has a nonzero size.
+            match NonNull::new(unsafe { alloc_zeroed(layout) } as *mut T) {
+                Some(ptr) => ptr,
+                None => handle_alloc_error(layout),
+            }
+        };
+        Self { ptr, len }
+    }
+
+    /// The number of bytes a transfer may cover, including the padding after the last element.
+    pub fn dma_len(&self) -> usize {
+        dma_layout(self.len * size_of::<T>()).size()
+    }
+
+    /// The address to give a device. Only meaningful when `dma_len()` is nonzero.
+    pub fn physical_address(&self) -> u32 {
+        physical_address(self.ptr.as_ptr())
+    }
+
+    /// The whole allocation, padding included, as bytes to hand to a transfer.
+    pub fn dma_bytes_mut(&mut self) -> &mut [u8] {
+        // SAFETY: The allocation spans `dma_len()` bytes, all initialized since it was zeroed, and
+        // `T: Pod` means any bytes written here form valid elements.
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr() as *mut u8, self.dma_len()) }
+    }
+
+    /// Flushes the CPU's writes so a device reads them, and returns the physical address.
+    pub fn prepare_for_device_read(&mut self) -> u32 {
+        prepare_for_device_read(self.dma_bytes_mut())
+    }
+
+    /// Invalidates the buffer so the CPU sees a device's writes, and returns the physical address.
+    /// The contents are unspecified until the transfer completes.
+    pub fn prepare_for_device_write(&mut self) -> u32 {
+        let bytes = self.dma_bytes_mut();
+        // SAFETY: u8 and MaybeUninit<u8> have the same layout, and every byte stays initialized
+        // from the CPU's point of view because the elements are Pod.
+        prepare_for_device_write(unsafe {
+            slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut MaybeUninit<u8>, bytes.len())
+        })
+    }
+}
+
+impl<T: Pod> Default for DmaBuffer<T> {
+    fn default() -> Self {
+        Self::zeroed(0)
+    }
+}
+
+impl<T: Pod> Deref for DmaBuffer<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        // SAFETY: The first `len` elements are allocated and initialized.
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T: Pod> DerefMut for DmaBuffer<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        // SAFETY: The first `len` elements are allocated and initialized.
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T: Pod> Drop for DmaBuffer<T> {
+    fn drop(&mut self) {
+        let size = self.len * size_of::<T>();
+        if size != 0 {
+            // SAFETY: The allocation was made with this layout in `zeroed()`.
+            unsafe { dealloc(self.ptr.as_ptr() as *mut u8, dma_layout(size)) };
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use core::alloc::Layout;
+use core::mem::{align_of, size_of};
+use core::ptr::NonNull;
+
+use crate::Region;
+
+/// A free block header, stored in the first bytes of the free memory it describes.
+struct FreeBlock {
+    size: usize,
+    next: Option<NonNull<FreeBlock>>,
+}
+
+/// Every block's address and size is a multiple of this, which guarantees that any leftover
+/// fragment is large enough to hold a [`FreeBlock`] header.
+const BLOCK_ALIGN: usize = {
+    let size = size_of::<FreeBlock>();
+    assert!(size.is_power_of_two());
+    assert!(align_of::<FreeBlock>() <= size);
+    size
+};
+
+const fn align_up(value: usize, align: usize) -> usize {
+    (value + align - 1) & !(align - 1)
+}
+
+const fn align_down(value: usize, align: usize) -> usize {
+    value & !(align - 1)
+}
+
+const fn block_size(layout: Layout) -> usize {
+    let size = if layout.size() == 0 { 1 } else { layout.size() };
+    align_up(size, BLOCK_ALIGN)
+}
+
+/// An address-ordered free list. Freed blocks are merged with their neighbors, so fragmentation is
+/// limited to holes that are actually in use.
+///
+/// This type does no locking of its own. See [`LinkedListAllocator`](crate::LinkedListAllocator)
+/// for a wrapper that can be installed as the global allocator.
+pub struct Heap {
+    head: Option<NonNull<FreeBlock>>,
+}
+
+// SAFETY: The heap exclusively owns the memory its free list points into.
+unsafe impl Send for Heap {}
+
+impl Heap {
+    pub const fn empty() -> Self {
+        Self { head: None }
+    }
+
+    /// Adds `region` to the free list. Regions too small to hold a single block are ignored.
+    ///
+    /// # Safety
+    ///
+    /// The region must be valid, writable memory that nothing else uses for the lifetime of the
+    /// heap. It must not overlap any region previously added to this heap.
+    pub unsafe fn add_region(&mut self, region: Region) {
+        let start = align_up(region.start(), BLOCK_ALIGN);
+        let end = align_down(region.end(), BLOCK_ALIGN);
+        if start < end {
+            unsafe { self.insert_free(start, end - start) };
+        }
+    }
+
+    /// Returns the total number of free bytes.
+    pub fn free_bytes(&self) -> usize {
+        let mut total = 0;
+        let mut cursor = self.head;
+        while let Some(block) = cursor {
+            // SAFETY: Every pointer in the free list refers to a valid header.
+            let block = unsafe { block.as_ref() };
+            total += block.size;
+            cursor = block.next;
+        }
+        total
+    }
+
+    /// Finds the first free block that can hold `layout` and carves the allocation out of it.
+    pub fn allocate(&mut self, layout: Layout) -> Option<NonNull<u8>> {
+        let align = layout.align().max(BLOCK_ALIGN);
+        let size = block_size(layout);
+
+        let mut prev: Option<NonNull<FreeBlock>> = None;
+        let mut cursor = self.head;
+        while let Some(mut block) = cursor {
+            let block_start = block.as_ptr() as usize;
+            // SAFETY: Every pointer in the free list refers to a valid header.
+            let (block_size, next) = unsafe { (block.as_ref().size, block.as_ref().next) };
+            let block_end = block_start + block_size;
+
+            let start = align_up(block_start, align);
+            let end = start.checked_add(size)?;
+            if end <= block_end {
+                // Any space after the allocation becomes a new free block.
+                let mut link = next;
+                if end < block_end {
+                    let tail = end as *mut FreeBlock;
+                    // SAFETY: The tail lies within the free block and is suitably aligned.
+                    unsafe {
+                        tail.write(FreeBlock {
+                            size: block_end - end,
+                            next,
+                        })
+                    };
+                    link = NonNull::new(tail);
+                }
+
+                if start > block_start {
+                    // Alignment padding at the front stays behind as a smaller free block.
+                    // SAFETY: The block is a valid header that is not otherwise borrowed.
+                    let block = unsafe { block.as_mut() };
+                    block.size = start - block_start;
+                    block.next = link;
+                } else {
+                    self.set_next(prev, link);
+                }
+
+                return NonNull::new(start as *mut u8);
+            }
+
+            prev = cursor;
+            cursor = next;
+        }
+        None
+    }
+
+    /// Returns an allocation to the free list.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by [`Heap::allocate`] on this heap with the same `layout`, and
+    /// must not be used afterward.
+    pub unsafe fn deallocate(&mut self, ptr: *mut u8, layout: Layout) {
+        unsafe { self.insert_free(ptr as usize, block_size(layout)) };
+    }
+
+    unsafe fn insert_free(&mut self, start: usize, size: usize) {
+        // Find the neighbors on either side of the new block.
+        let mut prev: Option<NonNull<FreeBlock>> = None;
+        let mut cursor = self.head;
+        while let Some(block) = cursor {
+            if block.as_ptr() as usize > start {
+                break;
+            }
+            prev = cursor;
+            // SAFETY: Every pointer in the free list refers to a valid header.
+            cursor = unsafe { block.as_ref().next };
+        }
+
+        let new = start as *mut FreeBlock;
+        let mut new_block = FreeBlock { size, next: cursor };
+
+        // Merge with the following block if they touch.
+        if let Some(next) = cursor {
+            if start + size == next.as_ptr() as usize {
+                // SAFETY: Every pointer in the free list refers to a valid header.
+                let next = unsafe { next.as_ref() };
+                new_block.size += next.size;
+                new_block.next = next.next;
+            }
+        }
+
+        // Merge into the preceding block if they touch, otherwise link the new block in.
+        if let Some(mut prev) = prev {
+            // SAFETY: The block is a valid header that is not otherwise borrowed.
+            let prev = unsafe { prev.as_mut() };
+            if prev.end() == start {
+                prev.size += new_block.size;
+                prev.next = new_block.next;
+                return;
+            }
+        }
+        // SAFETY: The caller guarantees the memory is free, writable, and aligned to BLOCK_ALIGN.
+        unsafe { new.write(new_block) };
+        self.set_next(prev, NonNull::new(new));
+    }
+
+    fn set_next(&mut self, prev: Option<NonNull<FreeBlock>>, next: Option<NonNull<FreeBlock>>) {
+        match prev {
+            // SAFETY: The block is a valid header that is not otherwise borrowed.
+            Some(mut prev) => unsafe { prev.as_mut().next = next },
+            None => self.head = next,
+        }
+    }
+}
+
+impl FreeBlock {
+    fn end(&self) -> usize {
+        self as *const Self as usize + self.size
+    }
+}
@@ -0,0 +1,191 @@
+#![no_std]
+
+extern crate alloc;
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::UnsafeCell;
+use core::ptr::null_mut;
+
+use gamecube_cpu::interrupts::InterruptGuard;
+
+pub use crate::dma::DmaBuffer;
+pub use crate::heap::Heap;
+
+pub mod dma;
+mod heap;
+
+/// The alignment required for buffers that are accessed by DMA or the GPU. This is also the size
+/// of a cache line, so buffers padded out to a multiple of it never share a line with unrelated
+/// data that a cache flush or invalidate would clobber.
+pub const DMA_ALIGN: usize = 32;
+
+/// Returns a layout suitable for a DMA buffer or display list of at least `size` bytes.
+///
+/// The size is rounded up to a multiple of [`DMA_ALIGN`] so that invalidating the buffer's cache
+/// lines cannot discard writes to a neighboring allocation.
+pub const fn dma_layout(size: usize) -> Layout {
+    let size = (size + DMA_ALIGN - 1) & !(DMA_ALIGN - 1);
+    match Layout::from_size_align(size, DMA_ALIGN) {
+        Ok(layout) => layout,
+        Err(_) => panic!("DMA buffer size overflows"),
+    }
+}
+
+/// A contiguous range of virtual addresses to manage as a heap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region {
+    start: usize,
+    end: usize,
+}
+
+impl Region {
+    /// All 24 MiB of MEM1 through the cached mirror.
+    ///
+    /// This overlaps the exception vectors, the OS globals, and the program image, so it will
+    /// usually be narrowed with [`Region::starting_at`] before use.
+    pub const MEM1: Self = Self::new(0x8000_0000, 0x8180_0000);
+
+    /// All 64 MiB of MEM2 through the cached mirror. Only present on the Wii.
+    pub const MEM2: Self = Self::new(0x9000_0000, 0x9400_0000);
+
+    /// Describes the half-open range `start..end`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `end` is less than `start`.
+    pub const fn new(start: usize, end: usize) -> Self {
+        assert!(start <= end);
+        Self { start, end }
+    }
+
+    /// Returns the portion of this region at or above `start`, which is typically the end of the
+    /// program image as reported by the linker.
+    pub const fn starting_at(self, start: usize) -> Self {
+        if start <= self.start {
+            self
+        } else if start >= self.end {
+            Self::new(self.end, self.end)
+        } else {
+            Self::new(start, self.end)
+        }
+    }
+
+    /// Returns the portion of this region below `end`, for example to reserve space at the top of
+    /// memory for framebuffers.
+    pub const fn ending_at(self, end: usize) -> Self {
+        if end >= self.end {
+            self
+        } else if end <= self.start {
+            Self::new(self.start, self.start)
+        } else {
+            Self::new(self.start, end)
+        }
+    }
+
+    pub const fn start(&self) -> usize {
+        self.start
+    }
+
+    pub const fn end(&self) -> usize {
+        self.end
+    }
+
+    pub const fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// A first-fit free-list allocator suitable for use as the `#[global_allocator]`.
+///
+/// Allocation and deallocation run with external interrupts disabled, so it is safe to allocate
+/// from interrupt handlers as well as from the main thread.
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: LinkedListAllocator = LinkedListAllocator::new();
+///
+/// unsafe { ALLOCATOR.init(Region::MEM1.starting_at(heap_start)) };
+/// ```
+pub struct LinkedListAllocator {
+    heap: UnsafeCell<Heap>,
+}
+
+// SAFETY: All access to the heap happens with external interrupts disabled on a single-core CPU.
+unsafe impl Sync for LinkedListAllocator {}
+
+impl LinkedListAllocator {
+    /// Creates an allocator with no memory to manage. Every allocation will fail until
+    /// [`LinkedListAllocator::init`] or [`LinkedListAllocator::add_region`] is called.
+    pub const fn new() -> Self {
+        Self {
+            heap: UnsafeCell::new(Heap::empty()),
+        }
+    }
+
+    /// Hands `region` to the allocator.
+    ///
+    /// # Safety
+    ///
+    /// The region must be valid, writable memory that nothing else uses for the rest of the
+    /// program. It must not overlap any region previously given to this allocator.
+    pub unsafe fn init(&self, region: Region) {
+        unsafe { self.add_region(region) };
+    }
+
+    /// Hands an additional region to the allocator, for example MEM2 on the Wii.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`LinkedListAllocator::init`].
+    pub unsafe fn add_region(&self, region: Region) {
+        let _guard = unsafe { InterruptGuard::new() };
+        unsafe { (*self.heap.get()).add_region(region) };
+    }
+
+    /// Returns the total number of free bytes, including fragments too small to be useful.
+    pub fn free_bytes(&self) -> usize {
+        let _guard = unsafe { InterruptGuard::new() };
+        unsafe { (*self.heap.get()).free_bytes() }
+    }
+
+    /// Allocates an uninitialized buffer of at least `size` bytes with [`dma_layout`]. The buffer
+    /// must be released with [`LinkedListAllocator::dealloc_dma`] using the same `size`.
+    pub fn alloc_dma(&self, size: usize) -> *mut u8 {
+        unsafe { self.alloc(dma_layout(size)) }
+    }
+
+    /// Releases a buffer returned by [`LinkedListAllocator::alloc_dma`].
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have come from [`LinkedListAllocator::alloc_dma`] on this allocator with the
+    /// same `size`, and must not be used afterward.
+    pub unsafe fn dealloc_dma(&self, ptr: *mut u8, size: usize) {
+        unsafe { self.dealloc(ptr, dma_layout(size)) };
+    }
+}
+
+impl Default for LinkedListAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl GlobalAlloc for LinkedListAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _guard = unsafe { InterruptGuard::new() };
+        match unsafe { (*self.heap.get()).allocate(layout) } {
+            Some(ptr) => ptr.as_ptr(),
+            None => null_mut(),
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let _guard = unsafe { InterruptGuard::new() };
+        unsafe { (*self.heap.get()).deallocate(ptr, layout) };
+    }
+}
@@ -40,3 +40,25 @@ pub fn flush_data_cache_blocks(ptr: *const (), block_count: usize) {
         ptr += 32;
     }
 }
+
+/// Invalidates a sequence of 32-byte blocks from the CPU data cache starting with the given
+/// address, discarding any cached writes so the next reads come from main memory.
+///
+/// # Safety
+///
+/// Every byte in the blocks must be fine to revert to whatever main memory holds, including
+/// bytes that merely share a block with the data of interest.
+pub unsafe fn invalidate_data_cache_blocks(ptr: *const (), block_count: usize) {
+    let mut ptr = ptr as usize;
+    for _ in 0..block_count {
+        // SAFETY: The caller guarantees that discarding these blocks loses nothing.
+        unsafe {
+            asm!(
+                "dcbi 0,{r}",
+                r = in(reg) ptr,
+                options(preserves_flags, nostack),
+            )
+        }
+        ptr += 32;
+    }
+}
@@ -1,5 +1,9 @@
+use core::marker::PhantomData;
+
 use crate::registers::msr::*;
 
+pub mod vectors;
+
 /// Disables external interrupts and returns whether they were enabled.
 ///
 /// # Safety
@@ -23,17 +27,85 @@ pub unsafe fn enable_external_interrupts() {
 }
 
 /// Invokes a function with external interrupts disabled. The previous enabled state is restored
-/// after it returns successfully.
+/// after it returns.
 ///
 /// # Safety
 ///
 /// ???
 #[inline(always)]
 pub unsafe fn with_external_interrupts_disabled<T>(f: impl FnOnce() -> T) -> T {
-    let was_enabled = unsafe { disable_external_interrupts() };
-    let result = f();
-    if was_enabled {
-        unsafe { enable_external_interrupts() };
+    let _guard = unsafe { InterruptGuard::new() };
+    f()
+}
+
+/// Keeps external interrupts disabled for as long as it is alive.
+///
+/// Dropping the guard restores MSR\[EE\] to whatever it was when the guard was created, so guards
+/// nest correctly as long as they are dropped in the reverse order of their creation. Only the
+/// outermost guard actually re-enables interrupts.
+///
+/// The guard is neither `Send` nor `Sync`. It describes the state of the CPU that created it and
+/// must not be dropped from another thread.
+#[must_use = "interrupts are restored as soon as the guard is dropped"]
+pub struct InterruptGuard {
+    was_enabled: bool,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl InterruptGuard {
+    /// Disables external interrupts until the returned guard is dropped.
+    ///
+    /// # Safety
+    ///
+    /// ???
+    #[inline(always)]
+    pub unsafe fn new() -> Self {
+        Self {
+            was_enabled: unsafe { disable_external_interrupts() },
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Returns whether external interrupts were enabled when this guard was created.
+    #[inline(always)]
+    pub fn was_enabled(&self) -> bool {
+        self.was_enabled
+    }
+}
+
+impl Drop for InterruptGuard {
+    #[inline(always)]
+    fn drop(&mut self) {
+        if self.was_enabled {
+            // SAFETY: This restores the state that was observed when the guard was created.
+            unsafe { enable_external_interrupts() };
+        }
+    }
+}
+
+#[cfg(feature = "critical-section")]
+mod critical_section_impl {
+    use critical_section::RawRestoreState;
+
+    use super::{disable_external_interrupts, enable_external_interrupts};
+
+    /// Backs the `critical-section` crate with MSR\[EE\]. The GameCube has a single core, so masking
+    /// external interrupts is sufficient for mutual exclusion.
+    struct ExternalInterruptCriticalSection;
+
+    critical_section::set_impl!(ExternalInterruptCriticalSection);
+
+    // SAFETY: Acquire returns the previous MSR[EE] state and release restores it, so nested
+    // critical sections only re-enable interrupts when the outermost one is released.
+    unsafe impl critical_section::Impl for ExternalInterruptCriticalSection {
+        unsafe fn acquire() -> RawRestoreState {
+            unsafe { disable_external_interrupts() }
+        }
+
+        unsafe fn release(was_enabled: RawRestoreState) {
+            if was_enabled {
+                unsafe { enable_external_interrupts() };
+            }
+        }
     }
-    result
 }
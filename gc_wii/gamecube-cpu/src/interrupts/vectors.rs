@@ -0,0 +1,295 @@
+//! Exception handlers installed directly at the PowerPC vectors, without libogc.
+//!
+//! Each installed vector holds a short stub that turns address translation back on and branches to
+//! a shared trampoline. The trampoline pushes an [`ExceptionFrame`] onto the interrupted code's
+//! stack, saving every register the C ABI lets a function clobber, then calls the registered Rust
+//! handler. Because the frame lives on the stack and SRR0/SRR1 are saved before MSR\[RI\] is set,
+//! a handler may itself take exceptions, and may allow external interrupts to nest.
+//!
+//! The floating point registers are not saved. Handlers run with MSR\[FP\] clear, so any floating
+//! point instruction in a handler raises a floating point unavailable exception.
+
+use core::arch::global_asm;
+use core::mem::size_of;
+
+use crate::cache::{flush_data_cache_block, invalidate_instruction_cache_block};
+use crate::interrupts::{
+    disable_external_interrupts, enable_external_interrupts, with_external_interrupts_disabled,
+};
+
+/// An exception that can be given a Rust handler.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Exception {
+    /// A data access failed. DSISR and DAR describe the access.
+    Dsi,
+    /// An instruction fetch failed. SRR1 describes the fetch.
+    Isi,
+    /// MSR\[EE\] is set and the processor interface is asserting its interrupt line.
+    ExternalInterrupt,
+    /// MSR\[EE\] is set and the decrementer passed zero. The handler must reload the decrementer or
+    /// the exception fires again almost immediately.
+    Decrementer,
+}
+
+impl Exception {
+    const COUNT: usize = 4;
+
+    /// The exception's vector, as an offset from the base of memory.
+    pub const fn vector(self) -> usize {
+        match self {
+            Self::Dsi => 0x0300,
+            Self::Isi => 0x0400,
+            Self::ExternalInterrupt => 0x0500,
+            Self::Decrementer => 0x0900,
+        }
+    }
+
+    const fn index(self) -> usize {
+        match self {
+            Self::Dsi => 0,
+            Self::Isi => 1,
+            Self::ExternalInterrupt => 2,
+            Self::Decrementer => 3,
+        }
+    }
+
+    const fn from_index(index: usize) -> Self {
+        match index {
+            0 => Self::Dsi,
+            1 => Self::Isi,
+            2 => Self::ExternalInterrupt,
+            3 => Self::Decrementer,
+            _ => panic!("exception index out of range"),
+        }
+    }
+}
+
+/// The interrupted state, as saved by the trampoline. The handler may modify it, for example to
+/// advance `srr0` past a faulting instruction. Nonvolatile registers and r1 are not included; the
+/// handler preserves them by following the ABI.
+///
+/// The layout is shared with the trampoline's assembly.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct ExceptionFrame {
+    /// The address execution resumes at.
+    pub srr0: u32,
+    /// The machine state that is restored on return.
+    pub srr1: u32,
+    pub r0: u32,
+    /// r3 through r12.
+    pub gprs: [u32; 10],
+    pub ctr: u32,
+    pub xer: u32,
+    pub lr: u32,
+    pub cr: u32,
+}
+
+const _: () = assert!(size_of::<ExceptionFrame>() == 68);
+
+/// Whether external interrupts may interrupt a handler.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Nesting {
+    /// External interrupts and the decrementer stay masked until the handler returns.
+    Disabled,
+    /// MSR\[EE\] is set while the handler runs, so external interrupts and the decrementer can
+    /// preempt it. The handler must already have silenced whatever raised its own exception.
+    Enabled,
+}
+
+pub type ExceptionHandler = fn(&mut ExceptionFrame);
+
+static mut HANDLERS: [Option<(ExceptionHandler, Nesting)>; Exception::COUNT] =
+    [None; Exception::COUNT];
+
+extern "C" {
+    static gamecube_cpu_dsi_vector_start: u32;
+    static gamecube_cpu_dsi_vector_end: u32;
+    static gamecube_cpu_isi_vector_start: u32;
+    static gamecube_cpu_isi_vector_end: u32;
+    static gamecube_cpu_external_interrupt_vector_start: u32;
+    static gamecube_cpu_external_interrupt_vector_end: u32;
+    static gamecube_cpu_decrementer_vector_start: u32;
+    static gamecube_cpu_decrementer_vector_end: u32;
+}
+
+/// Installs `handler` for `exception`, replacing whatever was at its vector.
+///
+/// # Safety
+///
+/// Nothing else may be relying on the vector, such as libogc's own exception handling. For
+/// external interrupts and the decrementer, the handler must acknowledge the condition that raised
+/// the exception.
+pub unsafe fn install_exception_handler(
+    exception: Exception,
+    handler: ExceptionHandler,
+    nesting: Nesting,
+) {
+    let stub = unsafe {
+        match exception {
+            Exception::Dsi => {
+                &gamecube_cpu_dsi_vector_start as *const u32
+                    ..&gamecube_cpu_dsi_vector_end as *const u32
+            }
+            Exception::Isi => {
+                &gamecube_cpu_isi_vector_start as *const u32
+                    ..&gamecube_cpu_isi_vector_end as *const u32
+            }
+            Exception::ExternalInterrupt => {
+                &gamecube_cpu_external_interrupt_vector_start as *const u32
+                    ..&gamecube_cpu_external_interrupt_vector_end as *const u32
+            }
+            Exception::Decrementer => {
+                &gamecube_cpu_decrementer_vector_start as *const u32
+                    ..&gamecube_cpu_decrementer_vector_end as *const u32
+            }
+        }
+    };
+
+    with_external_interrupts_disabled(|| unsafe {
+        HANDLERS[exception.index()] = Some((handler, nesting));
+
+        // Copy the stub in 32-byte blocks, making each one visible to instruction fetch as it's
+        // completed.
+        let mut src = stub.start;
+        let mut dst = (0x8000_0000 + exception.vector()) as *mut u32;
+        while src != stub.end {
+            let dst_block_start = dst;
+            for _ in 0..8 {
+                dst.write_volatile(src.read());
+                src = src.offset(1);
+                dst = dst.offset(1);
+            }
+            flush_data_cache_block(dst_block_start as _);
+            invalidate_instruction_cache_block(dst_block_start as _);
+        }
+    });
+}
+
+/// Called by the trampoline with translation on, MSR\[EE\] clear, and MSR\[RI\] set.
+extern "C" fn dispatch_exception(index: usize, frame: &mut ExceptionFrame) {
+    let exception = Exception::from_index(index);
+    let Some((handler, nesting)) = (unsafe { HANDLERS[index] }) else {
+        panic!(
+            "Unhandled {:?} exception at {:#010x}",
+            exception, frame.srr0
+        );
+    };
+
+    if nesting == Nesting::Enabled {
+        unsafe { enable_external_interrupts() };
+    }
+    handler(frame);
+    if nesting == Nesting::Enabled {
+        unsafe { disable_external_interrupts() };
+    }
+}
+
+// The vector stubs run in real mode with nothing saved. They park r3, r4, SRR0, and SRR1 in the
+// SPRGs and return from interrupt into the trampoline with translation on and the vector's index
+// in r4. Each stub is padded to a whole number of cache blocks for the copy.
+//
+// The trampoline's stack frame holds the back chain and the callee's LR save word, followed by an
+// ExceptionFrame at offset 8. It's padded from 76 to 80 bytes to keep r1 16-byte aligned.
+global_asm!(
+    ".macro vector_stub name, index",
+    ".section .text.gamecube_cpu_\\name\\()_vector",
+    ".balign 32",
+    ".global gamecube_cpu_\\name\\()_vector_start",
+    "gamecube_cpu_\\name\\()_vector_start:",
+    "    mtspr 272, 3", // SPRG0 = r3
+    "    mtspr 273, 4", // SPRG1 = r4
+    "    mfspr 3, 26",
+    "    mtspr 274, 3", // SPRG2 = SRR0
+    "    mfspr 3, 27",
+    "    mtspr 275, 3", // SPRG3 = SRR1
+    "    lis 3, gamecube_cpu_exception_trampoline@h",
+    "    ori 3, 3, gamecube_cpu_exception_trampoline@l",
+    "    mtspr 26, 3",
+    "    li 3, 0x1030", // MSR[ME|IR|DR]
+    "    mtspr 27, 3",
+    "    li 4, \\index",
+    "    rfi",
+    ".balign 32",
+    ".global gamecube_cpu_\\name\\()_vector_end",
+    "gamecube_cpu_\\name\\()_vector_end:",
+    ".endm",
+    "",
+    "vector_stub dsi, 0",
+    "vector_stub isi, 1",
+    "vector_stub external_interrupt, 2",
+    "vector_stub decrementer, 3",
+    ".purgem vector_stub",
+    "",
+    ".section .text.gamecube_cpu_exception_trampoline",
+    ".global gamecube_cpu_exception_trampoline",
+    "gamecube_cpu_exception_trampoline:",
+    "    stwu 1, -80(1)",
+    "    stw 0, 16(1)",
+    "    mfspr 0, 272",
+    "    stw 0, 20(1)", // r3
+    "    mfspr 0, 273",
+    "    stw 0, 24(1)", // r4
+    "    stw 5, 28(1)",
+    "    stw 6, 32(1)",
+    "    stw 7, 36(1)",
+    "    stw 8, 40(1)",
+    "    stw 9, 44(1)",
+    "    stw 10, 48(1)",
+    "    stw 11, 52(1)",
+    "    stw 12, 56(1)",
+    "    mfspr 0, 274",
+    "    stw 0, 8(1)", // SRR0
+    "    mfspr 0, 275",
+    "    stw 0, 12(1)", // SRR1
+    "    mfctr 0",
+    "    stw 0, 60(1)",
+    "    mfxer 0",
+    "    stw 0, 64(1)",
+    "    mflr 0",
+    "    stw 0, 68(1)",
+    "    mfcr 0",
+    "    stw 0, 72(1)",
+    "",
+    // The SPRGs and SRRs are free again, so another exception no longer loses state.
+    "    mfmsr 0",
+    "    ori 0, 0, 0x0002", // MSR[RI]
+    "    mtmsr 0",
+    "",
+    "    mr 3, 4",
+    "    addi 4, 1, 8",
+    "    bl {dispatch}",
+    "",
+    // Clear MSR[EE] and MSR[RI] before SRR0 and SRR1 are reloaded.
+    "    mfmsr 0",
+    "    rlwinm 0, 0, 0, 17, 15",
+    "    rlwinm 0, 0, 0, 31, 29",
+    "    mtmsr 0",
+    "",
+    "    lwz 0, 8(1)",
+    "    mtspr 26, 0",
+    "    lwz 0, 12(1)",
+    "    mtspr 27, 0",
+    "    lwz 0, 60(1)",
+    "    mtctr 0",
+    "    lwz 0, 64(1)",
+    "    mtxer 0",
+    "    lwz 0, 68(1)",
+    "    mtlr 0",
+    "    lwz 0, 72(1)",
+    "    mtcr 0",
+    "    lwz 3, 20(1)",
+    "    lwz 4, 24(1)",
+    "    lwz 5, 28(1)",
+    "    lwz 6, 32(1)",
+    "    lwz 7, 36(1)",
+    "    lwz 8, 40(1)",
+    "    lwz 9, 44(1)",
+    "    lwz 10, 48(1)",
+    "    lwz 11, 52(1)",
+    "    lwz 12, 56(1)",
+    "    lwz 0, 16(1)",
+    "    addi 1, 1, 80",
+    "    rfi",
+    dispatch = sym dispatch_exception,
+);
@@ -4,4 +4,5 @@
 
 pub mod cache;
 pub mod interrupts;
+pub mod performance_monitor;
 pub mod registers;
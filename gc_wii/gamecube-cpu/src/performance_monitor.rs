@@ -0,0 +1,210 @@
+//! The 750CXe's performance monitor: four 32-bit counters, each counting one selectable event.
+//!
+//! ```ignore
+//! let monitor = PerformanceMonitor::configure([
+//!     Some(Event::Cycles),
+//!     Some(Event::InstructionsCompleted),
+//!     Some(Event::L1DataMisses),
+//!     Some(Event::MispredictedBranches),
+//! ])?;
+//! let (result, counts) = monitor.measure(|| expensive_work());
+//! ```
+
+use core::arch::asm;
+
+/// MMCR0\[DIS\]: Freezes all counters.
+const MMCR0_DISABLE: u32 = 0x8000_0000;
+const MMCR0_PMC1_SELECT_SHIFT: u32 = 6;
+const MMCR0_PMC2_SELECT_SHIFT: u32 = 0;
+const MMCR1_PMC3_SELECT_SHIFT: u32 = 27;
+const MMCR1_PMC4_SELECT_SHIFT: u32 = 22;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Counter {
+    Pmc1,
+    Pmc2,
+    Pmc3,
+    Pmc4,
+}
+
+impl Counter {
+    pub const ALL: [Self; 4] = [Self::Pmc1, Self::Pmc2, Self::Pmc3, Self::Pmc4];
+}
+
+/// An event a counter can count. Each counter supports a different subset beyond the first three.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// Any counter.
+    Cycles,
+    /// Any counter. Folded branches are not included.
+    InstructionsCompleted,
+    /// Any counter.
+    InstructionsDispatched,
+    /// PMC1 only.
+    L2Hits,
+    /// PMC1 only. Cycles the dispatcher stalls on a second unresolved branch.
+    BranchStallCycles,
+    /// PMC2 only.
+    L1InstructionMisses,
+    /// PMC2 only.
+    L2InstructionMisses,
+    /// PMC3 only.
+    L1DataMisses,
+    /// PMC3 only.
+    L2DataMisses,
+    /// PMC4 only.
+    MispredictedBranches,
+}
+
+impl Event {
+    /// The event's select field encoding for `counter`, if that counter can count it.
+    pub fn select(self, counter: Counter) -> Option<u32> {
+        match (self, counter) {
+            (Self::Cycles, _) => Some(1),
+            (Self::InstructionsCompleted, _) => Some(2),
+            (Self::InstructionsDispatched, _) => Some(4),
+            (Self::L2Hits, Counter::Pmc1) => Some(7),
+            (Self::BranchStallCycles, Counter::Pmc1) => Some(12),
+            (Self::L1InstructionMisses, Counter::Pmc2) => Some(5),
+            (Self::L2InstructionMisses, Counter::Pmc2) => Some(7),
+            (Self::L1DataMisses, Counter::Pmc3) => Some(5),
+            (Self::L2DataMisses, Counter::Pmc3) => Some(7),
+            (Self::MispredictedBranches, Counter::Pmc4) => Some(8),
+            _ => None,
+        }
+    }
+}
+
+/// Returned when an event was assigned to a counter that can't count it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnsupportedEvent {
+    pub counter: Counter,
+    pub event: Event,
+}
+
+/// Counter values, indexed like `Counter::ALL`.
+pub type Counts = [u32; 4];
+
+/// Proof that the counters have been configured. Only one configuration is in effect at a time;
+/// configuring again changes what every existing monitor reads.
+#[derive(Clone, Copy, Debug)]
+pub struct PerformanceMonitor {
+    events: [Option<Event>; 4],
+}
+
+impl PerformanceMonitor {
+    /// Resets all counters and starts them counting the given events. Counters assigned `None`
+    /// hold at zero.
+    pub fn configure(events: [Option<Event>; 4]) -> Result<Self, UnsupportedEvent> {
+        let mut selects = [0; 4];
+        for ((select, counter), event) in selects.iter_mut().zip(Counter::ALL).zip(events) {
+            if let Some(event) = event {
+                *select = event
+                    .select(counter)
+                    .ok_or(UnsupportedEvent { counter, event })?;
+            }
+        }
+
+        // SAFETY: The performance monitor registers don't affect program state. Counter
+        // interrupts stay disabled.
+        unsafe {
+            write_mmcr0(MMCR0_DISABLE);
+            write_pmcs([0; 4]);
+            write_mmcr1(
+                selects[2] << MMCR1_PMC3_SELECT_SHIFT | selects[3] << MMCR1_PMC4_SELECT_SHIFT,
+            );
+            write_mmcr0(
+                selects[0] << MMCR0_PMC1_SELECT_SHIFT | selects[1] << MMCR0_PMC2_SELECT_SHIFT,
+            );
+        }
+        Ok(Self { events })
+    }
+
+    pub fn events(&self) -> [Option<Event>; 4] {
+        self.events
+    }
+
+    pub fn read(&self) -> Counts {
+        read_pmcs()
+    }
+
+    /// Runs `f` and returns how much each counter advanced. Anything else running in the meantime,
+    /// like interrupt handlers, is counted too.
+    pub fn measure<T>(&self, f: impl FnOnce() -> T) -> (T, Counts) {
+        let before = self.read();
+        let result = f();
+        let after = self.read();
+        let mut deltas = [0; 4];
+        for ((delta, after), before) in deltas.iter_mut().zip(after).zip(before) {
+            *delta = after.wrapping_sub(before);
+        }
+        (result, deltas)
+    }
+
+    /// Freezes all counters. They keep their values until the next `configure`.
+    pub fn stop(self) {
+        // SAFETY: See `configure`.
+        unsafe { write_mmcr0(MMCR0_DISABLE) };
+    }
+}
+
+macro_rules! spr_readers {
+    ($($name:ident = $spr:literal;)*) => {
+        $(
+            #[inline(always)]
+            fn $name() -> u32 {
+                let result;
+                unsafe {
+                    asm!(
+                        concat!("mfspr {r}, ", stringify!($spr)),
+                        r = out(reg) result,
+                        options(nomem, preserves_flags, nostack),
+                    );
+                }
+                result
+            }
+        )*
+    };
+}
+
+macro_rules! spr_writers {
+    ($($name:ident = $spr:literal;)*) => {
+        $(
+            #[inline(always)]
+            unsafe fn $name(value: u32) {
+                asm!(
+                    concat!("mtspr ", stringify!($spr), ", {r}"),
+                    r = in(reg) value,
+                    options(nomem, preserves_flags, nostack),
+                );
+            }
+        )*
+    };
+}
+
+spr_readers! {
+    read_pmc1 = 953;
+    read_pmc2 = 954;
+    read_pmc3 = 957;
+    read_pmc4 = 958;
+}
+
+spr_writers! {
+    write_mmcr0 = 952;
+    write_pmc1 = 953;
+    write_pmc2 = 954;
+    write_mmcr1 = 956;
+    write_pmc3 = 957;
+    write_pmc4 = 958;
+}
+
+fn read_pmcs() -> Counts {
+    [read_pmc1(), read_pmc2(), read_pmc3(), read_pmc4()]
+}
+
+unsafe fn write_pmcs(values: Counts) {
+    write_pmc1(values[0]);
+    write_pmc2(values[1]);
+    write_pmc3(values[2]);
+    write_pmc4(values[3]);
+}
@@ -2,6 +2,9 @@ use core::arch::asm;
 
 pub mod msr;
 
+/// The time base runs at a quarter of the 162 MHz bus clock.
+pub const TIME_BASE_TICKS_PER_MS: u64 = 40_500;
+
 pub fn time_base() -> u64 {
     loop {
         let tbu1: u32;
@@ -45,3 +48,67 @@ pub fn set_decrementer(value: u32) {
         );
     }
 }
+
+/// Reads r1, the stack pointer.
+#[inline(always)]
+pub fn stack_pointer() -> u32 {
+    let result;
+    unsafe {
+        asm!(
+            "mr {r}, 1",
+            r = out(reg) result,
+            options(nomem, preserves_flags, nostack),
+        );
+    }
+    result
+}
+
+/// Reads the link register. When inlined at the top of a function, this is the caller's return
+/// address.
+#[inline(always)]
+pub fn link_register() -> u32 {
+    let result;
+    unsafe {
+        asm!(
+            "mflr {r}",
+            r = out(reg) result,
+            options(nomem, preserves_flags, nostack),
+        );
+    }
+    result
+}
+
+macro_rules! spr_readers {
+    ($($(#[$attr:meta])* $name:ident = $spr:literal;)*) => {
+        $(
+            $(#[$attr])*
+            #[inline(always)]
+            pub fn $name() -> u32 {
+                let result;
+                unsafe {
+                    asm!(
+                        concat!("mfspr {r}, ", stringify!($spr)),
+                        r = out(reg) result,
+                        options(nomem, preserves_flags, nostack),
+                    );
+                }
+                result
+            }
+        )*
+    };
+}
+
+spr_readers! {
+    /// Reads DSISR, which describes the cause of the most recent DSI or alignment exception.
+    dsisr = 18;
+    /// Reads DAR, the effective address of the most recent DSI or alignment exception.
+    dar = 19;
+    /// Reads SRR0, the address an exception handler will return to.
+    srr0 = 26;
+    /// Reads SRR1, the machine state an exception handler will restore.
+    srr1 = 27;
+    /// Reads HID2, which holds the paired-single, locked cache, and DMA queue configuration.
+    hid2 = 920;
+    /// Reads HID0, which holds the cache and branch prediction configuration.
+    hid0 = 1008;
+}
@@ -0,0 +1,171 @@
+//! DTK audio streaming, where the drive decodes an ADPCM track straight off the disc and feeds the
+//! samples to the audio interface without touching main memory.
+//!
+//! The drive half is configured with DI commands on `DvdDriver`. The AI half, which decides
+//! whether the samples are heard and counts them as they play, is `AudioStreamOutput`.
+
+use gamecube_mmio::audio_interface::{AudioInterface, Control, StreamingSampleRate, Volume};
+use gamecube_mmio::dvd_interface::CommandA;
+
+use crate::{Command, DiscHeader, DvdDriver, DvdError};
+
+/// Tracks start on 32 KiB boundaries and span a whole number of 32 KiB blocks.
+pub const STREAM_ALIGNMENT: u32 = 0x8000;
+
+/// Each 32-byte ADPCM frame decodes to 28 stereo samples.
+const FRAME_SIZE: u32 = 32;
+const SAMPLES_PER_FRAME: u32 = 28;
+
+/// The buffer size the SDK uses when the disc header leaves it zero.
+const DEFAULT_BUFFER_SIZE: u8 = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AudioStreamStatus {
+    pub playing: bool,
+    /// The byte offset the drive is currently decoding.
+    pub position: u32,
+    /// The byte offset of the track being played.
+    pub start: u32,
+    /// The length in bytes of the track being played.
+    pub length: u32,
+}
+
+impl DvdDriver {
+    /// Enables or disables streaming in the drive. The drive only streams from discs that declare
+    /// it in their header, and this must be sent after `read_disc_id()` and before other reads.
+    pub fn configure_audio_buffer(&mut self, header: &DiscHeader) -> Result<(), DvdError> {
+        let buffer_size = match header.stream_buffer_size {
+            0 => DEFAULT_BUFFER_SIZE,
+            size => size,
+        };
+        self.immediate_command(Command {
+            a: CommandA::zero()
+                .with_command(0xe4)
+                .with_subcommand1(header.audio_streaming as u8)
+                .with_subcommand2((buffer_size & 0xf) as u16),
+            b: 0,
+            c: 0,
+        })?;
+        Ok(())
+    }
+
+    /// Starts streaming the track at `offset`. If a track is already playing, it finishes its
+    /// current pass and this one plays next. Either way the new track loops until replaced or
+    /// stopped.
+    pub fn play_audio_stream(&mut self, offset: u32, length: u32) -> Result<(), DvdError> {
+        assert_eq!(offset % STREAM_ALIGNMENT, 0);
+        assert_eq!(length % STREAM_ALIGNMENT, 0);
+        assert_ne!(length, 0);
+        self.audio_stream_command(0, offset / 4, length)
+    }
+
+    /// Lets the current track play to its end and then stops, instead of looping.
+    pub fn stop_audio_stream_at_end(&mut self) -> Result<(), DvdError> {
+        self.audio_stream_command(0, 0, 0)
+    }
+
+    /// Stops streaming immediately.
+    pub fn stop_audio_stream(&mut self) -> Result<(), DvdError> {
+        self.audio_stream_command(1, 0, 0)
+    }
+
+    pub fn audio_stream_status(&mut self) -> Result<AudioStreamStatus, DvdError> {
+        Ok(AudioStreamStatus {
+            playing: self.audio_status_command(0)? != 0,
+            position: self.audio_status_command(1)? * 4,
+            start: self.audio_status_command(2)? * 4,
+            length: self.audio_status_command(3)?,
+        })
+    }
+
+    fn audio_stream_command(&mut self, subcommand: u8, b: u32, c: u32) -> Result<(), DvdError> {
+        self.immediate_command(Command {
+            a: CommandA::zero()
+                .with_command(0xe1)
+                .with_subcommand1(subcommand),
+            b,
+            c,
+        })?;
+        Ok(())
+    }
+
+    fn audio_status_command(&mut self, subcommand: u8) -> Result<u32, DvdError> {
+        self.immediate_command(Command {
+            a: CommandA::zero()
+                .with_command(0xe2)
+                .with_subcommand1(subcommand),
+            b: 0,
+            c: 0,
+        })
+    }
+}
+
+/// The audio interface's side of streaming: whether the drive's samples are played, how loud, and
+/// how many have played so far.
+pub struct AudioStreamOutput {
+    ai: AudioInterface,
+}
+
+impl AudioStreamOutput {
+    /// Takes over the AI with streaming disabled and silenced.
+    pub fn new(ai: AudioInterface) -> Self {
+        ai.write_volume(Volume::zero());
+        ai.write_control(Control::zero().with_interrupt(true));
+        Self { ai }
+    }
+
+    /// The number of stereo samples in `length` bytes of stream data.
+    pub fn samples_in(length: u32) -> u32 {
+        length / FRAME_SIZE * SAMPLES_PER_FRAME
+    }
+
+    /// Starts playing the drive's samples and counting them from zero. DTK tracks are 48 kHz.
+    pub fn enable(&mut self, sample_rate: StreamingSampleRate) {
+        self.ai.modify_control(|control| {
+            control
+                .with_interrupt(false)
+                .with_streaming_sample_rate(sample_rate)
+                .with_sample_counter_reset(true)
+                .with_streaming_enable(true)
+        });
+    }
+
+    pub fn disable(&mut self) {
+        self.ai
+            .modify_control(|control| control.with_interrupt(false).with_streaming_enable(false));
+    }
+
+    pub fn set_volume(&mut self, left: u8, right: u8) {
+        self.ai
+            .write_volume(Volume::zero().with_left(left).with_right(right));
+    }
+
+    /// Stereo samples played since streaming was enabled.
+    pub fn sample_count(&self) -> u32 {
+        self.ai.read_sample_counter()
+    }
+
+    /// Arranges for `sample_count()` reaching `count` to be flagged, for example to notice the end
+    /// of a track played with `stop_audio_stream_at_end()`. `None` stops flagging.
+    pub fn set_sample_alarm(&mut self, count: Option<u32>) {
+        if let Some(count) = count {
+            self.ai.write_interrupt_timing(count);
+        }
+        self.ai.modify_control(|control| {
+            control
+                .with_interrupt(true)
+                .with_interrupt_timing_disable(count.is_none())
+        });
+    }
+
+    /// Returns whether the sample alarm has gone off, and clears it.
+    pub fn take_sample_alarm(&mut self) -> bool {
+        let control = self.ai.read_control();
+        if control.interrupt() {
+            self.ai.write_control(control);
+            true
+        } else {
+            false
+        }
+    }
+}
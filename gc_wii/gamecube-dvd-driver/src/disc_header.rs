@@ -0,0 +1,41 @@
+/// The fields of the disc header (`boot.bin`) needed to identify a disc and find its file system.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DiscHeader {
+    /// Console ID, game ID, and region, such as `GGME`.
+    pub game_code: [u8; 4],
+    pub maker_code: [u8; 2],
+    /// Zero-based index of this disc in a multi-disc game.
+    pub disc_number: u8,
+    pub version: u8,
+    pub audio_streaming: bool,
+    pub stream_buffer_size: u8,
+    pub dol_offset: u32,
+    pub fst_offset: u32,
+    pub fst_size: u32,
+    /// The largest FST of any disc in the game, for loaders that reuse one buffer across discs.
+    pub fst_max_size: u32,
+}
+
+impl DiscHeader {
+    /// Byte offset of the block holding the DOL and FST fields.
+    pub(crate) const LAYOUT_OFFSET: usize = 0x420;
+
+    /// Parses the 32-byte disc ID returned by the drive and the 32 bytes at
+    /// [`Self::LAYOUT_OFFSET`].
+    pub(crate) fn parse(disc_id: &[u8; 32], layout: &[u8; 32]) -> Self {
+        let word =
+            |offset: usize| u32::from_be_bytes(layout[offset..offset + 4].try_into().unwrap());
+        Self {
+            game_code: disc_id[0..4].try_into().unwrap(),
+            maker_code: disc_id[4..6].try_into().unwrap(),
+            disc_number: disc_id[6],
+            version: disc_id[7],
+            audio_streaming: disc_id[8] != 0,
+            stream_buffer_size: disc_id[9],
+            dol_offset: word(0x00),
+            fst_offset: word(0x04),
+            fst_size: word(0x08),
+            fst_max_size: word(0x0c),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use core::cell::UnsafeCell;
+use core::ffi::c_void;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use gamecube_mmio::dvd_interface::{Cover, DvdInterface};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DvdEvent {
+    CoverOpened,
+    CoverClosed,
+    /// A reset started with `DvdDriver::start_reset` has had time to finish.
+    ResetComplete,
+}
+
+const QUEUE_LEN: usize = 8;
+
+/// A single-producer, single-consumer ring of cover events. The interrupt handler is the only
+/// producer and the `DvdDriver` is the only consumer.
+struct EventQueue {
+    events: UnsafeCell<[DvdEvent; QUEUE_LEN]>,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+unsafe impl Sync for EventQueue {}
+
+impl EventQueue {
+    const fn new() -> Self {
+        Self {
+            events: UnsafeCell::new([DvdEvent::CoverClosed; QUEUE_LEN]),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    /// Drops the event if the queue is full. Only the most recent cover state really matters, and
+    /// the consumer can always read it directly.
+    fn push(&self, event: DvdEvent) {
+        let tail = self.tail.load(Ordering::Relaxed);
+        if tail.wrapping_sub(self.head.load(Ordering::Acquire)) == QUEUE_LEN {
+            return;
+        }
+        unsafe { (*self.events.get())[tail % QUEUE_LEN] = event };
+        self.tail.store(tail.wrapping_add(1), Ordering::Release);
+    }
+
+    fn pop(&self) -> Option<DvdEvent> {
+        let head = self.head.load(Ordering::Relaxed);
+        if head == self.tail.load(Ordering::Acquire) {
+            return None;
+        }
+        let event = unsafe { (*self.events.get())[head % QUEUE_LEN] };
+        self.head.store(head.wrapping_add(1), Ordering::Release);
+        Some(event)
+    }
+
+    fn clear(&self) {
+        self.head
+            .store(self.tail.load(Ordering::Acquire), Ordering::Release);
+    }
+}
+
+static COVER_EVENTS: EventQueue = EventQueue::new();
+
+pub(crate) fn pop_cover_event() -> Option<DvdEvent> {
+    COVER_EVENTS.pop()
+}
+
+pub(crate) fn clear_cover_events() {
+    COVER_EVENTS.clear();
+}
+
+pub(crate) unsafe extern "C" fn on_di_interrupt(_irq: u32, _ctx: *mut c_void) {
+    // SAFETY: The driver only writes the cover register with this interrupt masked, and the
+    // handler touches nothing else.
+    let di = unsafe { DvdInterface::steal() };
+    let cover = di.read_cover();
+    if cover.interrupt() {
+        // Acknowledge the interrupt, leaving it enabled.
+        di.write_cover(Cover::zero().with_mask(true).with_interrupt(true));
+        COVER_EVENTS.push(if cover.state() {
+            DvdEvent::CoverOpened
+        } else {
+            DvdEvent::CoverClosed
+        });
+    }
+}
@@ -1,17 +1,39 @@
-#![feature(allocator_api)]
 #![no_std]
 
+extern crate alloc;
+
 use core::intrinsics::transmute;
 use core::mem::MaybeUninit;
 use core::sync::atomic::{compiler_fence, Ordering};
 
-use aligned::{Aligned, A32};
-use gamecube_cpu::interrupts::with_external_interrupts_disabled;
+use gamecube_alloc::DmaBuffer;
+use gamecube_cpu::registers::TIME_BASE_TICKS_PER_MS;
 use gamecube_mmio::dvd_interface::*;
 use gamecube_mmio::processor_interface::ProcessorInterface;
-use ogc_sys::DCInvalidateRange;
 use snafu::Snafu;
 
+use crate::platform::{
+    free_di_interrupt, prepare_for_device_write, request_di_interrupt, time_base,
+    with_external_interrupts_disabled,
+};
+
+pub use crate::audio_stream::{AudioStreamOutput, AudioStreamStatus, STREAM_ALIGNMENT};
+pub use crate::disc_header::DiscHeader;
+pub use crate::events::DvdEvent;
+pub use crate::queue::{Completion, DvdQueue, Priority, RequestId, SECTOR_SIZE};
+
+mod audio_stream;
+mod disc_header;
+mod events;
+mod platform;
+mod queue;
+
+/// How long the drive's reset line is held, as libogc does: 12 microseconds.
+const RESET_PULSE_TICKS: u64 = 12 * TIME_BASE_TICKS_PER_MS / 1000;
+
+/// How long the drive needs after a hard reset before it accepts commands.
+const RESET_DURATION_MS: u64 = 115;
+
 struct Command {
     a: CommandA,
     b: u32,
@@ -26,37 +48,64 @@ pub enum DvdError {
 
 pub struct DvdDriver {
     di: DvdInterface,
+    cover_events_enabled: bool,
+    reset_deadline: Option<u64>,
 }
 
 impl DvdDriver {
     pub fn new(di: DvdInterface) -> Self {
-        Self { di }
+        Self {
+            di,
+            cover_events_enabled: false,
+            reset_deadline: None,
+        }
     }
 
     pub fn inquiry(&mut self) -> Result<[u8; 32], DvdError> {
-        let mut aligned = Aligned::<A32, _>([0; 32]);
+        let mut buf = DmaBuffer::<u8>::zeroed(32);
         self.dma_read_command(
             Command {
                 a: CommandA::zero().with_command(0x12),
                 b: 0,
                 c: 0x20,
             },
-            &mut *aligned,
+            &mut buf,
         )?;
-        Ok(*aligned)
+        Ok((*buf).try_into().unwrap())
     }
 
-    pub fn read_disc_id(&mut self) -> Result<[u8; 32], DvdError> {
-        let mut aligned = Aligned::<A32, _>([0; 32]);
+    /// Reads the disc header. The drive needs the disc ID read once after every reset before it
+    /// accepts other reads, so this also readies the drive.
+    pub fn read_disc_id(&mut self) -> Result<DiscHeader, DvdError> {
+        let mut disc_id = DmaBuffer::<u8>::zeroed(32);
         self.dma_read_command(
             Command {
                 a: CommandA::zero().with_command(0xa8).with_subcommand2(0x0040),
                 b: 0,
-                c: aligned.len() as u32,
+                c: disc_id.len() as u32,
             },
-            &mut *aligned,
+            &mut disc_id,
         )?;
-        Ok(*aligned)
+
+        let mut layout = DmaBuffer::<u8>::zeroed(32);
+        self.read(DiscHeader::LAYOUT_OFFSET, &mut layout)?;
+        Ok(DiscHeader::parse(
+            (*disc_id).try_into().unwrap(),
+            (*layout).try_into().unwrap(),
+        ))
+    }
+
+    /// Reads the file system table described by `header`.
+    pub fn read_fst(&mut self, header: &DiscHeader) -> Result<DmaBuffer<u8>, DvdError> {
+        let mut data = DmaBuffer::zeroed(header.fst_size as usize);
+        self.read_into(header.fst_offset as usize, &mut data)?;
+        Ok(data)
+    }
+
+    /// Fills `buf` from `offset`, reading on into its padding so the transfer covers whole cache
+    /// lines.
+    pub fn read_into(&mut self, offset: usize, buf: &mut DmaBuffer<u8>) -> Result<(), DvdError> {
+        self.read(offset, buf.dma_bytes_mut())
     }
 
     pub fn read(&mut self, offset: usize, buf: &mut [u8]) -> Result<(), DvdError> {
@@ -96,15 +145,117 @@ impl DvdDriver {
         while open != self.di.read_cover().state() {}
     }
 
-    pub fn reset(&mut self, pi: ProcessorInterface) {
+    pub fn is_cover_open(&self) -> bool {
+        self.di.read_cover().state()
+    }
+
+    /// Installs an interrupt handler that queues an event each time the cover opens or closes, so
+    /// callers can keep running while waiting for a disc swap. Events are read with
+    /// `poll_event()`.
+    pub fn enable_cover_events(&mut self) {
+        if self.cover_events_enabled {
+            return;
+        }
+        unsafe {
+            with_external_interrupts_disabled(|| {
+                events::clear_cover_events();
+                self.di
+                    .write_cover(Cover::zero().with_mask(true).with_interrupt(true));
+                request_di_interrupt(events::on_di_interrupt);
+            });
+        }
+        self.cover_events_enabled = true;
+    }
+
+    pub fn disable_cover_events(&mut self) {
+        if !self.cover_events_enabled {
+            return;
+        }
+        unsafe {
+            with_external_interrupts_disabled(|| {
+                free_di_interrupt();
+                self.di.write_cover(Cover::zero().with_interrupt(true));
+                events::clear_cover_events();
+            });
+        }
+        self.cover_events_enabled = false;
+    }
+
+    /// Returns the next pending event, if any. Cover events are only produced while enabled with
+    /// `enable_cover_events()`.
+    pub fn poll_event(&mut self) -> Option<DvdEvent> {
+        if let Some(event) = events::pop_cover_event() {
+            return Some(event);
+        }
+        match self.reset_deadline {
+            Some(deadline) if time_base() >= deadline => {
+                self.reset_deadline = None;
+                Some(DvdEvent::ResetComplete)
+            }
+            _ => None,
+        }
+    }
+
+    /// Performs a hard reset and blocks until the drive is ready.
+    pub fn reset(&mut self, pi: &ProcessorInterface) {
+        self.start_reset(pi);
+        while time_base() < self.reset_deadline.unwrap() {}
+        self.reset_deadline = None;
+    }
+
+    /// Starts a hard reset without waiting for the drive. `poll_event()` returns
+    /// `DvdEvent::ResetComplete` once the drive is ready.
+    pub fn start_reset(&mut self, pi: &ProcessorInterface) {
         unsafe {
             with_external_interrupts_disabled(|| {
-                // Perform a hard reset. I'm not sure what the individual bits or writes do.
-                pi.modify_di_control(|x| (x & !4) | 1);
-                pi.modify_di_control(|x| x | 5);
+                // Pulse the drive's reset line.
+                pi.set_dvd_reset(true);
+                let release = time_base() + RESET_PULSE_TICKS;
+                while time_base() < release {}
+                pi.set_dvd_reset(false);
             });
-            libc::usleep(115000);
         }
+        self.reset_deadline = Some(time_base() + RESET_DURATION_MS * TIME_BASE_TICKS_PER_MS);
+    }
+
+    pub fn is_reset_in_progress(&self) -> bool {
+        self.reset_deadline.is_some()
+    }
+
+    /// Issues a command that transfers no data and returns the drive's 32-bit reply.
+    fn immediate_command(&mut self, command: Command) -> Result<u32, DvdError> {
+        // Acknowledge all pending interrupts.
+        self.di.write_status(
+            Status::zero()
+                .with_break_complete_interrupt(true)
+                .with_transfer_complete_interrupt(true)
+                .with_device_error_interrupt(true),
+        );
+
+        self.di.write_command_buffer_a(command.a);
+        self.di.write_command_buffer_b(command.b);
+        self.di.write_command_buffer_c(command.c);
+        self.di.write_control(
+            Control::zero()
+                .with_access(Access::Read)
+                .with_transfer(true),
+        );
+
+        loop {
+            let status = self.di.read_status();
+            if status.device_error_interrupt() {
+                self.di
+                    .write_status(Status::zero().with_device_error_interrupt(true));
+                return Err(DvdError::Placeholder);
+            }
+            if status.transfer_complete_interrupt() {
+                self.di
+                    .write_status(Status::zero().with_transfer_complete_interrupt(true));
+                break;
+            }
+        }
+
+        Ok(self.di.read_immediate_buffer().as_u32())
     }
 
     fn dma_read_command(&mut self, command: Command, buf: &mut [u8]) -> Result<(), DvdError> {
@@ -131,10 +282,8 @@ impl DvdDriver {
             self.di.write_command_buffer_c(command.c);
 
             // Point to the buffer.
-            assert_eq!(buf.as_mut_ptr() as usize % 32, 0);
-            assert_eq!(buf.len() % 32, 0);
-            DCInvalidateRange(buf.as_mut_ptr() as _, buf.len() as u32);
-            self.di.write_dma_address(buf.as_mut_ptr() as u32);
+            let address = prepare_for_device_write(buf);
+            self.di.write_dma_address(address);
             self.di.write_dma_length(buf.len() as u32);
 
             // Fence before the transfer starts because the compiler can't see DMA.
@@ -169,3 +318,179 @@ impl DvdDriver {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::cell::Cell;
+    use std::rc::Rc;
+
+    use gamecube_alloc::DmaBuffer;
+    use gamecube_cpu::registers::TIME_BASE_TICKS_PER_MS;
+    use gamecube_mmio::dvd_interface::DvdInterface;
+    use gamecube_mmio::processor_interface::ProcessorInterface;
+    use gamecube_mmio_mock::{Access, MockBus};
+
+    use crate::platform::{advance_time_base, raise_di_interrupt};
+    use crate::{DvdDriver, DvdEvent, RESET_DURATION_MS};
+
+    const DEVICE_ERROR_INTERRUPT: u32 = 1 << 2;
+    const TRANSFER_COMPLETE_INTERRUPT: u32 = 1 << 4;
+    const COVER_STATE: u32 = 1 << 0;
+    const COVER_MASK: u32 = 1 << 1;
+    const COVER_INTERRUPT: u32 = 1 << 2;
+
+    fn driver() -> DvdDriver {
+        // SAFETY: Each test installs a bus private to its thread, so no other handle can see
+        // these registers.
+        DvdDriver::new(unsafe { DvdInterface::steal() })
+    }
+
+    /// Scripts the drive: each transfer finishes with `completion` in the status register after
+    /// the driver has polled it `polls` times, and writing the status register acknowledges every
+    /// interrupt.
+    fn script_transfers(bus: &MockBus, polls: usize, completion: u32) {
+        let remaining = Rc::new(Cell::new(None));
+        bus.on_write(DvdInterface::CONTROL_ADDRESS, {
+            let remaining = Rc::clone(&remaining);
+            move |_, value| {
+                if value & 1 != 0 {
+                    remaining.set(Some(polls));
+                }
+            }
+        });
+        bus.on_read(
+            DvdInterface::STATUS_ADDRESS,
+            move |registers, stored| match remaining.get() {
+                Some(0) => {
+                    remaining.set(None);
+                    registers.set(DvdInterface::STATUS_ADDRESS, completion);
+                    completion
+                }
+                Some(polls) => {
+                    remaining.set(Some(polls - 1));
+                    stored
+                }
+                None => stored,
+            },
+        );
+        bus.on_write(DvdInterface::STATUS_ADDRESS, |registers, _| {
+            registers.set(DvdInterface::STATUS_ADDRESS, 0);
+        });
+    }
+
+    fn status_reads(bus: &MockBus) -> usize {
+        bus.accesses()
+            .iter()
+            .filter(|access| {
+                matches!(
+                    access,
+                    Access::Read { address, .. } if *address == DvdInterface::STATUS_ADDRESS
+                )
+            })
+            .count()
+    }
+
+    #[test]
+    fn read_issues_command_and_waits_for_transfer() {
+        let bus = MockBus::install();
+        script_transfers(&bus, 3, TRANSFER_COMPLETE_INTERRUPT);
+        let mut driver = driver();
+
+        let mut buf = DmaBuffer::<u8>::zeroed(40);
+        driver.read_into(0x1000, &mut buf).unwrap();
+
+        assert_eq!(
+            bus.writes_to(DvdInterface::COMMAND_BUFFER_A_ADDRESS),
+            [0xa800_0000],
+        );
+        assert_eq!(
+            bus.writes_to(DvdInterface::COMMAND_BUFFER_B_ADDRESS),
+            [0x400]
+        );
+        assert_eq!(
+            bus.writes_to(DvdInterface::COMMAND_BUFFER_C_ADDRESS),
+            [buf.dma_len() as u32],
+        );
+        assert_eq!(
+            bus.writes_to(DvdInterface::DMA_ADDRESS_ADDRESS),
+            [buf.physical_address()],
+        );
+        assert_eq!(
+            bus.writes_to(DvdInterface::DMA_LENGTH_ADDRESS),
+            [buf.dma_len() as u32],
+        );
+        // Read, DMA, and transfer.
+        assert_eq!(bus.writes_to(DvdInterface::CONTROL_ADDRESS), [0b011]);
+        assert_eq!(status_reads(&bus), 4);
+    }
+
+    #[test]
+    fn read_reports_device_errors() {
+        let bus = MockBus::install();
+        script_transfers(&bus, 1, DEVICE_ERROR_INTERRUPT);
+        let mut driver = driver();
+
+        let mut buf = DmaBuffer::<u8>::zeroed(32);
+        assert!(driver.read_into(0, &mut buf).is_err());
+        // The error is acknowledged.
+        assert_eq!(
+            bus.writes_to(DvdInterface::STATUS_ADDRESS).last(),
+            Some(&DEVICE_ERROR_INTERRUPT),
+        );
+    }
+
+    #[test]
+    fn reset_pulses_the_reset_line_then_waits() {
+        let bus = MockBus::install();
+        // SAFETY: As above.
+        let pi = unsafe { ProcessorInterface::steal() };
+        let mut driver = driver();
+
+        driver.start_reset(&pi);
+        // Asserted, then released, with the system left running.
+        assert_eq!(
+            bus.writes_to(ProcessorInterface::RESET_ADDRESS),
+            [0b001, 0b101]
+        );
+        assert!(driver.is_reset_in_progress());
+        assert_eq!(driver.poll_event(), None);
+
+        advance_time_base(RESET_DURATION_MS * TIME_BASE_TICKS_PER_MS);
+        assert_eq!(driver.poll_event(), Some(DvdEvent::ResetComplete));
+        assert!(!driver.is_reset_in_progress());
+        assert_eq!(driver.poll_event(), None);
+    }
+
+    #[test]
+    fn cover_events_follow_the_interrupt() {
+        let bus = MockBus::install();
+        let mut driver = driver();
+
+        assert!(!raise_di_interrupt());
+        driver.enable_cover_events();
+        assert_eq!(
+            bus.writes_to(DvdInterface::COVER_ADDRESS),
+            [COVER_MASK | COVER_INTERRUPT],
+        );
+
+        bus.set(DvdInterface::COVER_ADDRESS, COVER_STATE | COVER_INTERRUPT);
+        assert!(raise_di_interrupt());
+        assert_eq!(driver.poll_event(), Some(DvdEvent::CoverOpened));
+
+        bus.set(DvdInterface::COVER_ADDRESS, COVER_INTERRUPT);
+        assert!(raise_di_interrupt());
+        assert_eq!(driver.poll_event(), Some(DvdEvent::CoverClosed));
+        assert_eq!(driver.poll_event(), None);
+
+        // Both interrupts are acknowledged, leaving the cover interrupt unmasked.
+        assert_eq!(
+            bus.writes_to(DvdInterface::COVER_ADDRESS)[1..],
+            [COVER_MASK | COVER_INTERRUPT; 2],
+        );
+
+        driver.disable_cover_events();
+        assert!(!raise_di_interrupt());
+    }
+}
@@ -0,0 +1,107 @@
+//! What the driver needs from the CPU and libogc besides its registers: the time base, interrupt
+//! control, and cache maintenance for DMA. Unit tests swap these for host stand-ins, much as
+//! `gamecube-mmio-mock` stands in for the registers.
+
+#[cfg(test)]
+pub(crate) use self::host::*;
+#[cfg(not(test))]
+pub(crate) use self::target::*;
+
+/// The signature libogc expects of an interrupt handler.
+pub(crate) type InterruptHandler = unsafe extern "C" fn(u32, *mut core::ffi::c_void);
+
+#[cfg(not(test))]
+mod target {
+    use core::ptr::null_mut;
+
+    pub(crate) use gamecube_alloc::dma::prepare_for_device_write;
+    pub(crate) use gamecube_cpu::interrupts::with_external_interrupts_disabled;
+    pub(crate) use gamecube_cpu::registers::time_base;
+    use ogc_sys::{__MaskIrq, __UnmaskIrq, IRQ_Free, IRQ_Request, IM_PI_DI, IRQ_PI_DI};
+
+    use super::InterruptHandler;
+
+    /// Installs `handler` for DI interrupts and unmasks them.
+    ///
+    /// # Safety
+    ///
+    /// External interrupts must be disabled.
+    pub(crate) unsafe fn request_di_interrupt(handler: InterruptHandler) {
+        unsafe {
+            IRQ_Request(IRQ_PI_DI, Some(handler), null_mut());
+            __UnmaskIrq(IM_PI_DI);
+        }
+    }
+
+    /// Masks DI interrupts and removes their handler.
+    ///
+    /// # Safety
+    ///
+    /// External interrupts must be disabled.
+    pub(crate) unsafe fn free_di_interrupt() {
+        unsafe {
+            __MaskIrq(IM_PI_DI);
+            IRQ_Free(IRQ_PI_DI);
+        }
+    }
+}
+
+#[cfg(test)]
+mod host {
+    extern crate std;
+
+    use core::cell::Cell;
+    use core::mem::MaybeUninit;
+    use core::ptr::null_mut;
+
+    use gamecube_alloc::dma::physical_address;
+
+    use super::InterruptHandler;
+
+    std::thread_local! {
+        static TIME_BASE: Cell<u64> = Cell::new(0);
+        static DI_INTERRUPT: Cell<Option<InterruptHandler>> = Cell::new(None);
+    }
+
+    /// Ticks once per read, so the driver's busy-waits always end.
+    pub(crate) fn time_base() -> u64 {
+        TIME_BASE.with(|time_base| {
+            let now = time_base.get();
+            time_base.set(now + 1);
+            now
+        })
+    }
+
+    pub(crate) fn advance_time_base(ticks: u64) {
+        TIME_BASE.with(|time_base| time_base.set(time_base.get() + ticks));
+    }
+
+    /// Nothing else runs on the test's thread, so there's nothing to keep out.
+    pub(crate) unsafe fn with_external_interrupts_disabled<T>(f: impl FnOnce() -> T) -> T {
+        f()
+    }
+
+    /// There's no cache to invalidate, so this only works out the address.
+    pub(crate) fn prepare_for_device_write(buf: &mut [MaybeUninit<u8>]) -> u32 {
+        physical_address(buf.as_ptr())
+    }
+
+    pub(crate) unsafe fn request_di_interrupt(handler: InterruptHandler) {
+        DI_INTERRUPT.with(|cell| cell.set(Some(handler)));
+    }
+
+    pub(crate) unsafe fn free_di_interrupt() {
+        DI_INTERRUPT.with(|cell| cell.set(None));
+    }
+
+    /// Runs the installed DI interrupt handler, if any, and returns whether there was one.
+    pub(crate) fn raise_di_interrupt() -> bool {
+        match DI_INTERRUPT.with(Cell::get) {
+            Some(handler) => {
+                unsafe { handler(0, null_mut()) };
+                true
+            }
+            None => false,
+        }
+    }
+}
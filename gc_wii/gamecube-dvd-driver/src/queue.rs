@@ -0,0 +1,151 @@
+use alloc::collections::VecDeque;
+
+use crate::{DvdDriver, DvdError};
+
+/// The drive reads whole 2 KiB sectors, so this is the finest granularity a transfer can usefully
+/// be split at.
+pub const SECTOR_SIZE: usize = 2048;
+
+/// Who is waiting on a read. Higher priorities are listed first and always run before lower ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// Streamed audio, which underruns audibly if it falls behind.
+    Audio,
+    /// Textures needed for upcoming frames.
+    TextureStreaming,
+    /// Everything else, such as reading ahead into a cache.
+    BulkPrefetch,
+}
+
+impl Priority {
+    const COUNT: usize = 3;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RequestId(u32);
+
+/// A finished request, handing its buffer back.
+pub struct Completion<'a> {
+    pub id: RequestId,
+    pub buf: &'a mut [u8],
+    pub result: Result<(), DvdError>,
+}
+
+struct Request<'a> {
+    id: RequestId,
+    offset: usize,
+    buf: &'a mut [u8],
+    /// Bytes transferred so far.
+    done: usize,
+}
+
+/// Schedules reads by priority, splitting each into chunks of whole sectors so that a long read
+/// can be preempted between chunks by a more urgent one.
+///
+/// Transfers are still synchronous. Call [`DvdQueue::step`] whenever there's time for another
+/// chunk, e.g. a few times per frame.
+pub struct DvdQueue<'a> {
+    dvd: DvdDriver,
+    chunk_size: usize,
+    next_id: u32,
+    /// FIFO queues indexed by `Priority`. The front request of each may be partly transferred.
+    pending: [VecDeque<Request<'a>>; Priority::COUNT],
+}
+
+impl<'a> DvdQueue<'a> {
+    /// Transfers at most `chunk_sectors` sectors per step. Smaller chunks let urgent requests in
+    /// sooner at the cost of more per-command overhead.
+    pub fn new(dvd: DvdDriver, chunk_sectors: usize) -> Self {
+        assert!(chunk_sectors > 0);
+        Self {
+            dvd,
+            chunk_size: chunk_sectors * SECTOR_SIZE,
+            next_id: 0,
+            pending: Default::default(),
+        }
+    }
+
+    /// Gives the driver back. Any pending requests are dropped.
+    pub fn into_driver(self) -> DvdDriver {
+        self.dvd
+    }
+
+    /// For cover events and resets. Don't issue reads through it while requests are pending.
+    pub fn driver(&mut self) -> &mut DvdDriver {
+        &mut self.dvd
+    }
+
+    /// Queues a read of `buf.len()` bytes from `offset`. The same alignment rules as
+    /// [`DvdDriver::read`] apply.
+    pub fn submit(&mut self, priority: Priority, offset: usize, buf: &'a mut [u8]) -> RequestId {
+        assert_eq!(offset % 4, 0);
+        assert_eq!(buf.as_ptr() as usize % 32, 0);
+        assert_eq!(buf.len() % 32, 0);
+
+        let id = RequestId(self.next_id);
+        self.next_id = self.next_id.wrapping_add(1);
+        self.pending[priority as usize].push_back(Request {
+            id,
+            offset,
+            buf,
+            done: 0,
+        });
+        id
+    }
+
+    /// Removes a request, finished or not, and returns its buffer.
+    pub fn cancel(&mut self, id: RequestId) -> Option<&'a mut [u8]> {
+        for queue in &mut self.pending {
+            if let Some(index) = queue.iter().position(|request| request.id == id) {
+                return queue.remove(index).map(|request| request.buf);
+            }
+        }
+        None
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.pending.iter().all(VecDeque::is_empty)
+    }
+
+    /// Transfers one chunk of the highest priority request. Returns that request if this chunk
+    /// finished it or the drive reported an error.
+    pub fn step(&mut self) -> Option<Completion<'a>> {
+        let queue = self.pending.iter_mut().find(|queue| !queue.is_empty())?;
+        let request = queue.front_mut().unwrap();
+
+        let len = (request.buf.len() - request.done).min(self.chunk_size);
+        let result = self.dvd.read(
+            request.offset + request.done,
+            &mut request.buf[request.done..request.done + len],
+        );
+        request.done += len;
+
+        if result.is_err() || request.done == request.buf.len() {
+            let request = queue.pop_front().unwrap();
+            Some(Completion {
+                id: request.id,
+                buf: request.buf,
+                result,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Steps until `id` completes, servicing anything more urgent along the way. Other requests
+    /// that complete meanwhile are passed to `on_other`.
+    pub fn finish(
+        &mut self,
+        id: RequestId,
+        mut on_other: impl FnMut(Completion<'a>),
+    ) -> Option<Completion<'a>> {
+        while !self.is_idle() {
+            match self.step() {
+                Some(completion) if completion.id == id => return Some(completion),
+                Some(completion) => on_other(completion),
+                None => (),
+            }
+        }
+        None
+    }
+}
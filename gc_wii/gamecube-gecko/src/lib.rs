@@ -0,0 +1,117 @@
+//! Debug output over a USB Gecko adapter plugged into a memory card slot.
+//!
+//! The Gecko presents itself as an EXI device with a small UART FIFO on each side. Every exchange
+//! is a single 16-bit immediate transfer: the high nibble of the word written selects a command and
+//! the word read back carries a status bit and, for receives, a data byte.
+
+#![no_std]
+
+use gamecube_mmio::external_interface::*;
+use mvbitfield::prelude::*;
+
+pub use crate::writer::{GeckoWriter, OverflowMode};
+
+#[cfg(feature = "log")]
+pub mod logger;
+mod writer;
+
+const COMMAND_IDENTIFY: u32 = 0x9000_0000;
+const COMMAND_RECEIVE: u32 = 0xa000_0000;
+const COMMAND_SEND: u32 = 0xb000_0000;
+const COMMAND_SEND_STATUS: u32 = 0xc000_0000;
+
+/// The response to [`COMMAND_IDENTIFY`] from a USB Gecko.
+const IDENTIFY_RESPONSE: u32 = 0x0470_0000;
+
+/// Set in the response to a send or send status command when the transmit FIFO had room.
+const SEND_READY: u32 = 0x0400_0000;
+
+/// Set in the response to a receive command when a byte was waiting.
+const RECEIVE_READY: u32 = 0x0800_0000;
+
+/// A memory card slot, each of which is wired to its own EXI channel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Slot {
+    /// EXI channel 0.
+    A,
+    /// EXI channel 1. This is where the Gecko usually lives.
+    B,
+}
+
+/// A USB Gecko that answered a probe.
+pub struct Gecko {
+    exi: ExternalInterface,
+    slot: Slot,
+}
+
+impl Gecko {
+    /// Looks for a Gecko in `slot`. Returns `None` if the slot is empty or holds something else.
+    pub fn probe(exi: ExternalInterface, slot: Slot) -> Option<Self> {
+        let gecko = Self { exi, slot };
+        if !gecko.is_inserted() {
+            return None;
+        }
+        (gecko.transfer(COMMAND_IDENTIFY) == IDENTIFY_RESPONSE).then_some(gecko)
+    }
+
+    pub fn slot(&self) -> Slot {
+        self.slot
+    }
+
+    /// Sends one byte if the transmit FIFO has room. Returns whether it was accepted.
+    pub fn try_send_byte(&self, byte: u8) -> bool {
+        if self.transfer(COMMAND_SEND_STATUS) & SEND_READY == 0 {
+            return false;
+        }
+        self.transfer(COMMAND_SEND | (byte as u32) << 20) & SEND_READY != 0
+    }
+
+    /// Sends one byte, spinning until the transmit FIFO has room.
+    pub fn send_byte(&self, byte: u8) {
+        while !self.try_send_byte(byte) {}
+    }
+
+    /// Receives one byte if the PC has sent one.
+    pub fn try_receive_byte(&self) -> Option<u8> {
+        let response = self.transfer(COMMAND_RECEIVE);
+        (response & RECEIVE_READY != 0).then_some((response >> 16) as u8)
+    }
+
+    fn is_inserted(&self) -> bool {
+        match self.slot {
+            Slot::A => self.exi.read_channel0_parameter().ext(),
+            Slot::B => self.exi.read_channel1_parameter().ext(),
+        }
+    }
+
+    /// Performs one 16-bit read-write immediate transfer with device 0 on this slot's channel.
+    fn transfer(&self, data: u32) -> u32 {
+        let parameter = Parameter::zero()
+            .with_clock(Clock::Mhz32)
+            .with_chip_select(U3::new_masked(1));
+        let control = Control::zero()
+            .with_transfer(true)
+            .with_access(Access::ReadWrite)
+            .with_length_minus_one(U2::new_masked(1));
+        match self.slot {
+            Slot::A => {
+                self.exi.write_channel0_parameter(parameter);
+                self.exi.write_channel0_data(data);
+                self.exi.write_channel0_control(control);
+                while self.exi.read_channel0_control().transfer() {}
+                let result = self.exi.read_channel0_data();
+                self.exi.write_channel0_parameter(Parameter::zero());
+                result
+            }
+            Slot::B => {
+                self.exi.write_channel1_parameter(parameter);
+                self.exi.write_channel1_data(data);
+                self.exi.write_channel1_control(control);
+                while self.exi.read_channel1_control().transfer() {}
+                let result = self.exi.read_channel1_data();
+                self.exi.write_channel1_parameter(Parameter::zero());
+                result
+            }
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! A [`log`] facade that writes records to a Gecko.
+
+use core::cell::UnsafeCell;
+use core::fmt::Write;
+
+use gamecube_cpu::interrupts::with_external_interrupts_disabled;
+use gamecube_cpu::registers::{time_base, TIME_BASE_TICKS_PER_MS};
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+use crate::{Gecko, GeckoWriter, OverflowMode};
+
+struct GeckoLogger {
+    writer: UnsafeCell<GeckoWriter<256>>,
+}
+
+// SAFETY: The writer is only touched with external interrupts disabled on a single-core machine.
+unsafe impl Sync for GeckoLogger {}
+
+static LOGGER: GeckoLogger = GeckoLogger {
+    writer: UnsafeCell::new(GeckoWriter::new(None, OverflowMode::Drop)),
+};
+
+/// Installs the Gecko logger with the given level filter.
+///
+/// Passing `None` installs it anyway, discarding every record, so the rest of the program can log
+/// unconditionally whether or not a Gecko was found. Output never blocks: records that don't fit in
+/// the Gecko's FIFO are dropped.
+pub fn init(gecko: Option<Gecko>, level: LevelFilter) -> Result<(), SetLoggerError> {
+    let found = gecko.is_some();
+    // SAFETY: Nothing else can be logging before the logger is installed.
+    unsafe { with_external_interrupts_disabled(|| (*LOGGER.writer.get()).set_gecko(gecko)) };
+    log::set_logger(&LOGGER)?;
+    log::set_max_level(if found { level } else { LevelFilter::Off });
+    Ok(())
+}
+
+/// Returns the number of bytes of log output dropped so far.
+pub fn dropped() -> usize {
+    // SAFETY: See `Sync for GeckoLogger`.
+    unsafe { with_external_interrupts_disabled(|| (*LOGGER.writer.get()).dropped()) }
+}
+
+impl Log for GeckoLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        let ms = time_base() / TIME_BASE_TICKS_PER_MS;
+        // SAFETY: See `Sync for GeckoLogger`.
+        unsafe {
+            with_external_interrupts_disabled(|| {
+                let writer = &mut *self.writer.get();
+                let _ = writeln!(
+                    writer,
+                    "[{:>8}.{:03} {:5} {}] {}",
+                    ms / 1000,
+                    ms % 1000,
+                    record.level(),
+                    record.target(),
+                    record.args(),
+                );
+            })
+        }
+    }
+
+    fn flush(&self) {
+        // SAFETY: See `Sync for GeckoLogger`.
+        unsafe { with_external_interrupts_disabled(|| (*self.writer.get()).flush()) }
+    }
+}
@@ -0,0 +1,94 @@
+use core::fmt::{self, Write};
+
+use crate::Gecko;
+
+/// What to do with output when the Gecko's transmit FIFO is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowMode {
+    /// Wait for the PC to drain the FIFO. Nothing is lost, but a stalled reader stalls the game.
+    Block,
+    /// Discard whatever doesn't fit and count it. Output never waits on the PC.
+    Drop,
+}
+
+/// A line-buffered [`Write`] sink for a [`Gecko`].
+///
+/// Text accumulates in a `N`-byte buffer that is flushed at each newline or when it fills up. A
+/// writer constructed without a Gecko accepts and discards everything, so callers don't need to
+/// care whether one is attached.
+pub struct GeckoWriter<const N: usize> {
+    gecko: Option<Gecko>,
+    mode: OverflowMode,
+    buf: [u8; N],
+    len: usize,
+    dropped: usize,
+}
+
+impl<const N: usize> GeckoWriter<N> {
+    pub const fn new(gecko: Option<Gecko>, mode: OverflowMode) -> Self {
+        Self {
+            gecko,
+            mode,
+            buf: [0; N],
+            len: 0,
+            dropped: 0,
+        }
+    }
+
+    pub fn gecko(&self) -> Option<&Gecko> {
+        self.gecko.as_ref()
+    }
+
+    pub fn set_gecko(&mut self, gecko: Option<Gecko>) {
+        self.flush();
+        self.gecko = gecko;
+    }
+
+    /// The number of bytes discarded so far in [`OverflowMode::Drop`].
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    pub fn write_bytes(&mut self, mut bytes: &[u8]) {
+        if self.gecko.is_none() {
+            return;
+        }
+        while !bytes.is_empty() {
+            let n = bytes.len().min(N - self.len);
+            self.buf[self.len..self.len + n].copy_from_slice(&bytes[..n]);
+            let flush = self.len + n == N || bytes[..n].contains(&b'\n');
+            self.len += n;
+            bytes = &bytes[n..];
+            if flush {
+                self.flush();
+            }
+        }
+    }
+
+    /// Sends everything buffered so far.
+    pub fn flush(&mut self) {
+        let Some(gecko) = &self.gecko else {
+            self.len = 0;
+            return;
+        };
+        for (sent, &byte) in self.buf[..self.len].iter().enumerate() {
+            match self.mode {
+                OverflowMode::Block => gecko.send_byte(byte),
+                OverflowMode::Drop => {
+                    if !gecko.try_send_byte(byte) {
+                        self.dropped += self.len - sent;
+                        break;
+                    }
+                }
+            }
+        }
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Write for GeckoWriter<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_bytes(s.as_bytes());
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+//! Immediate-mode drawing through the GX write-gather pipe.
+//!
+//! Vertices are written as typed structs whose layouts are checked against the
+//! [`VertexFormat`] descriptors at compile time, so a draw can't send more or fewer bytes per
+//! vertex than the format slot it names expects. Attribute formats still have to be loaded with
+//! the same descriptor before drawing.
+
+#![no_std]
+
+use core::ffi::c_void;
+use core::marker::PhantomData;
+
+use bytemuck::Pod;
+use inception_render_common::vertex_format::{VertexFormat, VertexFormatDescriptor};
+use ogc_sys::*;
+
+pub use crate::vertex::*;
+
+mod vertex;
+
+/// A vertex type whose bytes match a [`VertexFormat`]'s descriptor.
+///
+/// # Safety
+///
+/// The type's bytes must be exactly what GX expects of one vertex in `FORMAT`, in order. The
+/// [`vertex_types!`] macro checks the size; field order and types are up to the definition.
+pub unsafe trait Vertex: Pod {
+    const FORMAT: VertexFormat;
+}
+
+/// Replaces the vertex descriptor and loads the attribute formats into the descriptor's vertex
+/// format slot. Array pointers are left alone; callers still bind those with `GX_SetArray`.
+///
+/// # Safety
+///
+/// GX must be initialized and no draw may be in progress.
+pub unsafe fn apply_vertex_format(descriptor: &VertexFormatDescriptor) {
+    GX_ClearVtxDesc();
+    for attr in descriptor.attributes() {
+        GX_SetVtxDesc(attr.attribute, attr.input);
+        GX_SetVtxAttrFmt(
+            descriptor.vertex_format,
+            attr.attribute as u32,
+            attr.component_count as u32,
+            attr.component_type as u32,
+            attr.frac as u32,
+        );
+    }
+    GX_InvVtxCache();
+}
+
+/// GX primitive types, with their `GX_Begin` opcodes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Primitive {
+    Quads = 0x80,
+    Triangles = 0x90,
+    TriangleStrip = 0x98,
+    TriangleFan = 0xa0,
+    Lines = 0xa8,
+    LineStrip = 0xb0,
+    Points = 0xb8,
+}
+
+/// Exclusive access to the GX command FIFO. Every immediate-mode draw borrows it mutably, so two
+/// draws can't interleave their vertices.
+pub struct Gx {
+    _private: (),
+}
+
+impl Gx {
+    /// Allocates a FIFO of `fifo_size` bytes and initializes GX with it.
+    ///
+    /// # Safety
+    ///
+    /// Must be called exactly once, after the video interface is initialized.
+    pub unsafe fn init(fifo_size: usize) -> Self {
+        unsafe {
+            let fifo = MEM_K0_TO_K1(libc::memalign(32, fifo_size));
+            libc::memset(fifo, 0, fifo_size);
+            GX_Init(fifo as *mut c_void, fifo_size as u32);
+        }
+        Self { _private: () }
+    }
+
+    /// Starts a draw of `count` vertices. The returned session must be given exactly that many.
+    pub fn begin<V: Vertex>(&mut self, primitive: Primitive, count: u16) -> Draw<'_, V> {
+        unsafe { GX_Begin(primitive as u8, V::FORMAT as u8, count) };
+        Draw {
+            remaining: count,
+            _gx: PhantomData,
+        }
+    }
+
+    /// Draws a single quad, a common enough case to spare callers the session.
+    pub fn quad<V: Vertex>(&mut self, vertices: [V; 4]) {
+        let mut draw = self.begin(Primitive::Quads, 4);
+        for vertex in vertices {
+            draw.vertex(vertex);
+        }
+    }
+}
+
+/// An in-progress immediate-mode draw.
+#[must_use = "a draw must be given every vertex it was started with"]
+pub struct Draw<'a, V: Vertex> {
+    remaining: u16,
+    _gx: PhantomData<(&'a mut Gx, V)>,
+}
+
+impl<'a, V: Vertex> Draw<'a, V> {
+    pub fn vertex(&mut self, vertex: V) {
+        assert!(self.remaining > 0, "too many vertices for GX_Begin");
+        self.remaining -= 1;
+        write_bytes(bytemuck::bytes_of(&vertex));
+    }
+}
+
+impl<'a, V: Vertex> Drop for Draw<'a, V> {
+    fn drop(&mut self) {
+        debug_assert_eq!(self.remaining, 0, "too few vertices for GX_Begin");
+    }
+}
+
+/// Copies bytes into the write-gather pipe using the widest writes available. The pipe is a
+/// byte stream, so the write width doesn't affect what GX sees.
+fn write_bytes(mut bytes: &[u8]) {
+    unsafe {
+        while let [a, b, c, d, rest @ ..] = bytes {
+            (*wgPipe).U32 = u32::from_be_bytes([*a, *b, *c, *d]);
+            bytes = rest;
+        }
+        if let [a, b, rest @ ..] = bytes {
+            (*wgPipe).U16 = u16::from_be_bytes([*a, *b]);
+            bytes = rest;
+        }
+        if let [a] = bytes {
+            (*wgPipe).U8 = *a;
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use bytemuck::{Pod, Zeroable};
+use inception_render_common::vertex_format::VertexFormat;
+
+use crate::Vertex;
+
+/// Defines packed vertex structs for formats drawn in immediate mode, checking each one's size
+/// against its format's descriptor.
+macro_rules! vertex_types {
+    ($(
+        $(#[$meta:meta])*
+        $name:ident: $format:ident {
+            $($field:ident: $type:ty),* $(,)?
+        }
+    )*) => {$(
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Pod, Zeroable)]
+        #[repr(C, packed)]
+        pub struct $name {
+            $(pub $field: $type,)*
+        }
+
+        // SAFETY: The size is checked below, and the fields follow the descriptor's attributes.
+        unsafe impl Vertex for $name {
+            const FORMAT: VertexFormat = VertexFormat::$format;
+        }
+
+        const _: () = assert!(
+            ::core::mem::size_of::<$name>() == VertexFormat::$format.descriptor().vertex_size()
+        );
+    )*};
+}
+
+vertex_types! {
+    SkyboxVertex: Skybox {
+        pos: [i8; 3],
+        tex0: [u8; 2],
+    }
+
+    DebugColorVertex: DebugColor {
+        pos: [u16; 2],
+        clr0: [u8; 3],
+    }
+
+    DebugTextureVertex: DebugTexture {
+        pos: [u16; 2],
+        tex0: [u8; 2],
+    }
+
+    /// Positions are in world space. The same layout as baked overlay and prop geometry.
+    OverlayVertex: Overlay {
+        pos: [f32; 3],
+        clr0: [u8; 3],
+        tex0: [f32; 2],
+    }
+
+    /// Texture coordinates are in 64ths of the font atlas.
+    TextVertex: Text {
+        pos: [u16; 2],
+        tex0: [u8; 2],
+    }
+}
@@ -0,0 +1,15 @@
+//! Vectors and matrices for the render path, laid out the way GX loads them.
+//!
+//! The constructors reproduce libogc's `gu` functions exactly, down to axis conventions, so that
+//! swapping one for the other doesn't move the camera. Nothing here touches the hardware, so it
+//! builds and tests on the host too.
+
+#![no_std]
+
+pub use crate::mtx34::Mtx34;
+pub use crate::mtx44::Mtx44;
+pub use crate::vec3::Vec3;
+
+mod mtx34;
+mod mtx44;
+mod vec3;
@@ -0,0 +1,135 @@
+use core::ops::Mul;
+
+use crate::Vec3;
+
+/// A 3x4 affine transform in row-major order, as loaded by `GX_LoadPosMtxImm` and
+/// `GX_LoadTexMtxImm`. Has the same layout as libogc's `Mtx`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+pub struct Mtx34(pub [[f32; 4]; 3]);
+
+impl Mtx34 {
+    pub const fn identity() -> Self {
+        Self([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+        ])
+    }
+
+    /// Equivalent to `guMtxTrans`.
+    pub const fn translation(offset: Vec3) -> Self {
+        Self([
+            [1.0, 0.0, 0.0, offset.x],
+            [0.0, 1.0, 0.0, offset.y],
+            [0.0, 0.0, 1.0, offset.z],
+        ])
+    }
+
+    /// Equivalent to `guMtxRotRad(m, 'x', radians)`.
+    pub fn rotation_x(radians: f32) -> Self {
+        let (s, c) = libm::sincosf(radians);
+        Self([[1.0, 0.0, 0.0, 0.0], [0.0, c, -s, 0.0], [0.0, s, c, 0.0]])
+    }
+
+    /// Equivalent to `guMtxRotRad(m, 'y', radians)`.
+    pub fn rotation_y(radians: f32) -> Self {
+        let (s, c) = libm::sincosf(radians);
+        Self([[c, 0.0, s, 0.0], [0.0, 1.0, 0.0, 0.0], [-s, 0.0, c, 0.0]])
+    }
+
+    /// Equivalent to `guMtxRotRad(m, 'z', radians)`.
+    pub fn rotation_z(radians: f32) -> Self {
+        let (s, c) = libm::sincosf(radians);
+        Self([[c, -s, 0.0, 0.0], [s, c, 0.0, 0.0], [0.0, 0.0, 1.0, 0.0]])
+    }
+
+    /// A view matrix for a camera at `eye` looking at `target`. Equivalent to `guLookAt`, which
+    /// looks down -Z in view space.
+    pub fn look_at(eye: Vec3, up: Vec3, target: Vec3) -> Self {
+        let look = (eye - target).normalize();
+        let right = up.cross(look).normalize();
+        let up = look.cross(right);
+        Self([
+            [right.x, right.y, right.z, -eye.dot(right)],
+            [up.x, up.y, up.z, -eye.dot(up)],
+            [look.x, look.y, look.z, -eye.dot(look)],
+        ])
+    }
+
+    pub fn transform_point(&self, p: Vec3) -> Vec3 {
+        let m = &self.0;
+        Vec3::new(
+            m[0][0] * p.x + m[0][1] * p.y + m[0][2] * p.z + m[0][3],
+            m[1][0] * p.x + m[1][1] * p.y + m[1][2] * p.z + m[1][3],
+            m[2][0] * p.x + m[2][1] * p.y + m[2][2] * p.z + m[2][3],
+        )
+    }
+
+    /// For passing to GX functions, which take matrices as row pointers.
+    pub fn as_mut_ptr(&mut self) -> *mut [f32; 4] {
+        self.0.as_mut_ptr()
+    }
+}
+
+impl Default for Mtx34 {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Concatenation, applying `rhs` first. Equivalent to `guMtxConcat(self, rhs, result)`.
+impl Mul for Mtx34 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let (a, b) = (&self.0, &rhs.0);
+        let mut result = [[0.0; 4]; 3];
+        for (row, a) in result.iter_mut().zip(a) {
+            for col in 0..4 {
+                row[col] = a[0] * b[0][col] + a[1] * b[1][col] + a[2] * b[2][col];
+            }
+            row[3] += a[3];
+        }
+        Self(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f32::consts::FRAC_PI_2;
+
+    use crate::{Mtx34, Vec3};
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!((a - b).length() < 1e-5, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn concat_applies_rhs_first() {
+        let m = Mtx34::translation(Vec3::new(1.0, 0.0, 0.0)) * Mtx34::rotation_z(FRAC_PI_2);
+        assert_near(
+            m.transform_point(Vec3::new(1.0, 0.0, 0.0)),
+            Vec3::new(1.0, 1.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn look_at_puts_target_down_negative_z() {
+        let eye = Vec3::new(10.0, 20.0, 30.0);
+        let m = Mtx34::look_at(
+            eye,
+            Vec3::new(0.0, 0.0, 1.0),
+            eye + Vec3::new(5.0, 0.0, 0.0),
+        );
+        assert_near(m.transform_point(eye), Vec3::zero());
+        assert_near(
+            m.transform_point(eye + Vec3::new(5.0, 0.0, 0.0)),
+            Vec3::new(0.0, 0.0, -5.0),
+        );
+        assert_near(
+            m.transform_point(eye + Vec3::new(0.0, 0.0, 1.0)),
+            Vec3::new(0.0, 1.0, 0.0),
+        );
+    }
+}
@@ -0,0 +1,54 @@
+/// A 4x4 projection matrix in row-major order, as loaded by `GX_LoadProjectionMtx`. Has the same
+/// layout as libogc's `Mtx44`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+pub struct Mtx44(pub [[f32; 4]; 4]);
+
+impl Mtx44 {
+    /// Equivalent to `guPerspective`. `fovy` is in degrees. Depth maps to -1..0, which is what
+    /// GX expects of a `GX_PERSPECTIVE` matrix.
+    pub fn perspective(fovy: f32, aspect: f32, near: f32, far: f32) -> Self {
+        let cot = 1.0 / libm::tanf((fovy * 0.5).to_radians());
+        let depth = 1.0 / (far - near);
+        Self([
+            [cot / aspect, 0.0, 0.0, 0.0],
+            [0.0, cot, 0.0, 0.0],
+            [0.0, 0.0, -near * depth, -far * near * depth],
+            [0.0, 0.0, -1.0, 0.0],
+        ])
+    }
+
+    /// Equivalent to `guOrtho`, including its argument order.
+    pub fn ortho(top: f32, bottom: f32, left: f32, right: f32, near: f32, far: f32) -> Self {
+        let width = 1.0 / (right - left);
+        let height = 1.0 / (top - bottom);
+        let depth = 1.0 / (far - near);
+        Self([
+            [2.0 * width, 0.0, 0.0, -(right + left) * width],
+            [0.0, 2.0 * height, 0.0, -(top + bottom) * height],
+            [0.0, 0.0, -depth, -far * depth],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// For passing to GX functions, which take matrices as row pointers.
+    pub fn as_mut_ptr(&mut self) -> *mut [f32; 4] {
+        self.0.as_mut_ptr()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Mtx44;
+
+    #[test]
+    fn perspective_maps_near_and_far_planes() {
+        let m = Mtx44::perspective(90.0, 1.0, 1.0, 100.0);
+        for (z, expected_depth) in [(-1.0, -1.0), (-100.0, 0.0)] {
+            let clip_z = m.0[2][2] * z + m.0[2][3];
+            let clip_w = m.0[3][2] * z;
+            assert!((clip_z / clip_w - expected_depth).abs() < 1e-5);
+        }
+        assert!((m.0[0][0] - 1.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,237 @@
+//! A scriptable stand-in for the hardware behind `gamecube-mmio`, for testing drivers on the host.
+//!
+//! Registers are plain memory: reads return whatever was last written or set. Hooks add the side
+//! effects a driver waits on, like a status bit that clears some number of polls after a command
+//! is issued.
+//!
+//! ```ignore
+//! let bus = MockBus::install();
+//! bus.set_after_reads(DvdInterface::STATUS_ADDRESS, 3, TRANSFER_COMPLETE);
+//! // ... run the driver ...
+//! assert_eq!(bus.writes_to(DvdInterface::DMA_LENGTH_ADDRESS), [0x800]);
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use gamecube_mmio::mock::{set_bus, Bus};
+
+/// One access as the driver made it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    /// `value` is what the driver saw, after any read hook.
+    Read {
+        address: usize,
+        value: u32,
+    },
+    Write {
+        address: usize,
+        value: u32,
+    },
+}
+
+/// Register contents, as visible to hooks.
+#[derive(Default)]
+pub struct Registers {
+    values: HashMap<usize, u32>,
+}
+
+impl Registers {
+    /// Registers that were never written read as zero.
+    pub fn get(&self, address: usize) -> u32 {
+        self.values.get(&address).copied().unwrap_or(0)
+    }
+
+    pub fn set(&mut self, address: usize, value: u32) {
+        self.values.insert(address, value);
+    }
+}
+
+type ReadHook = Box<dyn FnMut(&mut Registers, u32) -> u32>;
+type WriteHook = Box<dyn FnMut(&mut Registers, u32)>;
+
+#[derive(Default)]
+struct State {
+    registers: Registers,
+    read_hooks: HashMap<usize, ReadHook>,
+    write_hooks: HashMap<usize, WriteHook>,
+    log: Vec<Access>,
+}
+
+#[derive(Default)]
+pub struct MockBus {
+    state: RefCell<State>,
+}
+
+impl MockBus {
+    /// Creates an empty bus and installs it for register accesses on the current thread.
+    pub fn install() -> Rc<Self> {
+        let bus = Rc::new(Self::default());
+        set_bus(Some(Rc::clone(&bus) as Rc<dyn Bus>));
+        bus
+    }
+
+    pub fn get(&self, address: usize) -> u32 {
+        self.state.borrow().registers.get(address)
+    }
+
+    /// Sets a register without running hooks or logging an access.
+    pub fn set(&self, address: usize, value: u32) {
+        self.state.borrow_mut().registers.set(address, value);
+    }
+
+    /// Runs `hook` on every read of `address`. It receives the stored value and returns the value
+    /// the driver sees. Replaces any previous read hook for `address`.
+    pub fn on_read(&self, address: usize, hook: impl FnMut(&mut Registers, u32) -> u32 + 'static) {
+        self.state
+            .borrow_mut()
+            .read_hooks
+            .insert(address, Box::new(hook));
+    }
+
+    /// Runs `hook` after every write to `address` is stored. It receives the written value.
+    /// Replaces any previous write hook for `address`.
+    pub fn on_write(&self, address: usize, hook: impl FnMut(&mut Registers, u32) + 'static) {
+        self.state
+            .borrow_mut()
+            .write_hooks
+            .insert(address, Box::new(hook));
+    }
+
+    /// Stores `value` to `address` once it has been read `reads` times, like a transfer that
+    /// completes while the driver polls. With `reads` of zero, stores it right away.
+    pub fn set_after_reads(&self, address: usize, reads: usize, value: u32) {
+        if reads == 0 {
+            self.set(address, value);
+            return;
+        }
+        let mut remaining = reads;
+        self.on_read(address, move |registers, stored| {
+            if remaining == 0 {
+                return stored;
+            }
+            remaining -= 1;
+            if remaining == 0 {
+                registers.set(address, value);
+            }
+            stored
+        });
+    }
+
+    /// Every access so far, oldest first.
+    pub fn accesses(&self) -> Vec<Access> {
+        self.state.borrow().log.clone()
+    }
+
+    /// The values written to `address` so far, oldest first.
+    pub fn writes_to(&self, address: usize) -> Vec<u32> {
+        self.state
+            .borrow()
+            .log
+            .iter()
+            .filter_map(|access| match *access {
+                Access::Write {
+                    address: written,
+                    value,
+                } if written == address => Some(value),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Bus for MockBus {
+    fn read(&self, address: usize, size: usize) -> u32 {
+        let mut state = self.state.borrow_mut();
+        let State {
+            registers,
+            read_hooks,
+            log,
+            ..
+        } = &mut *state;
+        let stored = registers.get(address);
+        let value = match read_hooks.get_mut(&address) {
+            Some(hook) => hook(registers, stored),
+            None => stored,
+        } & mask(size);
+        log.push(Access::Read { address, value });
+        value
+    }
+
+    fn write(&self, address: usize, size: usize, value: u32) {
+        let mut state = self.state.borrow_mut();
+        let State {
+            registers,
+            write_hooks,
+            log,
+            ..
+        } = &mut *state;
+        let value = value & mask(size);
+        registers.set(address, value);
+        if let Some(hook) = write_hooks.get_mut(&address) {
+            hook(registers, value);
+        }
+        log.push(Access::Write { address, value });
+    }
+}
+
+fn mask(size: usize) -> u32 {
+    match size {
+        4 => u32::MAX,
+        size => (1 << (8 * size)) - 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gamecube_mmio::dvd_interface::DvdInterface;
+
+    use super::{Access, MockBus};
+
+    #[test]
+    fn registers_read_back_writes() {
+        let bus = MockBus::install();
+        // SAFETY: The bus is private to this test's thread, so nothing else sees these registers.
+        let di = unsafe { DvdInterface::steal() };
+        di.write_dma_length(0x800);
+        assert_eq!(bus.get(DvdInterface::DMA_LENGTH_ADDRESS), 0x800);
+        assert_eq!(
+            bus.accesses(),
+            [Access::Write {
+                address: DvdInterface::DMA_LENGTH_ADDRESS,
+                value: 0x800,
+            }],
+        );
+    }
+
+    #[test]
+    fn set_after_reads_changes_value_on_schedule() {
+        let bus = MockBus::install();
+        // SAFETY: As above.
+        let di = unsafe { DvdInterface::steal() };
+        bus.set_after_reads(DvdInterface::STATUS_ADDRESS, 2, 0x10);
+        for _ in 0..3 {
+            di.read_status();
+        }
+        let seen: Vec<u32> = bus
+            .accesses()
+            .into_iter()
+            .map(|access| match access {
+                Access::Read { value, .. } => value,
+                Access::Write { .. } => unreachable!(),
+            })
+            .collect();
+        assert_eq!(seen, [0, 0, 0x10]);
+    }
+
+    #[test]
+    fn set_after_no_reads_applies_immediately() {
+        let bus = MockBus::install();
+        // SAFETY: As above.
+        let di = unsafe { DvdInterface::steal() };
+        bus.set_after_reads(DvdInterface::STATUS_ADDRESS, 0, 0x10);
+        assert_eq!(bus.get(DvdInterface::STATUS_ADDRESS), 0x10);
+        assert!(di.read_status().transfer_complete_interrupt());
+    }
+}
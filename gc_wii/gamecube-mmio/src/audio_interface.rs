@@ -0,0 +1,61 @@
+use core::mem::transmute;
+
+use mvbitfield::prelude::*;
+
+mmio_device! {
+    doc_name: "AI",
+    struct_name: AudioInterface,
+    base: 0xcc006c00,
+    size: 0x10,
+    regs: {
+        control: Control = rw,
+        volume: Volume = rw,
+        // Counts stereo samples played from the disc stream while streaming is enabled.
+        sample_counter: u32 = ro,
+        // The AI interrupt fires when `sample_counter` reaches this value.
+        interrupt_timing: u32 = rw,
+    },
+}
+
+mvbitfield! {
+    pub struct Control: u32 {
+        /// Plays samples streamed from the drive. While clear, the drive's samples are dropped.
+        pub streaming_enable: 1 as bool,
+        pub streaming_sample_rate: 1 as StreamingSampleRate,
+        pub interrupt_mask: 1 as bool,
+        /// Set when `sample_counter` reaches `interrupt_timing`. Write 1 to clear.
+        pub interrupt: 1 as bool,
+        /// When set, reaching `interrupt_timing` does not set `interrupt`.
+        pub interrupt_timing_disable: 1 as bool,
+        /// Write 1 to zero `sample_counter`.
+        pub sample_counter_reset: 1 as bool,
+        /// Set for 32 kHz DSP output, clear for 48 kHz.
+        pub dsp_sample_rate_32khz: 1 as bool,
+    }
+}
+
+#[repr(u8)]
+pub enum StreamingSampleRate {
+    Rate32kHz = 0,
+    Rate48kHz = 1,
+}
+
+impl StreamingSampleRate {
+    pub const fn from_u1(value: U1) -> Self {
+        // SAFETY: StreamingSampleRate and U1 have the same layout and valid bit patterns.
+        unsafe { transmute(value) }
+    }
+
+    pub const fn as_u1(self) -> U1 {
+        // SAFETY: StreamingSampleRate and U1 have the same layout and valid bit patterns.
+        unsafe { transmute(self) }
+    }
+}
+
+mvbitfield! {
+    /// Streaming playback volume. 0 is silent and 255 is full scale.
+    pub struct Volume: u32 {
+        pub left: 8,
+        pub right: 8,
+    }
+}
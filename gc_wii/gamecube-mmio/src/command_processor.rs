@@ -44,6 +44,10 @@ impl<'reg> CommandProcessor<'reg> {
         unsafe { ptr::write_volatile(&mut (*Self::PTR).status, value) };
     }
 
+    pub fn modify_status(&mut self, f: impl FnOnce(Status) -> Status) {
+        self.write_status(f(self.read_status()));
+    }
+
     pub fn read_control(&self) -> Control {
         unsafe { ptr::read_volatile(&(*Self::PTR).control) }
     }
@@ -51,6 +55,10 @@ impl<'reg> CommandProcessor<'reg> {
     pub fn write_control(&mut self, value: Control) {
         unsafe { ptr::write_volatile(&mut (*Self::PTR).control, value) };
     }
+
+    pub fn modify_control(&mut self, f: impl FnOnce(Control) -> Control) {
+        self.write_control(f(self.read_control()));
+    }
 }
 
 mvbitfield! {
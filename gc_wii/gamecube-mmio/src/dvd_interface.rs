@@ -16,7 +16,7 @@ mmio_device! {
         dma_address: u32 = wo,
         dma_length: u32 = wo,
         control: Control = wo,
-        immediate_buffer: ImmediateBuffer = wo,
+        immediate_buffer: ImmediateBuffer = rw,
         config: u32,
     },
 }
@@ -0,0 +1,99 @@
+use core::mem::transmute;
+
+use mvbitfield::prelude::*;
+
+mmio_device! {
+    doc_name: "EXI",
+    struct_name: ExternalInterface,
+    base: 0xcc006800,
+    size: 0x3c,
+    regs: {
+        channel0_parameter: Parameter = rw,
+        channel0_dma_address: u32 = rw,
+        channel0_dma_length: u32 = rw,
+        channel0_control: Control = rw,
+        channel0_data: u32 = rw,
+        channel1_parameter: Parameter = rw,
+        channel1_dma_address: u32 = rw,
+        channel1_dma_length: u32 = rw,
+        channel1_control: Control = rw,
+        channel1_data: u32 = rw,
+        channel2_parameter: Parameter = rw,
+        channel2_dma_address: u32 = rw,
+        channel2_dma_length: u32 = rw,
+        channel2_control: Control = rw,
+        channel2_data: u32 = rw,
+    },
+}
+
+mvbitfield! {
+    pub struct Parameter: u32 {
+        pub exi_interrupt_mask: 1 as bool,
+        pub exi_interrupt: 1 as bool,
+        pub transfer_complete_mask: 1 as bool,
+        pub transfer_complete_interrupt: 1 as bool,
+        pub clock: 3 as Clock,
+        /// One-hot device select. Zero deselects every device on the channel.
+        pub chip_select: 3,
+        pub ext_interrupt_mask: 1 as bool,
+        pub ext_interrupt: 1 as bool,
+        /// Whether a device is plugged into the channel. Only meaningful for channels 0 and 1.
+        pub ext: 1 as bool,
+        pub rom_disable: 1 as bool,
+    }
+}
+
+#[repr(u8)]
+pub enum Clock {
+    Mhz1 = 0,
+    Mhz2 = 1,
+    Mhz4 = 2,
+    Mhz8 = 3,
+    Mhz16 = 4,
+    Mhz32 = 5,
+    Reserved6 = 6,
+    Reserved7 = 7,
+}
+
+impl Clock {
+    pub const fn from_u3(value: U3) -> Self {
+        // SAFETY: Clock and U3 have the same layout and valid bit patterns.
+        unsafe { transmute(value) }
+    }
+
+    pub const fn as_u3(self) -> U3 {
+        // SAFETY: Clock and U3 have the same layout and valid bit patterns.
+        unsafe { transmute(self) }
+    }
+}
+
+mvbitfield! {
+    pub struct Control: u32 {
+        /// Set to begin a transfer. Reads as set until the transfer completes.
+        pub transfer: 1 as bool,
+        pub dma: 1 as bool,
+        pub access: 2 as Access,
+        /// Immediate transfer length in bytes, minus one.
+        pub length_minus_one: 2,
+    }
+}
+
+#[repr(u8)]
+pub enum Access {
+    Read = 0,
+    Write = 1,
+    ReadWrite = 2,
+    Reserved = 3,
+}
+
+impl Access {
+    pub const fn from_u2(value: U2) -> Self {
+        // SAFETY: Access and U2 have the same layout and valid bit patterns.
+        unsafe { transmute(value) }
+    }
+
+    pub const fn as_u2(self) -> U2 {
+        // SAFETY: Access and U2 have the same layout and valid bit patterns.
+        unsafe { transmute(self) }
+    }
+}
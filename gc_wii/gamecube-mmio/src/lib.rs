@@ -1,9 +1,73 @@
 #![no_std]
 
+#[cfg(feature = "mock")]
+extern crate std;
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::audio_interface::AudioInterface;
+use crate::dvd_interface::DvdInterface;
+use crate::external_interface::ExternalInterface;
+use crate::processor_interface::ProcessorInterface;
+use crate::video_interface::VideoInterface;
+
 #[macro_use]
 mod macros;
 
+pub mod audio_interface;
 pub mod command_processor;
 pub mod dvd_interface;
+pub mod external_interface;
+#[cfg(feature = "mock")]
+pub mod mock;
 pub mod processor_interface;
+#[cfg(any(feature = "trace", feature = "mock"))]
+mod raw;
+#[cfg(feature = "trace")]
+pub mod trace;
 pub mod video_interface;
+
+static TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Every MMIO device, to be split up and moved into the drivers that own them.
+///
+/// There is only one of each device handle, so two drivers can't both end up writing the same
+/// registers.
+pub struct Peripherals {
+    pub audio_interface: AudioInterface,
+    pub dvd_interface: DvdInterface,
+    pub external_interface: ExternalInterface,
+    pub processor_interface: ProcessorInterface,
+    pub video_interface: VideoInterface,
+}
+
+impl Peripherals {
+    /// Returns the peripherals the first time it's called and `None` after that.
+    pub fn take() -> Option<Self> {
+        if TAKEN.swap(true, Ordering::Relaxed) {
+            None
+        } else {
+            // SAFETY: This is the first call, so no other handles exist.
+            Some(unsafe { Self::steal() })
+        }
+    }
+
+    /// Unchecked version of [`Self::take`].
+    ///
+    /// # Safety
+    ///
+    /// See the `steal` functions of the individual devices.
+    pub unsafe fn steal() -> Self {
+        TAKEN.store(true, Ordering::Relaxed);
+        // SAFETY: The caller upholds each device's requirements.
+        unsafe {
+            Self {
+                audio_interface: AudioInterface::steal(),
+                dvd_interface: DvdInterface::steal(),
+                external_interface: ExternalInterface::steal(),
+                processor_interface: ProcessorInterface::steal(),
+                video_interface: VideoInterface::steal(),
+            }
+        }
+    }
+}
@@ -21,19 +21,39 @@ macro_rules! mmio_device {
 
         const _: () = assert!(::core::mem::size_of::<RegisterBlock>() == $size);
 
-        #[derive(Clone, Copy)]
-        #[doc = concat!("Represents the ", $doc_name, " MMIO device.")]
+        #[doc = concat!("Represents ownership of the ", $doc_name, " registers.")]
+        ///
+        /// Take it from [`crate::Peripherals`]. It can't be copied, so only its owner can touch
+        /// the registers.
         pub struct $struct_name {
             _private: (),
         }
 
         impl $struct_name {
             const PTR: *mut RegisterBlock = $base as usize as _;
-
-            pub fn new() -> Self {
+            #[allow(dead_code)]
+            const NAME: &'static str = $doc_name;
+
+            /// Unchecked version of taking the device from [`crate::Peripherals`].
+            ///
+            /// # Safety
+            ///
+            /// The caller must ensure the registers aren't accessed concurrently through another
+            /// handle, for example by only using the stolen handle in an interrupt handler for
+            /// registers its owner doesn't touch with that interrupt unmasked.
+            pub unsafe fn steal() -> Self {
                 Self { _private: () }
             }
 
+            $(
+                #[cfg(feature = "mock")]
+                ::paste::paste! {
+                    #[doc = concat!("The address of `", stringify!($reg_name), "`, for mock buses.")]
+                    pub const [<$reg_name:upper _ADDRESS>]: usize =
+                        $base + ::memoffset::offset_of!(RegisterBlock, $reg_name);
+                }
+            )*
+
             $(
                 mmio_device! { @reg_accessors $reg_name ($reg_type) $($reg_access $($indexed)?)? }
             )*
@@ -68,12 +88,12 @@ macro_rules! mmio_device {
     // Non-indexed read implementation.
     (@read $name:ident $type:ty) => {
         ::paste::paste! {
-            pub fn [<read_ $name>](self) -> $type {
-                unsafe {
-                    ::core::ptr::read_volatile(
-                        ::memoffset::raw_field!(Self::PTR, RegisterBlock, $name),
-                    )
-                }
+            pub fn [<read_ $name>](&self) -> $type {
+                let value = unsafe {
+                    mmio_device!(@load ::memoffset::raw_field!(Self::PTR, RegisterBlock, $name))
+                };
+                mmio_device!(@trace $name None, Read, &value);
+                value
             }
         }
     };
@@ -81,11 +101,13 @@ macro_rules! mmio_device {
     // Non-indexed write implementation.
     (@write $name:ident $type:ty) => {
         ::paste::paste! {
-            pub fn [<write_ $name>](self, value: $type) {
+            pub fn [<write_ $name>](&self, value: $type) {
+                mmio_device!(@trace $name None, Write, &value);
                 unsafe {
-                    ::core::ptr::write_volatile(
+                    mmio_device!(
+                        @store
                         ::memoffset::raw_field!(Self::PTR, RegisterBlock, $name).cast_mut(),
-                        value,
+                        value
                     );
                 }
             }
@@ -95,7 +117,7 @@ macro_rules! mmio_device {
     // Non-indexed modify implementation.
     (@modify $name:ident $type:ty) => {
         ::paste::paste! {
-            pub fn [<modify_ $name>](self, f: impl FnOnce($type) -> $type) {
+            pub fn [<modify_ $name>](&self, f: impl FnOnce($type) -> $type) {
                 self.[<write_ $name>](f(self.[<read_ $name>]()));
             }
         }
@@ -104,20 +126,25 @@ macro_rules! mmio_device {
     // Indexed read implementation.
     (@read_indexed $name:ident [$type:ty; $count:literal]) => {
         ::paste::paste! {
-            pub fn [<read_ $name>](self, index: mmio_device!(@log2 $count)) -> $type {
-                unsafe {
-                    ::core::ptr::read_volatile(
+            pub fn [<read_ $name>](&self, index: mmio_device!(@log2 $count)) -> $type {
+                let value = unsafe {
+                    mmio_device!(
+                        @load
                         ::memoffset::raw_field!(Self::PTR, RegisterBlock, $name)
                             .cast::<$type>()
-                            .offset(<mmio_device!(@log2 $count)>::as_u8(index) as isize),
+                            .offset(<mmio_device!(@log2 $count)>::as_u8(index) as isize)
                     )
-                }
+                };
+                mmio_device!(
+                    @trace $name Some(<mmio_device!(@log2 $count)>::as_u8(index)), Read, &value
+                );
+                value
             }
         }
 
         ::seq_macro::seq!(N in 0..$count {
             ::paste::paste! {
-                pub fn [<read_ $name _ N>](self) -> $type {
+                pub fn [<read_ $name _ N>](&self) -> $type {
                     self.[<read_ $name>](<mmio_device!(@log2 $count)>::new_masked(N))
                 }
             }
@@ -127,14 +154,18 @@ macro_rules! mmio_device {
     // Indexed write implementation.
     (@write_indexed $name:ident [$type:ty; $count:literal]) => {
         ::paste::paste! {
-            pub fn [<write_ $name>](self, index: mmio_device!(@log2 $count), value: $type) {
+            pub fn [<write_ $name>](&self, index: mmio_device!(@log2 $count), value: $type) {
+                mmio_device!(
+                    @trace $name Some(<mmio_device!(@log2 $count)>::as_u8(index)), Write, &value
+                );
                 unsafe {
-                    ::core::ptr::write_volatile(
+                    mmio_device!(
+                        @store
                         ::memoffset::raw_field!(Self::PTR, RegisterBlock, $name)
                             .cast_mut()
                             .cast::<$type>()
                             .offset(<mmio_device!(@log2 $count)>::as_u8(index) as isize),
-                        value,
+                        value
                     );
                 }
             }
@@ -142,7 +173,7 @@ macro_rules! mmio_device {
 
         ::seq_macro::seq!(N in 0..$count {
             ::paste::paste! {
-                pub fn [<write_ $name _ N>](self, value: $type) {
+                pub fn [<write_ $name _ N>](&self, value: $type) {
                     self.[<write_ $name>](<mmio_device!(@log2 $count)>::new_masked(N), value);
                 }
             }
@@ -153,7 +184,7 @@ macro_rules! mmio_device {
     (@modify_indexed $name:ident [$type:ty; $count:literal]) => {
         ::paste::paste! {
             pub fn [<modify_ $name>](
-                self,
+                &self,
                 index: mmio_device!(@log2 $count),
                 f: impl FnOnce($type) -> $type,
             ) {
@@ -163,13 +194,42 @@ macro_rules! mmio_device {
 
         ::seq_macro::seq!(N in 0..$count {
             ::paste::paste! {
-                pub fn [<modify_ $name _ N>](self, f: impl FnOnce($type) -> $type) {
+                pub fn [<modify_ $name _ N>](&self, f: impl FnOnce($type) -> $type) {
                     self.[<write_ $name _ N>](f(self.[<read_ $name _ N>]()));
                 }
             }
         });
     };
 
+    // Volatile load, or a mock bus access when testing on the host.
+    (@load $ptr:expr) => {{
+        #[cfg(not(feature = "mock"))]
+        let value = ::core::ptr::read_volatile($ptr);
+        #[cfg(feature = "mock")]
+        let value = $crate::mock::load($ptr);
+        value
+    }};
+
+    // Volatile store, or a mock bus access when testing on the host.
+    (@store $ptr:expr, $value:expr) => {{
+        #[cfg(not(feature = "mock"))]
+        ::core::ptr::write_volatile($ptr, $value);
+        #[cfg(feature = "mock")]
+        $crate::mock::store($ptr, $value);
+    }};
+
+    // Report an access when tracing is compiled in.
+    (@trace $name:ident $index:expr, $direction:ident, $value:expr) => {
+        #[cfg(feature = "trace")]
+        $crate::trace::record(
+            Self::NAME,
+            stringify!($name),
+            $index,
+            $crate::trace::Direction::$direction,
+            $value,
+        );
+    };
+
     // Map array length to narrow integer types for indexing.
     (@log2 2) => { ::mvbitfield::narrow_integer::U1 };
     (@log2 4) => { ::mvbitfield::narrow_integer::U2 };
@@ -0,0 +1,49 @@
+//! Host-side register access, enabled by the `mock` feature.
+//!
+//! Every generated register accessor goes to the bus installed on the current thread instead of
+//! touching memory, so driver logic can run in ordinary unit tests. Register addresses are
+//! available as `<Device>::<REGISTER>_ADDRESS` constants.
+
+use core::cell::RefCell;
+use core::mem::size_of;
+use std::rc::Rc;
+
+use crate::raw::{from_raw, to_raw};
+
+/// Backs register accesses in host tests.
+pub trait Bus {
+    /// Reads `size` bytes at `address`. The result is zero-extended.
+    fn read(&self, address: usize, size: usize) -> u32;
+
+    /// Writes the low `size` bytes of `value` at `address`.
+    fn write(&self, address: usize, size: usize, value: u32);
+}
+
+std::thread_local! {
+    static BUS: RefCell<Option<Rc<dyn Bus>>> = RefCell::new(None);
+}
+
+/// Installs a bus for register accesses on the current thread, replacing any previous one.
+pub fn set_bus(bus: Option<Rc<dyn Bus>>) {
+    BUS.with(|cell| *cell.borrow_mut() = bus);
+}
+
+fn bus() -> Rc<dyn Bus> {
+    BUS.with(|cell| cell.borrow().clone())
+        .expect("register access with no mock bus installed on this thread")
+}
+
+/// # Safety
+///
+/// `ptr` must point at a register, as for `read_volatile`.
+pub(crate) unsafe fn load<T: Copy>(ptr: *const T) -> T {
+    let raw = bus().read(ptr as usize, size_of::<T>());
+    unsafe { from_raw(raw) }
+}
+
+/// # Safety
+///
+/// `ptr` must point at a register, as for `write_volatile`.
+pub(crate) unsafe fn store<T: Copy>(ptr: *mut T, value: T) {
+    bus().write(ptr as usize, size_of::<T>(), to_raw(&value));
+}
@@ -6,7 +6,7 @@ mmio_device! {
     base: 0xcc003000,
     size: 0x30,
     regs: {
-        interrupt_cause: InterruptCause = ro,
+        interrupt_status: InterruptStatus = rw,
         interrupt_mask: InterruptMask = rw,
         unknown1: u32,
         fifo_base_start: u32,
@@ -15,14 +15,50 @@ mmio_device! {
         unknown2: u32,
         unknown3: u32,
         unknown4: u32,
-        reset: u32,
+        reset: Reset = rw,
         unknown5: u32,
-        di_control: u32 = rw,
+        flipper_revision: u32 = ro,
     },
 }
 
+impl ProcessorInterface {
+    /// Returns whether `cause` is asserted, whether or not it's masked.
+    pub fn is_asserted(&self, cause: InterruptCause) -> bool {
+        self.read_interrupt_status().interrupts().contains(cause)
+    }
+
+    /// Clears the given causes' latches in PI. Only the reset switch and GP error causes latch
+    /// here; the others mirror their device's own interrupt status and stay asserted until they're
+    /// acknowledged at the device.
+    pub fn acknowledge(&self, interrupts: Interrupts) {
+        self.write_interrupt_status(InterruptStatus::zero().with_interrupts(interrupts));
+    }
+
+    /// Lets `cause` raise an external interrupt.
+    pub fn unmask(&self, cause: InterruptCause) {
+        self.modify_interrupt_mask(|reg| {
+            reg.with_interrupts(reg.interrupts().with_cause(cause, true))
+        });
+    }
+
+    /// Stops `cause` from raising an external interrupt. It's still visible in the status.
+    pub fn mask(&self, cause: InterruptCause) {
+        self.modify_interrupt_mask(|reg| {
+            reg.with_interrupts(reg.interrupts().with_cause(cause, false))
+        });
+    }
+
+    /// Drives the DVD drive's reset line. The drive needs the line held for a few microseconds,
+    /// then time to boot after it's released.
+    pub fn set_dvd_reset(&self, asserted: bool) {
+        self.modify_reset(|reg| reg.with_system_enable(true).with_dvd_enable(!asserted));
+    }
+}
+
 mvbitfield! {
-    pub struct InterruptCause: u32 {
+    /// Which causes are asserted. Writing a 1 to a cause clears its latch; see
+    /// [`ProcessorInterface::acknowledge`].
+    pub struct InterruptStatus: u32 {
         pub interrupts: 14 as Interrupts,
         _reserved: 2,
         pub reset_switch_is_pressed: 1 as bool,
@@ -30,11 +66,25 @@ mvbitfield! {
 }
 
 mvbitfield! {
+    /// Which causes may raise an external interrupt.
     pub struct InterruptMask: u32 {
         pub interrupts: 14 as Interrupts,
     }
 }
 
+mvbitfield! {
+    /// Reset lines for the system and some of its devices. Each is active low: clearing a bit
+    /// holds its block in reset.
+    pub struct Reset: u32 {
+        /// Clearing this resets the whole system, which then boots with `reset_code` preserved.
+        pub system_enable: 1 as bool,
+        _unknown: 1,
+        pub dvd_enable: 1 as bool,
+        /// Passed across a system reset, so the IPL can tell a hot reset from a cold boot.
+        pub reset_code: 29,
+    }
+}
+
 mvbitfield! {
     pub struct Interrupts: U14 {
         pub gp_error: 1 as bool,
@@ -54,7 +104,77 @@ mvbitfield! {
     }
 }
 
+/// One of the causes in [`Interrupts`], numbered by its bit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum InterruptCause {
+    GpError = 0,
+    ResetSwitch = 1,
+    Dvd = 2,
+    Serial = 3,
+    Exi = 4,
+    Streaming = 5,
+    Dsp = 6,
+    MemoryInterface = 7,
+    VideoInterface = 8,
+    GpToken = 9,
+    GpFinish = 10,
+    CommandProcessor = 11,
+    ExternalDebugger = 12,
+    HighSpeedPort = 13,
+}
+
+impl InterruptCause {
+    /// Every cause, in bit order.
+    pub const ALL: [Self; 14] = [
+        Self::GpError,
+        Self::ResetSwitch,
+        Self::Dvd,
+        Self::Serial,
+        Self::Exi,
+        Self::Streaming,
+        Self::Dsp,
+        Self::MemoryInterface,
+        Self::VideoInterface,
+        Self::GpToken,
+        Self::GpFinish,
+        Self::CommandProcessor,
+        Self::ExternalDebugger,
+        Self::HighSpeedPort,
+    ];
+
+    const fn bit(self) -> u32 {
+        1 << self as u32
+    }
+}
+
 impl Interrupts {
+    /// Just `cause`.
+    pub fn from_cause(cause: InterruptCause) -> Self {
+        InterruptMask::from_u32(cause.bit()).interrupts()
+    }
+
+    pub fn contains(self, cause: InterruptCause) -> bool {
+        InterruptMask::zero().with_interrupts(self).as_u32() & cause.bit() != 0
+    }
+
+    pub fn with_cause(self, cause: InterruptCause, value: bool) -> Self {
+        let bits = InterruptMask::zero().with_interrupts(self).as_u32();
+        let bits = if value {
+            bits | cause.bit()
+        } else {
+            bits & !cause.bit()
+        };
+        InterruptMask::from_u32(bits).interrupts()
+    }
+
+    /// The asserted causes, in bit order.
+    pub fn causes(self) -> impl Iterator<Item = InterruptCause> {
+        InterruptCause::ALL
+            .into_iter()
+            .filter(move |&cause| self.contains(cause))
+    }
+
     pub const fn all() -> Self {
         Self::zero()
             .with_gp_error(true)
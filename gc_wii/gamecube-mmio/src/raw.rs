@@ -0,0 +1,33 @@
+//! Conversions between register values and their raw bits, for code that handles every register
+//! type alike.
+//!
+//! Register values are always plain integers or bitfields wrapping one.
+
+use core::mem::{size_of, transmute_copy};
+
+pub(crate) fn to_raw<T: Copy>(value: &T) -> u32 {
+    // SAFETY: Each conversion is to an integer exactly the size of `T`.
+    unsafe {
+        match size_of::<T>() {
+            1 => transmute_copy::<T, u8>(value) as u32,
+            2 => transmute_copy::<T, u16>(value) as u32,
+            4 => transmute_copy::<T, u32>(value),
+            size => panic!("unexpected register size: {size}"),
+        }
+    }
+}
+
+/// # Safety
+///
+/// `T` must be a register type, so that every bit pattern of its size is valid.
+pub(crate) unsafe fn from_raw<T: Copy>(raw: u32) -> T {
+    // SAFETY: Each conversion is from an integer exactly the size of `T`.
+    unsafe {
+        match size_of::<T>() {
+            1 => transmute_copy::<u8, T>(&(raw as u8)),
+            2 => transmute_copy::<u16, T>(&(raw as u16)),
+            4 => transmute_copy::<u32, T>(&raw),
+            size => panic!("unexpected register size: {size}"),
+        }
+    }
+}
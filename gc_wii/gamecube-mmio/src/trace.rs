@@ -0,0 +1,65 @@
+//! Register access tracing for driver bring-up.
+//!
+//! With the `trace` feature enabled, every generated register accessor reports its access to the
+//! installed sink. Without it, none of the hooks are compiled in.
+
+use core::mem::transmute;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::raw::to_raw;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Read,
+    Write,
+}
+
+/// A single register access.
+#[derive(Clone, Copy, Debug)]
+pub struct TraceEvent {
+    /// The device's short name, like "DI".
+    pub device: &'static str,
+    pub register: &'static str,
+    /// The element index for indexed registers.
+    pub index: Option<u8>,
+    pub direction: Direction,
+    /// The raw register value, zero-extended.
+    pub value: u32,
+}
+
+pub type TraceSink = fn(&TraceEvent);
+
+/// The installed sink as a `usize`, or zero for none.
+static SINK: AtomicUsize = AtomicUsize::new(0);
+
+/// Set while the sink runs. A sink that touches registers itself, like one printing over a USB
+/// Gecko, would otherwise recurse forever.
+static IN_SINK: AtomicBool = AtomicBool::new(false);
+
+/// Installs a sink for all register accesses, replacing any previous one. `None` stops tracing.
+pub fn set_sink(sink: Option<TraceSink>) {
+    SINK.store(sink.map_or(0, |sink| sink as usize), Ordering::Release);
+}
+
+pub(crate) fn record<T: Copy>(
+    device: &'static str,
+    register: &'static str,
+    index: Option<u8>,
+    direction: Direction,
+    value: &T,
+) {
+    let sink = SINK.load(Ordering::Acquire);
+    if sink == 0 || IN_SINK.swap(true, Ordering::Acquire) {
+        return;
+    }
+    // SAFETY: Nonzero values in `SINK` only come from `TraceSink`s in `set_sink`.
+    let sink: TraceSink = unsafe { transmute(sink) };
+    sink(&TraceEvent {
+        device,
+        register,
+        index,
+        direction,
+        value: to_raw(value),
+    });
+    IN_SINK.store(false, Ordering::Release);
+}
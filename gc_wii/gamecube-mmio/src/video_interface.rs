@@ -6,7 +6,7 @@ mmio_device! {
     doc_name: "VI",
     struct_name: VideoInterface,
     base: 0xcc002000,
-    size: 0x70,
+    size: 0x80,
     regs: {
         vertical_timing_a: VerticalTimingA = rw,
         display_configuration: DisplayConfiguration = rw,
@@ -24,10 +24,19 @@ mmio_device! {
         horizontal_position: u16 = ro,
         display_interrupt: [DisplayInterrupt; 4] = rw(indexed),
         display_latch: [DisplayLatch; 2] = rw(indexed),
+        scaling_width: ScalingWidth = rw,
         horizontal_scaling: HorizontalScaling = rw,
-        filter_coefficient_table: [u32; 8] = rw(indexed),
+        filter_taps_0_to_2: FilterTapsWide = rw,
+        filter_taps_3_to_5: FilterTapsWide = rw,
+        filter_taps_6_to_8: FilterTapsWide = rw,
+        filter_taps_9_to_24: [FilterTapsNarrow; 4] = rw(indexed),
+        anti_aliasing: u32 = rw,
         clock_select: ClockSelect = rw,
-        _padding_todo_there_are_more_registers: u16,
+        dtv_status: DtvStatus = rw,
+        border_width: u16 = rw,
+        border_horizontal_blank_end: BorderHorizontalBlankEnd = rw,
+        border_horizontal_blank_start: BorderHorizontalBlankStart = rw,
+        _reserved: [u16; 5],
     },
 }
 
@@ -155,7 +164,7 @@ mvbitfield! {
 mvbitfield! {
     pub struct FieldBase: u32 {
         // Must be 9-bit aligned.
-        pub addresss: 24,
+        pub address: 24,
         pub horizontal_offset: 4,
         pub shift_address_left_five: 1 as bool,
     }
@@ -184,13 +193,36 @@ mvbitfield! {
 }
 
 mvbitfield! {
-    pub struct HorizontalScaling: u32 {
+    pub struct ScalingWidth: u16 {
+        pub stride_per_half_line_in_16_byte_units: 8,
+        pub framebuffer_width_in_16_pixel_units: 7,
+    }
+}
+
+mvbitfield! {
+    pub struct HorizontalScaling: u16 {
         pub step_size_u1_8: 9,
         _reserved: 3,
         pub enable: 1 as bool,
-        _reserved: 3,
-        pub stride_per_half_line_in_16_byte_units: 8,
-        pub framebuffer_width_in_16_pixel_units: 7,
+    }
+}
+
+mvbitfield! {
+    /// Three of the first nine taps of the scaling filter. They're 10 bits wide.
+    pub struct FilterTapsWide: u32 {
+        pub tap_0: 10,
+        pub tap_1: 10,
+        pub tap_2: 10,
+    }
+}
+
+mvbitfield! {
+    /// Four of the last sixteen taps of the scaling filter. They're 8 bits wide.
+    pub struct FilterTapsNarrow: u32 {
+        pub tap_0: 8,
+        pub tap_1: 8,
+        pub tap_2: 8,
+        pub tap_3: 8,
     }
 }
 
@@ -217,3 +249,27 @@ impl Clock {
         unsafe { transmute(self) }
     }
 }
+
+mvbitfield! {
+    pub struct DtvStatus: u16 {
+        /// Set when a component cable is attached, so progressive scan is available.
+        pub component_cable: 1 as bool,
+        pub ntsc_j: 1 as bool,
+    }
+}
+
+mvbitfield! {
+    /// Horizontal blanking end for the border around a narrower-than-screen picture, in pixels
+    /// from the start of the line.
+    pub struct BorderHorizontalBlankEnd: u16 {
+        pub position: 10,
+        _reserved: 5,
+        pub border_enable: 1 as bool,
+    }
+}
+
+mvbitfield! {
+    pub struct BorderHorizontalBlankStart: u16 {
+        pub position: 10,
+    }
+}
@@ -0,0 +1,28 @@
+#[doc = r"Register block"]
+#[repr(C)]
+pub struct RegisterBlock {
+    #[doc = "0x00 - "]
+    pub control: crate::Reg<control::CONTROL_SPEC>,
+    #[doc = "0x04 - "]
+    pub volume: crate::Reg<volume::VOLUME_SPEC>,
+    #[doc = "0x08 - "]
+    pub sample_counter: crate::Reg<sample_counter::SAMPLE_COUNTER_SPEC>,
+    #[doc = "0x0c - "]
+    pub interrupt_timing: crate::Reg<interrupt_timing::INTERRUPT_TIMING_SPEC>,
+}
+#[doc = "control register accessor: an alias for `Reg<CONTROL_SPEC>`"]
+pub type CONTROL = crate::Reg<control::CONTROL_SPEC>;
+#[doc = ""]
+pub mod control;
+#[doc = "volume register accessor: an alias for `Reg<VOLUME_SPEC>`"]
+pub type VOLUME = crate::Reg<volume::VOLUME_SPEC>;
+#[doc = ""]
+pub mod volume;
+#[doc = "sample_counter register accessor: an alias for `Reg<SAMPLE_COUNTER_SPEC>`"]
+pub type SAMPLE_COUNTER = crate::Reg<sample_counter::SAMPLE_COUNTER_SPEC>;
+#[doc = ""]
+pub mod sample_counter;
+#[doc = "interrupt_timing register accessor: an alias for `Reg<INTERRUPT_TIMING_SPEC>`"]
+pub type INTERRUPT_TIMING = crate::Reg<interrupt_timing::INTERRUPT_TIMING_SPEC>;
+#[doc = ""]
+pub mod interrupt_timing;
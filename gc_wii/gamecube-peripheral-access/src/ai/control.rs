@@ -0,0 +1,462 @@
+#[doc = "Register `control` reader"]
+pub struct R(crate::R<CONTROL_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<CONTROL_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<CONTROL_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<CONTROL_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `control` writer"]
+pub struct W(crate::W<CONTROL_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<CONTROL_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<CONTROL_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<CONTROL_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = ""]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DMA_SAMPLE_RATE_A {
+    #[doc = "0: `0`"]
+    KHZ_48 = 0,
+    #[doc = "1: `1`"]
+    KHZ_32 = 1,
+}
+impl From<DMA_SAMPLE_RATE_A> for bool {
+    #[inline(always)]
+    fn from(variant: DMA_SAMPLE_RATE_A) -> Self {
+        variant as u8 != 0
+    }
+}
+#[doc = "Field `dma_sample_rate` reader - "]
+pub struct DMA_SAMPLE_RATE_R(crate::FieldReader<bool, DMA_SAMPLE_RATE_A>);
+impl DMA_SAMPLE_RATE_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        DMA_SAMPLE_RATE_R(crate::FieldReader::new(bits))
+    }
+    #[doc = r"Get enumerated values variant"]
+    #[inline(always)]
+    pub fn variant(&self) -> DMA_SAMPLE_RATE_A {
+        match self.bits {
+            false => DMA_SAMPLE_RATE_A::KHZ_48,
+            true => DMA_SAMPLE_RATE_A::KHZ_32,
+        }
+    }
+    #[doc = "Checks if the value of the field is `KHZ_48`"]
+    #[inline(always)]
+    pub fn is_khz_48(&self) -> bool {
+        **self == DMA_SAMPLE_RATE_A::KHZ_48
+    }
+    #[doc = "Checks if the value of the field is `KHZ_32`"]
+    #[inline(always)]
+    pub fn is_khz_32(&self) -> bool {
+        **self == DMA_SAMPLE_RATE_A::KHZ_32
+    }
+}
+impl core::ops::Deref for DMA_SAMPLE_RATE_R {
+    type Target = crate::FieldReader<bool, DMA_SAMPLE_RATE_A>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `dma_sample_rate` writer - "]
+pub struct DMA_SAMPLE_RATE_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> DMA_SAMPLE_RATE_W<'a> {
+    #[doc = r"Writes `variant` to the field"]
+    #[inline(always)]
+    pub fn variant(self, variant: DMA_SAMPLE_RATE_A) -> &'a mut W {
+        self.bit(variant.into())
+    }
+    #[doc = "`0`"]
+    #[inline(always)]
+    pub fn khz_48(self) -> &'a mut W {
+        self.variant(DMA_SAMPLE_RATE_A::KHZ_48)
+    }
+    #[doc = "`1`"]
+    #[inline(always)]
+    pub fn khz_32(self) -> &'a mut W {
+        self.variant(DMA_SAMPLE_RATE_A::KHZ_32)
+    }
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 6)) | ((value as u32 & 0x01) << 6);
+        self.w
+    }
+}
+#[doc = "Field `reset_sample_counter` writer - "]
+pub struct RESET_SAMPLE_COUNTER_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> RESET_SAMPLE_COUNTER_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 5)) | ((value as u32 & 0x01) << 5);
+        self.w
+    }
+}
+#[doc = "Field `interrupt_valid` reader - "]
+pub struct INTERRUPT_VALID_R(crate::FieldReader<bool, bool>);
+impl INTERRUPT_VALID_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        INTERRUPT_VALID_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for INTERRUPT_VALID_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `interrupt_valid` writer - "]
+pub struct INTERRUPT_VALID_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> INTERRUPT_VALID_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 4)) | ((value as u32 & 0x01) << 4);
+        self.w
+    }
+}
+#[doc = "Field `interrupt` reader - "]
+pub struct INTERRUPT_R(crate::FieldReader<bool, bool>);
+impl INTERRUPT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        INTERRUPT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for INTERRUPT_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `interrupt` writer - "]
+pub struct INTERRUPT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> INTERRUPT_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 3)) | ((value as u32 & 0x01) << 3);
+        self.w
+    }
+}
+#[doc = "Field `interrupt_mask` reader - "]
+pub struct INTERRUPT_MASK_R(crate::FieldReader<bool, bool>);
+impl INTERRUPT_MASK_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        INTERRUPT_MASK_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for INTERRUPT_MASK_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `interrupt_mask` writer - "]
+pub struct INTERRUPT_MASK_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> INTERRUPT_MASK_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 2)) | ((value as u32 & 0x01) << 2);
+        self.w
+    }
+}
+#[doc = ""]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum STREAMING_SAMPLE_RATE_A {
+    #[doc = "0: `0`"]
+    KHZ_32 = 0,
+    #[doc = "1: `1`"]
+    KHZ_48 = 1,
+}
+impl From<STREAMING_SAMPLE_RATE_A> for bool {
+    #[inline(always)]
+    fn from(variant: STREAMING_SAMPLE_RATE_A) -> Self {
+        variant as u8 != 0
+    }
+}
+#[doc = "Field `streaming_sample_rate` reader - "]
+pub struct STREAMING_SAMPLE_RATE_R(crate::FieldReader<bool, STREAMING_SAMPLE_RATE_A>);
+impl STREAMING_SAMPLE_RATE_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        STREAMING_SAMPLE_RATE_R(crate::FieldReader::new(bits))
+    }
+    #[doc = r"Get enumerated values variant"]
+    #[inline(always)]
+    pub fn variant(&self) -> STREAMING_SAMPLE_RATE_A {
+        match self.bits {
+            false => STREAMING_SAMPLE_RATE_A::KHZ_32,
+            true => STREAMING_SAMPLE_RATE_A::KHZ_48,
+        }
+    }
+    #[doc = "Checks if the value of the field is `KHZ_32`"]
+    #[inline(always)]
+    pub fn is_khz_32(&self) -> bool {
+        **self == STREAMING_SAMPLE_RATE_A::KHZ_32
+    }
+    #[doc = "Checks if the value of the field is `KHZ_48`"]
+    #[inline(always)]
+    pub fn is_khz_48(&self) -> bool {
+        **self == STREAMING_SAMPLE_RATE_A::KHZ_48
+    }
+}
+impl core::ops::Deref for STREAMING_SAMPLE_RATE_R {
+    type Target = crate::FieldReader<bool, STREAMING_SAMPLE_RATE_A>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `streaming_sample_rate` writer - "]
+pub struct STREAMING_SAMPLE_RATE_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> STREAMING_SAMPLE_RATE_W<'a> {
+    #[doc = r"Writes `variant` to the field"]
+    #[inline(always)]
+    pub fn variant(self, variant: STREAMING_SAMPLE_RATE_A) -> &'a mut W {
+        self.bit(variant.into())
+    }
+    #[doc = "`0`"]
+    #[inline(always)]
+    pub fn khz_32(self) -> &'a mut W {
+        self.variant(STREAMING_SAMPLE_RATE_A::KHZ_32)
+    }
+    #[doc = "`1`"]
+    #[inline(always)]
+    pub fn khz_48(self) -> &'a mut W {
+        self.variant(STREAMING_SAMPLE_RATE_A::KHZ_48)
+    }
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 1)) | ((value as u32 & 0x01) << 1);
+        self.w
+    }
+}
+#[doc = "Field `playing` reader - "]
+pub struct PLAYING_R(crate::FieldReader<bool, bool>);
+impl PLAYING_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        PLAYING_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for PLAYING_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `playing` writer - "]
+pub struct PLAYING_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> PLAYING_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x01) | (value as u32 & 0x01);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bit 6"]
+    #[inline(always)]
+    pub fn dma_sample_rate(&self) -> DMA_SAMPLE_RATE_R {
+        DMA_SAMPLE_RATE_R::new(((self.bits >> 6) & 0x01) != 0)
+    }
+    #[doc = "Bit 4"]
+    #[inline(always)]
+    pub fn interrupt_valid(&self) -> INTERRUPT_VALID_R {
+        INTERRUPT_VALID_R::new(((self.bits >> 4) & 0x01) != 0)
+    }
+    #[doc = "Bit 3"]
+    #[inline(always)]
+    pub fn interrupt(&self) -> INTERRUPT_R {
+        INTERRUPT_R::new(((self.bits >> 3) & 0x01) != 0)
+    }
+    #[doc = "Bit 2"]
+    #[inline(always)]
+    pub fn interrupt_mask(&self) -> INTERRUPT_MASK_R {
+        INTERRUPT_MASK_R::new(((self.bits >> 2) & 0x01) != 0)
+    }
+    #[doc = "Bit 1"]
+    #[inline(always)]
+    pub fn streaming_sample_rate(&self) -> STREAMING_SAMPLE_RATE_R {
+        STREAMING_SAMPLE_RATE_R::new(((self.bits >> 1) & 0x01) != 0)
+    }
+    #[doc = "Bit 0"]
+    #[inline(always)]
+    pub fn playing(&self) -> PLAYING_R {
+        PLAYING_R::new((self.bits & 0x01) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 6"]
+    #[inline(always)]
+    pub fn dma_sample_rate(&mut self) -> DMA_SAMPLE_RATE_W {
+        DMA_SAMPLE_RATE_W { w: self }
+    }
+    #[doc = "Bit 5"]
+    #[inline(always)]
+    pub fn reset_sample_counter(&mut self) -> RESET_SAMPLE_COUNTER_W {
+        RESET_SAMPLE_COUNTER_W { w: self }
+    }
+    #[doc = "Bit 4"]
+    #[inline(always)]
+    pub fn interrupt_valid(&mut self) -> INTERRUPT_VALID_W {
+        INTERRUPT_VALID_W { w: self }
+    }
+    #[doc = "Bit 3"]
+    #[inline(always)]
+    pub fn interrupt(&mut self) -> INTERRUPT_W {
+        INTERRUPT_W { w: self }
+    }
+    #[doc = "Bit 2"]
+    #[inline(always)]
+    pub fn interrupt_mask(&mut self) -> INTERRUPT_MASK_W {
+        INTERRUPT_MASK_W { w: self }
+    }
+    #[doc = "Bit 1"]
+    #[inline(always)]
+    pub fn streaming_sample_rate(&mut self) -> STREAMING_SAMPLE_RATE_W {
+        STREAMING_SAMPLE_RATE_W { w: self }
+    }
+    #[doc = "Bit 0"]
+    #[inline(always)]
+    pub fn playing(&mut self) -> PLAYING_W {
+        PLAYING_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [control](index.html) module"]
+pub struct CONTROL_SPEC;
+impl crate::RegisterSpec for CONTROL_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [control::R](R) reader structure"]
+impl crate::Readable for CONTROL_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [control::W](W) writer structure"]
+impl crate::Writable for CONTROL_SPEC {
+    type Writer = W;
+}
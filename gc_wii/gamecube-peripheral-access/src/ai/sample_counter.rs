@@ -0,0 +1,24 @@
+#[doc = "Register `sample_counter` reader"]
+pub struct R(crate::R<SAMPLE_COUNTER_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<SAMPLE_COUNTER_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<SAMPLE_COUNTER_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<SAMPLE_COUNTER_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [sample_counter](index.html) module"]
+pub struct SAMPLE_COUNTER_SPEC;
+impl crate::RegisterSpec for SAMPLE_COUNTER_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [sample_counter::R](R) reader structure"]
+impl crate::Readable for SAMPLE_COUNTER_SPEC {
+    type Reader = R;
+}
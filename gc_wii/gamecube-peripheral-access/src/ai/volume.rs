@@ -0,0 +1,133 @@
+#[doc = "Register `volume` reader"]
+pub struct R(crate::R<VOLUME_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<VOLUME_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<VOLUME_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<VOLUME_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `volume` writer"]
+pub struct W(crate::W<VOLUME_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<VOLUME_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<VOLUME_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<VOLUME_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `right` reader - "]
+pub struct RIGHT_R(crate::FieldReader<u8, u8>);
+impl RIGHT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        RIGHT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for RIGHT_R {
+    type Target = crate::FieldReader<u8, u8>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `right` writer - "]
+pub struct RIGHT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> RIGHT_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0xff << 8)) | ((value as u32 & 0xff) << 8);
+        self.w
+    }
+}
+#[doc = "Field `left` reader - "]
+pub struct LEFT_R(crate::FieldReader<u8, u8>);
+impl LEFT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        LEFT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for LEFT_R {
+    type Target = crate::FieldReader<u8, u8>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `left` writer - "]
+pub struct LEFT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> LEFT_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0xff) | (value as u32 & 0xff);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bits 8:15"]
+    #[inline(always)]
+    pub fn right(&self) -> RIGHT_R {
+        RIGHT_R::new(((self.bits >> 8) & 0xff) as u8)
+    }
+    #[doc = "Bits 0:7"]
+    #[inline(always)]
+    pub fn left(&self) -> LEFT_R {
+        LEFT_R::new((self.bits & 0xff) as u8)
+    }
+}
+impl W {
+    #[doc = "Bits 8:15"]
+    #[inline(always)]
+    pub fn right(&mut self) -> RIGHT_W {
+        RIGHT_W { w: self }
+    }
+    #[doc = "Bits 0:7"]
+    #[inline(always)]
+    pub fn left(&mut self) -> LEFT_W {
+        LEFT_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [volume](index.html) module"]
+pub struct VOLUME_SPEC;
+impl crate::RegisterSpec for VOLUME_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [volume::R](R) reader structure"]
+impl crate::Readable for VOLUME_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [volume::W](W) writer structure"]
+impl crate::Writable for VOLUME_SPEC {
+    type Writer = W;
+}
@@ -0,0 +1,124 @@
+#[doc = r"Register block"]
+#[repr(C)]
+pub struct RegisterBlock {
+    #[doc = "0x00 - "]
+    pub mailbox_to_dsp_h: crate::Reg<mailbox_to_dsp_h::MAILBOX_TO_DSP_H_SPEC>,
+    #[doc = "0x02 - "]
+    pub mailbox_to_dsp_l: crate::Reg<mailbox_to_dsp_l::MAILBOX_TO_DSP_L_SPEC>,
+    #[doc = "0x04 - "]
+    pub mailbox_from_dsp_h: crate::Reg<mailbox_from_dsp_h::MAILBOX_FROM_DSP_H_SPEC>,
+    #[doc = "0x06 - "]
+    pub mailbox_from_dsp_l: crate::Reg<mailbox_from_dsp_l::MAILBOX_FROM_DSP_L_SPEC>,
+    _reserved4: [u8; 0x02],
+    #[doc = "0x0a - "]
+    pub control: crate::Reg<control::CONTROL_SPEC>,
+    _reserved5: [u8; 0x06],
+    #[doc = "0x12 - "]
+    pub aram_size: crate::Reg<aram_size::ARAM_SIZE_SPEC>,
+    _reserved6: [u8; 0x02],
+    #[doc = "0x16 - "]
+    pub aram_mode: crate::Reg<aram_mode::ARAM_MODE_SPEC>,
+    _reserved7: [u8; 0x02],
+    #[doc = "0x1a - "]
+    pub aram_refresh: crate::Reg<aram_refresh::ARAM_REFRESH_SPEC>,
+    _reserved8: [u8; 0x04],
+    #[doc = "0x20 - "]
+    pub aram_dma_main_address_h: crate::Reg<aram_dma_main_address_h::ARAM_DMA_MAIN_ADDRESS_H_SPEC>,
+    #[doc = "0x22 - "]
+    pub aram_dma_main_address_l: crate::Reg<aram_dma_main_address_l::ARAM_DMA_MAIN_ADDRESS_L_SPEC>,
+    #[doc = "0x24 - "]
+    pub aram_dma_aram_address_h: crate::Reg<aram_dma_aram_address_h::ARAM_DMA_ARAM_ADDRESS_H_SPEC>,
+    #[doc = "0x26 - "]
+    pub aram_dma_aram_address_l: crate::Reg<aram_dma_aram_address_l::ARAM_DMA_ARAM_ADDRESS_L_SPEC>,
+    #[doc = "0x28 - "]
+    pub aram_dma_count_h: crate::Reg<aram_dma_count_h::ARAM_DMA_COUNT_H_SPEC>,
+    #[doc = "0x2a - "]
+    pub aram_dma_count_l: crate::Reg<aram_dma_count_l::ARAM_DMA_COUNT_L_SPEC>,
+    _reserved14: [u8; 0x04],
+    #[doc = "0x30 - "]
+    pub audio_dma_start_h: crate::Reg<audio_dma_start_h::AUDIO_DMA_START_H_SPEC>,
+    #[doc = "0x32 - "]
+    pub audio_dma_start_l: crate::Reg<audio_dma_start_l::AUDIO_DMA_START_L_SPEC>,
+    _reserved16: [u8; 0x02],
+    #[doc = "0x36 - "]
+    pub audio_dma_control: crate::Reg<audio_dma_control::AUDIO_DMA_CONTROL_SPEC>,
+    _reserved17: [u8; 0x02],
+    #[doc = "0x3a - "]
+    pub audio_dma_blocks_left: crate::Reg<audio_dma_blocks_left::AUDIO_DMA_BLOCKS_LEFT_SPEC>,
+}
+#[doc = "mailbox_to_dsp_h register accessor: an alias for `Reg<MAILBOX_TO_DSP_H_SPEC>`"]
+pub type MAILBOX_TO_DSP_H = crate::Reg<mailbox_to_dsp_h::MAILBOX_TO_DSP_H_SPEC>;
+#[doc = ""]
+pub mod mailbox_to_dsp_h;
+#[doc = "mailbox_to_dsp_l register accessor: an alias for `Reg<MAILBOX_TO_DSP_L_SPEC>`"]
+pub type MAILBOX_TO_DSP_L = crate::Reg<mailbox_to_dsp_l::MAILBOX_TO_DSP_L_SPEC>;
+#[doc = ""]
+pub mod mailbox_to_dsp_l;
+#[doc = "mailbox_from_dsp_h register accessor: an alias for `Reg<MAILBOX_FROM_DSP_H_SPEC>`"]
+pub type MAILBOX_FROM_DSP_H = crate::Reg<mailbox_from_dsp_h::MAILBOX_FROM_DSP_H_SPEC>;
+#[doc = ""]
+pub mod mailbox_from_dsp_h;
+#[doc = "mailbox_from_dsp_l register accessor: an alias for `Reg<MAILBOX_FROM_DSP_L_SPEC>`"]
+pub type MAILBOX_FROM_DSP_L = crate::Reg<mailbox_from_dsp_l::MAILBOX_FROM_DSP_L_SPEC>;
+#[doc = ""]
+pub mod mailbox_from_dsp_l;
+#[doc = "control register accessor: an alias for `Reg<CONTROL_SPEC>`"]
+pub type CONTROL = crate::Reg<control::CONTROL_SPEC>;
+#[doc = ""]
+pub mod control;
+#[doc = "aram_size register accessor: an alias for `Reg<ARAM_SIZE_SPEC>`"]
+pub type ARAM_SIZE = crate::Reg<aram_size::ARAM_SIZE_SPEC>;
+#[doc = ""]
+pub mod aram_size;
+#[doc = "aram_mode register accessor: an alias for `Reg<ARAM_MODE_SPEC>`"]
+pub type ARAM_MODE = crate::Reg<aram_mode::ARAM_MODE_SPEC>;
+#[doc = ""]
+pub mod aram_mode;
+#[doc = "aram_refresh register accessor: an alias for `Reg<ARAM_REFRESH_SPEC>`"]
+pub type ARAM_REFRESH = crate::Reg<aram_refresh::ARAM_REFRESH_SPEC>;
+#[doc = ""]
+pub mod aram_refresh;
+#[doc = "aram_dma_main_address_h register accessor: an alias for `Reg<ARAM_DMA_MAIN_ADDRESS_H_SPEC>`"]
+pub type ARAM_DMA_MAIN_ADDRESS_H =
+    crate::Reg<aram_dma_main_address_h::ARAM_DMA_MAIN_ADDRESS_H_SPEC>;
+#[doc = ""]
+pub mod aram_dma_main_address_h;
+#[doc = "aram_dma_main_address_l register accessor: an alias for `Reg<ARAM_DMA_MAIN_ADDRESS_L_SPEC>`"]
+pub type ARAM_DMA_MAIN_ADDRESS_L =
+    crate::Reg<aram_dma_main_address_l::ARAM_DMA_MAIN_ADDRESS_L_SPEC>;
+#[doc = ""]
+pub mod aram_dma_main_address_l;
+#[doc = "aram_dma_aram_address_h register accessor: an alias for `Reg<ARAM_DMA_ARAM_ADDRESS_H_SPEC>`"]
+pub type ARAM_DMA_ARAM_ADDRESS_H =
+    crate::Reg<aram_dma_aram_address_h::ARAM_DMA_ARAM_ADDRESS_H_SPEC>;
+#[doc = ""]
+pub mod aram_dma_aram_address_h;
+#[doc = "aram_dma_aram_address_l register accessor: an alias for `Reg<ARAM_DMA_ARAM_ADDRESS_L_SPEC>`"]
+pub type ARAM_DMA_ARAM_ADDRESS_L =
+    crate::Reg<aram_dma_aram_address_l::ARAM_DMA_ARAM_ADDRESS_L_SPEC>;
+#[doc = ""]
+pub mod aram_dma_aram_address_l;
+#[doc = "aram_dma_count_h register accessor: an alias for `Reg<ARAM_DMA_COUNT_H_SPEC>`"]
+pub type ARAM_DMA_COUNT_H = crate::Reg<aram_dma_count_h::ARAM_DMA_COUNT_H_SPEC>;
+#[doc = ""]
+pub mod aram_dma_count_h;
+#[doc = "aram_dma_count_l register accessor: an alias for `Reg<ARAM_DMA_COUNT_L_SPEC>`"]
+pub type ARAM_DMA_COUNT_L = crate::Reg<aram_dma_count_l::ARAM_DMA_COUNT_L_SPEC>;
+#[doc = ""]
+pub mod aram_dma_count_l;
+#[doc = "audio_dma_start_h register accessor: an alias for `Reg<AUDIO_DMA_START_H_SPEC>`"]
+pub type AUDIO_DMA_START_H = crate::Reg<audio_dma_start_h::AUDIO_DMA_START_H_SPEC>;
+#[doc = ""]
+pub mod audio_dma_start_h;
+#[doc = "audio_dma_start_l register accessor: an alias for `Reg<AUDIO_DMA_START_L_SPEC>`"]
+pub type AUDIO_DMA_START_L = crate::Reg<audio_dma_start_l::AUDIO_DMA_START_L_SPEC>;
+#[doc = ""]
+pub mod audio_dma_start_l;
+#[doc = "audio_dma_control register accessor: an alias for `Reg<AUDIO_DMA_CONTROL_SPEC>`"]
+pub type AUDIO_DMA_CONTROL = crate::Reg<audio_dma_control::AUDIO_DMA_CONTROL_SPEC>;
+#[doc = ""]
+pub mod audio_dma_control;
+#[doc = "audio_dma_blocks_left register accessor: an alias for `Reg<AUDIO_DMA_BLOCKS_LEFT_SPEC>`"]
+pub type AUDIO_DMA_BLOCKS_LEFT = crate::Reg<audio_dma_blocks_left::AUDIO_DMA_BLOCKS_LEFT_SPEC>;
+#[doc = ""]
+pub mod audio_dma_blocks_left;
@@ -0,0 +1,190 @@
+#[doc = "Register `aram_dma_count_h` reader"]
+pub struct R(crate::R<ARAM_DMA_COUNT_H_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<ARAM_DMA_COUNT_H_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<ARAM_DMA_COUNT_H_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<ARAM_DMA_COUNT_H_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `aram_dma_count_h` writer"]
+pub struct W(crate::W<ARAM_DMA_COUNT_H_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<ARAM_DMA_COUNT_H_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<ARAM_DMA_COUNT_H_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<ARAM_DMA_COUNT_H_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = ""]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DIRECTION_A {
+    #[doc = "0: `0`"]
+    MAIN_TO_ARAM = 0,
+    #[doc = "1: `1`"]
+    ARAM_TO_MAIN = 1,
+}
+impl From<DIRECTION_A> for bool {
+    #[inline(always)]
+    fn from(variant: DIRECTION_A) -> Self {
+        variant as u8 != 0
+    }
+}
+#[doc = "Field `direction` reader - "]
+pub struct DIRECTION_R(crate::FieldReader<bool, DIRECTION_A>);
+impl DIRECTION_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        DIRECTION_R(crate::FieldReader::new(bits))
+    }
+    #[doc = r"Get enumerated values variant"]
+    #[inline(always)]
+    pub fn variant(&self) -> DIRECTION_A {
+        match self.bits {
+            false => DIRECTION_A::MAIN_TO_ARAM,
+            true => DIRECTION_A::ARAM_TO_MAIN,
+        }
+    }
+    #[doc = "Checks if the value of the field is `MAIN_TO_ARAM`"]
+    #[inline(always)]
+    pub fn is_main_to_aram(&self) -> bool {
+        **self == DIRECTION_A::MAIN_TO_ARAM
+    }
+    #[doc = "Checks if the value of the field is `ARAM_TO_MAIN`"]
+    #[inline(always)]
+    pub fn is_aram_to_main(&self) -> bool {
+        **self == DIRECTION_A::ARAM_TO_MAIN
+    }
+}
+impl core::ops::Deref for DIRECTION_R {
+    type Target = crate::FieldReader<bool, DIRECTION_A>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `direction` writer - "]
+pub struct DIRECTION_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> DIRECTION_W<'a> {
+    #[doc = r"Writes `variant` to the field"]
+    #[inline(always)]
+    pub fn variant(self, variant: DIRECTION_A) -> &'a mut W {
+        self.bit(variant.into())
+    }
+    #[doc = "`0`"]
+    #[inline(always)]
+    pub fn main_to_aram(self) -> &'a mut W {
+        self.variant(DIRECTION_A::MAIN_TO_ARAM)
+    }
+    #[doc = "`1`"]
+    #[inline(always)]
+    pub fn aram_to_main(self) -> &'a mut W {
+        self.variant(DIRECTION_A::ARAM_TO_MAIN)
+    }
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 15)) | ((value as u16 & 0x01) << 15);
+        self.w
+    }
+}
+#[doc = "Field `count` reader - "]
+pub struct COUNT_R(crate::FieldReader<u16, u16>);
+impl COUNT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u16) -> Self {
+        COUNT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for COUNT_R {
+    type Target = crate::FieldReader<u16, u16>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `count` writer - "]
+pub struct COUNT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> COUNT_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u16) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x7fff) | (value as u16 & 0x7fff);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bit 15"]
+    #[inline(always)]
+    pub fn direction(&self) -> DIRECTION_R {
+        DIRECTION_R::new(((self.bits >> 15) & 0x01) != 0)
+    }
+    #[doc = "Bits 0:14"]
+    #[inline(always)]
+    pub fn count(&self) -> COUNT_R {
+        COUNT_R::new((self.bits & 0x7fff) as u16)
+    }
+}
+impl W {
+    #[doc = "Bit 15"]
+    #[inline(always)]
+    pub fn direction(&mut self) -> DIRECTION_W {
+        DIRECTION_W { w: self }
+    }
+    #[doc = "Bits 0:14"]
+    #[inline(always)]
+    pub fn count(&mut self) -> COUNT_W {
+        COUNT_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u16) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [aram_dma_count_h](index.html) module"]
+pub struct ARAM_DMA_COUNT_H_SPEC;
+impl crate::RegisterSpec for ARAM_DMA_COUNT_H_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [aram_dma_count_h::R](R) reader structure"]
+impl crate::Readable for ARAM_DMA_COUNT_H_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [aram_dma_count_h::W](W) writer structure"]
+impl crate::Writable for ARAM_DMA_COUNT_H_SPEC {
+    type Writer = W;
+}
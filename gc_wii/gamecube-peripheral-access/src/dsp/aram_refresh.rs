@@ -0,0 +1,57 @@
+#[doc = "Register `aram_refresh` reader"]
+pub struct R(crate::R<ARAM_REFRESH_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<ARAM_REFRESH_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<ARAM_REFRESH_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<ARAM_REFRESH_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `aram_refresh` writer"]
+pub struct W(crate::W<ARAM_REFRESH_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<ARAM_REFRESH_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<ARAM_REFRESH_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<ARAM_REFRESH_SPEC>) -> Self {
+        W(writer)
+    }
+}
+impl W {
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u16) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [aram_refresh](index.html) module"]
+pub struct ARAM_REFRESH_SPEC;
+impl crate::RegisterSpec for ARAM_REFRESH_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [aram_refresh::R](R) reader structure"]
+impl crate::Readable for ARAM_REFRESH_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [aram_refresh::W](W) writer structure"]
+impl crate::Writable for ARAM_REFRESH_SPEC {
+    type Writer = W;
+}
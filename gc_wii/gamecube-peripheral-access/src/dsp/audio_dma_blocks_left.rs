@@ -0,0 +1,24 @@
+#[doc = "Register `audio_dma_blocks_left` reader"]
+pub struct R(crate::R<AUDIO_DMA_BLOCKS_LEFT_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<AUDIO_DMA_BLOCKS_LEFT_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<AUDIO_DMA_BLOCKS_LEFT_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<AUDIO_DMA_BLOCKS_LEFT_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [audio_dma_blocks_left](index.html) module"]
+pub struct AUDIO_DMA_BLOCKS_LEFT_SPEC;
+impl crate::RegisterSpec for AUDIO_DMA_BLOCKS_LEFT_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [audio_dma_blocks_left::R](R) reader structure"]
+impl crate::Readable for AUDIO_DMA_BLOCKS_LEFT_SPEC {
+    type Reader = R;
+}
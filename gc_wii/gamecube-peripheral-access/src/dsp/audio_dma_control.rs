@@ -0,0 +1,143 @@
+#[doc = "Register `audio_dma_control` reader"]
+pub struct R(crate::R<AUDIO_DMA_CONTROL_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<AUDIO_DMA_CONTROL_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<AUDIO_DMA_CONTROL_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<AUDIO_DMA_CONTROL_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `audio_dma_control` writer"]
+pub struct W(crate::W<AUDIO_DMA_CONTROL_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<AUDIO_DMA_CONTROL_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<AUDIO_DMA_CONTROL_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<AUDIO_DMA_CONTROL_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `play` reader - "]
+pub struct PLAY_R(crate::FieldReader<bool, bool>);
+impl PLAY_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        PLAY_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for PLAY_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `play` writer - "]
+pub struct PLAY_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> PLAY_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 15)) | ((value as u16 & 0x01) << 15);
+        self.w
+    }
+}
+#[doc = "Field `length_in_32_byte_units` reader - "]
+pub struct LENGTH_IN_32_BYTE_UNITS_R(crate::FieldReader<u16, u16>);
+impl LENGTH_IN_32_BYTE_UNITS_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u16) -> Self {
+        LENGTH_IN_32_BYTE_UNITS_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for LENGTH_IN_32_BYTE_UNITS_R {
+    type Target = crate::FieldReader<u16, u16>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `length_in_32_byte_units` writer - "]
+pub struct LENGTH_IN_32_BYTE_UNITS_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> LENGTH_IN_32_BYTE_UNITS_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u16) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x7fff) | (value as u16 & 0x7fff);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bit 15"]
+    #[inline(always)]
+    pub fn play(&self) -> PLAY_R {
+        PLAY_R::new(((self.bits >> 15) & 0x01) != 0)
+    }
+    #[doc = "Bits 0:14"]
+    #[inline(always)]
+    pub fn length_in_32_byte_units(&self) -> LENGTH_IN_32_BYTE_UNITS_R {
+        LENGTH_IN_32_BYTE_UNITS_R::new((self.bits & 0x7fff) as u16)
+    }
+}
+impl W {
+    #[doc = "Bit 15"]
+    #[inline(always)]
+    pub fn play(&mut self) -> PLAY_W {
+        PLAY_W { w: self }
+    }
+    #[doc = "Bits 0:14"]
+    #[inline(always)]
+    pub fn length_in_32_byte_units(&mut self) -> LENGTH_IN_32_BYTE_UNITS_W {
+        LENGTH_IN_32_BYTE_UNITS_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u16) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [audio_dma_control](index.html) module"]
+pub struct AUDIO_DMA_CONTROL_SPEC;
+impl crate::RegisterSpec for AUDIO_DMA_CONTROL_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [audio_dma_control::R](R) reader structure"]
+impl crate::Readable for AUDIO_DMA_CONTROL_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [audio_dma_control::W](W) writer structure"]
+impl crate::Writable for AUDIO_DMA_CONTROL_SPEC {
+    type Writer = W;
+}
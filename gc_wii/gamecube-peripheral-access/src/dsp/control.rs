@@ -0,0 +1,549 @@
+#[doc = "Register `control` reader"]
+pub struct R(crate::R<CONTROL_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<CONTROL_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<CONTROL_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<CONTROL_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `control` writer"]
+pub struct W(crate::W<CONTROL_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<CONTROL_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<CONTROL_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<CONTROL_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `init` reader - "]
+pub struct INIT_R(crate::FieldReader<bool, bool>);
+impl INIT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        INIT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for INIT_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `init` writer - "]
+pub struct INIT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> INIT_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 11)) | ((value as u16 & 0x01) << 11);
+        self.w
+    }
+}
+#[doc = "Field `dsp_dma_pending` reader - "]
+pub struct DSP_DMA_PENDING_R(crate::FieldReader<bool, bool>);
+impl DSP_DMA_PENDING_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        DSP_DMA_PENDING_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for DSP_DMA_PENDING_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `dsp_interrupt_mask` reader - "]
+pub struct DSP_INTERRUPT_MASK_R(crate::FieldReader<bool, bool>);
+impl DSP_INTERRUPT_MASK_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        DSP_INTERRUPT_MASK_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for DSP_INTERRUPT_MASK_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `dsp_interrupt_mask` writer - "]
+pub struct DSP_INTERRUPT_MASK_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> DSP_INTERRUPT_MASK_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 8)) | ((value as u16 & 0x01) << 8);
+        self.w
+    }
+}
+#[doc = "Field `dsp_interrupt` reader - "]
+pub struct DSP_INTERRUPT_R(crate::FieldReader<bool, bool>);
+impl DSP_INTERRUPT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        DSP_INTERRUPT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for DSP_INTERRUPT_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `dsp_interrupt` writer - "]
+pub struct DSP_INTERRUPT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> DSP_INTERRUPT_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 7)) | ((value as u16 & 0x01) << 7);
+        self.w
+    }
+}
+#[doc = "Field `aram_interrupt_mask` reader - "]
+pub struct ARAM_INTERRUPT_MASK_R(crate::FieldReader<bool, bool>);
+impl ARAM_INTERRUPT_MASK_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        ARAM_INTERRUPT_MASK_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for ARAM_INTERRUPT_MASK_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `aram_interrupt_mask` writer - "]
+pub struct ARAM_INTERRUPT_MASK_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> ARAM_INTERRUPT_MASK_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 6)) | ((value as u16 & 0x01) << 6);
+        self.w
+    }
+}
+#[doc = "Field `aram_interrupt` reader - "]
+pub struct ARAM_INTERRUPT_R(crate::FieldReader<bool, bool>);
+impl ARAM_INTERRUPT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        ARAM_INTERRUPT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for ARAM_INTERRUPT_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `aram_interrupt` writer - "]
+pub struct ARAM_INTERRUPT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> ARAM_INTERRUPT_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 5)) | ((value as u16 & 0x01) << 5);
+        self.w
+    }
+}
+#[doc = "Field `audio_dma_interrupt_mask` reader - "]
+pub struct AUDIO_DMA_INTERRUPT_MASK_R(crate::FieldReader<bool, bool>);
+impl AUDIO_DMA_INTERRUPT_MASK_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        AUDIO_DMA_INTERRUPT_MASK_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for AUDIO_DMA_INTERRUPT_MASK_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `audio_dma_interrupt_mask` writer - "]
+pub struct AUDIO_DMA_INTERRUPT_MASK_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> AUDIO_DMA_INTERRUPT_MASK_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 4)) | ((value as u16 & 0x01) << 4);
+        self.w
+    }
+}
+#[doc = "Field `audio_dma_interrupt` reader - "]
+pub struct AUDIO_DMA_INTERRUPT_R(crate::FieldReader<bool, bool>);
+impl AUDIO_DMA_INTERRUPT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        AUDIO_DMA_INTERRUPT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for AUDIO_DMA_INTERRUPT_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `audio_dma_interrupt` writer - "]
+pub struct AUDIO_DMA_INTERRUPT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> AUDIO_DMA_INTERRUPT_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 3)) | ((value as u16 & 0x01) << 3);
+        self.w
+    }
+}
+#[doc = "Field `halt` reader - "]
+pub struct HALT_R(crate::FieldReader<bool, bool>);
+impl HALT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        HALT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for HALT_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `halt` writer - "]
+pub struct HALT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> HALT_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 2)) | ((value as u16 & 0x01) << 2);
+        self.w
+    }
+}
+#[doc = "Field `interrupt` reader - "]
+pub struct INTERRUPT_R(crate::FieldReader<bool, bool>);
+impl INTERRUPT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        INTERRUPT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for INTERRUPT_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `interrupt` writer - "]
+pub struct INTERRUPT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> INTERRUPT_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 1)) | ((value as u16 & 0x01) << 1);
+        self.w
+    }
+}
+#[doc = "Field `reset` reader - "]
+pub struct RESET_R(crate::FieldReader<bool, bool>);
+impl RESET_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        RESET_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for RESET_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `reset` writer - "]
+pub struct RESET_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> RESET_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x01) | (value as u16 & 0x01);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bit 11"]
+    #[inline(always)]
+    pub fn init(&self) -> INIT_R {
+        INIT_R::new(((self.bits >> 11) & 0x01) != 0)
+    }
+    #[doc = "Bit 9"]
+    #[inline(always)]
+    pub fn dsp_dma_pending(&self) -> DSP_DMA_PENDING_R {
+        DSP_DMA_PENDING_R::new(((self.bits >> 9) & 0x01) != 0)
+    }
+    #[doc = "Bit 8"]
+    #[inline(always)]
+    pub fn dsp_interrupt_mask(&self) -> DSP_INTERRUPT_MASK_R {
+        DSP_INTERRUPT_MASK_R::new(((self.bits >> 8) & 0x01) != 0)
+    }
+    #[doc = "Bit 7"]
+    #[inline(always)]
+    pub fn dsp_interrupt(&self) -> DSP_INTERRUPT_R {
+        DSP_INTERRUPT_R::new(((self.bits >> 7) & 0x01) != 0)
+    }
+    #[doc = "Bit 6"]
+    #[inline(always)]
+    pub fn aram_interrupt_mask(&self) -> ARAM_INTERRUPT_MASK_R {
+        ARAM_INTERRUPT_MASK_R::new(((self.bits >> 6) & 0x01) != 0)
+    }
+    #[doc = "Bit 5"]
+    #[inline(always)]
+    pub fn aram_interrupt(&self) -> ARAM_INTERRUPT_R {
+        ARAM_INTERRUPT_R::new(((self.bits >> 5) & 0x01) != 0)
+    }
+    #[doc = "Bit 4"]
+    #[inline(always)]
+    pub fn audio_dma_interrupt_mask(&self) -> AUDIO_DMA_INTERRUPT_MASK_R {
+        AUDIO_DMA_INTERRUPT_MASK_R::new(((self.bits >> 4) & 0x01) != 0)
+    }
+    #[doc = "Bit 3"]
+    #[inline(always)]
+    pub fn audio_dma_interrupt(&self) -> AUDIO_DMA_INTERRUPT_R {
+        AUDIO_DMA_INTERRUPT_R::new(((self.bits >> 3) & 0x01) != 0)
+    }
+    #[doc = "Bit 2"]
+    #[inline(always)]
+    pub fn halt(&self) -> HALT_R {
+        HALT_R::new(((self.bits >> 2) & 0x01) != 0)
+    }
+    #[doc = "Bit 1"]
+    #[inline(always)]
+    pub fn interrupt(&self) -> INTERRUPT_R {
+        INTERRUPT_R::new(((self.bits >> 1) & 0x01) != 0)
+    }
+    #[doc = "Bit 0"]
+    #[inline(always)]
+    pub fn reset(&self) -> RESET_R {
+        RESET_R::new((self.bits & 0x01) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 11"]
+    #[inline(always)]
+    pub fn init(&mut self) -> INIT_W {
+        INIT_W { w: self }
+    }
+    #[doc = "Bit 8"]
+    #[inline(always)]
+    pub fn dsp_interrupt_mask(&mut self) -> DSP_INTERRUPT_MASK_W {
+        DSP_INTERRUPT_MASK_W { w: self }
+    }
+    #[doc = "Bit 7"]
+    #[inline(always)]
+    pub fn dsp_interrupt(&mut self) -> DSP_INTERRUPT_W {
+        DSP_INTERRUPT_W { w: self }
+    }
+    #[doc = "Bit 6"]
+    #[inline(always)]
+    pub fn aram_interrupt_mask(&mut self) -> ARAM_INTERRUPT_MASK_W {
+        ARAM_INTERRUPT_MASK_W { w: self }
+    }
+    #[doc = "Bit 5"]
+    #[inline(always)]
+    pub fn aram_interrupt(&mut self) -> ARAM_INTERRUPT_W {
+        ARAM_INTERRUPT_W { w: self }
+    }
+    #[doc = "Bit 4"]
+    #[inline(always)]
+    pub fn audio_dma_interrupt_mask(&mut self) -> AUDIO_DMA_INTERRUPT_MASK_W {
+        AUDIO_DMA_INTERRUPT_MASK_W { w: self }
+    }
+    #[doc = "Bit 3"]
+    #[inline(always)]
+    pub fn audio_dma_interrupt(&mut self) -> AUDIO_DMA_INTERRUPT_W {
+        AUDIO_DMA_INTERRUPT_W { w: self }
+    }
+    #[doc = "Bit 2"]
+    #[inline(always)]
+    pub fn halt(&mut self) -> HALT_W {
+        HALT_W { w: self }
+    }
+    #[doc = "Bit 1"]
+    #[inline(always)]
+    pub fn interrupt(&mut self) -> INTERRUPT_W {
+        INTERRUPT_W { w: self }
+    }
+    #[doc = "Bit 0"]
+    #[inline(always)]
+    pub fn reset(&mut self) -> RESET_W {
+        RESET_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u16) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [control](index.html) module"]
+pub struct CONTROL_SPEC;
+impl crate::RegisterSpec for CONTROL_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [control::R](R) reader structure"]
+impl crate::Readable for CONTROL_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [control::W](W) writer structure"]
+impl crate::Writable for CONTROL_SPEC {
+    type Writer = W;
+}
@@ -0,0 +1,66 @@
+#[doc = "Register `mailbox_from_dsp_h` reader"]
+pub struct R(crate::R<MAILBOX_FROM_DSP_H_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<MAILBOX_FROM_DSP_H_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<MAILBOX_FROM_DSP_H_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<MAILBOX_FROM_DSP_H_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Field `full` reader - "]
+pub struct FULL_R(crate::FieldReader<bool, bool>);
+impl FULL_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        FULL_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for FULL_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `data` reader - "]
+pub struct DATA_R(crate::FieldReader<u16, u16>);
+impl DATA_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u16) -> Self {
+        DATA_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for DATA_R {
+    type Target = crate::FieldReader<u16, u16>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl R {
+    #[doc = "Bit 15"]
+    #[inline(always)]
+    pub fn full(&self) -> FULL_R {
+        FULL_R::new(((self.bits >> 15) & 0x01) != 0)
+    }
+    #[doc = "Bits 0:14"]
+    #[inline(always)]
+    pub fn data(&self) -> DATA_R {
+        DATA_R::new((self.bits & 0x7fff) as u16)
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [mailbox_from_dsp_h](index.html) module"]
+pub struct MAILBOX_FROM_DSP_H_SPEC;
+impl crate::RegisterSpec for MAILBOX_FROM_DSP_H_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [mailbox_from_dsp_h::R](R) reader structure"]
+impl crate::Readable for MAILBOX_FROM_DSP_H_SPEC {
+    type Reader = R;
+}
@@ -0,0 +1,24 @@
+#[doc = "Register `mailbox_from_dsp_l` reader"]
+pub struct R(crate::R<MAILBOX_FROM_DSP_L_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<MAILBOX_FROM_DSP_L_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<MAILBOX_FROM_DSP_L_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<MAILBOX_FROM_DSP_L_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [mailbox_from_dsp_l](index.html) module"]
+pub struct MAILBOX_FROM_DSP_L_SPEC;
+impl crate::RegisterSpec for MAILBOX_FROM_DSP_L_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [mailbox_from_dsp_l::R](R) reader structure"]
+impl crate::Readable for MAILBOX_FROM_DSP_L_SPEC {
+    type Reader = R;
+}
@@ -0,0 +1,116 @@
+#[doc = "Register `mailbox_to_dsp_h` reader"]
+pub struct R(crate::R<MAILBOX_TO_DSP_H_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<MAILBOX_TO_DSP_H_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<MAILBOX_TO_DSP_H_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<MAILBOX_TO_DSP_H_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `mailbox_to_dsp_h` writer"]
+pub struct W(crate::W<MAILBOX_TO_DSP_H_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<MAILBOX_TO_DSP_H_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<MAILBOX_TO_DSP_H_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<MAILBOX_TO_DSP_H_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `full` reader - "]
+pub struct FULL_R(crate::FieldReader<bool, bool>);
+impl FULL_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        FULL_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for FULL_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `data` reader - "]
+pub struct DATA_R(crate::FieldReader<u16, u16>);
+impl DATA_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u16) -> Self {
+        DATA_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for DATA_R {
+    type Target = crate::FieldReader<u16, u16>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `data` writer - "]
+pub struct DATA_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> DATA_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u16) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x7fff) | (value as u16 & 0x7fff);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bit 15"]
+    #[inline(always)]
+    pub fn full(&self) -> FULL_R {
+        FULL_R::new(((self.bits >> 15) & 0x01) != 0)
+    }
+    #[doc = "Bits 0:14"]
+    #[inline(always)]
+    pub fn data(&self) -> DATA_R {
+        DATA_R::new((self.bits & 0x7fff) as u16)
+    }
+}
+impl W {
+    #[doc = "Bits 0:14"]
+    #[inline(always)]
+    pub fn data(&mut self) -> DATA_W {
+        DATA_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u16) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [mailbox_to_dsp_h](index.html) module"]
+pub struct MAILBOX_TO_DSP_H_SPEC;
+impl crate::RegisterSpec for MAILBOX_TO_DSP_H_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [mailbox_to_dsp_h::R](R) reader structure"]
+impl crate::Readable for MAILBOX_TO_DSP_H_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [mailbox_to_dsp_h::W](W) writer structure"]
+impl crate::Writable for MAILBOX_TO_DSP_H_SPEC {
+    type Writer = W;
+}
@@ -0,0 +1,23 @@
+#[doc = r"Register block"]
+#[repr(C)]
+pub struct RegisterBlock {
+    #[doc = "0x00..0x3c - Per-channel parameter, DMA, control, and data registers"]
+    pub channel: [CHANNEL; 3],
+}
+#[doc = r"Register block"]
+#[repr(C)]
+pub struct CHANNEL {
+    #[doc = "0x00 - "]
+    pub parameter: crate::Reg<self::channel::parameter::PARAMETER_SPEC>,
+    #[doc = "0x04 - "]
+    pub dma_address: crate::Reg<self::channel::dma_address::DMA_ADDRESS_SPEC>,
+    #[doc = "0x08 - "]
+    pub dma_length: crate::Reg<self::channel::dma_length::DMA_LENGTH_SPEC>,
+    #[doc = "0x0c - "]
+    pub control: crate::Reg<self::channel::control::CONTROL_SPEC>,
+    #[doc = "0x10 - "]
+    pub data: crate::Reg<self::channel::data::DATA_SPEC>,
+}
+#[doc = r"Register block"]
+#[doc = "Per-channel parameter, DMA, control, and data registers"]
+pub mod channel;
@@ -0,0 +1,20 @@
+#[doc = "parameter register accessor: an alias for `Reg<PARAMETER_SPEC>`"]
+pub type PARAMETER = crate::Reg<parameter::PARAMETER_SPEC>;
+#[doc = ""]
+pub mod parameter;
+#[doc = "dma_address register accessor: an alias for `Reg<DMA_ADDRESS_SPEC>`"]
+pub type DMA_ADDRESS = crate::Reg<dma_address::DMA_ADDRESS_SPEC>;
+#[doc = ""]
+pub mod dma_address;
+#[doc = "dma_length register accessor: an alias for `Reg<DMA_LENGTH_SPEC>`"]
+pub type DMA_LENGTH = crate::Reg<dma_length::DMA_LENGTH_SPEC>;
+#[doc = ""]
+pub mod dma_length;
+#[doc = "control register accessor: an alias for `Reg<CONTROL_SPEC>`"]
+pub type CONTROL = crate::Reg<control::CONTROL_SPEC>;
+#[doc = ""]
+pub mod control;
+#[doc = "data register accessor: an alias for `Reg<DATA_SPEC>`"]
+pub type DATA = crate::Reg<data::DATA_SPEC>;
+#[doc = ""]
+pub mod data;
@@ -0,0 +1,289 @@
+#[doc = "Register `control` reader"]
+pub struct R(crate::R<CONTROL_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<CONTROL_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<CONTROL_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<CONTROL_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `control` writer"]
+pub struct W(crate::W<CONTROL_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<CONTROL_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<CONTROL_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<CONTROL_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `transfer_length` reader - "]
+pub struct TRANSFER_LENGTH_R(crate::FieldReader<u8, u8>);
+impl TRANSFER_LENGTH_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        TRANSFER_LENGTH_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for TRANSFER_LENGTH_R {
+    type Target = crate::FieldReader<u8, u8>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `transfer_length` writer - "]
+pub struct TRANSFER_LENGTH_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> TRANSFER_LENGTH_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x03 << 4)) | ((value as u32 & 0x03) << 4);
+        self.w
+    }
+}
+#[doc = ""]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(u8)]
+pub enum DIRECTION_A {
+    #[doc = "0: `0`"]
+    READ = 0,
+    #[doc = "1: `1`"]
+    WRITE = 1,
+    #[doc = "2: `10`"]
+    READ_WRITE = 2,
+}
+impl From<DIRECTION_A> for u8 {
+    #[inline(always)]
+    fn from(variant: DIRECTION_A) -> Self {
+        variant as _
+    }
+}
+#[doc = "Field `direction` reader - "]
+pub struct DIRECTION_R(crate::FieldReader<u8, DIRECTION_A>);
+impl DIRECTION_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        DIRECTION_R(crate::FieldReader::new(bits))
+    }
+    #[doc = r"Get enumerated values variant"]
+    #[inline(always)]
+    pub fn variant(&self) -> Option<DIRECTION_A> {
+        match self.bits {
+            0 => Some(DIRECTION_A::READ),
+            1 => Some(DIRECTION_A::WRITE),
+            2 => Some(DIRECTION_A::READ_WRITE),
+            _ => None,
+        }
+    }
+    #[doc = "Checks if the value of the field is `READ`"]
+    #[inline(always)]
+    pub fn is_read(&self) -> bool {
+        **self == DIRECTION_A::READ
+    }
+    #[doc = "Checks if the value of the field is `WRITE`"]
+    #[inline(always)]
+    pub fn is_write(&self) -> bool {
+        **self == DIRECTION_A::WRITE
+    }
+    #[doc = "Checks if the value of the field is `READ_WRITE`"]
+    #[inline(always)]
+    pub fn is_read_write(&self) -> bool {
+        **self == DIRECTION_A::READ_WRITE
+    }
+}
+impl core::ops::Deref for DIRECTION_R {
+    type Target = crate::FieldReader<u8, DIRECTION_A>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `direction` writer - "]
+pub struct DIRECTION_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> DIRECTION_W<'a> {
+    #[doc = r"Writes `variant` to the field"]
+    #[inline(always)]
+    pub fn variant(self, variant: DIRECTION_A) -> &'a mut W {
+        unsafe { self.bits(variant.into()) }
+    }
+    #[doc = "`0`"]
+    #[inline(always)]
+    pub fn read(self) -> &'a mut W {
+        self.variant(DIRECTION_A::READ)
+    }
+    #[doc = "`1`"]
+    #[inline(always)]
+    pub fn write(self) -> &'a mut W {
+        self.variant(DIRECTION_A::WRITE)
+    }
+    #[doc = "`10`"]
+    #[inline(always)]
+    pub fn read_write(self) -> &'a mut W {
+        self.variant(DIRECTION_A::READ_WRITE)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x03 << 2)) | ((value as u32 & 0x03) << 2);
+        self.w
+    }
+}
+#[doc = "Field `dma` reader - "]
+pub struct DMA_R(crate::FieldReader<bool, bool>);
+impl DMA_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        DMA_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for DMA_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `dma` writer - "]
+pub struct DMA_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> DMA_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 1)) | ((value as u32 & 0x01) << 1);
+        self.w
+    }
+}
+#[doc = "Field `transfer_pending` reader - "]
+pub struct TRANSFER_PENDING_R(crate::FieldReader<bool, bool>);
+impl TRANSFER_PENDING_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        TRANSFER_PENDING_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for TRANSFER_PENDING_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `start_transfer` writer - "]
+pub struct START_TRANSFER_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> START_TRANSFER_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x01) | (value as u32 & 0x01);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bits 4:5"]
+    #[inline(always)]
+    pub fn transfer_length(&self) -> TRANSFER_LENGTH_R {
+        TRANSFER_LENGTH_R::new(((self.bits >> 4) & 0x03) as u8)
+    }
+    #[doc = "Bits 2:3"]
+    #[inline(always)]
+    pub fn direction(&self) -> DIRECTION_R {
+        DIRECTION_R::new(((self.bits >> 2) & 0x03) as u8)
+    }
+    #[doc = "Bit 1"]
+    #[inline(always)]
+    pub fn dma(&self) -> DMA_R {
+        DMA_R::new(((self.bits >> 1) & 0x01) != 0)
+    }
+    #[doc = "Bit 0"]
+    #[inline(always)]
+    pub fn transfer_pending(&self) -> TRANSFER_PENDING_R {
+        TRANSFER_PENDING_R::new((self.bits & 0x01) != 0)
+    }
+}
+impl W {
+    #[doc = "Bits 4:5"]
+    #[inline(always)]
+    pub fn transfer_length(&mut self) -> TRANSFER_LENGTH_W {
+        TRANSFER_LENGTH_W { w: self }
+    }
+    #[doc = "Bits 2:3"]
+    #[inline(always)]
+    pub fn direction(&mut self) -> DIRECTION_W {
+        DIRECTION_W { w: self }
+    }
+    #[doc = "Bit 1"]
+    #[inline(always)]
+    pub fn dma(&mut self) -> DMA_W {
+        DMA_W { w: self }
+    }
+    #[doc = "Bit 0"]
+    #[inline(always)]
+    pub fn start_transfer(&mut self) -> START_TRANSFER_W {
+        START_TRANSFER_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [control](index.html) module"]
+pub struct CONTROL_SPEC;
+impl crate::RegisterSpec for CONTROL_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [control::R](R) reader structure"]
+impl crate::Readable for CONTROL_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [control::W](W) writer structure"]
+impl crate::Writable for CONTROL_SPEC {
+    type Writer = W;
+}
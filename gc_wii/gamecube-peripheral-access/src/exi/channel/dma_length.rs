@@ -0,0 +1,57 @@
+#[doc = "Register `dma_length` reader"]
+pub struct R(crate::R<DMA_LENGTH_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<DMA_LENGTH_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<DMA_LENGTH_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<DMA_LENGTH_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `dma_length` writer"]
+pub struct W(crate::W<DMA_LENGTH_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<DMA_LENGTH_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<DMA_LENGTH_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<DMA_LENGTH_SPEC>) -> Self {
+        W(writer)
+    }
+}
+impl W {
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [dma_length](index.html) module"]
+pub struct DMA_LENGTH_SPEC;
+impl crate::RegisterSpec for DMA_LENGTH_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [dma_length::R](R) reader structure"]
+impl crate::Readable for DMA_LENGTH_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [dma_length::W](W) writer structure"]
+impl crate::Writable for DMA_LENGTH_SPEC {
+    type Writer = W;
+}
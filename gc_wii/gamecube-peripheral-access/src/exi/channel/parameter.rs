@@ -0,0 +1,583 @@
+#[doc = "Register `parameter` reader"]
+pub struct R(crate::R<PARAMETER_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<PARAMETER_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<PARAMETER_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<PARAMETER_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `parameter` writer"]
+pub struct W(crate::W<PARAMETER_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<PARAMETER_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<PARAMETER_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<PARAMETER_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `rom_descramble_disable` reader - "]
+pub struct ROM_DESCRAMBLE_DISABLE_R(crate::FieldReader<bool, bool>);
+impl ROM_DESCRAMBLE_DISABLE_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        ROM_DESCRAMBLE_DISABLE_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for ROM_DESCRAMBLE_DISABLE_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `rom_descramble_disable` writer - "]
+pub struct ROM_DESCRAMBLE_DISABLE_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> ROM_DESCRAMBLE_DISABLE_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 13)) | ((value as u32 & 0x01) << 13);
+        self.w
+    }
+}
+#[doc = "Field `device_connected` reader - "]
+pub struct DEVICE_CONNECTED_R(crate::FieldReader<bool, bool>);
+impl DEVICE_CONNECTED_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        DEVICE_CONNECTED_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for DEVICE_CONNECTED_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `external_interrupt` reader - "]
+pub struct EXTERNAL_INTERRUPT_R(crate::FieldReader<bool, bool>);
+impl EXTERNAL_INTERRUPT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        EXTERNAL_INTERRUPT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for EXTERNAL_INTERRUPT_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `external_interrupt` writer - "]
+pub struct EXTERNAL_INTERRUPT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> EXTERNAL_INTERRUPT_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 11)) | ((value as u32 & 0x01) << 11);
+        self.w
+    }
+}
+#[doc = "Field `external_interrupt_mask` reader - "]
+pub struct EXTERNAL_INTERRUPT_MASK_R(crate::FieldReader<bool, bool>);
+impl EXTERNAL_INTERRUPT_MASK_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        EXTERNAL_INTERRUPT_MASK_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for EXTERNAL_INTERRUPT_MASK_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `external_interrupt_mask` writer - "]
+pub struct EXTERNAL_INTERRUPT_MASK_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> EXTERNAL_INTERRUPT_MASK_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 10)) | ((value as u32 & 0x01) << 10);
+        self.w
+    }
+}
+#[doc = "Field `chip_select` reader - "]
+pub struct CHIP_SELECT_R(crate::FieldReader<u8, u8>);
+impl CHIP_SELECT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        CHIP_SELECT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for CHIP_SELECT_R {
+    type Target = crate::FieldReader<u8, u8>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `chip_select` writer - "]
+pub struct CHIP_SELECT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> CHIP_SELECT_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x07 << 7)) | ((value as u32 & 0x07) << 7);
+        self.w
+    }
+}
+#[doc = ""]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(u8)]
+pub enum CLOCK_A {
+    #[doc = "0: `0`"]
+    MHZ_1 = 0,
+    #[doc = "1: `1`"]
+    MHZ_2 = 1,
+    #[doc = "2: `10`"]
+    MHZ_4 = 2,
+    #[doc = "3: `11`"]
+    MHZ_8 = 3,
+    #[doc = "4: `100`"]
+    MHZ_16 = 4,
+    #[doc = "5: `101`"]
+    MHZ_32 = 5,
+}
+impl From<CLOCK_A> for u8 {
+    #[inline(always)]
+    fn from(variant: CLOCK_A) -> Self {
+        variant as _
+    }
+}
+#[doc = "Field `clock` reader - "]
+pub struct CLOCK_R(crate::FieldReader<u8, CLOCK_A>);
+impl CLOCK_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        CLOCK_R(crate::FieldReader::new(bits))
+    }
+    #[doc = r"Get enumerated values variant"]
+    #[inline(always)]
+    pub fn variant(&self) -> Option<CLOCK_A> {
+        match self.bits {
+            0 => Some(CLOCK_A::MHZ_1),
+            1 => Some(CLOCK_A::MHZ_2),
+            2 => Some(CLOCK_A::MHZ_4),
+            3 => Some(CLOCK_A::MHZ_8),
+            4 => Some(CLOCK_A::MHZ_16),
+            5 => Some(CLOCK_A::MHZ_32),
+            _ => None,
+        }
+    }
+    #[doc = "Checks if the value of the field is `MHZ_1`"]
+    #[inline(always)]
+    pub fn is_mhz_1(&self) -> bool {
+        **self == CLOCK_A::MHZ_1
+    }
+    #[doc = "Checks if the value of the field is `MHZ_2`"]
+    #[inline(always)]
+    pub fn is_mhz_2(&self) -> bool {
+        **self == CLOCK_A::MHZ_2
+    }
+    #[doc = "Checks if the value of the field is `MHZ_4`"]
+    #[inline(always)]
+    pub fn is_mhz_4(&self) -> bool {
+        **self == CLOCK_A::MHZ_4
+    }
+    #[doc = "Checks if the value of the field is `MHZ_8`"]
+    #[inline(always)]
+    pub fn is_mhz_8(&self) -> bool {
+        **self == CLOCK_A::MHZ_8
+    }
+    #[doc = "Checks if the value of the field is `MHZ_16`"]
+    #[inline(always)]
+    pub fn is_mhz_16(&self) -> bool {
+        **self == CLOCK_A::MHZ_16
+    }
+    #[doc = "Checks if the value of the field is `MHZ_32`"]
+    #[inline(always)]
+    pub fn is_mhz_32(&self) -> bool {
+        **self == CLOCK_A::MHZ_32
+    }
+}
+impl core::ops::Deref for CLOCK_R {
+    type Target = crate::FieldReader<u8, CLOCK_A>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `clock` writer - "]
+pub struct CLOCK_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> CLOCK_W<'a> {
+    #[doc = r"Writes `variant` to the field"]
+    #[inline(always)]
+    pub fn variant(self, variant: CLOCK_A) -> &'a mut W {
+        unsafe { self.bits(variant.into()) }
+    }
+    #[doc = "`0`"]
+    #[inline(always)]
+    pub fn mhz_1(self) -> &'a mut W {
+        self.variant(CLOCK_A::MHZ_1)
+    }
+    #[doc = "`1`"]
+    #[inline(always)]
+    pub fn mhz_2(self) -> &'a mut W {
+        self.variant(CLOCK_A::MHZ_2)
+    }
+    #[doc = "`10`"]
+    #[inline(always)]
+    pub fn mhz_4(self) -> &'a mut W {
+        self.variant(CLOCK_A::MHZ_4)
+    }
+    #[doc = "`11`"]
+    #[inline(always)]
+    pub fn mhz_8(self) -> &'a mut W {
+        self.variant(CLOCK_A::MHZ_8)
+    }
+    #[doc = "`100`"]
+    #[inline(always)]
+    pub fn mhz_16(self) -> &'a mut W {
+        self.variant(CLOCK_A::MHZ_16)
+    }
+    #[doc = "`101`"]
+    #[inline(always)]
+    pub fn mhz_32(self) -> &'a mut W {
+        self.variant(CLOCK_A::MHZ_32)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x07 << 4)) | ((value as u32 & 0x07) << 4);
+        self.w
+    }
+}
+#[doc = "Field `transfer_complete_interrupt` reader - "]
+pub struct TRANSFER_COMPLETE_INTERRUPT_R(crate::FieldReader<bool, bool>);
+impl TRANSFER_COMPLETE_INTERRUPT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        TRANSFER_COMPLETE_INTERRUPT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for TRANSFER_COMPLETE_INTERRUPT_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `transfer_complete_interrupt` writer - "]
+pub struct TRANSFER_COMPLETE_INTERRUPT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> TRANSFER_COMPLETE_INTERRUPT_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 3)) | ((value as u32 & 0x01) << 3);
+        self.w
+    }
+}
+#[doc = "Field `transfer_complete_interrupt_mask` reader - "]
+pub struct TRANSFER_COMPLETE_INTERRUPT_MASK_R(crate::FieldReader<bool, bool>);
+impl TRANSFER_COMPLETE_INTERRUPT_MASK_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        TRANSFER_COMPLETE_INTERRUPT_MASK_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for TRANSFER_COMPLETE_INTERRUPT_MASK_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `transfer_complete_interrupt_mask` writer - "]
+pub struct TRANSFER_COMPLETE_INTERRUPT_MASK_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> TRANSFER_COMPLETE_INTERRUPT_MASK_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 2)) | ((value as u32 & 0x01) << 2);
+        self.w
+    }
+}
+#[doc = "Field `exi_interrupt` reader - "]
+pub struct EXI_INTERRUPT_R(crate::FieldReader<bool, bool>);
+impl EXI_INTERRUPT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        EXI_INTERRUPT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for EXI_INTERRUPT_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `exi_interrupt` writer - "]
+pub struct EXI_INTERRUPT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> EXI_INTERRUPT_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 1)) | ((value as u32 & 0x01) << 1);
+        self.w
+    }
+}
+#[doc = "Field `exi_interrupt_mask` reader - "]
+pub struct EXI_INTERRUPT_MASK_R(crate::FieldReader<bool, bool>);
+impl EXI_INTERRUPT_MASK_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        EXI_INTERRUPT_MASK_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for EXI_INTERRUPT_MASK_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `exi_interrupt_mask` writer - "]
+pub struct EXI_INTERRUPT_MASK_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> EXI_INTERRUPT_MASK_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x01) | (value as u32 & 0x01);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bit 13"]
+    #[inline(always)]
+    pub fn rom_descramble_disable(&self) -> ROM_DESCRAMBLE_DISABLE_R {
+        ROM_DESCRAMBLE_DISABLE_R::new(((self.bits >> 13) & 0x01) != 0)
+    }
+    #[doc = "Bit 12"]
+    #[inline(always)]
+    pub fn device_connected(&self) -> DEVICE_CONNECTED_R {
+        DEVICE_CONNECTED_R::new(((self.bits >> 12) & 0x01) != 0)
+    }
+    #[doc = "Bit 11"]
+    #[inline(always)]
+    pub fn external_interrupt(&self) -> EXTERNAL_INTERRUPT_R {
+        EXTERNAL_INTERRUPT_R::new(((self.bits >> 11) & 0x01) != 0)
+    }
+    #[doc = "Bit 10"]
+    #[inline(always)]
+    pub fn external_interrupt_mask(&self) -> EXTERNAL_INTERRUPT_MASK_R {
+        EXTERNAL_INTERRUPT_MASK_R::new(((self.bits >> 10) & 0x01) != 0)
+    }
+    #[doc = "Bits 7:9"]
+    #[inline(always)]
+    pub fn chip_select(&self) -> CHIP_SELECT_R {
+        CHIP_SELECT_R::new(((self.bits >> 7) & 0x07) as u8)
+    }
+    #[doc = "Bits 4:6"]
+    #[inline(always)]
+    pub fn clock(&self) -> CLOCK_R {
+        CLOCK_R::new(((self.bits >> 4) & 0x07) as u8)
+    }
+    #[doc = "Bit 3"]
+    #[inline(always)]
+    pub fn transfer_complete_interrupt(&self) -> TRANSFER_COMPLETE_INTERRUPT_R {
+        TRANSFER_COMPLETE_INTERRUPT_R::new(((self.bits >> 3) & 0x01) != 0)
+    }
+    #[doc = "Bit 2"]
+    #[inline(always)]
+    pub fn transfer_complete_interrupt_mask(&self) -> TRANSFER_COMPLETE_INTERRUPT_MASK_R {
+        TRANSFER_COMPLETE_INTERRUPT_MASK_R::new(((self.bits >> 2) & 0x01) != 0)
+    }
+    #[doc = "Bit 1"]
+    #[inline(always)]
+    pub fn exi_interrupt(&self) -> EXI_INTERRUPT_R {
+        EXI_INTERRUPT_R::new(((self.bits >> 1) & 0x01) != 0)
+    }
+    #[doc = "Bit 0"]
+    #[inline(always)]
+    pub fn exi_interrupt_mask(&self) -> EXI_INTERRUPT_MASK_R {
+        EXI_INTERRUPT_MASK_R::new((self.bits & 0x01) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 13"]
+    #[inline(always)]
+    pub fn rom_descramble_disable(&mut self) -> ROM_DESCRAMBLE_DISABLE_W {
+        ROM_DESCRAMBLE_DISABLE_W { w: self }
+    }
+    #[doc = "Bit 11"]
+    #[inline(always)]
+    pub fn external_interrupt(&mut self) -> EXTERNAL_INTERRUPT_W {
+        EXTERNAL_INTERRUPT_W { w: self }
+    }
+    #[doc = "Bit 10"]
+    #[inline(always)]
+    pub fn external_interrupt_mask(&mut self) -> EXTERNAL_INTERRUPT_MASK_W {
+        EXTERNAL_INTERRUPT_MASK_W { w: self }
+    }
+    #[doc = "Bits 7:9"]
+    #[inline(always)]
+    pub fn chip_select(&mut self) -> CHIP_SELECT_W {
+        CHIP_SELECT_W { w: self }
+    }
+    #[doc = "Bits 4:6"]
+    #[inline(always)]
+    pub fn clock(&mut self) -> CLOCK_W {
+        CLOCK_W { w: self }
+    }
+    #[doc = "Bit 3"]
+    #[inline(always)]
+    pub fn transfer_complete_interrupt(&mut self) -> TRANSFER_COMPLETE_INTERRUPT_W {
+        TRANSFER_COMPLETE_INTERRUPT_W { w: self }
+    }
+    #[doc = "Bit 2"]
+    #[inline(always)]
+    pub fn transfer_complete_interrupt_mask(&mut self) -> TRANSFER_COMPLETE_INTERRUPT_MASK_W {
+        TRANSFER_COMPLETE_INTERRUPT_MASK_W { w: self }
+    }
+    #[doc = "Bit 1"]
+    #[inline(always)]
+    pub fn exi_interrupt(&mut self) -> EXI_INTERRUPT_W {
+        EXI_INTERRUPT_W { w: self }
+    }
+    #[doc = "Bit 0"]
+    #[inline(always)]
+    pub fn exi_interrupt_mask(&mut self) -> EXI_INTERRUPT_MASK_W {
+        EXI_INTERRUPT_MASK_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [parameter](index.html) module"]
+pub struct PARAMETER_SPEC;
+impl crate::RegisterSpec for PARAMETER_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [parameter::R](R) reader structure"]
+impl crate::Readable for PARAMETER_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [parameter::W](W) writer structure"]
+impl crate::Writable for PARAMETER_SPEC {
+    type Writer = W;
+}
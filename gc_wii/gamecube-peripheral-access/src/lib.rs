@@ -108,6 +108,174 @@ impl core::fmt::Debug for DI {
 }
 #[doc = "DVD Interface"]
 pub mod di;
+#[doc = "Video Interface"]
+pub struct VI {
+    _marker: PhantomData<*const ()>,
+}
+unsafe impl Send for VI {}
+impl VI {
+    #[doc = r"Pointer to the register block"]
+    pub const PTR: *const vi::RegisterBlock = 0xcc00_2000 as *const _;
+    #[doc = r"Return the pointer to the register block"]
+    #[inline(always)]
+    pub const fn ptr() -> *const vi::RegisterBlock {
+        Self::PTR
+    }
+}
+impl Deref for VI {
+    type Target = vi::RegisterBlock;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*Self::PTR }
+    }
+}
+impl core::fmt::Debug for VI {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("VI").finish()
+    }
+}
+#[doc = "Video Interface"]
+pub mod vi;
+#[doc = "Memory Interface"]
+pub struct MI {
+    _marker: PhantomData<*const ()>,
+}
+unsafe impl Send for MI {}
+impl MI {
+    #[doc = r"Pointer to the register block"]
+    pub const PTR: *const mi::RegisterBlock = 0xcc00_4000 as *const _;
+    #[doc = r"Return the pointer to the register block"]
+    #[inline(always)]
+    pub const fn ptr() -> *const mi::RegisterBlock {
+        Self::PTR
+    }
+}
+impl Deref for MI {
+    type Target = mi::RegisterBlock;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*Self::PTR }
+    }
+}
+impl core::fmt::Debug for MI {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("MI").finish()
+    }
+}
+#[doc = "Memory Interface"]
+pub mod mi;
+#[doc = "DSP Interface"]
+pub struct DSP {
+    _marker: PhantomData<*const ()>,
+}
+unsafe impl Send for DSP {}
+impl DSP {
+    #[doc = r"Pointer to the register block"]
+    pub const PTR: *const dsp::RegisterBlock = 0xcc00_5000 as *const _;
+    #[doc = r"Return the pointer to the register block"]
+    #[inline(always)]
+    pub const fn ptr() -> *const dsp::RegisterBlock {
+        Self::PTR
+    }
+}
+impl Deref for DSP {
+    type Target = dsp::RegisterBlock;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*Self::PTR }
+    }
+}
+impl core::fmt::Debug for DSP {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("DSP").finish()
+    }
+}
+#[doc = "DSP Interface"]
+pub mod dsp;
+#[doc = "Serial Interface"]
+pub struct SI {
+    _marker: PhantomData<*const ()>,
+}
+unsafe impl Send for SI {}
+impl SI {
+    #[doc = r"Pointer to the register block"]
+    pub const PTR: *const si::RegisterBlock = 0xcc00_6400 as *const _;
+    #[doc = r"Return the pointer to the register block"]
+    #[inline(always)]
+    pub const fn ptr() -> *const si::RegisterBlock {
+        Self::PTR
+    }
+}
+impl Deref for SI {
+    type Target = si::RegisterBlock;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*Self::PTR }
+    }
+}
+impl core::fmt::Debug for SI {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("SI").finish()
+    }
+}
+#[doc = "Serial Interface"]
+pub mod si;
+#[doc = "External Interface"]
+pub struct EXI {
+    _marker: PhantomData<*const ()>,
+}
+unsafe impl Send for EXI {}
+impl EXI {
+    #[doc = r"Pointer to the register block"]
+    pub const PTR: *const exi::RegisterBlock = 0xcc00_6800 as *const _;
+    #[doc = r"Return the pointer to the register block"]
+    #[inline(always)]
+    pub const fn ptr() -> *const exi::RegisterBlock {
+        Self::PTR
+    }
+}
+impl Deref for EXI {
+    type Target = exi::RegisterBlock;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*Self::PTR }
+    }
+}
+impl core::fmt::Debug for EXI {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("EXI").finish()
+    }
+}
+#[doc = "External Interface"]
+pub mod exi;
+#[doc = "Audio Interface"]
+pub struct AI {
+    _marker: PhantomData<*const ()>,
+}
+unsafe impl Send for AI {}
+impl AI {
+    #[doc = r"Pointer to the register block"]
+    pub const PTR: *const ai::RegisterBlock = 0xcc00_6c00 as *const _;
+    #[doc = r"Return the pointer to the register block"]
+    #[inline(always)]
+    pub const fn ptr() -> *const ai::RegisterBlock {
+        Self::PTR
+    }
+}
+impl Deref for AI {
+    type Target = ai::RegisterBlock;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*Self::PTR }
+    }
+}
+impl core::fmt::Debug for AI {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("AI").finish()
+    }
+}
+#[doc = "Audio Interface"]
+pub mod ai;
 #[no_mangle]
 static mut DEVICE_PERIPHERALS: bool = false;
 #[doc = r"All the peripherals"]
@@ -119,6 +287,18 @@ pub struct Peripherals {
     pub PI: PI,
     #[doc = "DI"]
     pub DI: DI,
+    #[doc = "VI"]
+    pub VI: VI,
+    #[doc = "MI"]
+    pub MI: MI,
+    #[doc = "DSP"]
+    pub DSP: DSP,
+    #[doc = "SI"]
+    pub SI: SI,
+    #[doc = "EXI"]
+    pub EXI: EXI,
+    #[doc = "AI"]
+    pub AI: AI,
 }
 impl Peripherals {
     #[doc = r"Unchecked version of `Peripherals::take`"]
@@ -135,6 +315,24 @@ impl Peripherals {
             DI: DI {
                 _marker: PhantomData,
             },
+            VI: VI {
+                _marker: PhantomData,
+            },
+            MI: MI {
+                _marker: PhantomData,
+            },
+            DSP: DSP {
+                _marker: PhantomData,
+            },
+            SI: SI {
+                _marker: PhantomData,
+            },
+            EXI: EXI {
+                _marker: PhantomData,
+            },
+            AI: AI {
+                _marker: PhantomData,
+            },
         }
     }
 }
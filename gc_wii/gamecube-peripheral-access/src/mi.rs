@@ -0,0 +1,49 @@
+#[doc = r"Register block"]
+#[repr(C)]
+pub struct RegisterBlock {
+    #[doc = "0x00..0x10 - Protected memory region, in 1 KiB units"]
+    pub protected_region: [PROTECTED_REGION; 4],
+    #[doc = "0x10 - "]
+    pub protection_type: crate::Reg<protection_type::PROTECTION_TYPE_SPEC>,
+    _reserved2: [u8; 0x0a],
+    #[doc = "0x1c - "]
+    pub interrupt_mask: crate::Reg<interrupt_mask::INTERRUPT_MASK_SPEC>,
+    #[doc = "0x1e - "]
+    pub interrupt_cause: crate::Reg<interrupt_cause::INTERRUPT_CAUSE_SPEC>,
+    _reserved4: [u8; 0x02],
+    #[doc = "0x22 - "]
+    pub fault_address_l: crate::Reg<fault_address_l::FAULT_ADDRESS_L_SPEC>,
+    #[doc = "0x24 - "]
+    pub fault_address_h: crate::Reg<fault_address_h::FAULT_ADDRESS_H_SPEC>,
+}
+#[doc = r"Register block"]
+#[repr(C)]
+pub struct PROTECTED_REGION {
+    #[doc = "0x00 - "]
+    pub start: crate::Reg<self::protected_region::start::START_SPEC>,
+    #[doc = "0x02 - "]
+    pub end: crate::Reg<self::protected_region::end::END_SPEC>,
+}
+#[doc = r"Register block"]
+#[doc = "Protected memory region, in 1 KiB units"]
+pub mod protected_region;
+#[doc = "protection_type register accessor: an alias for `Reg<PROTECTION_TYPE_SPEC>`"]
+pub type PROTECTION_TYPE = crate::Reg<protection_type::PROTECTION_TYPE_SPEC>;
+#[doc = ""]
+pub mod protection_type;
+#[doc = "interrupt_mask register accessor: an alias for `Reg<INTERRUPT_MASK_SPEC>`"]
+pub type INTERRUPT_MASK = crate::Reg<interrupt_mask::INTERRUPT_MASK_SPEC>;
+#[doc = ""]
+pub mod interrupt_mask;
+#[doc = "interrupt_cause register accessor: an alias for `Reg<INTERRUPT_CAUSE_SPEC>`"]
+pub type INTERRUPT_CAUSE = crate::Reg<interrupt_cause::INTERRUPT_CAUSE_SPEC>;
+#[doc = ""]
+pub mod interrupt_cause;
+#[doc = "fault_address_l register accessor: an alias for `Reg<FAULT_ADDRESS_L_SPEC>`"]
+pub type FAULT_ADDRESS_L = crate::Reg<fault_address_l::FAULT_ADDRESS_L_SPEC>;
+#[doc = ""]
+pub mod fault_address_l;
+#[doc = "fault_address_h register accessor: an alias for `Reg<FAULT_ADDRESS_H_SPEC>`"]
+pub type FAULT_ADDRESS_H = crate::Reg<fault_address_h::FAULT_ADDRESS_H_SPEC>;
+#[doc = ""]
+pub mod fault_address_h;
@@ -0,0 +1,24 @@
+#[doc = "Register `fault_address_h` reader"]
+pub struct R(crate::R<FAULT_ADDRESS_H_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<FAULT_ADDRESS_H_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<FAULT_ADDRESS_H_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<FAULT_ADDRESS_H_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [fault_address_h](index.html) module"]
+pub struct FAULT_ADDRESS_H_SPEC;
+impl crate::RegisterSpec for FAULT_ADDRESS_H_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [fault_address_h::R](R) reader structure"]
+impl crate::Readable for FAULT_ADDRESS_H_SPEC {
+    type Reader = R;
+}
@@ -0,0 +1,294 @@
+#[doc = "Register `interrupt_mask` reader"]
+pub struct R(crate::R<INTERRUPT_MASK_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<INTERRUPT_MASK_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<INTERRUPT_MASK_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<INTERRUPT_MASK_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `interrupt_mask` writer"]
+pub struct W(crate::W<INTERRUPT_MASK_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<INTERRUPT_MASK_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<INTERRUPT_MASK_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<INTERRUPT_MASK_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `all_regions` reader - "]
+pub struct ALL_REGIONS_R(crate::FieldReader<bool, bool>);
+impl ALL_REGIONS_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        ALL_REGIONS_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for ALL_REGIONS_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `all_regions` writer - "]
+pub struct ALL_REGIONS_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> ALL_REGIONS_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 4)) | ((value as u16 & 0x01) << 4);
+        self.w
+    }
+}
+#[doc = "Field `region3` reader - "]
+pub struct REGION3_R(crate::FieldReader<bool, bool>);
+impl REGION3_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        REGION3_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for REGION3_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `region3` writer - "]
+pub struct REGION3_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> REGION3_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 3)) | ((value as u16 & 0x01) << 3);
+        self.w
+    }
+}
+#[doc = "Field `region2` reader - "]
+pub struct REGION2_R(crate::FieldReader<bool, bool>);
+impl REGION2_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        REGION2_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for REGION2_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `region2` writer - "]
+pub struct REGION2_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> REGION2_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 2)) | ((value as u16 & 0x01) << 2);
+        self.w
+    }
+}
+#[doc = "Field `region1` reader - "]
+pub struct REGION1_R(crate::FieldReader<bool, bool>);
+impl REGION1_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        REGION1_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for REGION1_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `region1` writer - "]
+pub struct REGION1_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> REGION1_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 1)) | ((value as u16 & 0x01) << 1);
+        self.w
+    }
+}
+#[doc = "Field `region0` reader - "]
+pub struct REGION0_R(crate::FieldReader<bool, bool>);
+impl REGION0_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        REGION0_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for REGION0_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `region0` writer - "]
+pub struct REGION0_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> REGION0_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x01) | (value as u16 & 0x01);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bit 4"]
+    #[inline(always)]
+    pub fn all_regions(&self) -> ALL_REGIONS_R {
+        ALL_REGIONS_R::new(((self.bits >> 4) & 0x01) != 0)
+    }
+    #[doc = "Bit 3"]
+    #[inline(always)]
+    pub fn region3(&self) -> REGION3_R {
+        REGION3_R::new(((self.bits >> 3) & 0x01) != 0)
+    }
+    #[doc = "Bit 2"]
+    #[inline(always)]
+    pub fn region2(&self) -> REGION2_R {
+        REGION2_R::new(((self.bits >> 2) & 0x01) != 0)
+    }
+    #[doc = "Bit 1"]
+    #[inline(always)]
+    pub fn region1(&self) -> REGION1_R {
+        REGION1_R::new(((self.bits >> 1) & 0x01) != 0)
+    }
+    #[doc = "Bit 0"]
+    #[inline(always)]
+    pub fn region0(&self) -> REGION0_R {
+        REGION0_R::new((self.bits & 0x01) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 4"]
+    #[inline(always)]
+    pub fn all_regions(&mut self) -> ALL_REGIONS_W {
+        ALL_REGIONS_W { w: self }
+    }
+    #[doc = "Bit 3"]
+    #[inline(always)]
+    pub fn region3(&mut self) -> REGION3_W {
+        REGION3_W { w: self }
+    }
+    #[doc = "Bit 2"]
+    #[inline(always)]
+    pub fn region2(&mut self) -> REGION2_W {
+        REGION2_W { w: self }
+    }
+    #[doc = "Bit 1"]
+    #[inline(always)]
+    pub fn region1(&mut self) -> REGION1_W {
+        REGION1_W { w: self }
+    }
+    #[doc = "Bit 0"]
+    #[inline(always)]
+    pub fn region0(&mut self) -> REGION0_W {
+        REGION0_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u16) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [interrupt_mask](index.html) module"]
+pub struct INTERRUPT_MASK_SPEC;
+impl crate::RegisterSpec for INTERRUPT_MASK_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [interrupt_mask::R](R) reader structure"]
+impl crate::Readable for INTERRUPT_MASK_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [interrupt_mask::W](W) writer structure"]
+impl crate::Writable for INTERRUPT_MASK_SPEC {
+    type Writer = W;
+}
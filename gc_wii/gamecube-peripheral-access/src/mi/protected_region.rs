@@ -0,0 +1,8 @@
+#[doc = "start register accessor: an alias for `Reg<START_SPEC>`"]
+pub type START = crate::Reg<start::START_SPEC>;
+#[doc = ""]
+pub mod start;
+#[doc = "end register accessor: an alias for `Reg<END_SPEC>`"]
+pub type END = crate::Reg<end::END_SPEC>;
+#[doc = ""]
+pub mod end;
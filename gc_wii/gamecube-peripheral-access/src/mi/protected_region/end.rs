@@ -0,0 +1,57 @@
+#[doc = "Register `end` reader"]
+pub struct R(crate::R<END_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<END_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<END_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<END_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `end` writer"]
+pub struct W(crate::W<END_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<END_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<END_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<END_SPEC>) -> Self {
+        W(writer)
+    }
+}
+impl W {
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u16) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [end](index.html) module"]
+pub struct END_SPEC;
+impl crate::RegisterSpec for END_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [end::R](R) reader structure"]
+impl crate::Readable for END_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [end::W](W) writer structure"]
+impl crate::Writable for END_SPEC {
+    type Writer = W;
+}
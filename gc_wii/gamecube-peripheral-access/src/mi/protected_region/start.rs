@@ -0,0 +1,57 @@
+#[doc = "Register `start` reader"]
+pub struct R(crate::R<START_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<START_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<START_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<START_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `start` writer"]
+pub struct W(crate::W<START_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<START_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<START_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<START_SPEC>) -> Self {
+        W(writer)
+    }
+}
+impl W {
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u16) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [start](index.html) module"]
+pub struct START_SPEC;
+impl crate::RegisterSpec for START_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [start::R](R) reader structure"]
+impl crate::Readable for START_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [start::W](W) writer structure"]
+impl crate::Writable for START_SPEC {
+    type Writer = W;
+}
@@ -0,0 +1,324 @@
+#[doc = "Register `protection_type` reader"]
+pub struct R(crate::R<PROTECTION_TYPE_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<PROTECTION_TYPE_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<PROTECTION_TYPE_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<PROTECTION_TYPE_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `protection_type` writer"]
+pub struct W(crate::W<PROTECTION_TYPE_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<PROTECTION_TYPE_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<PROTECTION_TYPE_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<PROTECTION_TYPE_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = ""]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(u8)]
+pub enum REGION3_A {
+    #[doc = "0: `0`"]
+    NO_ACCESS = 0,
+    #[doc = "1: `1`"]
+    READ_ONLY = 1,
+    #[doc = "2: `10`"]
+    WRITE_ONLY = 2,
+    #[doc = "3: `11`"]
+    READ_WRITE = 3,
+}
+impl From<REGION3_A> for u8 {
+    #[inline(always)]
+    fn from(variant: REGION3_A) -> Self {
+        variant as _
+    }
+}
+#[doc = "Field `region3` reader - "]
+pub struct REGION3_R(crate::FieldReader<u8, REGION3_A>);
+impl REGION3_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        REGION3_R(crate::FieldReader::new(bits))
+    }
+    #[doc = r"Get enumerated values variant"]
+    #[inline(always)]
+    pub fn variant(&self) -> REGION3_A {
+        match self.bits {
+            0 => REGION3_A::NO_ACCESS,
+            1 => REGION3_A::READ_ONLY,
+            2 => REGION3_A::WRITE_ONLY,
+            3 => REGION3_A::READ_WRITE,
+            _ => unreachable!(),
+        }
+    }
+    #[doc = "Checks if the value of the field is `NO_ACCESS`"]
+    #[inline(always)]
+    pub fn is_no_access(&self) -> bool {
+        **self == REGION3_A::NO_ACCESS
+    }
+    #[doc = "Checks if the value of the field is `READ_ONLY`"]
+    #[inline(always)]
+    pub fn is_read_only(&self) -> bool {
+        **self == REGION3_A::READ_ONLY
+    }
+    #[doc = "Checks if the value of the field is `WRITE_ONLY`"]
+    #[inline(always)]
+    pub fn is_write_only(&self) -> bool {
+        **self == REGION3_A::WRITE_ONLY
+    }
+    #[doc = "Checks if the value of the field is `READ_WRITE`"]
+    #[inline(always)]
+    pub fn is_read_write(&self) -> bool {
+        **self == REGION3_A::READ_WRITE
+    }
+}
+impl core::ops::Deref for REGION3_R {
+    type Target = crate::FieldReader<u8, REGION3_A>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `region3` writer - "]
+pub struct REGION3_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> REGION3_W<'a> {
+    #[doc = r"Writes `variant` to the field"]
+    #[inline(always)]
+    pub fn variant(self, variant: REGION3_A) -> &'a mut W {
+        self.bits(variant.into())
+    }
+    #[doc = "`0`"]
+    #[inline(always)]
+    pub fn no_access(self) -> &'a mut W {
+        self.variant(REGION3_A::NO_ACCESS)
+    }
+    #[doc = "`1`"]
+    #[inline(always)]
+    pub fn read_only(self) -> &'a mut W {
+        self.variant(REGION3_A::READ_ONLY)
+    }
+    #[doc = "`10`"]
+    #[inline(always)]
+    pub fn write_only(self) -> &'a mut W {
+        self.variant(REGION3_A::WRITE_ONLY)
+    }
+    #[doc = "`11`"]
+    #[inline(always)]
+    pub fn read_write(self) -> &'a mut W {
+        self.variant(REGION3_A::READ_WRITE)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x03 << 6)) | ((value as u16 & 0x03) << 6);
+        self.w
+    }
+}
+#[doc = ""]
+pub type REGION2_A = REGION3_A;
+#[doc = "Field `region2` reader - "]
+pub type REGION2_R = REGION3_R;
+#[doc = "Field `region2` writer - "]
+pub struct REGION2_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> REGION2_W<'a> {
+    #[doc = r"Writes `variant` to the field"]
+    #[inline(always)]
+    pub fn variant(self, variant: REGION2_A) -> &'a mut W {
+        self.bits(variant.into())
+    }
+    #[doc = "`0`"]
+    #[inline(always)]
+    pub fn no_access(self) -> &'a mut W {
+        self.variant(REGION2_A::NO_ACCESS)
+    }
+    #[doc = "`1`"]
+    #[inline(always)]
+    pub fn read_only(self) -> &'a mut W {
+        self.variant(REGION2_A::READ_ONLY)
+    }
+    #[doc = "`10`"]
+    #[inline(always)]
+    pub fn write_only(self) -> &'a mut W {
+        self.variant(REGION2_A::WRITE_ONLY)
+    }
+    #[doc = "`11`"]
+    #[inline(always)]
+    pub fn read_write(self) -> &'a mut W {
+        self.variant(REGION2_A::READ_WRITE)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x03 << 4)) | ((value as u16 & 0x03) << 4);
+        self.w
+    }
+}
+#[doc = ""]
+pub type REGION1_A = REGION3_A;
+#[doc = "Field `region1` reader - "]
+pub type REGION1_R = REGION3_R;
+#[doc = "Field `region1` writer - "]
+pub struct REGION1_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> REGION1_W<'a> {
+    #[doc = r"Writes `variant` to the field"]
+    #[inline(always)]
+    pub fn variant(self, variant: REGION1_A) -> &'a mut W {
+        self.bits(variant.into())
+    }
+    #[doc = "`0`"]
+    #[inline(always)]
+    pub fn no_access(self) -> &'a mut W {
+        self.variant(REGION1_A::NO_ACCESS)
+    }
+    #[doc = "`1`"]
+    #[inline(always)]
+    pub fn read_only(self) -> &'a mut W {
+        self.variant(REGION1_A::READ_ONLY)
+    }
+    #[doc = "`10`"]
+    #[inline(always)]
+    pub fn write_only(self) -> &'a mut W {
+        self.variant(REGION1_A::WRITE_ONLY)
+    }
+    #[doc = "`11`"]
+    #[inline(always)]
+    pub fn read_write(self) -> &'a mut W {
+        self.variant(REGION1_A::READ_WRITE)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x03 << 2)) | ((value as u16 & 0x03) << 2);
+        self.w
+    }
+}
+#[doc = ""]
+pub type REGION0_A = REGION3_A;
+#[doc = "Field `region0` reader - "]
+pub type REGION0_R = REGION3_R;
+#[doc = "Field `region0` writer - "]
+pub struct REGION0_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> REGION0_W<'a> {
+    #[doc = r"Writes `variant` to the field"]
+    #[inline(always)]
+    pub fn variant(self, variant: REGION0_A) -> &'a mut W {
+        self.bits(variant.into())
+    }
+    #[doc = "`0`"]
+    #[inline(always)]
+    pub fn no_access(self) -> &'a mut W {
+        self.variant(REGION0_A::NO_ACCESS)
+    }
+    #[doc = "`1`"]
+    #[inline(always)]
+    pub fn read_only(self) -> &'a mut W {
+        self.variant(REGION0_A::READ_ONLY)
+    }
+    #[doc = "`10`"]
+    #[inline(always)]
+    pub fn write_only(self) -> &'a mut W {
+        self.variant(REGION0_A::WRITE_ONLY)
+    }
+    #[doc = "`11`"]
+    #[inline(always)]
+    pub fn read_write(self) -> &'a mut W {
+        self.variant(REGION0_A::READ_WRITE)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x03) | (value as u16 & 0x03);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bits 6:7"]
+    #[inline(always)]
+    pub fn region3(&self) -> REGION3_R {
+        REGION3_R::new(((self.bits >> 6) & 0x03) as u8)
+    }
+    #[doc = "Bits 4:5"]
+    #[inline(always)]
+    pub fn region2(&self) -> REGION2_R {
+        REGION2_R::new(((self.bits >> 4) & 0x03) as u8)
+    }
+    #[doc = "Bits 2:3"]
+    #[inline(always)]
+    pub fn region1(&self) -> REGION1_R {
+        REGION1_R::new(((self.bits >> 2) & 0x03) as u8)
+    }
+    #[doc = "Bits 0:1"]
+    #[inline(always)]
+    pub fn region0(&self) -> REGION0_R {
+        REGION0_R::new((self.bits & 0x03) as u8)
+    }
+}
+impl W {
+    #[doc = "Bits 6:7"]
+    #[inline(always)]
+    pub fn region3(&mut self) -> REGION3_W {
+        REGION3_W { w: self }
+    }
+    #[doc = "Bits 4:5"]
+    #[inline(always)]
+    pub fn region2(&mut self) -> REGION2_W {
+        REGION2_W { w: self }
+    }
+    #[doc = "Bits 2:3"]
+    #[inline(always)]
+    pub fn region1(&mut self) -> REGION1_W {
+        REGION1_W { w: self }
+    }
+    #[doc = "Bits 0:1"]
+    #[inline(always)]
+    pub fn region0(&mut self) -> REGION0_W {
+        REGION0_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u16) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [protection_type](index.html) module"]
+pub struct PROTECTION_TYPE_SPEC;
+impl crate::RegisterSpec for PROTECTION_TYPE_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [protection_type::R](R) reader structure"]
+impl crate::Readable for PROTECTION_TYPE_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [protection_type::W](W) writer structure"]
+impl crate::Writable for PROTECTION_TYPE_SPEC {
+    type Writer = W;
+}
@@ -0,0 +1,50 @@
+#[doc = r"Register block"]
+#[repr(C)]
+pub struct RegisterBlock {
+    #[doc = "0x00..0x30 - Per-channel output and input buffers"]
+    pub channel: [CHANNEL; 4],
+    #[doc = "0x30 - "]
+    pub poll: crate::Reg<poll::POLL_SPEC>,
+    #[doc = "0x34 - "]
+    pub communication_control: crate::Reg<communication_control::COMMUNICATION_CONTROL_SPEC>,
+    #[doc = "0x38 - "]
+    pub status: crate::Reg<status::STATUS_SPEC>,
+    #[doc = "0x3c - "]
+    pub exi_clock_lock: crate::Reg<exi_clock_lock::EXI_CLOCK_LOCK_SPEC>,
+    _reserved5: [u8; 0x40],
+    #[doc = "0x80..0x100 - "]
+    pub io_buffer: [crate::Reg<io_buffer::IO_BUFFER_SPEC>; 32],
+}
+#[doc = r"Register block"]
+#[repr(C)]
+pub struct CHANNEL {
+    #[doc = "0x00 - "]
+    pub output_buffer: crate::Reg<self::channel::output_buffer::OUTPUT_BUFFER_SPEC>,
+    #[doc = "0x04 - "]
+    pub input_buffer_h: crate::Reg<self::channel::input_buffer_h::INPUT_BUFFER_H_SPEC>,
+    #[doc = "0x08 - "]
+    pub input_buffer_l: crate::Reg<self::channel::input_buffer_l::INPUT_BUFFER_L_SPEC>,
+}
+#[doc = r"Register block"]
+#[doc = "Per-channel output and input buffers"]
+pub mod channel;
+#[doc = "poll register accessor: an alias for `Reg<POLL_SPEC>`"]
+pub type POLL = crate::Reg<poll::POLL_SPEC>;
+#[doc = ""]
+pub mod poll;
+#[doc = "communication_control register accessor: an alias for `Reg<COMMUNICATION_CONTROL_SPEC>`"]
+pub type COMMUNICATION_CONTROL = crate::Reg<communication_control::COMMUNICATION_CONTROL_SPEC>;
+#[doc = ""]
+pub mod communication_control;
+#[doc = "status register accessor: an alias for `Reg<STATUS_SPEC>`"]
+pub type STATUS = crate::Reg<status::STATUS_SPEC>;
+#[doc = ""]
+pub mod status;
+#[doc = "exi_clock_lock register accessor: an alias for `Reg<EXI_CLOCK_LOCK_SPEC>`"]
+pub type EXI_CLOCK_LOCK = crate::Reg<exi_clock_lock::EXI_CLOCK_LOCK_SPEC>;
+#[doc = ""]
+pub mod exi_clock_lock;
+#[doc = "io_buffer register accessor: an alias for `Reg<IO_BUFFER_SPEC>`"]
+pub type IO_BUFFER = crate::Reg<io_buffer::IO_BUFFER_SPEC>;
+#[doc = ""]
+pub mod io_buffer;
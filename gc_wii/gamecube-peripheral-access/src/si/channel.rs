@@ -0,0 +1,12 @@
+#[doc = "output_buffer register accessor: an alias for `Reg<OUTPUT_BUFFER_SPEC>`"]
+pub type OUTPUT_BUFFER = crate::Reg<output_buffer::OUTPUT_BUFFER_SPEC>;
+#[doc = ""]
+pub mod output_buffer;
+#[doc = "input_buffer_h register accessor: an alias for `Reg<INPUT_BUFFER_H_SPEC>`"]
+pub type INPUT_BUFFER_H = crate::Reg<input_buffer_h::INPUT_BUFFER_H_SPEC>;
+#[doc = ""]
+pub mod input_buffer_h;
+#[doc = "input_buffer_l register accessor: an alias for `Reg<INPUT_BUFFER_L_SPEC>`"]
+pub type INPUT_BUFFER_L = crate::Reg<input_buffer_l::INPUT_BUFFER_L_SPEC>;
+#[doc = ""]
+pub mod input_buffer_l;
@@ -0,0 +1,66 @@
+#[doc = "Register `input_buffer_h` reader"]
+pub struct R(crate::R<INPUT_BUFFER_H_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<INPUT_BUFFER_H_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<INPUT_BUFFER_H_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<INPUT_BUFFER_H_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Field `error_status` reader - "]
+pub struct ERROR_STATUS_R(crate::FieldReader<bool, bool>);
+impl ERROR_STATUS_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        ERROR_STATUS_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for ERROR_STATUS_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `error_latch` reader - "]
+pub struct ERROR_LATCH_R(crate::FieldReader<bool, bool>);
+impl ERROR_LATCH_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        ERROR_LATCH_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for ERROR_LATCH_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl R {
+    #[doc = "Bit 31"]
+    #[inline(always)]
+    pub fn error_status(&self) -> ERROR_STATUS_R {
+        ERROR_STATUS_R::new(((self.bits >> 31) & 0x01) != 0)
+    }
+    #[doc = "Bit 30"]
+    #[inline(always)]
+    pub fn error_latch(&self) -> ERROR_LATCH_R {
+        ERROR_LATCH_R::new(((self.bits >> 30) & 0x01) != 0)
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [input_buffer_h](index.html) module"]
+pub struct INPUT_BUFFER_H_SPEC;
+impl crate::RegisterSpec for INPUT_BUFFER_H_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [input_buffer_h::R](R) reader structure"]
+impl crate::Readable for INPUT_BUFFER_H_SPEC {
+    type Reader = R;
+}
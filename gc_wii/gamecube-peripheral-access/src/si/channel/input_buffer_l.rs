@@ -0,0 +1,24 @@
+#[doc = "Register `input_buffer_l` reader"]
+pub struct R(crate::R<INPUT_BUFFER_L_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<INPUT_BUFFER_L_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<INPUT_BUFFER_L_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<INPUT_BUFFER_L_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [input_buffer_l](index.html) module"]
+pub struct INPUT_BUFFER_L_SPEC;
+impl crate::RegisterSpec for INPUT_BUFFER_L_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [input_buffer_l::R](R) reader structure"]
+impl crate::Readable for INPUT_BUFFER_L_SPEC {
+    type Reader = R;
+}
@@ -0,0 +1,398 @@
+#[doc = "Register `communication_control` reader"]
+pub struct R(crate::R<COMMUNICATION_CONTROL_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<COMMUNICATION_CONTROL_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<COMMUNICATION_CONTROL_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<COMMUNICATION_CONTROL_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `communication_control` writer"]
+pub struct W(crate::W<COMMUNICATION_CONTROL_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<COMMUNICATION_CONTROL_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<COMMUNICATION_CONTROL_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<COMMUNICATION_CONTROL_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `transfer_complete_interrupt` reader - "]
+pub struct TRANSFER_COMPLETE_INTERRUPT_R(crate::FieldReader<bool, bool>);
+impl TRANSFER_COMPLETE_INTERRUPT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        TRANSFER_COMPLETE_INTERRUPT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for TRANSFER_COMPLETE_INTERRUPT_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `transfer_complete_interrupt` writer - "]
+pub struct TRANSFER_COMPLETE_INTERRUPT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> TRANSFER_COMPLETE_INTERRUPT_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 31)) | ((value as u32 & 0x01) << 31);
+        self.w
+    }
+}
+#[doc = "Field `transfer_complete_interrupt_mask` reader - "]
+pub struct TRANSFER_COMPLETE_INTERRUPT_MASK_R(crate::FieldReader<bool, bool>);
+impl TRANSFER_COMPLETE_INTERRUPT_MASK_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        TRANSFER_COMPLETE_INTERRUPT_MASK_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for TRANSFER_COMPLETE_INTERRUPT_MASK_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `transfer_complete_interrupt_mask` writer - "]
+pub struct TRANSFER_COMPLETE_INTERRUPT_MASK_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> TRANSFER_COMPLETE_INTERRUPT_MASK_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 30)) | ((value as u32 & 0x01) << 30);
+        self.w
+    }
+}
+#[doc = "Field `communication_error` reader - "]
+pub struct COMMUNICATION_ERROR_R(crate::FieldReader<bool, bool>);
+impl COMMUNICATION_ERROR_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        COMMUNICATION_ERROR_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for COMMUNICATION_ERROR_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `read_status_interrupt` reader - "]
+pub struct READ_STATUS_INTERRUPT_R(crate::FieldReader<bool, bool>);
+impl READ_STATUS_INTERRUPT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        READ_STATUS_INTERRUPT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for READ_STATUS_INTERRUPT_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `read_status_interrupt_mask` reader - "]
+pub struct READ_STATUS_INTERRUPT_MASK_R(crate::FieldReader<bool, bool>);
+impl READ_STATUS_INTERRUPT_MASK_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        READ_STATUS_INTERRUPT_MASK_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for READ_STATUS_INTERRUPT_MASK_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `read_status_interrupt_mask` writer - "]
+pub struct READ_STATUS_INTERRUPT_MASK_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> READ_STATUS_INTERRUPT_MASK_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 27)) | ((value as u32 & 0x01) << 27);
+        self.w
+    }
+}
+#[doc = "Field `output_length` reader - "]
+pub struct OUTPUT_LENGTH_R(crate::FieldReader<u8, u8>);
+impl OUTPUT_LENGTH_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        OUTPUT_LENGTH_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for OUTPUT_LENGTH_R {
+    type Target = crate::FieldReader<u8, u8>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `output_length` writer - "]
+pub struct OUTPUT_LENGTH_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> OUTPUT_LENGTH_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x7f << 16)) | ((value as u32 & 0x7f) << 16);
+        self.w
+    }
+}
+#[doc = "Field `input_length` reader - "]
+pub struct INPUT_LENGTH_R(crate::FieldReader<u8, u8>);
+impl INPUT_LENGTH_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        INPUT_LENGTH_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for INPUT_LENGTH_R {
+    type Target = crate::FieldReader<u8, u8>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `input_length` writer - "]
+pub struct INPUT_LENGTH_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> INPUT_LENGTH_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x7f << 8)) | ((value as u32 & 0x7f) << 8);
+        self.w
+    }
+}
+#[doc = "Field `channel` reader - "]
+pub struct CHANNEL_R(crate::FieldReader<u8, u8>);
+impl CHANNEL_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        CHANNEL_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for CHANNEL_R {
+    type Target = crate::FieldReader<u8, u8>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `channel` writer - "]
+pub struct CHANNEL_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> CHANNEL_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x03 << 1)) | ((value as u32 & 0x03) << 1);
+        self.w
+    }
+}
+#[doc = "Field `transfer_start` reader - "]
+pub struct TRANSFER_START_R(crate::FieldReader<bool, bool>);
+impl TRANSFER_START_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        TRANSFER_START_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for TRANSFER_START_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `transfer_start` writer - "]
+pub struct TRANSFER_START_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> TRANSFER_START_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x01) | (value as u32 & 0x01);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bit 31"]
+    #[inline(always)]
+    pub fn transfer_complete_interrupt(&self) -> TRANSFER_COMPLETE_INTERRUPT_R {
+        TRANSFER_COMPLETE_INTERRUPT_R::new(((self.bits >> 31) & 0x01) != 0)
+    }
+    #[doc = "Bit 30"]
+    #[inline(always)]
+    pub fn transfer_complete_interrupt_mask(&self) -> TRANSFER_COMPLETE_INTERRUPT_MASK_R {
+        TRANSFER_COMPLETE_INTERRUPT_MASK_R::new(((self.bits >> 30) & 0x01) != 0)
+    }
+    #[doc = "Bit 29"]
+    #[inline(always)]
+    pub fn communication_error(&self) -> COMMUNICATION_ERROR_R {
+        COMMUNICATION_ERROR_R::new(((self.bits >> 29) & 0x01) != 0)
+    }
+    #[doc = "Bit 28"]
+    #[inline(always)]
+    pub fn read_status_interrupt(&self) -> READ_STATUS_INTERRUPT_R {
+        READ_STATUS_INTERRUPT_R::new(((self.bits >> 28) & 0x01) != 0)
+    }
+    #[doc = "Bit 27"]
+    #[inline(always)]
+    pub fn read_status_interrupt_mask(&self) -> READ_STATUS_INTERRUPT_MASK_R {
+        READ_STATUS_INTERRUPT_MASK_R::new(((self.bits >> 27) & 0x01) != 0)
+    }
+    #[doc = "Bits 16:22"]
+    #[inline(always)]
+    pub fn output_length(&self) -> OUTPUT_LENGTH_R {
+        OUTPUT_LENGTH_R::new(((self.bits >> 16) & 0x7f) as u8)
+    }
+    #[doc = "Bits 8:14"]
+    #[inline(always)]
+    pub fn input_length(&self) -> INPUT_LENGTH_R {
+        INPUT_LENGTH_R::new(((self.bits >> 8) & 0x7f) as u8)
+    }
+    #[doc = "Bits 1:2"]
+    #[inline(always)]
+    pub fn channel(&self) -> CHANNEL_R {
+        CHANNEL_R::new(((self.bits >> 1) & 0x03) as u8)
+    }
+    #[doc = "Bit 0"]
+    #[inline(always)]
+    pub fn transfer_start(&self) -> TRANSFER_START_R {
+        TRANSFER_START_R::new((self.bits & 0x01) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 31"]
+    #[inline(always)]
+    pub fn transfer_complete_interrupt(&mut self) -> TRANSFER_COMPLETE_INTERRUPT_W {
+        TRANSFER_COMPLETE_INTERRUPT_W { w: self }
+    }
+    #[doc = "Bit 30"]
+    #[inline(always)]
+    pub fn transfer_complete_interrupt_mask(&mut self) -> TRANSFER_COMPLETE_INTERRUPT_MASK_W {
+        TRANSFER_COMPLETE_INTERRUPT_MASK_W { w: self }
+    }
+    #[doc = "Bit 27"]
+    #[inline(always)]
+    pub fn read_status_interrupt_mask(&mut self) -> READ_STATUS_INTERRUPT_MASK_W {
+        READ_STATUS_INTERRUPT_MASK_W { w: self }
+    }
+    #[doc = "Bits 16:22"]
+    #[inline(always)]
+    pub fn output_length(&mut self) -> OUTPUT_LENGTH_W {
+        OUTPUT_LENGTH_W { w: self }
+    }
+    #[doc = "Bits 8:14"]
+    #[inline(always)]
+    pub fn input_length(&mut self) -> INPUT_LENGTH_W {
+        INPUT_LENGTH_W { w: self }
+    }
+    #[doc = "Bits 1:2"]
+    #[inline(always)]
+    pub fn channel(&mut self) -> CHANNEL_W {
+        CHANNEL_W { w: self }
+    }
+    #[doc = "Bit 0"]
+    #[inline(always)]
+    pub fn transfer_start(&mut self) -> TRANSFER_START_W {
+        TRANSFER_START_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [communication_control](index.html) module"]
+pub struct COMMUNICATION_CONTROL_SPEC;
+impl crate::RegisterSpec for COMMUNICATION_CONTROL_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [communication_control::R](R) reader structure"]
+impl crate::Readable for COMMUNICATION_CONTROL_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [communication_control::W](W) writer structure"]
+impl crate::Writable for COMMUNICATION_CONTROL_SPEC {
+    type Writer = W;
+}
@@ -0,0 +1,106 @@
+#[doc = "Register `exi_clock_lock` reader"]
+pub struct R(crate::R<EXI_CLOCK_LOCK_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<EXI_CLOCK_LOCK_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<EXI_CLOCK_LOCK_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<EXI_CLOCK_LOCK_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `exi_clock_lock` writer"]
+pub struct W(crate::W<EXI_CLOCK_LOCK_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<EXI_CLOCK_LOCK_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<EXI_CLOCK_LOCK_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<EXI_CLOCK_LOCK_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `lock` reader - "]
+pub struct LOCK_R(crate::FieldReader<bool, bool>);
+impl LOCK_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        LOCK_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for LOCK_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `lock` writer - "]
+pub struct LOCK_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> LOCK_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 31)) | ((value as u32 & 0x01) << 31);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bit 31"]
+    #[inline(always)]
+    pub fn lock(&self) -> LOCK_R {
+        LOCK_R::new(((self.bits >> 31) & 0x01) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 31"]
+    #[inline(always)]
+    pub fn lock(&mut self) -> LOCK_W {
+        LOCK_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [exi_clock_lock](index.html) module"]
+pub struct EXI_CLOCK_LOCK_SPEC;
+impl crate::RegisterSpec for EXI_CLOCK_LOCK_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [exi_clock_lock::R](R) reader structure"]
+impl crate::Readable for EXI_CLOCK_LOCK_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [exi_clock_lock::W](W) writer structure"]
+impl crate::Writable for EXI_CLOCK_LOCK_SPEC {
+    type Writer = W;
+}
@@ -0,0 +1,207 @@
+#[doc = "Register `poll` reader"]
+pub struct R(crate::R<POLL_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<POLL_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<POLL_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<POLL_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `poll` writer"]
+pub struct W(crate::W<POLL_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<POLL_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<POLL_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<POLL_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `lines_per_poll` reader - "]
+pub struct LINES_PER_POLL_R(crate::FieldReader<u16, u16>);
+impl LINES_PER_POLL_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u16) -> Self {
+        LINES_PER_POLL_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for LINES_PER_POLL_R {
+    type Target = crate::FieldReader<u16, u16>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `lines_per_poll` writer - "]
+pub struct LINES_PER_POLL_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> LINES_PER_POLL_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u16) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x03ff << 16)) | ((value as u32 & 0x03ff) << 16);
+        self.w
+    }
+}
+#[doc = "Field `polls_per_frame` reader - "]
+pub struct POLLS_PER_FRAME_R(crate::FieldReader<u8, u8>);
+impl POLLS_PER_FRAME_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        POLLS_PER_FRAME_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for POLLS_PER_FRAME_R {
+    type Target = crate::FieldReader<u8, u8>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `polls_per_frame` writer - "]
+pub struct POLLS_PER_FRAME_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> POLLS_PER_FRAME_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0xff << 8)) | ((value as u32 & 0xff) << 8);
+        self.w
+    }
+}
+#[doc = "Field `enable` reader - "]
+pub struct ENABLE_R(crate::FieldReader<u8, u8>);
+impl ENABLE_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        ENABLE_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for ENABLE_R {
+    type Target = crate::FieldReader<u8, u8>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `enable` writer - "]
+pub struct ENABLE_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> ENABLE_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x0f << 4)) | ((value as u32 & 0x0f) << 4);
+        self.w
+    }
+}
+#[doc = "Field `copy_on_vblank` reader - "]
+pub struct COPY_ON_VBLANK_R(crate::FieldReader<u8, u8>);
+impl COPY_ON_VBLANK_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        COPY_ON_VBLANK_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for COPY_ON_VBLANK_R {
+    type Target = crate::FieldReader<u8, u8>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `copy_on_vblank` writer - "]
+pub struct COPY_ON_VBLANK_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> COPY_ON_VBLANK_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x0f) | (value as u32 & 0x0f);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bits 16:25"]
+    #[inline(always)]
+    pub fn lines_per_poll(&self) -> LINES_PER_POLL_R {
+        LINES_PER_POLL_R::new(((self.bits >> 16) & 0x03ff) as u16)
+    }
+    #[doc = "Bits 8:15"]
+    #[inline(always)]
+    pub fn polls_per_frame(&self) -> POLLS_PER_FRAME_R {
+        POLLS_PER_FRAME_R::new(((self.bits >> 8) & 0xff) as u8)
+    }
+    #[doc = "Bits 4:7"]
+    #[inline(always)]
+    pub fn enable(&self) -> ENABLE_R {
+        ENABLE_R::new(((self.bits >> 4) & 0x0f) as u8)
+    }
+    #[doc = "Bits 0:3"]
+    #[inline(always)]
+    pub fn copy_on_vblank(&self) -> COPY_ON_VBLANK_R {
+        COPY_ON_VBLANK_R::new((self.bits & 0x0f) as u8)
+    }
+}
+impl W {
+    #[doc = "Bits 16:25"]
+    #[inline(always)]
+    pub fn lines_per_poll(&mut self) -> LINES_PER_POLL_W {
+        LINES_PER_POLL_W { w: self }
+    }
+    #[doc = "Bits 8:15"]
+    #[inline(always)]
+    pub fn polls_per_frame(&mut self) -> POLLS_PER_FRAME_W {
+        POLLS_PER_FRAME_W { w: self }
+    }
+    #[doc = "Bits 4:7"]
+    #[inline(always)]
+    pub fn enable(&mut self) -> ENABLE_W {
+        ENABLE_W { w: self }
+    }
+    #[doc = "Bits 0:3"]
+    #[inline(always)]
+    pub fn copy_on_vblank(&mut self) -> COPY_ON_VBLANK_W {
+        COPY_ON_VBLANK_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [poll](index.html) module"]
+pub struct POLL_SPEC;
+impl crate::RegisterSpec for POLL_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [poll::R](R) reader structure"]
+impl crate::Readable for POLL_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [poll::W](W) writer structure"]
+impl crate::Writable for POLL_SPEC {
+    type Writer = W;
+}
@@ -0,0 +1,135 @@
+#[doc = r"Register block"]
+#[repr(C)]
+pub struct RegisterBlock {
+    #[doc = "0x00 - "]
+    pub vertical_timing_a: crate::Reg<vertical_timing_a::VERTICAL_TIMING_A_SPEC>,
+    #[doc = "0x02 - "]
+    pub display_configuration: crate::Reg<display_configuration::DISPLAY_CONFIGURATION_SPEC>,
+    #[doc = "0x04 - "]
+    pub horizontal_timing_a: crate::Reg<horizontal_timing_a::HORIZONTAL_TIMING_A_SPEC>,
+    #[doc = "0x08 - "]
+    pub horizontal_timing_b: crate::Reg<horizontal_timing_b::HORIZONTAL_TIMING_B_SPEC>,
+    #[doc = "0x0c - "]
+    pub vertical_timing_b_odd_field:
+        crate::Reg<vertical_timing_b_odd_field::VERTICAL_TIMING_B_ODD_FIELD_SPEC>,
+    #[doc = "0x10 - "]
+    pub vertical_timing_b_even_field:
+        crate::Reg<vertical_timing_b_even_field::VERTICAL_TIMING_B_EVEN_FIELD_SPEC>,
+    #[doc = "0x14 - "]
+    pub burst_blanking_odd_field:
+        crate::Reg<burst_blanking_odd_field::BURST_BLANKING_ODD_FIELD_SPEC>,
+    #[doc = "0x18 - "]
+    pub burst_blanking_even_field:
+        crate::Reg<burst_blanking_even_field::BURST_BLANKING_EVEN_FIELD_SPEC>,
+    #[doc = "0x1c - "]
+    pub top_left_field_base: crate::Reg<top_left_field_base::TOP_LEFT_FIELD_BASE_SPEC>,
+    #[doc = "0x20 - "]
+    pub top_right_field_base: crate::Reg<top_right_field_base::TOP_RIGHT_FIELD_BASE_SPEC>,
+    #[doc = "0x24 - "]
+    pub bottom_left_field_base: crate::Reg<bottom_left_field_base::BOTTOM_LEFT_FIELD_BASE_SPEC>,
+    #[doc = "0x28 - "]
+    pub bottom_right_field_base: crate::Reg<bottom_right_field_base::BOTTOM_RIGHT_FIELD_BASE_SPEC>,
+    #[doc = "0x2c - "]
+    pub vertical_position: crate::Reg<vertical_position::VERTICAL_POSITION_SPEC>,
+    #[doc = "0x2e - "]
+    pub horizontal_position: crate::Reg<horizontal_position::HORIZONTAL_POSITION_SPEC>,
+    #[doc = "0x30..0x40 - "]
+    pub display_interrupt: [crate::Reg<display_interrupt::DISPLAY_INTERRUPT_SPEC>; 4],
+    #[doc = "0x40..0x48 - "]
+    pub display_latch: [crate::Reg<display_latch::DISPLAY_LATCH_SPEC>; 2],
+    #[doc = "0x48 - "]
+    pub horizontal_scaling: crate::Reg<horizontal_scaling::HORIZONTAL_SCALING_SPEC>,
+    #[doc = "0x4c..0x6c - "]
+    pub filter_coefficient_table:
+        [crate::Reg<filter_coefficient_table::FILTER_COEFFICIENT_TABLE_SPEC>; 8],
+    #[doc = "0x6c - "]
+    pub clock_select: crate::Reg<clock_select::CLOCK_SELECT_SPEC>,
+    #[doc = "0x6e - "]
+    pub dtv_status: crate::Reg<dtv_status::DTV_STATUS_SPEC>,
+}
+#[doc = "vertical_timing_a register accessor: an alias for `Reg<VERTICAL_TIMING_A_SPEC>`"]
+pub type VERTICAL_TIMING_A = crate::Reg<vertical_timing_a::VERTICAL_TIMING_A_SPEC>;
+#[doc = ""]
+pub mod vertical_timing_a;
+#[doc = "display_configuration register accessor: an alias for `Reg<DISPLAY_CONFIGURATION_SPEC>`"]
+pub type DISPLAY_CONFIGURATION = crate::Reg<display_configuration::DISPLAY_CONFIGURATION_SPEC>;
+#[doc = ""]
+pub mod display_configuration;
+#[doc = "horizontal_timing_a register accessor: an alias for `Reg<HORIZONTAL_TIMING_A_SPEC>`"]
+pub type HORIZONTAL_TIMING_A = crate::Reg<horizontal_timing_a::HORIZONTAL_TIMING_A_SPEC>;
+#[doc = ""]
+pub mod horizontal_timing_a;
+#[doc = "horizontal_timing_b register accessor: an alias for `Reg<HORIZONTAL_TIMING_B_SPEC>`"]
+pub type HORIZONTAL_TIMING_B = crate::Reg<horizontal_timing_b::HORIZONTAL_TIMING_B_SPEC>;
+#[doc = ""]
+pub mod horizontal_timing_b;
+#[doc = "vertical_timing_b_odd_field register accessor: an alias for `Reg<VERTICAL_TIMING_B_ODD_FIELD_SPEC>`"]
+pub type VERTICAL_TIMING_B_ODD_FIELD =
+    crate::Reg<vertical_timing_b_odd_field::VERTICAL_TIMING_B_ODD_FIELD_SPEC>;
+#[doc = ""]
+pub mod vertical_timing_b_odd_field;
+#[doc = "vertical_timing_b_even_field register accessor: an alias for `Reg<VERTICAL_TIMING_B_EVEN_FIELD_SPEC>`"]
+pub type VERTICAL_TIMING_B_EVEN_FIELD =
+    crate::Reg<vertical_timing_b_even_field::VERTICAL_TIMING_B_EVEN_FIELD_SPEC>;
+#[doc = ""]
+pub mod vertical_timing_b_even_field;
+#[doc = "burst_blanking_odd_field register accessor: an alias for `Reg<BURST_BLANKING_ODD_FIELD_SPEC>`"]
+pub type BURST_BLANKING_ODD_FIELD =
+    crate::Reg<burst_blanking_odd_field::BURST_BLANKING_ODD_FIELD_SPEC>;
+#[doc = ""]
+pub mod burst_blanking_odd_field;
+#[doc = "burst_blanking_even_field register accessor: an alias for `Reg<BURST_BLANKING_EVEN_FIELD_SPEC>`"]
+pub type BURST_BLANKING_EVEN_FIELD =
+    crate::Reg<burst_blanking_even_field::BURST_BLANKING_EVEN_FIELD_SPEC>;
+#[doc = ""]
+pub mod burst_blanking_even_field;
+#[doc = "top_left_field_base register accessor: an alias for `Reg<TOP_LEFT_FIELD_BASE_SPEC>`"]
+pub type TOP_LEFT_FIELD_BASE = crate::Reg<top_left_field_base::TOP_LEFT_FIELD_BASE_SPEC>;
+#[doc = ""]
+pub mod top_left_field_base;
+#[doc = "top_right_field_base register accessor: an alias for `Reg<TOP_RIGHT_FIELD_BASE_SPEC>`"]
+pub type TOP_RIGHT_FIELD_BASE = crate::Reg<top_right_field_base::TOP_RIGHT_FIELD_BASE_SPEC>;
+#[doc = ""]
+pub mod top_right_field_base;
+#[doc = "bottom_left_field_base register accessor: an alias for `Reg<BOTTOM_LEFT_FIELD_BASE_SPEC>`"]
+pub type BOTTOM_LEFT_FIELD_BASE = crate::Reg<bottom_left_field_base::BOTTOM_LEFT_FIELD_BASE_SPEC>;
+#[doc = ""]
+pub mod bottom_left_field_base;
+#[doc = "bottom_right_field_base register accessor: an alias for `Reg<BOTTOM_RIGHT_FIELD_BASE_SPEC>`"]
+pub type BOTTOM_RIGHT_FIELD_BASE =
+    crate::Reg<bottom_right_field_base::BOTTOM_RIGHT_FIELD_BASE_SPEC>;
+#[doc = ""]
+pub mod bottom_right_field_base;
+#[doc = "vertical_position register accessor: an alias for `Reg<VERTICAL_POSITION_SPEC>`"]
+pub type VERTICAL_POSITION = crate::Reg<vertical_position::VERTICAL_POSITION_SPEC>;
+#[doc = ""]
+pub mod vertical_position;
+#[doc = "horizontal_position register accessor: an alias for `Reg<HORIZONTAL_POSITION_SPEC>`"]
+pub type HORIZONTAL_POSITION = crate::Reg<horizontal_position::HORIZONTAL_POSITION_SPEC>;
+#[doc = ""]
+pub mod horizontal_position;
+#[doc = "display_interrupt register accessor: an alias for `Reg<DISPLAY_INTERRUPT_SPEC>`"]
+pub type DISPLAY_INTERRUPT = crate::Reg<display_interrupt::DISPLAY_INTERRUPT_SPEC>;
+#[doc = ""]
+pub mod display_interrupt;
+#[doc = "display_latch register accessor: an alias for `Reg<DISPLAY_LATCH_SPEC>`"]
+pub type DISPLAY_LATCH = crate::Reg<display_latch::DISPLAY_LATCH_SPEC>;
+#[doc = ""]
+pub mod display_latch;
+#[doc = "horizontal_scaling register accessor: an alias for `Reg<HORIZONTAL_SCALING_SPEC>`"]
+pub type HORIZONTAL_SCALING = crate::Reg<horizontal_scaling::HORIZONTAL_SCALING_SPEC>;
+#[doc = ""]
+pub mod horizontal_scaling;
+#[doc = "filter_coefficient_table register accessor: an alias for `Reg<FILTER_COEFFICIENT_TABLE_SPEC>`"]
+pub type FILTER_COEFFICIENT_TABLE =
+    crate::Reg<filter_coefficient_table::FILTER_COEFFICIENT_TABLE_SPEC>;
+#[doc = ""]
+pub mod filter_coefficient_table;
+#[doc = "clock_select register accessor: an alias for `Reg<CLOCK_SELECT_SPEC>`"]
+pub type CLOCK_SELECT = crate::Reg<clock_select::CLOCK_SELECT_SPEC>;
+#[doc = ""]
+pub mod clock_select;
+#[doc = "dtv_status register accessor: an alias for `Reg<DTV_STATUS_SPEC>`"]
+pub type DTV_STATUS = crate::Reg<dtv_status::DTV_STATUS_SPEC>;
+#[doc = ""]
+pub mod dtv_status;
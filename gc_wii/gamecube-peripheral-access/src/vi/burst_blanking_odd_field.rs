@@ -0,0 +1,57 @@
+#[doc = "Register `burst_blanking_odd_field` reader"]
+pub struct R(crate::R<BURST_BLANKING_ODD_FIELD_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<BURST_BLANKING_ODD_FIELD_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<BURST_BLANKING_ODD_FIELD_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<BURST_BLANKING_ODD_FIELD_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `burst_blanking_odd_field` writer"]
+pub struct W(crate::W<BURST_BLANKING_ODD_FIELD_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<BURST_BLANKING_ODD_FIELD_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<BURST_BLANKING_ODD_FIELD_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<BURST_BLANKING_ODD_FIELD_SPEC>) -> Self {
+        W(writer)
+    }
+}
+impl W {
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [burst_blanking_odd_field](index.html) module"]
+pub struct BURST_BLANKING_ODD_FIELD_SPEC;
+impl crate::RegisterSpec for BURST_BLANKING_ODD_FIELD_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [burst_blanking_odd_field::R](R) reader structure"]
+impl crate::Readable for BURST_BLANKING_ODD_FIELD_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [burst_blanking_odd_field::W](W) writer structure"]
+impl crate::Writable for BURST_BLANKING_ODD_FIELD_SPEC {
+    type Writer = W;
+}
@@ -0,0 +1,153 @@
+#[doc = "Register `clock_select` reader"]
+pub struct R(crate::R<CLOCK_SELECT_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<CLOCK_SELECT_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<CLOCK_SELECT_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<CLOCK_SELECT_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `clock_select` writer"]
+pub struct W(crate::W<CLOCK_SELECT_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<CLOCK_SELECT_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<CLOCK_SELECT_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<CLOCK_SELECT_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = ""]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CLOCK_A {
+    #[doc = "0: `0`"]
+    MHZ_27 = 0,
+    #[doc = "1: `1`"]
+    MHZ_54 = 1,
+}
+impl From<CLOCK_A> for bool {
+    #[inline(always)]
+    fn from(variant: CLOCK_A) -> Self {
+        variant as u8 != 0
+    }
+}
+#[doc = "Field `clock` reader - "]
+pub struct CLOCK_R(crate::FieldReader<bool, CLOCK_A>);
+impl CLOCK_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        CLOCK_R(crate::FieldReader::new(bits))
+    }
+    #[doc = r"Get enumerated values variant"]
+    #[inline(always)]
+    pub fn variant(&self) -> CLOCK_A {
+        match self.bits {
+            false => CLOCK_A::MHZ_27,
+            true => CLOCK_A::MHZ_54,
+        }
+    }
+    #[doc = "Checks if the value of the field is `MHZ_27`"]
+    #[inline(always)]
+    pub fn is_mhz_27(&self) -> bool {
+        **self == CLOCK_A::MHZ_27
+    }
+    #[doc = "Checks if the value of the field is `MHZ_54`"]
+    #[inline(always)]
+    pub fn is_mhz_54(&self) -> bool {
+        **self == CLOCK_A::MHZ_54
+    }
+}
+impl core::ops::Deref for CLOCK_R {
+    type Target = crate::FieldReader<bool, CLOCK_A>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `clock` writer - "]
+pub struct CLOCK_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> CLOCK_W<'a> {
+    #[doc = r"Writes `variant` to the field"]
+    #[inline(always)]
+    pub fn variant(self, variant: CLOCK_A) -> &'a mut W {
+        self.bit(variant.into())
+    }
+    #[doc = "`0`"]
+    #[inline(always)]
+    pub fn mhz_27(self) -> &'a mut W {
+        self.variant(CLOCK_A::MHZ_27)
+    }
+    #[doc = "`1`"]
+    #[inline(always)]
+    pub fn mhz_54(self) -> &'a mut W {
+        self.variant(CLOCK_A::MHZ_54)
+    }
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x01) | (value as u16 & 0x01);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bit 0"]
+    #[inline(always)]
+    pub fn clock(&self) -> CLOCK_R {
+        CLOCK_R::new((self.bits & 0x01) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 0"]
+    #[inline(always)]
+    pub fn clock(&mut self) -> CLOCK_W {
+        CLOCK_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u16) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [clock_select](index.html) module"]
+pub struct CLOCK_SELECT_SPEC;
+impl crate::RegisterSpec for CLOCK_SELECT_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [clock_select::R](R) reader structure"]
+impl crate::Readable for CLOCK_SELECT_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [clock_select::W](W) writer structure"]
+impl crate::Writable for CLOCK_SELECT_SPEC {
+    type Writer = W;
+}
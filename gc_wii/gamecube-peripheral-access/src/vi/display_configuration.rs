@@ -0,0 +1,569 @@
+#[doc = "Register `display_configuration` reader"]
+pub struct R(crate::R<DISPLAY_CONFIGURATION_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<DISPLAY_CONFIGURATION_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<DISPLAY_CONFIGURATION_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<DISPLAY_CONFIGURATION_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `display_configuration` writer"]
+pub struct W(crate::W<DISPLAY_CONFIGURATION_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<DISPLAY_CONFIGURATION_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<DISPLAY_CONFIGURATION_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<DISPLAY_CONFIGURATION_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = ""]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(u8)]
+pub enum FORMAT_A {
+    #[doc = "0: `0`"]
+    NTSC = 0,
+    #[doc = "1: `1`"]
+    PAL = 1,
+    #[doc = "2: `10`"]
+    MPAL = 2,
+    #[doc = "3: `11`"]
+    DEBUG = 3,
+}
+impl From<FORMAT_A> for u8 {
+    #[inline(always)]
+    fn from(variant: FORMAT_A) -> Self {
+        variant as _
+    }
+}
+#[doc = "Field `format` reader - "]
+pub struct FORMAT_R(crate::FieldReader<u8, FORMAT_A>);
+impl FORMAT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        FORMAT_R(crate::FieldReader::new(bits))
+    }
+    #[doc = r"Get enumerated values variant"]
+    #[inline(always)]
+    pub fn variant(&self) -> FORMAT_A {
+        match self.bits {
+            0 => FORMAT_A::NTSC,
+            1 => FORMAT_A::PAL,
+            2 => FORMAT_A::MPAL,
+            3 => FORMAT_A::DEBUG,
+            _ => unreachable!(),
+        }
+    }
+    #[doc = "Checks if the value of the field is `NTSC`"]
+    #[inline(always)]
+    pub fn is_ntsc(&self) -> bool {
+        **self == FORMAT_A::NTSC
+    }
+    #[doc = "Checks if the value of the field is `PAL`"]
+    #[inline(always)]
+    pub fn is_pal(&self) -> bool {
+        **self == FORMAT_A::PAL
+    }
+    #[doc = "Checks if the value of the field is `MPAL`"]
+    #[inline(always)]
+    pub fn is_mpal(&self) -> bool {
+        **self == FORMAT_A::MPAL
+    }
+    #[doc = "Checks if the value of the field is `DEBUG`"]
+    #[inline(always)]
+    pub fn is_debug(&self) -> bool {
+        **self == FORMAT_A::DEBUG
+    }
+}
+impl core::ops::Deref for FORMAT_R {
+    type Target = crate::FieldReader<u8, FORMAT_A>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `format` writer - "]
+pub struct FORMAT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> FORMAT_W<'a> {
+    #[doc = r"Writes `variant` to the field"]
+    #[inline(always)]
+    pub fn variant(self, variant: FORMAT_A) -> &'a mut W {
+        self.bits(variant.into())
+    }
+    #[doc = "`0`"]
+    #[inline(always)]
+    pub fn ntsc(self) -> &'a mut W {
+        self.variant(FORMAT_A::NTSC)
+    }
+    #[doc = "`1`"]
+    #[inline(always)]
+    pub fn pal(self) -> &'a mut W {
+        self.variant(FORMAT_A::PAL)
+    }
+    #[doc = "`10`"]
+    #[inline(always)]
+    pub fn mpal(self) -> &'a mut W {
+        self.variant(FORMAT_A::MPAL)
+    }
+    #[doc = "`11`"]
+    #[inline(always)]
+    pub fn debug(self) -> &'a mut W {
+        self.variant(FORMAT_A::DEBUG)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x03 << 8)) | ((value as u16 & 0x03) << 8);
+        self.w
+    }
+}
+#[doc = ""]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(u8)]
+pub enum LATCH_1_ENABLE_A {
+    #[doc = "0: `0`"]
+    OFF = 0,
+    #[doc = "1: `1`"]
+    ON_FOR_ONE_FIELD = 1,
+    #[doc = "2: `10`"]
+    ON_FOR_TWO_FIELDS = 2,
+    #[doc = "3: `11`"]
+    ALWAYS_ON = 3,
+}
+impl From<LATCH_1_ENABLE_A> for u8 {
+    #[inline(always)]
+    fn from(variant: LATCH_1_ENABLE_A) -> Self {
+        variant as _
+    }
+}
+#[doc = "Field `latch_1_enable` reader - "]
+pub struct LATCH_1_ENABLE_R(crate::FieldReader<u8, LATCH_1_ENABLE_A>);
+impl LATCH_1_ENABLE_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        LATCH_1_ENABLE_R(crate::FieldReader::new(bits))
+    }
+    #[doc = r"Get enumerated values variant"]
+    #[inline(always)]
+    pub fn variant(&self) -> LATCH_1_ENABLE_A {
+        match self.bits {
+            0 => LATCH_1_ENABLE_A::OFF,
+            1 => LATCH_1_ENABLE_A::ON_FOR_ONE_FIELD,
+            2 => LATCH_1_ENABLE_A::ON_FOR_TWO_FIELDS,
+            3 => LATCH_1_ENABLE_A::ALWAYS_ON,
+            _ => unreachable!(),
+        }
+    }
+    #[doc = "Checks if the value of the field is `OFF`"]
+    #[inline(always)]
+    pub fn is_off(&self) -> bool {
+        **self == LATCH_1_ENABLE_A::OFF
+    }
+    #[doc = "Checks if the value of the field is `ON_FOR_ONE_FIELD`"]
+    #[inline(always)]
+    pub fn is_on_for_one_field(&self) -> bool {
+        **self == LATCH_1_ENABLE_A::ON_FOR_ONE_FIELD
+    }
+    #[doc = "Checks if the value of the field is `ON_FOR_TWO_FIELDS`"]
+    #[inline(always)]
+    pub fn is_on_for_two_fields(&self) -> bool {
+        **self == LATCH_1_ENABLE_A::ON_FOR_TWO_FIELDS
+    }
+    #[doc = "Checks if the value of the field is `ALWAYS_ON`"]
+    #[inline(always)]
+    pub fn is_always_on(&self) -> bool {
+        **self == LATCH_1_ENABLE_A::ALWAYS_ON
+    }
+}
+impl core::ops::Deref for LATCH_1_ENABLE_R {
+    type Target = crate::FieldReader<u8, LATCH_1_ENABLE_A>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `latch_1_enable` writer - "]
+pub struct LATCH_1_ENABLE_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> LATCH_1_ENABLE_W<'a> {
+    #[doc = r"Writes `variant` to the field"]
+    #[inline(always)]
+    pub fn variant(self, variant: LATCH_1_ENABLE_A) -> &'a mut W {
+        self.bits(variant.into())
+    }
+    #[doc = "`0`"]
+    #[inline(always)]
+    pub fn off(self) -> &'a mut W {
+        self.variant(LATCH_1_ENABLE_A::OFF)
+    }
+    #[doc = "`1`"]
+    #[inline(always)]
+    pub fn on_for_one_field(self) -> &'a mut W {
+        self.variant(LATCH_1_ENABLE_A::ON_FOR_ONE_FIELD)
+    }
+    #[doc = "`10`"]
+    #[inline(always)]
+    pub fn on_for_two_fields(self) -> &'a mut W {
+        self.variant(LATCH_1_ENABLE_A::ON_FOR_TWO_FIELDS)
+    }
+    #[doc = "`11`"]
+    #[inline(always)]
+    pub fn always_on(self) -> &'a mut W {
+        self.variant(LATCH_1_ENABLE_A::ALWAYS_ON)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x03 << 6)) | ((value as u16 & 0x03) << 6);
+        self.w
+    }
+}
+#[doc = ""]
+pub type LATCH_0_ENABLE_A = LATCH_1_ENABLE_A;
+#[doc = "Field `latch_0_enable` reader - "]
+pub type LATCH_0_ENABLE_R = LATCH_1_ENABLE_R;
+#[doc = "Field `latch_0_enable` writer - "]
+pub struct LATCH_0_ENABLE_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> LATCH_0_ENABLE_W<'a> {
+    #[doc = r"Writes `variant` to the field"]
+    #[inline(always)]
+    pub fn variant(self, variant: LATCH_0_ENABLE_A) -> &'a mut W {
+        self.bits(variant.into())
+    }
+    #[doc = "`0`"]
+    #[inline(always)]
+    pub fn off(self) -> &'a mut W {
+        self.variant(LATCH_0_ENABLE_A::OFF)
+    }
+    #[doc = "`1`"]
+    #[inline(always)]
+    pub fn on_for_one_field(self) -> &'a mut W {
+        self.variant(LATCH_0_ENABLE_A::ON_FOR_ONE_FIELD)
+    }
+    #[doc = "`10`"]
+    #[inline(always)]
+    pub fn on_for_two_fields(self) -> &'a mut W {
+        self.variant(LATCH_0_ENABLE_A::ON_FOR_TWO_FIELDS)
+    }
+    #[doc = "`11`"]
+    #[inline(always)]
+    pub fn always_on(self) -> &'a mut W {
+        self.variant(LATCH_0_ENABLE_A::ALWAYS_ON)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x03 << 4)) | ((value as u16 & 0x03) << 4);
+        self.w
+    }
+}
+#[doc = "Field `three_d_display` reader - "]
+pub struct THREE_D_DISPLAY_R(crate::FieldReader<bool, bool>);
+impl THREE_D_DISPLAY_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        THREE_D_DISPLAY_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for THREE_D_DISPLAY_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `three_d_display` writer - "]
+pub struct THREE_D_DISPLAY_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> THREE_D_DISPLAY_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 3)) | ((value as u16 & 0x01) << 3);
+        self.w
+    }
+}
+#[doc = ""]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum INTERLACE_A {
+    #[doc = "0: `0`"]
+    INTERLACED = 0,
+    #[doc = "1: `1`"]
+    NON_INTERLACED = 1,
+}
+impl From<INTERLACE_A> for bool {
+    #[inline(always)]
+    fn from(variant: INTERLACE_A) -> Self {
+        variant as u8 != 0
+    }
+}
+#[doc = "Field `interlace` reader - "]
+pub struct INTERLACE_R(crate::FieldReader<bool, INTERLACE_A>);
+impl INTERLACE_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        INTERLACE_R(crate::FieldReader::new(bits))
+    }
+    #[doc = r"Get enumerated values variant"]
+    #[inline(always)]
+    pub fn variant(&self) -> INTERLACE_A {
+        match self.bits {
+            false => INTERLACE_A::INTERLACED,
+            true => INTERLACE_A::NON_INTERLACED,
+        }
+    }
+    #[doc = "Checks if the value of the field is `INTERLACED`"]
+    #[inline(always)]
+    pub fn is_interlaced(&self) -> bool {
+        **self == INTERLACE_A::INTERLACED
+    }
+    #[doc = "Checks if the value of the field is `NON_INTERLACED`"]
+    #[inline(always)]
+    pub fn is_non_interlaced(&self) -> bool {
+        **self == INTERLACE_A::NON_INTERLACED
+    }
+}
+impl core::ops::Deref for INTERLACE_R {
+    type Target = crate::FieldReader<bool, INTERLACE_A>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `interlace` writer - "]
+pub struct INTERLACE_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> INTERLACE_W<'a> {
+    #[doc = r"Writes `variant` to the field"]
+    #[inline(always)]
+    pub fn variant(self, variant: INTERLACE_A) -> &'a mut W {
+        self.bit(variant.into())
+    }
+    #[doc = "`0`"]
+    #[inline(always)]
+    pub fn interlaced(self) -> &'a mut W {
+        self.variant(INTERLACE_A::INTERLACED)
+    }
+    #[doc = "`1`"]
+    #[inline(always)]
+    pub fn non_interlaced(self) -> &'a mut W {
+        self.variant(INTERLACE_A::NON_INTERLACED)
+    }
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 2)) | ((value as u16 & 0x01) << 2);
+        self.w
+    }
+}
+#[doc = "Field `reset` reader - "]
+pub struct RESET_R(crate::FieldReader<bool, bool>);
+impl RESET_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        RESET_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for RESET_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `reset` writer - "]
+pub struct RESET_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> RESET_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 1)) | ((value as u16 & 0x01) << 1);
+        self.w
+    }
+}
+#[doc = "Field `enable` reader - "]
+pub struct ENABLE_R(crate::FieldReader<bool, bool>);
+impl ENABLE_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        ENABLE_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for ENABLE_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `enable` writer - "]
+pub struct ENABLE_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> ENABLE_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x01) | (value as u16 & 0x01);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bits 8:9"]
+    #[inline(always)]
+    pub fn format(&self) -> FORMAT_R {
+        FORMAT_R::new(((self.bits >> 8) & 0x03) as u8)
+    }
+    #[doc = "Bits 6:7"]
+    #[inline(always)]
+    pub fn latch_1_enable(&self) -> LATCH_1_ENABLE_R {
+        LATCH_1_ENABLE_R::new(((self.bits >> 6) & 0x03) as u8)
+    }
+    #[doc = "Bits 4:5"]
+    #[inline(always)]
+    pub fn latch_0_enable(&self) -> LATCH_0_ENABLE_R {
+        LATCH_0_ENABLE_R::new(((self.bits >> 4) & 0x03) as u8)
+    }
+    #[doc = "Bit 3"]
+    #[inline(always)]
+    pub fn three_d_display(&self) -> THREE_D_DISPLAY_R {
+        THREE_D_DISPLAY_R::new(((self.bits >> 3) & 0x01) != 0)
+    }
+    #[doc = "Bit 2"]
+    #[inline(always)]
+    pub fn interlace(&self) -> INTERLACE_R {
+        INTERLACE_R::new(((self.bits >> 2) & 0x01) != 0)
+    }
+    #[doc = "Bit 1"]
+    #[inline(always)]
+    pub fn reset(&self) -> RESET_R {
+        RESET_R::new(((self.bits >> 1) & 0x01) != 0)
+    }
+    #[doc = "Bit 0"]
+    #[inline(always)]
+    pub fn enable(&self) -> ENABLE_R {
+        ENABLE_R::new((self.bits & 0x01) != 0)
+    }
+}
+impl W {
+    #[doc = "Bits 8:9"]
+    #[inline(always)]
+    pub fn format(&mut self) -> FORMAT_W {
+        FORMAT_W { w: self }
+    }
+    #[doc = "Bits 6:7"]
+    #[inline(always)]
+    pub fn latch_1_enable(&mut self) -> LATCH_1_ENABLE_W {
+        LATCH_1_ENABLE_W { w: self }
+    }
+    #[doc = "Bits 4:5"]
+    #[inline(always)]
+    pub fn latch_0_enable(&mut self) -> LATCH_0_ENABLE_W {
+        LATCH_0_ENABLE_W { w: self }
+    }
+    #[doc = "Bit 3"]
+    #[inline(always)]
+    pub fn three_d_display(&mut self) -> THREE_D_DISPLAY_W {
+        THREE_D_DISPLAY_W { w: self }
+    }
+    #[doc = "Bit 2"]
+    #[inline(always)]
+    pub fn interlace(&mut self) -> INTERLACE_W {
+        INTERLACE_W { w: self }
+    }
+    #[doc = "Bit 1"]
+    #[inline(always)]
+    pub fn reset(&mut self) -> RESET_W {
+        RESET_W { w: self }
+    }
+    #[doc = "Bit 0"]
+    #[inline(always)]
+    pub fn enable(&mut self) -> ENABLE_W {
+        ENABLE_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u16) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [display_configuration](index.html) module"]
+pub struct DISPLAY_CONFIGURATION_SPEC;
+impl crate::RegisterSpec for DISPLAY_CONFIGURATION_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [display_configuration::R](R) reader structure"]
+impl crate::Readable for DISPLAY_CONFIGURATION_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [display_configuration::W](W) writer structure"]
+impl crate::Writable for DISPLAY_CONFIGURATION_SPEC {
+    type Writer = W;
+}
@@ -0,0 +1,227 @@
+#[doc = "Register `display_interrupt[%s]` reader"]
+pub struct R(crate::R<DISPLAY_INTERRUPT_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<DISPLAY_INTERRUPT_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<DISPLAY_INTERRUPT_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<DISPLAY_INTERRUPT_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `display_interrupt[%s]` writer"]
+pub struct W(crate::W<DISPLAY_INTERRUPT_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<DISPLAY_INTERRUPT_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<DISPLAY_INTERRUPT_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<DISPLAY_INTERRUPT_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `interrupt_status` reader - "]
+pub struct INTERRUPT_STATUS_R(crate::FieldReader<bool, bool>);
+impl INTERRUPT_STATUS_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        INTERRUPT_STATUS_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for INTERRUPT_STATUS_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `interrupt_status` writer - "]
+pub struct INTERRUPT_STATUS_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> INTERRUPT_STATUS_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 31)) | ((value as u32 & 0x01) << 31);
+        self.w
+    }
+}
+#[doc = "Field `interrupt_enable` reader - "]
+pub struct INTERRUPT_ENABLE_R(crate::FieldReader<bool, bool>);
+impl INTERRUPT_ENABLE_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        INTERRUPT_ENABLE_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for INTERRUPT_ENABLE_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `interrupt_enable` writer - "]
+pub struct INTERRUPT_ENABLE_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> INTERRUPT_ENABLE_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 28)) | ((value as u32 & 0x01) << 28);
+        self.w
+    }
+}
+#[doc = "Field `vertical_position` reader - "]
+pub struct VERTICAL_POSITION_R(crate::FieldReader<u16, u16>);
+impl VERTICAL_POSITION_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u16) -> Self {
+        VERTICAL_POSITION_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for VERTICAL_POSITION_R {
+    type Target = crate::FieldReader<u16, u16>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `vertical_position` writer - "]
+pub struct VERTICAL_POSITION_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> VERTICAL_POSITION_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u16) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x07ff << 16)) | ((value as u32 & 0x07ff) << 16);
+        self.w
+    }
+}
+#[doc = "Field `horizontal_position` reader - "]
+pub struct HORIZONTAL_POSITION_R(crate::FieldReader<u16, u16>);
+impl HORIZONTAL_POSITION_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u16) -> Self {
+        HORIZONTAL_POSITION_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for HORIZONTAL_POSITION_R {
+    type Target = crate::FieldReader<u16, u16>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `horizontal_position` writer - "]
+pub struct HORIZONTAL_POSITION_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> HORIZONTAL_POSITION_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u16) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x07ff) | (value as u32 & 0x07ff);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bit 31"]
+    #[inline(always)]
+    pub fn interrupt_status(&self) -> INTERRUPT_STATUS_R {
+        INTERRUPT_STATUS_R::new(((self.bits >> 31) & 0x01) != 0)
+    }
+    #[doc = "Bit 28"]
+    #[inline(always)]
+    pub fn interrupt_enable(&self) -> INTERRUPT_ENABLE_R {
+        INTERRUPT_ENABLE_R::new(((self.bits >> 28) & 0x01) != 0)
+    }
+    #[doc = "Bits 16:26"]
+    #[inline(always)]
+    pub fn vertical_position(&self) -> VERTICAL_POSITION_R {
+        VERTICAL_POSITION_R::new(((self.bits >> 16) & 0x07ff) as u16)
+    }
+    #[doc = "Bits 0:10"]
+    #[inline(always)]
+    pub fn horizontal_position(&self) -> HORIZONTAL_POSITION_R {
+        HORIZONTAL_POSITION_R::new((self.bits & 0x07ff) as u16)
+    }
+}
+impl W {
+    #[doc = "Bit 31"]
+    #[inline(always)]
+    pub fn interrupt_status(&mut self) -> INTERRUPT_STATUS_W {
+        INTERRUPT_STATUS_W { w: self }
+    }
+    #[doc = "Bit 28"]
+    #[inline(always)]
+    pub fn interrupt_enable(&mut self) -> INTERRUPT_ENABLE_W {
+        INTERRUPT_ENABLE_W { w: self }
+    }
+    #[doc = "Bits 16:26"]
+    #[inline(always)]
+    pub fn vertical_position(&mut self) -> VERTICAL_POSITION_W {
+        VERTICAL_POSITION_W { w: self }
+    }
+    #[doc = "Bits 0:10"]
+    #[inline(always)]
+    pub fn horizontal_position(&mut self) -> HORIZONTAL_POSITION_W {
+        HORIZONTAL_POSITION_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [display_interrupt](index.html) module"]
+pub struct DISPLAY_INTERRUPT_SPEC;
+impl crate::RegisterSpec for DISPLAY_INTERRUPT_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [display_interrupt::R](R) reader structure"]
+impl crate::Readable for DISPLAY_INTERRUPT_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [display_interrupt::W](W) writer structure"]
+impl crate::Writable for DISPLAY_INTERRUPT_SPEC {
+    type Writer = W;
+}
@@ -0,0 +1,180 @@
+#[doc = "Register `display_latch[%s]` reader"]
+pub struct R(crate::R<DISPLAY_LATCH_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<DISPLAY_LATCH_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<DISPLAY_LATCH_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<DISPLAY_LATCH_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `display_latch[%s]` writer"]
+pub struct W(crate::W<DISPLAY_LATCH_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<DISPLAY_LATCH_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<DISPLAY_LATCH_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<DISPLAY_LATCH_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `trigger` reader - "]
+pub struct TRIGGER_R(crate::FieldReader<bool, bool>);
+impl TRIGGER_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        TRIGGER_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for TRIGGER_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `trigger` writer - "]
+pub struct TRIGGER_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> TRIGGER_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 31)) | ((value as u32 & 0x01) << 31);
+        self.w
+    }
+}
+#[doc = "Field `vertical_count` reader - "]
+pub struct VERTICAL_COUNT_R(crate::FieldReader<u16, u16>);
+impl VERTICAL_COUNT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u16) -> Self {
+        VERTICAL_COUNT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for VERTICAL_COUNT_R {
+    type Target = crate::FieldReader<u16, u16>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `vertical_count` writer - "]
+pub struct VERTICAL_COUNT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> VERTICAL_COUNT_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u16) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x07ff << 16)) | ((value as u32 & 0x07ff) << 16);
+        self.w
+    }
+}
+#[doc = "Field `horizontal_count` reader - "]
+pub struct HORIZONTAL_COUNT_R(crate::FieldReader<u16, u16>);
+impl HORIZONTAL_COUNT_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u16) -> Self {
+        HORIZONTAL_COUNT_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for HORIZONTAL_COUNT_R {
+    type Target = crate::FieldReader<u16, u16>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `horizontal_count` writer - "]
+pub struct HORIZONTAL_COUNT_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> HORIZONTAL_COUNT_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u16) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x07ff) | (value as u32 & 0x07ff);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bit 31"]
+    #[inline(always)]
+    pub fn trigger(&self) -> TRIGGER_R {
+        TRIGGER_R::new(((self.bits >> 31) & 0x01) != 0)
+    }
+    #[doc = "Bits 16:26"]
+    #[inline(always)]
+    pub fn vertical_count(&self) -> VERTICAL_COUNT_R {
+        VERTICAL_COUNT_R::new(((self.bits >> 16) & 0x07ff) as u16)
+    }
+    #[doc = "Bits 0:10"]
+    #[inline(always)]
+    pub fn horizontal_count(&self) -> HORIZONTAL_COUNT_R {
+        HORIZONTAL_COUNT_R::new((self.bits & 0x07ff) as u16)
+    }
+}
+impl W {
+    #[doc = "Bit 31"]
+    #[inline(always)]
+    pub fn trigger(&mut self) -> TRIGGER_W {
+        TRIGGER_W { w: self }
+    }
+    #[doc = "Bits 16:26"]
+    #[inline(always)]
+    pub fn vertical_count(&mut self) -> VERTICAL_COUNT_W {
+        VERTICAL_COUNT_W { w: self }
+    }
+    #[doc = "Bits 0:10"]
+    #[inline(always)]
+    pub fn horizontal_count(&mut self) -> HORIZONTAL_COUNT_W {
+        HORIZONTAL_COUNT_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [display_latch](index.html) module"]
+pub struct DISPLAY_LATCH_SPEC;
+impl crate::RegisterSpec for DISPLAY_LATCH_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [display_latch::R](R) reader structure"]
+impl crate::Readable for DISPLAY_LATCH_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [display_latch::W](W) writer structure"]
+impl crate::Writable for DISPLAY_LATCH_SPEC {
+    type Writer = W;
+}
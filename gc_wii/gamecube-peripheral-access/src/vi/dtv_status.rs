@@ -0,0 +1,46 @@
+#[doc = "Register `dtv_status` reader"]
+pub struct R(crate::R<DTV_STATUS_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<DTV_STATUS_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<DTV_STATUS_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<DTV_STATUS_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Field `component_cable` reader - "]
+pub struct COMPONENT_CABLE_R(crate::FieldReader<bool, bool>);
+impl COMPONENT_CABLE_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        COMPONENT_CABLE_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for COMPONENT_CABLE_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl R {
+    #[doc = "Bit 0"]
+    #[inline(always)]
+    pub fn component_cable(&self) -> COMPONENT_CABLE_R {
+        COMPONENT_CABLE_R::new((self.bits & 0x01) != 0)
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [dtv_status](index.html) module"]
+pub struct DTV_STATUS_SPEC;
+impl crate::RegisterSpec for DTV_STATUS_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [dtv_status::R](R) reader structure"]
+impl crate::Readable for DTV_STATUS_SPEC {
+    type Reader = R;
+}
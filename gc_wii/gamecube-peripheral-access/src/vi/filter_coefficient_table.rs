@@ -0,0 +1,57 @@
+#[doc = "Register `filter_coefficient_table[%s]` reader"]
+pub struct R(crate::R<FILTER_COEFFICIENT_TABLE_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<FILTER_COEFFICIENT_TABLE_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<FILTER_COEFFICIENT_TABLE_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<FILTER_COEFFICIENT_TABLE_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `filter_coefficient_table[%s]` writer"]
+pub struct W(crate::W<FILTER_COEFFICIENT_TABLE_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<FILTER_COEFFICIENT_TABLE_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<FILTER_COEFFICIENT_TABLE_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<FILTER_COEFFICIENT_TABLE_SPEC>) -> Self {
+        W(writer)
+    }
+}
+impl W {
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [filter_coefficient_table](index.html) module"]
+pub struct FILTER_COEFFICIENT_TABLE_SPEC;
+impl crate::RegisterSpec for FILTER_COEFFICIENT_TABLE_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [filter_coefficient_table::R](R) reader structure"]
+impl crate::Readable for FILTER_COEFFICIENT_TABLE_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [filter_coefficient_table::W](W) writer structure"]
+impl crate::Writable for FILTER_COEFFICIENT_TABLE_SPEC {
+    type Writer = W;
+}
@@ -0,0 +1,24 @@
+#[doc = "Register `horizontal_position` reader"]
+pub struct R(crate::R<HORIZONTAL_POSITION_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<HORIZONTAL_POSITION_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<HORIZONTAL_POSITION_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<HORIZONTAL_POSITION_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [horizontal_position](index.html) module"]
+pub struct HORIZONTAL_POSITION_SPEC;
+impl crate::RegisterSpec for HORIZONTAL_POSITION_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [horizontal_position::R](R) reader structure"]
+impl crate::Readable for HORIZONTAL_POSITION_SPEC {
+    type Reader = R;
+}
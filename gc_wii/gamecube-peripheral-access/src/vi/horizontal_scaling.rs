@@ -0,0 +1,219 @@
+#[doc = "Register `horizontal_scaling` reader"]
+pub struct R(crate::R<HORIZONTAL_SCALING_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<HORIZONTAL_SCALING_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<HORIZONTAL_SCALING_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<HORIZONTAL_SCALING_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `horizontal_scaling` writer"]
+pub struct W(crate::W<HORIZONTAL_SCALING_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<HORIZONTAL_SCALING_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<HORIZONTAL_SCALING_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<HORIZONTAL_SCALING_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `framebuffer_width_in_16_pixel_units` reader - "]
+pub struct FRAMEBUFFER_WIDTH_IN_16_PIXEL_UNITS_R(crate::FieldReader<u8, u8>);
+impl FRAMEBUFFER_WIDTH_IN_16_PIXEL_UNITS_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        FRAMEBUFFER_WIDTH_IN_16_PIXEL_UNITS_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for FRAMEBUFFER_WIDTH_IN_16_PIXEL_UNITS_R {
+    type Target = crate::FieldReader<u8, u8>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `framebuffer_width_in_16_pixel_units` writer - "]
+pub struct FRAMEBUFFER_WIDTH_IN_16_PIXEL_UNITS_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> FRAMEBUFFER_WIDTH_IN_16_PIXEL_UNITS_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x7f << 24)) | ((value as u32 & 0x7f) << 24);
+        self.w
+    }
+}
+#[doc = "Field `stride_per_half_line_in_16_byte_units` reader - "]
+pub struct STRIDE_PER_HALF_LINE_IN_16_BYTE_UNITS_R(crate::FieldReader<u8, u8>);
+impl STRIDE_PER_HALF_LINE_IN_16_BYTE_UNITS_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        STRIDE_PER_HALF_LINE_IN_16_BYTE_UNITS_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for STRIDE_PER_HALF_LINE_IN_16_BYTE_UNITS_R {
+    type Target = crate::FieldReader<u8, u8>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `stride_per_half_line_in_16_byte_units` writer - "]
+pub struct STRIDE_PER_HALF_LINE_IN_16_BYTE_UNITS_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> STRIDE_PER_HALF_LINE_IN_16_BYTE_UNITS_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0xff << 16)) | ((value as u32 & 0xff) << 16);
+        self.w
+    }
+}
+#[doc = "Field `enable` reader - "]
+pub struct ENABLE_R(crate::FieldReader<bool, bool>);
+impl ENABLE_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        ENABLE_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for ENABLE_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `enable` writer - "]
+pub struct ENABLE_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> ENABLE_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 12)) | ((value as u32 & 0x01) << 12);
+        self.w
+    }
+}
+#[doc = "Field `step_size_u1_8` reader - "]
+pub struct STEP_SIZE_U1_8_R(crate::FieldReader<u16, u16>);
+impl STEP_SIZE_U1_8_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u16) -> Self {
+        STEP_SIZE_U1_8_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for STEP_SIZE_U1_8_R {
+    type Target = crate::FieldReader<u16, u16>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `step_size_u1_8` writer - "]
+pub struct STEP_SIZE_U1_8_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> STEP_SIZE_U1_8_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u16) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x01ff) | (value as u32 & 0x01ff);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bits 24:30"]
+    #[inline(always)]
+    pub fn framebuffer_width_in_16_pixel_units(&self) -> FRAMEBUFFER_WIDTH_IN_16_PIXEL_UNITS_R {
+        FRAMEBUFFER_WIDTH_IN_16_PIXEL_UNITS_R::new(((self.bits >> 24) & 0x7f) as u8)
+    }
+    #[doc = "Bits 16:23"]
+    #[inline(always)]
+    pub fn stride_per_half_line_in_16_byte_units(&self) -> STRIDE_PER_HALF_LINE_IN_16_BYTE_UNITS_R {
+        STRIDE_PER_HALF_LINE_IN_16_BYTE_UNITS_R::new(((self.bits >> 16) & 0xff) as u8)
+    }
+    #[doc = "Bit 12"]
+    #[inline(always)]
+    pub fn enable(&self) -> ENABLE_R {
+        ENABLE_R::new(((self.bits >> 12) & 0x01) != 0)
+    }
+    #[doc = "Bits 0:8"]
+    #[inline(always)]
+    pub fn step_size_u1_8(&self) -> STEP_SIZE_U1_8_R {
+        STEP_SIZE_U1_8_R::new((self.bits & 0x01ff) as u16)
+    }
+}
+impl W {
+    #[doc = "Bits 24:30"]
+    #[inline(always)]
+    pub fn framebuffer_width_in_16_pixel_units(&mut self) -> FRAMEBUFFER_WIDTH_IN_16_PIXEL_UNITS_W {
+        FRAMEBUFFER_WIDTH_IN_16_PIXEL_UNITS_W { w: self }
+    }
+    #[doc = "Bits 16:23"]
+    #[inline(always)]
+    pub fn stride_per_half_line_in_16_byte_units(
+        &mut self,
+    ) -> STRIDE_PER_HALF_LINE_IN_16_BYTE_UNITS_W {
+        STRIDE_PER_HALF_LINE_IN_16_BYTE_UNITS_W { w: self }
+    }
+    #[doc = "Bit 12"]
+    #[inline(always)]
+    pub fn enable(&mut self) -> ENABLE_W {
+        ENABLE_W { w: self }
+    }
+    #[doc = "Bits 0:8"]
+    #[inline(always)]
+    pub fn step_size_u1_8(&mut self) -> STEP_SIZE_U1_8_W {
+        STEP_SIZE_U1_8_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [horizontal_scaling](index.html) module"]
+pub struct HORIZONTAL_SCALING_SPEC;
+impl crate::RegisterSpec for HORIZONTAL_SCALING_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [horizontal_scaling::R](R) reader structure"]
+impl crate::Readable for HORIZONTAL_SCALING_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [horizontal_scaling::W](W) writer structure"]
+impl crate::Writable for HORIZONTAL_SCALING_SPEC {
+    type Writer = W;
+}
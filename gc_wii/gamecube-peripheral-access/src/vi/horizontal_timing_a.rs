@@ -0,0 +1,170 @@
+#[doc = "Register `horizontal_timing_a` reader"]
+pub struct R(crate::R<HORIZONTAL_TIMING_A_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<HORIZONTAL_TIMING_A_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<HORIZONTAL_TIMING_A_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<HORIZONTAL_TIMING_A_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `horizontal_timing_a` writer"]
+pub struct W(crate::W<HORIZONTAL_TIMING_A_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<HORIZONTAL_TIMING_A_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<HORIZONTAL_TIMING_A_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<HORIZONTAL_TIMING_A_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `hsync_start_to_color_burst_start` reader - "]
+pub struct HSYNC_START_TO_COLOR_BURST_START_R(crate::FieldReader<u8, u8>);
+impl HSYNC_START_TO_COLOR_BURST_START_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        HSYNC_START_TO_COLOR_BURST_START_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for HSYNC_START_TO_COLOR_BURST_START_R {
+    type Target = crate::FieldReader<u8, u8>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `hsync_start_to_color_burst_start` writer - "]
+pub struct HSYNC_START_TO_COLOR_BURST_START_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> HSYNC_START_TO_COLOR_BURST_START_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x7f << 24)) | ((value as u32 & 0x7f) << 24);
+        self.w
+    }
+}
+#[doc = "Field `hsync_start_to_color_burst_end` reader - "]
+pub struct HSYNC_START_TO_COLOR_BURST_END_R(crate::FieldReader<u8, u8>);
+impl HSYNC_START_TO_COLOR_BURST_END_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        HSYNC_START_TO_COLOR_BURST_END_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for HSYNC_START_TO_COLOR_BURST_END_R {
+    type Target = crate::FieldReader<u8, u8>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `hsync_start_to_color_burst_end` writer - "]
+pub struct HSYNC_START_TO_COLOR_BURST_END_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> HSYNC_START_TO_COLOR_BURST_END_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x7f << 16)) | ((value as u32 & 0x7f) << 16);
+        self.w
+    }
+}
+#[doc = "Field `halfline_width` reader - "]
+pub struct HALFLINE_WIDTH_R(crate::FieldReader<u16, u16>);
+impl HALFLINE_WIDTH_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u16) -> Self {
+        HALFLINE_WIDTH_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for HALFLINE_WIDTH_R {
+    type Target = crate::FieldReader<u16, u16>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `halfline_width` writer - "]
+pub struct HALFLINE_WIDTH_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> HALFLINE_WIDTH_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u16) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x01ff) | (value as u32 & 0x01ff);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bits 24:30"]
+    #[inline(always)]
+    pub fn hsync_start_to_color_burst_start(&self) -> HSYNC_START_TO_COLOR_BURST_START_R {
+        HSYNC_START_TO_COLOR_BURST_START_R::new(((self.bits >> 24) & 0x7f) as u8)
+    }
+    #[doc = "Bits 16:22"]
+    #[inline(always)]
+    pub fn hsync_start_to_color_burst_end(&self) -> HSYNC_START_TO_COLOR_BURST_END_R {
+        HSYNC_START_TO_COLOR_BURST_END_R::new(((self.bits >> 16) & 0x7f) as u8)
+    }
+    #[doc = "Bits 0:8"]
+    #[inline(always)]
+    pub fn halfline_width(&self) -> HALFLINE_WIDTH_R {
+        HALFLINE_WIDTH_R::new((self.bits & 0x01ff) as u16)
+    }
+}
+impl W {
+    #[doc = "Bits 24:30"]
+    #[inline(always)]
+    pub fn hsync_start_to_color_burst_start(&mut self) -> HSYNC_START_TO_COLOR_BURST_START_W {
+        HSYNC_START_TO_COLOR_BURST_START_W { w: self }
+    }
+    #[doc = "Bits 16:22"]
+    #[inline(always)]
+    pub fn hsync_start_to_color_burst_end(&mut self) -> HSYNC_START_TO_COLOR_BURST_END_W {
+        HSYNC_START_TO_COLOR_BURST_END_W { w: self }
+    }
+    #[doc = "Bits 0:8"]
+    #[inline(always)]
+    pub fn halfline_width(&mut self) -> HALFLINE_WIDTH_W {
+        HALFLINE_WIDTH_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [horizontal_timing_a](index.html) module"]
+pub struct HORIZONTAL_TIMING_A_SPEC;
+impl crate::RegisterSpec for HORIZONTAL_TIMING_A_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [horizontal_timing_a::R](R) reader structure"]
+impl crate::Readable for HORIZONTAL_TIMING_A_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [horizontal_timing_a::W](W) writer structure"]
+impl crate::Writable for HORIZONTAL_TIMING_A_SPEC {
+    type Writer = W;
+}
@@ -0,0 +1,170 @@
+#[doc = "Register `horizontal_timing_b` reader"]
+pub struct R(crate::R<HORIZONTAL_TIMING_B_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<HORIZONTAL_TIMING_B_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<HORIZONTAL_TIMING_B_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<HORIZONTAL_TIMING_B_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `horizontal_timing_b` writer"]
+pub struct W(crate::W<HORIZONTAL_TIMING_B_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<HORIZONTAL_TIMING_B_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<HORIZONTAL_TIMING_B_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<HORIZONTAL_TIMING_B_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `half_line_to_hblank_start` reader - "]
+pub struct HALF_LINE_TO_HBLANK_START_R(crate::FieldReader<u16, u16>);
+impl HALF_LINE_TO_HBLANK_START_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u16) -> Self {
+        HALF_LINE_TO_HBLANK_START_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for HALF_LINE_TO_HBLANK_START_R {
+    type Target = crate::FieldReader<u16, u16>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `half_line_to_hblank_start` writer - "]
+pub struct HALF_LINE_TO_HBLANK_START_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> HALF_LINE_TO_HBLANK_START_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u16) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x03ff << 17)) | ((value as u32 & 0x03ff) << 17);
+        self.w
+    }
+}
+#[doc = "Field `hsync_start_to_hblank_end` reader - "]
+pub struct HSYNC_START_TO_HBLANK_END_R(crate::FieldReader<u16, u16>);
+impl HSYNC_START_TO_HBLANK_END_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u16) -> Self {
+        HSYNC_START_TO_HBLANK_END_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for HSYNC_START_TO_HBLANK_END_R {
+    type Target = crate::FieldReader<u16, u16>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `hsync_start_to_hblank_end` writer - "]
+pub struct HSYNC_START_TO_HBLANK_END_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> HSYNC_START_TO_HBLANK_END_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u16) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x03ff << 7)) | ((value as u32 & 0x03ff) << 7);
+        self.w
+    }
+}
+#[doc = "Field `hsync_width` reader - "]
+pub struct HSYNC_WIDTH_R(crate::FieldReader<u8, u8>);
+impl HSYNC_WIDTH_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        HSYNC_WIDTH_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for HSYNC_WIDTH_R {
+    type Target = crate::FieldReader<u8, u8>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `hsync_width` writer - "]
+pub struct HSYNC_WIDTH_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> HSYNC_WIDTH_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x7f) | (value as u32 & 0x7f);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bits 17:26"]
+    #[inline(always)]
+    pub fn half_line_to_hblank_start(&self) -> HALF_LINE_TO_HBLANK_START_R {
+        HALF_LINE_TO_HBLANK_START_R::new(((self.bits >> 17) & 0x03ff) as u16)
+    }
+    #[doc = "Bits 7:16"]
+    #[inline(always)]
+    pub fn hsync_start_to_hblank_end(&self) -> HSYNC_START_TO_HBLANK_END_R {
+        HSYNC_START_TO_HBLANK_END_R::new(((self.bits >> 7) & 0x03ff) as u16)
+    }
+    #[doc = "Bits 0:6"]
+    #[inline(always)]
+    pub fn hsync_width(&self) -> HSYNC_WIDTH_R {
+        HSYNC_WIDTH_R::new((self.bits & 0x7f) as u8)
+    }
+}
+impl W {
+    #[doc = "Bits 17:26"]
+    #[inline(always)]
+    pub fn half_line_to_hblank_start(&mut self) -> HALF_LINE_TO_HBLANK_START_W {
+        HALF_LINE_TO_HBLANK_START_W { w: self }
+    }
+    #[doc = "Bits 7:16"]
+    #[inline(always)]
+    pub fn hsync_start_to_hblank_end(&mut self) -> HSYNC_START_TO_HBLANK_END_W {
+        HSYNC_START_TO_HBLANK_END_W { w: self }
+    }
+    #[doc = "Bits 0:6"]
+    #[inline(always)]
+    pub fn hsync_width(&mut self) -> HSYNC_WIDTH_W {
+        HSYNC_WIDTH_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [horizontal_timing_b](index.html) module"]
+pub struct HORIZONTAL_TIMING_B_SPEC;
+impl crate::RegisterSpec for HORIZONTAL_TIMING_B_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [horizontal_timing_b::R](R) reader structure"]
+impl crate::Readable for HORIZONTAL_TIMING_B_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [horizontal_timing_b::W](W) writer structure"]
+impl crate::Writable for HORIZONTAL_TIMING_B_SPEC {
+    type Writer = W;
+}
@@ -0,0 +1,180 @@
+#[doc = "Register `top_left_field_base` reader"]
+pub struct R(crate::R<TOP_LEFT_FIELD_BASE_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<TOP_LEFT_FIELD_BASE_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<TOP_LEFT_FIELD_BASE_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<TOP_LEFT_FIELD_BASE_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `top_left_field_base` writer"]
+pub struct W(crate::W<TOP_LEFT_FIELD_BASE_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<TOP_LEFT_FIELD_BASE_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<TOP_LEFT_FIELD_BASE_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<TOP_LEFT_FIELD_BASE_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `shift_address_left_five` reader - "]
+pub struct SHIFT_ADDRESS_LEFT_FIVE_R(crate::FieldReader<bool, bool>);
+impl SHIFT_ADDRESS_LEFT_FIVE_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: bool) -> Self {
+        SHIFT_ADDRESS_LEFT_FIVE_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for SHIFT_ADDRESS_LEFT_FIVE_R {
+    type Target = crate::FieldReader<bool, bool>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `shift_address_left_five` writer - "]
+pub struct SHIFT_ADDRESS_LEFT_FIVE_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> SHIFT_ADDRESS_LEFT_FIVE_W<'a> {
+    #[doc = r"Sets the field bit"]
+    #[inline(always)]
+    pub fn set_bit(self) -> &'a mut W {
+        self.bit(true)
+    }
+    #[doc = r"Clears the field bit"]
+    #[inline(always)]
+    pub fn clear_bit(self) -> &'a mut W {
+        self.bit(false)
+    }
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub fn bit(self, value: bool) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x01 << 28)) | ((value as u32 & 0x01) << 28);
+        self.w
+    }
+}
+#[doc = "Field `horizontal_offset` reader - "]
+pub struct HORIZONTAL_OFFSET_R(crate::FieldReader<u8, u8>);
+impl HORIZONTAL_OFFSET_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        HORIZONTAL_OFFSET_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for HORIZONTAL_OFFSET_R {
+    type Target = crate::FieldReader<u8, u8>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `horizontal_offset` writer - "]
+pub struct HORIZONTAL_OFFSET_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> HORIZONTAL_OFFSET_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x0f << 24)) | ((value as u32 & 0x0f) << 24);
+        self.w
+    }
+}
+#[doc = "Field `address` reader - "]
+pub struct ADDRESS_R(crate::FieldReader<u32, u32>);
+impl ADDRESS_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u32) -> Self {
+        ADDRESS_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for ADDRESS_R {
+    type Target = crate::FieldReader<u32, u32>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `address` writer - "]
+pub struct ADDRESS_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> ADDRESS_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u32) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x00ff_ffff) | (value as u32 & 0x00ff_ffff);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bit 28"]
+    #[inline(always)]
+    pub fn shift_address_left_five(&self) -> SHIFT_ADDRESS_LEFT_FIVE_R {
+        SHIFT_ADDRESS_LEFT_FIVE_R::new(((self.bits >> 28) & 0x01) != 0)
+    }
+    #[doc = "Bits 24:27"]
+    #[inline(always)]
+    pub fn horizontal_offset(&self) -> HORIZONTAL_OFFSET_R {
+        HORIZONTAL_OFFSET_R::new(((self.bits >> 24) & 0x0f) as u8)
+    }
+    #[doc = "Bits 0:23"]
+    #[inline(always)]
+    pub fn address(&self) -> ADDRESS_R {
+        ADDRESS_R::new((self.bits & 0x00ff_ffff) as u32)
+    }
+}
+impl W {
+    #[doc = "Bit 28"]
+    #[inline(always)]
+    pub fn shift_address_left_five(&mut self) -> SHIFT_ADDRESS_LEFT_FIVE_W {
+        SHIFT_ADDRESS_LEFT_FIVE_W { w: self }
+    }
+    #[doc = "Bits 24:27"]
+    #[inline(always)]
+    pub fn horizontal_offset(&mut self) -> HORIZONTAL_OFFSET_W {
+        HORIZONTAL_OFFSET_W { w: self }
+    }
+    #[doc = "Bits 0:23"]
+    #[inline(always)]
+    pub fn address(&mut self) -> ADDRESS_W {
+        ADDRESS_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [top_left_field_base](index.html) module"]
+pub struct TOP_LEFT_FIELD_BASE_SPEC;
+impl crate::RegisterSpec for TOP_LEFT_FIELD_BASE_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [top_left_field_base::R](R) reader structure"]
+impl crate::Readable for TOP_LEFT_FIELD_BASE_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [top_left_field_base::W](W) writer structure"]
+impl crate::Writable for TOP_LEFT_FIELD_BASE_SPEC {
+    type Writer = W;
+}
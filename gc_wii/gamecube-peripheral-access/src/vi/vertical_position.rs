@@ -0,0 +1,24 @@
+#[doc = "Register `vertical_position` reader"]
+pub struct R(crate::R<VERTICAL_POSITION_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<VERTICAL_POSITION_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<VERTICAL_POSITION_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<VERTICAL_POSITION_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [vertical_position](index.html) module"]
+pub struct VERTICAL_POSITION_SPEC;
+impl crate::RegisterSpec for VERTICAL_POSITION_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [vertical_position::R](R) reader structure"]
+impl crate::Readable for VERTICAL_POSITION_SPEC {
+    type Reader = R;
+}
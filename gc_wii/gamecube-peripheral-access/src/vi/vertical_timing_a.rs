@@ -0,0 +1,133 @@
+#[doc = "Register `vertical_timing_a` reader"]
+pub struct R(crate::R<VERTICAL_TIMING_A_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<VERTICAL_TIMING_A_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<VERTICAL_TIMING_A_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<VERTICAL_TIMING_A_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `vertical_timing_a` writer"]
+pub struct W(crate::W<VERTICAL_TIMING_A_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<VERTICAL_TIMING_A_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<VERTICAL_TIMING_A_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<VERTICAL_TIMING_A_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `active_video_lines` reader - "]
+pub struct ACTIVE_VIDEO_LINES_R(crate::FieldReader<u16, u16>);
+impl ACTIVE_VIDEO_LINES_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u16) -> Self {
+        ACTIVE_VIDEO_LINES_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for ACTIVE_VIDEO_LINES_R {
+    type Target = crate::FieldReader<u16, u16>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `active_video_lines` writer - "]
+pub struct ACTIVE_VIDEO_LINES_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> ACTIVE_VIDEO_LINES_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u16) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x03ff << 4)) | ((value as u16 & 0x03ff) << 4);
+        self.w
+    }
+}
+#[doc = "Field `equalization_pulse_half_lines` reader - "]
+pub struct EQUALIZATION_PULSE_HALF_LINES_R(crate::FieldReader<u8, u8>);
+impl EQUALIZATION_PULSE_HALF_LINES_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u8) -> Self {
+        EQUALIZATION_PULSE_HALF_LINES_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for EQUALIZATION_PULSE_HALF_LINES_R {
+    type Target = crate::FieldReader<u8, u8>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `equalization_pulse_half_lines` writer - "]
+pub struct EQUALIZATION_PULSE_HALF_LINES_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> EQUALIZATION_PULSE_HALF_LINES_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u8) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x0f) | (value as u16 & 0x0f);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bits 4:13"]
+    #[inline(always)]
+    pub fn active_video_lines(&self) -> ACTIVE_VIDEO_LINES_R {
+        ACTIVE_VIDEO_LINES_R::new(((self.bits >> 4) & 0x03ff) as u16)
+    }
+    #[doc = "Bits 0:3"]
+    #[inline(always)]
+    pub fn equalization_pulse_half_lines(&self) -> EQUALIZATION_PULSE_HALF_LINES_R {
+        EQUALIZATION_PULSE_HALF_LINES_R::new((self.bits & 0x0f) as u8)
+    }
+}
+impl W {
+    #[doc = "Bits 4:13"]
+    #[inline(always)]
+    pub fn active_video_lines(&mut self) -> ACTIVE_VIDEO_LINES_W {
+        ACTIVE_VIDEO_LINES_W { w: self }
+    }
+    #[doc = "Bits 0:3"]
+    #[inline(always)]
+    pub fn equalization_pulse_half_lines(&mut self) -> EQUALIZATION_PULSE_HALF_LINES_W {
+        EQUALIZATION_PULSE_HALF_LINES_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u16) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [vertical_timing_a](index.html) module"]
+pub struct VERTICAL_TIMING_A_SPEC;
+impl crate::RegisterSpec for VERTICAL_TIMING_A_SPEC {
+    type Ux = u16;
+}
+#[doc = "`read()` method returns [vertical_timing_a::R](R) reader structure"]
+impl crate::Readable for VERTICAL_TIMING_A_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [vertical_timing_a::W](W) writer structure"]
+impl crate::Writable for VERTICAL_TIMING_A_SPEC {
+    type Writer = W;
+}
@@ -0,0 +1,133 @@
+#[doc = "Register `vertical_timing_b_even_field` reader"]
+pub struct R(crate::R<VERTICAL_TIMING_B_EVEN_FIELD_SPEC>);
+impl core::ops::Deref for R {
+    type Target = crate::R<VERTICAL_TIMING_B_EVEN_FIELD_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl From<crate::R<VERTICAL_TIMING_B_EVEN_FIELD_SPEC>> for R {
+    #[inline(always)]
+    fn from(reader: crate::R<VERTICAL_TIMING_B_EVEN_FIELD_SPEC>) -> Self {
+        R(reader)
+    }
+}
+#[doc = "Register `vertical_timing_b_even_field` writer"]
+pub struct W(crate::W<VERTICAL_TIMING_B_EVEN_FIELD_SPEC>);
+impl core::ops::Deref for W {
+    type Target = crate::W<VERTICAL_TIMING_B_EVEN_FIELD_SPEC>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl core::ops::DerefMut for W {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<crate::W<VERTICAL_TIMING_B_EVEN_FIELD_SPEC>> for W {
+    #[inline(always)]
+    fn from(writer: crate::W<VERTICAL_TIMING_B_EVEN_FIELD_SPEC>) -> Self {
+        W(writer)
+    }
+}
+#[doc = "Field `post_blanking_half_lines` reader - "]
+pub struct POST_BLANKING_HALF_LINES_R(crate::FieldReader<u16, u16>);
+impl POST_BLANKING_HALF_LINES_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u16) -> Self {
+        POST_BLANKING_HALF_LINES_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for POST_BLANKING_HALF_LINES_R {
+    type Target = crate::FieldReader<u16, u16>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `post_blanking_half_lines` writer - "]
+pub struct POST_BLANKING_HALF_LINES_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> POST_BLANKING_HALF_LINES_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u16) -> &'a mut W {
+        self.w.bits = (self.w.bits & !(0x03ff << 16)) | ((value as u32 & 0x03ff) << 16);
+        self.w
+    }
+}
+#[doc = "Field `pre_blanking_half_lines` reader - "]
+pub struct PRE_BLANKING_HALF_LINES_R(crate::FieldReader<u16, u16>);
+impl PRE_BLANKING_HALF_LINES_R {
+    #[inline(always)]
+    pub(crate) fn new(bits: u16) -> Self {
+        PRE_BLANKING_HALF_LINES_R(crate::FieldReader::new(bits))
+    }
+}
+impl core::ops::Deref for PRE_BLANKING_HALF_LINES_R {
+    type Target = crate::FieldReader<u16, u16>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+#[doc = "Field `pre_blanking_half_lines` writer - "]
+pub struct PRE_BLANKING_HALF_LINES_W<'a> {
+    w: &'a mut W,
+}
+impl<'a> PRE_BLANKING_HALF_LINES_W<'a> {
+    #[doc = r"Writes raw bits to the field"]
+    #[inline(always)]
+    pub unsafe fn bits(self, value: u16) -> &'a mut W {
+        self.w.bits = (self.w.bits & !0x03ff) | (value as u32 & 0x03ff);
+        self.w
+    }
+}
+impl R {
+    #[doc = "Bits 16:25"]
+    #[inline(always)]
+    pub fn post_blanking_half_lines(&self) -> POST_BLANKING_HALF_LINES_R {
+        POST_BLANKING_HALF_LINES_R::new(((self.bits >> 16) & 0x03ff) as u16)
+    }
+    #[doc = "Bits 0:9"]
+    #[inline(always)]
+    pub fn pre_blanking_half_lines(&self) -> PRE_BLANKING_HALF_LINES_R {
+        PRE_BLANKING_HALF_LINES_R::new((self.bits & 0x03ff) as u16)
+    }
+}
+impl W {
+    #[doc = "Bits 16:25"]
+    #[inline(always)]
+    pub fn post_blanking_half_lines(&mut self) -> POST_BLANKING_HALF_LINES_W {
+        POST_BLANKING_HALF_LINES_W { w: self }
+    }
+    #[doc = "Bits 0:9"]
+    #[inline(always)]
+    pub fn pre_blanking_half_lines(&mut self) -> PRE_BLANKING_HALF_LINES_W {
+        PRE_BLANKING_HALF_LINES_W { w: self }
+    }
+    #[doc = "Writes raw bits to the register."]
+    #[inline(always)]
+    pub unsafe fn bits(&mut self, bits: u32) -> &mut Self {
+        self.0.bits(bits);
+        self
+    }
+}
+#[doc = "\n\nThis register you can [`read`](crate::generic::Reg::read), [`write_with_zero`](crate::generic::Reg::write_with_zero), [`modify`](crate::generic::Reg::modify). See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [vertical_timing_b_even_field](index.html) module"]
+pub struct VERTICAL_TIMING_B_EVEN_FIELD_SPEC;
+impl crate::RegisterSpec for VERTICAL_TIMING_B_EVEN_FIELD_SPEC {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [vertical_timing_b_even_field::R](R) reader structure"]
+impl crate::Readable for VERTICAL_TIMING_B_EVEN_FIELD_SPEC {
+    type Reader = R;
+}
+#[doc = "`write(|w| ..)` method takes [vertical_timing_b_even_field::W](W) writer structure"]
+impl crate::Writable for VERTICAL_TIMING_B_EVEN_FIELD_SPEC {
+    type Writer = W;
+}
@@ -2,142 +2,10 @@
 
 use derive_more::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
 
-#[derive(Clone, Copy, Debug)]
-#[repr(u8)]
-pub enum TevColorIn {
-    PrevColor = 0,
-    PrevAlpha = 1,
-    Reg0Color = 2,
-    Reg0Alpha = 3,
-    Reg1Color = 4,
-    Reg1Alpha = 5,
-    Reg2Color = 6,
-    Reg2Alpha = 7,
-    TexColor = 8,
-    TexAlpha = 9,
-    RasColor = 10,
-    RasAlpha = 11,
-    Constant1 = 12,
-    Constant1_2 = 13,
-    Konst = 14,
-    Constant0 = 15,
-}
-
-#[derive(Clone, Copy, Debug)]
-#[repr(u8)]
-pub enum TevAlphaIn {
-    PrevAlpha = 0,
-    Reg0Alpha = 1,
-    Reg1Alpha = 2,
-    Reg2Alpha = 3,
-    TexAlpha = 4,
-    RasAlpha = 5,
-    Konst = 6,
-    Constant0 = 7,
-}
-
-#[derive(Clone, Copy, Debug)]
-#[repr(u8)]
-pub enum TevOp {
-    Add = 0,
-    Sub = 1,
-    CompR8Gt = 8,
-    CompR8Eq = 9,
-    CompGr16Gt = 10,
-    CompGr16Eq = 11,
-    CompBgr24Gt = 12,
-    CompBgr24Eq = 13,
-    CompRgb8OrA8Gt = 14,
-    CompRgb8OrA8Eq = 15,
-}
-
-#[derive(Clone, Copy, Debug)]
-#[repr(u8)]
-pub enum TevBias {
-    Zero = 0,
-    AddHalf = 1,
-    SubHalf = 2,
-}
-
-#[derive(Clone, Copy, Debug)]
-#[repr(u8)]
-pub enum TevScale {
-    K1 = 0,
-    K2 = 1,
-    K4 = 2,
-    K1_2 = 3,
-}
-
-#[derive(Clone, Copy, Debug)]
-#[repr(u8)]
-pub enum TevReg {
-    Prev = 0,
-    Reg0 = 1,
-    Reg1 = 2,
-    Reg2 = 3,
-}
-
-#[derive(Clone, Copy, Debug)]
-#[repr(u8)]
-pub enum TevColorKonst {
-    Constant1 = 0x00,
-    Constant7_8 = 0x01,
-    Constant3_4 = 0x02,
-    Constant5_8 = 0x03,
-    Constant1_2 = 0x04,
-    Constant3_8 = 0x05,
-    Constant1_4 = 0x06,
-    Constant1_8 = 0x07,
-    K0Rgb = 0x0c,
-    K1Rgb = 0x0d,
-    K2Rgb = 0x0e,
-    K3Rgb = 0x0f,
-    K0R = 0x10,
-    K1R = 0x11,
-    K2R = 0x12,
-    K3R = 0x13,
-    K0G = 0x14,
-    K1G = 0x15,
-    K2G = 0x16,
-    K3G = 0x17,
-    K0B = 0x18,
-    K1B = 0x19,
-    K2B = 0x1a,
-    K3B = 0x1b,
-    K0A = 0x1c,
-    K1A = 0x1d,
-    K2A = 0x1e,
-    K3A = 0x1f,
-}
-
-#[derive(Clone, Copy, Debug)]
-#[repr(u8)]
-pub enum TevAlphaKonst {
-    Constant1 = 0x00,
-    Constant7_8 = 0x01,
-    Constant3_4 = 0x02,
-    Constant5_8 = 0x03,
-    Constant1_2 = 0x04,
-    Constant3_8 = 0x05,
-    Constant1_4 = 0x06,
-    Constant1_8 = 0x07,
-    K0R = 0x10,
-    K1R = 0x11,
-    K2R = 0x12,
-    K3R = 0x13,
-    K0G = 0x14,
-    K1G = 0x15,
-    K2G = 0x16,
-    K3G = 0x17,
-    K0B = 0x18,
-    K1B = 0x19,
-    K2B = 0x1a,
-    K3B = 0x1b,
-    K0A = 0x1c,
-    K1A = 0x1d,
-    K2A = 0x1e,
-    K3A = 0x1f,
-}
+pub use inception_render_common::shader::{
+    TevAlphaIn, TevAlphaKonst, TevBias, TevChannel, TevColorIn, TevColorKonst, TevOp, TevReg,
+    TevScale,
+};
 
 #[derive(Clone, Copy, Debug)]
 #[repr(u8)]
@@ -153,6 +21,22 @@ pub enum TevTexCoord {
     Null = 0xff,
 }
 
+impl TevTexCoord {
+    pub const fn from_index(index: u8) -> Self {
+        match index {
+            0 => Self::TexCoord0,
+            1 => Self::TexCoord1,
+            2 => Self::TexCoord2,
+            3 => Self::TexCoord3,
+            4 => Self::TexCoord4,
+            5 => Self::TexCoord5,
+            6 => Self::TexCoord6,
+            7 => Self::TexCoord7,
+            _ => panic!("texture coordinate index out of range"),
+        }
+    }
+}
+
 #[derive(
     Clone,
     Copy,
@@ -180,7 +64,7 @@ impl TevTexMap {
     pub const NULL: Self = Self(0xff);
     pub const DISABLE_FLAG: Self = Self(0x100);
 
-    pub fn from_u32(value: u32) -> Self {
+    pub const fn from_u32(value: u32) -> Self {
         Self(value)
     }
 
@@ -189,21 +73,6 @@ impl TevTexMap {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
-#[repr(u8)]
-pub enum TevChannel {
-    Color0 = 0,
-    Color1 = 1,
-    Alpha0 = 2,
-    Alpha1 = 3,
-    Color0A0 = 4,
-    Color1A1 = 5,
-    Zero = 6,
-    AlphaBump = 7,
-    AlphaBumpN = 8,
-    Null = 0xff,
-}
-
 #[derive(Clone, Copy, Debug)]
 #[repr(u8)]
 pub enum TexGenType {
@@ -244,6 +113,22 @@ pub enum TexGenSrc {
     TexCoord6 = 18,
 }
 
+impl TexGenSrc {
+    pub const fn from_tex_index(index: u8) -> Self {
+        match index {
+            0 => Self::Tex0,
+            1 => Self::Tex1,
+            2 => Self::Tex2,
+            3 => Self::Tex3,
+            4 => Self::Tex4,
+            5 => Self::Tex5,
+            6 => Self::Tex6,
+            7 => Self::Tex7,
+            _ => panic!("vertex texture coordinate index out of range"),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct TexMtxIndex(u32);
 
@@ -260,11 +145,11 @@ impl TexMtxIndex {
     pub const TEXMTX8: Self = Self(54);
     pub const TEXMTX9: Self = Self(57);
 
-    pub fn from_u32(value: u32) -> Self {
+    pub const fn from_u32(value: u32) -> Self {
         Self(value)
     }
 
-    pub fn as_u32(self) -> u32 {
+    pub const fn as_u32(self) -> u32 {
         self.0
     }
 }
@@ -285,7 +170,7 @@ impl PostTransformTexMtxIndex {
     pub const DTTMTX8: Self = Self(88);
     pub const DTTMTX9: Self = Self(91);
 
-    pub fn from_u32(value: u32) -> Self {
+    pub const fn from_u32(value: u32) -> Self {
         Self(value)
     }
 
@@ -7,6 +7,12 @@ use seq_macro::seq;
 
 use crate::gx::*;
 
+use inception_render_common::shader::{TevProgram, TexGenDesc, TexGenProjection, TexGenSource};
+
+pub use inception_render_common::shader::{
+    ComponentIn, ComponentKonst, TevStageAlpha, TevStageColor, TevStageComponent,
+};
+
 mod flat_textured;
 pub mod gx;
 
@@ -95,6 +101,34 @@ impl Shader {
         }
     }
 
+    /// Compiles a portable TEV program to GX state.
+    pub const fn from_program(program: &TevProgram) -> Self {
+        let mut shader = Self::default();
+        shader.num_chans = program.num_chans;
+
+        let mut index = 0;
+        while index < program.stages.len() {
+            let desc = &program.stages[index];
+            let mut stage = TevStage::new(desc.color, desc.alpha).with_channel(desc.channel);
+            if let Some(tex) = desc.tex {
+                stage = stage.with_tex(
+                    TevTexCoord::from_index(tex.coord),
+                    TevTexMap::from_u32(tex.map as u32),
+                );
+            }
+            shader.tev_stages[index] = Some(stage);
+            index += 1;
+        }
+
+        let mut index = 0;
+        while index < program.tex_gens.len() {
+            shader.tex_gens[index] = Some(TexGen::from_desc(&program.tex_gens[index]));
+            index += 1;
+        }
+
+        shader
+    }
+
     pub fn apply(&self) {
         unsafe {
             let num_tev_stages = self.num_tev_stages();
@@ -300,147 +334,6 @@ impl TevStage {
     }
 }
 
-pub trait ComponentIn: Copy {
-    const ZERO: Self;
-    const PREV: Self;
-}
-
-impl ComponentIn for TevColorIn {
-    const ZERO: Self = Self::Constant0;
-    const PREV: Self = Self::PrevColor;
-}
-
-impl ComponentIn for TevAlphaIn {
-    const ZERO: Self = Self::Constant0;
-    const PREV: Self = Self::PrevAlpha;
-}
-
-pub trait ComponentKonst: Copy {}
-
-impl ComponentKonst for TevColorKonst {}
-
-impl ComponentKonst for TevAlphaKonst {}
-
-pub type TevStageColor = TevStageComponent<TevColorIn, TevColorKonst>;
-pub type TevStageAlpha = TevStageComponent<TevAlphaIn, TevAlphaKonst>;
-
-#[derive(Clone, Copy, Debug)]
-pub struct TevStageComponent<Input: ComponentIn, Konst: ComponentKonst> {
-    pub inputs: [Input; 4],
-    pub op: TevOp,
-    pub bias: TevBias,
-    pub scale: TevScale,
-    pub clamp: bool,
-    pub dst: TevReg,
-    pub konst_sel: Option<Konst>,
-}
-
-impl<Input: ComponentIn, Konst: ComponentKonst> TevStageComponent<Input, Konst> {
-    pub const fn zero() -> Self {
-        Self {
-            inputs: [Input::ZERO; 4],
-            op: TevOp::Add,
-            bias: TevBias::Zero,
-            scale: TevScale::K1,
-            clamp: true,
-            dst: TevReg::Prev,
-            konst_sel: None,
-        }
-    }
-
-    pub const fn pass() -> Self {
-        Self::just(Input::PREV)
-    }
-
-    pub const fn just(input_10bit: Input) -> Self {
-        Self {
-            inputs: [Input::ZERO, Input::ZERO, Input::ZERO, input_10bit],
-            ..Self::zero()
-        }
-    }
-
-    pub const fn add(a_10bit: Input, b: Input) -> Self {
-        Self {
-            inputs: [b, Input::ZERO, Input::ZERO, a_10bit],
-            ..Self::zero()
-        }
-    }
-
-    pub const fn sub(a_10bit: Input, b: Input) -> Self {
-        Self {
-            inputs: [b, Input::ZERO, Input::ZERO, a_10bit],
-            op: TevOp::Sub,
-            ..Self::zero()
-        }
-    }
-
-    pub const fn mul(a: Input, b: Input) -> Self {
-        Self {
-            inputs: [Input::ZERO, a, b, Input::ZERO],
-            ..Self::zero()
-        }
-    }
-
-    /// Computes a + b * c.
-    pub const fn add_mul(a_10bit: Input, b: Input, c: Input) -> Self {
-        Self {
-            inputs: [Input::ZERO, b, c, a_10bit],
-            ..Self::zero()
-        }
-    }
-
-    /// Computes a - b * c.
-    pub const fn sub_mul(a_10bit: Input, b: Input, c: Input) -> Self {
-        Self {
-            inputs: [Input::ZERO, b, c, a_10bit],
-            op: TevOp::Sub,
-            ..Self::zero()
-        }
-    }
-
-    /// Computes (1 - c) * a + c * b.
-    pub const fn mix(a: Input, b: Input, c: Input) -> Self {
-        Self {
-            inputs: [a, b, c, Input::ZERO],
-            ..Self::zero()
-        }
-    }
-
-    /// Computes (a.r > b.r ? c : 0).
-    pub const fn comp_r8_gt(a: Input, b: Input, c: Input) -> Self {
-        Self::comp_r8_gt_add(a, b, c, Input::ZERO)
-    }
-
-    /// Computes (a.r > b.r ? c : 0) + d.
-    pub const fn comp_r8_gt_add(a: Input, b: Input, c: Input, d_10bit: Input) -> Self {
-        Self {
-            inputs: [a, b, c, d_10bit],
-            op: TevOp::CompR8Gt,
-            ..Self::zero()
-        }
-    }
-
-    pub const fn with_bias(self, bias: TevBias) -> Self {
-        Self { bias, ..self }
-    }
-
-    pub const fn with_scale(self, scale: TevScale) -> Self {
-        Self { scale, ..self }
-    }
-
-    pub const fn with_clamp(self, clamp: bool) -> Self {
-        Self { clamp, ..self }
-    }
-
-    pub const fn with_dst(self, dst: TevReg) -> Self {
-        Self { dst, ..self }
-    }
-
-    pub const fn with_konst_sel(self, konst_sel: Option<Konst>) -> Self {
-        Self { konst_sel, ..self }
-    }
-}
-
 #[derive(Clone, Copy, Debug)]
 pub struct IndTexStage {
     tex_coord: TevTexCoord,
@@ -483,6 +376,26 @@ impl TexGen {
         }
     }
 
+    pub const fn from_desc(desc: &TexGenDesc) -> Self {
+        Self::new(
+            match desc.projection {
+                TexGenProjection::St => TexGenType::Mtx2x4,
+                TexGenProjection::Stq => TexGenType::Mtx3x4,
+            },
+            match desc.src {
+                TexGenSource::Position => TexGenSrc::Position,
+                TexGenSource::Normal => TexGenSrc::Normal,
+                TexGenSource::Tex(index) => TexGenSrc::from_tex_index(index),
+            },
+            match desc.tex_mtx {
+                Some(tex_mtx) => {
+                    TexMtxIndex::from_u32(TexMtxIndex::TEXMTX0.as_u32() + 3 * tex_mtx as u32)
+                }
+                None => TexMtxIndex::IDENTITY,
+            },
+        )
+    }
+
     pub const fn with_normalize(self, normalize: bool) -> Self {
         Self { normalize, ..self }
     }
@@ -5,13 +5,96 @@ use mvbitfield::prelude::*;
 
 pub mod framebuffer;
 
+/// Every configured mode uses 429 tick half-lines.
+const HALF_LINE_WIDTH: u16 = 429;
+
 pub struct VideoDriver {
     vi: VideoInterface,
+    half_lines_per_field: u16,
+}
+
+/// Which field of the frame the beam is scanning out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field {
+    First,
+    Second,
+}
+
+/// How many pixels wide the framebuffer is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FramebufferWidth {
+    /// 640 pixels, one per output pixel.
+    Full,
+    /// 320 pixels, stretched across the line by the horizontal scaler. This halves fill and copy
+    /// costs. The scaler filters with whatever coefficients are already loaded, which the IPL
+    /// leaves set up.
+    Half,
+}
+
+impl FramebufferWidth {
+    /// The width in pixels.
+    pub const fn pixels(self) -> u16 {
+        match self {
+            Self::Full => 640,
+            Self::Half => 320,
+        }
+    }
+}
+
+/// Where the beam is, as of the moment the position registers were read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BeamPosition {
+    /// The raw vertical count, in lines from the start of the frame, starting at 1.
+    pub vertical_count: u16,
+    /// The raw horizontal count, in ticks from the start of the line, starting at 1.
+    pub horizontal_count: u16,
+    pub field: Field,
+    /// Half-lines since the start of `field`.
+    pub half_line: u16,
 }
 
 impl VideoDriver {
     pub fn new(vi: VideoInterface) -> Self {
-        Self { vi }
+        Self {
+            vi,
+            half_lines_per_field: 0,
+        }
+    }
+
+    /// Reads the VI beam position.
+    ///
+    /// This is meant for beam racing: deciding how late the next flip can be latched, rather than
+    /// always waiting for the vblank interrupt.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no video mode has been configured yet.
+    pub fn current_position(&self) -> BeamPosition {
+        assert!(self.half_lines_per_field != 0, "video mode not configured");
+
+        // The two counters can't be read atomically. Read the vertical count on both sides of the
+        // horizontal count and try again if the beam crossed a line in between.
+        let (vertical_count, horizontal_count) = loop {
+            let vertical_count = self.vi.read_vertical_position() & 0x7ff;
+            let horizontal_count = self.vi.read_horizontal_position() & 0x7ff;
+            if self.vi.read_vertical_position() & 0x7ff == vertical_count {
+                break (vertical_count, horizontal_count);
+            }
+        };
+
+        let half_line = 2 * vertical_count.saturating_sub(1)
+            + horizontal_count.saturating_sub(1) / HALF_LINE_WIDTH;
+        let (field, half_line) = if half_line < self.half_lines_per_field {
+            (Field::First, half_line)
+        } else {
+            (Field::Second, half_line - self.half_lines_per_field)
+        };
+        BeamPosition {
+            vertical_count,
+            horizontal_count,
+            field,
+            half_line,
+        }
     }
 
     /// Configures the video interface for NTSC 480i output.
@@ -58,6 +141,7 @@ impl VideoDriver {
     ///           |=================|=====|-- HSync start to hblank end:        162 ticks (12.0 us)
     /// ```
     pub fn configure_for_ntsc_480i(&mut self, framebuffer: *const ()) {
+        self.half_lines_per_field = 525;
         self.vi
             .write_display_configuration(DisplayConfiguration::zero().with_reset(true));
 
@@ -104,14 +188,14 @@ impl VideoDriver {
         );
         self.vi.write_top_left_field_base(
             FieldBase::zero()
-                .with_addresss(U24::new_masked(framebuffer as u32))
+                .with_address(U24::new_masked(framebuffer as u32))
                 .with_shift_address_left_five(false),
         );
         self.vi.write_bottom_left_field_base(
             FieldBase::zero()
                 // 1280 is the byte stride of a 640 pixel wide framebuffer. The bottom field
                 // starts on the second line.
-                .with_addresss(U24::new_masked(framebuffer as u32 + 1280))
+                .with_address(U24::new_masked(framebuffer as u32 + 1280))
                 .with_shift_address_left_five(false),
         );
         // Send an interrupt at the beginning of every vblank.
@@ -135,7 +219,10 @@ impl VideoDriver {
         self.vi.write_horizontal_scaling(
             HorizontalScaling::zero()
                 .with_step_size_u1_8(U9::new_masked(0x100))
-                .with_enable(false)
+                .with_enable(false),
+        );
+        self.vi.write_scaling_width(
+            ScalingWidth::zero()
                 // 80 * 16 bytes = 1280 bytes, one line of a 640 pixel wide framebuffer. That's the
                 // stride per half line, so every other line is displayed.
                 .with_stride_per_half_line_in_16_byte_units(80)
@@ -199,6 +286,7 @@ impl VideoDriver {
     ///           |=======================|-- HSync start to hblank end:        162 ticks ( 6.0 us)
     /// ```
     pub fn configure_for_ntsc_480p(&mut self, framebuffer: *const ()) {
+        self.half_lines_per_field = 1050;
         self.vi
             .write_display_configuration(DisplayConfiguration::zero().with_reset(true));
 
@@ -245,12 +333,12 @@ impl VideoDriver {
         );
         self.vi.write_top_left_field_base(
             FieldBase::zero()
-                .with_addresss(U24::new_masked(framebuffer as u32))
+                .with_address(U24::new_masked(framebuffer as u32))
                 .with_shift_address_left_five(false),
         );
         self.vi.write_bottom_left_field_base(
             FieldBase::zero()
-                .with_addresss(U24::new_masked(framebuffer as u32))
+                .with_address(U24::new_masked(framebuffer as u32))
                 .with_shift_address_left_five(false),
         );
         // Send an interrupt at the beginning of every vblank.
@@ -271,7 +359,10 @@ impl VideoDriver {
         self.vi.write_horizontal_scaling(
             HorizontalScaling::zero()
                 .with_step_size_u1_8(U9::new_masked(0x100))
-                .with_enable(false)
+                .with_enable(false),
+        );
+        self.vi.write_scaling_width(
+            ScalingWidth::zero()
                 // 40 * 16 bytes = 640 bytes, one half-line of a 640 pixel wide framebuffer.
                 .with_stride_per_half_line_in_16_byte_units(40)
                 // 40 * 16 pixels = 640 pixels
@@ -287,4 +378,229 @@ impl VideoDriver {
                 .with_format(Format::Ntsc),
         );
     }
+
+    /// Configures the video interface for NTSC 240p output, also known as double-strike. This is
+    /// a performance escape hatch for heavy maps, and it's what CRTs showed for most consoles of
+    /// the era.
+    ///
+    /// The framebuffer must be 32-byte aligned and below physical memory address 0x01000000 (16
+    /// MiB). It holds 240 lines of `width` pixels.
+    ///
+    /// # Timing
+    ///
+    /// The clock is set to 27 MHz, as for 480i, and horizontal timing is identical to 480i.
+    ///
+    /// ## Vertical
+    ///
+    /// There are 240 active lines. Both fields are the same, so every field scans out the whole
+    /// framebuffer on the same lines.
+    ///
+    /// Line sequence in a field:
+    /// - Equalization: 3x 3 lines
+    /// - Pre-blanking: 12 lines
+    /// - Active video: 240 lines
+    /// - Post-blanking: 2 lines
+    /// - Total: 263 lines
+    ///
+    /// Burst blanking:
+    /// - All fields start 12 halflines into the field and end 520 halflines into the field
+    pub fn configure_for_ntsc_240p(&mut self, framebuffer: *const (), width: FramebufferWidth) {
+        self.half_lines_per_field = 526;
+        self.vi
+            .write_display_configuration(DisplayConfiguration::zero().with_reset(true));
+
+        self.vi.write_vertical_timing_a(
+            VerticalTimingA::zero()
+                .with_equalization_pulse_half_lines(U4::new_masked(6))
+                .with_active_video_lines(U10::new_masked(240)),
+        );
+        self.vi.write_horizontal_timing_a(
+            HorizontalTimingA::zero()
+                .with_halfline_width(U9::new_masked(429))
+                .with_hsync_start_to_color_burst_end(U7::new_masked(105))
+                .with_hsync_start_to_color_burst_start(U7::new_masked(71)),
+        );
+        self.vi.write_horizontal_timing_b(
+            HorizontalTimingB::zero()
+                .with_hsync_width(U7::new_masked(64))
+                .with_hsync_start_to_hblank_end(U10::new_masked(162))
+                .with_half_line_to_hblank_start(U10::new_masked(373)),
+        );
+        self.vi.write_vertical_timing_b_odd_field(
+            VerticalTimingB::zero()
+                .with_pre_blanking_half_lines(U10::new_masked(24))
+                .with_post_blanking_half_lines(U10::new_masked(4)),
+        );
+        self.vi.write_vertical_timing_b_even_field(
+            VerticalTimingB::zero()
+                .with_pre_blanking_half_lines(U10::new_masked(24))
+                .with_post_blanking_half_lines(U10::new_masked(4)),
+        );
+        self.vi.write_burst_blanking_odd_field(
+            BurstBlankingOddField::zero()
+                .with_field_1_start_to_burst_blanking_start_half_lines(U5::new_masked(12))
+                .with_field_1_start_to_burst_blanking_end_half_lines(U11::new_masked(520))
+                .with_field_3_start_to_burst_blanking_start_half_lines(U5::new_masked(12))
+                .with_field_3_start_to_burst_blanking_end_half_lines(U11::new_masked(520)),
+        );
+        self.vi.write_burst_blanking_even_field(
+            BurstBlankingEvenField::zero()
+                .with_field_2_start_to_burst_blanking_start_half_lines(U5::new_masked(12))
+                .with_field_2_start_to_burst_blanking_end_half_lines(U11::new_masked(520))
+                .with_field_4_start_to_burst_blanking_start_half_lines(U5::new_masked(12))
+                .with_field_4_start_to_burst_blanking_end_half_lines(U11::new_masked(520)),
+        );
+        self.vi.write_top_left_field_base(
+            FieldBase::zero()
+                .with_address(U24::new_masked(framebuffer as u32))
+                .with_shift_address_left_five(false),
+        );
+        self.vi.write_bottom_left_field_base(
+            FieldBase::zero()
+                .with_address(U24::new_masked(framebuffer as u32))
+                .with_shift_address_left_five(false),
+        );
+        // Send an interrupt at the beginning of every vblank.
+        self.vi.write_display_interrupt_0(
+            DisplayInterrupt::zero()
+                .with_horizontal_position(U11::new_masked(641))
+                .with_vertical_position(U11::new_masked(240)) // The last line of the first field.
+                .with_interrupt_enable(true),
+        );
+        self.vi.write_display_interrupt_1(
+            DisplayInterrupt::zero()
+                .with_horizontal_position(U11::new_masked(641))
+                .with_vertical_position(U11::new_masked(503)) // The last line of the second field.
+                .with_interrupt_enable(true),
+        );
+        self.vi.write_display_interrupt_2(DisplayInterrupt::zero());
+        self.vi.write_display_interrupt_3(DisplayInterrupt::zero());
+        let width_units = (width.pixels() / 16) as u8;
+        self.vi.write_horizontal_scaling(
+            HorizontalScaling::zero()
+                // The step through the framebuffer per output pixel, in 1/256ths of a pixel.
+                .with_step_size_u1_8(U9::new_masked((256 * width.pixels() as u32 / 640) as u16))
+                .with_enable(width != FramebufferWidth::Full),
+        );
+        self.vi.write_scaling_width(
+            ScalingWidth::zero()
+                // Advance one framebuffer line per displayed line, as for 480p.
+                .with_stride_per_half_line_in_16_byte_units(width_units)
+                .with_framebuffer_width_in_16_pixel_units(U7::new_masked(width_units)),
+        );
+        self.vi
+            .write_clock_select(ClockSelect::zero().with_clock(Clock::K27MHz));
+
+        self.vi.write_display_configuration(
+            DisplayConfiguration::zero()
+                .with_enable(true)
+                .with_interlace(Interlace::NonInterlaced)
+                .with_format(Format::Ntsc),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gamecube_mmio::video_interface::VideoInterface;
+    use gamecube_mmio_mock::MockBus;
+
+    use super::{BeamPosition, Field, FramebufferWidth, VideoDriver};
+
+    const FRAMEBUFFER: usize = 0x0010_0000;
+
+    fn vi() -> VideoInterface {
+        // SAFETY: Each test installs a bus private to its thread, so no other handle can see
+        // these registers.
+        unsafe { VideoInterface::steal() }
+    }
+
+    #[test]
+    fn current_position_retries_when_the_line_changes() {
+        let bus = MockBus::install();
+        let mut driver = VideoDriver::new(vi());
+        driver.configure_for_ntsc_480i(FRAMEBUFFER as *const ());
+
+        // The beam moves to the next line between the first two reads.
+        let mut vertical_counts = [10, 11, 11, 11].into_iter();
+        bus.on_read(VideoInterface::VERTICAL_POSITION_ADDRESS, move |_, _| {
+            vertical_counts.next().unwrap()
+        });
+        let mut horizontal_counts = [850, 20].into_iter();
+        bus.on_read(VideoInterface::HORIZONTAL_POSITION_ADDRESS, move |_, _| {
+            horizontal_counts.next().unwrap()
+        });
+
+        assert_eq!(
+            driver.current_position(),
+            BeamPosition {
+                vertical_count: 11,
+                horizontal_count: 20,
+                field: Field::First,
+                half_line: 20,
+            },
+        );
+    }
+
+    #[test]
+    fn current_position_splits_fields() {
+        let bus = MockBus::install();
+        let mut driver = VideoDriver::new(vi());
+        driver.configure_for_ntsc_480i(FRAMEBUFFER as *const ());
+
+        bus.set(VideoInterface::VERTICAL_POSITION_ADDRESS, 300);
+        bus.set(VideoInterface::HORIZONTAL_POSITION_ADDRESS, 430);
+        let position = driver.current_position();
+        assert_eq!(position.field, Field::Second);
+        assert_eq!(position.half_line, 2 * 299 + 1 - 525);
+    }
+
+    #[test]
+    fn configure_for_ntsc_480i_points_fields_at_alternate_lines() {
+        let bus = MockBus::install();
+        let mut driver = VideoDriver::new(vi());
+        driver.configure_for_ntsc_480i(FRAMEBUFFER as *const ());
+
+        let address = |raw: u32| raw & 0xff_ffff;
+        assert_eq!(
+            address(bus.get(VideoInterface::TOP_LEFT_FIELD_BASE_ADDRESS)),
+            FRAMEBUFFER as u32,
+        );
+        assert_eq!(
+            address(bus.get(VideoInterface::BOTTOM_LEFT_FIELD_BASE_ADDRESS)),
+            FRAMEBUFFER as u32 + 1280,
+        );
+
+        // Reset first, enable last.
+        let configurations = bus.writes_to(VideoInterface::DISPLAY_CONFIGURATION_ADDRESS);
+        assert_eq!(configurations.len(), 2);
+        assert_eq!(configurations[0], 0b10);
+        assert_eq!(configurations[1] & 0b11, 0b01);
+    }
+
+    #[test]
+    fn configure_for_ntsc_240p_half_width_scales_both_fields_from_one_image() {
+        let bus = MockBus::install();
+        let mut driver = VideoDriver::new(vi());
+        driver.configure_for_ntsc_240p(FRAMEBUFFER as *const (), FramebufferWidth::Half);
+
+        let address = |raw: u32| raw & 0xff_ffff;
+        assert_eq!(
+            address(bus.get(VideoInterface::TOP_LEFT_FIELD_BASE_ADDRESS)),
+            FRAMEBUFFER as u32,
+        );
+        assert_eq!(
+            address(bus.get(VideoInterface::BOTTOM_LEFT_FIELD_BASE_ADDRESS)),
+            FRAMEBUFFER as u32,
+        );
+
+        // Step 0x80 with scaling enabled, then 20 units of stride and width.
+        let scaling = bus.get(VideoInterface::HORIZONTAL_SCALING_ADDRESS);
+        assert_eq!(scaling, 0x80 | 1 << 12);
+        let width = bus.get(VideoInterface::SCALING_WIDTH_ADDRESS);
+        assert_eq!(width, 20 | 20 << 8);
+
+        let configurations = bus.writes_to(VideoInterface::DISPLAY_CONFIGURATION_ADDRESS);
+        assert_eq!(configurations.last().unwrap() & 0b111, 0b101);
+    }
 }
@@ -30,7 +30,9 @@ struct Bat {
 
 #[no_mangle]
 extern "C" fn bsod(args: &BsodArgs) -> ! {
-    VideoDriver::new(VideoInterface::new()).configure_for_ntsc_480i(FRAMEBUFFER.as_ptr().cast());
+    // SAFETY: The BSOD never returns, so it can take the video hardware from whoever had it.
+    let vi = unsafe { VideoInterface::steal() };
+    VideoDriver::new(vi).configure_for_ntsc_480i(FRAMEBUFFER.as_ptr().cast());
 
     let mut console = TextConsole::new();
     writeln!(
@@ -13,8 +13,9 @@ pub static FRAMEBUFFER: Framebuffer = Framebuffer::zero();
 #[link_section = ".bss"]
 static STATE: DriverState<TextConsole> = DriverState::uninit();
 
-pub fn init() {
+pub fn init(vi: VideoInterface) {
     STATE.init_with(TextConsole::new);
+    VideoDriver::new(vi).configure_for_ntsc_480p(FRAMEBUFFER.as_ptr().cast());
     create_thread(render_thread, USER_MACHINE_STATE, None);
 }
 
@@ -35,8 +36,6 @@ fn render_if_changed() {
 }
 
 extern "C" fn render_thread() -> ! {
-    VideoDriver::new(VideoInterface::new()).configure_for_ntsc_480p(FRAMEBUFFER.as_ptr().cast());
-
     loop {
         render_if_changed();
         system_call::wait_for(
@@ -1,4 +1,4 @@
-use gamecube_mmio::processor_interface::ProcessorInterface;
+use gamecube_mmio::processor_interface::{InterruptCause, ProcessorInterface};
 use gamecube_mmio::video_interface::VideoInterface;
 use mvbitfield::prelude::*;
 
@@ -7,12 +7,13 @@ use crate::thread::{wake_thread, WaitingFor};
 
 #[no_mangle]
 extern "C" fn handle_external_interrupt() -> WaitingFor {
-    let pi = ProcessorInterface::new();
-    let vi = VideoInterface::new();
+    // SAFETY: Threads only touch the interrupt status and display interrupt registers from here,
+    // and this runs with external interrupts disabled.
+    let pi = unsafe { ProcessorInterface::steal() };
+    let vi = unsafe { VideoInterface::steal() };
 
     let mut fired = WaitingFor::zero();
-    let pi_cause = pi.read_interrupt_cause();
-    if pi_cause.interrupts().video_interface() {
+    if pi.is_asserted(InterruptCause::VideoInterface) {
         for i in 0..4 {
             let i = U2::new(i).unwrap();
             let reg = vi.read_display_interrupt(i);
@@ -4,7 +4,8 @@
 #![no_std]
 
 use gamecube_cpu::registers::msr::modify_msr;
-use gamecube_mmio::processor_interface::{InterruptMask, Interrupts, ProcessorInterface};
+use gamecube_mmio::processor_interface::{InterruptMask, Interrupts};
+use gamecube_mmio::Peripherals;
 use panic_abort as _;
 
 use crate::exception::install_exception_handlers;
@@ -27,8 +28,9 @@ unsafe extern "C" fn main() -> ! {
     install_exception_handlers();
 
     // Initialize drivers.
+    let peripherals = Peripherals::take().unwrap();
     driver::timer::init();
-    driver::text_console::init();
+    driver::text_console::init(peripherals.video_interface);
 
     // Prepare the initial threads.
     create_thread(crate::user::entry_a, USER_MACHINE_STATE, None);
@@ -36,7 +38,7 @@ unsafe extern "C" fn main() -> ! {
 
     driver::text_console::print("System initialized. Starting threads.\n");
 
-    ProcessorInterface::new().write_interrupt_mask(
+    peripherals.processor_interface.write_interrupt_mask(
         InterruptMask::zero().with_interrupts(Interrupts::zero().with_video_interface(true)),
     );
 
@@ -10,11 +10,9 @@ extern crate alloc;
 use core::alloc::{AllocError, Allocator, GlobalAlloc, Layout};
 use core::ffi::c_void;
 use core::intrinsics::copy_nonoverlapping;
-use core::panic::PanicInfo;
 use core::ptr::NonNull;
 
 use alloc::alloc::Global;
-use alloc::format;
 
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
@@ -69,8 +67,11 @@ unsafe impl Allocator for GlobalAlign32 {
     }
 }
 
+#[cfg(feature = "panic_handler")]
 #[panic_handler]
-fn panic(info: &PanicInfo) -> ! {
+fn panic(info: &core::panic::PanicInfo) -> ! {
+    use alloc::format;
+
     unsafe {
         let rmode = VIDEO_GetPreferredMode(core::ptr::null_mut());
         CON_InitEx(
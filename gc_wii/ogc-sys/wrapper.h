@@ -2,3 +2,6 @@
 #include <ogc/conf.h>
 #include <ogc/lwp_watchdog.h>
 #include <network.h>
+#ifdef HW_RVL
+#include <wiiuse/wpad.h>
+#endif
@@ -0,0 +1,175 @@
+use std::fmt::Write as _;
+use std::fs::{read_to_string, write};
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use nalgebra_glm::{vec3, Vec3};
+use nom::character::complete::{char, multispace0};
+use nom::combinator::all_consuming;
+use nom::multi::separated_list0;
+use nom::number::complete::float;
+use nom::sequence::delimited;
+use nom::IResult;
+
+use crate::game_state::GameState;
+
+/// One camera placement per rendered frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraFrame {
+    pub pos: Vec3,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl CameraFrame {
+    pub fn from_game_state(game_state: &GameState) -> Self {
+        Self {
+            pos: game_state.pos,
+            yaw: game_state.yaw,
+            pitch: game_state.pitch,
+        }
+    }
+
+    pub fn apply(&self, game_state: &mut GameState) {
+        game_state.pos = self.pos;
+        game_state.yaw = self.yaw;
+        game_state.pitch = self.pitch;
+    }
+}
+
+/// A recorded free-fly camera path. Saved as a JSON array of `[x, y, z, yaw, pitch]` arrays.
+///
+/// Playback advances one frame per rendered frame rather than following wall-clock time, so every
+/// run renders exactly the same views.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CameraPath {
+    pub frames: Vec<CameraFrame>,
+}
+
+impl CameraPath {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = read_to_string(path).with_context(|| format!("reading {path:?}"))?;
+        Self::parse(&text).with_context(|| format!("parsing {path:?}"))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        write(path, self.to_json()).with_context(|| format!("writing {path:?}"))
+    }
+
+    fn parse(text: &str) -> Result<Self> {
+        fn ws<'a, O>(
+            inner: impl FnMut(&'a str) -> IResult<&'a str, O>,
+        ) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+            delimited(multispace0, inner, multispace0)
+        }
+        fn list<'a, O>(
+            item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+        ) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+            delimited(
+                ws(char('[')),
+                separated_list0(ws(char(',')), item),
+                ws(char(']')),
+            )
+        }
+
+        let (_, frames) = all_consuming(list(list(ws(float))))(text)
+            .map_err(|e| anyhow!("malformed camera path: {e}"))?;
+        let frames = frames
+            .into_iter()
+            .enumerate()
+            .map(|(index, values)| match values[..] {
+                [x, y, z, yaw, pitch] => Ok(CameraFrame {
+                    pos: vec3(x, y, z),
+                    yaw,
+                    pitch,
+                }),
+                _ => Err(anyhow!("frame {index} has {} values, not 5", values.len())),
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { frames })
+    }
+
+    fn to_json(&self) -> String {
+        let mut json = String::from("[\n");
+        for (index, frame) in self.frames.iter().enumerate() {
+            let separator = if index + 1 < self.frames.len() {
+                ","
+            } else {
+                ""
+            };
+            writeln!(
+                json,
+                "  [{}, {}, {}, {}, {}]{separator}",
+                frame.pos.x, frame.pos.y, frame.pos.z, frame.yaw, frame.pitch,
+            )
+            .unwrap();
+        }
+        json.push_str("]\n");
+        json
+    }
+}
+
+/// Collects per-frame measurements during benchmark playback.
+#[derive(Default)]
+pub struct BenchmarkStats {
+    frame_times: Vec<Duration>,
+    draw_calls: Vec<usize>,
+}
+
+impl BenchmarkStats {
+    pub fn record(&mut self, frame_time: Duration, draw_calls: usize) {
+        self.frame_times.push(frame_time);
+        self.draw_calls.push(draw_calls);
+    }
+
+    pub fn report(&self) -> String {
+        if self.frame_times.is_empty() {
+            return "no frames rendered".to_string();
+        }
+        let mut sorted = self.frame_times.clone();
+        sorted.sort();
+        let average = sorted.iter().sum::<Duration>() / sorted.len() as u32;
+        let p99 = sorted[(sorted.len() * 99).div_ceil(100) - 1];
+        let average_draw_calls =
+            self.draw_calls.iter().sum::<usize>() as f64 / self.draw_calls.len() as f64;
+        format!(
+            "{} frames: average {:.3} ms, 99th percentile {:.3} ms, {:.1} draw calls per frame",
+            sorted.len(),
+            average.as_secs_f64() * 1e3,
+            p99.as_secs_f64() * 1e3,
+            average_draw_calls,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra_glm::vec3;
+
+    use super::{CameraFrame, CameraPath};
+
+    #[test]
+    fn round_trip() {
+        let path = CameraPath {
+            frames: vec![
+                CameraFrame {
+                    pos: vec3(-4875.0, -1237.0, 140.0),
+                    yaw: 3.0,
+                    pitch: 0.0,
+                },
+                CameraFrame {
+                    pos: vec3(1.5, -2.25, 1e-3),
+                    yaw: 0.5,
+                    pitch: -0.25,
+                },
+            ],
+        };
+        assert_eq!(CameraPath::parse(&path.to_json()).unwrap(), path);
+    }
+
+    #[test]
+    fn rejects_short_frames() {
+        assert!(CameraPath::parse("[[1, 2, 3]]").is_err());
+    }
+}
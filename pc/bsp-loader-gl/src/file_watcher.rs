@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+use std::fs::metadata;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Notices changes to a set of files by polling their modification times.
+///
+/// Files don't need to exist to be watched. Creating one counts as a change.
+pub struct FileWatcher {
+    modified_by_path: HashMap<PathBuf, Option<SystemTime>>,
+    last_poll: Instant,
+}
+
+impl FileWatcher {
+    pub fn new() -> Self {
+        Self {
+            modified_by_path: HashMap::new(),
+            last_poll: Instant::now(),
+        }
+    }
+
+    /// Starts watching a file. Has no effect if the file is already being watched.
+    pub fn watch(&mut self, path: PathBuf) {
+        self.modified_by_path
+            .entry(path)
+            .or_insert_with_key(|path| modified(path));
+    }
+
+    /// Returns the watched files that have changed since the last poll.
+    ///
+    /// Polls are rate limited, so this is cheap enough to call every frame.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let now = Instant::now();
+        if now - self.last_poll < POLL_INTERVAL {
+            return Vec::new();
+        }
+        self.last_poll = now;
+
+        let mut changed = Vec::new();
+        for (path, last_modified) in &mut self.modified_by_path {
+            let modified = modified(path);
+            if modified != *last_modified {
+                *last_modified = modified;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
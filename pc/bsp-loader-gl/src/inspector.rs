@@ -0,0 +1,236 @@
+//! An egui overlay for comparing materials, lightmaps, and light styles against the GX renderer.
+//! F1 toggles it.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use egui::{ColorImage, Context, TextureHandle, TextureId, TextureOptions};
+use egui_glium::EguiGlium;
+use glium::glutin::event::{ElementState, VirtualKeyCode, WindowEvent};
+use glium::glutin::event_loop::EventLoopWindowTarget;
+use glium::texture::SrgbTexture2d;
+use glium::{Display, Surface};
+use source_reader::asset::vmt::{
+    LightmappedGeneric, Shader, Sky, UnlitGeneric, VertexLitGeneric, WorldVertexTransition,
+};
+use source_reader::asset::AssetLoader;
+use source_reader::vpk::path::VpkPath;
+use texture_format::{TextureBuf, TextureFormat};
+
+use crate::light_styles::LightStyles;
+
+/// Previews are scaled down to fit in a square this many points on a side.
+const PREVIEW_SIZE: f32 = 256.0;
+
+/// Blended over the selected material's geometry while isolating it.
+const SELECTED_HIGHLIGHT: [f32; 4] = [1.0, 0.0, 1.0, 0.35];
+/// Blended over everything else while isolating.
+const UNSELECTED_HIGHLIGHT: [f32; 4] = [0.0, 0.0, 0.0, 0.8];
+const NO_HIGHLIGHT: [f32; 4] = [0.0; 4];
+
+pub struct Inspector {
+    egui: EguiGlium,
+    open: bool,
+    filter: String,
+    /// Every material the world uses, sorted by path.
+    material_paths: Vec<VpkPath>,
+    selected: Option<Selection>,
+    /// Dims everything but the selected material's geometry.
+    isolate: bool,
+    lightmap_texture_ids: HashMap<i16, TextureId>,
+}
+
+struct Selection {
+    material_path: VpkPath,
+    /// `None` if the material has no base texture or it failed to load.
+    base_texture: Option<TextureHandle>,
+}
+
+impl Inspector {
+    pub fn new<E>(
+        display: &Display,
+        event_loop: &EventLoopWindowTarget<E>,
+        material_paths: impl IntoIterator<Item = VpkPath>,
+    ) -> Self {
+        let mut material_paths: Vec<VpkPath> = material_paths.into_iter().collect();
+        material_paths.sort();
+        material_paths.dedup();
+        Self {
+            egui: EguiGlium::new(display, event_loop),
+            open: false,
+            filter: String::new(),
+            material_paths,
+            selected: None,
+            isolate: false,
+            lightmap_texture_ids: HashMap::new(),
+        }
+    }
+
+    /// Returns true if the event was consumed by the inspector and shouldn't move the camera.
+    pub fn on_event(&mut self, event: &WindowEvent) -> bool {
+        if let WindowEvent::KeyboardInput { input, .. } = event {
+            if input.virtual_keycode == Some(VirtualKeyCode::F1)
+                && input.state == ElementState::Pressed
+            {
+                self.open ^= true;
+                return true;
+            }
+        }
+        self.open && self.egui.on_event(event).consumed
+    }
+
+    /// The `highlight` uniform for a world batch drawn with `material_path`.
+    pub fn highlight(&self, material_path: &VpkPath) -> [f32; 4] {
+        match &self.selected {
+            Some(selected) if self.open && self.isolate => {
+                if selected.material_path == *material_path {
+                    SELECTED_HIGHLIGHT
+                } else {
+                    UNSELECTED_HIGHLIGHT
+                }
+            }
+            _ => NO_HIGHLIGHT,
+        }
+    }
+
+    /// Lays out the overlay for this frame. `cluster` is the cluster the camera is in, if any.
+    pub fn run(
+        &mut self,
+        display: &Display,
+        asset_loader: &AssetLoader,
+        cluster: Option<i16>,
+        cluster_lightmap_textures: &HashMap<i16, Rc<SrgbTexture2d>>,
+        light_styles: &mut LightStyles,
+    ) {
+        if !self.open {
+            return;
+        }
+
+        let lightmap = cluster.and_then(|cluster| {
+            let texture = cluster_lightmap_textures.get(&cluster)?;
+            let id = *self.lightmap_texture_ids.entry(cluster).or_insert_with(|| {
+                self.egui
+                    .painter
+                    .register_native_texture(Rc::clone(texture), TextureOptions::NEAREST)
+            });
+            Some((cluster, id, [texture.width(), texture.height()]))
+        });
+
+        let Self {
+            egui,
+            filter,
+            material_paths,
+            selected,
+            isolate,
+            ..
+        } = self;
+        egui.run(display, |ctx| {
+            egui::SidePanel::left("materials").show(ctx, |ui| {
+                ui.heading("Materials");
+                ui.text_edit_singleline(filter);
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for path in material_paths.iter() {
+                        let name = path.to_string();
+                        if !name.contains(filter.as_str()) {
+                            continue;
+                        }
+                        let is_selected = selected
+                            .as_ref()
+                            .is_some_and(|selected| selected.material_path == *path);
+                        if ui.selectable_label(is_selected, name).clicked() && !is_selected {
+                            *selected = Some(Selection::new(ctx, asset_loader, path));
+                        }
+                    }
+                });
+            });
+
+            if let Some(selection) = selected {
+                egui::Window::new("Material").show(ctx, |ui| {
+                    ui.label(selection.material_path.to_string());
+                    ui.checkbox(isolate, "Isolate");
+                    match asset_loader.get_material(&selection.material_path) {
+                        Ok(material) => {
+                            ui.monospace(format!("{:#?}", material.shader()));
+                        }
+                        Err(e) => {
+                            ui.colored_label(egui::Color32::RED, format!("{e:?}"));
+                        }
+                    }
+                    if let Some(texture) = &selection.base_texture {
+                        let size = texture.size();
+                        ui.image(texture, preview_size([size[0] as u32, size[1] as u32]));
+                    }
+                });
+            }
+
+            egui::Window::new("Lightmap").show(ctx, |ui| match lightmap {
+                Some((cluster, id, size)) => {
+                    ui.label(format!("Cluster {cluster}, {}x{}", size[0], size[1]));
+                    ui.image(id, preview_size(size));
+                }
+                None => {
+                    ui.label("No lightmap for this cluster");
+                }
+            });
+
+            egui::Window::new("Light styles").show(ctx, |ui| light_styles.ui(ui));
+        });
+    }
+
+    /// Draws the overlay laid out by the last call to [`Self::run`].
+    pub fn paint<T: Surface>(&mut self, display: &Display, target: &mut T) {
+        if self.open {
+            self.egui.paint(display, target);
+        }
+    }
+}
+
+impl Selection {
+    fn new(ctx: &Context, asset_loader: &AssetLoader, material_path: &VpkPath) -> Self {
+        let base_texture = asset_loader
+            .get_material(material_path)
+            .ok()
+            .and_then(|material| base_texture_path(material.shader()).cloned())
+            .and_then(|path| load_preview(ctx, asset_loader, &path));
+        Self {
+            material_path: material_path.clone(),
+            base_texture,
+        }
+    }
+}
+
+fn base_texture_path(shader: &Shader) -> Option<&VpkPath> {
+    match shader {
+        Shader::LightmappedGeneric(LightmappedGeneric {
+            base_texture_path, ..
+        })
+        | Shader::UnlitGeneric(UnlitGeneric {
+            base_texture_path, ..
+        })
+        | Shader::WorldVertexTransition(WorldVertexTransition {
+            base_texture_path, ..
+        })
+        | Shader::Sky(Sky { base_texture_path })
+        | Shader::VertexLitGeneric(VertexLitGeneric { base_texture_path }) => {
+            Some(base_texture_path)
+        }
+        Shader::CompileSky | Shader::Unsupported { .. } => None,
+    }
+}
+
+/// Decodes the top mip of a texture's first face for display.
+fn load_preview(
+    ctx: &Context,
+    asset_loader: &AssetLoader,
+    texture_path: &VpkPath,
+) -> Option<TextureHandle> {
+    let texture = asset_loader.get_texture(texture_path).ok()?;
+    let rgba = TextureBuf::transcode(texture.mips()[0][0].as_slice(), TextureFormat::Rgba8);
+    let image = ColorImage::from_rgba_unmultiplied([rgba.width(), rgba.height()], rgba.data());
+    Some(ctx.load_texture(texture_path.to_string(), image, TextureOptions::LINEAR))
+}
+
+fn preview_size(size: [u32; 2]) -> egui::Vec2 {
+    let scale = PREVIEW_SIZE / size[0].max(size[1]) as f32;
+    egui::vec2(size[0] as f32 * scale, size[1] as f32 * scale)
+}
@@ -0,0 +1,325 @@
+//! Light style animation, for checking how the GameCube renderer handles lightmap styles.
+//!
+//! VRAD bakes up to four lightmaps per face, one for each light style that reaches it. The engine
+//! scales each by its style's current brightness and sums them. The GameCube renderer can't afford
+//! that, so it shows a single one of the four style slots the packer emits for every face. Both
+//! are shown here: animate the twelve standard styles the way the engine does, or pick a slot the
+//! way the GameCube does, and compare.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Instant;
+
+use glium::texture::{ClientFormat, RawImage2d, SrgbTexture2d};
+use glium::Rect;
+use source_reader::bsp::{Bsp, ColorRgbExp32};
+
+/// The engine's standard style patterns, from `worldlight.cpp`. Each letter is one frame, where
+/// `a` is dark, `m` is normal brightness, and `z` is about double.
+const STYLE_PATTERNS: [&str; 12] = [
+    // 0 normal
+    "m",
+    // 1 flicker (first variety)
+    "mmnmmommommnonmmonqnmmo",
+    // 2 slow strong pulse
+    "abcdefghijklmnopqrstuvwxyzyxwvutsrqponmlkjihgfedcba",
+    // 3 candle (first variety)
+    "mmmmmaaaaammmmmaaaaaabcdefgabcdefg",
+    // 4 fast strobe
+    "mamamamamama",
+    // 5 gentle pulse 1
+    "jklmnopqrstuvwxyzyxwvutsrqponmlkj",
+    // 6 flicker (second variety)
+    "nmonqnmomnmomomno",
+    // 7 candle (second variety)
+    "mmmaaaabcdefgmmmmaaaammmaamm",
+    // 8 candle (third variety)
+    "mmmaaammmaaammmabcdefaaaammmmabcdefmmmaaaa",
+    // 9 slow strobe
+    "aaaaaaaazzzzzzzz",
+    // 10 fluorescent flicker
+    "mmamammmmammamamaaamammma",
+    // 11 slow pulse not fade to black
+    "abcdefghijklmnopqrrqponmlkjihgfedcba",
+];
+
+/// Style patterns advance at ten frames per second.
+const FRAMES_PER_SECOND: f32 = 10.0;
+
+/// Marks an unused style slot in a face's `styles`.
+const NO_STYLE: u8 = 255;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LightStyleMode {
+    /// Sum every style's lightmap, scaled by its brightness on the current frame.
+    Animate,
+    /// Show only this style slot, like the GameCube renderer's lightmap style setting.
+    GameCubeSlot(usize),
+}
+
+pub struct LightStyles {
+    pub mode: LightStyleMode,
+    pub playing: bool,
+    /// Seconds into the animation.
+    pub time: f32,
+    last_step: Instant,
+}
+
+impl LightStyles {
+    pub fn new() -> Self {
+        Self {
+            mode: LightStyleMode::Animate,
+            playing: true,
+            time: 0.0,
+            last_step: Instant::now(),
+        }
+    }
+
+    /// Advances the animation by the time since the last step, if it's playing.
+    pub fn step(&mut self) {
+        let now = Instant::now();
+        if self.playing {
+            self.time += (now - self.last_step).as_secs_f32();
+        }
+        self.last_step = now;
+    }
+
+    /// The brightness of `style` on the current frame, where 1.0 is normal. Styles past the
+    /// standard twelve are toggled by entities, so they're shown switched on.
+    pub fn brightness(&self, style: u8) -> f32 {
+        match STYLE_PATTERNS.get(style as usize) {
+            Some(pattern) => {
+                let frame = (self.time * FRAMES_PER_SECOND) as usize % pattern.len();
+                (pattern.as_bytes()[frame] - b'a') as f32 / (b'm' - b'a') as f32
+            }
+            None => 1.0,
+        }
+    }
+
+    /// How much each of a face's style slots contributes to its lightmap.
+    pub fn weights(&self, styles: [u8; 4]) -> [f32; 4] {
+        let style_count = styles
+            .iter()
+            .take_while(|&&style| style != NO_STYLE)
+            .count();
+        let mut weights = [0.0; 4];
+        match self.mode {
+            LightStyleMode::Animate => {
+                for (weight, &style) in weights.iter_mut().zip(&styles[..style_count]) {
+                    *weight = self.brightness(style);
+                }
+            }
+            LightStyleMode::GameCubeSlot(slot) if style_count > 0 => {
+                weights[gamecube_source_style(slot, style_count)] = 1.0;
+            }
+            LightStyleMode::GameCubeSlot(_) => (),
+        }
+        weights
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut self.mode, LightStyleMode::Animate, "Animate");
+            for slot in 0..4 {
+                ui.radio_value(
+                    &mut self.mode,
+                    LightStyleMode::GameCubeSlot(slot),
+                    format!("GX slot {slot}"),
+                );
+            }
+        });
+        ui.add_enabled_ui(self.mode == LightStyleMode::Animate, |ui| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.playing, "Play");
+                ui.add(egui::Slider::new(&mut self.time, 0.0..=60.0).text("seconds"));
+            });
+            egui::Grid::new("light styles").show(ui, |ui| {
+                for (style, pattern) in STYLE_PATTERNS.iter().enumerate() {
+                    ui.label(format!("{style}"));
+                    ui.monospace(*pattern);
+                    ui.label(format!("{:.2}", self.brightness(style as u8)));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+}
+
+/// The packer writes a face's styles to the GameCube in reverse, and the renderer clamps the slot
+/// to the face's last style. Returns the index into the face's `styles` a slot ends up showing.
+fn gamecube_source_style(slot: usize, style_count: usize) -> usize {
+    style_count - 1 - slot.min(style_count - 1)
+}
+
+/// A face's lightmap patch, with every one of its styles.
+pub struct FacePatch {
+    pub light_ofs: usize,
+    pub styles: [u8; 4],
+    pub width: usize,
+    pub height: usize,
+    /// 4 for bump mapped faces, which store three directional samples after each omnidirectional
+    /// one.
+    pub angle_count: usize,
+    pub luxel_offset: [usize; 2],
+    pub is_flipped: bool,
+}
+
+struct ClusterLightmap {
+    width: usize,
+    height: usize,
+    patches: Vec<FacePatch>,
+    texture: Rc<SrgbTexture2d>,
+    /// The weights of each patch's styles as of the last write, or `None` before the first.
+    written_weights: Option<Vec<[f32; 4]>>,
+}
+
+/// Cluster lightmap textures that are rewritten as light styles change.
+pub struct StyledLightmaps {
+    clusters: HashMap<i16, ClusterLightmap>,
+}
+
+impl StyledLightmaps {
+    pub fn new() -> Self {
+        Self {
+            clusters: HashMap::new(),
+        }
+    }
+
+    pub fn insert(
+        &mut self,
+        cluster: i16,
+        width: usize,
+        height: usize,
+        patches: Vec<FacePatch>,
+        texture: Rc<SrgbTexture2d>,
+    ) {
+        self.clusters.insert(
+            cluster,
+            ClusterLightmap {
+                width,
+                height,
+                patches,
+                texture,
+                written_weights: None,
+            },
+        );
+    }
+
+    /// Rewrites every cluster lightmap whose styles have changed brightness since it was last
+    /// written.
+    pub fn update(&mut self, bsp: Bsp, light_styles: &LightStyles) {
+        for cluster in self.clusters.values_mut() {
+            let weights: Vec<[f32; 4]> = cluster
+                .patches
+                .iter()
+                .map(|patch| light_styles.weights(patch.styles))
+                .collect();
+            if cluster.written_weights.as_ref() == Some(&weights) {
+                continue;
+            }
+            cluster.write(bsp, &weights);
+            cluster.written_weights = Some(weights);
+        }
+    }
+}
+
+impl ClusterLightmap {
+    fn write(&self, bsp: Bsp, weights: &[[f32; 4]]) {
+        let mut data = vec![0u8; 3 * self.width * self.height];
+        for (patch, weights) in self.patches.iter().zip(weights) {
+            let luxel_count = patch.width * patch.height;
+            for src_dy in 0..patch.height {
+                for src_dx in 0..patch.width {
+                    let luxel = patch.width * src_dy + src_dx;
+                    let mut linear = [0.0; 3];
+                    for (style, &weight) in weights.iter().enumerate() {
+                        if weight == 0.0 {
+                            continue;
+                        }
+                        // Each style holds every angle's samples, omnidirectional first.
+                        let src_offset =
+                            patch.light_ofs + 4 * (style * patch.angle_count * luxel_count + luxel);
+                        let sample = bsp.lighting().at_offset(src_offset, 1)[0].to_linear();
+                        for (sum, x) in linear.iter_mut().zip(sample) {
+                            *sum += weight * x;
+                        }
+                    }
+
+                    let (dst_x, dst_y) = if patch.is_flipped {
+                        (
+                            patch.luxel_offset[0] + src_dy,
+                            patch.luxel_offset[1] + src_dx,
+                        )
+                    } else {
+                        (
+                            patch.luxel_offset[0] + src_dx,
+                            patch.luxel_offset[1] + src_dy,
+                        )
+                    };
+                    let dst_offset = 3 * (self.width * dst_y + dst_x);
+                    data[dst_offset..dst_offset + 3]
+                        .copy_from_slice(&ColorRgbExp32::linear_to_srgb8(linear));
+                }
+            }
+        }
+
+        self.texture.write(
+            Rect {
+                left: 0,
+                bottom: 0,
+                width: self.width as u32,
+                height: self.height as u32,
+            },
+            RawImage2d {
+                data: Cow::Owned(data),
+                width: self.width as u32,
+                height: self.height as u32,
+                format: ClientFormat::U8U8U8,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{gamecube_source_style, LightStyleMode, LightStyles, NO_STYLE};
+
+    #[test]
+    fn brightness_follows_patterns() {
+        let mut light_styles = LightStyles::new();
+        light_styles.time = 0.05;
+        assert_eq!(light_styles.brightness(0), 1.0);
+        assert_eq!(light_styles.brightness(4), 1.0);
+        assert_eq!(light_styles.brightness(9), 0.0);
+        assert_eq!(light_styles.brightness(32), 1.0);
+
+        // One frame later.
+        light_styles.time = 0.15;
+        assert_eq!(light_styles.brightness(0), 1.0);
+        assert_eq!(light_styles.brightness(4), 0.0);
+        assert_eq!(light_styles.brightness(2), 1.0 / 12.0);
+    }
+
+    #[test]
+    fn gamecube_slots_count_down_from_the_last_style() {
+        assert_eq!(gamecube_source_style(0, 3), 2);
+        assert_eq!(gamecube_source_style(1, 3), 1);
+        assert_eq!(gamecube_source_style(2, 3), 0);
+        assert_eq!(gamecube_source_style(3, 3), 0);
+        assert_eq!(gamecube_source_style(0, 1), 0);
+        assert_eq!(gamecube_source_style(3, 1), 0);
+    }
+
+    #[test]
+    fn weights_skip_unused_slots() {
+        let mut light_styles = LightStyles::new();
+        light_styles.time = 0.05;
+        let styles = [0, 9, NO_STYLE, NO_STYLE];
+        assert_eq!(light_styles.weights(styles), [1.0, 0.0, 0.0, 0.0]);
+
+        light_styles.mode = LightStyleMode::GameCubeSlot(0);
+        assert_eq!(light_styles.weights(styles), [0.0, 1.0, 0.0, 0.0]);
+        assert_eq!(light_styles.weights([NO_STYLE; 4]), [0.0; 4]);
+    }
+}
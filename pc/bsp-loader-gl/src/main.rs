@@ -1,7 +1,6 @@
 #![deny(unsafe_op_in_unsafe_fn, unused_unsafe)]
 
-use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fs::File;
 use std::hash::Hash;
 use std::path::{Path, PathBuf};
@@ -9,23 +8,25 @@ use std::rc::Rc;
 use std::time::Instant;
 
 use anyhow::{bail, Context, Result};
+use clap::Parser;
 use glium::glutin::dpi::LogicalSize;
 use glium::glutin::event::{DeviceEvent, Event, WindowEvent};
 use glium::glutin::event_loop::{ControlFlow, EventLoop};
 use glium::glutin::window::WindowBuilder;
 use glium::index::PrimitiveType;
-use glium::program::ProgramCreationInput;
-use glium::texture::{ClientFormat, MipmapsOption, RawImage2d, SrgbFormat, SrgbTexture2d};
+use glium::texture::{MipmapsOption, SrgbFormat, SrgbTexture2d};
 use glium::uniforms::{MagnifySamplerFilter, MinifySamplerFilter, Sampler, SamplerWrapFunction};
 use glium::{
     implement_vertex, uniform, BackfaceCullingMode, Depth, DepthTest, Display, DrawParameters,
-    IndexBuffer, Program, Rect, Surface, VertexBuffer,
+    IndexBuffer, Program, Surface, VertexBuffer,
 };
 use memmap::Mmap;
-use nalgebra_glm::{look_at, perspective, radians, rotate, translate, vec1, vec3};
+use nalgebra_glm::{look_at, perspective, radians, rotate, translate, vec1, vec3, Mat4, Vec3};
+use source_reader::asset::dependency_graph::{AssetKind, DependencyGraph};
 use source_reader::asset::vmt::{LightmappedGeneric, Shader, VertexLitGeneric};
 use source_reader::asset::AssetLoader;
-use source_reader::bsp::{self, Bsp};
+use source_reader::bsp::{Bsp, FaceIndex};
+use source_reader::file::directory::DirectoryLoader;
 use source_reader::file::zip::ZipArchiveLoader;
 use source_reader::file::{FallbackFileLoader, FileLoader};
 use source_reader::geometry::convert_vertex;
@@ -34,15 +35,50 @@ use source_reader::vpk::path::VpkPath;
 use source_reader::vpk::Vpk;
 use texture_format::TextureFormat;
 
+use crate::camera_path::{BenchmarkStats, CameraFrame, CameraPath};
+use crate::file_watcher::FileWatcher;
 use crate::game_state::GameState;
+use crate::inspector::Inspector;
+use crate::light_styles::{FacePatch, LightStyles, StyledLightmaps};
+use crate::shaders::{build_model_shaders, build_shaders, ALL_SHADER_FILES};
 use crate::texture::{
     create_texture, create_texture_encoded, AnyTexture2d, CreateCompressedSrgbTexture2dDxt1,
     CreateCompressedSrgbTexture2dDxt5, CreateSrgbTexture2dRgba8,
 };
 
+mod camera_path;
+mod file_watcher;
 mod game_state;
+mod inspector;
+mod light_styles;
+mod model_viewer;
+mod packed_map;
+mod shaders;
 mod texture;
 
+/// Names a directory laid out like the game directory whose loose files take precedence over the
+/// map and VPKs. Materials in it are reloaded when they change.
+const OVERRIDE_DIR_VAR: &str = "BSP_LOADER_GL_OVERRIDE_DIR";
+
+#[derive(Parser)]
+struct Args {
+    /// Records the camera on every frame and writes the path to this file on exit.
+    #[arg(long)]
+    record: Option<PathBuf>,
+    /// Plays back a recorded camera path one frame per recorded frame, then prints frame time
+    /// and draw call statistics and exits.
+    #[arg(long, conflicts_with = "record")]
+    benchmark: Option<PathBuf>,
+    /// Views a map packed for the GameCube by interpreting its display lists, instead of loading
+    /// the BSP.
+    #[arg(long, conflicts_with_all = ["record", "benchmark"])]
+    packed_map: Option<PathBuf>,
+    /// Opens a browser for the models in the VPKs instead of loading a map, starting with this
+    /// model if given, e.g. `models/police.mdl`.
+    #[arg(long, num_args = 0..=1, conflicts_with_all = ["record", "benchmark", "packed_map"])]
+    view_model: Option<Option<String>>,
+}
+
 #[derive(Clone, Copy)]
 struct Vertex {
     position: [f32; 3],
@@ -63,28 +99,23 @@ impl From<source_reader::geometry::Vertex> for Vertex {
 implement_vertex!(Vertex, position, lightmap_coord, texture_coord);
 
 struct GraphicsData {
-    cluster_lightmap_textures: HashMap<i16, SrgbTexture2d>,
+    cluster_lightmap_textures: HashMap<i16, Rc<SrgbTexture2d>>,
+    styled_lightmaps: StyledLightmaps,
     vertices: Vec<Vertex>,
     indices_by_cluster_material: HashMap<i16, HashMap<VpkPath, Vec<u16>>>,
 }
 
 fn main() -> Result<()> {
-    #[cfg(not(target_os = "windows"))]
-    let hl2_base = {
-        let mut hl2_base = PathBuf::from(std::env::var("HOME").unwrap());
-        hl2_base.extend([
-            ".steam",
-            "steam",
-            "steamapps",
-            "common",
-            "Half-Life 2",
-            "hl2",
-        ]);
-        hl2_base
-    };
-    #[cfg(target_os = "windows")]
-    let hl2_base =
-        PathBuf::from("C:\\Program Files (x86)\\Steam\\steamapps\\common\\Half-Life 2\\hl2");
+    let args = Args::parse();
+    if let Some(path) = args.packed_map.as_deref() {
+        return packed_map::run(path);
+    }
+
+    let hl2_base = hl2_base();
+    if let Some(model_path) = args.view_model {
+        return model_viewer::run(&hl2_base, model_path.as_deref());
+    }
+
     let hl2_misc = {
         let vpk_path = hl2_base.join("hl2_misc");
         Rc::new(Vpk::new(&vpk_path).with_context(|| format!("opening vpk {vpk_path:?}"))?)
@@ -97,9 +128,16 @@ fn main() -> Result<()> {
         map_path
     };
     let bsp_file = File::open(map_path)?;
-    let bsp_data = unsafe { Mmap::map(&bsp_file) }?;
-    let bsp = Bsp::new(&bsp_data);
-    let asset_loader = build_asset_loader(&hl2_base, bsp, Rc::clone(&hl2_misc))?;
+    // Leaked so that the asset loader can move into the event loop, which never returns.
+    let bsp_data: &'static Mmap = Box::leak(Box::new(unsafe { Mmap::map(&bsp_file) }?));
+    let bsp = Bsp::new(bsp_data);
+    let override_dir = std::env::var_os(OVERRIDE_DIR_VAR).map(PathBuf::from);
+    let asset_loader = build_asset_loader(
+        &hl2_base,
+        Some(bsp),
+        Rc::clone(&hl2_misc),
+        override_dir.as_deref(),
+    )?;
 
     let events_loop = EventLoop::new();
     let display = Display::new(
@@ -113,18 +151,24 @@ fn main() -> Result<()> {
 
     let GraphicsData {
         cluster_lightmap_textures,
+        mut styled_lightmaps,
         vertices,
         indices_by_cluster_material,
     } = load_graphics_data(&display, bsp, &asset_loader)?;
 
-    let program = build_shaders(&display)?;
+    let mut program = build_shaders(&display)?;
     let vertex_buffer = VertexBuffer::new(&display, &vertices)?;
-    let mut textures_by_path =
-        load_textures(&display, &asset_loader, &indices_by_cluster_material)?;
-    let batches_by_cluster = build_batches_by_cluster(
+    let mut textures_by_path = HashMap::new();
+    load_textures(
         &display,
         &asset_loader,
-        indices_by_cluster_material,
+        &mut textures_by_path,
+        &indices_by_cluster_material,
+    )?;
+    let mut batches_by_cluster = build_batches_by_cluster(
+        &display,
+        &asset_loader,
+        &indices_by_cluster_material,
         &textures_by_path,
     )?;
 
@@ -149,7 +193,7 @@ fn main() -> Result<()> {
     };
     let vvd = source_reader::model::vvd::Vvd::new(&vvd_data);
 
-    let model_program = build_model_shaders(&display)?;
+    let mut model_program = build_model_shaders(&display)?;
     let (model_vertex_data, model_batches) =
         source_reader::model::glium::build_vertex_buffer(&display, &asset_loader, mdl, vtx, vvd);
     let model_vertex_buffer = VertexBuffer::new(&display, &model_vertex_data)?;
@@ -166,7 +210,38 @@ fn main() -> Result<()> {
 
     // End model hack stuff.
 
+    let mut shader_watcher = FileWatcher::new();
+    for shader_file in ALL_SHADER_FILES {
+        shader_watcher.watch(shader_file.path());
+    }
+    let mut material_watcher = FileWatcher::new();
+    if let Some(override_dir) = override_dir.as_deref() {
+        watch_materials(
+            &mut material_watcher,
+            override_dir,
+            &asset_loader,
+            &indices_by_cluster_material,
+        )?;
+    }
+
+    let mut inspector = Inspector::new(
+        &display,
+        &events_loop,
+        indices_by_cluster_material
+            .values()
+            .flat_map(|indices_by_material| indices_by_material.keys().cloned()),
+    );
     let mut game_state = GameState::new();
+    let mut light_styles = LightStyles::new();
+    let mut recording = args.record.map(|path| (path, CameraPath::default()));
+    let mut playback = match args.benchmark.as_deref() {
+        Some(path) => Some((
+            CameraPath::load(path)?.frames.into_iter(),
+            BenchmarkStats::default(),
+        )),
+        None => None,
+    };
+    let mut last_frame_start = Instant::now();
     events_loop.run(move |event, _target, control_flow| match event {
         Event::DeviceEvent { event, .. } => match event {
             DeviceEvent::MouseMotion { delta } => {
@@ -174,8 +249,17 @@ fn main() -> Result<()> {
             }
             _ => (),
         },
+        Event::WindowEvent { event, .. } if inspector.on_event(&event) => (),
         Event::WindowEvent { event, .. } => match event {
-            WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
+            WindowEvent::CloseRequested => {
+                if let Some((path, camera_path)) = &recording {
+                    match camera_path.save(path) {
+                        Ok(()) => println!("Saved {} frames to {path:?}", camera_path.frames.len()),
+                        Err(e) => eprintln!("Failed to save camera path: {e:?}"),
+                    }
+                }
+                *control_flow = ControlFlow::Exit;
+            }
             WindowEvent::MouseInput { button, state, .. } => {
                 game_state.handle_mouse_input(&display, button, state);
             }
@@ -185,9 +269,71 @@ fn main() -> Result<()> {
             _ => (),
         },
         Event::MainEventsCleared => {
-            game_state.step();
+            if !shader_watcher.poll().is_empty() {
+                match (build_shaders(&display), build_model_shaders(&display)) {
+                    (Ok(new_program), Ok(new_model_program)) => {
+                        program = new_program;
+                        model_program = new_model_program;
+                        println!("Reloaded shaders");
+                    }
+                    (Err(e), _) | (_, Err(e)) => eprintln!("Failed to reload shaders: {e:?}"),
+                }
+            }
+            if !material_watcher.poll().is_empty() {
+                match reload_materials(
+                    &display,
+                    &asset_loader,
+                    &mut textures_by_path,
+                    &indices_by_cluster_material,
+                ) {
+                    Ok(new_batches_by_cluster) => {
+                        batches_by_cluster = new_batches_by_cluster;
+                        println!("Reloaded materials");
+                    }
+                    Err(e) => eprintln!("Failed to reload materials: {e:?}"),
+                }
+                if let Some(override_dir) = override_dir.as_deref() {
+                    // Patch materials may have started including something new.
+                    if let Err(e) = watch_materials(
+                        &mut material_watcher,
+                        override_dir,
+                        &asset_loader,
+                        &indices_by_cluster_material,
+                    ) {
+                        eprintln!("Failed to watch materials: {e:?}");
+                    }
+                }
+            }
 
-            draw(
+            match &mut playback {
+                Some((frames, stats)) => match frames.next() {
+                    Some(frame) => frame.apply(&mut game_state),
+                    None => {
+                        println!("{}", stats.report());
+                        *control_flow = ControlFlow::Exit;
+                        return;
+                    }
+                },
+                None => game_state.step(),
+            }
+            if let Some((_, camera_path)) = &mut recording {
+                camera_path
+                    .frames
+                    .push(CameraFrame::from_game_state(&game_state));
+            }
+
+            let cluster = bsp.leaf(bsp.leaf_for_point(game_state.pos)).cluster();
+            inspector.run(
+                &display,
+                &asset_loader,
+                (cluster != -1).then_some(cluster),
+                &cluster_lightmap_textures,
+                &mut light_styles,
+            );
+            light_styles.step();
+            styled_lightmaps.update(bsp, &light_styles);
+
+            let draw_calls = draw(
                 &display,
                 &game_state,
                 &vertex_buffer,
@@ -198,8 +344,15 @@ fn main() -> Result<()> {
                 &model_vertex_buffer,
                 &model_batches,
                 &model_program,
+                &mut inspector,
             );
 
+            let now = Instant::now();
+            if let Some((_, stats)) = &mut playback {
+                stats.record(now - last_frame_start, draw_calls);
+            }
+            last_frame_start = now;
+
             let next_frame_time = Instant::now();
             *control_flow = ControlFlow::WaitUntil(next_frame_time);
         }
@@ -207,23 +360,92 @@ fn main() -> Result<()> {
     })
 }
 
+fn hl2_base() -> PathBuf {
+    #[cfg(not(target_os = "windows"))]
+    {
+        let mut hl2_base = PathBuf::from(std::env::var("HOME").unwrap());
+        hl2_base.extend([
+            ".steam",
+            "steam",
+            "steamapps",
+            "common",
+            "Half-Life 2",
+            "hl2",
+        ]);
+        hl2_base
+    }
+    #[cfg(target_os = "windows")]
+    PathBuf::from("C:\\Program Files (x86)\\Steam\\steamapps\\common\\Half-Life 2\\hl2")
+}
+
+/// Materials come from the override directory, the map's pak file if there is a map, and
+/// `hl2_misc`. Textures come from the same places, but with `hl2_textures` in place of `hl2_misc`.
 fn build_asset_loader<'a>(
     hl2_base: &Path,
-    bsp: Bsp<'a>,
+    bsp: Option<Bsp<'a>>,
     hl2_misc: Rc<Vpk>,
+    override_dir: Option<&Path>,
 ) -> Result<AssetLoader<'a>> {
-    let pak_loader = Rc::new(ZipArchiveLoader::new(bsp.pak_file()));
-    let material_loader = Rc::new(FallbackFileLoader::new(vec![
-        Rc::clone(&pak_loader) as Rc<dyn FileLoader>,
-        hl2_misc,
-    ]));
-    let texture_loader = Rc::new(FallbackFileLoader::new(vec![
-        Rc::clone(&pak_loader) as Rc<dyn FileLoader>,
-        Rc::new(Vpk::new(hl2_base.join("hl2_textures"))?),
-    ]));
+    let override_loader: Option<Rc<dyn FileLoader + 'a>> =
+        override_dir.map(|dir| Rc::new(DirectoryLoader::new(dir.to_path_buf())) as _);
+    let pak_loader: Option<Rc<dyn FileLoader + 'a>> =
+        bsp.map(|bsp| Rc::new(ZipArchiveLoader::new(bsp.pak_file())) as _);
+    let local_loaders = || override_loader.iter().chain(&pak_loader).map(Rc::clone);
+    let material_loader = Rc::new(FallbackFileLoader::new(
+        local_loaders().chain([hl2_misc as _]).collect(),
+    ));
+    let texture_loader = Rc::new(FallbackFileLoader::new(
+        local_loaders()
+            .chain([Rc::new(Vpk::new(hl2_base.join("hl2_textures"))?) as _])
+            .collect(),
+    ));
     Ok(AssetLoader::new(material_loader, texture_loader))
 }
 
+/// Watches the override directory for every material the map uses, including patch includes.
+fn watch_materials(
+    watcher: &mut FileWatcher,
+    override_dir: &Path,
+    asset_loader: &AssetLoader,
+    indices_by_cluster_material: &HashMap<i16, HashMap<VpkPath, Vec<u16>>>,
+) -> Result<()> {
+    let material_paths: BTreeSet<VpkPath> = indices_by_cluster_material
+        .values()
+        .flat_map(|indices_by_material| indices_by_material.keys().cloned())
+        .collect();
+    let graph =
+        DependencyGraph::from_materials(material_paths.into_iter().collect(), asset_loader)?;
+    for (path, node) in graph.iter() {
+        if node.kind() == AssetKind::Material {
+            watcher.watch(override_dir.join(path.as_canonical_path().as_str()));
+        }
+    }
+    Ok(())
+}
+
+/// Rereads every material and rebuilds the batches that depend on them. Textures that are already
+/// loaded are reused.
+fn reload_materials(
+    display: &Display,
+    asset_loader: &AssetLoader,
+    textures_by_path: &mut HashMap<VpkPath, AnyTexture2d>,
+    indices_by_cluster_material: &HashMap<i16, HashMap<VpkPath, Vec<u16>>>,
+) -> Result<HashMap<i16, Vec<Batch>>> {
+    asset_loader.invalidate_materials();
+    load_textures(
+        display,
+        asset_loader,
+        textures_by_path,
+        indices_by_cluster_material,
+    )?;
+    build_batches_by_cluster(
+        display,
+        asset_loader,
+        indices_by_cluster_material,
+        textures_by_path,
+    )
+}
+
 fn load_graphics_data(
     display: &Display,
     bsp: Bsp,
@@ -234,263 +456,148 @@ fn load_graphics_data(
 
     #[derive(Clone, Copy, PartialEq, Eq, Hash)]
     struct VertexKey {
-        face: *const bsp::Face,
+        cluster: i16,
+        face: FaceIndex,
         vertex_index: usize,
     }
 
-    let mut cluster_lightmap_texture_data: HashMap<i16, Vec<u8>> = HashMap::new();
+    let mut patches_by_cluster: HashMap<i16, Vec<FacePatch>> = HashMap::new();
     let mut vertices = Vec::new();
     let mut indices_by_cluster_material: HashMap<i16, HashMap<VpkPath, Vec<u16>>> = HashMap::new();
-    for leaf in bsp.iter_worldspawn_leaves() {
-        let cluster = leaf.cluster();
-        if cluster == -1 {
-            // Leaf is not potentially visible from anywhere.
-            continue;
-        }
+    let mut emitted_vertices_by_source = HashMap::new();
+    let world_faces = bsp
+        .iter_worldspawn_leaves()
+        .filter(|&leaf| {
+            // Skip leaves that are not potentially visible from anywhere.
+            bsp.leaf(leaf).cluster() != -1
+        })
+        .flat_map(|leaf| {
+            let cluster = bsp.leaf(leaf).cluster();
+            bsp.iter_faces_from_leaf(leaf)
+                .map(move |face| (cluster, face, Vec3::zeros()))
+        });
+    for (cluster, face_index, origin) in world_faces.chain(bsp.iter_static_brush_entity_faces()) {
+        let face = bsp.face(face_index);
         let cluster_lightmap = match cluster_lightmaps.get(&cluster) {
             Some(lightmap) => lightmap,
             // TODO: Render non-lightmapped geometry.
             None => continue,
         };
-        let lightmap_texture_data = cluster_lightmap_texture_data
-            .entry(cluster)
-            .or_insert_with(|| vec![0u8; 3 * cluster_lightmap.width * cluster_lightmap.height]);
+        let patches = patches_by_cluster.entry(cluster).or_default();
         let indices_by_material = indices_by_cluster_material.entry(cluster).or_default();
-        let mut emitted_vertices_by_source = HashMap::new();
 
-        for face in bsp.iter_faces_from_leaf(leaf) {
-            if face.light_ofs == -1 || face.tex_info == -1 {
-                // Not a textured lightmapped surface.
-                continue;
-            }
+        if face.light_ofs == -1 || face.tex_info == -1 {
+            // Not a textured lightmapped surface.
+            continue;
+        }
 
-            let lightmap_metadata = &cluster_lightmap.metadata_by_data_offset[&face.light_ofs];
-            let tex_info = &bsp.tex_infos()[face.tex_info as usize];
-            if tex_info.tex_data == -1 {
-                // Not textured.
-                // TODO: Determine whether any such faces need to be drawn.
-                continue;
-            }
+        let lightmap_metadata = &cluster_lightmap.metadata_by_data_offset[&face.light_ofs];
+        let tex_info = &bsp.tex_infos()[face.tex_info as usize];
+        if tex_info.tex_data == -1 {
+            // Not textured.
+            // TODO: Determine whether any such faces need to be drawn.
+            continue;
+        }
 
-            // Write texels to the lightmap.
-            {
-                let patch_width = face.lightmap_texture_size_in_luxels[0] as usize + 1;
-                let patch_height = face.lightmap_texture_size_in_luxels[1] as usize + 1;
-                assert_eq!(lightmap_metadata.luxel_offset[0] % 4, 0);
-                assert_eq!(lightmap_metadata.luxel_offset[1] % 4, 0);
-
-                let mut src_offset = face.light_ofs as usize;
-                for src_dy in 0..patch_height {
-                    for src_dx in 0..patch_width {
-                        let rgb = bsp.lighting().at_offset(src_offset, 1)[0].to_srgb8();
-                        src_offset += 4;
-
-                        let (dst_x, dst_y) = if lightmap_metadata.is_flipped {
-                            (
-                                lightmap_metadata.luxel_offset[0] + src_dy,
-                                lightmap_metadata.luxel_offset[1] + src_dx,
-                            )
-                        } else {
-                            (
-                                lightmap_metadata.luxel_offset[0] + src_dx,
-                                lightmap_metadata.luxel_offset[1] + src_dy,
-                            )
-                        };
-                        let dst_offset = 3 * (cluster_lightmap.width * dst_y + dst_x);
-                        lightmap_texture_data[dst_offset..dst_offset + 3].copy_from_slice(&rgb);
-                    }
+        // Texels are written by `StyledLightmaps` once the light styles are known.
+        assert_eq!(lightmap_metadata.luxel_offset[0] % 4, 0);
+        assert_eq!(lightmap_metadata.luxel_offset[1] % 4, 0);
+        patches.push(FacePatch {
+            light_ofs: face.light_ofs as usize,
+            styles: face.styles,
+            width: face.lightmap_texture_size_in_luxels[0] as usize + 1,
+            height: face.lightmap_texture_size_in_luxels[1] as usize + 1,
+            angle_count: if tex_info.flags & 0x800 != 0 { 4 } else { 1 },
+            luxel_offset: lightmap_metadata.luxel_offset,
+            is_flipped: lightmap_metadata.is_flipped,
+        });
+
+        // This is a textured face.
+        let tex_data = &bsp.tex_datas()[tex_info.tex_data as usize];
+        let material_path = VpkPath::new_with_prefix_and_extension(
+            bsp.tex_data_strings()
+                .get(tex_data.name_string_table_id as usize),
+            "materials",
+            "vmt",
+        );
+        let _material = asset_loader.get_material(&material_path)?;
+        let indices = indices_by_material
+            .entry(material_path.clone())
+            .or_default();
+
+        let mut first_index = None;
+        let mut prev_index = None;
+        for vertex_index in bsp.iter_vertex_indices_from_face(face) {
+            let key = VertexKey {
+                cluster,
+                face: face_index,
+                vertex_index,
+            };
+            let remapped_index = if emitted_vertices_by_source.contains_key(&key) {
+                *emitted_vertices_by_source.get(&key).unwrap()
+            } else {
+                let mut vertex =
+                    convert_vertex(bsp, Some(cluster_lightmap), face, tex_info, vertex_index);
+                for (position, offset) in vertex.position.iter_mut().zip(origin.iter()) {
+                    *position += offset;
                 }
-            }
 
-            // This is a textured face.
-            let tex_data = &bsp.tex_datas()[tex_info.tex_data as usize];
-            let material_path = VpkPath::new_with_prefix_and_extension(
-                bsp.tex_data_strings()
-                    .get(tex_data.name_string_table_id as usize),
-                "materials",
-                "vmt",
-            );
-            let _material = asset_loader.get_material(&material_path)?;
-            let indices = indices_by_material
-                .entry(material_path.clone())
-                .or_default();
-
-            let mut first_index = None;
-            let mut prev_index = None;
-            for vertex_index in bsp.iter_vertex_indices_from_face(face) {
-                let key = VertexKey { face, vertex_index };
-                let remapped_index = if emitted_vertices_by_source.contains_key(&key) {
-                    *emitted_vertices_by_source.get(&key).unwrap()
-                } else {
-                    let vertex =
-                        convert_vertex(bsp, Some(cluster_lightmap), face, tex_info, vertex_index);
-
-                    // Emit the vertex.
-                    let remapped_index = u16::try_from(vertices.len()).unwrap();
-                    vertices.push(Vertex::from(vertex));
-                    emitted_vertices_by_source.insert(key, remapped_index);
-                    remapped_index
-                };
-
-                if first_index.is_none() {
-                    first_index = Some(remapped_index);
-                }
+                // Emit the vertex.
+                let remapped_index = u16::try_from(vertices.len()).unwrap();
+                vertices.push(Vertex::from(vertex));
+                emitted_vertices_by_source.insert(key, remapped_index);
+                remapped_index
+            };
 
-                if let Some(prev_index) = prev_index {
-                    indices.push(first_index.unwrap());
-                    indices.push(prev_index);
-                    indices.push(remapped_index);
-                }
-                prev_index = Some(remapped_index);
+            if first_index.is_none() {
+                first_index = Some(remapped_index);
+            }
+
+            if let Some(prev_index) = prev_index {
+                indices.push(first_index.unwrap());
+                indices.push(prev_index);
+                indices.push(remapped_index);
             }
+            prev_index = Some(remapped_index);
         }
     }
 
-    let cluster_lightmap_textures: HashMap<i16, SrgbTexture2d> = cluster_lightmap_texture_data
-        .into_iter()
-        .map(|(cluster_index, lightmap_texture_data)| {
-            let cluster_lightmap = &cluster_lightmaps[&cluster_index];
-
-            let lightmap_texture = SrgbTexture2d::empty_with_format(
-                display,
-                SrgbFormat::U8U8U8,
-                MipmapsOption::NoMipmap,
-                cluster_lightmap.width as u32,
-                cluster_lightmap.height as u32,
-            )?;
-            lightmap_texture.write(
-                Rect {
-                    left: 0,
-                    bottom: 0,
-                    width: cluster_lightmap.width as u32,
-                    height: cluster_lightmap.height as u32,
-                },
-                RawImage2d {
-                    data: Cow::Owned(lightmap_texture_data),
-                    width: cluster_lightmap.width as u32,
-                    height: cluster_lightmap.height as u32,
-                    format: ClientFormat::U8U8U8,
-                },
-            );
-            Ok((cluster_index, lightmap_texture))
-        })
-        .collect::<Result<_>>()?;
+    let mut cluster_lightmap_textures = HashMap::new();
+    let mut styled_lightmaps = StyledLightmaps::new();
+    for (cluster_index, patches) in patches_by_cluster {
+        let cluster_lightmap = &cluster_lightmaps[&cluster_index];
+        let lightmap_texture = Rc::new(SrgbTexture2d::empty_with_format(
+            display,
+            SrgbFormat::U8U8U8,
+            MipmapsOption::NoMipmap,
+            cluster_lightmap.width as u32,
+            cluster_lightmap.height as u32,
+        )?);
+        styled_lightmaps.insert(
+            cluster_index,
+            cluster_lightmap.width,
+            cluster_lightmap.height,
+            patches,
+            Rc::clone(&lightmap_texture),
+        );
+        cluster_lightmap_textures.insert(cluster_index, lightmap_texture);
+    }
 
     Ok(GraphicsData {
         cluster_lightmap_textures,
+        styled_lightmaps,
         vertices,
         indices_by_cluster_material,
     })
 }
 
-fn build_shaders(display: &Display) -> Result<Program> {
-    const VERTEX_SHADER_SOURCE: &str = r#"
-        #version 330
-
-        uniform mat4 mvp_matrix;
-        uniform vec2 inv_base_map_size;
-
-        in vec3 position;
-        in vec2 lightmap_coord;
-        in vec2 texture_coord;
-
-        out vec2 interpolated_lightmap_coord;
-        out vec2 interpolated_texture_coord;
-
-        void main() {
-            gl_Position = mvp_matrix * vec4(position, 1.0);
-            interpolated_lightmap_coord = lightmap_coord;
-            interpolated_texture_coord = texture_coord * inv_base_map_size;
-        }
-    "#;
-    const FRAGMENT_SHADER_SOURCE: &str = r#"
-        #version 330
-
-        uniform sampler2D lightmap;
-        uniform sampler2D base_map;
-
-        in vec2 interpolated_lightmap_coord;
-        in vec2 interpolated_texture_coord;
-
-        out vec4 rendered_color;
-
-        void main() {
-            vec4 lightmap_color = vec4(texture(lightmap, interpolated_lightmap_coord).rgb, 1.0);
-            vec4 base_color = texture(base_map, interpolated_texture_coord);
-            rendered_color = lightmap_color * base_color * 4.59479;
-        }
-    "#;
-    Ok(Program::new(
-        display,
-        ProgramCreationInput::SourceCode {
-            vertex_shader: VERTEX_SHADER_SOURCE,
-            tessellation_control_shader: None,
-            tessellation_evaluation_shader: None,
-            geometry_shader: None,
-            fragment_shader: FRAGMENT_SHADER_SOURCE,
-            transform_feedback_varyings: None,
-            outputs_srgb: false,
-            uses_point_size: false,
-        },
-    )?)
-}
-
-fn build_model_shaders(display: &Display) -> Result<Program> {
-    const VERTEX_SHADER_SOURCE: &str = r#"
-        #version 330
-
-        uniform mat4 mvp_matrix;
-
-        in vec3 position;
-        in vec3 normal;
-        in vec2 tex_coord;
-
-        out vec3 interpolated_normal;
-        out vec2 interpolated_tex_coord;
-
-        void main() {
-            gl_Position = mvp_matrix * vec4(position, 1.0);
-            interpolated_normal = normal;
-            interpolated_tex_coord = tex_coord;
-        }
-    "#;
-    const FRAGMENT_SHADER_SOURCE: &str = r#"
-        #version 330
-
-        uniform sampler2D base_map;
-
-        in vec3 interpolated_normal;
-        in vec2 interpolated_tex_coord;
-
-        out vec4 rendered_color;
-
-        void main() {
-            rendered_color = vec4(
-                texture(base_map, interpolated_tex_coord).rgb,
-                1.0);
-        }
-    "#;
-    Ok(Program::new(
-        display,
-        ProgramCreationInput::SourceCode {
-            vertex_shader: VERTEX_SHADER_SOURCE,
-            tessellation_control_shader: None,
-            tessellation_evaluation_shader: None,
-            geometry_shader: None,
-            fragment_shader: FRAGMENT_SHADER_SOURCE,
-            transform_feedback_varyings: None,
-            outputs_srgb: false,
-            uses_point_size: false,
-        },
-    )?)
-}
-
 fn load_textures(
     display: &Display,
     asset_loader: &AssetLoader,
+    textures_by_path: &mut HashMap<VpkPath, AnyTexture2d>,
     indices_by_cluster_material: &HashMap<i16, HashMap<VpkPath, Vec<u16>>>,
-) -> Result<HashMap<VpkPath, AnyTexture2d>> {
-    let mut textures_by_path = HashMap::new();
+) -> Result<()> {
+    let mut base_texture_paths = BTreeSet::new();
     for (_cluster_index, indices_by_material) in indices_by_cluster_material {
         for material_path in indices_by_material.keys() {
             let material = asset_loader.get_material(material_path)?;
@@ -499,17 +606,17 @@ fn load_textures(
             }) = material.shader()
             {
                 if !textures_by_path.contains_key(base_texture_path) {
-                    load_texture(
-                        display,
-                        asset_loader,
-                        &mut textures_by_path,
-                        base_texture_path,
-                    )?;
+                    base_texture_paths.insert(base_texture_path.clone());
                 }
             }
         }
     }
-    Ok(textures_by_path)
+
+    asset_loader.prefetch_textures(&base_texture_paths)?;
+    for base_texture_path in &base_texture_paths {
+        load_texture(display, asset_loader, textures_by_path, base_texture_path)?;
+    }
+    Ok(())
 }
 
 fn load_texture(
@@ -564,6 +671,7 @@ fn load_model_textures(
 
 struct Batch {
     index_buffer: IndexBuffer<u16>,
+    material_path: VpkPath,
     base_map_path: VpkPath,
     inv_base_map_size: [f32; 2],
 }
@@ -571,23 +679,24 @@ struct Batch {
 fn build_batches_by_cluster(
     display: &Display,
     asset_loader: &AssetLoader,
-    indices_by_cluster_material: HashMap<i16, HashMap<VpkPath, Vec<u16>>>,
+    indices_by_cluster_material: &HashMap<i16, HashMap<VpkPath, Vec<u16>>>,
     textures_by_path: &HashMap<VpkPath, AnyTexture2d>,
 ) -> Result<HashMap<i16, Vec<Batch>>> {
     let mut batches_by_cluster = HashMap::new();
-    for (cluster_index, indices_by_material) in indices_by_cluster_material {
+    for (&cluster_index, indices_by_material) in indices_by_cluster_material {
         let mut batches = Vec::new();
         for (material_path, indices) in indices_by_material {
-            let material = asset_loader.get_material(&material_path)?;
+            let material = asset_loader.get_material(material_path)?;
             if let Shader::LightmappedGeneric(LightmappedGeneric {
                 base_texture_path, ..
             }) = material.shader()
             {
                 let index_buffer =
-                    IndexBuffer::new(display, PrimitiveType::TrianglesList, &indices)?;
+                    IndexBuffer::new(display, PrimitiveType::TrianglesList, indices)?;
                 if let Some(base_map_texture) = textures_by_path.get(base_texture_path) {
                     batches.push(Batch {
                         index_buffer,
+                        material_path: material_path.clone(),
                         base_map_path: base_texture_path.to_owned(),
                         inv_base_map_size: [
                             1.0 / base_map_texture.width() as f32,
@@ -621,18 +730,7 @@ impl ModelBatch {
     }
 }
 
-fn draw(
-    display: &Display,
-    game_state: &GameState,
-    vertex_buffer: &VertexBuffer<Vertex>,
-    batches_by_cluster: &HashMap<i16, Vec<Batch>>,
-    program: &Program,
-    textures_by_path: &HashMap<VpkPath, AnyTexture2d>,
-    cluster_lightmap_textures: &HashMap<i16, SrgbTexture2d>,
-    model_vertex_buffer: &VertexBuffer<source_reader::model::glium::Vertex>,
-    model_batches: &[ModelBatch],
-    model_program: &Program,
-) {
+fn view_projection(display: &Display, game_state: &GameState) -> Mat4 {
     let dimensions = display.get_framebuffer_dimensions();
     let proj = perspective(
         dimensions.0 as f32 / dimensions.1 as f32,
@@ -648,13 +746,30 @@ fn draw(
     let view = rotate(&view, game_state.pitch, &vec3(0.0, 1.0, 0.0));
     let view = rotate(&view, game_state.yaw, &vec3(0.0, 0.0, 1.0));
     let view = translate(&view, &-game_state.pos);
-    let mvp_matrix = proj * view;
+    proj * view
+}
+
+fn draw(
+    display: &Display,
+    game_state: &GameState,
+    vertex_buffer: &VertexBuffer<Vertex>,
+    batches_by_cluster: &HashMap<i16, Vec<Batch>>,
+    program: &Program,
+    textures_by_path: &HashMap<VpkPath, AnyTexture2d>,
+    cluster_lightmap_textures: &HashMap<i16, Rc<SrgbTexture2d>>,
+    model_vertex_buffer: &VertexBuffer<source_reader::model::glium::Vertex>,
+    model_batches: &[ModelBatch],
+    model_program: &Program,
+    inspector: &mut Inspector,
+) -> usize {
+    let mvp_matrix = view_projection(display, game_state);
 
     let mut target = display.draw();
     target.clear_color_and_depth((0.5, 0.5, 0.5, 1.0), 1.0);
     for (cluster_index, batches) in batches_by_cluster {
         for batch in batches {
             let base_texture = &textures_by_path[&batch.base_map_path];
+            let highlight = inspector.highlight(&batch.material_path);
             match base_texture {
                 AnyTexture2d::Texture2d(x) => target
                     .draw(
@@ -663,7 +778,7 @@ fn draw(
                         &program,
                         &uniform! {
                             mvp_matrix: mvp_matrix.data.0,
-                            lightmap: Sampler::new(&cluster_lightmap_textures[cluster_index])
+                            lightmap: Sampler::new(&*cluster_lightmap_textures[cluster_index])
                                 .wrap_function(SamplerWrapFunction::Clamp)
                                 .magnify_filter(MagnifySamplerFilter::Linear)
                                 .minify_filter(MinifySamplerFilter::Nearest),
@@ -673,6 +788,7 @@ fn draw(
                                 .minify_filter(MinifySamplerFilter::LinearMipmapNearest)
                                 .anisotropy(16),
                             inv_base_map_size: batch.inv_base_map_size,
+                            highlight: highlight,
                         },
                         &DrawParameters {
                             depth: Depth {
@@ -692,7 +808,7 @@ fn draw(
                         &program,
                         &uniform! {
                             mvp_matrix: mvp_matrix.data.0,
-                            lightmap: Sampler::new(&cluster_lightmap_textures[cluster_index])
+                            lightmap: Sampler::new(&*cluster_lightmap_textures[cluster_index])
                                 .wrap_function(SamplerWrapFunction::Clamp)
                                 .magnify_filter(MagnifySamplerFilter::Linear)
                                 .minify_filter(MinifySamplerFilter::Nearest),
@@ -702,6 +818,7 @@ fn draw(
                                 .minify_filter(MinifySamplerFilter::LinearMipmapLinear)
                                 .anisotropy(16),
                             inv_base_map_size: batch.inv_base_map_size,
+                            highlight: highlight,
                         },
                         &DrawParameters {
                             depth: Depth {
@@ -721,7 +838,7 @@ fn draw(
                         &program,
                         &uniform! {
                             mvp_matrix: mvp_matrix.data.0,
-                            lightmap: Sampler::new(&cluster_lightmap_textures[cluster_index])
+                            lightmap: Sampler::new(&*cluster_lightmap_textures[cluster_index])
                                 .wrap_function(SamplerWrapFunction::Clamp)
                                 .magnify_filter(MagnifySamplerFilter::Linear)
                                 .minify_filter(MinifySamplerFilter::Nearest),
@@ -731,6 +848,7 @@ fn draw(
                                 .minify_filter(MinifySamplerFilter::LinearMipmapLinear)
                                 .anisotropy(16),
                             inv_base_map_size: batch.inv_base_map_size,
+                            highlight: highlight,
                         },
                         &DrawParameters {
                             depth: Depth {
@@ -750,7 +868,7 @@ fn draw(
                         &program,
                         &uniform! {
                             mvp_matrix: mvp_matrix.data.0,
-                            lightmap: Sampler::new(&cluster_lightmap_textures[cluster_index])
+                            lightmap: Sampler::new(&*cluster_lightmap_textures[cluster_index])
                                 .wrap_function(SamplerWrapFunction::Clamp)
                                 .magnify_filter(MagnifySamplerFilter::Linear)
                                 .minify_filter(MinifySamplerFilter::Nearest),
@@ -760,6 +878,7 @@ fn draw(
                                 .minify_filter(MinifySamplerFilter::LinearMipmapLinear)
                                 .anisotropy(16),
                             inv_base_map_size: batch.inv_base_map_size,
+                            highlight: highlight,
                         },
                         &DrawParameters {
                             depth: Depth {
@@ -878,5 +997,8 @@ fn draw(
         }
     }
 
+    inspector.paint(display, &mut target);
     target.finish().unwrap();
+
+    batches_by_cluster.values().map(Vec::len).sum::<usize>() + model_batches.len()
 }
@@ -0,0 +1,679 @@
+//! Browses the models in `hl2_misc` and draws one at a time, so prop conversion issues can be
+//! debugged without loading a map that happens to contain the prop.
+//!
+//! Drag with the left button to orbit and scroll to zoom. Bones are drawn in the bind pose as
+//! lines to their parents, and attachments as small crosses, both on top of the mesh.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use egui_glium::EguiGlium;
+use glium::glutin::dpi::LogicalSize;
+use glium::glutin::event::{
+    DeviceEvent, ElementState, Event, MouseButton, MouseScrollDelta, WindowEvent,
+};
+use glium::glutin::event_loop::{ControlFlow, EventLoop, EventLoopWindowTarget};
+use glium::glutin::window::{CursorGrabMode, WindowBuilder};
+use glium::index::{NoIndices, PrimitiveType};
+use glium::texture::SrgbTexture2d;
+use glium::uniforms::{MagnifySamplerFilter, MinifySamplerFilter, Sampler, SamplerWrapFunction};
+use glium::{
+    implement_vertex, uniform, BackfaceCullingMode, Depth, DepthTest, Display, DrawParameters,
+    IndexBuffer, Program, Surface, VertexBuffer,
+};
+use nalgebra_glm::{look_at, make_vec3, perspective, radians, vec1, vec3, Mat4, Vec3};
+use source_reader::asset::vmt::{Shader, UnlitGeneric, VertexLitGeneric};
+use source_reader::asset::vtf::Vtf;
+use source_reader::asset::AssetLoader;
+use source_reader::file::FileLoader;
+use source_reader::model::find_material;
+use source_reader::model::glium::{build_vertex_buffer, Vertex};
+use source_reader::model::mdl::Mdl;
+use source_reader::model::vtx::Vtx;
+use source_reader::model::vvd::Vvd;
+use source_reader::vpk::path::VpkPath;
+use source_reader::vpk::Vpk;
+use texture_format::{TextureBuf, TextureFormat};
+
+use crate::file_watcher::FileWatcher;
+use crate::shaders::{
+    build_line_shaders, build_model_shaders, LINE_FRAGMENT_SHADER, LINE_VERTEX_SHADER,
+    MODEL_FRAGMENT_SHADER, MODEL_VERTEX_SHADER,
+};
+use crate::texture::{CreateGliumTexture, CreateSrgbTexture2dRgba8};
+use crate::{build_asset_loader, OVERRIDE_DIR_VAR};
+
+const BONE_COLOR: [f32; 3] = [1.0, 1.0, 0.0];
+const ATTACHMENT_COLOR: [f32; 3] = [0.0, 1.0, 1.0];
+/// Each arm of an attachment's cross is this fraction of the model's radius.
+const ATTACHMENT_CROSS_SCALE: f32 = 0.05;
+
+#[derive(Clone, Copy)]
+struct LineVertex {
+    position: [f32; 3],
+    color: [f32; 3],
+}
+
+implement_vertex!(LineVertex, position, color);
+
+/// Opens the viewer, showing `initial_model` if given. Paths are resolved like `models/police.mdl`,
+/// but the prefix and extension may be omitted.
+pub fn run(hl2_base: &Path, initial_model: Option<&str>) -> Result<()> {
+    let hl2_misc = {
+        let vpk_path = hl2_base.join("hl2_misc");
+        Rc::new(Vpk::new(&vpk_path).with_context(|| format!("opening vpk {vpk_path:?}"))?)
+    };
+    let model_paths = hl2_misc.paths_with_extension("mdl");
+    let override_dir = std::env::var_os(OVERRIDE_DIR_VAR).map(PathBuf::from);
+    let asset_loader = build_asset_loader(
+        hl2_base,
+        None,
+        Rc::clone(&hl2_misc),
+        override_dir.as_deref(),
+    )?;
+
+    let events_loop = EventLoop::new();
+    let display = Display::new(
+        WindowBuilder::new()
+            .with_inner_size(LogicalSize::new(1024.0, 768.0))
+            .with_title("bsp-loader-gl (models)"),
+        glium::glutin::ContextBuilder::new(),
+        &events_loop,
+    )
+    .unwrap();
+
+    let mut model_program = build_model_shaders(&display)?;
+    let mut line_program = build_line_shaders(&display)?;
+    let missing_texture = missing_texture(&display)?;
+    let mut textures = HashMap::new();
+    let mut model = match initial_model {
+        Some(name) => Some(ViewedModel::load(
+            &display,
+            &asset_loader,
+            &hl2_misc,
+            &mut textures,
+            &VpkPath::new_with_prefix_and_extension(name, "models", "mdl"),
+        )?),
+        None => None,
+    };
+    let mut camera = match &model {
+        Some(model) => OrbitCamera::framing(model.center, model.radius),
+        None => OrbitCamera::framing(Vec3::zeros(), 64.0),
+    };
+    let mut browser = Browser::new(&display, &events_loop, model_paths);
+
+    let mut shader_watcher = FileWatcher::new();
+    for shader_file in [
+        &MODEL_VERTEX_SHADER,
+        &MODEL_FRAGMENT_SHADER,
+        &LINE_VERTEX_SHADER,
+        &LINE_FRAGMENT_SHADER,
+    ] {
+        shader_watcher.watch(shader_file.path());
+    }
+    events_loop.run(move |event, _target, control_flow| match event {
+        Event::DeviceEvent {
+            event: DeviceEvent::MouseMotion { delta },
+            ..
+        } => camera.handle_mouse_motion(delta),
+        Event::WindowEvent { event, .. } if browser.on_event(&event) => (),
+        Event::WindowEvent { event, .. } => match event {
+            WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
+            WindowEvent::MouseInput { button, state, .. } => {
+                camera.handle_mouse_input(&display, button, state);
+            }
+            WindowEvent::MouseWheel { delta, .. } => camera.handle_mouse_wheel(delta),
+            _ => (),
+        },
+        Event::MainEventsCleared => {
+            if !shader_watcher.poll().is_empty() {
+                match (build_model_shaders(&display), build_line_shaders(&display)) {
+                    (Ok(new_model_program), Ok(new_line_program)) => {
+                        model_program = new_model_program;
+                        line_program = new_line_program;
+                        println!("Reloaded shaders");
+                    }
+                    (Err(e), _) | (_, Err(e)) => eprintln!("Failed to reload shaders: {e:?}"),
+                }
+            }
+
+            if let Some(path) = browser.run(&display, model.as_ref()) {
+                match ViewedModel::load(&display, &asset_loader, &hl2_misc, &mut textures, &path) {
+                    Ok(new_model) => {
+                        camera = OrbitCamera::framing(new_model.center, new_model.radius);
+                        model = Some(new_model);
+                        browser.error = None;
+                    }
+                    Err(e) => browser.error = Some(format!("{e:?}")),
+                }
+            }
+
+            draw(
+                &display,
+                &camera,
+                model.as_ref(),
+                &textures,
+                &missing_texture,
+                &model_program,
+                &line_program,
+                &mut browser,
+            );
+            *control_flow = ControlFlow::WaitUntil(Instant::now());
+        }
+        _ => (),
+    })
+}
+
+/// A model's GPU resources, plus what the browser shows about it.
+struct ViewedModel {
+    path: VpkPath,
+    vertex_buffer: VertexBuffer<Vertex>,
+    batches: Vec<ModelBatch>,
+    /// `None` if the model has no lines of that kind, since glium can't draw an empty buffer.
+    bone_lines: Option<VertexBuffer<LineVertex>>,
+    attachment_lines: Option<VertexBuffer<LineVertex>>,
+    center: Vec3,
+    radius: f32,
+    bone_names: Vec<String>,
+    attachment_names: Vec<String>,
+    /// One line per texture, naming the material it resolved to.
+    materials: Vec<String>,
+}
+
+struct ModelBatch {
+    index_buffer: IndexBuffer<u16>,
+    /// `None` draws the batch with the missing texture.
+    base_texture_path: Option<VpkPath>,
+}
+
+impl ViewedModel {
+    fn load(
+        display: &Display,
+        asset_loader: &AssetLoader,
+        hl2_misc: &Vpk,
+        textures: &mut HashMap<VpkPath, SrgbTexture2d>,
+        mdl_path: &VpkPath,
+    ) -> Result<Self> {
+        let load = |extension: &str| -> Result<Vec<u8>> {
+            let path = sibling_path(mdl_path, extension);
+            hl2_misc
+                .load_file(&path)?
+                .with_context(|| format!("asset not found: {path}"))
+        };
+        let mdl_data = load("mdl")?;
+        let vtx_data = load("dx90.vtx")?;
+        let vvd_data = load("vvd")?;
+        let mdl = Mdl::new(&mdl_data);
+
+        let (vertices, batches) = build_vertex_buffer(
+            display,
+            asset_loader,
+            mdl,
+            Vtx::new(&vtx_data),
+            Vvd::new(&vvd_data),
+        );
+        let batches = batches
+            .into_iter()
+            .map(|batch| {
+                let base_texture_path =
+                    batch.base_map.and_then(|material| match material.shader() {
+                        Shader::VertexLitGeneric(VertexLitGeneric { base_texture_path })
+                        | Shader::UnlitGeneric(UnlitGeneric {
+                            base_texture_path, ..
+                        }) => Some(base_texture_path.clone()),
+                        shader => {
+                            eprintln!(
+                                "WARNING: Drawing {} untextured because its shader is {}",
+                                material.path(),
+                                shader.name(),
+                            );
+                            None
+                        }
+                    });
+                let base_texture_path = base_texture_path.filter(|path| {
+                    match load_texture(display, asset_loader, textures, path) {
+                        Ok(()) => true,
+                        Err(e) => {
+                            eprintln!("WARNING: Failed to load texture {path}: {e:?}");
+                            false
+                        }
+                    }
+                });
+                ModelBatch {
+                    index_buffer: batch.index_buffer,
+                    base_texture_path,
+                }
+            })
+            .collect();
+
+        let header = mdl.header();
+        let hull_min = make_vec3(&header.hull_min);
+        let hull_max = make_vec3(&header.hull_max);
+        let center = (hull_min + hull_max) / 2.0;
+        let radius = ((hull_max - hull_min).norm() / 2.0).max(1.0);
+
+        let bones = mdl.bones();
+        let bone_origins: Vec<[f32; 3]> = bones
+            .iter()
+            .map(|bone| bone.transform_to_model([0.0; 3]))
+            .collect();
+        let mut bone_lines = Vec::new();
+        for (bone, &origin) in bones.iter().zip(&bone_origins) {
+            if let Ok(parent) = usize::try_from(bone.parent) {
+                for position in [bone_origins[parent], origin] {
+                    bone_lines.push(LineVertex {
+                        position,
+                        color: BONE_COLOR,
+                    });
+                }
+            }
+        }
+        let mut attachment_lines = Vec::new();
+        for attachment in mdl.attachments() {
+            let origin =
+                bones[attachment.localbone as usize].transform_to_model(attachment.origin());
+            for axis in 0..3 {
+                for sign in [-1.0, 1.0] {
+                    let mut position = origin;
+                    position[axis] += sign * ATTACHMENT_CROSS_SCALE * radius;
+                    attachment_lines.push(LineVertex {
+                        position,
+                        color: ATTACHMENT_COLOR,
+                    });
+                }
+            }
+        }
+
+        let materials = (0..mdl.textures().len())
+            .map(|texture_index| {
+                let name = mdl.textures()[texture_index].name(mdl);
+                match find_material(asset_loader, mdl, texture_index) {
+                    Ok(Some(material)) => format!("{name}: {}", material.path()),
+                    Ok(None) => format!("{name}: no VMT"),
+                    Err(e) => format!("{name}: {e}"),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            path: mdl_path.clone(),
+            vertex_buffer: VertexBuffer::new(display, &vertices)?,
+            batches,
+            bone_lines: line_buffer(display, &bone_lines)?,
+            attachment_lines: line_buffer(display, &attachment_lines)?,
+            center,
+            radius,
+            bone_names: bones
+                .iter()
+                .map(|bone| bone.name(mdl).to_string())
+                .collect(),
+            attachment_names: mdl
+                .attachments()
+                .iter()
+                .map(|attachment| {
+                    format!(
+                        "{} on {}",
+                        attachment.name(mdl),
+                        bones[attachment.localbone as usize].name(mdl),
+                    )
+                })
+                .collect(),
+            materials,
+        })
+    }
+}
+
+/// The file alongside a model with the same stem, like its `dx90.vtx`.
+fn sibling_path(mdl_path: &VpkPath, extension: &str) -> VpkPath {
+    let stem = mdl_path
+        .as_canonical_path()
+        .as_str()
+        .strip_suffix(".mdl")
+        .unwrap();
+    VpkPath::new_with_prefix_and_extension(stem, "", extension)
+}
+
+fn line_buffer(
+    display: &Display,
+    vertices: &[LineVertex],
+) -> Result<Option<VertexBuffer<LineVertex>>> {
+    if vertices.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(VertexBuffer::new(display, vertices)?))
+}
+
+/// Uploads a texture if it isn't already loaded. Everything is transcoded to RGBA8 so the viewer
+/// handles any format the models use, at some cost in load time.
+fn load_texture(
+    display: &Display,
+    asset_loader: &AssetLoader,
+    textures: &mut HashMap<VpkPath, SrgbTexture2d>,
+    texture_path: &VpkPath,
+) -> Result<()> {
+    if let Entry::Vacant(entry) = textures.entry(texture_path.clone()) {
+        let texture = asset_loader.get_texture(texture_path)?;
+        entry.insert(upload_texture(display, &texture)?);
+    }
+    Ok(())
+}
+
+fn upload_texture(display: &Display, vtf: &Vtf) -> Result<SrgbTexture2d> {
+    let mips = vtf.mips();
+    let texture = CreateSrgbTexture2dRgba8::create_texture(
+        display,
+        vtf.width() as u32,
+        vtf.height() as u32,
+        mips.len() as u32,
+    )?;
+    for (mip_level, faces) in mips.iter().enumerate() {
+        CreateSrgbTexture2dRgba8::write_mip(
+            &texture,
+            mip_level as u32,
+            &TextureBuf::transcode(faces[0].as_slice(), TextureFormat::Rgba8),
+        )?;
+    }
+    Ok(texture)
+}
+
+/// A magenta and black checkerboard for batches without a usable base texture.
+fn missing_texture(display: &Display) -> Result<SrgbTexture2d> {
+    let texture = CreateSrgbTexture2dRgba8::create_texture(display, 2, 2, 1)?;
+    CreateSrgbTexture2dRgba8::write_mip(
+        &texture,
+        0,
+        &TextureBuf::new(
+            TextureFormat::Rgba8,
+            2,
+            2,
+            [
+                [255, 0, 255, 255],
+                [0, 0, 0, 255],
+                [0, 0, 0, 255],
+                [255, 0, 255, 255],
+            ]
+            .concat(),
+        ),
+    )?;
+    Ok(texture)
+}
+
+/// Orbits a target point in Source's Z-up space.
+struct OrbitCamera {
+    target: Vec3,
+    distance: f32,
+    /// Angle of the eye around the target, counterclockwise from +X when viewed from above.
+    yaw: f32,
+    pitch: f32,
+    dragging: bool,
+}
+
+impl OrbitCamera {
+    /// Frames a sphere, viewed from slightly above and in front. Models face +X.
+    fn framing(center: Vec3, radius: f32) -> Self {
+        Self {
+            target: center,
+            distance: 2.5 * radius,
+            yaw: 0.0,
+            pitch: radians(&vec1(15.0)).x,
+            dragging: false,
+        }
+    }
+
+    fn eye(&self) -> Vec3 {
+        self.target
+            + self.distance
+                * vec3(
+                    self.pitch.cos() * self.yaw.cos(),
+                    self.pitch.cos() * self.yaw.sin(),
+                    self.pitch.sin(),
+                )
+    }
+
+    fn handle_mouse_motion(&mut self, delta: (f64, f64)) {
+        if self.dragging {
+            self.yaw = (self.yaw - 0.01 * delta.0 as f32).rem_euclid(std::f32::consts::TAU);
+            self.pitch = (self.pitch + 0.01 * delta.1 as f32)
+                .clamp(radians(&vec1(-89.0)).x, radians(&vec1(89.0)).x);
+        }
+    }
+
+    fn handle_mouse_input(&mut self, display: &Display, button: MouseButton, state: ElementState) {
+        if button == MouseButton::Left {
+            self.dragging = state == ElementState::Pressed;
+            display
+                .gl_window()
+                .window()
+                .set_cursor_grab(if self.dragging {
+                    CursorGrabMode::Confined
+                } else {
+                    CursorGrabMode::None
+                })
+                .unwrap();
+        }
+    }
+
+    fn handle_mouse_wheel(&mut self, delta: MouseScrollDelta) {
+        let lines = match delta {
+            MouseScrollDelta::LineDelta(_, y) => y,
+            MouseScrollDelta::PixelDelta(position) => position.y as f32 / 50.0,
+        };
+        self.distance = (self.distance * 0.9f32.powf(lines)).max(1.0);
+    }
+
+    fn view_projection(&self, display: &Display) -> Mat4 {
+        let dimensions = display.get_framebuffer_dimensions();
+        let proj = perspective(
+            dimensions.0 as f32 / dimensions.1 as f32,
+            radians(&vec1(60.0)).x,
+            0.01 * self.distance,
+            100.0 * self.distance,
+        );
+        let view = look_at(&self.eye(), &self.target, &vec3(0.0, 0.0, 1.0));
+        proj * view
+    }
+}
+
+/// A side panel listing every model, and a window describing the one being viewed.
+struct Browser {
+    egui: EguiGlium,
+    filter: String,
+    model_paths: Vec<VpkPath>,
+    show_bones: bool,
+    show_attachments: bool,
+    /// Why the last model picked failed to load.
+    error: Option<String>,
+}
+
+impl Browser {
+    fn new<E>(
+        display: &Display,
+        event_loop: &EventLoopWindowTarget<E>,
+        model_paths: Vec<VpkPath>,
+    ) -> Self {
+        Self {
+            egui: EguiGlium::new(display, event_loop),
+            filter: String::new(),
+            model_paths,
+            show_bones: true,
+            show_attachments: true,
+            error: None,
+        }
+    }
+
+    /// Returns true if the event was consumed by the browser and shouldn't move the camera.
+    fn on_event(&mut self, event: &WindowEvent) -> bool {
+        self.egui.on_event(event).consumed
+    }
+
+    /// Lays out the browser for this frame. Returns the model the user picked, if any.
+    fn run(&mut self, display: &Display, model: Option<&ViewedModel>) -> Option<VpkPath> {
+        let Self {
+            egui,
+            filter,
+            model_paths,
+            show_bones,
+            show_attachments,
+            error,
+        } = self;
+        let mut picked = None;
+        egui.run(display, |ctx| {
+            egui::SidePanel::left("models").show(ctx, |ui| {
+                ui.heading("Models");
+                ui.text_edit_singleline(filter);
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for path in model_paths.iter() {
+                        let name = path.to_string();
+                        if !name.contains(filter.as_str()) {
+                            continue;
+                        }
+                        let is_selected = model.is_some_and(|model| model.path == *path);
+                        if ui.selectable_label(is_selected, name).clicked() && !is_selected {
+                            picked = Some(path.clone());
+                        }
+                    }
+                });
+            });
+
+            egui::Window::new("Model").show(ctx, |ui| {
+                if let Some(error) = error {
+                    ui.colored_label(egui::Color32::RED, error.as_str());
+                }
+                let model = match model {
+                    Some(model) => model,
+                    None => {
+                        ui.label("Pick a model from the list");
+                        return;
+                    }
+                };
+                ui.label(model.path.to_string());
+                ui.checkbox(show_bones, "Show bones");
+                ui.checkbox(show_attachments, "Show attachments");
+                for (heading, lines) in [
+                    ("Materials", &model.materials),
+                    ("Bones", &model.bone_names),
+                    ("Attachments", &model.attachment_names),
+                ] {
+                    ui.collapsing(format!("{heading} ({})", lines.len()), |ui| {
+                        for line in lines {
+                            ui.monospace(line);
+                        }
+                    });
+                }
+            });
+        });
+        picked
+    }
+
+    fn paint<T: Surface>(&mut self, display: &Display, target: &mut T) {
+        self.egui.paint(display, target);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw(
+    display: &Display,
+    camera: &OrbitCamera,
+    model: Option<&ViewedModel>,
+    textures: &HashMap<VpkPath, SrgbTexture2d>,
+    missing_texture: &SrgbTexture2d,
+    model_program: &Program,
+    line_program: &Program,
+    browser: &mut Browser,
+) {
+    let mut target = display.draw();
+    target.clear_color_and_depth((0.5, 0.5, 0.5, 1.0), 1.0);
+    if let Some(model) = model {
+        let mvp_matrix = camera.view_projection(display);
+        for batch in &model.batches {
+            let texture = batch
+                .base_texture_path
+                .as_ref()
+                .map_or(missing_texture, |path| &textures[path]);
+            target
+                .draw(
+                    &model.vertex_buffer,
+                    &batch.index_buffer,
+                    model_program,
+                    &uniform! {
+                        mvp_matrix: mvp_matrix.data.0,
+                        base_map: Sampler::new(texture)
+                            .wrap_function(SamplerWrapFunction::Repeat)
+                            .magnify_filter(MagnifySamplerFilter::Linear)
+                            .minify_filter(MinifySamplerFilter::LinearMipmapLinear)
+                            .anisotropy(16),
+                    },
+                    &DrawParameters {
+                        depth: Depth {
+                            test: DepthTest::IfLess,
+                            write: true,
+                            ..Default::default()
+                        },
+                        backface_culling: BackfaceCullingMode::CullCounterClockwise,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+        }
+
+        // Drawn without depth testing so the skeleton shows through the mesh.
+        for (show, lines) in [
+            (browser.show_bones, &model.bone_lines),
+            (browser.show_attachments, &model.attachment_lines),
+        ] {
+            if let Some(lines) = lines.as_ref().filter(|_| show) {
+                target
+                    .draw(
+                        lines,
+                        NoIndices(PrimitiveType::LinesList),
+                        line_program,
+                        &uniform! { mvp_matrix: mvp_matrix.data.0 },
+                        &DrawParameters {
+                            line_width: Some(2.0),
+                            ..Default::default()
+                        },
+                    )
+                    .unwrap();
+            }
+        }
+    }
+
+    browser.paint(display, &mut target);
+    target.finish().unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra_glm::vec3;
+    use source_reader::vpk::path::VpkPath;
+
+    use super::{sibling_path, OrbitCamera};
+
+    #[test]
+    fn sibling_paths_share_the_stem() {
+        let mdl_path =
+            VpkPath::new_with_prefix_and_extension("props_c17/bench01a", "models", "mdl");
+        assert_eq!(
+            sibling_path(&mdl_path, "dx90.vtx").to_string(),
+            "models/props_c17/bench01a.dx90.vtx",
+        );
+        assert_eq!(
+            sibling_path(&mdl_path, "vvd").to_string(),
+            "models/props_c17/bench01a.vvd",
+        );
+    }
+
+    #[test]
+    fn framing_views_the_front_from_above() {
+        let camera = OrbitCamera::framing(vec3(0.0, 0.0, 10.0), 10.0);
+        let eye = camera.eye();
+        assert!(eye.x > 0.0);
+        assert!(eye.y.abs() < 1e-4);
+        assert!(eye.z > 10.0);
+        assert!(((eye - camera.target).norm() - 25.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,1162 @@
+//! Draws the map data that inception-pack writes for the GameCube, by interpreting its bytecode
+//! and GX display lists, so packer output can be checked without a console.
+//!
+//! World brushes, displacements, static props, and overlays are drawn for every cluster at once.
+//! Lightmaps and vertex colors aren't decoded yet, so everything is fullbright, and displacements
+//! that blend between two textures show only the first.
+
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+use anyhow::{anyhow, bail, Context, Result};
+use byteorder::{BigEndian, ByteOrder};
+use glium::glutin::dpi::LogicalSize;
+use glium::glutin::event::{DeviceEvent, Event, WindowEvent};
+use glium::glutin::event_loop::{ControlFlow, EventLoop};
+use glium::glutin::window::WindowBuilder;
+use glium::index::PrimitiveType;
+use glium::texture::SrgbTexture2d;
+use glium::uniforms::{MagnifySamplerFilter, MinifySamplerFilter, Sampler, SamplerWrapFunction};
+use glium::{
+    uniform, BackfaceCullingMode, Blend, Depth, DepthTest, Display, DrawParameters, IndexBuffer,
+    Program, Surface, VertexBuffer,
+};
+use gx::bp::{BpInterleavedTexReg, BpTexImageRegD};
+use inception_render_common::bytecode::{BytecodeOp, BytecodeReader};
+use inception_render_common::map_data::{
+    shared_texture_path, PackedMapFile, RelocationTableEntry, SharedTextureTableEntry,
+    TextureTableEntry,
+};
+use inception_render_common::vertex_format::{VertexFormat, VertexFormatDescriptor};
+use texture_format::{TextureBuf, TextureFormat};
+
+use crate::game_state::GameState;
+use crate::shaders::{build_packed_shaders, PACKED_FRAGMENT_SHADER, PACKED_VERTEX_SHADER};
+use crate::texture::{CreateGliumTexture, CreateSrgbTexture2dRgba8};
+use crate::view_projection;
+use crate::Vertex;
+
+/// The kinds of geometry a packed map draws, each from its own bytecode and display list sections.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GeometryKind {
+    /// World brushes, in six modes per cluster. Mode 3 is alpha-blended, and modes 1 and 3 take
+    /// alpha from the aux texture.
+    Cluster,
+    /// Displacements, in two modes: lightmapped and blended between two textures.
+    Displacement,
+    /// Static props, in two modes per cluster. Mode 1 takes alpha from the aux texture.
+    Prop,
+    /// Overlays and decals, alpha-blended, in the same two modes as props.
+    Overlay,
+}
+
+impl GeometryKind {
+    fn is_translucent(self, mode: u8) -> bool {
+        match self {
+            Self::Cluster => mode == 3,
+            Self::Displacement | Self::Prop => false,
+            Self::Overlay => true,
+        }
+    }
+
+    fn takes_aux_alpha(self, mode: u8) -> bool {
+        match self {
+            Self::Cluster => mode == 1 || mode == 3,
+            Self::Displacement => false,
+            Self::Prop | Self::Overlay => mode == 1,
+        }
+    }
+}
+
+/// One kind of geometry's bytecode and the display lists it draws.
+struct GeometrySections {
+    kind: GeometryKind,
+    /// Bytecode index ranges, each with the mode it draws in.
+    ranges: Vec<(u8, [u32; 2])>,
+    byte_code: Vec<u32>,
+    display_lists: Vec<u8>,
+}
+
+/// The sections of a packed map needed to draw it, decoded from big-endian.
+pub struct PackedMap {
+    position_data: Vec<u8>,
+    texture_coord_data: Vec<u8>,
+    displacement_position_data: Vec<u8>,
+    displacement_texture_coordinate_data: Vec<u8>,
+    geometry: Vec<GeometrySections>,
+    vertex_format_table: Vec<VertexFormatDescriptor>,
+    texture_table: Vec<TextureTableEntry>,
+    texture_data: Vec<u8>,
+    shared_texture_table: Vec<SharedTextureTableEntry>,
+    /// Images of the textures flagged `FLAG_SHARED`, by texture ID.
+    shared_textures: BTreeMap<u16, Vec<u8>>,
+}
+
+impl PackedMap {
+    /// Loads a packed map along with the shared textures it uses, which inception-pack writes next
+    /// to the `maps` directory.
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = fs::read(path).with_context(|| format!("Reading packed map {:?}", path))?;
+        let mut map =
+            Self::parse(&data).with_context(|| format!("Parsing packed map {:?}", path))?;
+        let root = path
+            .parent()
+            .and_then(Path::parent)
+            .unwrap_or(Path::new("."));
+        map.load_shared_textures(root)?;
+        Ok(map)
+    }
+
+    /// Parses a packed map and relocates its texture references.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let file = PackedMapFile::new(data).map_err(|e| anyhow!("{:?}", e))?;
+
+        let cluster_geometry_table = words(file.section("cluster_geometry_table"))
+            .chunks_exact(12)
+            .flat_map(mode_ranges)
+            .collect();
+        let displacement_table = words(file.section("displacement_table"))
+            .chunks_exact(2)
+            .enumerate()
+            .map(|(mode, range)| (mode as u8, [range[0], range[1]]))
+            .collect();
+        let overlay_table = words(file.section("overlay_table"))
+            .chunks_exact(4)
+            .flat_map(mode_ranges)
+            .collect();
+        let prop_table = words(file.section("prop_table"))
+            .chunks_exact(4)
+            .flat_map(mode_ranges)
+            .collect();
+        let geometry = vec![
+            GeometrySections {
+                kind: GeometryKind::Cluster,
+                ranges: cluster_geometry_table,
+                byte_code: words(file.section("cluster_geometry_byte_code")),
+                display_lists: file.section("cluster_geometry_display_lists").to_vec(),
+            },
+            GeometrySections {
+                kind: GeometryKind::Displacement,
+                ranges: displacement_table,
+                byte_code: words(file.section("displacement_byte_code")),
+                display_lists: file.section("displacement_display_lists").to_vec(),
+            },
+            GeometrySections {
+                kind: GeometryKind::Prop,
+                ranges: prop_table,
+                byte_code: words(file.section("prop_byte_code")),
+                display_lists: file.section("prop_display_lists").to_vec(),
+            },
+            GeometrySections {
+                kind: GeometryKind::Overlay,
+                ranges: overlay_table,
+                byte_code: words(file.section("overlay_byte_code")),
+                display_lists: file.section("overlay_display_lists").to_vec(),
+            },
+        ];
+
+        // Descriptors are all single bytes, so they read the same on any host.
+        let vertex_format_table = file
+            .section("vertex_format_table")
+            .chunks_exact(std::mem::size_of::<VertexFormatDescriptor>())
+            .map(bytemuck::pod_read_unaligned)
+            .collect();
+        let texture_table = file
+            .section("texture_table")
+            .chunks_exact(16)
+            .map(|entry| TextureTableEntry {
+                width: BigEndian::read_u16(&entry[0..]),
+                height: BigEndian::read_u16(&entry[2..]),
+                mip_count: entry[4],
+                flags: entry[5],
+                format: entry[6],
+                _padding: 0,
+                start_offset: BigEndian::read_u32(&entry[8..]),
+                end_offset: BigEndian::read_u32(&entry[12..]),
+            })
+            .collect();
+        let shared_texture_table = file
+            .section("shared_texture_table")
+            .chunks_exact(28)
+            .map(|entry| SharedTextureTableEntry {
+                texture_id: BigEndian::read_u32(&entry[0..]),
+                size: BigEndian::read_u32(&entry[4..]),
+                crc32: BigEndian::read_u32(&entry[8..]),
+                hash: entry[12..28].try_into().unwrap(),
+            })
+            .collect();
+        let relocation_table: Vec<_> = file
+            .section("relocation_table")
+            .chunks_exact(8)
+            .map(|entry| RelocationTableEntry {
+                display_list_offset: BigEndian::read_u32(&entry[0..]),
+                texture_id: BigEndian::read_u16(&entry[4..]),
+                display_lists: entry[6],
+                _padding: 0,
+            })
+            .collect();
+
+        let mut map = Self {
+            position_data: file.section("position_data").to_vec(),
+            texture_coord_data: file.section("texture_coord_data").to_vec(),
+            displacement_position_data: file.section("displacement_position_data").to_vec(),
+            displacement_texture_coordinate_data: file
+                .section("displacement_texture_coordinate_data")
+                .to_vec(),
+            geometry,
+            vertex_format_table,
+            texture_table,
+            texture_data: file.section("texture_data").to_vec(),
+            shared_texture_table,
+            shared_textures: BTreeMap::new(),
+        };
+        map.relocate(&relocation_table)?;
+        Ok(map)
+    }
+
+    /// Patches each BP TexImageRegD write in the relocation table to hold its texture ID. The
+    /// console patches in the texture's physical address instead; here the interpreter looks the
+    /// ID up when it reaches the write.
+    fn relocate(&mut self, relocation_table: &[RelocationTableEntry]) -> Result<()> {
+        for entry in relocation_table {
+            if entry.texture_id as usize >= self.texture_table.len() {
+                bail!("relocation of undefined texture {}", entry.texture_id);
+            }
+            let kind = match entry.display_lists {
+                RelocationTableEntry::CLUSTER_GEOMETRY => GeometryKind::Cluster,
+                RelocationTableEntry::DISPLACEMENT => GeometryKind::Displacement,
+                RelocationTableEntry::OVERLAY => GeometryKind::Overlay,
+                RelocationTableEntry::PROP => GeometryKind::Prop,
+                x => bail!("relocation into unknown display lists {}", x),
+            };
+            let display_lists = &mut self
+                .geometry
+                .iter_mut()
+                .find(|sections| sections.kind == kind)
+                .unwrap()
+                .display_lists;
+            let offset = entry.display_list_offset as usize;
+            let Some(bytes) = display_lists.get_mut(offset..offset + 4) else {
+                bail!("{:?} relocation at {:#x} is out of bounds", kind, offset);
+            };
+            let value = BigEndian::read_u32(bytes);
+            BigEndian::write_u32(bytes, value & 0xff000000 | entry.texture_id as u32);
+        }
+        Ok(())
+    }
+
+    /// Reads the image of each shared texture from under `root` and checks it against its entry.
+    fn load_shared_textures(&mut self, root: &Path) -> Result<()> {
+        for entry in &self.shared_texture_table {
+            let path = root.join(shared_texture_path(&entry.hash));
+            let data =
+                fs::read(&path).with_context(|| format!("Reading shared texture {:?}", path))?;
+            if !entry.matches(&data) {
+                bail!("shared texture {:?} doesn't match its checksum", path);
+            }
+            self.shared_textures.insert(entry.texture_id as u16, data);
+        }
+        Ok(())
+    }
+
+    pub fn texture_table(&self) -> &[TextureTableEntry] {
+        &self.texture_table
+    }
+
+    /// Decodes every mip of a texture to RGBA8.
+    pub fn decode_texture(&self, texture_id: u16) -> Result<Vec<TextureBuf>> {
+        let entry = &self.texture_table[texture_id as usize];
+        let format = match entry.format {
+            1 => TextureFormat::GxTfI8,
+            3 => TextureFormat::GxTfIa8,
+            6 => TextureFormat::GxTfRgba8,
+            14 => TextureFormat::GxTfCmpr,
+            x => bail!("texture {} has unexpected format {}", texture_id, x),
+        };
+        let (data, start_offset, end_offset) = if entry.flags & TextureTableEntry::FLAG_SHARED != 0
+        {
+            let Some(data) = self.shared_textures.get(&texture_id) else {
+                bail!("shared texture {} wasn't loaded", texture_id);
+            };
+            (data, 0, data.len())
+        } else {
+            (
+                &self.texture_data,
+                entry.start_offset as usize,
+                entry.end_offset as usize,
+            )
+        };
+        let mut offset = start_offset;
+        let mut mips = Vec::new();
+        for mip_level in 0..entry.mip_count {
+            let width = (entry.width as usize >> mip_level).max(1);
+            let height = (entry.height as usize >> mip_level).max(1);
+            let size = format.metrics().encoded_size(width, height);
+            let Some(data) = data.get(offset..offset + size) else {
+                bail!("texture {} mip {} is out of bounds", texture_id, mip_level);
+            };
+            let mip = TextureBuf::new(format, width, height, data.to_vec());
+            mips.push(TextureBuf::transcode(mip.as_slice(), TextureFormat::Rgba8));
+            offset += size;
+        }
+        if offset > end_offset {
+            bail!("texture {} mips overrun its entry", texture_id);
+        }
+        Ok(mips)
+    }
+
+    /// Runs the bytecode and display lists of every kind of geometry, collecting the triangles
+    /// they draw.
+    pub fn interpret(&self) -> Result<Geometry> {
+        let mut geometry = Geometry::default();
+        for sections in &self.geometry {
+            for (index, &(mode, [start, end])) in sections.ranges.iter().enumerate() {
+                let mut state = DrawState {
+                    kind: sections.kind,
+                    mode,
+                    ..Default::default()
+                };
+                let Some(byte_code) = sections.byte_code.get(start as usize..end as usize) else {
+                    bail!(
+                        "{:?} bytecode range {} is out of bounds",
+                        sections.kind,
+                        index
+                    );
+                };
+                for op in BytecodeReader::new(byte_code) {
+                    match op {
+                        BytecodeOp::Draw {
+                            display_list_offset,
+                            display_list_size,
+                        } => {
+                            let offset = display_list_offset as usize;
+                            let Some(display_list) = sections
+                                .display_lists
+                                .get(offset..offset + display_list_size as usize)
+                            else {
+                                bail!("display list at {:#x} is out of bounds", offset);
+                            };
+                            self.interpret_display_list(display_list, &mut state, &mut geometry)
+                                .with_context(|| {
+                                    format!(
+                                        "Interpreting {:?} display list at {:#x}",
+                                        sections.kind, offset,
+                                    )
+                                })?;
+                        }
+                        BytecodeOp::SetAlphaCompare {
+                            compare_type,
+                            reference,
+                            ..
+                        } => {
+                            state.alpha_threshold = (compare_type
+                                == BytecodeOp::ALPHA_COMPARE_TYPE_GEQUAL)
+                                .then_some(reference);
+                        }
+                        BytecodeOp::SetVertexDesc { .. } | BytecodeOp::SetFaceIndex { .. } => (),
+                    }
+                }
+            }
+        }
+        Ok(geometry)
+    }
+
+    fn interpret_display_list(
+        &self,
+        mut data: &[u8],
+        state: &mut DrawState,
+        geometry: &mut Geometry,
+    ) -> Result<()> {
+        let base_image_addr = BpTexImageRegD::addr_for_image(1).unwrap();
+        let aux_image_addr = BpTexImageRegD::addr_for_image(2).unwrap();
+
+        while let Some((&opcode, rest)) = data.split_first() {
+            data = rest;
+            match opcode {
+                0x00 => (),
+                0x61 => {
+                    let value = take(&mut data, 4)?;
+                    let value = BigEndian::read_u32(value);
+                    let addr = (value >> 24) as u8;
+                    let texture_id = (value & 0x00ffffff) as u16;
+                    if addr == base_image_addr {
+                        state.base_texture_id = Some(texture_id);
+                    } else if addr == aux_image_addr {
+                        state.aux_texture_id = Some(texture_id);
+                    }
+                }
+                0x08 => {
+                    take(&mut data, 5)?;
+                }
+                0x10 => {
+                    let count = BigEndian::read_u16(take(&mut data, 2)?) as usize + 1;
+                    take(&mut data, 2 + 4 * count)?;
+                }
+                _ => {
+                    let primitive = opcode & 0xf8;
+                    let vertex_format = self.vertex_format(opcode & 0x07)?;
+                    let vertex_size = vertex_format.descriptor().vertex_size();
+                    let count = BigEndian::read_u16(take(&mut data, 2)?) as usize;
+                    let first = u32::try_from(geometry.vertices.len()).unwrap();
+                    for _ in 0..count {
+                        let vertex_data = take(&mut data, vertex_size)?;
+                        let vertex = match vertex_format {
+                            VertexFormat::Brush => self.read_brush_vertex(vertex_data)?,
+                            VertexFormat::Displacement => {
+                                self.read_displacement_vertex(vertex_data)?
+                            }
+                            _ => read_overlay_vertex(vertex_data),
+                        };
+                        geometry.vertices.push(vertex);
+                    }
+                    let indices = geometry.batch(state)?;
+                    let count = count as u32;
+                    match primitive {
+                        // Quads
+                        0x80 => {
+                            for quad in (first..first + count).step_by(4) {
+                                indices.extend([quad, quad + 1, quad + 2]);
+                                indices.extend([quad, quad + 2, quad + 3]);
+                            }
+                        }
+                        // Triangles
+                        0x90 => indices.extend(first..first + count),
+                        // TriangleStrip
+                        0x98 => {
+                            for i in 2..count {
+                                let i = first + i;
+                                if (i - first) % 2 == 0 {
+                                    indices.extend([i - 2, i - 1, i]);
+                                } else {
+                                    indices.extend([i - 1, i - 2, i]);
+                                }
+                            }
+                        }
+                        // TriangleFan
+                        0xa0 => {
+                            for i in 2..count {
+                                indices.extend([first, first + i - 1, first + i]);
+                            }
+                        }
+                        _ => bail!("unexpected display list opcode {:#04x}", opcode),
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Looks up a draw's vertex format, checking that the map lays it out the way this
+    /// interpreter reads it.
+    fn vertex_format(&self, index: u8) -> Result<VertexFormat> {
+        let Some(format) = [
+            VertexFormat::Brush,
+            VertexFormat::Displacement,
+            VertexFormat::Overlay,
+        ]
+        .into_iter()
+        .find(|&format| format as u8 == index) else {
+            bail!("unsupported vertex format {}", index);
+        };
+        match self
+            .vertex_format_table
+            .iter()
+            .find(|descriptor| descriptor.vertex_format == index)
+        {
+            Some(descriptor) if *descriptor == format.descriptor() => Ok(format),
+            Some(_) => bail!("vertex format {:?} has an unexpected layout", format),
+            None => bail!("vertex format {:?} is missing from the map", format),
+        }
+    }
+
+    /// Reads a `VertexFormat::Brush` vertex: indexed position, indexed normal, direct lightmap
+    /// coordinates, and indexed base texture coordinates.
+    fn read_brush_vertex(&self, data: &[u8]) -> Result<Vertex> {
+        Ok(Vertex {
+            position: read_indexed_position(&self.position_data, &data[0..])?,
+            lightmap_coord: read_lightmap_coord(&data[4..]),
+            texture_coord: read_indexed_texture_coord(&self.texture_coord_data, &data[8..])?,
+        })
+    }
+
+    /// Reads a `VertexFormat::Displacement` vertex: indexed position, indexed vertex color, direct
+    /// lightmap coordinates, and two indexed sets of base texture coordinates. Only the first set
+    /// is used.
+    fn read_displacement_vertex(&self, data: &[u8]) -> Result<Vertex> {
+        Ok(Vertex {
+            position: read_indexed_position(&self.displacement_position_data, &data[0..])?,
+            lightmap_coord: read_lightmap_coord(&data[4..]),
+            texture_coord: read_indexed_texture_coord(
+                &self.displacement_texture_coordinate_data,
+                &data[8..],
+            )?,
+        })
+    }
+}
+
+/// Decodes a section of big-endian words.
+fn words(section: &[u8]) -> Vec<u32> {
+    section.chunks_exact(4).map(BigEndian::read_u32).collect()
+}
+
+/// Splits a table entry's bytecode index ranges by mode.
+fn mode_ranges(entry: &[u32]) -> impl Iterator<Item = (u8, [u32; 2])> + '_ {
+    entry
+        .chunks_exact(2)
+        .enumerate()
+        .map(|(mode, range)| (mode as u8, [range[0], range[1]]))
+}
+
+fn read_indexed_position(position_data: &[u8], index: &[u8]) -> Result<[f32; 3]> {
+    let index = BigEndian::read_u16(index) as usize;
+    let Some(position) = position_data.get(12 * index..12 * index + 12) else {
+        bail!("position index {} is out of bounds", index);
+    };
+    Ok([
+        BigEndian::read_f32(&position[0..]),
+        BigEndian::read_f32(&position[4..]),
+        BigEndian::read_f32(&position[8..]),
+    ])
+}
+
+/// Reads base texture coordinates, stored as unsigned 8.8 fixed point.
+fn read_indexed_texture_coord(texture_coord_data: &[u8], index: &[u8]) -> Result<[f32; 2]> {
+    let index = BigEndian::read_u16(index) as usize;
+    let Some(texture_coord) = texture_coord_data.get(4 * index..4 * index + 4) else {
+        bail!("texture coord index {} is out of bounds", index);
+    };
+    Ok([
+        BigEndian::read_u16(&texture_coord[0..]) as f32 / 256.0,
+        BigEndian::read_u16(&texture_coord[2..]) as f32 / 256.0,
+    ])
+}
+
+/// Reads direct lightmap coordinates, stored as unsigned 1.15 fixed point.
+fn read_lightmap_coord(data: &[u8]) -> [f32; 2] {
+    [
+        BigEndian::read_u16(&data[0..]) as f32 / 32768.0,
+        BigEndian::read_u16(&data[2..]) as f32 / 32768.0,
+    ]
+}
+
+/// Reads a `VertexFormat::Overlay` vertex: direct position, baked lighting as a vertex color, and
+/// base texture coordinates. Overlays have no lightmap.
+fn read_overlay_vertex(data: &[u8]) -> Vertex {
+    Vertex {
+        position: [
+            BigEndian::read_f32(&data[0..]),
+            BigEndian::read_f32(&data[4..]),
+            BigEndian::read_f32(&data[8..]),
+        ],
+        lightmap_coord: [0.0, 0.0],
+        texture_coord: [
+            BigEndian::read_f32(&data[15..]),
+            BigEndian::read_f32(&data[19..]),
+        ],
+    }
+}
+
+fn take<'a>(data: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if data.len() < len {
+        bail!("display list ends mid-command");
+    }
+    let (taken, rest) = data.split_at(len);
+    *data = rest;
+    Ok(taken)
+}
+
+/// GX state that carries across the display lists of one bytecode range.
+struct DrawState {
+    kind: GeometryKind,
+    mode: u8,
+    base_texture_id: Option<u16>,
+    aux_texture_id: Option<u16>,
+    alpha_threshold: Option<u8>,
+}
+
+impl Default for DrawState {
+    fn default() -> Self {
+        Self {
+            kind: GeometryKind::Cluster,
+            mode: 0,
+            base_texture_id: None,
+            aux_texture_id: None,
+            alpha_threshold: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BatchKey {
+    pub kind: GeometryKind,
+    pub mode: u8,
+    pub base_texture_id: u16,
+    /// Only set in modes that read alpha from the aux texture.
+    pub aux_texture_id: Option<u16>,
+    pub alpha_threshold: Option<u8>,
+}
+
+impl BatchKey {
+    pub fn is_translucent(&self) -> bool {
+        self.kind.is_translucent(self.mode)
+    }
+}
+
+#[derive(Default)]
+pub struct Geometry {
+    pub vertices: Vec<Vertex>,
+    /// Triangle lists, in draw order.
+    pub indices_by_batch: BTreeMap<BatchKey, Vec<u32>>,
+}
+
+impl Geometry {
+    fn batch(&mut self, state: &DrawState) -> Result<&mut Vec<u32>> {
+        let Some(base_texture_id) = state.base_texture_id else {
+            bail!("draw with no base texture bound");
+        };
+        let aux_texture_id = if state.kind.takes_aux_alpha(state.mode) {
+            match state.aux_texture_id {
+                Some(id) => Some(id),
+                None => bail!(
+                    "{:?} draw in mode {} with no aux texture bound",
+                    state.kind,
+                    state.mode
+                ),
+            }
+        } else {
+            None
+        };
+        Ok(self
+            .indices_by_batch
+            .entry(BatchKey {
+                kind: state.kind,
+                mode: state.mode,
+                base_texture_id,
+                aux_texture_id,
+                alpha_threshold: state.alpha_threshold,
+            })
+            .or_default())
+    }
+}
+
+struct Batch {
+    key: BatchKey,
+    index_buffer: IndexBuffer<u32>,
+}
+
+/// Opens a window showing the packed map at `path`.
+pub fn run(path: &Path) -> Result<()> {
+    let map = PackedMap::load(path)?;
+    let geometry = map.interpret()?;
+
+    let events_loop = EventLoop::new();
+    let display = Display::new(
+        WindowBuilder::new()
+            .with_inner_size(LogicalSize::new(1024.0, 768.0))
+            .with_title("bsp-loader-gl (packed)"),
+        glium::glutin::ContextBuilder::new(),
+        &events_loop,
+    )
+    .unwrap();
+
+    let mut program = build_packed_shaders(&display)?;
+    let vertex_buffer = VertexBuffer::new(&display, &geometry.vertices)?;
+    let mut textures = BTreeMap::new();
+    let mut batches = Vec::new();
+    for (&key, indices) in &geometry.indices_by_batch {
+        for texture_id in [Some(key.base_texture_id), key.aux_texture_id]
+            .into_iter()
+            .flatten()
+        {
+            if let Entry::Vacant(entry) = textures.entry(texture_id) {
+                entry.insert(
+                    upload_texture(&display, &map, texture_id)
+                        .with_context(|| format!("Loading texture {}", texture_id))?,
+                );
+            }
+        }
+        batches.push(Batch {
+            key,
+            index_buffer: IndexBuffer::new(&display, PrimitiveType::TrianglesList, indices)?,
+        });
+    }
+    // Blended batches go last, over everything opaque. The sort is stable, so each half keeps its
+    // order.
+    batches.sort_by_key(|batch| batch.key.is_translucent());
+    println!(
+        "Loaded {} vertices in {} batches with {} textures",
+        geometry.vertices.len(),
+        batches.len(),
+        textures.len(),
+    );
+
+    let mut shader_watcher = crate::file_watcher::FileWatcher::new();
+    for shader_file in [&PACKED_VERTEX_SHADER, &PACKED_FRAGMENT_SHADER] {
+        shader_watcher.watch(shader_file.path());
+    }
+    let mut game_state = GameState::new();
+    events_loop.run(move |event, _target, control_flow| match event {
+        Event::DeviceEvent {
+            event: DeviceEvent::MouseMotion { delta },
+            ..
+        } => game_state.handle_mouse_motion(delta),
+        Event::WindowEvent { event, .. } => match event {
+            WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
+            WindowEvent::MouseInput { button, state, .. } => {
+                game_state.handle_mouse_input(&display, button, state);
+            }
+            WindowEvent::KeyboardInput { input, .. } => {
+                game_state.handle_keyboard_input(input);
+            }
+            _ => (),
+        },
+        Event::MainEventsCleared => {
+            if !shader_watcher.poll().is_empty() {
+                match build_packed_shaders(&display) {
+                    Ok(new_program) => {
+                        program = new_program;
+                        println!("Reloaded shaders");
+                    }
+                    Err(e) => eprintln!("Failed to reload shaders: {e:?}"),
+                }
+            }
+            game_state.step();
+            draw(
+                &display,
+                &game_state,
+                &map,
+                &vertex_buffer,
+                &batches,
+                &textures,
+                &program,
+            );
+            *control_flow = ControlFlow::WaitUntil(Instant::now());
+        }
+        _ => (),
+    })
+}
+
+fn upload_texture(display: &Display, map: &PackedMap, texture_id: u16) -> Result<SrgbTexture2d> {
+    let mips = map.decode_texture(texture_id)?;
+    let texture = CreateSrgbTexture2dRgba8::create_texture(
+        display,
+        mips[0].width() as u32,
+        mips[0].height() as u32,
+        mips.len() as u32,
+    )?;
+    for (mip_level, mip) in mips.iter().enumerate() {
+        CreateSrgbTexture2dRgba8::write_mip(&texture, mip_level as u32, mip)?;
+    }
+    Ok(texture)
+}
+
+fn sampler<'a>(
+    map: &PackedMap,
+    textures: &'a BTreeMap<u16, SrgbTexture2d>,
+    texture_id: u16,
+) -> Sampler<'a, SrgbTexture2d> {
+    let entry = &map.texture_table()[texture_id as usize];
+    let wrap = |clamp_flag| {
+        if entry.flags & clamp_flag != 0 {
+            SamplerWrapFunction::Clamp
+        } else {
+            SamplerWrapFunction::Repeat
+        }
+    };
+    let mut sampler = Sampler::new(&textures[&texture_id])
+        .magnify_filter(MagnifySamplerFilter::Linear)
+        .minify_filter(MinifySamplerFilter::LinearMipmapLinear)
+        .anisotropy(16);
+    sampler.1.wrap_function = (
+        wrap(TextureTableEntry::FLAG_CLAMP_S),
+        wrap(TextureTableEntry::FLAG_CLAMP_T),
+        SamplerWrapFunction::Repeat,
+    );
+    sampler
+}
+
+fn draw(
+    display: &Display,
+    game_state: &GameState,
+    map: &PackedMap,
+    vertex_buffer: &VertexBuffer<Vertex>,
+    batches: &[Batch],
+    textures: &BTreeMap<u16, SrgbTexture2d>,
+    program: &Program,
+) {
+    let mvp_matrix = view_projection(display, game_state);
+
+    let mut target = display.draw();
+    target.clear_color_and_depth((0.5, 0.5, 0.5, 1.0), 1.0);
+    for batch in batches {
+        let translucent = batch.key.is_translucent();
+        let base_map = sampler(map, textures, batch.key.base_texture_id);
+        let aux_map = sampler(
+            map,
+            textures,
+            batch
+                .key
+                .aux_texture_id
+                .unwrap_or(batch.key.base_texture_id),
+        );
+        target
+            .draw(
+                vertex_buffer,
+                &batch.index_buffer,
+                program,
+                &uniform! {
+                    mvp_matrix: mvp_matrix.data.0,
+                    base_map: base_map,
+                    aux_map: aux_map,
+                    alpha_from_aux: batch.key.aux_texture_id.is_some(),
+                    // Zero never discards.
+                    alpha_threshold: batch.key.alpha_threshold.map_or(0.0, |x| x as f32 / 255.0),
+                },
+                &DrawParameters {
+                    depth: Depth {
+                        test: DepthTest::IfLess,
+                        write: !translucent,
+                        ..Default::default()
+                    },
+                    blend: if translucent {
+                        Blend::alpha_blending()
+                    } else {
+                        Blend::default()
+                    },
+                    backface_culling: BackfaceCullingMode::CullCounterClockwise,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+    }
+    target.finish().unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use byteorder::{BigEndian, WriteBytesExt};
+    use gx::display_list::{Command, DisplayList, GxPrimitive, Reference};
+    use inception_render_common::bytecode::BytecodeOp;
+    use inception_render_common::map_data::{
+        ClusterGeometryTableEntry, DisplacementTableEntry, OverlayTableEntry, OwnedMapData,
+        RelocationTableEntry, TextureTableEntry, WriteTo,
+    };
+    use inception_render_common::vertex_format::{VertexFormat, VertexFormatDescriptor};
+
+    use super::{BatchKey, GeometryKind, PackedMap};
+
+    fn texture_table_entry() -> TextureTableEntry {
+        TextureTableEntry {
+            width: 8,
+            height: 8,
+            mip_count: 1,
+            flags: 0,
+            format: 14,
+            _padding: 0,
+            start_offset: 0,
+            end_offset: 32,
+        }
+    }
+
+    fn vertex_format_table() -> Vec<VertexFormatDescriptor> {
+        [
+            VertexFormat::Brush,
+            VertexFormat::Displacement,
+            VertexFormat::Overlay,
+        ]
+        .map(VertexFormat::descriptor)
+        .to_vec()
+    }
+
+    /// Appends a display list that binds `textures` to images 1, 2, ... and then draws one
+    /// triangle, adding its texture references to `relocation_table`. Returns the bytecode that
+    /// draws it.
+    fn append_triangle(
+        display_lists: &mut Vec<u8>,
+        display_lists_kind: u8,
+        relocation_table: &mut Vec<RelocationTableEntry>,
+        textures: &[u16],
+        vertex_format: VertexFormat,
+        vertex_data: Vec<u8>,
+    ) -> Vec<u32> {
+        let mut display_list = DisplayList::new();
+        for (image, &texture_id) in (1..).zip(textures) {
+            display_list
+                .commands
+                .push(Command::write_bp_tex_image_reg_d_reference(image, texture_id).unwrap());
+        }
+        display_list.commands.push(Command::Draw {
+            primitive: GxPrimitive::Triangles,
+            vertex_format: vertex_format as u8,
+            vertex_count: 3,
+            vertex_data,
+        });
+        display_list.pad_to_alignment();
+
+        let display_list_offset = display_lists.len() as u32;
+        display_list
+            .write_to(display_lists, |display_lists, reference| {
+                let Reference::Texture(texture_id) = reference;
+                relocation_table.push(RelocationTableEntry {
+                    display_list_offset: display_lists.len() as u32,
+                    texture_id,
+                    display_lists: display_lists_kind,
+                    _padding: 0,
+                });
+            })
+            .unwrap();
+
+        let mut byte_code = Vec::new();
+        BytecodeOp::Draw {
+            display_list_offset,
+            display_list_size: display_lists.len() as u32 - display_list_offset,
+        }
+        .append_to(&mut byte_code);
+        byte_code
+    }
+
+    fn parse(map: &OwnedMapData) -> anyhow::Result<PackedMap> {
+        let mut data = Cursor::new(Vec::new());
+        map.write_to(&mut data).unwrap();
+        PackedMap::parse(&data.into_inner())
+    }
+
+    #[test]
+    fn interprets_relocated_display_list() {
+        let mut vertex_data = Vec::new();
+        for (position_index, lightmap_s) in [(0u16, 0u16), (1, 16384), (2, 32768)] {
+            vertex_data.write_u16::<BigEndian>(position_index).unwrap();
+            vertex_data.write_u16::<BigEndian>(0).unwrap();
+            vertex_data.write_u16::<BigEndian>(lightmap_s).unwrap();
+            vertex_data.write_u16::<BigEndian>(0).unwrap();
+            vertex_data.write_u16::<BigEndian>(0).unwrap();
+        }
+        let mut map = OwnedMapData {
+            position_data: (0..9).flat_map(|x| (x as f32).to_be_bytes()).collect(),
+            texture_coord_data: vec![0x01, 0x00, 0x02, 0x00],
+            texture_table: vec![texture_table_entry(); 2],
+            texture_data: vec![0; 32],
+            vertex_format_table: vertex_format_table(),
+            ..Default::default()
+        };
+        map.cluster_geometry_byte_code = append_triangle(
+            &mut map.cluster_geometry_display_lists,
+            RelocationTableEntry::CLUSTER_GEOMETRY,
+            &mut map.relocation_table,
+            &[1],
+            VertexFormat::Brush,
+            vertex_data,
+        );
+        let mut byte_code_index_ranges = [[0; 2]; 6];
+        byte_code_index_ranges[0] = [0, map.cluster_geometry_byte_code.len() as u32];
+        map.cluster_geometry_table = vec![ClusterGeometryTableEntry {
+            byte_code_index_ranges,
+        }];
+
+        let geometry = parse(&map).unwrap().interpret().unwrap();
+
+        assert_eq!(geometry.vertices.len(), 3);
+        assert_eq!(geometry.vertices[1].position, [3.0, 4.0, 5.0]);
+        assert_eq!(geometry.vertices[2].lightmap_coord, [1.0, 0.0]);
+        assert_eq!(geometry.vertices[0].texture_coord, [1.0, 2.0]);
+        let batches: Vec<_> = geometry.indices_by_batch.into_iter().collect();
+        assert_eq!(
+            batches,
+            [(
+                BatchKey {
+                    kind: GeometryKind::Cluster,
+                    mode: 0,
+                    base_texture_id: 1,
+                    aux_texture_id: None,
+                    alpha_threshold: None,
+                },
+                vec![0, 1, 2],
+            )],
+        );
+    }
+
+    #[test]
+    fn interprets_displacements_and_overlays() {
+        let mut displacement_vertex_data = Vec::new();
+        for _ in 0..3 {
+            displacement_vertex_data.write_u16::<BigEndian>(0).unwrap();
+            displacement_vertex_data.write_u16::<BigEndian>(0).unwrap();
+            displacement_vertex_data
+                .write_u16::<BigEndian>(16384)
+                .unwrap();
+            displacement_vertex_data.write_u16::<BigEndian>(0).unwrap();
+            displacement_vertex_data.write_u16::<BigEndian>(0).unwrap();
+            displacement_vertex_data.write_u16::<BigEndian>(0).unwrap();
+        }
+        let mut overlay_vertex_data = Vec::new();
+        for _ in 0..3 {
+            for x in [7.0f32, 8.0, 9.0] {
+                overlay_vertex_data.write_f32::<BigEndian>(x).unwrap();
+            }
+            overlay_vertex_data.extend([255; 3]);
+            for x in [0.25f32, 0.75] {
+                overlay_vertex_data.write_f32::<BigEndian>(x).unwrap();
+            }
+        }
+        let mut map = OwnedMapData {
+            displacement_position_data: [1.0f32, 2.0, 3.0]
+                .iter()
+                .flat_map(|x| x.to_be_bytes())
+                .collect(),
+            displacement_texture_coordinate_data: vec![0x00, 0x80, 0x01, 0x00],
+            texture_table: vec![texture_table_entry(); 2],
+            texture_data: vec![0; 32],
+            vertex_format_table: vertex_format_table(),
+            ..Default::default()
+        };
+        map.displacement_byte_code = append_triangle(
+            &mut map.displacement_display_lists,
+            RelocationTableEntry::DISPLACEMENT,
+            &mut map.relocation_table,
+            &[0],
+            VertexFormat::Displacement,
+            displacement_vertex_data,
+        );
+        map.displacement_table = vec![DisplacementTableEntry {
+            byte_code_start_index: 0,
+            byte_code_end_index: map.displacement_byte_code.len() as u32,
+        }];
+        map.overlay_byte_code = append_triangle(
+            &mut map.overlay_display_lists,
+            RelocationTableEntry::OVERLAY,
+            &mut map.relocation_table,
+            &[0, 1],
+            VertexFormat::Overlay,
+            overlay_vertex_data,
+        );
+        map.overlay_table = vec![OverlayTableEntry {
+            byte_code_index_ranges: [[0, 0], [0, map.overlay_byte_code.len() as u32]],
+        }];
+
+        let geometry = parse(&map).unwrap().interpret().unwrap();
+
+        assert_eq!(geometry.vertices.len(), 6);
+        assert_eq!(geometry.vertices[0].position, [1.0, 2.0, 3.0]);
+        assert_eq!(geometry.vertices[0].lightmap_coord, [0.5, 0.0]);
+        assert_eq!(geometry.vertices[0].texture_coord, [0.5, 1.0]);
+        assert_eq!(geometry.vertices[3].position, [7.0, 8.0, 9.0]);
+        assert_eq!(geometry.vertices[3].texture_coord, [0.25, 0.75]);
+        let batches: Vec<_> = geometry.indices_by_batch.into_iter().collect();
+        assert_eq!(
+            batches,
+            [
+                (
+                    BatchKey {
+                        kind: GeometryKind::Displacement,
+                        mode: 0,
+                        base_texture_id: 0,
+                        aux_texture_id: None,
+                        alpha_threshold: None,
+                    },
+                    vec![0, 1, 2],
+                ),
+                (
+                    BatchKey {
+                        kind: GeometryKind::Overlay,
+                        mode: 1,
+                        base_texture_id: 0,
+                        aux_texture_id: Some(1),
+                        alpha_threshold: None,
+                    },
+                    vec![3, 4, 5],
+                ),
+            ],
+        );
+        assert!(batches[1].0.is_translucent());
+    }
+
+    #[test]
+    fn rejects_bad_relocations() {
+        let mut map = OwnedMapData {
+            cluster_geometry_display_lists: vec![0; 32],
+            texture_table: vec![texture_table_entry()],
+            texture_data: vec![0; 32],
+            relocation_table: vec![RelocationTableEntry {
+                display_list_offset: 30,
+                texture_id: 0,
+                display_lists: RelocationTableEntry::CLUSTER_GEOMETRY,
+                _padding: 0,
+            }],
+            ..Default::default()
+        };
+        assert!(parse(&map).is_err());
+
+        map.relocation_table[0].display_list_offset = 0;
+        assert!(parse(&map).is_ok());
+        map.relocation_table[0].texture_id = 1;
+        assert!(parse(&map).is_err());
+        map.relocation_table[0].texture_id = 0;
+        map.relocation_table[0].display_lists = 4;
+        assert!(parse(&map).is_err());
+    }
+
+    #[test]
+    fn rejects_draw_without_texture() {
+        let mut display_list = DisplayList::new();
+        display_list.commands.push(Command::Draw {
+            primitive: GxPrimitive::Triangles,
+            vertex_format: 0,
+            vertex_count: 0,
+            vertex_data: Vec::new(),
+        });
+        let mut data = Vec::new();
+        display_list.write_to(&mut data, |_, _| ()).unwrap();
+        let map = parse(&OwnedMapData {
+            vertex_format_table: vertex_format_table(),
+            ..Default::default()
+        })
+        .unwrap();
+        let mut state = Default::default();
+        let mut geometry = Default::default();
+        assert!(map
+            .interpret_display_list(&data, &mut state, &mut geometry)
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_unexpected_vertex_formats() {
+        let mut vertex_format_table = vertex_format_table();
+        vertex_format_table[1].attributes[0].component_type += 1;
+        let map = parse(&OwnedMapData {
+            vertex_format_table,
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(map.vertex_format(VertexFormat::Brush as u8).is_ok());
+        assert!(map.vertex_format(VertexFormat::Displacement as u8).is_err());
+        assert!(map.vertex_format(VertexFormat::Skybox as u8).is_err());
+        let map = parse(&OwnedMapData::default()).unwrap();
+        assert!(map.vertex_format(VertexFormat::Brush as u8).is_err());
+    }
+
+    #[test]
+    fn decodes_shared_textures() {
+        let mut map = parse(&OwnedMapData {
+            texture_table: vec![TextureTableEntry {
+                flags: TextureTableEntry::FLAG_SHARED,
+                start_offset: 0,
+                end_offset: 0,
+                ..texture_table_entry()
+            }],
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(map.decode_texture(0).is_err());
+
+        map.shared_textures.insert(0, vec![0; 32]);
+        let mips = map.decode_texture(0).unwrap();
+        assert_eq!(mips.len(), 1);
+        assert_eq!((mips[0].width(), mips[0].height()), (8, 8));
+    }
+}
@@ -0,0 +1,94 @@
+use std::borrow::Cow;
+use std::fs::read_to_string;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use glium::program::ProgramCreationInput;
+use glium::{Display, Program};
+
+/// A GLSL source file that is embedded in the binary but preferentially read from the source tree
+/// so that edits can be picked up without rebuilding.
+pub struct ShaderFile {
+    name: &'static str,
+    embedded: &'static str,
+}
+
+impl ShaderFile {
+    pub fn path(&self) -> PathBuf {
+        [env!("CARGO_MANIFEST_DIR"), "src", "shaders", self.name]
+            .iter()
+            .collect()
+    }
+
+    fn source(&self) -> Cow<'static, str> {
+        match read_to_string(self.path()) {
+            Ok(source) => Cow::Owned(source),
+            Err(_) => Cow::Borrowed(self.embedded),
+        }
+    }
+}
+
+macro_rules! shader_file {
+    ($name:literal) => {
+        ShaderFile {
+            name: $name,
+            embedded: include_str!(concat!("shaders/", $name)),
+        }
+    };
+}
+
+pub const WORLD_VERTEX_SHADER: ShaderFile = shader_file!("world.vert");
+pub const WORLD_FRAGMENT_SHADER: ShaderFile = shader_file!("world.frag");
+pub const MODEL_VERTEX_SHADER: ShaderFile = shader_file!("model.vert");
+pub const MODEL_FRAGMENT_SHADER: ShaderFile = shader_file!("model.frag");
+pub const PACKED_VERTEX_SHADER: ShaderFile = shader_file!("packed.vert");
+pub const PACKED_FRAGMENT_SHADER: ShaderFile = shader_file!("packed.frag");
+pub const LINE_VERTEX_SHADER: ShaderFile = shader_file!("line.vert");
+pub const LINE_FRAGMENT_SHADER: ShaderFile = shader_file!("line.frag");
+
+pub const ALL_SHADER_FILES: [&ShaderFile; 4] = [
+    &WORLD_VERTEX_SHADER,
+    &WORLD_FRAGMENT_SHADER,
+    &MODEL_VERTEX_SHADER,
+    &MODEL_FRAGMENT_SHADER,
+];
+
+pub fn build_shaders(display: &Display) -> Result<Program> {
+    build_program(display, &WORLD_VERTEX_SHADER, &WORLD_FRAGMENT_SHADER)
+}
+
+pub fn build_model_shaders(display: &Display) -> Result<Program> {
+    build_program(display, &MODEL_VERTEX_SHADER, &MODEL_FRAGMENT_SHADER)
+}
+
+pub fn build_packed_shaders(display: &Display) -> Result<Program> {
+    build_program(display, &PACKED_VERTEX_SHADER, &PACKED_FRAGMENT_SHADER)
+}
+
+pub fn build_line_shaders(display: &Display) -> Result<Program> {
+    build_program(display, &LINE_VERTEX_SHADER, &LINE_FRAGMENT_SHADER)
+}
+
+fn build_program(display: &Display, vertex: &ShaderFile, fragment: &ShaderFile) -> Result<Program> {
+    let vertex_source = vertex.source();
+    let fragment_source = fragment.source();
+    Program::new(
+        display,
+        ProgramCreationInput::SourceCode {
+            vertex_shader: &vertex_source,
+            tessellation_control_shader: None,
+            tessellation_evaluation_shader: None,
+            geometry_shader: None,
+            fragment_shader: &fragment_source,
+            transform_feedback_varyings: None,
+            outputs_srgb: false,
+            uses_point_size: false,
+        },
+    )
+    .with_context(|| {
+        format!(
+            "Building program from {} and {}",
+            vertex.name, fragment.name
+        )
+    })
+}
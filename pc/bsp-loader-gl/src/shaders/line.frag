@@ -0,0 +1,9 @@
+#version 330
+
+in vec3 interpolated_color;
+
+out vec4 rendered_color;
+
+void main() {
+    rendered_color = vec4(interpolated_color, 1.0);
+}
@@ -0,0 +1,13 @@
+#version 330
+
+uniform mat4 mvp_matrix;
+
+in vec3 position;
+in vec3 color;
+
+out vec3 interpolated_color;
+
+void main() {
+    gl_Position = mvp_matrix * vec4(position, 1.0);
+    interpolated_color = color;
+}
@@ -0,0 +1,14 @@
+#version 330
+
+uniform sampler2D base_map;
+
+in vec3 interpolated_normal;
+in vec2 interpolated_tex_coord;
+
+out vec4 rendered_color;
+
+void main() {
+    rendered_color = vec4(
+        texture(base_map, interpolated_tex_coord).rgb,
+        1.0);
+}
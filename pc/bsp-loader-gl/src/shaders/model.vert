@@ -0,0 +1,16 @@
+#version 330
+
+uniform mat4 mvp_matrix;
+
+in vec3 position;
+in vec3 normal;
+in vec2 tex_coord;
+
+out vec3 interpolated_normal;
+out vec2 interpolated_tex_coord;
+
+void main() {
+    gl_Position = mvp_matrix * vec4(position, 1.0);
+    interpolated_normal = normal;
+    interpolated_tex_coord = tex_coord;
+}
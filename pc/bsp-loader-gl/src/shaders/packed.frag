@@ -0,0 +1,22 @@
+#version 330
+
+uniform sampler2D base_map;
+uniform sampler2D aux_map;
+uniform bool alpha_from_aux;
+// Fragments with less alpha are discarded.
+uniform float alpha_threshold;
+
+in vec2 interpolated_texture_coord;
+
+out vec4 rendered_color;
+
+void main() {
+    vec4 base_color = texture(base_map, interpolated_texture_coord);
+    float alpha = alpha_from_aux
+        ? texture(aux_map, interpolated_texture_coord).a
+        : base_color.a;
+    if (alpha < alpha_threshold) {
+        discard;
+    }
+    rendered_color = vec4(base_color.rgb, alpha);
+}
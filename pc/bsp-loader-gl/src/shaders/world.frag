@@ -0,0 +1,18 @@
+#version 330
+
+uniform sampler2D lightmap;
+uniform sampler2D base_map;
+// Blended over the result by the inspector. Alpha is the blend weight.
+uniform vec4 highlight;
+
+in vec2 interpolated_lightmap_coord;
+in vec2 interpolated_texture_coord;
+
+out vec4 rendered_color;
+
+void main() {
+    vec4 lightmap_color = vec4(texture(lightmap, interpolated_lightmap_coord).rgb, 1.0);
+    vec4 base_color = texture(base_map, interpolated_texture_coord);
+    rendered_color = lightmap_color * base_color * 4.59479;
+    rendered_color.rgb = mix(rendered_color.rgb, highlight.rgb, highlight.a);
+}
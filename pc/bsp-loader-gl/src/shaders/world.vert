@@ -0,0 +1,17 @@
+#version 330
+
+uniform mat4 mvp_matrix;
+uniform vec2 inv_base_map_size;
+
+in vec3 position;
+in vec2 lightmap_coord;
+in vec2 texture_coord;
+
+out vec2 interpolated_lightmap_coord;
+out vec2 interpolated_texture_coord;
+
+void main() {
+    gl_Position = mvp_matrix * vec4(position, 1.0);
+    interpolated_lightmap_coord = lightmap_coord;
+    interpolated_texture_coord = texture_coord * inv_base_map_size;
+}
@@ -0,0 +1,206 @@
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Result};
+use inception_render_common::map_data::TextureTableEntry;
+
+const GX_TF_RGBA8: u8 = 0x6;
+
+/// Controls the memory budget check made after packing a map.
+#[derive(Default)]
+pub struct BudgetConfig {
+    /// The most bytes the packed map may take, if limited.
+    pub max_bytes: Option<usize>,
+    /// Print a warning instead of failing the pack when the map is over budget.
+    pub warn_only: bool,
+}
+
+/// Broad kinds of map data, ordered as they're listed in the breakdown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Category {
+    DisplayLists,
+    Textures,
+    Lightmaps,
+    VertexData,
+    Other,
+}
+
+impl Category {
+    fn of(section: &str) -> Self {
+        if section.ends_with("_display_lists") || section.ends_with("_byte_code") {
+            Self::DisplayLists
+        } else if section.starts_with("lightmap_") {
+            Self::Lightmaps
+        } else if matches!(
+            section,
+            "position_data" | "normal_data" | "texture_coord_data"
+        ) || (section.starts_with("displacement_") && section.ends_with("_data"))
+        {
+            Self::VertexData
+        } else if section.starts_with("texture_")
+            || matches!(section, "animated_texture_table" | "shared_textures")
+        {
+            Self::Textures
+        } else {
+            Self::Other
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::DisplayLists => "display lists",
+            Self::Textures => "textures",
+            Self::Lightmaps => "lightmaps",
+            Self::VertexData => "vertex data",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// Checks the packed map's size, including any shared textures it loads, against the budget. Over budget, prints a per-section breakdown
+/// and suggestions for what to shrink, then fails unless the config only asks for a warning.
+pub fn check_budget(
+    config: &BudgetConfig,
+    map_name: &str,
+    total_bytes: usize,
+    section_sizes: &[(&'static str, usize)],
+    texture_table: &[TextureTableEntry],
+) -> Result<()> {
+    let max_bytes = match config.max_bytes {
+        Some(max_bytes) => max_bytes,
+        None => return Ok(()),
+    };
+    if total_bytes <= max_bytes {
+        println!(
+            "{map_name}: {} of {} budget",
+            format_size(total_bytes),
+            format_size(max_bytes),
+        );
+        return Ok(());
+    }
+
+    eprintln!(
+        "{map_name}: {} is over the {} budget by {}",
+        format_size(total_bytes),
+        format_size(max_bytes),
+        format_size(total_bytes - max_bytes),
+    );
+    let mut sections_by_category: BTreeMap<Category, Vec<(&str, usize)>> = BTreeMap::new();
+    for &(name, len) in section_sizes {
+        sections_by_category
+            .entry(Category::of(name))
+            .or_default()
+            .push((name, len));
+    }
+    for (category, mut sections) in sections_by_category {
+        let category_bytes: usize = sections.iter().map(|&(_, len)| len).sum();
+        eprintln!(
+            "  {}: {} ({:.1}%)",
+            category.name(),
+            format_size(category_bytes),
+            100.0 * category_bytes as f32 / total_bytes as f32,
+        );
+        sections.sort_by_key(|&(name, len)| (std::cmp::Reverse(len), name));
+        for (name, len) in sections.into_iter().filter(|&(_, len)| len > 0) {
+            eprintln!("    {name}: {}", format_size(len));
+        }
+    }
+    for suggestion in suggestions(texture_table) {
+        eprintln!("  suggestion: {suggestion}");
+    }
+
+    if !config.warn_only {
+        bail!("{map_name} is over its memory budget");
+    }
+    eprintln!("WARNING: {map_name} is over its memory budget");
+    Ok(())
+}
+
+fn suggestions(texture_table: &[TextureTableEntry]) -> Vec<String> {
+    let texture_bytes =
+        |entry: &TextureTableEntry| (entry.end_offset - entry.start_offset) as usize;
+    let mut suggestions = Vec::new();
+
+    let rgba8: Vec<_> = texture_table
+        .iter()
+        .filter(|entry| entry.format == GX_TF_RGBA8)
+        .collect();
+    if !rgba8.is_empty() {
+        let bytes: usize = rgba8.iter().map(|entry| texture_bytes(entry)).sum();
+        suggestions.push(format!(
+            "{} RGBA8 textures take {}; as CMPR they would take about {}",
+            rgba8.len(),
+            format_size(bytes),
+            format_size(bytes / 8),
+        ));
+    }
+
+    // The top level of a full mip chain is about three quarters of it.
+    let mipmapped_bytes: usize = texture_table
+        .iter()
+        .filter(|entry| entry.mip_count > 1)
+        .map(texture_bytes)
+        .sum();
+    if mipmapped_bytes > 0 {
+        suggestions.push(format!(
+            "dropping the top mip level of every mipmapped texture would save about {}",
+            format_size(mipmapped_bytes / 4 * 3),
+        ));
+    }
+
+    suggestions
+}
+
+/// Parses a byte count for `--budget`, with an optional `K` or `M` suffix for KiB or MiB.
+pub fn parse_size(s: &str) -> Result<usize, String> {
+    let (digits, multiplier) = match s.as_bytes().last() {
+        Some(b'K' | b'k') => (&s[..s.len() - 1], 1 << 10),
+        Some(b'M' | b'm') => (&s[..s.len() - 1], 1 << 20),
+        _ => (s, 1),
+    };
+    match digits.parse::<f64>() {
+        Ok(value) if value >= 0.0 => Ok((value * multiplier as f64) as usize),
+        _ => Err(format!("invalid size: {s:?}")),
+    }
+}
+
+fn format_size(bytes: usize) -> String {
+    if bytes >= 1 << 20 {
+        format!("{:.2} MiB", bytes as f64 / (1 << 20) as f64)
+    } else if bytes >= 1 << 10 {
+        format!("{:.1} KiB", bytes as f64 / (1 << 10) as f64)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_size, Category};
+
+    #[test]
+    fn parse_size_suffixes() {
+        assert_eq!(parse_size("1234"), Ok(1234));
+        assert_eq!(parse_size("16K"), Ok(16 << 10));
+        assert_eq!(parse_size("1.5M"), Ok(3 << 19));
+        assert!(parse_size("M").is_err());
+        assert!(parse_size("-1").is_err());
+    }
+
+    #[test]
+    fn categorize_sections() {
+        assert_eq!(
+            Category::of("cluster_geometry_display_lists"),
+            Category::DisplayLists
+        );
+        assert_eq!(Category::of("prop_byte_code"), Category::DisplayLists);
+        assert_eq!(Category::of("texture_data"), Category::Textures);
+        assert_eq!(Category::of("shared_textures"), Category::Textures);
+        assert_eq!(Category::of("texture_coord_data"), Category::VertexData);
+        assert_eq!(
+            Category::of("displacement_position_data"),
+            Category::VertexData
+        );
+        assert_eq!(Category::of("displacement_table"), Category::Other);
+        assert_eq!(Category::of("lightmap_data"), Category::Lightmaps);
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use source_reader::bsp::{Bsp, Visibility};
+
+/// The most lightmap luxels a merged group may hold, so that its shared atlas stays near the size
+/// the lightmap report warns about.
+pub const MAX_GROUP_LUXELS: usize = 256 * 256 / 2;
+
+/// Assigns each cluster to a group of clusters that are always drawn together.
+///
+/// The loader draws every cluster in the view cluster's PVS, one display list per material.
+/// Clusters that are potentially visible from exactly the same set of clusters are never drawn
+/// apart, so their display lists can be concatenated per material and drawn with one call each.
+/// Their faces must sample the same lightmap atlas for that to work, so groups are decided before
+/// lightmaps are laid out and each group is named after its lowest-numbered cluster.
+pub struct ClusterGroups {
+    group_by_cluster: Vec<i16>,
+}
+
+impl ClusterGroups {
+    /// Leaves every cluster in a group of its own.
+    pub fn separate(num_clusters: usize) -> Self {
+        Self {
+            group_by_cluster: (0..num_clusters)
+                .map(|cluster| i16::try_from(cluster).unwrap())
+                .collect(),
+        }
+    }
+
+    /// Groups clusters whose sets of viewers are identical. A group is closed once adding the next
+    /// cluster would take its lightmap patches past `max_luxels`.
+    pub fn from_visibility(bsp: Bsp, max_luxels: usize) -> Self {
+        let visibility = bsp.visibility();
+        Self::from_viewers(
+            &viewers_by_cluster(visibility),
+            &lightmap_luxels_by_cluster(bsp),
+            max_luxels,
+        )
+    }
+
+    fn from_viewers(
+        viewers_by_cluster: &[Vec<u64>],
+        luxels_by_cluster: &BTreeMap<i16, usize>,
+        max_luxels: usize,
+    ) -> Self {
+        // The open group for each viewer set, and the luxels it holds so far.
+        let mut open_groups: HashMap<&[u64], (i16, usize)> = HashMap::new();
+        let group_by_cluster = viewers_by_cluster
+            .iter()
+            .enumerate()
+            .map(|(cluster, viewers)| {
+                let cluster = i16::try_from(cluster).unwrap();
+                let luxels = luxels_by_cluster.get(&cluster).copied().unwrap_or(0);
+                match open_groups.get_mut(viewers.as_slice()) {
+                    Some((group, group_luxels)) if *group_luxels + luxels <= max_luxels => {
+                        *group_luxels += luxels;
+                        *group
+                    }
+                    _ => {
+                        open_groups.insert(viewers, (cluster, luxels));
+                        cluster
+                    }
+                }
+            })
+            .collect();
+        Self { group_by_cluster }
+    }
+
+    /// Returns the group a cluster was merged into. Cluster -1, which is never visible, stays on
+    /// its own.
+    pub fn group(&self, cluster: i16) -> i16 {
+        match usize::try_from(cluster) {
+            Ok(index) => self.group_by_cluster.get(index).copied().unwrap_or(cluster),
+            Err(_) => cluster,
+        }
+    }
+
+    pub fn cluster_count(&self) -> usize {
+        self.group_by_cluster.len()
+    }
+
+    pub fn group_count(&self) -> usize {
+        self.group_by_cluster.iter().collect::<HashSet<_>>().len()
+    }
+}
+
+/// Transposes the PVS: bit `v` of `viewers[c]` is set if cluster `c` is potentially visible from
+/// cluster `v`.
+fn viewers_by_cluster(visibility: Visibility) -> Vec<Vec<u64>> {
+    let num_clusters = visibility.num_clusters();
+    let mut viewers = vec![vec![0u64; num_clusters.div_ceil(64)]; num_clusters];
+    for (viewer, bitmap) in visibility.iter_clusters().enumerate() {
+        for cluster in bitmap.iter_visible_clusters() {
+            viewers[cluster.0][viewer / 64] |= 1 << (viewer % 64);
+        }
+    }
+    viewers
+}
+
+/// Counts the lightmap luxels each cluster's faces need, the same way the atlas builder does.
+fn lightmap_luxels_by_cluster(bsp: Bsp) -> BTreeMap<i16, usize> {
+    let mut light_ofs_by_cluster: BTreeMap<i16, HashSet<i32>> = BTreeMap::new();
+    let mut luxels_by_cluster = BTreeMap::new();
+    let faces = bsp
+        .iter_worldspawn_leaves()
+        .flat_map(|leaf| {
+            let cluster = bsp.leaf(leaf).cluster();
+            bsp.iter_faces_from_leaf(leaf)
+                .map(move |face| (cluster, face))
+        })
+        .chain(
+            bsp.iter_static_brush_entity_faces()
+                .map(|(cluster, face, _)| (cluster, face)),
+        );
+    for (cluster, face) in faces {
+        let face = bsp.face(face);
+        if cluster == -1 || face.light_ofs == -1 || face.tex_info == -1 {
+            continue;
+        }
+        if light_ofs_by_cluster
+            .entry(cluster)
+            .or_default()
+            .insert(face.light_ofs)
+        {
+            let width = face.lightmap_texture_size_in_luxels[0] as usize + 1;
+            let height = face.lightmap_texture_size_in_luxels[1] as usize + 1;
+            *luxels_by_cluster.entry(cluster).or_default() +=
+                ((width + 3) & !3) * ((height + 3) & !3);
+        }
+    }
+    luxels_by_cluster
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::ClusterGroups;
+
+    #[test]
+    fn groups_identical_viewer_sets() {
+        // Clusters 0 and 2 are seen from 0, 1, and 2. Cluster 1 is also seen from 3.
+        let viewers = [vec![0b0111], vec![0b1111], vec![0b0111], vec![0b1000]];
+        let groups = ClusterGroups::from_viewers(&viewers, &BTreeMap::new(), 100);
+        assert_eq!(
+            (0..4)
+                .map(|cluster| groups.group(cluster))
+                .collect::<Vec<_>>(),
+            [0, 1, 0, 3],
+        );
+        assert_eq!(groups.group_count(), 3);
+        assert_eq!(groups.group(-1), -1);
+    }
+
+    #[test]
+    fn closes_full_groups() {
+        let viewers = [vec![1], vec![1], vec![1], vec![1]];
+        let luxels = BTreeMap::from([(0, 40), (1, 40), (2, 40), (3, 10)]);
+        let groups = ClusterGroups::from_viewers(&viewers, &luxels, 100);
+        assert_eq!(
+            (0..4)
+                .map(|cluster| groups.group(cluster))
+                .collect::<Vec<_>>(),
+            [0, 0, 2, 2],
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Detail props, the grass and rocks VBSP scatters over outdoor ground.
+//!
+//! Sprites are packed as GX point sprites rather than display lists: each one is a position, a
+//! scale, and the lighting VRAD sampled at its origin, plus an index into a table of sprite types
+//! holding its size and its cell of the detail material. Cross and tri shapes are drawn as a
+//! single sprite. Model detail props aren't supported.
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Context, Result};
+use inception_render_common::map_data::{
+    DetailSpriteClusterTableEntry, DetailSpriteEntry, DetailSpriteTypeTableEntry,
+};
+use source_reader::asset::AssetLoader;
+use source_reader::bsp::{Bsp, DetailPropType, DetailSprite};
+use source_reader::vpk::path::VpkPath;
+
+use crate::packed_material::PackedMaterial;
+use crate::texture_key::TextureIdAllocator;
+
+#[derive(Default)]
+pub struct DetailGeometry {
+    types: Vec<DetailSpriteTypeTableEntry>,
+    sprites_by_cluster: BTreeMap<u16, Vec<DetailSpriteEntry>>,
+}
+
+pub struct PackedDetailSprites {
+    pub detail_sprite_type_table: Vec<DetailSpriteTypeTableEntry>,
+    pub detail_sprite_cluster_table: Vec<DetailSpriteClusterTableEntry>,
+    pub detail_sprites: Vec<DetailSpriteEntry>,
+}
+
+pub fn process_detail_props(
+    bsp: Bsp,
+    asset_loader: &AssetLoader,
+    ids: &mut TextureIdAllocator,
+) -> Result<DetailGeometry> {
+    let detail_props = bsp.detail_props()?;
+    if detail_props.props.is_empty() {
+        return Ok(DetailGeometry::default());
+    }
+    if detail_props.sprites.len() > 256 {
+        bail!(
+            "{} detail sprite types don't fit in a byte",
+            detail_props.sprites.len(),
+        );
+    }
+
+    let material_name = bsp.map_settings().detail_material;
+    let material_path = VpkPath::new_with_prefix_and_extension(&material_name, "materials", "vmt");
+    let material = asset_loader.get_material(&material_path)?;
+    let packed_material = match PackedMaterial::from_material(asset_loader, ids, &material, false)?
+    {
+        Some(packed_material) => packed_material,
+        None => {
+            eprintln!(
+                "WARNING: Skipping detail props with unsupported material {}",
+                material_name,
+            );
+            return Ok(DetailGeometry::default());
+        }
+    };
+    let types = detail_props
+        .sprites
+        .iter()
+        .map(|sprite| sprite_type(sprite, &packed_material))
+        .collect();
+
+    let mut sprites_by_cluster: BTreeMap<u16, Vec<DetailSpriteEntry>> = BTreeMap::new();
+    let mut skipped_models = 0;
+    for prop in &detail_props.props {
+        if prop.prop_type == DetailPropType::Model {
+            skipped_models += 1;
+            continue;
+        }
+        let cluster = match u16::try_from(bsp.leaf(prop.leaf).cluster()) {
+            Ok(cluster) => cluster,
+            Err(_) => continue,
+        };
+        let sprite_type = u8::try_from(prop.detail_model)
+            .ok()
+            .filter(|&index| (index as usize) < detail_props.sprites.len())
+            .with_context(|| format!("detail sprite type {} is undefined", prop.detail_model))?;
+        sprites_by_cluster
+            .entry(cluster)
+            .or_default()
+            .push(DetailSpriteEntry {
+                position: prop.origin.into(),
+                scale: prop.scale,
+                color: prop.lighting.to_srgb8(),
+                sprite_type,
+            });
+    }
+    if skipped_models > 0 {
+        eprintln!("WARNING: Skipping {} detail prop models", skipped_models);
+    }
+
+    // Keep each type's sprites together so a cluster's sprites are drawn in one batch per type.
+    for sprites in sprites_by_cluster.values_mut() {
+        sprites.sort_by_key(|sprite| sprite.sprite_type);
+    }
+
+    Ok(DetailGeometry {
+        types,
+        sprites_by_cluster,
+    })
+}
+
+fn sprite_type(sprite: &DetailSprite, material: &PackedMaterial) -> DetailSpriteTypeTableEntry {
+    DetailSpriteTypeTableEntry {
+        size: [
+            sprite.lower_right[0] - sprite.upper_left[0],
+            sprite.upper_left[1] - sprite.lower_right[1],
+        ],
+        center_height: 0.5 * (sprite.upper_left[1] + sprite.lower_right[1]),
+        tex_upper_left: sprite.tex_upper_left,
+        tex_lower_right: sprite.tex_lower_right,
+        base_texture_id: material.base_id,
+        aux_texture_id: material.aux_id.unwrap_or(u16::MAX),
+    }
+}
+
+/// Lays out every cluster's sprites back to back, with an entry for each cluster even if it has
+/// none.
+pub fn pack_detail_sprites(cluster_count: usize, geometry: &DetailGeometry) -> PackedDetailSprites {
+    let mut detail_sprite_cluster_table = Vec::new();
+    let mut detail_sprites = Vec::new();
+    for cluster in 0..cluster_count {
+        let start = u32::try_from(detail_sprites.len()).unwrap();
+        if let Some(sprites) = geometry.sprites_by_cluster.get(&(cluster as u16)) {
+            detail_sprites.extend_from_slice(sprites);
+        }
+        let end = u32::try_from(detail_sprites.len()).unwrap();
+        detail_sprite_cluster_table.push(DetailSpriteClusterTableEntry {
+            sprite_index_range: [start, end],
+        });
+    }
+
+    PackedDetailSprites {
+        detail_sprite_type_table: geometry.types.clone(),
+        detail_sprite_cluster_table,
+        detail_sprites,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use inception_render_common::map_data::DetailSpriteEntry;
+
+    use super::{pack_detail_sprites, DetailGeometry};
+
+    fn sprite(sprite_type: u8) -> DetailSpriteEntry {
+        DetailSpriteEntry {
+            position: [0.0; 3],
+            scale: 1.0,
+            color: [255; 3],
+            sprite_type,
+        }
+    }
+
+    #[test]
+    fn packs_ranges_for_every_cluster() {
+        let geometry = DetailGeometry {
+            types: Vec::new(),
+            sprites_by_cluster: BTreeMap::from([
+                (1, vec![sprite(0), sprite(2)]),
+                (3, vec![sprite(1)]),
+            ]),
+        };
+        let packed = pack_detail_sprites(4, &geometry);
+        assert_eq!(
+            packed
+                .detail_sprite_cluster_table
+                .iter()
+                .map(|entry| entry.sprite_index_range)
+                .collect::<Vec<_>>(),
+            [[0, 0], [0, 2], [2, 2], [2, 3]],
+        );
+        assert_eq!(
+            packed
+                .detail_sprites
+                .iter()
+                .map(|sprite| sprite.sprite_type)
+                .collect::<Vec<_>>(),
+            [0, 2, 1],
+        );
+    }
+}
@@ -1,24 +1,28 @@
 use gx::display_list::{Command, DisplayList, GxPrimitive};
+use inception_render_common::vertex_format::VertexFormat;
 
 #[derive(Clone)]
 pub struct DrawBuilder {
     primitive: GxPrimitive,
     vertex_format: u8,
+    vertex_size: usize,
     vertex_count: usize,
     vertex_data: Vec<u8>,
 }
 
 impl DrawBuilder {
-    pub fn new(primitive: GxPrimitive, vertex_format: u8) -> Self {
+    pub fn new(primitive: GxPrimitive, vertex_format: VertexFormat) -> Self {
         Self {
             primitive,
-            vertex_format,
+            vertex_format: vertex_format as u8,
+            vertex_size: vertex_format.descriptor().vertex_size(),
             vertex_count: 0,
             vertex_data: Vec::new(),
         }
     }
 
     pub fn emit_vertices(&mut self, count: usize, data: &[u8]) {
+        assert_eq!(data.len(), count * self.vertex_size);
         self.vertex_count += count;
         self.vertex_data.extend_from_slice(data);
     }
@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+
+use source_reader::lightmap::Lightmap;
+
+/// Controls the lightmap atlas statistics printed after packing a map.
+pub struct LightmapReportConfig {
+    /// Print per-atlas statistics and histograms, not just warnings.
+    pub verbose: bool,
+    /// Warn about any atlas wider or taller than this many luxels.
+    pub max_dimension: usize,
+}
+
+impl Default for LightmapReportConfig {
+    fn default() -> Self {
+        Self {
+            verbose: false,
+            max_dimension: 256,
+        }
+    }
+}
+
+/// Reports how well lightmap patches packed into their atlases, to help tune the atlas layout.
+pub fn report_lightmaps(
+    config: &LightmapReportConfig,
+    cluster_lightmaps: &BTreeMap<i16, Lightmap>,
+    displacement_lightmaps: &BTreeMap<u16, Lightmap>,
+) {
+    let atlases = cluster_lightmaps
+        .iter()
+        .map(|(cluster, lightmap)| (format!("cluster {cluster}"), lightmap))
+        .chain(
+            displacement_lightmaps
+                .iter()
+                .map(|(face, lightmap)| (format!("displacement face {face}"), lightmap)),
+        );
+
+    let mut dimension_histogram: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+    let mut occupancy_histogram = [0; 10];
+    let mut total_luxels = 0;
+    let mut total_patch_luxels = 0;
+    let mut total_block_aligned_luxels = 0;
+    for (name, lightmap) in atlases {
+        let occupancy = lightmap.occupancy();
+        if config.verbose {
+            println!(
+                "{name}: {}x{}, {:.1}% occupied ({:.1}% with block alignment), \
+                {} of {} patches flipped",
+                lightmap.width,
+                lightmap.height,
+                100.0 * occupancy,
+                100.0 * lightmap.block_aligned_luxels as f32
+                    / (lightmap.width * lightmap.height) as f32,
+                lightmap.flipped_patch_count(),
+                lightmap.metadata_by_data_offset.len(),
+            );
+        }
+        if lightmap.width.max(lightmap.height) > config.max_dimension {
+            eprintln!(
+                "WARNING: {name} lightmap atlas is {}x{}, over the {} luxel limit",
+                lightmap.width, lightmap.height, config.max_dimension,
+            );
+        }
+
+        *dimension_histogram
+            .entry((lightmap.width, lightmap.height))
+            .or_default() += 1;
+        occupancy_histogram[((10.0 * occupancy) as usize).min(9)] += 1;
+        total_luxels += lightmap.width * lightmap.height;
+        total_patch_luxels += lightmap.patch_luxels;
+        total_block_aligned_luxels += lightmap.block_aligned_luxels;
+    }
+
+    if config.verbose {
+        println!("Atlas dimensions:");
+        for ((width, height), count) in dimension_histogram {
+            println!("{count:>10} {width}x{height}");
+        }
+        println!("Atlas occupancy:");
+        for (bucket, count) in occupancy_histogram.into_iter().enumerate() {
+            println!("{count:>10} {}-{}%", 10 * bucket, 10 * bucket + 10);
+        }
+        println!(
+            "Lightmaps use {total_patch_luxels} of {total_luxels} luxels \
+            ({total_block_aligned_luxels} with block alignment)",
+        );
+    }
+}
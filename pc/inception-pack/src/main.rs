@@ -27,16 +27,27 @@ use texture_format::{TextureBuf, TextureFormat};
 #[cfg(test)]
 use quickcheck::Arbitrary;
 
-use crate::map::pack_map;
+use crate::budget::{parse_size, BudgetConfig};
+use crate::lightmap_report::LightmapReportConfig;
+use crate::map::{describe_map_dependencies, pack_map};
 use crate::model::pack_model;
+use crate::pass_config::PassConfig;
 
+mod budget;
+mod cluster_merge;
 mod counter;
+mod detail;
 mod draw_builder;
 mod gx_helpers;
 mod legacy_pass_params;
+mod lightmap_report;
 mod map;
 mod model;
+mod overlay;
 mod packed_material;
+mod pass_config;
+mod prop;
+mod shared_textures;
 mod texture_key;
 mod write_big_endian;
 
@@ -45,6 +56,9 @@ struct Args {
     /// Path to a Half-Life 2 installation.
     #[clap(long)]
     hl2_base: PathBuf,
+    /// Path to a config file overriding which pass materials are drawn in.
+    #[clap(long)]
+    pass_config: Option<PathBuf>,
     #[command(subcommand)]
     command: Command,
 }
@@ -58,12 +72,49 @@ enum Command {
         /// Path to write packed outputs
         #[arg(long, default_value = ".")]
         dst: PathBuf,
+        /// Print which pass each brush face lands in instead of writing outputs
+        #[arg(long)]
+        dry_run: bool,
+        /// Keep every cluster's display lists separate, even for clusters that are always visible
+        /// together
+        #[arg(long)]
+        no_cluster_merge: bool,
+        /// Print lightmap atlas dimensions, occupancy, and flipped patch counts
+        #[arg(long)]
+        lightmap_report: bool,
+        /// Warn about lightmap atlases wider or taller than this many luxels
+        #[arg(long, default_value_t = LightmapReportConfig::default().max_dimension)]
+        max_lightmap_dimension: usize,
+        /// Fail if the packed map is larger than this many bytes, with an optional K or M suffix
+        /// (example: 12M)
+        #[arg(long, value_parser = parse_size)]
+        budget: Option<usize>,
+        /// Only warn when the packed map is over --budget
+        #[arg(long)]
+        budget_warn_only: bool,
+        /// Store textures once under textures/ for every map to share, instead of in the map
+        #[arg(long)]
+        shared_textures: bool,
+        /// Keep texture names in the map so runtime diagnostics can say which texture is involved
+        #[arg(long)]
+        texture_names: bool,
     },
     /// Packs maps for use on GC/Wii.
     PackAllMaps {
         /// Path to write packed outputs
         #[arg(default_value = ".")]
         dst: PathBuf,
+        /// Store textures once under textures/ for every map to share, instead of in each map
+        #[arg(long)]
+        shared_textures: bool,
+        /// Keep texture names in each map so runtime diagnostics can say which texture is involved
+        #[arg(long)]
+        texture_names: bool,
+    },
+    /// Lists every material and texture a map references and reports any that are missing.
+    DescribeMapDependencies {
+        /// Map name or path to map file if ending with ".bsp" (example: d1_trainstation_01)
+        map: String,
     },
     /// Dumps an arbitrary BSP lump to stdout.
     CatLump {
@@ -98,10 +149,55 @@ enum Command {
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    let pass_config = match &args.pass_config {
+        Some(path) => PassConfig::load(path)?,
+        None => PassConfig::default(),
+    };
 
     match args.command {
-        Command::PackMap { map, dst } => pack_map(&args.hl2_base, &dst, &map)?,
-        Command::PackAllMaps { dst } => pack_all_maps(&args.hl2_base, &dst)?,
+        Command::PackMap {
+            map,
+            dst,
+            dry_run,
+            no_cluster_merge,
+            lightmap_report,
+            max_lightmap_dimension,
+            budget,
+            budget_warn_only,
+            shared_textures,
+            texture_names,
+        } => pack_map(
+            &args.hl2_base,
+            &dst,
+            &map,
+            &pass_config,
+            dry_run,
+            !no_cluster_merge,
+            &LightmapReportConfig {
+                verbose: lightmap_report,
+                max_dimension: max_lightmap_dimension,
+            },
+            &BudgetConfig {
+                max_bytes: budget,
+                warn_only: budget_warn_only,
+            },
+            shared_textures,
+            texture_names,
+        )?,
+        Command::PackAllMaps {
+            dst,
+            shared_textures,
+            texture_names,
+        } => pack_all_maps(
+            &args.hl2_base,
+            &dst,
+            pass_config,
+            shared_textures,
+            texture_names,
+        )?,
+        Command::DescribeMapDependencies { map } => {
+            describe_map_dependencies(&args.hl2_base, &map)?
+        }
         Command::CatLump {
             map_name,
             lump_index,
@@ -165,13 +261,21 @@ fn describe_texture(hl2_base: &Path, name: &str) -> Result<()> {
     println!("height: {}", vtf.height());
     println!("flags: 0x{:08x}", vtf.flags());
     println!("mips: {}", vtf.mips().len());
+    println!("frames: {}", vtf.frame_count());
     println!("faces: {}", vtf.face_count());
     println!("format: {:?}", vtf.format());
 
     Ok(())
 }
 
-fn pack_all_maps(hl2_base: &Path, dst: &Path) -> Result<()> {
+fn pack_all_maps(
+    hl2_base: &Path,
+    dst: &Path,
+    pass_config: PassConfig,
+    shared_textures: bool,
+    texture_names: bool,
+) -> Result<()> {
+    let pass_config = Arc::new(pass_config);
     let map_queue = Arc::new(Mutex::new(VecDeque::new()));
     let mut locked_queue = map_queue.lock().unwrap();
     for entry in read_dir(&hl2_base.join("maps"))? {
@@ -186,6 +290,8 @@ fn pack_all_maps(hl2_base: &Path, dst: &Path) -> Result<()> {
             }
         }
     }
+    // Directory iteration order varies between filesystems.
+    locked_queue.make_contiguous().sort();
     drop(locked_queue);
 
     let mut threads = Vec::new();
@@ -194,6 +300,7 @@ fn pack_all_maps(hl2_base: &Path, dst: &Path) -> Result<()> {
             let hl2_base = hl2_base.to_path_buf();
             let dst = PathBuf::from(dst);
             let map_queue = Arc::clone(&map_queue);
+            let pass_config = Arc::clone(&pass_config);
             move || -> Result<()> {
                 loop {
                     let map_path = match map_queue.lock().unwrap().pop_front() {
@@ -201,8 +308,19 @@ fn pack_all_maps(hl2_base: &Path, dst: &Path) -> Result<()> {
                         None => break,
                     };
                     println!("Pulled {} from the queue", map_path);
-                    pack_map(&hl2_base, &dst, &map_path)
-                        .with_context(|| format!("Packing map {}", map_path))?;
+                    pack_map(
+                        &hl2_base,
+                        &dst,
+                        &map_path,
+                        &pass_config,
+                        false,
+                        true,
+                        &LightmapReportConfig::default(),
+                        &BudgetConfig::default(),
+                        shared_textures,
+                        texture_names,
+                    )
+                    .with_context(|| format!("Packing map {}", map_path))?;
                 }
                 Ok(())
             }
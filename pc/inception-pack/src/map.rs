@@ -1,61 +1,85 @@
+use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, HashMap};
 use std::fs::{create_dir_all, File};
 use std::hash::Hash;
-use std::io::Write;
+use std::io::{Cursor, Write};
+use std::mem::replace;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
-use anyhow::{bail, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use byteorder::{BigEndian, WriteBytesExt};
 use gx::display_list::{DisplayList, GxPrimitive};
 use inception_render_common::bytecode::BytecodeOp;
 use inception_render_common::map_data::{
-    BspLeaf, BspNode, ClusterGeometryReferencesEntry, ClusterGeometryTableEntry,
-    ClusterLightmapTableEntry, CommonLightmapTableEntry, DisplacementLightmapTableEntry,
-    DisplacementReferencesEntry, DisplacementTableEntry, LightmapPatchTableEntry, OwnedMapData,
-    TextureTableEntry, WriteTo,
+    shared_texture_path, AnimatedTextureTableEntry, BspLeaf, BspNode, ClusterGeometryTableEntry,
+    ClusterLightmapTableEntry, ClusterLodTableEntry, CommonLightmapTableEntry,
+    DisplacementLightmapTableEntry, DisplacementTableEntry, FogTableEntry, LandmarkTableEntry,
+    LevelTransitionTableEntry, LightmapPatchTableEntry, NameTableEntry, OwnedMapData,
+    RelocationTableEntry, TextureTableEntry, WriteTo,
 };
+use inception_render_common::vertex_format::VertexFormat;
 use memmap::Mmap;
 use nalgebra_glm::{lerp, vec2, vec3, Mat2x3, Vec2, Vec3};
 use num_traits::PrimInt;
 use ordered_float::NotNan;
-use source_reader::asset::vmt::{
-    LightmappedGeneric, Shader, Sky, UnlitGeneric, WorldVertexTransition,
-};
+use sha2::{Digest, Sha256};
+use source_reader::asset::dependency_graph::{AssetKind, DependencyGraph};
+use source_reader::asset::skybox::{Skybox, SkyboxFace};
+use source_reader::asset::vmt::{LightmappedGeneric, Shader, UnlitGeneric, WorldVertexTransition};
 use source_reader::asset::vtf::{Vtf, VtfFaceMip};
 use source_reader::asset::AssetLoader;
 use source_reader::bsp::{Bsp, DispInfo, Face};
 use source_reader::file::zip::ZipArchiveLoader;
 use source_reader::file::FallbackFileLoader;
 use source_reader::geometry::{convert_vertex, Vertex};
-use source_reader::lightmap::{build_lightmaps, Lightmap, LightmapMetadata, LightmapPatch};
+use source_reader::lightmap::{build_grouped_lightmaps, Lightmap, LightmapMetadata, LightmapPatch};
 use source_reader::vpk::path::VpkPath;
 use source_reader::vpk::Vpk;
-use texture_format::{TextureBuf, TextureFormat};
+use texture_format::{StreamingEncoder, TextureBuf, TextureFormat, TextureSlice};
 
+use crate::budget::{check_budget, BudgetConfig};
+use crate::cluster_merge::{ClusterGroups, MAX_GROUP_LUXELS};
 use crate::counter::Counter;
+use crate::detail::{
+    pack_detail_sprites, process_detail_props, DetailGeometry, PackedDetailSprites,
+};
 use crate::draw_builder::DrawBuilder;
 use crate::gx_helpers::DisplayListExt;
 use crate::legacy_pass_params::{DisplacementPass, Pass, ShaderParams, ShaderParamsAlpha};
+use crate::lightmap_report::{report_lightmaps, LightmapReportConfig};
+use crate::overlay::{pack_overlays, process_overlays, OverlayGeometry, PackedOverlays};
 use crate::packed_material::PackedMaterial;
+use crate::pass_config::{FaceSource, PassClassifier, PassConfig};
+use crate::prop::{pack_props, process_static_props, PackedProps, PropGeometry};
+use crate::shared_textures::{split_shared_textures, write_shared_textures};
 use crate::texture_key::{OwnedTextureKey, TextureIdAllocator};
 use crate::write_big_endian::WriteBigEndian;
 use crate::{hashable_float, FloatByBits};
 
-pub fn pack_map(hl2_base: &Path, dst: &Path, map_name_or_path: &str) -> Result<()> {
-    let map_path = if map_name_or_path.ends_with(".bsp") {
+fn resolve_map_path(hl2_base: &Path, map_name_or_path: &str) -> PathBuf {
+    if map_name_or_path.ends_with(".bsp") {
         map_name_or_path.into()
     } else {
         let mut path = hl2_base.join("maps");
         path.push(format!("{}.bsp", map_name_or_path));
         path
-    };
+    }
+}
+
+fn map_bsp_file(map_path: &Path) -> Result<Mmap> {
     let bsp_file =
-        File::open(&map_path).with_context(|| format!("Opening map file {:?}", map_path))?;
-    let bsp_data = unsafe { Mmap::map(&bsp_file) }?;
-    let bsp = Bsp::new(&bsp_data);
+        File::open(map_path).with_context(|| format!("Opening map file {:?}", map_path))?;
+    Ok(unsafe { Mmap::map(&bsp_file) }?)
+}
+
+/// Upper bound on the asset files each map's loader keeps cached. `pack-all-maps` runs several
+/// loaders at once.
+const ASSET_CACHE_BUDGET: usize = 256 << 20;
 
+/// Creates an asset loader that prefers files embedded in the map over the game's VPKs.
+fn map_asset_loader<'a>(hl2_base: &Path, bsp: Bsp<'a>) -> Result<AssetLoader<'a>> {
     let pak_loader = Rc::new(ZipArchiveLoader::new(bsp.pak_file()));
     let material_loader = Rc::new(FallbackFileLoader::new(vec![
         Rc::clone(&pak_loader) as _,
@@ -65,53 +89,186 @@ pub fn pack_map(hl2_base: &Path, dst: &Path, map_name_or_path: &str) -> Result<(
         pak_loader,
         Rc::new(Vpk::new(hl2_base.join("hl2_textures"))?),
     ]));
-    let asset_loader = AssetLoader::new(material_loader, texture_loader);
+    Ok(AssetLoader::new(material_loader, texture_loader).with_budget(ASSET_CACHE_BUDGET))
+}
+
+pub fn describe_map_dependencies(hl2_base: &Path, map_name_or_path: &str) -> Result<()> {
+    let bsp_data = map_bsp_file(&resolve_map_path(hl2_base, map_name_or_path))?;
+    let bsp = Bsp::new(&bsp_data);
+    let asset_loader = map_asset_loader(hl2_base, bsp)?;
+    let graph = DependencyGraph::from_bsp(bsp, &asset_loader)?;
+
+    for (path, node) in graph.iter() {
+        match node.size() {
+            Some(size) => println!("{:>10} {}", size, path),
+            None => println!("{:>10} {}", "MISSING", path),
+        }
+        for dependency in node.dependencies() {
+            println!("{:>10}   -> {}", "", dependency);
+        }
+    }
+
+    let missing: Vec<_> = graph.missing().collect();
+    println!(
+        "{} materials, {} files, {} bytes, {} missing",
+        graph.roots().len(),
+        graph.iter().count(),
+        graph.total_size(),
+        missing.len(),
+    );
+    if !missing.is_empty() {
+        bail!(
+            "missing assets: {}",
+            missing
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+    }
+    Ok(())
+}
 
-    let (cluster_lightmaps, displacement_lightmaps) = build_lightmaps(bsp)?;
-    let map_geometry = process_geometry(
+/// Packs a map into `dst`. With `dry_run`, prints which pass each brush face landed in instead
+/// of writing anything. With `merge_clusters`, clusters that are always visible together share
+/// display lists. With `texture_names`, the map keeps each texture's name for runtime diagnostics.
+#[allow(clippy::too_many_arguments)]
+pub fn pack_map(
+    hl2_base: &Path,
+    dst: &Path,
+    map_name_or_path: &str,
+    pass_config: &PassConfig,
+    dry_run: bool,
+    merge_clusters: bool,
+    lightmap_report: &LightmapReportConfig,
+    budget: &BudgetConfig,
+    share_textures: bool,
+    texture_names: bool,
+) -> Result<()> {
+    let map_path = resolve_map_path(hl2_base, map_name_or_path);
+    let map_name = map_path.file_stem().unwrap().to_str().unwrap();
+    let bsp_data = map_bsp_file(&map_path)?;
+    let bsp = Bsp::new(&bsp_data);
+    let asset_loader = map_asset_loader(hl2_base, bsp)?;
+
+    let cluster_groups = if merge_clusters {
+        ClusterGroups::from_visibility(bsp, MAX_GROUP_LUXELS)
+    } else {
+        ClusterGroups::separate(bsp.visibility().num_clusters())
+    };
+    let (cluster_lightmaps, displacement_lightmaps) =
+        build_grouped_lightmaps(bsp, |cluster| cluster_groups.group(cluster))?;
+    let mut classifier = PassClassifier::new(pass_config.for_map(map_name));
+    let mut map_geometry = process_geometry(
         bsp,
+        &cluster_groups,
         &cluster_lightmaps,
         &displacement_lightmaps,
         &asset_loader,
+        &mut classifier,
     )?;
+    if dry_run {
+        classifier.print_report();
+        return Ok(());
+    }
+    if merge_clusters {
+        merge_cluster_geometry(&mut map_geometry.clusters, &cluster_groups);
+    }
 
-    let (texture_table, texture_data) = pack_textures(&asset_loader, &map_geometry)?;
-    let (
+    let (mut texture_table, mut texture_data, animated_texture_table) =
+        pack_textures(&asset_loader, &map_geometry)?;
+    let (shared_texture_table, shared_textures) = if share_textures {
+        split_shared_textures(
+            &mut texture_table,
+            &mut texture_data,
+            &animated_texture_table,
+        )
+    } else {
+        Default::default()
+    };
+    let (texture_name_table, mut name_data) = if texture_names {
+        pack_texture_names(&map_geometry.texture_keys)
+    } else {
+        Default::default()
+    };
+    let (level_transition_table, landmark_table) = pack_level_transitions(bsp, &mut name_data)?;
+    let fog_table = pack_fog_table(bsp);
+    let PackedClusterGeometry {
         cluster_geometry_table,
+        cluster_lod_table,
         cluster_geometry_byte_code,
         cluster_geometry_display_lists,
-        cluster_geometry_references,
-    ) = pack_brush_geometry(&map_geometry, &texture_table);
+        cluster_geometry_relocations,
+    } = pack_brush_geometry(&map_geometry, &texture_table);
     let bsp_nodes = pack_bsp_nodes(bsp);
     let bsp_leaves = pack_bsp_leaves(bsp);
     let visibility = pack_visibility(bsp);
     let (lightmap_cluster_table, lightmap_displacement_table, lightmap_patch_table, lightmap_data) =
-        pack_lightmaps(bsp, &cluster_lightmaps, &displacement_lightmaps);
+        pack_lightmaps(
+            bsp,
+            &cluster_groups,
+            &cluster_lightmaps,
+            &displacement_lightmaps,
+        );
+    report_lightmaps(lightmap_report, &cluster_lightmaps, &displacement_lightmaps);
     let (
         displacement_table,
         displacement_byte_code,
         displacement_display_lists,
-        displacement_references,
+        displacement_relocations,
     ) = pack_displacement_geometry(&map_geometry, &texture_table);
+    let PackedOverlays {
+        overlay_table,
+        overlay_byte_code,
+        overlay_display_lists,
+        overlay_relocations,
+    } = pack_overlays(
+        map_geometry.clusters.len(),
+        &map_geometry.overlays,
+        &texture_table,
+    );
+    let PackedProps {
+        prop_table,
+        prop_byte_code,
+        prop_display_lists,
+        prop_relocations,
+    } = pack_props(
+        map_geometry.clusters.len(),
+        &map_geometry.props,
+        &texture_table,
+    );
+    let PackedDetailSprites {
+        detail_sprite_type_table,
+        detail_sprite_cluster_table,
+        detail_sprites,
+    } = pack_detail_sprites(map_geometry.clusters.len(), &map_geometry.detail_sprites);
 
     let dst_path = dst.join("maps");
     create_dir_all(&dst_path)?;
 
-    let dst_file_name = format!("{}.dat", map_path.file_stem().unwrap().to_str().unwrap());
-    let mut file = File::create(&dst_path.join(dst_file_name))?;
-    OwnedMapData {
+    // Every texture reference gets patched at load time, and animated textures are found again
+    // through the same table.
+    let relocation_table: Vec<_> = cluster_geometry_relocations
+        .into_iter()
+        .chain(displacement_relocations)
+        .chain(overlay_relocations)
+        .chain(prop_relocations)
+        .collect();
+
+    let map_data = OwnedMapData {
         position_data: map_geometry.position_data,
         normal_data: map_geometry.normal_data,
         texture_coord_data: map_geometry.texture_coord_data,
         cluster_geometry_table,
         cluster_geometry_byte_code,
         cluster_geometry_display_lists,
-        cluster_geometry_references,
         bsp_nodes,
         bsp_leaves,
         visibility,
         texture_table,
         texture_data,
+        texture_name_table,
+        name_data,
         lightmap_cluster_table,
         lightmap_displacement_table,
         lightmap_patch_table,
@@ -122,11 +279,83 @@ pub fn pack_map(hl2_base: &Path, dst: &Path, map_name_or_path: &str) -> Result<(
         displacement_table,
         displacement_byte_code,
         displacement_display_lists,
-        displacement_references,
-    }
-    .write_to(&mut file)?;
+        level_transition_table,
+        landmark_table,
+        vertex_format_table: vec![
+            VertexFormat::Brush.descriptor(),
+            VertexFormat::Displacement.descriptor(),
+            VertexFormat::Overlay.descriptor(),
+        ],
+        cluster_lod_table,
+        overlay_table,
+        overlay_byte_code,
+        overlay_display_lists,
+        animated_texture_table,
+        relocation_table,
+        prop_table,
+        prop_byte_code,
+        prop_display_lists,
+        detail_sprite_type_table,
+        detail_sprite_cluster_table,
+        detail_sprites,
+        fog_table,
+        shared_texture_table,
+    };
+    let mut packed = Cursor::new(Vec::new());
+    map_data.write_to(&mut packed)?;
+    let packed = packed.into_inner();
+    let sections = map_data.serialize_sections()?;
+    // Shared textures are loaded alongside the map, so they count against its budget too.
+    let shared_texture_bytes: usize = map_data
+        .shared_texture_table
+        .iter()
+        .map(|entry| entry.size as usize)
+        .sum();
+    let section_sizes: Vec<_> = sections
+        .iter()
+        .map(|(name, data)| (*name, data.len()))
+        .chain([("shared_textures", shared_texture_bytes)])
+        .collect();
+    check_budget(
+        budget,
+        map_name,
+        packed.len() + shared_texture_bytes,
+        &section_sizes,
+        &map_data.texture_table,
+    )?;
+    write_shared_textures(dst, map_name, &shared_textures)?;
+
+    let mut file = File::create(dst_path.join(format!("{}.dat", map_name)))?;
+    file.write_all(&packed)?;
     file.flush()?;
 
+    let mut manifest = File::create(dst_path.join(format!("{}.manifest", map_name)))?;
+    write_manifest_line(&mut manifest, "*", &packed)?;
+    for (name, data) in &sections {
+        write_manifest_line(&mut manifest, name, data)?;
+    }
+    for (hash, data) in &shared_textures {
+        write_manifest_line(&mut manifest, &shared_texture_path(hash), data)?;
+    }
+    manifest.flush()?;
+
+    Ok(())
+}
+
+/// Records a SHA-256 hash, length, and CRC-32 for a section, or for the whole file if `name` is `*`.
+fn write_manifest_line(w: &mut impl Write, name: &str, data: &[u8]) -> Result<()> {
+    let hash: String = Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    writeln!(
+        w,
+        "{} {} {:08x} {}",
+        hash,
+        data.len(),
+        crc32fast::hash(data),
+        name,
+    )?;
     Ok(())
 }
 
@@ -140,7 +369,12 @@ struct MapGeometry {
     displacement_texture_coordinate_data: Vec<u8>,
     displacement_display_lists_by_pass_face_material:
         BTreeMap<(DisplacementPass, u16, PackedMaterial), DisplayList>,
+    overlays: OverlayGeometry,
+    props: PropGeometry,
+    detail_sprites: DetailGeometry,
     texture_keys: Vec<OwnedTextureKey>,
+    /// Keyed by texture ID.
+    texture_frame_rates: BTreeMap<u16, f32>,
 }
 
 struct AttributeBuilder<Value, Index> {
@@ -202,15 +436,71 @@ impl<'a, Vertex: Copy + WriteBigEndian> PolygonBuilder<'a, Vertex> {
     }
 }
 
+/// Faces smaller than this many square units are left out of cluster LOD geometry.
+const LOD_MIN_FACE_AREA: f32 = 32.0 * 32.0;
+
+/// The alpha-blended pass is left out of cluster LOD geometry entirely.
+const LOD_EXCLUDED_MODE: u8 = 3;
+
+type DisplayListsByPassMaterialParams = BTreeMap<(Pass, PackedMaterial, ShaderParams), DisplayList>;
+
 struct ClusterGeometry {
-    display_lists_by_pass_material_params:
-        BTreeMap<(Pass, PackedMaterial, ShaderParams), DisplayList>,
+    display_lists_by_pass_material_params: DisplayListsByPassMaterialParams,
+    /// A cheaper version for distant clusters. Small faces and the alpha-blended pass are dropped.
+    lod_display_lists_by_pass_material_params: DisplayListsByPassMaterialParams,
+    mins: [f32; 3],
+    maxs: [f32; 3],
+}
+
+impl ClusterGeometry {
+    /// Takes on another cluster's geometry, concatenating display lists that share a pass,
+    /// material, and shader params.
+    fn merge(&mut self, other: ClusterGeometry) {
+        fn merge_display_lists(
+            dst: &mut DisplayListsByPassMaterialParams,
+            src: DisplayListsByPassMaterialParams,
+        ) {
+            for (key, display_list) in src {
+                dst.entry(key)
+                    .or_default()
+                    .commands
+                    .extend(display_list.commands);
+            }
+        }
+
+        merge_display_lists(
+            &mut self.display_lists_by_pass_material_params,
+            other.display_lists_by_pass_material_params,
+        );
+        merge_display_lists(
+            &mut self.lod_display_lists_by_pass_material_params,
+            other.lod_display_lists_by_pass_material_params,
+        );
+        for axis in 0..3 {
+            self.mins[axis] = self.mins[axis].min(other.mins[axis]);
+            self.maxs[axis] = self.maxs[axis].max(other.maxs[axis]);
+        }
+    }
 }
 
-#[derive(Default)]
 struct ClusterGeometryBuilder {
     draw_builders_by_pass_material_params:
         BTreeMap<(Pass, PackedMaterial, ShaderParams), DrawBuilder>,
+    lod_draw_builders_by_pass_material_params:
+        BTreeMap<(Pass, PackedMaterial, ShaderParams), DrawBuilder>,
+    mins: [f32; 3],
+    maxs: [f32; 3],
+}
+
+impl Default for ClusterGeometryBuilder {
+    fn default() -> Self {
+        Self {
+            draw_builders_by_pass_material_params: BTreeMap::new(),
+            lod_draw_builders_by_pass_material_params: BTreeMap::new(),
+            mins: [f32::INFINITY; 3],
+            maxs: [f32::NEG_INFINITY; 3],
+        }
+    }
 }
 
 impl ClusterGeometryBuilder {
@@ -222,27 +512,103 @@ impl ClusterGeometryBuilder {
     ) -> &mut DrawBuilder {
         self.draw_builders_by_pass_material_params
             .entry((pass, material, params))
-            .or_insert_with(|| DrawBuilder::new(GxPrimitive::Triangles, 0))
+            .or_insert_with(|| DrawBuilder::new(GxPrimitive::Triangles, VertexFormat::Brush))
+    }
+
+    pub fn lod_draw_builder(
+        &mut self,
+        pass: Pass,
+        material: PackedMaterial,
+        params: ShaderParams,
+    ) -> &mut DrawBuilder {
+        self.lod_draw_builders_by_pass_material_params
+            .entry((pass, material, params))
+            .or_insert_with(|| DrawBuilder::new(GxPrimitive::Triangles, VertexFormat::Brush))
+    }
+
+    pub fn extend_bounds(&mut self, position: [f32; 3]) {
+        for ((min, max), x) in self.mins.iter_mut().zip(&mut self.maxs).zip(position) {
+            *min = min.min(x);
+            *max = max.max(x);
+        }
     }
 
     pub fn build(self) -> ClusterGeometry {
-        ClusterGeometry {
-            display_lists_by_pass_material_params: self
-                .draw_builders_by_pass_material_params
+        fn build_display_lists(
+            draw_builders: BTreeMap<(Pass, PackedMaterial, ShaderParams), DrawBuilder>,
+        ) -> DisplayListsByPassMaterialParams {
+            draw_builders
                 .into_iter()
                 .map(|(key, draw_builder)| (key, draw_builder.build()))
                 .filter(|(_, display_list)| !display_list.commands.is_empty())
-                .collect(),
+                .collect()
+        }
+
+        ClusterGeometry {
+            display_lists_by_pass_material_params: build_display_lists(
+                self.draw_builders_by_pass_material_params,
+            ),
+            lod_display_lists_by_pass_material_params: build_display_lists(
+                self.lod_draw_builders_by_pass_material_params,
+            ),
+            mins: self.mins,
+            maxs: self.maxs,
         }
     }
 }
 
+/// Moves each cluster's geometry into the first cluster of its group, leaving the rest empty, and
+/// reports how many display list calls that saves when every cluster is drawn.
+fn merge_cluster_geometry(clusters: &mut [ClusterGeometry], cluster_groups: &ClusterGroups) {
+    fn count_display_lists(clusters: &[ClusterGeometry]) -> usize {
+        clusters
+            .iter()
+            .map(|cluster| cluster.display_lists_by_pass_material_params.len())
+            .sum()
+    }
+
+    let display_lists_before = count_display_lists(clusters);
+    for cluster in 0..clusters.len() {
+        // Groups are named after their lowest-numbered cluster, which is always visited first.
+        let group = cluster_groups.group(i16::try_from(cluster).unwrap()) as usize;
+        if group != cluster {
+            let geometry = replace(
+                &mut clusters[cluster],
+                ClusterGeometryBuilder::default().build(),
+            );
+            clusters[group].merge(geometry);
+        }
+    }
+    let display_lists_after = count_display_lists(clusters);
+
+    println!(
+        "Merged {} clusters into {} groups: {} display lists became {} ({:.1}% fewer)",
+        cluster_groups.cluster_count(),
+        cluster_groups.group_count(),
+        display_lists_before,
+        display_lists_after,
+        100.0 * (display_lists_before - display_lists_after) as f32
+            / display_lists_before.max(1) as f32,
+    );
+}
+
 fn process_geometry(
     bsp: Bsp,
-    cluster_lightmaps: &HashMap<i16, Lightmap>,
-    displacement_lightmaps: &HashMap<u16, Lightmap>,
+    cluster_groups: &ClusterGroups,
+    cluster_lightmaps: &BTreeMap<i16, Lightmap>,
+    displacement_lightmaps: &BTreeMap<u16, Lightmap>,
     asset_loader: &AssetLoader,
+    classifier: &mut PassClassifier,
 ) -> Result<MapGeometry> {
+    // Decode every texture the map could need up front, in parallel.
+    let graph = DependencyGraph::from_bsp(bsp, asset_loader)?;
+    asset_loader.prefetch_textures(
+        graph
+            .iter()
+            .filter(|(_, node)| node.kind() == AssetKind::Texture && !node.is_missing())
+            .map(|(path, _)| path),
+    )?;
+
     let mut ids = TextureIdAllocator::new();
     // The first five texture IDs are reserved for the 2D skybox.
     allocate_skybox_textures(bsp, asset_loader, &mut ids)?;
@@ -252,7 +618,7 @@ fn process_geometry(
     let mut texture_coords = AttributeBuilder::new();
     let mut clusters: Vec<ClusterGeometryBuilder> = Vec::new();
     for leaf in bsp.iter_worldspawn_leaves() {
-        let cluster = leaf.cluster();
+        let cluster = bsp.leaf(leaf).cluster();
         if cluster == -1 {
             // Leaf is not potentially visible from anywhere.
             continue;
@@ -261,9 +627,10 @@ fn process_geometry(
             clusters.resize_with(cluster as usize + 1, Default::default);
         }
         let cluster_builder = &mut clusters[cluster as usize];
-        let lightmap = cluster_lightmaps.get(&cluster);
+        let lightmap = cluster_lightmaps.get(&cluster_groups.group(cluster));
 
         for face in bsp.iter_faces_from_leaf(leaf) {
+            let face = bsp.face(face);
             if face.tex_info != -1 {
                 process_textured_brush_face(
                     bsp,
@@ -273,12 +640,38 @@ fn process_geometry(
                     &mut normals,
                     &mut texture_coords,
                     cluster_builder,
+                    classifier,
                     lightmap,
                     face,
+                    FaceSource::World,
+                    Vec3::zeros(),
                 )?;
             }
         }
     }
+    for (cluster, face, origin) in bsp.iter_static_brush_entity_faces() {
+        let face = bsp.face(face);
+        if face.tex_info == -1 {
+            continue;
+        }
+        if clusters.len() < (cluster as usize + 1) {
+            clusters.resize_with(cluster as usize + 1, Default::default);
+        }
+        process_textured_brush_face(
+            bsp,
+            asset_loader,
+            &mut ids,
+            &mut positions,
+            &mut normals,
+            &mut texture_coords,
+            &mut clusters[cluster as usize],
+            classifier,
+            cluster_lightmaps.get(&cluster_groups.group(cluster)),
+            face,
+            FaceSource::BrushEntity,
+            origin,
+        )?;
+    }
 
     let mut displacement_positions = AttributeBuilder::new();
     let mut displacement_vertex_colors = AttributeBuilder::new();
@@ -304,6 +697,11 @@ fn process_geometry(
             .map(|(key, builder)| (key, builder.build()))
             .collect();
 
+    let overlays = process_overlays(bsp, asset_loader, &mut ids)?;
+    let props = process_static_props(bsp, asset_loader, &mut ids)?;
+    let detail_sprites = process_detail_props(bsp, asset_loader, &mut ids)?;
+    let (texture_keys, texture_frame_rates) = ids.into_keys_and_frame_rates();
+
     Ok(MapGeometry {
         position_data: positions.build(),
         normal_data: normals.build(),
@@ -316,7 +714,11 @@ fn process_geometry(
         displacement_vertex_color_data: displacement_vertex_colors.build(),
         displacement_texture_coordinate_data: displacement_texture_coordinates.build(),
         displacement_display_lists_by_pass_face_material,
-        texture_keys: ids.into_keys(),
+        overlays,
+        props,
+        detail_sprites,
+        texture_keys,
+        texture_frame_rates,
     })
 }
 
@@ -325,30 +727,14 @@ fn allocate_skybox_textures(
     asset_loader: &AssetLoader,
     ids: &mut TextureIdAllocator,
 ) -> Result<()> {
-    let entities = bsp.entities();
-    let worldspawn = &entities[0];
-
-    for face in ["rt", "lf", "bk", "ft", "up"] {
-        let material = asset_loader.get_material(&VpkPath::new_with_prefix_and_extension(
-            &format!("{}{}", &worldspawn["skyname"], face),
-            "materials/skybox",
-            "vmt",
-        ))?;
-        let texture_path: &VpkPath = match material.shader() {
-            Shader::UnlitGeneric(UnlitGeneric {
-                base_texture_path, ..
-            }) => base_texture_path,
-
-            Shader::Sky(Sky { base_texture_path }) => base_texture_path,
-
-            shader => panic!(
-                "Unexpected skybox shader {:?} in {}",
-                shader.name(),
-                material.path(),
-            ),
-        };
+    let skyname = bsp
+        .map_settings()
+        .skyname
+        .context("worldspawn has no skyname")?;
+    let skybox = Skybox::load(asset_loader, &skyname)?;
+    for face in SkyboxFace::DRAWN {
         ids.get_force_unique(&OwnedTextureKey::EncodeAsIs {
-            texture_path: texture_path.to_owned(),
+            texture_path: skybox.face(face).unwrap().texture_path.clone(),
         });
     }
 
@@ -439,7 +825,7 @@ fn process_displacement(
     };
     let display_list_builder = draw_builders_by_pass_face_material
         .entry((pass, disp_info.map_face, packed_material))
-        .or_insert_with(|| DrawBuilder::new(GxPrimitive::Quads, 0));
+        .or_insert_with(|| DrawBuilder::new(GxPrimitive::Quads, VertexFormat::Displacement));
 
     struct DisplacementVertex {
         position: Vec3,
@@ -621,8 +1007,11 @@ fn process_textured_brush_face(
     normals: &mut AttributeBuilder<[u8; 3], u16>,
     texture_coords: &mut AttributeBuilder<[u16; 2], u16>,
     cluster_builder: &mut ClusterGeometryBuilder,
+    classifier: &mut PassClassifier,
     lightmap: Option<&Lightmap>,
     face: &Face,
+    source: FaceSource,
+    origin: Vec3,
 ) -> Result<()> {
     let tex_info = &bsp.tex_infos()[face.tex_info as usize];
     if tex_info.tex_data == -1 {
@@ -633,12 +1022,10 @@ fn process_textured_brush_face(
 
     // This is a textured face.
     let tex_data = &bsp.tex_datas()[tex_info.tex_data as usize];
-    let material_path = VpkPath::new_with_prefix_and_extension(
-        bsp.tex_data_strings()
-            .get(tex_data.name_string_table_id as usize),
-        "materials",
-        "vmt",
-    );
+    let material_name = bsp
+        .tex_data_strings()
+        .get(tex_data.name_string_table_id as usize);
+    let material_path = VpkPath::new_with_prefix_and_extension(material_name, "materials", "vmt");
     let material = asset_loader.get_material(&material_path)?;
     let base_texture_size = match material.shader() {
         Shader::LightmappedGeneric(LightmappedGeneric {
@@ -673,17 +1060,16 @@ fn process_textured_brush_face(
     };
     let packed_material =
         PackedMaterial::from_material(asset_loader, ids, &material, false)?.unwrap();
-    let mut polygon_builder = PolygonBuilder::new(cluster_builder.draw_builder(
-        Pass::from_material(&material, &packed_material),
-        packed_material,
-        ShaderParams::from_material(&material),
-    ));
+    let (pass, params) = classifier.classify(material_name, source, &material, &packed_material)?;
 
     let texture_transform = material.texture_transform();
     let face_vertices: Vec<Vertex> = bsp
         .iter_vertex_indices_from_face(face)
         .map(|vertex_index| {
             let mut vertex = convert_vertex(bsp, lightmap, face, tex_info, vertex_index);
+            for (position, offset) in vertex.position.iter_mut().zip(origin.iter()) {
+                *position += offset;
+            }
             let texture_coord = texture_transform
                 * vec3(
                     vertex.texture_coord[0] / base_texture_size[0],
@@ -706,7 +1092,13 @@ fn process_textured_brush_face(
         .min()
         .unwrap();
 
+    let include_in_lod = pass.as_mode() != LOD_EXCLUDED_MODE
+        && polygon_area(face_vertices.iter().map(|vertex| vertex.position)) >= LOD_MIN_FACE_AREA;
+
+    let mut packed_vertices = Vec::with_capacity(face_vertices.len());
     for vertex in face_vertices {
+        cluster_builder.extend_bounds(vertex.position);
+
         let position_index: u16 = positions.add_vertex(hashable_float(&vertex.position));
         let normal_index: u16 = normals.add_vertex(quantize_normal(vertex.normal));
         let lightmap_coord = quantize_lightmap_coord(vertex.lightmap_coord);
@@ -714,18 +1106,45 @@ fn process_textured_brush_face(
             vertex.texture_coord[0] - min_tile_s,
             vertex.texture_coord[1] - min_tile_t,
         ]));
-
-        polygon_builder.add_vertex((
+        packed_vertices.push((
             position_index,
             normal_index,
             lightmap_coord,
             texture_coord_index,
-        ))?;
+        ));
+    }
+
+    let mut polygon_builder = PolygonBuilder::new(cluster_builder.draw_builder(
+        pass,
+        packed_material.clone(),
+        params.clone(),
+    ));
+    for &vertex in &packed_vertices {
+        polygon_builder.add_vertex(vertex)?;
+    }
+    if include_in_lod {
+        let mut polygon_builder =
+            PolygonBuilder::new(cluster_builder.lod_draw_builder(pass, packed_material, params));
+        for &vertex in &packed_vertices {
+            polygon_builder.add_vertex(vertex)?;
+        }
     }
 
     Ok(())
 }
 
+/// Computes the area of a planar convex polygon.
+fn polygon_area(vertices: impl Iterator<Item = [f32; 3]>) -> f32 {
+    let vertices: Vec<Vec3> = vertices.map(|v| vec3(v[0], v[1], v[2])).collect();
+    let Some((&first, rest)) = vertices.split_first() else {
+        return 0.0;
+    };
+    rest.windows(2)
+        .map(|pair| (pair[0] - first).cross(&(pair[1] - first)).norm())
+        .sum::<f32>()
+        / 2.0
+}
+
 fn quantize_normal(normal: [f32; 3]) -> [u8; 3] {
     let mut result = [0; 3];
     for index in 0..3 {
@@ -750,6 +1169,21 @@ fn quantize_lightmap_coord(coord: [f32; 2]) -> [u16; 2] {
     result
 }
 
+/// Appends `src` encoded as `format`. Uncompressed sources, like the 4096x4096 HDR skybox faces,
+/// are encoded a block row at a time so no second full-size copy is ever held.
+fn append_encoded(texture_data: &mut Vec<u8>, src: TextureSlice, format: TextureFormat) {
+    match src.format() {
+        // Copied or repacked block for block, so transcoding doesn't expand them.
+        src_format
+            if src_format == format
+                || matches!(src_format, TextureFormat::Dxt1 | TextureFormat::Dxt5) =>
+        {
+            texture_data.extend_from_slice(TextureBuf::transcode(src, format).data())
+        }
+        _ => StreamingEncoder::encode_slice(format, src, |row| texture_data.extend_from_slice(row)),
+    }
+}
+
 fn quantize_texture_coord(coord: [f32; 2]) -> [u16; 2] {
     let mut result = [0; 2];
     for index in 0..2 {
@@ -769,7 +1203,11 @@ fn quantize_texture_coord(coord: [f32; 2]) -> [u16; 2] {
 fn pack_textures(
     asset_loader: &AssetLoader,
     map_geometry: &MapGeometry,
-) -> Result<(Vec<TextureTableEntry>, Vec<u8>)> {
+) -> Result<(
+    Vec<TextureTableEntry>,
+    Vec<u8>,
+    Vec<AnimatedTextureTableEntry>,
+)> {
     fn get_dst_format(src_format: TextureFormat) -> Result<TextureFormat> {
         Ok(match src_format {
             TextureFormat::Dxt1 | TextureFormat::Dxt5 | TextureFormat::Rgba16f => {
@@ -784,11 +1222,11 @@ fn pack_textures(
         })
     }
 
-    fn limit_face_mips(texture: &Vtf, max_dimension: usize) -> Vec<VtfFaceMip> {
+    fn limit_face_mips(texture: &Vtf, frame: usize, max_dimension: usize) -> Vec<VtfFaceMip> {
         let mut any_mip_matched = false;
         let mut smallest_face_mip = None;
         let mut limited_face_mips = Vec::new();
-        for face_mip in texture.iter_face_mips() {
+        for face_mip in texture.iter_frame_face_mips(frame) {
             smallest_face_mip = Some(face_mip);
             if face_mip.texture.width() <= max_dimension
                 && face_mip.texture.height() <= max_dimension
@@ -805,25 +1243,36 @@ fn pack_textures(
         limited_face_mips
     }
 
+    // Frames of an animated texture are packed back to back after the first. Only the keys that
+    // encode a single texture can be animated.
+    let frame_count = |id: usize, texture: &Vtf| {
+        if map_geometry.texture_frame_rates.contains_key(&(id as u16)) {
+            texture.frame_count()
+        } else {
+            1
+        }
+    };
+
     const GAMECUBE_MEMORY_BUDGET: usize = 8 * 1024 * 1024;
     for max_dimension in [1024, 512, 256, 128, 64, 32, 16, 8] {
         let mut total_size = 0;
-        for key in &map_geometry.texture_keys {
+        for (id, key) in map_geometry.texture_keys.iter().enumerate() {
             match key {
                 OwnedTextureKey::EncodeAsIs { texture_path } => {
                     let texture = asset_loader.get_texture(texture_path)?;
                     let dst_format = get_dst_format(texture.format())?;
-                    for face_mip in limit_face_mips(&texture, max_dimension) {
-                        total_size += dst_format
-                            .metrics()
-                            .encoded_size(face_mip.texture.width(), face_mip.texture.height());
+                    for face_mip in limit_face_mips(&texture, 0, max_dimension) {
+                        total_size += frame_count(id, &texture)
+                            * dst_format
+                                .metrics()
+                                .encoded_size(face_mip.texture.width(), face_mip.texture.height());
                     }
                 }
 
                 OwnedTextureKey::Intensity { texture_path } => {
                     let texture = asset_loader.get_texture(texture_path)?;
                     let dst_format = TextureFormat::GxTfI8;
-                    for face_mip in limit_face_mips(&texture, max_dimension) {
+                    for face_mip in limit_face_mips(&texture, 0, max_dimension) {
                         total_size += dst_format
                             .metrics()
                             .encoded_size(face_mip.texture.width(), face_mip.texture.height());
@@ -833,10 +1282,11 @@ fn pack_textures(
                 OwnedTextureKey::AlphaToIntensity { texture_path } => {
                     let texture = asset_loader.get_texture(texture_path)?;
                     let dst_format = TextureFormat::GxTfI8;
-                    for face_mip in limit_face_mips(&texture, max_dimension) {
-                        total_size += dst_format
-                            .metrics()
-                            .encoded_size(face_mip.texture.width(), face_mip.texture.height());
+                    for face_mip in limit_face_mips(&texture, 0, max_dimension) {
+                        total_size += frame_count(id, &texture)
+                            * dst_format
+                                .metrics()
+                                .encoded_size(face_mip.texture.width(), face_mip.texture.height());
                     }
                 }
 
@@ -853,7 +1303,7 @@ fn pack_textures(
                         assert_eq!(intensity_texture.mips().len(), alpha_texture.mips().len());
 
                         let dst_format = TextureFormat::GxTfIa8;
-                        for face_mip in limit_face_mips(&intensity_texture, max_dimension) {
+                        for face_mip in limit_face_mips(&intensity_texture, 0, max_dimension) {
                             total_size += dst_format
                                 .metrics()
                                 .encoded_size(face_mip.texture.width(), face_mip.texture.height());
@@ -873,14 +1323,21 @@ fn pack_textures(
 
         let mut texture_table = Vec::new();
         let mut texture_data = Vec::new();
+        let mut animated_texture_table = Vec::new();
+        // Different keys can encode to identical data, e.g. when a material is duplicated under
+        // another name. Later copies are dropped and share the first copy's data.
+        let mut start_offsets_by_digest: HashMap<[u8; 32], u32> = HashMap::new();
+        let mut deduplicated_count = 0;
+        let mut deduplicated_size = 0;
 
         let budgeted_size = total_size;
         total_size = 0;
-        for key in &map_geometry.texture_keys {
+        for (id, key) in map_geometry.texture_keys.iter().enumerate() {
             struct TextureMetadata {
                 width: usize,
                 height: usize,
                 mip_count: usize,
+                frame_count: usize,
                 gx_flags: u8,
                 gx_format: u8,
             }
@@ -892,24 +1349,32 @@ fn pack_textures(
                     assert_eq!(texture.face_count(), 1);
 
                     let dst_format = get_dst_format(texture.format())?;
+                    let frame_count = frame_count(id, &texture);
                     let mut base_mip_size = None;
                     let mut mip_count = 0;
-                    for face_mip in limit_face_mips(&texture, max_dimension) {
-                        assert_eq!(face_mip.face, 0);
-                        if base_mip_size.is_none() {
-                            base_mip_size =
-                                Some((face_mip.texture.width(), face_mip.texture.height()));
+                    for frame in 0..frame_count {
+                        for face_mip in limit_face_mips(&texture, frame, max_dimension) {
+                            assert_eq!(face_mip.face, 0);
+                            if base_mip_size.is_none() {
+                                base_mip_size =
+                                    Some((face_mip.texture.width(), face_mip.texture.height()));
+                            }
+                            append_encoded(
+                                &mut texture_data,
+                                face_mip.texture.as_slice(),
+                                dst_format,
+                            );
+                            if frame == 0 {
+                                mip_count += 1;
+                            }
                         }
-                        texture_data.extend_from_slice(
-                            TextureBuf::transcode(face_mip.texture.as_slice(), dst_format).data(),
-                        );
-                        mip_count += 1;
                     }
 
                     TextureMetadata {
                         width: base_mip_size.unwrap().0,
                         height: base_mip_size.unwrap().1,
                         mip_count,
+                        frame_count,
                         gx_flags: gx_texture_flags(texture.flags()),
                         gx_format: gx_texture_format(dst_format),
                     }
@@ -922,7 +1387,7 @@ fn pack_textures(
                     let dst_format = TextureFormat::GxTfI8;
                     let mut base_mip_size = None;
                     let mut mip_count = 0;
-                    for face_mip in limit_face_mips(&texture, max_dimension) {
+                    for face_mip in limit_face_mips(&texture, 0, max_dimension) {
                         assert_eq!(face_mip.face, 0);
                         if base_mip_size.is_none() {
                             base_mip_size =
@@ -938,6 +1403,7 @@ fn pack_textures(
                         width: base_mip_size.unwrap().0,
                         height: base_mip_size.unwrap().1,
                         mip_count,
+                        frame_count: 1,
                         gx_flags: gx_texture_flags(texture.flags()),
                         gx_format: gx_texture_format(dst_format),
                     }
@@ -948,49 +1414,57 @@ fn pack_textures(
                     assert_eq!(texture.face_count(), 1);
                     let dst_format = TextureFormat::GxTfI8;
 
+                    let frame_count = frame_count(id, &texture);
                     let mut base_mip_size = None;
                     let mut mip_count = 0;
-                    for face_mip in limit_face_mips(&texture, max_dimension) {
-                        assert_eq!(face_mip.face, 0);
-                        if base_mip_size.is_none() {
-                            base_mip_size =
-                                Some((face_mip.texture.width(), face_mip.texture.height()));
-                        }
+                    for frame in 0..frame_count {
+                        for face_mip in limit_face_mips(&texture, frame, max_dimension) {
+                            assert_eq!(face_mip.face, 0);
+                            if base_mip_size.is_none() {
+                                base_mip_size =
+                                    Some((face_mip.texture.width(), face_mip.texture.height()));
+                            }
 
-                        // Broadcast alpha to all channels.
-                        let mut texel_data = TextureBuf::transcode(
-                            face_mip.texture.as_slice(),
-                            TextureFormat::Rgba8,
-                        )
-                        .into_data();
-                        for texel_index in 0..face_mip.texture.width() * face_mip.texture.height() {
-                            let offset = 4 * texel_index;
-                            texel_data[offset] = texel_data[offset + 3];
-                            texel_data[offset + 1] = texel_data[offset + 3];
-                            texel_data[offset + 2] = texel_data[offset + 3];
-                        }
+                            // Broadcast alpha to all channels.
+                            let mut texel_data = TextureBuf::transcode(
+                                face_mip.texture.as_slice(),
+                                TextureFormat::Rgba8,
+                            )
+                            .into_data();
+                            for texel_index in
+                                0..face_mip.texture.width() * face_mip.texture.height()
+                            {
+                                let offset = 4 * texel_index;
+                                texel_data[offset] = texel_data[offset + 3];
+                                texel_data[offset + 1] = texel_data[offset + 3];
+                                texel_data[offset + 2] = texel_data[offset + 3];
+                            }
 
-                        texture_data.extend_from_slice(
-                            TextureBuf::transcode(
-                                TextureBuf::new(
-                                    TextureFormat::Rgba8,
-                                    face_mip.texture.width(),
-                                    face_mip.texture.height(),
-                                    texel_data,
+                            texture_data.extend_from_slice(
+                                TextureBuf::transcode(
+                                    TextureBuf::new(
+                                        TextureFormat::Rgba8,
+                                        face_mip.texture.width(),
+                                        face_mip.texture.height(),
+                                        texel_data,
+                                    )
+                                    .as_slice(),
+                                    dst_format,
                                 )
-                                .as_slice(),
-                                dst_format,
-                            )
-                            .data(),
-                        );
+                                .data(),
+                            );
 
-                        mip_count += 1;
+                            if frame == 0 {
+                                mip_count += 1;
+                            }
+                        }
                     }
 
                     TextureMetadata {
                         width: base_mip_size.unwrap().0,
                         height: base_mip_size.unwrap().1,
                         mip_count,
+                        frame_count,
                         gx_flags: gx_texture_flags(texture.flags()),
                         gx_format: gx_texture_format(dst_format),
                     }
@@ -1015,8 +1489,8 @@ fn pack_textures(
                         let mut base_mip_size = None;
                         let mut mip_count = 0;
                         let intensity_face_mips =
-                            limit_face_mips(&intensity_texture, max_dimension);
-                        let alpha_face_mips = limit_face_mips(&alpha_texture, max_dimension);
+                            limit_face_mips(&intensity_texture, 0, max_dimension);
+                        let alpha_face_mips = limit_face_mips(&alpha_texture, 0, max_dimension);
                         assert_eq!(intensity_face_mips.len(), alpha_face_mips.len());
                         for index in 0..intensity_face_mips.len() {
                             let intensity_face_mip = intensity_face_mips[index];
@@ -1081,6 +1555,7 @@ fn pack_textures(
                             width: base_mip_size.unwrap().0,
                             height: base_mip_size.unwrap().1,
                             mip_count,
+                            frame_count: 1,
                             gx_flags: gx_texture_flags(intensity_texture.flags()),
                             gx_format: gx_texture_format(dst_format),
                         }
@@ -1097,6 +1572,7 @@ fn pack_textures(
                             width: 8,
                             height: 8,
                             mip_count: 1,
+                            frame_count: 1,
                             gx_flags: 0,
                             gx_format: gx_texture_format(TextureFormat::GxTfCmpr),
                         }
@@ -1104,9 +1580,21 @@ fn pack_textures(
                 }
             };
 
-            let end_offset = u32::try_from(texture_data.len()).unwrap();
+            let packed_size = u32::try_from(texture_data.len()).unwrap() - start_offset;
+            let frame_size = packed_size / metadata.frame_count as u32;
+            let digest = Sha256::digest(&texture_data[start_offset as usize..]).into();
+            let start_offset = match start_offsets_by_digest.entry(digest) {
+                Entry::Occupied(entry) => {
+                    texture_data.truncate(start_offset as usize);
+                    deduplicated_count += 1;
+                    deduplicated_size += packed_size as usize;
+                    *entry.get()
+                }
+                Entry::Vacant(entry) => *entry.insert(start_offset),
+            };
+            let end_offset = start_offset + frame_size;
 
-            // Write a texture table entry.
+            // Write a texture table entry describing the first frame.
             texture_table.push(TextureTableEntry {
                 width: metadata.width as u16,
                 height: metadata.height as u16,
@@ -1117,16 +1605,145 @@ fn pack_textures(
                 start_offset,
                 end_offset,
             });
-            total_size += (end_offset - start_offset) as usize;
+            if metadata.frame_count > 1 {
+                animated_texture_table.push(AnimatedTextureTableEntry {
+                    texture_id: id as u16,
+                    frame_count: u16::try_from(metadata.frame_count).unwrap(),
+                    frames_per_second: map_geometry.texture_frame_rates[&(id as u16)],
+                });
+            }
+            total_size += packed_size as usize;
         }
 
         assert_eq!(total_size, budgeted_size);
+        assert_eq!(texture_data.len(), total_size - deduplicated_size);
+        println!(
+            "Deduplicated {deduplicated_count} textures, saving {deduplicated_size} bytes ({} bytes remain)",
+            texture_data.len(),
+        );
 
-        return Ok((texture_table, texture_data));
+        return Ok((texture_table, texture_data, animated_texture_table));
     }
     bail!("Unable to fit textures within the memory budget.");
 }
 
+fn pack_texture_names(texture_keys: &[OwnedTextureKey]) -> (Vec<NameTableEntry>, Vec<u8>) {
+    let mut name_table = Vec::new();
+    let mut name_data = Vec::new();
+    for key in texture_keys {
+        name_table.push(pack_name(&mut name_data, &key.to_string()));
+    }
+    (name_table, name_data)
+}
+
+fn pack_name(name_data: &mut Vec<u8>, name: &str) -> NameTableEntry {
+    let entry = NameTableEntry {
+        offset: u32::try_from(name_data.len()).unwrap(),
+        len: u32::try_from(name.len()).unwrap(),
+    };
+    name_data.extend_from_slice(name.as_bytes());
+    entry
+}
+
+fn pack_level_transitions(
+    bsp: Bsp,
+    name_data: &mut Vec<u8>,
+) -> Result<(Vec<LevelTransitionTableEntry>, Vec<LandmarkTableEntry>)> {
+    const SF_CHANGELEVEL_NOTOUCH: u32 = 0x0002;
+
+    let mut level_transition_table = Vec::new();
+    let mut landmark_table = Vec::new();
+    for entity in bsp.entities() {
+        match entity.get("classname").map(String::as_str) {
+            Some("trigger_changelevel") => {
+                let spawn_flags: u32 = match entity.get("spawnflags") {
+                    Some(value) => value.parse()?,
+                    None => 0,
+                };
+                if spawn_flags & SF_CHANGELEVEL_NOTOUCH != 0 {
+                    // Only fired by map logic, which isn't supported.
+                    continue;
+                }
+
+                let model_name = entity
+                    .get("model")
+                    .context("trigger_changelevel has no model")?;
+                let model_index: usize = model_name
+                    .strip_prefix('*')
+                    .with_context(|| format!("trigger_changelevel has bad model {:?}", model_name))?
+                    .parse()?;
+                let model = bsp.models().get(model_index).with_context(|| {
+                    format!("trigger_changelevel has bad model {:?}", model_name)
+                })?;
+                let origin = match entity.get("origin") {
+                    Some(value) => parse_vec3(value)?,
+                    None => [0.0; 3],
+                };
+
+                level_transition_table.push(LevelTransitionTableEntry {
+                    mins: [0, 1, 2].map(|i| model.mins[i] + origin[i]),
+                    maxs: [0, 1, 2].map(|i| model.maxs[i] + origin[i]),
+                    map_name: pack_name(
+                        name_data,
+                        entity
+                            .get("map")
+                            .context("trigger_changelevel has no map")?,
+                    ),
+                    landmark_name: pack_name(
+                        name_data,
+                        entity.get("landmark").map(String::as_str).unwrap_or(""),
+                    ),
+                });
+            }
+            Some("info_landmark") => {
+                let Some(name) = entity.get("targetname") else {
+                    continue;
+                };
+                landmark_table.push(LandmarkTableEntry {
+                    origin: parse_vec3(
+                        entity
+                            .get("origin")
+                            .context("info_landmark has no origin")?,
+                    )?,
+                    name: pack_name(name_data, name),
+                });
+            }
+            _ => (),
+        }
+    }
+    Ok((level_transition_table, landmark_table))
+}
+
+fn pack_fog_table(bsp: Bsp) -> Vec<FogTableEntry> {
+    let settings = bsp.map_settings();
+    [
+        (FogTableEntry::WORLD, settings.fog),
+        (FogTableEntry::SKYBOX, settings.skybox_fog),
+    ]
+    .into_iter()
+    .filter_map(|(kind, fog)| {
+        let fog = fog?;
+        Some(FogTableEntry {
+            start: fog.start,
+            end: fog.end,
+            max_density: fog.max_density.clamp(0.0, 1.0),
+            color: fog.color,
+            kind,
+        })
+    })
+    .collect()
+}
+
+pub fn parse_vec3(value: &str) -> Result<[f32; 3]> {
+    let components = value
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<Vec<f32>, _>>()?;
+    components
+        .try_into()
+        .map_err(|_| anyhow!("expected three components: {:?}", value))
+}
+
 fn gx_texture_flags(vtf_flags: u32) -> u8 {
     let wrap_s = (vtf_flags & 0x4) >> 2; // 0x01
     let wrap_t = (vtf_flags & 0x8) >> 2; // 0x02
@@ -1143,131 +1760,166 @@ fn gx_texture_format(format: TextureFormat) -> u8 {
     }
 }
 
+struct PackedClusterGeometry {
+    cluster_geometry_table: Vec<ClusterGeometryTableEntry>,
+    cluster_lod_table: Vec<ClusterLodTableEntry>,
+    cluster_geometry_byte_code: Vec<u32>,
+    cluster_geometry_display_lists: Vec<u8>,
+    cluster_geometry_relocations: Vec<RelocationTableEntry>,
+}
+
 fn pack_brush_geometry(
     map_geometry: &MapGeometry,
     texture_table: &[TextureTableEntry],
-) -> (
-    Vec<ClusterGeometryTableEntry>,
-    Vec<u32>,
-    Vec<u8>,
-    Vec<ClusterGeometryReferencesEntry>,
-) {
+) -> PackedClusterGeometry {
     // TODO: Transpose this table for potential cache friendliness?
 
     let mut cluster_geometry_table = Vec::new();
+    let mut cluster_lod_table = Vec::new();
     let mut cluster_geometry_byte_code = Vec::new();
     let mut cluster_geometry_display_lists = Vec::new();
-    let mut cluster_geometry_references = Vec::new();
+    let mut cluster_geometry_relocations = Vec::new();
 
     for cluster in &map_geometry.clusters {
-        let mut cluster_geometry_table_entry = ClusterGeometryTableEntry {
-            byte_code_index_ranges: [[0, 0]; 6],
-        };
-        let mut display_list_offset = u32::try_from(cluster_geometry_display_lists.len()).unwrap();
-        for mode in 0..6 {
-            cluster_geometry_table_entry.byte_code_index_ranges[mode as usize][0] =
-                u32::try_from(cluster_geometry_byte_code.len()).unwrap();
-
-            let mut prev_base_map_id = None;
-            let mut prev_aux_map_id = None;
-            let mut prev_alpha = None;
-            for ((pass, material, params), draw_display_list) in
-                &cluster.display_lists_by_pass_material_params
-            {
-                let mut display_list = DisplayList::new();
-                if pass.as_mode() == mode {
-                    // Bind the base texture as TEXMAP1 using TEXCOORD1.
-                    if prev_base_map_id != Some(material.base_id) {
-                        prev_base_map_id = Some(material.base_id);
-                        display_list.append_bind_texture(1, material.base_id, texture_table);
-                        display_list.append_texcoord_scale(1, material.base_id, texture_table);
-                    }
+        cluster_geometry_table.push(ClusterGeometryTableEntry {
+            byte_code_index_ranges: pack_cluster_display_lists(
+                &cluster.display_lists_by_pass_material_params,
+                texture_table,
+                &mut cluster_geometry_byte_code,
+                &mut cluster_geometry_display_lists,
+                &mut cluster_geometry_relocations,
+            ),
+        });
+        cluster_lod_table.push(ClusterLodTableEntry {
+            mins: cluster.mins,
+            maxs: cluster.maxs,
+            geometry: ClusterGeometryTableEntry {
+                byte_code_index_ranges: pack_cluster_display_lists(
+                    &cluster.lod_display_lists_by_pass_material_params,
+                    texture_table,
+                    &mut cluster_geometry_byte_code,
+                    &mut cluster_geometry_display_lists,
+                    &mut cluster_geometry_relocations,
+                ),
+            },
+        });
+    }
 
-                    // Bind the aux texture as TEXMAP2 reusing TEXCOORD1.
-                    if let Some(aux_id) = material.aux_id {
-                        if prev_aux_map_id != Some(aux_id) {
-                            prev_aux_map_id = Some(aux_id);
-                            display_list.append_bind_texture(2, aux_id, texture_table);
-                            // NOTE: Assume the aux texture has the same dimensions as the base
-                            // texture. They share texture coordinate 1 so there's no need to set
-                            // the scale again.
-                        }
-                    }
+    PackedClusterGeometry {
+        cluster_geometry_table,
+        cluster_lod_table,
+        cluster_geometry_byte_code,
+        cluster_geometry_display_lists,
+        cluster_geometry_relocations,
+    }
+}
 
-                    if prev_alpha != Some(params.alpha) {
-                        prev_alpha = Some(params.alpha);
+/// Appends byte code and display lists for one cluster's geometry, returning the byte code index
+/// range for each pass.
+fn pack_cluster_display_lists(
+    display_lists_by_pass_material_params: &DisplayListsByPassMaterialParams,
+    texture_table: &[TextureTableEntry],
+    cluster_geometry_byte_code: &mut Vec<u32>,
+    cluster_geometry_display_lists: &mut Vec<u8>,
+    cluster_geometry_relocations: &mut Vec<RelocationTableEntry>,
+) -> [[u32; 2]; 6] {
+    let mut byte_code_index_ranges = [[0, 0]; 6];
+    let mut display_list_offset = u32::try_from(cluster_geometry_display_lists.len()).unwrap();
+    for mode in 0..6 {
+        byte_code_index_ranges[mode as usize][0] =
+            u32::try_from(cluster_geometry_byte_code.len()).unwrap();
+
+        let mut prev_base_map_id = None;
+        let mut prev_aux_map_id = None;
+        let mut prev_alpha = None;
+        for ((pass, material, params), draw_display_list) in display_lists_by_pass_material_params {
+            let mut display_list = DisplayList::new();
+            if pass.as_mode() == mode {
+                // Bind the base texture as TEXMAP1 using TEXCOORD1.
+                if prev_base_map_id != Some(material.base_id) {
+                    prev_base_map_id = Some(material.base_id);
+                    display_list.append_bind_texture(1, material.base_id, texture_table);
+                    display_list.append_texcoord_scale(1, material.base_id, texture_table);
+                }
 
-                        let z_comp_before_tex = match params.alpha {
-                            ShaderParamsAlpha::AlphaTest { .. } => 0,
-                            _ => 1,
-                        };
-                        let compare_type = match params.alpha {
-                            ShaderParamsAlpha::AlphaTest { .. } => {
-                                BytecodeOp::ALPHA_COMPARE_TYPE_GEQUAL
-                            }
-                            _ => BytecodeOp::ALPHA_COMPARE_TYPE_ALWAYS,
-                        };
-                        let reference = match params.alpha {
-                            ShaderParamsAlpha::AlphaTest { threshold } => threshold,
-                            _ => 0,
-                        };
-                        BytecodeOp::SetAlphaCompare {
-                            z_comp_before_tex,
-                            compare_type,
-                            reference,
-                        }
-                        .append_to(&mut cluster_geometry_byte_code);
+                // Bind the aux texture as TEXMAP2 reusing TEXCOORD1.
+                if let Some(aux_id) = material.aux_id {
+                    if prev_aux_map_id != Some(aux_id) {
+                        prev_aux_map_id = Some(aux_id);
+                        display_list.append_bind_texture(2, aux_id, texture_table);
+                        // NOTE: Assume the aux texture has the same dimensions as the base
+                        // texture. They share texture coordinate 1 so there's no need to set
+                        // the scale again.
                     }
+                }
 
-                    display_list
-                        .commands
-                        .extend_from_slice(&draw_display_list.commands);
-                    display_list.pad_to_alignment();
-                    display_list
-                        .write_to(
-                            &mut cluster_geometry_display_lists,
-                            |cluster_geometry_display_lists, reference| {
-                                cluster_geometry_references.push(ClusterGeometryReferencesEntry {
-                                    display_list_offset: cluster_geometry_display_lists
-                                        .len()
-                                        .try_into()
-                                        .unwrap(),
-                                    texture_id: match reference {
-                                        gx::display_list::Reference::Texture(x) => x,
-                                    },
-                                    _padding: 0,
-                                });
-                            },
-                        )
-                        .unwrap();
-                    let next_display_list_offset =
-                        u32::try_from(cluster_geometry_display_lists.len()).unwrap();
-                    assert_eq!(next_display_list_offset & 31, 0);
-                    let display_list_size = next_display_list_offset - display_list_offset;
-                    assert_eq!(display_list_size & 31, 0);
-
-                    BytecodeOp::Draw {
-                        display_list_offset,
-                        display_list_size,
+                if prev_alpha != Some(params.alpha) {
+                    prev_alpha = Some(params.alpha);
+
+                    let z_comp_before_tex = match params.alpha {
+                        ShaderParamsAlpha::AlphaTest { .. } => 0,
+                        _ => 1,
+                    };
+                    let compare_type = match params.alpha {
+                        ShaderParamsAlpha::AlphaTest { .. } => {
+                            BytecodeOp::ALPHA_COMPARE_TYPE_GEQUAL
+                        }
+                        _ => BytecodeOp::ALPHA_COMPARE_TYPE_ALWAYS,
+                    };
+                    let reference = match params.alpha {
+                        ShaderParamsAlpha::AlphaTest { threshold } => threshold,
+                        _ => 0,
+                    };
+                    BytecodeOp::SetAlphaCompare {
+                        z_comp_before_tex,
+                        compare_type,
+                        reference,
                     }
-                    .append_to(&mut cluster_geometry_byte_code);
+                    .append_to(cluster_geometry_byte_code);
+                }
 
-                    display_list_offset = next_display_list_offset;
+                display_list
+                    .commands
+                    .extend_from_slice(&draw_display_list.commands);
+                display_list.pad_to_alignment();
+                display_list
+                    .write_to(
+                        cluster_geometry_display_lists,
+                        |cluster_geometry_display_lists, reference| {
+                            cluster_geometry_relocations.push(RelocationTableEntry {
+                                display_list_offset: cluster_geometry_display_lists
+                                    .len()
+                                    .try_into()
+                                    .unwrap(),
+                                texture_id: match reference {
+                                    gx::display_list::Reference::Texture(x) => x,
+                                },
+                                display_lists: RelocationTableEntry::CLUSTER_GEOMETRY,
+                                _padding: 0,
+                            });
+                        },
+                    )
+                    .unwrap();
+                let next_display_list_offset =
+                    u32::try_from(cluster_geometry_display_lists.len()).unwrap();
+                assert_eq!(next_display_list_offset & 31, 0);
+                let display_list_size = next_display_list_offset - display_list_offset;
+                assert_eq!(display_list_size & 31, 0);
+
+                BytecodeOp::Draw {
+                    display_list_offset,
+                    display_list_size,
                 }
-            }
+                .append_to(cluster_geometry_byte_code);
 
-            cluster_geometry_table_entry.byte_code_index_ranges[mode as usize][1] =
-                u32::try_from(cluster_geometry_byte_code.len()).unwrap();
+                display_list_offset = next_display_list_offset;
+            }
         }
-        cluster_geometry_table.push(cluster_geometry_table_entry);
-    }
 
-    (
-        cluster_geometry_table,
-        cluster_geometry_byte_code,
-        cluster_geometry_display_lists,
-        cluster_geometry_references,
-    )
+        byte_code_index_ranges[mode as usize][1] =
+            u32::try_from(cluster_geometry_byte_code.len()).unwrap();
+    }
+    byte_code_index_ranges
 }
 
 fn pack_displacement_geometry(
@@ -1277,12 +1929,12 @@ fn pack_displacement_geometry(
     Vec<DisplacementTableEntry>,
     Vec<u32>,
     Vec<u8>,
-    Vec<DisplacementReferencesEntry>,
+    Vec<RelocationTableEntry>,
 ) {
     let mut displacement_table = Vec::new();
     let mut displacement_byte_code = Vec::new();
     let mut displacement_display_lists = Vec::new();
-    let mut displacement_references = Vec::new();
+    let mut displacement_relocations = Vec::new();
 
     for mode in 0..2 {
         let byte_code_start_index = u32::try_from(displacement_byte_code.len()).unwrap();
@@ -1321,7 +1973,7 @@ fn pack_displacement_geometry(
                 .write_to(
                     &mut displacement_display_lists,
                     |displacement_display_lists, reference| {
-                        displacement_references.push(DisplacementReferencesEntry {
+                        displacement_relocations.push(RelocationTableEntry {
                             display_list_offset: displacement_display_lists
                                 .len()
                                 .try_into()
@@ -1329,6 +1981,7 @@ fn pack_displacement_geometry(
                             texture_id: match reference {
                                 gx::display_list::Reference::Texture(x) => x,
                             },
+                            display_lists: RelocationTableEntry::DISPLACEMENT,
                             _padding: 0,
                         });
                     },
@@ -1357,7 +2010,7 @@ fn pack_displacement_geometry(
         displacement_table,
         displacement_byte_code,
         displacement_display_lists,
-        displacement_references,
+        displacement_relocations,
     )
 }
 
@@ -1390,34 +2043,19 @@ fn pack_bsp_leaves(bsp: Bsp) -> Vec<BspLeaf> {
 
 fn pack_visibility(bsp: Bsp) -> Vec<u8> {
     // Scan each vis chunk to determine its length.
-    let mut sized_vis_chunks = Vec::new();
-    for cluster in bsp.visibility().iter_clusters() {
-        sized_vis_chunks.push(cluster.find_data());
-    }
-
-    // Build the index.
-    let mut offset = 4 * sized_vis_chunks.len() as u32 + 4;
-    let mut visibility = Vec::new();
-    visibility
-        .write_u32::<BigEndian>(sized_vis_chunks.len() as u32)
-        .unwrap();
-    for &chunk in &sized_vis_chunks {
-        visibility.write_u32::<BigEndian>(offset).unwrap();
-        offset += chunk.len() as u32;
-    }
-
-    // Append all chunks.
-    for chunk in sized_vis_chunks {
-        visibility.extend_from_slice(chunk);
-    }
-
-    visibility
+    let sized_vis_chunks: Vec<_> = bsp
+        .visibility()
+        .iter_clusters()
+        .map(|cluster| cluster.find_data())
+        .collect();
+    visibility::pack(sized_vis_chunks.into_iter())
 }
 
 fn pack_lightmaps(
     bsp: Bsp,
-    cluster_lightmaps: &HashMap<i16, Lightmap>,
-    displacement_lightmaps: &HashMap<u16, Lightmap>,
+    cluster_groups: &ClusterGroups,
+    cluster_lightmaps: &BTreeMap<i16, Lightmap>,
+    displacement_lightmaps: &BTreeMap<u16, Lightmap>,
 ) -> (
     Vec<ClusterLightmapTableEntry>,
     Vec<DisplacementLightmapTableEntry>,
@@ -1434,6 +2072,7 @@ fn pack_lightmaps(
             let patch_table_start_index = u32::try_from(lightmap_patch_table.len()).unwrap();
             pack_cluster_lightmap_patches(
                 bsp,
+                cluster_groups,
                 cluster_index,
                 lightmap,
                 &mut lightmap_patch_table,
@@ -1476,20 +2115,23 @@ fn pack_lightmaps(
     )
 }
 
+/// Packs the patches of every cluster in the group named by `cluster_index`.
 fn pack_cluster_lightmap_patches(
     bsp: Bsp,
+    cluster_groups: &ClusterGroups,
     cluster_index: i16,
     lightmap: &Lightmap,
     lightmap_patch_table: &mut Vec<LightmapPatchTableEntry>,
     lightmap_data: &mut Vec<u8>,
 ) {
     for leaf in bsp.iter_worldspawn_leaves() {
-        if leaf.cluster() != cluster_index {
+        if cluster_groups.group(bsp.leaf(leaf).cluster()) != cluster_index {
             continue;
         }
 
-        let mut lightmap_patches_by_data_offset = HashMap::new();
+        let mut lightmap_patches_by_data_offset = BTreeMap::new();
         for face in bsp.iter_faces_from_leaf(leaf) {
+            let face = bsp.face(face);
             if face.light_ofs == -1 || face.tex_info == -1 {
                 continue;
             }
@@ -1513,7 +2155,7 @@ fn pack_cluster_lightmap_patches(
 
 fn pack_displacement_lightmap_patches(
     bsp: Bsp,
-    displacement_lightmaps: &HashMap<u16, Lightmap>,
+    displacement_lightmaps: &BTreeMap<u16, Lightmap>,
     lightmap_patch_table: &mut Vec<LightmapPatchTableEntry>,
     lightmap_data: &mut Vec<u8>,
 ) -> Vec<DisplacementLightmapTableEntry> {
@@ -1526,7 +2168,7 @@ fn pack_displacement_lightmap_patches(
         let face = &bsp.faces()[disp_info.map_face as usize];
 
         let patch_table_start_index = u32::try_from(lightmap_patch_table.len()).unwrap();
-        let mut lightmap_patches_by_data_offset = HashMap::new();
+        let mut lightmap_patches_by_data_offset = BTreeMap::new();
         let metadata = lightmap.metadata_by_data_offset[&face.light_ofs];
         lightmap_patches_by_data_offset
             .entry(face.light_ofs)
@@ -1577,16 +2219,13 @@ fn lightmap_patch_from_face(bsp: Bsp, face: &Face, metadata: LightmapMetadata) -
 
 fn pack_lightmap_patches(
     bsp: Bsp,
-    lightmap_patches_by_data_offset: HashMap<i32, LightmapPatch>,
+    lightmap_patches_by_data_offset: BTreeMap<i32, LightmapPatch>,
     lightmap_patch_table: &mut Vec<LightmapPatchTableEntry>,
     lightmap_data: &mut Vec<u8>,
 ) {
-    let mut data_offsets: Vec<_> = lightmap_patches_by_data_offset.keys().copied().collect();
-    data_offsets.sort_unstable();
-    for data_offset in data_offsets {
+    for (&data_offset, patch) in &lightmap_patches_by_data_offset {
         let data_start_offset = u32::try_from(lightmap_data.len()).unwrap();
 
-        let patch = &lightmap_patches_by_data_offset[&data_offset];
         assert_eq!(patch.luxel_offset[0] % 4, 0);
         assert_eq!(patch.luxel_offset[1] % 4, 0);
         let patch_size = 4 * patch.width as usize * patch.height as usize;
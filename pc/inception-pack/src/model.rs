@@ -5,6 +5,7 @@ use std::rc::Rc;
 
 use anyhow::{anyhow, bail, Result};
 use gx::display_list::{DisplayList, GxPrimitive};
+use inception_render_common::vertex_format::VertexFormat;
 use source_reader::asset::AssetLoader;
 use source_reader::file::FileLoader;
 use source_reader::model::mdl::Mdl;
@@ -160,7 +161,7 @@ fn pack_model_geometry(
                             } else {
                                 unreachable!();
                             },
-                            0,
+                            VertexFormat::Model,
                         );
 
                         for i in 0..strip.num_indices() as usize {
@@ -0,0 +1,478 @@
+//! Overlays (`info_overlay`) and decals (`infodecal`), projected onto world faces ahead of time.
+//!
+//! Each overlay or decal is a textured quad. It's clipped against every face it was placed on and
+//! the pieces are flattened onto those faces, so the runtime only has to draw triangles slightly
+//! in front of the world. Lighting is sampled from each face's lightmap into vertex colors.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Result;
+use byteorder::{BigEndian, WriteBytesExt};
+use gx::display_list::{DisplayList, GxPrimitive};
+use inception_render_common::bytecode::BytecodeOp;
+use inception_render_common::map_data::{
+    OverlayTableEntry, RelocationTableEntry, TextureTableEntry,
+};
+use inception_render_common::vertex_format::VertexFormat;
+use nalgebra_glm::{vec2, vec3, Vec2, Vec3};
+use source_reader::asset::vmt::{LightmappedGeneric, Shader, UnlitGeneric};
+use source_reader::asset::AssetLoader;
+use source_reader::bsp::{Bsp, Face};
+use source_reader::lightmap::LuxelSpace;
+use source_reader::vpk::path::VpkPath;
+
+use crate::draw_builder::DrawBuilder;
+use crate::gx_helpers::DisplayListExt;
+use crate::map::parse_vec3;
+use crate::packed_material::{PackedMaterial, PackedMaterialBaseAlpha};
+use crate::texture_key::TextureIdAllocator;
+
+/// A decal is placed on any face whose plane passes within this distance of its origin.
+const DECAL_PLANE_EPSILON: f32 = 1.0;
+
+/// Faces this close to edge-on to the projection direction are skipped.
+const MIN_PROJECTION_COSINE: f32 = 0.1;
+
+/// Sorted by cluster, then mode, then render order, so packing can walk each cluster's draws in
+/// order.
+type OverlayKey = (u16, u8, u16, PackedMaterial);
+
+pub struct OverlayGeometry {
+    display_lists: BTreeMap<OverlayKey, DisplayList>,
+}
+
+pub struct PackedOverlays {
+    pub overlay_table: Vec<OverlayTableEntry>,
+    pub overlay_byte_code: Vec<u32>,
+    pub overlay_display_lists: Vec<u8>,
+    pub overlay_relocations: Vec<RelocationTableEntry>,
+}
+
+/// A textured quad to project along `normal` onto a set of faces.
+struct Projection {
+    material_path: VpkPath,
+    render_order: u16,
+    /// World space positions and texture coordinates, in winding order.
+    corners: [(Vec3, [f32; 2]); 4],
+    normal: Vec3,
+    faces: Vec<usize>,
+}
+
+pub fn process_overlays(
+    bsp: Bsp,
+    asset_loader: &AssetLoader,
+    ids: &mut TextureIdAllocator,
+) -> Result<OverlayGeometry> {
+    let clusters_by_face = clusters_by_face(bsp);
+
+    let mut projections = Vec::new();
+    for overlay in bsp.overlays() {
+        let tex_info = &bsp.tex_infos()[overlay.tex_info as usize];
+        let tex_data = &bsp.tex_datas()[tex_info.tex_data as usize];
+        projections.push(Projection {
+            material_path: VpkPath::new_with_prefix_and_extension(
+                bsp.tex_data_strings()
+                    .get(tex_data.name_string_table_id as usize),
+                "materials",
+                "vmt",
+            ),
+            render_order: overlay.render_order(),
+            corners: overlay.corners(),
+            normal: overlay.basis()[2],
+            faces: overlay.faces().iter().map(|&face| face as usize).collect(),
+        });
+    }
+    for entity in bsp.entities() {
+        if entity.get("classname").map(String::as_str) != Some("infodecal") {
+            continue;
+        }
+        if let Some(projection) = place_decal(bsp, asset_loader, &entity)? {
+            projections.push(projection);
+        }
+    }
+
+    let mut draw_builders = BTreeMap::new();
+    for projection in &projections {
+        let material = asset_loader.get_material(&projection.material_path)?;
+        let packed_material =
+            match PackedMaterial::from_material(asset_loader, ids, &material, false)? {
+                Some(packed_material) => packed_material,
+                None => continue,
+            };
+        let mode = match packed_material.base_alpha {
+            PackedMaterialBaseAlpha::BaseTextureAlpha => 0,
+            PackedMaterialBaseAlpha::AuxTextureAlpha => 1,
+        };
+
+        for &face_index in &projection.faces {
+            let cluster = match clusters_by_face.get(&face_index) {
+                Some(&cluster) => cluster,
+                None => continue,
+            };
+            let draw_builder = draw_builders
+                .entry((
+                    cluster,
+                    mode,
+                    projection.render_order,
+                    packed_material.clone(),
+                ))
+                .or_insert_with(|| DrawBuilder::new(GxPrimitive::Triangles, VertexFormat::Overlay));
+            project_onto_face(bsp, projection, face_index, draw_builder);
+        }
+    }
+
+    Ok(OverlayGeometry {
+        display_lists: draw_builders
+            .into_iter()
+            .map(|(key, builder)| (key, builder.build()))
+            .collect(),
+    })
+}
+
+/// Maps each world face to the first cluster that contains it.
+fn clusters_by_face(bsp: Bsp) -> HashMap<usize, u16> {
+    let mut clusters_by_face = HashMap::new();
+    for leaf in bsp.iter_worldspawn_leaves() {
+        let leaf = bsp.leaf(leaf);
+        let cluster = match u16::try_from(leaf.cluster()) {
+            Ok(cluster) => cluster,
+            Err(_) => continue,
+        };
+        let start = leaf.first_leaf_face() as usize;
+        let end = start + leaf.num_leaf_faces() as usize;
+        for &face_index in &bsp.leaf_faces()[start..end] {
+            clusters_by_face
+                .entry(face_index as usize)
+                .or_insert(cluster);
+        }
+    }
+    clusters_by_face
+}
+
+/// Finds the world face an `infodecal` sits on and builds a quad the size of its base texture,
+/// aligned to that face's texture axes.
+fn place_decal(
+    bsp: Bsp,
+    asset_loader: &AssetLoader,
+    entity: &HashMap<String, String>,
+) -> Result<Option<Projection>> {
+    let (texture, origin) = match (entity.get("texture"), entity.get("origin")) {
+        (Some(texture), Some(origin)) => (texture, Vec3::from(parse_vec3(origin)?)),
+        _ => return Ok(None),
+    };
+    let material_path = VpkPath::new_with_prefix_and_extension(texture, "materials", "vmt");
+    let material = asset_loader.get_material(&material_path)?;
+    let base_texture = match material.shader() {
+        Shader::LightmappedGeneric(LightmappedGeneric {
+            base_texture_path, ..
+        })
+        | Shader::UnlitGeneric(UnlitGeneric {
+            base_texture_path, ..
+        }) => asset_loader.get_texture(base_texture_path)?,
+        _ => return Ok(None),
+    };
+
+    let world = &bsp.models()[0];
+    let face_index = match (world.first_face as usize
+        ..(world.first_face + world.num_faces) as usize)
+        .find(|&face_index| {
+            let face = &bsp.faces()[face_index];
+            if face.tex_info == -1 || face.disp_info != -1 {
+                return false;
+            }
+            let (normal, dist) = face_plane(bsp, face);
+            (normal.dot(&origin) - dist).abs() <= DECAL_PLANE_EPSILON
+                && polygon_contains(&face_polygon_2d(bsp, face, normal), to_2d(origin, normal))
+        }) {
+        Some(face_index) => face_index,
+        None => {
+            eprintln!(
+                "WARNING: Skipping infodecal {} at {:?} with no face to sit on",
+                texture, origin,
+            );
+            return Ok(None);
+        }
+    };
+
+    let face = &bsp.faces()[face_index];
+    let (normal, _) = face_plane(bsp, face);
+    let tex_info = &bsp.tex_infos()[face.tex_info as usize];
+    let axis = |i: usize| {
+        let v = vec3(
+            tex_info.texture_vecs[i][0],
+            tex_info.texture_vecs[i][1],
+            tex_info.texture_vecs[i][2],
+        );
+        (v - normal * v.dot(&normal)).normalize()
+    };
+    let u = axis(0) * (base_texture.width() as f32 / 2.0);
+    let v = axis(1) * (base_texture.height() as f32 / 2.0);
+
+    Ok(Some(Projection {
+        material_path,
+        render_order: 0,
+        corners: [
+            (origin - u - v, [0.0, 0.0]),
+            (origin - u + v, [0.0, 1.0]),
+            (origin + u + v, [1.0, 1.0]),
+            (origin + u - v, [1.0, 0.0]),
+        ],
+        normal,
+        faces: vec![face_index],
+    }))
+}
+
+/// Clips a projection's quad to one face and emits the pieces, flattened onto the face's plane.
+fn project_onto_face(
+    bsp: Bsp,
+    projection: &Projection,
+    face_index: usize,
+    draw_builder: &mut DrawBuilder,
+) {
+    let face = &bsp.faces()[face_index];
+    if face.tex_info == -1 || face.disp_info != -1 {
+        return;
+    }
+    let (face_normal, face_dist) = face_plane(bsp, face);
+    let cosine = face_normal.dot(&projection.normal);
+    if cosine.abs() < MIN_PROJECTION_COSINE {
+        return;
+    }
+
+    let face_polygon = face_polygon_2d(bsp, face, projection.normal);
+    let corners_2d = projection
+        .corners
+        .map(|(position, _)| to_2d(position, projection.normal));
+    let [frame_u, frame_v] = frame(projection.normal);
+
+    for [a, b, c] in [[0, 1, 2], [0, 2, 3]] {
+        let triangle = [corners_2d[a], corners_2d[b], corners_2d[c]];
+        let clipped = clip_convex(&triangle, &face_polygon);
+        if clipped.len() < 3 {
+            continue;
+        }
+
+        let vertices: Vec<_> = clipped
+            .iter()
+            .map(|&point| {
+                let [wa, wb, wc] = barycentric(&triangle, point);
+                let texture_coord = [0, 1].map(|i| {
+                    wa * projection.corners[a].1[i]
+                        + wb * projection.corners[b].1[i]
+                        + wc * projection.corners[c].1[i]
+                });
+
+                // Slide the point along the projection direction until it lands on the face.
+                let in_plane = frame_u * point.x + frame_v * point.y;
+                let t = (face_dist - face_normal.dot(&in_plane)) / cosine;
+                let position = in_plane + projection.normal * t;
+
+                (
+                    position,
+                    sample_lighting(bsp, face, position),
+                    texture_coord,
+                )
+            })
+            .collect();
+
+        // Fan out from the first vertex.
+        for i in 1..vertices.len() - 1 {
+            for (position, color, texture_coord) in [&vertices[0], &vertices[i], &vertices[i + 1]] {
+                let mut data = Vec::with_capacity(23);
+                for &x in position.iter() {
+                    data.write_f32::<BigEndian>(x).unwrap();
+                }
+                data.extend_from_slice(color);
+                for &x in texture_coord {
+                    data.write_f32::<BigEndian>(x).unwrap();
+                }
+                draw_builder.emit_vertices(1, &data);
+            }
+        }
+    }
+}
+
+/// Returns the face's outward normal and distance from the origin.
+fn face_plane(bsp: Bsp, face: &Face) -> (Vec3, f32) {
+    let plane = &bsp.planes()[face.plane_num as usize];
+    let normal = Vec3::from(plane.normal);
+    if face.side == 0 {
+        (normal, plane.dist)
+    } else {
+        (-normal, -plane.dist)
+    }
+}
+
+/// Two unit vectors perpendicular to `normal` and to each other.
+fn frame(normal: Vec3) -> [Vec3; 2] {
+    let reference = if normal.z.abs() < 0.9 {
+        vec3(0.0, 0.0, 1.0)
+    } else {
+        vec3(1.0, 0.0, 0.0)
+    };
+    let u = reference.cross(&normal).normalize();
+    [u, normal.cross(&u)]
+}
+
+fn to_2d(position: Vec3, normal: Vec3) -> Vec2 {
+    let [u, v] = frame(normal);
+    vec2(position.dot(&u), position.dot(&v))
+}
+
+/// Projects a face's polygon along `normal`, wound counterclockwise.
+fn face_polygon_2d(bsp: Bsp, face: &Face, normal: Vec3) -> Vec<Vec2> {
+    let mut polygon: Vec<Vec2> = bsp
+        .iter_vertex_indices_from_face(face)
+        .map(|index| to_2d(bsp.vertices()[index], normal))
+        .collect();
+    if signed_area(&polygon) < 0.0 {
+        polygon.reverse();
+    }
+    polygon
+}
+
+fn signed_area(polygon: &[Vec2]) -> f32 {
+    let mut sum = 0.0;
+    for (i, a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        sum += a.x * b.y - b.x * a.y;
+    }
+    sum / 2.0
+}
+
+fn polygon_contains(polygon: &[Vec2], point: Vec2) -> bool {
+    (0..polygon.len())
+        .all(|i| edge_side(polygon[i], polygon[(i + 1) % polygon.len()], point) >= 0.0)
+}
+
+/// Positive when `point` is left of the edge from `a` to `b`.
+fn edge_side(a: Vec2, b: Vec2, point: Vec2) -> f32 {
+    (b.x - a.x) * (point.y - a.y) - (b.y - a.y) * (point.x - a.x)
+}
+
+/// Clips a convex polygon against each edge of a counterclockwise convex polygon.
+fn clip_convex(subject: &[Vec2], clip: &[Vec2]) -> Vec<Vec2> {
+    let mut output = subject.to_vec();
+    for i in 0..clip.len() {
+        let (a, b) = (clip[i], clip[(i + 1) % clip.len()]);
+        let input = std::mem::take(&mut output);
+        for (j, &current) in input.iter().enumerate() {
+            let next = input[(j + 1) % input.len()];
+            let current_side = edge_side(a, b, current);
+            let next_side = edge_side(a, b, next);
+            if current_side >= 0.0 {
+                output.push(current);
+            }
+            if (current_side >= 0.0) != (next_side >= 0.0) {
+                let t = current_side / (current_side - next_side);
+                output.push(current + (next - current) * t);
+            }
+        }
+        if output.is_empty() {
+            break;
+        }
+    }
+    output
+}
+
+fn barycentric(triangle: &[Vec2; 3], point: Vec2) -> [f32; 3] {
+    let [a, b, c] = *triangle;
+    let area = edge_side(a, b, c);
+    let wa = edge_side(b, c, point) / area;
+    let wb = edge_side(c, a, point) / area;
+    [wa, wb, 1.0 - wa - wb]
+}
+
+/// Reads the face's first lightmap style at `position`, or full brightness for unlit faces.
+fn sample_lighting(bsp: Bsp, face: &Face, position: Vec3) -> [u8; 3] {
+    if face.light_ofs == -1 {
+        return [255; 3];
+    }
+    let coords = LuxelSpace::for_face(bsp, face).world_to_luxel(position);
+    let luxel = |i: usize| {
+        (coords[i].round().max(0.0) as usize).min(face.lightmap_texture_size_in_luxels[i] as usize)
+    };
+    let width = face.lightmap_texture_size_in_luxels[0] as usize + 1;
+    let offset = face.light_ofs as usize + 4 * (width * luxel(1) + luxel(0));
+    bsp.lighting().at_offset(offset, 1)[0].to_srgb8()
+}
+
+/// Packs each cluster's overlay display lists, following the same layout as displacements.
+pub fn pack_overlays(
+    cluster_count: usize,
+    geometry: &OverlayGeometry,
+    texture_table: &[TextureTableEntry],
+) -> PackedOverlays {
+    let mut overlay_table = Vec::new();
+    let mut overlay_byte_code = Vec::new();
+    let mut overlay_display_lists = Vec::new();
+    let mut overlay_relocations = Vec::new();
+
+    for cluster in 0..cluster_count {
+        let mut byte_code_index_ranges = [[0; 2]; 2];
+        for (mode, range) in byte_code_index_ranges.iter_mut().enumerate() {
+            range[0] = u32::try_from(overlay_byte_code.len()).unwrap();
+            let cluster = cluster as u16;
+            let mode = mode as u8;
+            for ((_, _, _, packed_material), draw_display_list) in geometry
+                .display_lists
+                .iter()
+                .filter(|((c, m, _, _), _)| (*c, *m) == (cluster, mode))
+            {
+                let display_list_offset = u32::try_from(overlay_display_lists.len()).unwrap();
+                let mut display_list = DisplayList::new();
+
+                // Bind the base texture to TEXMAP0 and the aux texture, if any, to TEXMAP1. Both
+                // are sampled with TEXCOORD0.
+                display_list.append_bind_texture(0, packed_material.base_id, texture_table);
+                display_list.append_texcoord_scale(0, packed_material.base_id, texture_table);
+                if let Some(aux_id) = packed_material.aux_id {
+                    display_list.append_bind_texture(1, aux_id, texture_table);
+                }
+
+                display_list
+                    .commands
+                    .extend_from_slice(&draw_display_list.commands);
+                display_list.pad_to_alignment();
+                display_list
+                    .write_to(
+                        &mut overlay_display_lists,
+                        |overlay_display_lists, reference| {
+                            overlay_relocations.push(RelocationTableEntry {
+                                display_list_offset: overlay_display_lists
+                                    .len()
+                                    .try_into()
+                                    .unwrap(),
+                                texture_id: match reference {
+                                    gx::display_list::Reference::Texture(x) => x,
+                                },
+                                display_lists: RelocationTableEntry::OVERLAY,
+                                _padding: 0,
+                            });
+                        },
+                    )
+                    .unwrap();
+                let next_display_list_offset = u32::try_from(overlay_display_lists.len()).unwrap();
+                let display_list_size = next_display_list_offset - display_list_offset;
+                assert_eq!(display_list_size & 31, 0);
+
+                BytecodeOp::Draw {
+                    display_list_offset,
+                    display_list_size,
+                }
+                .append_to(&mut overlay_byte_code);
+            }
+            range[1] = u32::try_from(overlay_byte_code.len()).unwrap();
+        }
+        overlay_table.push(OverlayTableEntry {
+            byte_code_index_ranges,
+        });
+    }
+
+    PackedOverlays {
+        overlay_table,
+        overlay_byte_code,
+        overlay_display_lists,
+        overlay_relocations,
+    }
+}
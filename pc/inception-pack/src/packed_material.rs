@@ -1,6 +1,6 @@
 use anyhow::Result;
 use source_reader::asset::vmt::{
-    LightmappedGeneric, Shader, UnlitGeneric, Vmt, WorldVertexTransition,
+    LightmappedGeneric, Shader, UnlitGeneric, VertexLitGeneric, Vmt, WorldVertexTransition,
 };
 use source_reader::asset::AssetLoader;
 use texture_format::TextureFormat;
@@ -27,12 +27,13 @@ impl PackedMaterial {
         material: &Vmt,
         for_displacement: bool,
     ) -> Result<Option<Self>> {
-        Ok(match material.shader() {
+        let packed_material = match material.shader() {
             Shader::LightmappedGeneric(LightmappedGeneric {
                 base_texture_path,
                 self_illum: false,
                 ..
-            }) => {
+            })
+            | Shader::VertexLitGeneric(VertexLitGeneric { base_texture_path }) => {
                 let base_texture = asset_loader.get_texture(base_texture_path)?;
                 let base_id = ids.get(&BorrowedTextureKey::EncodeAsIs {
                     texture_path: base_texture_path,
@@ -86,6 +87,7 @@ impl PackedMaterial {
             Shader::UnlitGeneric(UnlitGeneric {
                 base_texture_path,
                 self_illum: false,
+                ..
             }) => {
                 let base_id = ids.get(&BorrowedTextureKey::EncodeAsIs {
                     texture_path: base_texture_path,
@@ -101,6 +103,7 @@ impl PackedMaterial {
             Shader::UnlitGeneric(UnlitGeneric {
                 base_texture_path,
                 self_illum: true,
+                ..
             }) => {
                 let base_id = ids.get(&BorrowedTextureKey::EncodeAsIs {
                     texture_path: base_texture_path,
@@ -156,6 +159,19 @@ impl PackedMaterial {
                 );
                 None
             }
-        })
+        };
+
+        // Only shaders whose aux texture is derived from the base texture can be animated, so
+        // both animate together.
+        if let (Some(packed_material), Some(frame_rate)) =
+            (&packed_material, material.base_texture_frame_rate())
+        {
+            ids.set_frame_rate(packed_material.base_id, frame_rate);
+            if let Some(aux_id) = packed_material.aux_id {
+                ids.set_frame_rate(aux_id, frame_rate);
+            }
+        }
+
+        Ok(packed_material)
     }
 }
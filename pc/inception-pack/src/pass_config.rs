@@ -0,0 +1,355 @@
+//! Data-driven overrides for which pass a brush face is drawn in.
+//!
+//! Passes normally follow from each material's shader and flags (see `Pass::from_material`). A
+//! pass config overrides that per material, for every map or for a single map:
+//!
+//! ```text
+//! # Rules under [*] apply to every map.
+//! [*]
+//! glass/* translucent
+//! tools/toolsblack unlit
+//!
+//! # Map sections are applied after [*]. The last matching rule wins.
+//! [d1_trainstation_01]
+//! metal/citadel_* self-illum brush-entity
+//! glass/glasswindow001a default
+//! ```
+//!
+//! A rule is a material name pattern (`*` matches any run of characters, ignoring case), a pass,
+//! and optionally `world` or `brush-entity` to only match faces from one source. The pass
+//! `default` restores the material's own pass.
+
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use source_reader::asset::vmt::Vmt;
+
+use crate::legacy_pass_params::{Pass, PassAlpha, ShaderParams, ShaderParamsAlpha};
+use crate::packed_material::{PackedMaterial, PackedMaterialBaseAlpha};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PassOverride {
+    Default,
+    /// Lightmapped, opaque or alpha-tested.
+    Lightmapped,
+    /// Lightmapped and alpha-blended.
+    Translucent,
+    SelfIllum,
+    Unlit,
+}
+
+impl PassOverride {
+    fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "default" => Self::Default,
+            "lightmapped" => Self::Lightmapped,
+            "translucent" => Self::Translucent,
+            "self-illum" => Self::SelfIllum,
+            "unlit" => Self::Unlit,
+            _ => return None,
+        })
+    }
+
+    /// Returns the overridden pass, adjusting `params` to match. Fails if the packed material
+    /// lacks a texture the pass reads.
+    fn apply(
+        self,
+        pass: Pass,
+        packed_material: &PackedMaterial,
+        params: &mut ShaderParams,
+    ) -> Result<Pass> {
+        Ok(match self {
+            Self::Default => pass,
+            Self::Lightmapped => {
+                if params.alpha == ShaderParamsAlpha::AlphaBlend {
+                    params.alpha = ShaderParamsAlpha::Opaque;
+                }
+                Pass::LightmappedGeneric {
+                    alpha: PassAlpha::OpaqueOrAlphaTest,
+                    base_alpha: packed_material.base_alpha,
+                }
+            }
+            Self::Translucent => {
+                if packed_material.base_alpha != PackedMaterialBaseAlpha::AuxTextureAlpha {
+                    bail!("the translucent pass needs a base texture with alpha");
+                }
+                params.alpha = ShaderParamsAlpha::AlphaBlend;
+                Pass::LightmappedGeneric {
+                    alpha: PassAlpha::AlphaBlend,
+                    base_alpha: PackedMaterialBaseAlpha::AuxTextureAlpha,
+                }
+            }
+            Self::SelfIllum => {
+                if packed_material.aux_id.is_none() {
+                    bail!("the self-illum pass needs a base texture with alpha");
+                }
+                Pass::SelfIllum
+            }
+            Self::Unlit => Pass::UnlitGeneric,
+        })
+    }
+}
+
+/// Where a brush face comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FaceSource {
+    World,
+    /// A static brush entity like `func_detail` or `func_brush`.
+    BrushEntity,
+}
+
+impl FaceSource {
+    fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "world" => Self::World,
+            "brush-entity" => Self::BrushEntity,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Rule {
+    /// Lowercase.
+    pattern: String,
+    pass: PassOverride,
+    source: Option<FaceSource>,
+}
+
+impl Rule {
+    fn matches(&self, material_name: &str, source: FaceSource) -> bool {
+        self.source.is_none_or(|s| s == source)
+            && glob_match(&self.pattern, &material_name.to_ascii_lowercase())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PassConfig {
+    /// Section names, lowercase, with the rules under each. `*` applies to every map.
+    sections: Vec<(String, Vec<Rule>)>,
+}
+
+impl PassConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text =
+            read_to_string(path).with_context(|| format!("Reading pass config {:?}", path))?;
+        Self::parse(&text).with_context(|| format!("Parsing pass config {:?}", path))
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut sections = Vec::new();
+        for (line_index, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[') {
+                let Some(name) = name.strip_suffix(']') else {
+                    bail!("line {}: unterminated section header", line_index + 1);
+                };
+                sections.push((name.trim().to_ascii_lowercase(), Vec::new()));
+                continue;
+            }
+
+            let Some((_, rules)) = sections.last_mut() else {
+                bail!("line {}: rule outside of any section", line_index + 1);
+            };
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (pattern, pass, source) = match fields[..] {
+                [pattern, pass] => (pattern, pass, None),
+                [pattern, pass, source] => (pattern, pass, Some(source)),
+                _ => bail!(
+                    "line {}: expected a material pattern, a pass, and an optional source",
+                    line_index + 1,
+                ),
+            };
+            let Some(pass) = PassOverride::parse(pass) else {
+                bail!("line {}: unknown pass {:?}", line_index + 1, pass);
+            };
+            let source = match source {
+                Some(source) => match FaceSource::parse(source) {
+                    Some(source) => Some(source),
+                    None => bail!("line {}: unknown face source {:?}", line_index + 1, source),
+                },
+                None => None,
+            };
+            rules.push(Rule {
+                pattern: pattern.to_ascii_lowercase(),
+                pass,
+                source,
+            });
+        }
+        Ok(Self { sections })
+    }
+
+    /// Collects the rules that apply to `map_name`, in the order they are applied.
+    pub fn for_map(&self, map_name: &str) -> MapPassRules<'_> {
+        let map_name = map_name.to_ascii_lowercase();
+        let global = self.sections.iter().filter(|(name, _)| name == "*");
+        let map = self.sections.iter().filter(|(name, _)| *name == map_name);
+        MapPassRules {
+            rules: global
+                .chain(map)
+                .flat_map(|(_, rules)| rules.iter())
+                .collect(),
+        }
+    }
+}
+
+pub struct MapPassRules<'a> {
+    rules: Vec<&'a Rule>,
+}
+
+impl<'a> MapPassRules<'a> {
+    pub fn lookup(&self, material_name: &str, source: FaceSource) -> PassOverride {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(material_name, source))
+            .map_or(PassOverride::Default, |rule| rule.pass)
+    }
+}
+
+/// Classifies brush faces into passes and tallies where they landed.
+pub struct PassClassifier<'a> {
+    rules: MapPassRules<'a>,
+    /// Face counts keyed by pass, material name, and whether a rule overrode the pass.
+    report: BTreeMap<(Pass, String, bool), usize>,
+}
+
+impl<'a> PassClassifier<'a> {
+    pub fn new(rules: MapPassRules<'a>) -> Self {
+        Self {
+            rules,
+            report: BTreeMap::new(),
+        }
+    }
+
+    pub fn classify(
+        &mut self,
+        material_name: &str,
+        source: FaceSource,
+        material: &Vmt,
+        packed_material: &PackedMaterial,
+    ) -> Result<(Pass, ShaderParams)> {
+        let pass = Pass::from_material(material, packed_material);
+        let mut params = ShaderParams::from_material(material);
+        let pass_override = self.rules.lookup(material_name, source);
+        let pass = pass_override
+            .apply(pass, packed_material, &mut params)
+            .with_context(|| {
+                format!(
+                    "Overriding material {} to pass {:?}",
+                    material_name, pass_override,
+                )
+            })?;
+
+        *self
+            .report
+            .entry((
+                pass,
+                material_name.to_ascii_lowercase(),
+                pass_override != PassOverride::Default,
+            ))
+            .or_default() += 1;
+        Ok((pass, params))
+    }
+
+    /// Prints face counts by pass and material. Faces are counted once per cluster that draws
+    /// them.
+    pub fn print_report(&self) {
+        let mut totals: BTreeMap<Pass, usize> = BTreeMap::new();
+        for (&(pass, _, _), &count) in &self.report {
+            *totals.entry(pass).or_default() += count;
+        }
+        for (pass, total) in totals {
+            println!("mode {} {:?}: {} faces", pass.as_mode(), pass, total);
+            for ((_, material_name, overridden), count) in
+                self.report.iter().filter(|((p, _, _), _)| *p == pass)
+            {
+                println!(
+                    "{:>10} {}{}",
+                    count,
+                    material_name,
+                    if *overridden { " (override)" } else { "" },
+                );
+            }
+        }
+    }
+}
+
+/// Matches `text` against `pattern`, where `*` matches any run of characters.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => {
+            let Some(text) = text.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=text.len())
+                .filter(|&i| text.is_char_boundary(i))
+                .any(|i| glob_match(rest, &text[i..]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{glob_match, FaceSource, PassConfig, PassOverride};
+
+    #[test]
+    fn glob_match_wildcards() {
+        assert!(glob_match("glass/*", "glass/glasswindow001a"));
+        assert!(glob_match("*/citadel_*a", "metal/citadel_wall01a"));
+        assert!(!glob_match("*/citadel_*a", "metal/citadel_wall01b"));
+        assert!(glob_match("tools/toolsblack", "tools/toolsblack"));
+        assert!(!glob_match("tools/toolsblack", "tools/toolsblack2"));
+    }
+
+    #[test]
+    fn map_sections_override_global_rules() {
+        let config = PassConfig::parse(
+            "[*]\n\
+             glass/* translucent # comment\n\
+             \n\
+             [d1_trainstation_01]\n\
+             Glass/GlassWindow001a default\n\
+             metal/* self-illum brush-entity\n",
+        )
+        .unwrap();
+
+        let other = config.for_map("d1_canals_01");
+        assert_eq!(
+            other.lookup("GLASS/GLASSWINDOW001A", FaceSource::World),
+            PassOverride::Translucent,
+        );
+
+        let rules = config.for_map("d1_trainstation_01");
+        assert_eq!(
+            rules.lookup("glass/glasswindow001a", FaceSource::World),
+            PassOverride::Default,
+        );
+        assert_eq!(
+            rules.lookup("glass/glasswindow002a", FaceSource::World),
+            PassOverride::Translucent,
+        );
+        assert_eq!(
+            rules.lookup("metal/metalwall001a", FaceSource::World),
+            PassOverride::Default,
+        );
+        assert_eq!(
+            rules.lookup("metal/metalwall001a", FaceSource::BrushEntity),
+            PassOverride::SelfIllum,
+        );
+    }
+
+    #[test]
+    fn parse_rejects_bad_lines() {
+        assert!(PassConfig::parse("glass/* translucent\n").is_err());
+        assert!(PassConfig::parse("[*]\nglass/* blended\n").is_err());
+        assert!(PassConfig::parse("[*]\nglass/* unlit everywhere\n").is_err());
+        assert!(PassConfig::parse("[*\n").is_err());
+    }
+}
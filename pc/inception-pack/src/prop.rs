@@ -0,0 +1,388 @@
+//! Static props (`prop_static`), baked into world space ahead of time.
+//!
+//! Each prop's model is posed once with its placement transform and the transformed triangles are
+//! merged into per-cluster display lists, so the runtime draws props like any other static
+//! geometry. Lighting is a single ambient cube sampled at the prop's lighting origin and evaluated
+//! per vertex into vertex colors.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Result;
+use byteorder::{BigEndian, WriteBytesExt};
+use gx::display_list::{DisplayList, GxPrimitive};
+use inception_render_common::bytecode::BytecodeOp;
+use inception_render_common::map_data::{PropTableEntry, RelocationTableEntry, TextureTableEntry};
+use inception_render_common::vertex_format::VertexFormat;
+use nalgebra_glm::Vec3;
+use source_reader::asset::AssetLoader;
+use source_reader::bsp::{Bsp, CompressedLightCube, LeafAmbientLighting, LeafIndex, StaticProp};
+use source_reader::model::find_material;
+use source_reader::model::mdl::Mdl;
+use source_reader::model::vtx::{LodSelection, Vtx};
+use source_reader::model::vvd::{Vertex, Vvd};
+use source_reader::vpk::path::VpkPath;
+
+use crate::draw_builder::DrawBuilder;
+use crate::gx_helpers::DisplayListExt;
+use crate::packed_material::{PackedMaterial, PackedMaterialBaseAlpha};
+use crate::texture_key::TextureIdAllocator;
+
+/// Sorted by cluster, then mode, so packing can walk each cluster's draws in order.
+type PropKey = (u16, u8, PackedMaterial);
+
+pub struct PropGeometry {
+    display_lists: BTreeMap<PropKey, DisplayList>,
+}
+
+pub struct PackedProps {
+    pub prop_table: Vec<PropTableEntry>,
+    pub prop_byte_code: Vec<u32>,
+    pub prop_display_lists: Vec<u8>,
+    pub prop_relocations: Vec<RelocationTableEntry>,
+}
+
+/// The three files that make up a model.
+struct ModelFiles {
+    mdl: Vec<u8>,
+    vtx: Vec<u8>,
+    vvd: Vec<u8>,
+}
+
+pub fn process_static_props(
+    bsp: Bsp,
+    asset_loader: &AssetLoader,
+    ids: &mut TextureIdAllocator,
+) -> Result<PropGeometry> {
+    let static_props = bsp.static_props()?;
+
+    let mut props_by_type: BTreeMap<u16, Vec<&StaticProp>> = BTreeMap::new();
+    for prop in &static_props.props {
+        props_by_type.entry(prop.prop_type).or_default().push(prop);
+    }
+
+    let mut draw_builders = BTreeMap::new();
+    for (prop_type, props) in props_by_type {
+        let model_name = &static_props.model_names[prop_type as usize];
+        let files = match load_model_files(asset_loader, model_name)? {
+            Some(files) => files,
+            None => {
+                eprintln!(
+                    "WARNING: Skipping static prop with missing model {}",
+                    model_name
+                );
+                continue;
+            }
+        };
+        let mdl = Mdl::new(&files.mdl);
+        let vtx = Vtx::new(&files.vtx);
+        let vvd = Vvd::new(&files.vvd);
+        let vertices = lod0_vertices(vvd);
+
+        // Keyed by texture index, which already accounts for each prop's skin.
+        let mut materials = HashMap::new();
+
+        for prop in props {
+            let cluster = match prop_cluster(bsp, prop, static_props.leaves(prop)) {
+                Some(cluster) => cluster,
+                None => continue,
+            };
+            let light_cube = sample_light_cube(bsp, prop.lighting_origin);
+            let rotation = prop.rotation();
+
+            for (mdl_body_part, vtx_body_part) in mdl.body_parts().iter().zip(vtx.body_parts()) {
+                // Only the default submodel of each body part is drawn.
+                let (mdl_model, vtx_model) = match (
+                    mdl_body_part.models(mdl).first(),
+                    vtx_body_part.models(vtx).first(),
+                ) {
+                    (Some(mdl_model), Some(vtx_model)) => (mdl_model, vtx_model),
+                    _ => continue,
+                };
+                let lod = match vtx_model.select_lod(vtx, LodSelection::Index(0)) {
+                    Some((_, lod)) => lod,
+                    None => continue,
+                };
+
+                for (mdl_mesh, vtx_mesh) in mdl_model.meshes(mdl).iter().zip(lod.iter_meshes(vtx)) {
+                    let texture_index = mdl
+                        .skin_texture_index(prop.skin.max(0) as usize, mdl_mesh.material as usize);
+                    let packed_material = match materials.get(&texture_index) {
+                        Some(packed_material) => Option::clone(packed_material),
+                        None => {
+                            let packed_material =
+                                resolve_material(asset_loader, ids, mdl, texture_index)?;
+                            materials.insert(texture_index, packed_material.clone());
+                            packed_material
+                        }
+                    };
+                    let packed_material = match packed_material {
+                        Some(packed_material) => packed_material,
+                        None => continue,
+                    };
+                    let mode = match packed_material.base_alpha {
+                        PackedMaterialBaseAlpha::BaseTextureAlpha => 0,
+                        PackedMaterialBaseAlpha::AuxTextureAlpha => 1,
+                    };
+                    let draw_builder = draw_builders
+                        .entry((cluster, mode, packed_material))
+                        .or_insert_with(|| {
+                            DrawBuilder::new(GxPrimitive::Triangles, VertexFormat::Overlay)
+                        });
+
+                    let base_index =
+                        mdl_model.vertexindex as usize / 48 + mdl_mesh.vertexoffset as usize;
+                    for strip_group in vtx_mesh.iter_strip_groups() {
+                        for strip in strip_group.iter_strips() {
+                            let indices: Vec<usize> = (0..strip.num_indices() as usize)
+                                .map(|i| {
+                                    let index = strip_group.index(strip.index_offset() as usize + i)
+                                        as usize;
+                                    base_index
+                                        + strip_group.vert(index).orig_mesh_vert_id() as usize
+                                })
+                                .collect();
+                            for triangle in triangles(&indices, strip.flags().is_tristrip()) {
+                                for index in triangle {
+                                    let vertex = vertices[index];
+                                    let position =
+                                        prop.transform_point(Vec3::from(vertex.position));
+                                    let normal = rotation * Vec3::from(vertex.normal);
+                                    emit_vertex(
+                                        draw_builder,
+                                        position,
+                                        evaluate_light_cube(&light_cube, normal),
+                                        vertex.tex_coord,
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(PropGeometry {
+        display_lists: draw_builders
+            .into_iter()
+            .map(|(key, builder)| (key, builder.build()))
+            .collect(),
+    })
+}
+
+/// Loads a model's MDL, VTX, and VVD files, or returns `None` if any of them is missing.
+fn load_model_files(asset_loader: &AssetLoader, model_name: &str) -> Result<Option<ModelFiles>> {
+    let stem = model_name.strip_suffix(".mdl").unwrap_or(model_name);
+    let load = |extension| {
+        asset_loader
+            .material_loader()
+            .load_file(&VpkPath::new_with_prefix_and_extension(
+                stem, "models", extension,
+            ))
+    };
+    Ok(match (load("mdl")?, load("dx90.vtx")?, load("vvd")?) {
+        (Some(mdl), Some(vtx), Some(vvd)) => Some(ModelFiles { mdl, vtx, vvd }),
+        _ => None,
+    })
+}
+
+/// Applies the VVD fixup table for LOD 0, returning vertices in the order the MDL's meshes
+/// expect.
+fn lod0_vertices<'a>(vvd: Vvd<'a>) -> Vec<&'a Vertex> {
+    let fixups = vvd.fixups();
+    if fixups.is_empty() {
+        return (0..vvd.header().num_lod_vertexes[0] as usize)
+            .map(|index| vvd.vertex(index))
+            .collect();
+    }
+    let mut vertices = Vec::new();
+    for fixup in fixups {
+        if fixup.lod >= 0 {
+            for i in 0..fixup.num_vertexes as usize {
+                vertices.push(vvd.vertex(fixup.source_vertex_id as usize + i));
+            }
+        }
+    }
+    vertices
+}
+
+/// Packs the material for one of the model's textures, or warns if no texture directory has it.
+fn resolve_material(
+    asset_loader: &AssetLoader,
+    ids: &mut TextureIdAllocator,
+    mdl: Mdl,
+    texture_index: usize,
+) -> Result<Option<PackedMaterial>> {
+    if let Some(material) = find_material(asset_loader, mdl, texture_index)? {
+        return PackedMaterial::from_material(asset_loader, ids, &material, false);
+    }
+    eprintln!(
+        "WARNING: Skipping static prop material {} with no matching VMT",
+        mdl.textures()[texture_index].name(mdl),
+    );
+    Ok(None)
+}
+
+/// Returns the cluster containing the prop's origin, falling back to the first leaf it touches
+/// that belongs to a cluster. Props entirely outside the world get `None`.
+fn prop_cluster(
+    bsp: Bsp,
+    prop: &StaticProp,
+    leaves: impl Iterator<Item = LeafIndex>,
+) -> Option<u16> {
+    std::iter::once(bsp.leaf_for_point(prop.origin))
+        .chain(leaves)
+        .find_map(|leaf| u16::try_from(bsp.leaf(leaf).cluster()).ok())
+}
+
+/// Picks the ambient sample nearest to `position` in the leaf that contains it.
+fn sample_light_cube<'a>(bsp: Bsp<'a>, position: Vec3) -> Option<&'a CompressedLightCube> {
+    let leaf_index = bsp.leaf_for_point(position);
+    let leaf = bsp.leaf(leaf_index);
+    bsp.leaf_ambient_samples(leaf_index)
+        .iter()
+        .min_by(|a, b| {
+            let distance = |sample: &LeafAmbientLighting| {
+                (Vec3::from(sample.position(leaf)) - position).norm_squared()
+            };
+            distance(a).total_cmp(&distance(b))
+        })
+        .map(|sample| &sample.cube)
+}
+
+/// Blends the cube's six faces by the squared components of `normal`, the same weighting the
+/// engine's vertex lit shaders use. Props with no ambient data are drawn at full brightness.
+fn evaluate_light_cube(cube: &Option<&CompressedLightCube>, normal: Vec3) -> [u8; 3] {
+    let cube = match cube {
+        Some(cube) => cube,
+        None => return [255; 3],
+    };
+    let normal = normal.normalize();
+    let mut color = [0.0f32; 3];
+    for axis in 0..3 {
+        let face = if normal[axis] >= 0.0 {
+            2 * axis
+        } else {
+            2 * axis + 1
+        };
+        let weight = normal[axis] * normal[axis];
+        for (channel, value) in color.iter_mut().zip(cube[face].to_srgb8()) {
+            *channel += weight * value as f32;
+        }
+    }
+    color.map(|x| (x + 0.5).clamp(0.0, 255.0) as u8)
+}
+
+/// Expands a strip's indices into triangles. Strips alternate winding, so every other triangle is
+/// flipped back, and degenerate triangles used to stitch strips together are dropped.
+fn triangles(indices: &[usize], is_strip: bool) -> Vec<[usize; 3]> {
+    if !is_strip {
+        return indices
+            .chunks_exact(3)
+            .map(|chunk| [chunk[0], chunk[1], chunk[2]])
+            .collect();
+    }
+    indices
+        .windows(3)
+        .enumerate()
+        .map(|(i, window)| {
+            if i % 2 == 0 {
+                [window[0], window[1], window[2]]
+            } else {
+                [window[1], window[0], window[2]]
+            }
+        })
+        .filter(|[a, b, c]| a != b && b != c && a != c)
+        .collect()
+}
+
+fn emit_vertex(
+    draw_builder: &mut DrawBuilder,
+    position: Vec3,
+    color: [u8; 3],
+    texture_coord: [f32; 2],
+) {
+    let mut data = Vec::with_capacity(23);
+    for &x in position.iter() {
+        data.write_f32::<BigEndian>(x).unwrap();
+    }
+    data.extend_from_slice(&color);
+    for x in texture_coord {
+        data.write_f32::<BigEndian>(x).unwrap();
+    }
+    draw_builder.emit_vertices(1, &data);
+}
+
+/// Packs each cluster's prop display lists, following the same layout as overlays.
+pub fn pack_props(
+    cluster_count: usize,
+    geometry: &PropGeometry,
+    texture_table: &[TextureTableEntry],
+) -> PackedProps {
+    let mut prop_table = Vec::new();
+    let mut prop_byte_code = Vec::new();
+    let mut prop_display_lists = Vec::new();
+    let mut prop_relocations = Vec::new();
+
+    for cluster in 0..cluster_count {
+        let mut byte_code_index_ranges = [[0; 2]; 2];
+        for (mode, range) in byte_code_index_ranges.iter_mut().enumerate() {
+            range[0] = u32::try_from(prop_byte_code.len()).unwrap();
+            let cluster = cluster as u16;
+            let mode = mode as u8;
+            for ((_, _, packed_material), draw_display_list) in geometry
+                .display_lists
+                .iter()
+                .filter(|((c, m, _), _)| (*c, *m) == (cluster, mode))
+            {
+                let display_list_offset = u32::try_from(prop_display_lists.len()).unwrap();
+                let mut display_list = DisplayList::new();
+
+                // Bind the base texture to TEXMAP0 and the aux texture, if any, to TEXMAP1. Both
+                // are sampled with TEXCOORD0.
+                display_list.append_bind_texture(0, packed_material.base_id, texture_table);
+                display_list.append_texcoord_scale(0, packed_material.base_id, texture_table);
+                if let Some(aux_id) = packed_material.aux_id {
+                    display_list.append_bind_texture(1, aux_id, texture_table);
+                }
+
+                display_list
+                    .commands
+                    .extend_from_slice(&draw_display_list.commands);
+                display_list.pad_to_alignment();
+                display_list
+                    .write_to(&mut prop_display_lists, |prop_display_lists, reference| {
+                        prop_relocations.push(RelocationTableEntry {
+                            display_list_offset: prop_display_lists.len().try_into().unwrap(),
+                            texture_id: match reference {
+                                gx::display_list::Reference::Texture(x) => x,
+                            },
+                            display_lists: RelocationTableEntry::PROP,
+                            _padding: 0,
+                        });
+                    })
+                    .unwrap();
+                let next_display_list_offset = u32::try_from(prop_display_lists.len()).unwrap();
+                let display_list_size = next_display_list_offset - display_list_offset;
+                assert_eq!(display_list_size & 31, 0);
+
+                BytecodeOp::Draw {
+                    display_list_offset,
+                    display_list_size,
+                }
+                .append_to(&mut prop_byte_code);
+            }
+            range[1] = u32::try_from(prop_byte_code.len()).unwrap();
+        }
+        prop_table.push(PropTableEntry {
+            byte_code_index_ranges,
+        });
+    }
+
+    PackedProps {
+        prop_table,
+        prop_byte_code,
+        prop_display_lists,
+        prop_relocations,
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{create_dir_all, rename, write};
+use std::path::Path;
+
+use anyhow::Result;
+use inception_render_common::map_data::{
+    shared_texture_path, AnimatedTextureTableEntry, SharedTextureTableEntry, TextureTableEntry,
+};
+use sha2::{Digest, Sha256};
+
+/// The 2D skybox's textures are bound directly rather than through relocations, so they always
+/// stay in the map.
+const FIRST_SHAREABLE_TEXTURE_ID: usize = 5;
+
+/// Images moved out of a map, keyed by the hash that names their files.
+pub type SharedTextures = BTreeMap<[u8; 16], Vec<u8>>;
+
+/// Moves every texture that can be shared between maps out of `texture_data`, flagging its table
+/// entry and listing it in the returned shared texture table. Animated textures stay in the map,
+/// since their frames are found by offset from the first.
+pub fn split_shared_textures(
+    texture_table: &mut [TextureTableEntry],
+    texture_data: &mut Vec<u8>,
+    animated_texture_table: &[AnimatedTextureTableEntry],
+) -> (Vec<SharedTextureTableEntry>, SharedTextures) {
+    let mut shared_texture_table = Vec::new();
+    let mut shared_textures = SharedTextures::new();
+    let mut kept_data = Vec::new();
+    // Textures deduplicated within the map share a range, so each range is copied only once.
+    let mut kept_offsets: HashMap<u32, u32> = HashMap::new();
+    for (id, entry) in texture_table.iter_mut().enumerate() {
+        let animation = animated_texture_table
+            .iter()
+            .find(|animation| animation.texture_id as usize == id);
+        let frame_count = animation.map_or(1, |animation| animation.frame_count as u32);
+        let frame_size = entry.end_offset - entry.start_offset;
+        let data = &texture_data
+            [entry.start_offset as usize..(entry.start_offset + frame_count * frame_size) as usize];
+
+        if id < FIRST_SHAREABLE_TEXTURE_ID || animation.is_some() {
+            let start_offset = *kept_offsets.entry(entry.start_offset).or_insert_with(|| {
+                let start_offset = u32::try_from(kept_data.len()).unwrap();
+                kept_data.extend_from_slice(data);
+                start_offset
+            });
+            assert_eq!(start_offset % 32, 0);
+            entry.start_offset = start_offset;
+            entry.end_offset = start_offset + frame_size;
+        } else {
+            let hash = Sha256::digest(data)[..16].try_into().unwrap();
+            shared_texture_table.push(SharedTextureTableEntry {
+                texture_id: u32::try_from(id).unwrap(),
+                size: frame_size,
+                crc32: crc32fast::hash(data),
+                hash,
+            });
+            shared_textures.entry(hash).or_insert_with(|| data.to_vec());
+            entry.flags |= TextureTableEntry::FLAG_SHARED;
+            entry.start_offset = 0;
+            entry.end_offset = 0;
+        }
+    }
+
+    *texture_data = kept_data;
+    (shared_texture_table, shared_textures)
+}
+
+/// Writes any shared textures that aren't already in `dst`. Maps packed in parallel may race to
+/// write the same texture, so each is written to a temporary file and renamed into place.
+pub fn write_shared_textures(dst: &Path, map_name: &str, textures: &SharedTextures) -> Result<()> {
+    create_dir_all(dst.join("textures"))?;
+    for (hash, data) in textures {
+        let path = dst.join(shared_texture_path(hash));
+        if path.exists() {
+            continue;
+        }
+        let temp_path = path.with_extension(format!("tex.{map_name}.tmp"));
+        write(&temp_path, data)?;
+        rename(&temp_path, &path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use inception_render_common::map_data::{AnimatedTextureTableEntry, TextureTableEntry};
+
+    use super::split_shared_textures;
+
+    fn entry(start_offset: u32, end_offset: u32) -> TextureTableEntry {
+        TextureTableEntry {
+            width: 8,
+            height: 8,
+            mip_count: 1,
+            flags: 0,
+            format: 0xe,
+            _padding: 0,
+            start_offset,
+            end_offset,
+        }
+    }
+
+    #[test]
+    fn keeps_skybox_and_animated_textures() {
+        // Five skybox faces sharing one image, then a shared texture used twice, then a
+        // two-frame animation.
+        let mut texture_table = vec![entry(0, 32); 5];
+        texture_table.extend([entry(32, 64), entry(32, 64), entry(64, 96)]);
+        let mut texture_data: Vec<u8> = (0..4).flat_map(|i| [i; 32]).collect();
+        let animated_texture_table = [AnimatedTextureTableEntry {
+            texture_id: 7,
+            frame_count: 2,
+            frames_per_second: 10.0,
+        }];
+
+        let (shared_texture_table, shared_textures) = split_shared_textures(
+            &mut texture_table,
+            &mut texture_data,
+            &animated_texture_table,
+        );
+
+        assert_eq!(shared_texture_table.len(), 2);
+        assert_eq!(shared_texture_table[0].texture_id, 5);
+        assert_eq!(shared_texture_table[1].texture_id, 6);
+        assert_eq!(shared_texture_table[0].hash, shared_texture_table[1].hash);
+        assert_eq!(shared_textures.len(), 1);
+        assert_eq!(shared_textures.values().next().unwrap(), &[1; 32]);
+
+        assert!(texture_table[5].flags & TextureTableEntry::FLAG_SHARED != 0);
+        assert_eq!(texture_table[0].start_offset, 0);
+        assert_eq!(
+            (texture_table[7].start_offset, texture_table[7].end_offset),
+            (32, 64),
+        );
+        let expected: Vec<u8> = [0, 2, 3].into_iter().flat_map(|i| [i; 32]).collect();
+        assert_eq!(texture_data, expected);
+    }
+}
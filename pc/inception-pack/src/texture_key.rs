@@ -1,5 +1,6 @@
 use std::borrow::Borrow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Display, Formatter};
 use std::hash::{Hash, Hasher};
 
 use source_reader::vpk::path::VpkPath;
@@ -72,6 +73,27 @@ pub enum OwnedTextureKey {
     },
 }
 
+impl Display for OwnedTextureKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EncodeAsIs { texture_path } => write!(f, "{texture_path}"),
+            Self::Intensity { texture_path } => write!(f, "intensity({texture_path})"),
+            Self::AlphaToIntensity { texture_path } => {
+                write!(f, "alpha_to_intensity({texture_path})")
+            }
+            Self::ComposeIntensityAlpha {
+                intensity_texture_path,
+                intensity_from_alpha,
+                alpha_texture_path,
+            } => write!(
+                f,
+                "compose_intensity_alpha({intensity_texture_path}{}, {alpha_texture_path})",
+                if *intensity_from_alpha { ".a" } else { "" },
+            ),
+        }
+    }
+}
+
 #[cfg(test)]
 impl Arbitrary for OwnedTextureKey {
     fn arbitrary(g: &mut quickcheck::Gen) -> Self {
@@ -156,6 +178,7 @@ impl<'a> Borrow<dyn TextureKey + 'a> for BorrowedTextureKey<'a> {
 pub struct TextureIdAllocator {
     keys_by_id: Vec<OwnedTextureKey>,
     ids_by_key: HashMap<OwnedTextureKey, u16>,
+    frame_rates_by_id: BTreeMap<u16, f32>,
 }
 
 impl TextureIdAllocator {
@@ -183,8 +206,14 @@ impl TextureIdAllocator {
         id
     }
 
-    pub fn into_keys(self) -> Vec<OwnedTextureKey> {
-        self.keys_by_id
+    /// Marks a texture as animated so that every frame of its VTF is packed. The first frame rate
+    /// given for a texture wins.
+    pub fn set_frame_rate(&mut self, id: u16, frame_rate: f32) {
+        self.frame_rates_by_id.entry(id).or_insert(frame_rate);
+    }
+
+    pub fn into_keys_and_frame_rates(self) -> (Vec<OwnedTextureKey>, BTreeMap<u16, f32>) {
+        (self.keys_by_id, self.frame_rates_by_id)
     }
 }
 
@@ -0,0 +1,396 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Context, Result};
+use toml::value::Table;
+use toml::Value;
+
+/// A device's register map as read from a TOML description.
+pub struct Device {
+    pub doc_name: String,
+    pub struct_name: String,
+    pub base: u32,
+    pub size: u32,
+    pub registers: Vec<Register>,
+    pub items: Vec<Item>,
+}
+
+pub struct Register {
+    pub name: String,
+    /// Emitted as plain comments, since `mmio_device!` doesn't accept attributes on registers.
+    pub doc: Option<String>,
+    pub ty: String,
+    /// `None` reserves the space without generating accessors.
+    pub access: Option<Access>,
+    /// Set for an array of registers with indexed accessors.
+    pub count: Option<u32>,
+}
+
+#[derive(Clone, Copy)]
+pub enum Access {
+    ReadOnly,
+    WriteOnly,
+    ReadWrite,
+}
+
+impl Access {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ReadOnly => "ro",
+            Self::WriteOnly => "wo",
+            Self::ReadWrite => "rw",
+        }
+    }
+}
+
+/// A type definition, emitted in description order after the device.
+pub enum Item {
+    Bitfield(Bitfield),
+    Enum(Enum),
+}
+
+pub struct Bitfield {
+    pub name: String,
+    pub doc: Option<String>,
+    pub width: u32,
+    pub fields: Vec<Field>,
+}
+
+pub struct Field {
+    pub name: String,
+    pub doc: Option<String>,
+    pub width: u32,
+    /// `bool` or the name of an enum. `None` for a plain narrow integer.
+    pub ty: Option<String>,
+}
+
+impl Field {
+    pub fn is_public(&self) -> bool {
+        !self.name.starts_with('_')
+    }
+}
+
+pub struct Enum {
+    pub name: String,
+    pub width: u32,
+    pub variants: Vec<Variant>,
+}
+
+pub struct Variant {
+    pub name: String,
+    pub value: u32,
+}
+
+impl Device {
+    pub fn parse(text: &str) -> Result<Self> {
+        let root: Value = toml::from_str(text)?;
+        let root = as_table(&root, "description")?;
+
+        let device = as_table(get(root, "device", "description")?, "device")?;
+        let registers = match root.get("register") {
+            Some(registers) => as_array(registers, "register")?
+                .iter()
+                .enumerate()
+                .map(|(index, register)| {
+                    parse_register(as_table(register, "register")?)
+                        .with_context(|| format!("register {index}"))
+                })
+                .collect::<Result<_>>()?,
+            None => Vec::new(),
+        };
+        let items = match root.get("item") {
+            Some(items) => as_array(items, "item")?
+                .iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    parse_item(as_table(item, "item")?).with_context(|| format!("item {index}"))
+                })
+                .collect::<Result<_>>()?,
+            None => Vec::new(),
+        };
+
+        let device = Self {
+            doc_name: get_str(device, "doc_name", "device")?,
+            struct_name: get_str(device, "struct_name", "device")?,
+            base: get_u32(device, "base", "device")?,
+            size: get_u32(device, "size", "device")?,
+            registers,
+            items,
+        };
+        device.validate()?;
+        Ok(device)
+    }
+
+    /// Checks the description for mistakes that would otherwise surface as confusing macro errors
+    /// or, worse, as silently wrong register offsets.
+    fn validate(&self) -> Result<()> {
+        check_type_name(&self.struct_name)?;
+
+        let mut widths = HashMap::new();
+        let mut enum_widths = HashMap::new();
+        for item in &self.items {
+            let (name, width) = match item {
+                Item::Bitfield(bitfield) => (&bitfield.name, bitfield.width),
+                Item::Enum(e) => {
+                    enum_widths.insert(e.name.as_str(), e.width);
+                    (&e.name, e.width)
+                }
+            };
+            check_type_name(name)?;
+            if widths.insert(name.as_str(), width).is_some() {
+                bail!("type {name} is defined twice");
+            }
+        }
+        for item in &self.items {
+            match item {
+                Item::Bitfield(bitfield) => validate_bitfield(bitfield, &enum_widths)
+                    .with_context(|| format!("bitfield {}", bitfield.name))?,
+                Item::Enum(e) => validate_enum(e).with_context(|| format!("enum {}", e.name))?,
+            }
+        }
+
+        let mut offset = 0;
+        for register in &self.registers {
+            check_field_name(&register.name)?;
+            let width = match register.ty.as_str() {
+                "u8" => 8,
+                "u16" => 16,
+                "u32" => 32,
+                ty => match (widths.get(ty), enum_widths.get(ty)) {
+                    (Some(&width), None) if [8, 16, 32].contains(&width) => width,
+                    (Some(_), _) => bail!(
+                        "register {} has type {ty}, which isn't a byte-sized bitfield",
+                        register.name,
+                    ),
+                    (None, _) => bail!("register {} has unknown type {ty}", register.name),
+                },
+            };
+            let size = width / 8;
+            if offset % size != 0 {
+                bail!(
+                    "register {} at offset {offset:#x} isn't aligned to its size",
+                    register.name,
+                );
+            }
+            match register.count {
+                Some(count)
+                    if register.access.is_some()
+                        && !(count.is_power_of_two() && (2..=256).contains(&count)) =>
+                {
+                    bail!(
+                        "register {} has count {count}, but indexed registers need a power of two \
+                        from 2 to 256",
+                        register.name,
+                    )
+                }
+                _ => (),
+            }
+            offset += size * register.count.unwrap_or(1);
+        }
+        if offset != self.size {
+            bail!(
+                "registers occupy {offset:#x} bytes, but the device size is {:#x}",
+                self.size,
+            );
+        }
+        Ok(())
+    }
+}
+
+fn validate_bitfield(bitfield: &Bitfield, enum_widths: &HashMap<&str, u32>) -> Result<()> {
+    if ![8, 16, 32, 64].contains(&bitfield.width) {
+        bail!("unsupported width {}", bitfield.width);
+    }
+    let mut total = 0;
+    for field in &bitfield.fields {
+        if field.is_public() {
+            check_field_name(&field.name)?;
+        }
+        if field.width == 0 {
+            bail!("field {} is empty", field.name);
+        }
+        match field.ty.as_deref() {
+            None => (),
+            Some("bool") if field.width == 1 => (),
+            Some("bool") => bail!("bool field {} must be one bit wide", field.name),
+            Some(ty) => match enum_widths.get(ty) {
+                Some(&width) if width == field.width => (),
+                Some(&width) => bail!(
+                    "field {} is {} bits wide, but enum {ty} is {width}",
+                    field.name,
+                    field.width,
+                ),
+                None => bail!("field {} has unknown type {ty}", field.name),
+            },
+        }
+        total += field.width;
+    }
+    if total > bitfield.width {
+        bail!("fields take {total} bits of {}", bitfield.width);
+    }
+    Ok(())
+}
+
+fn validate_enum(e: &Enum) -> Result<()> {
+    if !(1..=8).contains(&e.width) {
+        bail!("unsupported width {}", e.width);
+    }
+    // The conversions transmute, so every bit pattern must name a variant.
+    let mut seen = vec![false; 1 << e.width];
+    for variant in &e.variants {
+        check_type_name(&variant.name)?;
+        match seen.get_mut(variant.value as usize) {
+            Some(seen) if !*seen => *seen = true,
+            Some(_) => bail!("value {} is used twice", variant.value),
+            None => bail!("value {} doesn't fit in {} bits", variant.value, e.width),
+        }
+    }
+    if let Some(missing) = seen.iter().position(|&seen| !seen) {
+        bail!("no variant has value {missing}");
+    }
+    Ok(())
+}
+
+fn check_field_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        || name.starts_with(|c: char| c.is_ascii_digit())
+    {
+        bail!("{name:?} isn't a snake_case name");
+    }
+    check_spelling(name)
+}
+
+fn check_type_name(name: &str) -> Result<()> {
+    if !name.starts_with(|c: char| c.is_ascii_uppercase())
+        || !name.chars().all(|c| c.is_ascii_alphanumeric())
+    {
+        bail!("{name:?} isn't a CamelCase name");
+    }
+    check_spelling(name)
+}
+
+/// Catches transcription slips like `addresss`. No English word has a letter three times in a
+/// row.
+fn check_spelling(name: &str) -> Result<()> {
+    let lower = name.to_ascii_lowercase();
+    let bytes = lower.as_bytes();
+    if bytes
+        .windows(3)
+        .any(|w| w[0].is_ascii_alphabetic() && w[0] == w[1] && w[1] == w[2])
+    {
+        bail!("{name:?} repeats a letter three times");
+    }
+    Ok(())
+}
+
+fn parse_register(table: &Table) -> Result<Register> {
+    Ok(Register {
+        name: get_str(table, "name", "register")?,
+        doc: get_optional_str(table, "doc")?,
+        ty: get_str(table, "type", "register")?,
+        access: match table.get("access") {
+            Some(access) => Some(match as_str(access, "access")? {
+                "ro" => Access::ReadOnly,
+                "wo" => Access::WriteOnly,
+                "rw" => Access::ReadWrite,
+                access => bail!("unknown access {access:?}"),
+            }),
+            None => None,
+        },
+        count: table
+            .get("count")
+            .map(|count| as_u32(count, "count"))
+            .transpose()?,
+    })
+}
+
+fn parse_item(table: &Table) -> Result<Item> {
+    let name = get_str(table, "name", "item")?;
+    let width = get_u32(table, "width", "item")?;
+    match get_str(table, "kind", "item")?.as_str() {
+        "bitfield" => Ok(Item::Bitfield(Bitfield {
+            name,
+            doc: get_optional_str(table, "doc")?,
+            width,
+            fields: as_array(get(table, "fields", "item")?, "fields")?
+                .iter()
+                .map(|field| {
+                    let field = as_table(field, "field")?;
+                    Ok(Field {
+                        name: get_str(field, "name", "field")?,
+                        doc: get_optional_str(field, "doc")?,
+                        width: get_u32(field, "width", "field")?,
+                        ty: get_optional_str(field, "type")?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+        })),
+        "enum" => Ok(Item::Enum(Enum {
+            name,
+            width,
+            variants: as_array(get(table, "variants", "item")?, "variants")?
+                .iter()
+                .enumerate()
+                .map(|(index, variant)| {
+                    let variant = as_table(variant, "variant")?;
+                    Ok(Variant {
+                        name: get_str(variant, "name", "variant")?,
+                        value: match variant.get("value") {
+                            Some(value) => as_u32(value, "value")?,
+                            None => index as u32,
+                        },
+                    })
+                })
+                .collect::<Result<_>>()?,
+        })),
+        kind => bail!("unknown item kind {kind:?}"),
+    }
+}
+
+fn get<'a>(table: &'a Table, key: &str, context: &str) -> Result<&'a Value> {
+    table
+        .get(key)
+        .ok_or_else(|| anyhow!("{context} is missing {key:?}"))
+}
+
+fn get_str(table: &Table, key: &str, context: &str) -> Result<String> {
+    Ok(as_str(get(table, key, context)?, key)?.to_string())
+}
+
+fn get_optional_str(table: &Table, key: &str) -> Result<Option<String>> {
+    table
+        .get(key)
+        .map(|value| Ok(as_str(value, key)?.to_string()))
+        .transpose()
+}
+
+fn get_u32(table: &Table, key: &str, context: &str) -> Result<u32> {
+    as_u32(get(table, key, context)?, key)
+}
+
+fn as_table<'a>(value: &'a Value, what: &str) -> Result<&'a Table> {
+    value
+        .as_table()
+        .ok_or_else(|| anyhow!("{what} must be a table"))
+}
+
+fn as_array<'a>(value: &'a Value, what: &str) -> Result<&'a Vec<Value>> {
+    value
+        .as_array()
+        .ok_or_else(|| anyhow!("{what} must be an array"))
+}
+
+fn as_str<'a>(value: &'a Value, what: &str) -> Result<&'a str> {
+    value
+        .as_str()
+        .ok_or_else(|| anyhow!("{what} must be a string"))
+}
+
+fn as_u32(value: &Value, what: &str) -> Result<u32> {
+    value
+        .as_integer()
+        .and_then(|value| u32::try_from(value).ok())
+        .ok_or_else(|| anyhow!("{what} must be an integer that fits in 32 bits"))
+}
@@ -0,0 +1,226 @@
+use std::fmt::Write;
+
+use crate::description::{Bitfield, Device, Enum, Item};
+
+/// Renders a device as a gamecube-mmio module, formatted as rustfmt would leave it.
+pub fn emit(device: &Device) -> String {
+    let mut out = String::new();
+    if device
+        .items
+        .iter()
+        .any(|item| matches!(item, Item::Enum(_)))
+    {
+        out.push_str("use core::mem::transmute;\n\n");
+    }
+    out.push_str("use mvbitfield::prelude::*;\n");
+
+    emit_device(&mut out, device);
+    for item in &device.items {
+        match item {
+            Item::Bitfield(bitfield) => emit_bitfield(&mut out, bitfield),
+            Item::Enum(e) => emit_enum(&mut out, e),
+        }
+    }
+    out
+}
+
+fn emit_device(out: &mut String, device: &Device) {
+    writeln!(out).unwrap();
+    writeln!(out, "mmio_device! {{").unwrap();
+    writeln!(out, "    doc_name: {:?},", device.doc_name).unwrap();
+    writeln!(out, "    struct_name: {},", device.struct_name).unwrap();
+    writeln!(out, "    base: {:#x},", device.base).unwrap();
+    writeln!(out, "    size: {:#x},", device.size).unwrap();
+    writeln!(out, "    regs: {{").unwrap();
+    for register in &device.registers {
+        for line in register.doc.iter().flat_map(|doc| doc.lines()) {
+            if line.is_empty() {
+                writeln!(out, "        //").unwrap();
+            } else {
+                writeln!(out, "        // {line}").unwrap();
+            }
+        }
+        let ty = match register.count {
+            Some(count) => format!("[{}; {count}]", register.ty),
+            None => register.ty.clone(),
+        };
+        match register.access {
+            Some(access) => {
+                let indexed = if register.count.is_some() {
+                    "(indexed)"
+                } else {
+                    ""
+                };
+                writeln!(
+                    out,
+                    "        {}: {ty} = {}{indexed},",
+                    register.name,
+                    access.as_str(),
+                )
+                .unwrap();
+            }
+            None => writeln!(out, "        {}: {ty},", register.name).unwrap(),
+        }
+    }
+    writeln!(out, "    }},").unwrap();
+    writeln!(out, "}}").unwrap();
+}
+
+fn emit_bitfield(out: &mut String, bitfield: &Bitfield) {
+    writeln!(out).unwrap();
+    writeln!(out, "mvbitfield! {{").unwrap();
+    emit_doc(out, "    ", bitfield.doc.as_deref());
+    writeln!(
+        out,
+        "    pub struct {}: u{} {{",
+        bitfield.name, bitfield.width,
+    )
+    .unwrap();
+    for field in &bitfield.fields {
+        emit_doc(out, "        ", field.doc.as_deref());
+        let visibility = if field.is_public() { "pub " } else { "" };
+        let ty = match &field.ty {
+            Some(ty) => format!(" as {ty}"),
+            None => String::new(),
+        };
+        writeln!(
+            out,
+            "        {visibility}{}: {}{ty},",
+            field.name, field.width,
+        )
+        .unwrap();
+    }
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "}}").unwrap();
+}
+
+fn emit_enum(out: &mut String, e: &Enum) {
+    let narrow = format!("U{}", e.width);
+    let suffix = format!("u{}", e.width);
+
+    writeln!(out).unwrap();
+    writeln!(out, "#[repr(u8)]").unwrap();
+    writeln!(out, "pub enum {} {{", e.name).unwrap();
+    for variant in &e.variants {
+        writeln!(out, "    {} = {},", variant.name, variant.value).unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "impl {} {{", e.name).unwrap();
+    writeln!(
+        out,
+        "    pub const fn from_{suffix}(value: {narrow}) -> Self {{",
+    )
+    .unwrap();
+    emit_transmute(out, &e.name, &narrow, "value");
+    writeln!(out, "    }}").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "    pub const fn as_{suffix}(self) -> {narrow} {{").unwrap();
+    emit_transmute(out, &e.name, &narrow, "self");
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "}}").unwrap();
+}
+
+fn emit_transmute(out: &mut String, name: &str, narrow: &str, arg: &str) {
+    writeln!(
+        out,
+        "        // SAFETY: {name} and {narrow} have the same layout and valid bit patterns.",
+    )
+    .unwrap();
+    writeln!(out, "        unsafe {{ transmute({arg}) }}").unwrap();
+}
+
+fn emit_doc(out: &mut String, indent: &str, doc: Option<&str>) {
+    for line in doc.into_iter().flat_map(str::lines) {
+        if line.is_empty() {
+            writeln!(out, "{indent}///").unwrap();
+        } else {
+            writeln!(out, "{indent}/// {line}").unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::emit;
+    use crate::description::Device;
+
+    /// The checked-in DI module was written by hand before the generator existed. Its description
+    /// must reproduce it exactly.
+    #[test]
+    fn regenerates_dvd_interface() {
+        let device = Device::parse(include_str!(
+            "../../../gc_wii/gamecube-mmio/registers/dvd_interface.toml"
+        ))
+        .unwrap();
+        assert_eq!(
+            emit(&device),
+            include_str!("../../../gc_wii/gamecube-mmio/src/dvd_interface.rs"),
+        );
+    }
+
+    #[test]
+    fn regenerates_audio_interface() {
+        let device = Device::parse(include_str!(
+            "../../../gc_wii/gamecube-mmio/registers/audio_interface.toml"
+        ))
+        .unwrap();
+        assert_eq!(
+            emit(&device),
+            include_str!("../../../gc_wii/gamecube-mmio/src/audio_interface.rs"),
+        );
+    }
+
+    #[test]
+    fn rejects_misspelled_names() {
+        let error = Device::parse(
+            r#"
+            [device]
+            doc_name = "XX"
+            struct_name = "Example"
+            base = 0xcc000000
+            size = 4
+
+            [[register]]
+            name = "field_base"
+            type = "FieldBase"
+            access = "rw"
+
+            [[item]]
+            kind = "bitfield"
+            name = "FieldBase"
+            width = 32
+            fields = [{ name = "addresss", width = 24 }]
+            "#,
+        )
+        .err()
+        .unwrap();
+        assert!(format!("{error:#}").contains("addresss"));
+    }
+
+    #[test]
+    fn rejects_size_mismatch() {
+        let error = Device::parse(
+            r#"
+            [device]
+            doc_name = "XX"
+            struct_name = "Example"
+            base = 0xcc000000
+            size = 8
+
+            [[register]]
+            name = "status"
+            type = "u16"
+            access = "ro"
+
+            [[register]]
+            name = "control"
+            type = "u32"
+            access = "rw"
+            "#,
+        )
+        .err()
+        .unwrap();
+        assert!(format!("{error:#}").contains("aligned"));
+    }
+}
@@ -0,0 +1,62 @@
+//! Generates a gamecube-mmio device module from a TOML register description.
+//!
+//! A description has a `[device]` table with `doc_name`, `struct_name`, `base`, and `size`, a
+//! `[[register]]` array in address order, and an `[[item]]` array of the bitfields and enums the
+//! registers use. See `gc_wii/gamecube-mmio/registers/` for examples.
+
+use std::fs::{read_to_string, write};
+use std::io::{stdout, Write};
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+
+use crate::description::Device;
+use crate::emit::emit;
+
+mod description;
+mod emit;
+
+#[derive(Parser)]
+#[clap(name = "mmio-gen")]
+struct Args {
+    /// The register description to read.
+    input: PathBuf,
+
+    /// Where to write the module. Defaults to standard output.
+    #[clap(long)]
+    output: Option<PathBuf>,
+
+    /// Instead of writing, fail if the output file doesn't match what would be generated.
+    #[clap(long, requires = "output")]
+    check: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let text =
+        read_to_string(&args.input).with_context(|| format!("Reading {}", args.input.display()))?;
+    let device =
+        Device::parse(&text).with_context(|| format!("Parsing {}", args.input.display()))?;
+    let module = emit(&device);
+
+    match args.output {
+        Some(output) if args.check => {
+            let existing =
+                read_to_string(&output).with_context(|| format!("Reading {}", output.display()))?;
+            if existing != module {
+                bail!(
+                    "{} is out of date with {}",
+                    output.display(),
+                    args.input.display(),
+                );
+            }
+        }
+        Some(output) => {
+            write(&output, module).with_context(|| format!("Writing {}", output.display()))?
+        }
+        None => stdout().write_all(module.as_bytes())?,
+    }
+    Ok(())
+}
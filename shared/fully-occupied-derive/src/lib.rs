@@ -0,0 +1,143 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::token::Paren;
+use syn::{parenthesized, parse_macro_input, Data, DeriveInput, Error, Fields, Meta};
+
+/// Derives `FullyOccupied` for a struct.
+///
+/// The struct must be `repr(C)` or `repr(transparent)`, every field must be `FullyOccupied`, and
+/// the fields must exactly fill the struct with no padding. The last two requirements are checked
+/// at compile time.
+///
+/// ```
+/// use fully_occupied::FullyOccupied;
+///
+/// #[derive(Clone, Copy, FullyOccupied)]
+/// #[repr(C)]
+/// struct Entry {
+///     id: u32,
+///     flags: u16,
+///     kind: [u8; 2],
+/// }
+/// ```
+///
+/// Padding is rejected:
+///
+/// ```compile_fail
+/// use fully_occupied::FullyOccupied;
+///
+/// #[derive(Clone, Copy, FullyOccupied)]
+/// #[repr(C)]
+/// struct Padded {
+///     kind: u8,
+///     id: u32,
+/// }
+/// ```
+///
+/// So is a struct whose layout Rust is free to change:
+///
+/// ```compile_fail
+/// use fully_occupied::FullyOccupied;
+///
+/// #[derive(Clone, Copy, FullyOccupied)]
+/// struct Unordered {
+///     id: u32,
+///     flags: u32,
+/// }
+/// ```
+///
+/// And so is a field that isn't `FullyOccupied` itself:
+///
+/// ```compile_fail
+/// use fully_occupied::FullyOccupied;
+///
+/// #[derive(Clone, Copy, FullyOccupied)]
+/// #[repr(C)]
+/// struct Flagged {
+///     enabled: bool,
+///     kind: [u8; 3],
+/// }
+/// ```
+#[proc_macro_derive(FullyOccupied)]
+pub fn derive_fully_occupied(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "FullyOccupied can't be derived for generic types",
+        ));
+    }
+
+    if !has_stable_repr(&input)? {
+        return Err(Error::new(
+            Span::call_site(),
+            "FullyOccupied requires #[repr(C)] or #[repr(transparent)]",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields.named.iter().collect(),
+            Fields::Unnamed(fields) => fields.unnamed.iter().collect(),
+            Fields::Unit => Vec::new(),
+        },
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "FullyOccupied can only be derived for structs",
+            ))
+        }
+    };
+    let field_types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+
+    Ok(quote! {
+        const _: fn() = || {
+            fn assert_fully_occupied<T: ::fully_occupied::FullyOccupied>() {}
+            #(assert_fully_occupied::<#field_types>();)*
+        };
+
+        const _: () = {
+            assert!(
+                ::core::mem::size_of::<#name>()
+                    == 0 #(+ ::core::mem::size_of::<#field_types>())*,
+                concat!(stringify!(#name), " has padding bytes"),
+            );
+        };
+
+        // SAFETY: Every field is fully occupied and the fields cover the whole struct.
+        unsafe impl ::fully_occupied::FullyOccupied for #name {}
+    })
+}
+
+fn has_stable_repr(input: &DeriveInput) -> syn::Result<bool> {
+    let mut result = false;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("repr") {
+            continue;
+        }
+        if let Meta::List(_) = &attr.meta {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("C") || meta.path.is_ident("transparent") {
+                    result = true;
+                }
+                // Skip arguments like the alignment in `align(4)`.
+                if meta.input.peek(Paren) {
+                    let _content;
+                    parenthesized!(_content in meta.input);
+                }
+                Ok(())
+            })?;
+        }
+    }
+    Ok(result)
+}
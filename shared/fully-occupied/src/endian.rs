@@ -0,0 +1,153 @@
+use core::fmt::{self, Debug, Formatter};
+
+use crate::FullyOccupied;
+
+/// A primitive that can be converted between native and a fixed byte order.
+pub trait EndianPrimitive: FullyOccupied + Copy {
+    fn from_be(raw: Self) -> Self;
+    fn from_le(raw: Self) -> Self;
+    fn to_be(self) -> Self;
+    fn to_le(self) -> Self;
+}
+
+macro_rules! impl_endian_primitive_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl EndianPrimitive for $t {
+                fn from_be(raw: Self) -> Self {
+                    <$t>::from_be(raw)
+                }
+
+                fn from_le(raw: Self) -> Self {
+                    <$t>::from_le(raw)
+                }
+
+                fn to_be(self) -> Self {
+                    <$t>::to_be(self)
+                }
+
+                fn to_le(self) -> Self {
+                    <$t>::to_le(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_endian_primitive_for_int!(u16, u32, u64, u128, i16, i32, i64, i128);
+
+macro_rules! impl_endian_primitive_for_float {
+    ($($t:ty),*) => {
+        $(
+            impl EndianPrimitive for $t {
+                fn from_be(raw: Self) -> Self {
+                    <$t>::from_bits(EndianPrimitive::from_be(raw.to_bits()))
+                }
+
+                fn from_le(raw: Self) -> Self {
+                    <$t>::from_bits(EndianPrimitive::from_le(raw.to_bits()))
+                }
+
+                fn to_be(self) -> Self {
+                    <$t>::from_bits(EndianPrimitive::to_be(self.to_bits()))
+                }
+
+                fn to_le(self) -> Self {
+                    <$t>::from_bits(EndianPrimitive::to_le(self.to_bits()))
+                }
+            }
+        )*
+    };
+}
+
+impl_endian_primitive_for_float!(f32, f64);
+
+macro_rules! define_endian_wrapper {
+    ($name:ident, $from:ident, $to:ident, $order:literal) => {
+        #[doc = concat!("A `T` stored in ", $order, " byte order regardless of the host.")]
+        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+        #[repr(transparent)]
+        pub struct $name<T>(T);
+
+        impl<T: EndianPrimitive> $name<T> {
+            pub fn new(value: T) -> Self {
+                Self(value.$to())
+            }
+
+            pub fn get(self) -> T {
+                T::$from(self.0)
+            }
+
+            pub fn set(&mut self, value: T) {
+                self.0 = value.$to();
+            }
+        }
+
+        impl<T: EndianPrimitive> From<T> for $name<T> {
+            fn from(value: T) -> Self {
+                Self::new(value)
+            }
+        }
+
+        impl<T: EndianPrimitive + Debug> Debug for $name<T> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                self.get().fmt(f)
+            }
+        }
+
+        // SAFETY: The wrapper is transparent over a fully occupied type.
+        unsafe impl<T: EndianPrimitive> FullyOccupied for $name<T> {}
+    };
+}
+
+define_endian_wrapper!(BigEndian, from_be, to_be, "big-endian");
+define_endian_wrapper!(LittleEndian, from_le, to_le, "little-endian");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{as_bytes, read_from_bytes};
+
+    macro_rules! test_round_trip {
+        ($($name:ident: $t:ty = $value:expr;)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let value: $t = $value;
+
+                    let big = BigEndian::new(value);
+                    assert_eq!(as_bytes(&big), value.to_be_bytes());
+                    assert_eq!(big.get(), value);
+                    let big: BigEndian<$t> = read_from_bytes(&value.to_be_bytes());
+                    assert_eq!(big.get(), value);
+
+                    let little = LittleEndian::new(value);
+                    assert_eq!(as_bytes(&little), value.to_le_bytes());
+                    assert_eq!(little.get(), value);
+                    let little: LittleEndian<$t> = read_from_bytes(&value.to_le_bytes());
+                    assert_eq!(little.get(), value);
+
+                    let mut big = BigEndian::default();
+                    big.set(value);
+                    assert_eq!(as_bytes(&big), value.to_be_bytes());
+                    let mut little = LittleEndian::default();
+                    little.set(value);
+                    assert_eq!(as_bytes(&little), value.to_le_bytes());
+                }
+            )*
+        };
+    }
+
+    test_round_trip! {
+        round_trip_u16: u16 = 0x0102;
+        round_trip_u32: u32 = 0x0102_0304;
+        round_trip_u64: u64 = 0x0102_0304_0506_0708;
+        round_trip_u128: u128 = 0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10;
+        round_trip_i16: i16 = -0x0102;
+        round_trip_i32: i32 = -0x0102_0304;
+        round_trip_i64: i64 = -0x0102_0304_0506_0708;
+        round_trip_i128: i128 = -0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10;
+        round_trip_f32: f32 = -1.5e-3;
+        round_trip_f64: f64 = 6.02214076e23;
+    }
+}
@@ -1,17 +1,97 @@
 #![deny(unsafe_op_in_unsafe_fn)]
 #![no_std]
 
-use core::mem::{align_of, size_of};
-use core::slice::from_raw_parts;
+extern crate alloc;
+
+use core::fmt::{self, Display, Formatter};
+use core::mem::{align_of, size_of, MaybeUninit};
+use core::ptr::copy_nonoverlapping;
+use core::slice::{from_raw_parts, from_raw_parts_mut};
+
+use alloc::vec::Vec;
+
+pub use fully_occupied_derive::FullyOccupied;
+
+pub use crate::endian::{BigEndian, EndianPrimitive, LittleEndian};
+
+mod endian;
 
 fn is_aligned<T>(ptr: *const T) -> bool {
     (ptr as usize) % align_of::<T>() == 0
 }
 
+/// Why a byte slice couldn't be reinterpreted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtractError {
+    /// The slice is shorter than the value.
+    TooShort { needed: usize, available: usize },
+    /// The slice's length isn't a whole number of elements.
+    NotMultipleOfSize { len: usize, size: usize },
+    /// The slice's address isn't aligned for the type.
+    Misaligned { address: usize, align: usize },
+}
+
+impl Display for ExtractError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::TooShort { needed, available } => {
+                write!(
+                    f,
+                    "needed {needed} bytes but only {available} are available"
+                )
+            }
+            Self::NotMultipleOfSize { len, size } => {
+                write!(f, "length {len} is not a multiple of element size {size}")
+            }
+            Self::Misaligned { address, align } => {
+                write!(f, "address {address:#x} is not aligned to {align} bytes")
+            }
+        }
+    }
+}
+
+fn check_prefix<T>(bytes: &[u8]) -> Result<*const T, ExtractError> {
+    if bytes.len() < size_of::<T>() {
+        return Err(ExtractError::TooShort {
+            needed: size_of::<T>(),
+            available: bytes.len(),
+        });
+    }
+    check_aligned(bytes.as_ptr() as *const T)
+}
+
+fn check_whole<T>(bytes: &[u8]) -> Result<*const T, ExtractError> {
+    if bytes.len() % size_of::<T>() != 0 {
+        return Err(ExtractError::NotMultipleOfSize {
+            len: bytes.len(),
+            size: size_of::<T>(),
+        });
+    }
+    check_aligned(bytes.as_ptr() as *const T)
+}
+
+fn check_aligned<T>(ptr: *const T) -> Result<*const T, ExtractError> {
+    if is_aligned(ptr) {
+        Ok(ptr)
+    } else {
+        Err(ExtractError::Misaligned {
+            address: ptr as usize,
+            align: align_of::<T>(),
+        })
+    }
+}
+
 /// Marker trait for fully occupied types. A fully occupied type is valid for any state of the bits
 /// in its representation.
 ///
 /// A fully occupied type may be safely extracted from any correctly aligned byte slice.
+///
+/// Structs should use `#[derive(FullyOccupied)]` rather than an unsafe impl. The derive checks
+/// the representation, the fields, and the absence of padding.
+///
+/// # Safety
+///
+/// Every bit pattern of `size_of::<Self>()` bytes must be a valid `Self`.
 pub unsafe trait FullyOccupied {}
 
 // SAFETY: Primitive integer types are fully occupied.
@@ -50,8 +130,18 @@ pub fn slice_as_bytes<T: FullyOccupied>(values: &[T]) -> &[u8] {
 /// - `data.len()` must be at least `size_of::<T>()`.
 /// - `data.as_ptr()` must be aligned for `T`.
 pub fn extract<T: FullyOccupied>(bytes: &[u8]) -> &T {
-    // SAFETY: The unsafe impl of `FullyOccupied` promises that the bytes are valid for T.
-    unsafe { extract_unchecked(bytes) }
+    try_extract(bytes).unwrap_or_else(|e| panic!("extract: {e}"))
+}
+
+/// Reinteprets a prefix of a byte slice as a value of T, or returns an error if the slice is too
+/// short or misaligned.
+pub fn try_extract<T: FullyOccupied>(bytes: &[u8]) -> Result<&T, ExtractError> {
+    let ptr = check_prefix::<T>(bytes)?;
+
+    // SAFETY: The unsafe impl of `FullyOccupied` promises that the bytes are valid for T. The
+    // memory is from the provided byte slice and thus is valid. Length and alignment have been
+    // checked.
+    Ok(unsafe { &*ptr })
 }
 
 /// Reinteprets a byte slice as a slice of T.
@@ -63,8 +153,102 @@ pub fn extract<T: FullyOccupied>(bytes: &[u8]) -> &T {
 /// - `data.len()` must be a multiple of `size_of::<T>()`.
 /// - `data.as_ptr()` must be aligned for `T`.
 pub fn extract_slice<T: FullyOccupied>(bytes: &[u8]) -> &[T] {
-    // SAFETY: The unsafe impl of `FullyOccupied` promises that the bytes are valid for T.
-    unsafe { extract_slice_unchecked(bytes) }
+    try_extract_slice(bytes).unwrap_or_else(|e| panic!("extract_slice: {e}"))
+}
+
+/// Reinteprets a byte slice as a slice of T, or returns an error if the slice isn't a whole number
+/// of elements or is misaligned.
+pub fn try_extract_slice<T: FullyOccupied>(bytes: &[u8]) -> Result<&[T], ExtractError> {
+    let ptr = check_whole::<T>(bytes)?;
+
+    // SAFETY: As for `try_extract`.
+    Ok(unsafe { from_raw_parts(ptr, bytes.len() / size_of::<T>()) })
+}
+
+/// Reinteprets a prefix of a mutable byte slice as a value of T.
+///
+/// # Panics
+///
+/// Panics if any of the following preconditions fail:
+///
+/// - `data.len()` must be at least `size_of::<T>()`.
+/// - `data.as_ptr()` must be aligned for `T`.
+pub fn extract_mut<T: FullyOccupied>(bytes: &mut [u8]) -> &mut T {
+    let bytes = &mut bytes[..size_of::<T>()];
+    let ptr = bytes.as_mut_ptr() as *mut T;
+    assert!(is_aligned(ptr));
+
+    // SAFETY: The unsafe impl of `FullyOccupied` promises that the bytes are valid for T, and any
+    // value written through the reference leaves valid bytes behind. The memory is from the
+    // provided byte slice and thus is valid. Alignment has been checked.
+    unsafe { &mut *ptr }
+}
+
+/// Reinteprets a mutable byte slice as a slice of T.
+///
+/// # Panics
+///
+/// Panics if any of the following preconditions fail:
+///
+/// - `data.len()` must be a multiple of `size_of::<T>()`.
+/// - `data.as_ptr()` must be aligned for `T`.
+pub fn extract_slice_mut<T: FullyOccupied>(bytes: &mut [u8]) -> &mut [T] {
+    assert_eq!(bytes.len() % size_of::<T>(), 0);
+    let ptr = bytes.as_mut_ptr() as *mut T;
+    assert!(is_aligned(ptr));
+
+    // SAFETY: As for `extract_mut`.
+    unsafe { from_raw_parts_mut(ptr, bytes.len() / size_of::<T>()) }
+}
+
+/// Copies a value of T out of a prefix of a byte slice. Unlike `extract`, the bytes need not be
+/// aligned.
+///
+/// # Panics
+///
+/// Panics if `data.len()` is less than `size_of::<T>()`.
+pub fn read_from_bytes<T: FullyOccupied>(bytes: &[u8]) -> T {
+    let bytes = &bytes[..size_of::<T>()];
+    let mut value = MaybeUninit::<T>::uninit();
+
+    // SAFETY: The source has been bounds checked and the destination is a local of the right
+    // size. The unsafe impl of `FullyOccupied` promises that the copied bytes are valid for T.
+    unsafe {
+        copy_nonoverlapping(
+            bytes.as_ptr(),
+            value.as_mut_ptr() as *mut u8,
+            size_of::<T>(),
+        );
+        value.assume_init()
+    }
+}
+
+/// Copies a byte slice into a newly allocated `Vec` of T. The bytes need not be aligned.
+///
+/// # Panics
+///
+/// Panics if `data.len()` is not a multiple of `size_of::<T>()`.
+pub fn read_vec_from_bytes<T: FullyOccupied>(bytes: &[u8]) -> Vec<T> {
+    assert_eq!(bytes.len() % size_of::<T>(), 0);
+    let len = bytes.len() / size_of::<T>();
+    let mut values = Vec::with_capacity(len);
+
+    // SAFETY: The capacity covers exactly `bytes.len()` bytes. The unsafe impl of `FullyOccupied`
+    // promises that the copied bytes are valid for T, so the length may then be set.
+    unsafe {
+        copy_nonoverlapping(bytes.as_ptr(), values.as_mut_ptr() as *mut u8, bytes.len());
+        values.set_len(len);
+    }
+    values
+}
+
+/// Copies a value of T into a prefix of a byte slice. The bytes need not be aligned.
+///
+/// # Panics
+///
+/// Panics if `data.len()` is less than `size_of::<T>()`.
+pub fn write_to_bytes<T: FullyOccupied>(bytes: &mut [u8], value: &T) {
+    bytes[..size_of::<T>()].copy_from_slice(as_bytes(value));
 }
 
 /// Reinteprets a prefix of a byte slice as a value of T, regardless of whether T is fully occupied.
@@ -110,3 +294,92 @@ pub unsafe fn extract_slice_unchecked<T>(bytes: &[u8]) -> &[T] {
     // slice and thus is valid. Alignment has been checked.
     unsafe { from_raw_parts(ptr, bytes.len() / size_of::<T>()) }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::{
+        extract_mut, extract_slice_mut, read_from_bytes, read_vec_from_bytes, try_extract,
+        try_extract_slice, write_to_bytes, ExtractError,
+    };
+
+    #[repr(align(4))]
+    struct Aligned([u8; 12]);
+
+    #[test]
+    fn try_extract_reports_errors() {
+        let data = Aligned([0; 12]);
+        assert!(try_extract::<u32>(&data.0[4..]).is_ok());
+        assert_eq!(
+            try_extract::<u32>(&data.0[..3]),
+            Err(ExtractError::TooShort {
+                needed: 4,
+                available: 3,
+            }),
+        );
+        assert!(matches!(
+            try_extract::<u32>(&data.0[1..]),
+            Err(ExtractError::Misaligned { align: 4, .. }),
+        ));
+    }
+
+    #[test]
+    fn try_extract_slice_reports_errors() {
+        let data = Aligned([0; 12]);
+        assert_eq!(try_extract_slice::<u32>(&data.0).unwrap().len(), 3);
+        assert_eq!(
+            try_extract_slice::<u32>(&data.0[..10]),
+            Err(ExtractError::NotMultipleOfSize { len: 10, size: 4 }),
+        );
+        assert!(matches!(
+            try_extract_slice::<u32>(&data.0[2..10]),
+            Err(ExtractError::Misaligned { align: 4, .. }),
+        ));
+    }
+
+    #[test]
+    fn extract_mut_writes_through() {
+        let mut data = Aligned([0; 12]);
+        *extract_mut::<u32>(&mut data.0[4..]) = 0x1234_5678;
+        assert_eq!(data.0[4..8], 0x1234_5678u32.to_ne_bytes());
+
+        extract_slice_mut::<u16>(&mut data.0[8..]).copy_from_slice(&[0xabcd, 0xef01]);
+        assert_eq!(data.0[8..10], 0xabcdu16.to_ne_bytes());
+        assert_eq!(data.0[10..12], 0xef01u16.to_ne_bytes());
+        assert_eq!(data.0[..4], [0; 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn extract_mut_rejects_misaligned() {
+        let mut data = Aligned([0; 12]);
+        extract_mut::<u32>(&mut data.0[1..]);
+    }
+
+    #[test]
+    fn unaligned_round_trip() {
+        let mut data = Aligned([0; 12]);
+        let value: [u32; 2] = [0x0102_0304, 0xa0b0_c0d0];
+        write_to_bytes(&mut data.0[1..], &value);
+        assert_eq!(data.0[1..5], value[0].to_ne_bytes());
+        assert_eq!(data.0[5..9], value[1].to_ne_bytes());
+        assert_eq!(data.0[9..], [0; 3]);
+
+        assert_eq!(read_from_bytes::<[u32; 2]>(&data.0[1..]), value);
+        assert_eq!(
+            read_vec_from_bytes::<u32>(&data.0[1..9]),
+            vec![value[0], value[1]]
+        );
+        assert_eq!(
+            read_from_bytes::<u16>(&data.0[3..]),
+            u16::from_ne_bytes([data.0[3], data.0[4]])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn read_vec_from_bytes_rejects_partial_elements() {
+        read_vec_from_bytes::<u32>(&[0; 6]);
+    }
+}
@@ -6,6 +6,12 @@ pub struct HashableMat<const R: usize, const C: usize> {
     matrix: nalgebra_glm::TMat<f32, R, C>,
 }
 
+impl<const R: usize, const C: usize> HashableMat<R, C> {
+    pub fn as_matrix(&self) -> &nalgebra_glm::TMat<f32, R, C> {
+        &self.matrix
+    }
+}
+
 impl<const R: usize, const C: usize> Eq for HashableMat<R, C> {}
 
 impl<const R: usize, const C: usize> Hash for HashableMat<R, C> {
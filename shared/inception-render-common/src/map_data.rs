@@ -1,18 +1,22 @@
-use core::ops::Deref;
+use core::mem::size_of;
+use core::ops::{Deref, DerefMut};
 use core::slice;
 #[cfg(feature = "std")]
 use std::borrow::Cow;
 #[cfg(feature = "std")]
-use std::io::{self, Seek, Write};
+use std::io::{self, Cursor, Seek, Write};
 
+use alloc::format;
+use alloc::string::String;
 use alloc::vec::Vec;
 use bytemuck::{Pod, Zeroable};
 #[cfg(feature = "std")]
 use byteorder::{BigEndian, WriteBytesExt};
 #[cfg(feature = "std")]
-use relocation::{PointerFormat, RelocationWriter};
+use relocation::{PointerFormat, RelocationWriter, SectionBuilder};
 
 use crate::bytecode::{BytecodeOp, BytecodeReader};
+use crate::vertex_format::{VertexFormat, VertexFormatDescriptor};
 
 #[cfg(feature = "std")]
 pub trait WriteTo<W: Seek + Write> {
@@ -68,6 +72,7 @@ impl<W: Seek + Write, T: WriteTo<W>> WriteTo<W> for [T] {
 //     pub bytecode_end_offset: u32,
 // }
 
+#[derive(Default)]
 pub struct OwnedMapData {
     pub position_data: Vec<u8>,
     pub normal_data: Vec<u8>,
@@ -75,7 +80,6 @@ pub struct OwnedMapData {
     pub cluster_geometry_table: Vec<ClusterGeometryTableEntry>,
     pub cluster_geometry_byte_code: Vec<u32>,
     pub cluster_geometry_display_lists: Vec<u8>,
-    pub cluster_geometry_references: Vec<ClusterGeometryReferencesEntry>,
 
     pub bsp_nodes: Vec<BspNode>,
     pub bsp_leaves: Vec<BspLeaf>,
@@ -83,6 +87,9 @@ pub struct OwnedMapData {
 
     pub texture_table: Vec<TextureTableEntry>,
     pub texture_data: Vec<u8>,
+    /// Parallel to `texture_table`. May be empty if names were not preserved.
+    pub texture_name_table: Vec<NameTableEntry>,
+    pub name_data: Vec<u8>,
 
     pub lightmap_cluster_table: Vec<ClusterLightmapTableEntry>,
     pub lightmap_displacement_table: Vec<DisplacementLightmapTableEntry>,
@@ -95,13 +102,57 @@ pub struct OwnedMapData {
     pub displacement_table: Vec<DisplacementTableEntry>,
     pub displacement_byte_code: Vec<u32>,
     pub displacement_display_lists: Vec<u8>,
-    pub displacement_references: Vec<DisplacementReferencesEntry>,
+
+    /// Names are stored in `name_data`.
+    pub level_transition_table: Vec<LevelTransitionTableEntry>,
+    /// Names are stored in `name_data`.
+    pub landmark_table: Vec<LandmarkTableEntry>,
+
+    /// One entry per vertex format used by this map's display lists.
+    pub vertex_format_table: Vec<VertexFormatDescriptor>,
+
+    /// Parallel to `cluster_geometry_table`. Byte code and display lists share the
+    /// `cluster_geometry_*` sections.
+    pub cluster_lod_table: Vec<ClusterLodTableEntry>,
+
+    /// Parallel to `cluster_geometry_table`. Overlays and decals are drawn with the cluster that
+    /// contains the face they were projected onto.
+    pub overlay_table: Vec<OverlayTableEntry>,
+    pub overlay_byte_code: Vec<u32>,
+    pub overlay_display_lists: Vec<u8>,
+
+    /// Textures whose frames are packed back to back in `texture_data`. References always point
+    /// at the first frame.
+    pub animated_texture_table: Vec<AnimatedTextureTableEntry>,
+
+    /// Every texture reference in the display lists, to be pointed at its image by
+    /// [`MapData::relocate`] at load time.
+    pub relocation_table: Vec<RelocationTableEntry>,
+
+    /// Parallel to `cluster_geometry_table`. Static props are baked into world space and drawn
+    /// with the cluster that contains their origin.
+    pub prop_table: Vec<PropTableEntry>,
+    pub prop_byte_code: Vec<u32>,
+    pub prop_display_lists: Vec<u8>,
+
+    /// Detail sprites, as GX point sprites. Each type is one cell of the map's detail material.
+    pub detail_sprite_type_table: Vec<DetailSpriteTypeTableEntry>,
+    /// Parallel to `cluster_geometry_table`.
+    pub detail_sprite_cluster_table: Vec<DetailSpriteClusterTableEntry>,
+    /// Sorted by cluster, then type.
+    pub detail_sprites: Vec<DetailSpriteEntry>,
+
+    /// At most one entry of each kind. A missing kind is unfogged.
+    pub fog_table: Vec<FogTableEntry>,
+
+    /// Sorted by texture ID. One entry per texture flagged `FLAG_SHARED`.
+    pub shared_texture_table: Vec<SharedTextureTableEntry>,
 }
 
 #[cfg(feature = "std")]
 impl<W: Seek + Write> WriteTo<W> for OwnedMapData {
     fn write_to(&self, w: &mut W) -> io::Result<()> {
-        let mut w = RelocationWriter::new(w);
+        let mut w = SectionBuilder::new(RelocationWriter::new(w));
 
         // Write the header, which is a list of offsets to the sections.
 
@@ -110,7 +161,10 @@ impl<W: Seek + Write> WriteTo<W> for OwnedMapData {
             symbol: &'static str,
             slice: &[T],
         ) -> io::Result<()> {
-            w.write_pointer(PointerFormat::BigEndianU32, Cow::Borrowed(symbol))?;
+            w.write_pointer(
+                PointerFormat::BigEndianU32,
+                Cow::Owned(format!("{}_start", symbol)),
+            )?;
             w.write_u32::<BigEndian>(u32::try_from(slice.len()).unwrap())?;
             Ok(())
         }
@@ -127,12 +181,13 @@ impl<W: Seek + Write> WriteTo<W> for OwnedMapData {
         write_slice_header!(cluster_geometry_table);
         write_slice_header!(cluster_geometry_byte_code);
         write_slice_header!(cluster_geometry_display_lists);
-        write_slice_header!(cluster_geometry_references);
         write_slice_header!(bsp_nodes);
         write_slice_header!(bsp_leaves);
         write_slice_header!(visibility);
         write_slice_header!(texture_table);
         write_slice_header!(texture_data);
+        write_slice_header!(texture_name_table);
+        write_slice_header!(name_data);
         write_slice_header!(lightmap_cluster_table);
         write_slice_header!(lightmap_displacement_table);
         write_slice_header!(lightmap_patch_table);
@@ -143,35 +198,52 @@ impl<W: Seek + Write> WriteTo<W> for OwnedMapData {
         write_slice_header!(displacement_table);
         write_slice_header!(displacement_byte_code);
         write_slice_header!(displacement_display_lists);
-        write_slice_header!(displacement_references);
+        write_slice_header!(level_transition_table);
+        write_slice_header!(landmark_table);
+        write_slice_header!(vertex_format_table);
+        write_slice_header!(cluster_lod_table);
+        write_slice_header!(overlay_table);
+        write_slice_header!(overlay_byte_code);
+        write_slice_header!(overlay_display_lists);
+        write_slice_header!(animated_texture_table);
+        write_slice_header!(relocation_table);
+        write_slice_header!(prop_table);
+        write_slice_header!(prop_byte_code);
+        write_slice_header!(prop_display_lists);
+        write_slice_header!(detail_sprite_type_table);
+        write_slice_header!(detail_sprite_cluster_table);
+        write_slice_header!(detail_sprites);
+        write_slice_header!(fog_table);
+        write_slice_header!(shared_texture_table);
+
+        // Follow the offsets with a checksum of each section, in the same order.
+        let sections = self.serialize_sections()?;
+        assert_eq!(sections.len(), SECTION_COUNT);
+        for (_, data) in &sections {
+            w.write_u32::<BigEndian>(crc32fast::hash(data))?;
+        }
 
         // Write each section.
 
         fn write_slice_data<T: WriteTo<W>, W: Seek + Write>(
-            w: &mut RelocationWriter<W>,
+            w: &mut SectionBuilder<W>,
             symbol: &'static str,
             slice: &[T],
         ) -> io::Result<()> {
-            while w.stream_position()? % std::mem::align_of::<T>() as u64 != 0 {
-                w.write_u8(0)?;
-            }
-            w.define_symbol_here(Cow::Borrowed(symbol))?;
-            slice.write_to(&mut *w)?;
-            Ok(())
+            w.section(
+                Cow::Borrowed(symbol),
+                std::mem::align_of::<T>() as u64,
+                |w| slice.write_to(&mut **w),
+            )
         }
 
         fn write_slice_bytes<W: Seek + Write>(
-            w: &mut RelocationWriter<W>,
+            w: &mut SectionBuilder<W>,
             symbol: &'static str,
             slice: &[u8],
             align: u64,
         ) -> io::Result<()> {
-            while w.stream_position()? % align != 0 {
-                w.write_u8(0)?;
-            }
-            w.define_symbol_here(Cow::Borrowed(symbol))?;
-            w.write_all(slice)?;
-            Ok(())
+            w.section(Cow::Borrowed(symbol), align, |w| w.write_all(slice))
         }
 
         macro_rules! write_slice_data {
@@ -195,12 +267,13 @@ impl<W: Seek + Write> WriteTo<W> for OwnedMapData {
         write_slice_data!(cluster_geometry_table);
         write_slice_data!(cluster_geometry_byte_code);
         write_slice_bytes!(cluster_geometry_display_lists, 32);
-        write_slice_data!(cluster_geometry_references);
         write_slice_data!(bsp_nodes);
         write_slice_data!(bsp_leaves);
         write_slice_bytes!(visibility);
         write_slice_data!(texture_table);
         write_slice_bytes!(texture_data, 32);
+        write_slice_data!(texture_name_table);
+        write_slice_bytes!(name_data);
         write_slice_data!(lightmap_cluster_table);
         write_slice_data!(lightmap_displacement_table);
         write_slice_data!(lightmap_patch_table);
@@ -211,13 +284,169 @@ impl<W: Seek + Write> WriteTo<W> for OwnedMapData {
         write_slice_data!(displacement_table);
         write_slice_data!(displacement_byte_code);
         write_slice_bytes!(displacement_display_lists, 32);
-        write_slice_data!(displacement_references);
+        write_slice_data!(level_transition_table);
+        write_slice_data!(landmark_table);
+        write_slice_data!(vertex_format_table);
+        write_slice_data!(cluster_lod_table);
+        write_slice_data!(overlay_table);
+        write_slice_data!(overlay_byte_code);
+        write_slice_bytes!(overlay_display_lists, 32);
+        write_slice_data!(animated_texture_table);
+        write_slice_data!(relocation_table);
+        write_slice_data!(prop_table);
+        write_slice_data!(prop_byte_code);
+        write_slice_bytes!(prop_display_lists, 32);
+        write_slice_data!(detail_sprite_type_table);
+        write_slice_data!(detail_sprite_cluster_table);
+        write_slice_data!(detail_sprites);
+        write_slice_data!(fog_table);
+        write_slice_data!(shared_texture_table);
 
         w.finish()?;
         Ok(())
     }
 }
 
+#[cfg(feature = "std")]
+impl OwnedMapData {
+    /// Serializes each section on its own, in the order they appear in the packed file. The bytes
+    /// match what `write_to` emits for that section, excluding alignment padding.
+    pub fn serialize_sections(&self) -> io::Result<Vec<(&'static str, Vec<u8>)>> {
+        let mut sections = Vec::new();
+
+        macro_rules! section_data {
+            ($name:ident) => {
+                let mut w = Cursor::new(Vec::new());
+                self.$name.write_to(&mut w)?;
+                sections.push((stringify!($name), w.into_inner()));
+            };
+        }
+
+        macro_rules! section_bytes {
+            ($name:ident) => {
+                sections.push((stringify!($name), self.$name.clone()));
+            };
+        }
+
+        section_bytes!(position_data);
+        section_bytes!(normal_data);
+        section_bytes!(texture_coord_data);
+        section_data!(cluster_geometry_table);
+        section_data!(cluster_geometry_byte_code);
+        section_bytes!(cluster_geometry_display_lists);
+        section_data!(bsp_nodes);
+        section_data!(bsp_leaves);
+        section_bytes!(visibility);
+        section_data!(texture_table);
+        section_bytes!(texture_data);
+        section_data!(texture_name_table);
+        section_bytes!(name_data);
+        section_data!(lightmap_cluster_table);
+        section_data!(lightmap_displacement_table);
+        section_data!(lightmap_patch_table);
+        section_bytes!(lightmap_data);
+        section_bytes!(displacement_position_data);
+        section_bytes!(displacement_vertex_color_data);
+        section_bytes!(displacement_texture_coordinate_data);
+        section_data!(displacement_table);
+        section_data!(displacement_byte_code);
+        section_bytes!(displacement_display_lists);
+        section_data!(level_transition_table);
+        section_data!(landmark_table);
+        section_data!(vertex_format_table);
+        section_data!(cluster_lod_table);
+        section_data!(overlay_table);
+        section_data!(overlay_byte_code);
+        section_bytes!(overlay_display_lists);
+        section_data!(animated_texture_table);
+        section_data!(relocation_table);
+        section_data!(prop_table);
+        section_data!(prop_byte_code);
+        section_bytes!(prop_display_lists);
+        section_data!(detail_sprite_type_table);
+        section_data!(detail_sprite_cluster_table);
+        section_data!(detail_sprites);
+        section_data!(fog_table);
+        section_data!(shared_texture_table);
+
+        Ok(sections)
+    }
+}
+
+/// The number of sections in a map data file.
+pub const SECTION_COUNT: usize = 40;
+
+/// The name and element size of each section, in header order.
+const SECTION_LAYOUT: [(&str, usize); SECTION_COUNT] = [
+    ("position_data", size_of::<u8>()),
+    ("normal_data", size_of::<u8>()),
+    ("texture_coord_data", size_of::<u8>()),
+    (
+        "cluster_geometry_table",
+        size_of::<ClusterGeometryTableEntry>(),
+    ),
+    ("cluster_geometry_byte_code", size_of::<u32>()),
+    ("cluster_geometry_display_lists", size_of::<u8>()),
+    ("bsp_nodes", size_of::<BspNode>()),
+    ("bsp_leaves", size_of::<BspLeaf>()),
+    ("visibility", size_of::<u8>()),
+    ("texture_table", size_of::<TextureTableEntry>()),
+    ("texture_data", size_of::<u8>()),
+    ("texture_name_table", size_of::<NameTableEntry>()),
+    ("name_data", size_of::<u8>()),
+    (
+        "lightmap_cluster_table",
+        size_of::<ClusterLightmapTableEntry>(),
+    ),
+    (
+        "lightmap_displacement_table",
+        size_of::<DisplacementLightmapTableEntry>(),
+    ),
+    ("lightmap_patch_table", size_of::<LightmapPatchTableEntry>()),
+    ("lightmap_data", size_of::<u8>()),
+    ("displacement_position_data", size_of::<u8>()),
+    ("displacement_vertex_color_data", size_of::<u8>()),
+    ("displacement_texture_coordinate_data", size_of::<u8>()),
+    ("displacement_table", size_of::<DisplacementTableEntry>()),
+    ("displacement_byte_code", size_of::<u32>()),
+    ("displacement_display_lists", size_of::<u8>()),
+    (
+        "level_transition_table",
+        size_of::<LevelTransitionTableEntry>(),
+    ),
+    ("landmark_table", size_of::<LandmarkTableEntry>()),
+    ("vertex_format_table", size_of::<VertexFormatDescriptor>()),
+    ("cluster_lod_table", size_of::<ClusterLodTableEntry>()),
+    ("overlay_table", size_of::<OverlayTableEntry>()),
+    ("overlay_byte_code", size_of::<u32>()),
+    ("overlay_display_lists", size_of::<u8>()),
+    (
+        "animated_texture_table",
+        size_of::<AnimatedTextureTableEntry>(),
+    ),
+    ("relocation_table", size_of::<RelocationTableEntry>()),
+    ("prop_table", size_of::<PropTableEntry>()),
+    ("prop_byte_code", size_of::<u32>()),
+    ("prop_display_lists", size_of::<u8>()),
+    (
+        "detail_sprite_type_table",
+        size_of::<DetailSpriteTypeTableEntry>(),
+    ),
+    (
+        "detail_sprite_cluster_table",
+        size_of::<DetailSpriteClusterTableEntry>(),
+    ),
+    ("detail_sprites", size_of::<DetailSpriteEntry>()),
+    ("fog_table", size_of::<FogTableEntry>()),
+    ("shared_texture_table", size_of::<SharedTextureTableEntry>()),
+];
+
+// The header is read in place, so it can't have padding. Its offsets and lengths are eight bytes
+// on 64-bit hosts, so this fails there if the checksums after them don't come in pairs.
+const _: () = assert!(
+    size_of::<PackedMapData>() == SECTION_COUNT * (2 * size_of::<usize>() + size_of::<u32>())
+);
+
 #[derive(Clone, Copy, Pod, Zeroable)]
 #[repr(C)]
 struct PackedMapData {
@@ -233,8 +462,6 @@ struct PackedMapData {
     cluster_geometry_byte_code_len: usize,
     cluster_geometry_display_lists_offset: usize,
     cluster_geometry_display_lists_len: usize,
-    cluster_geometry_references_offset: usize,
-    cluster_geometry_references_len: usize,
 
     bsp_nodes_offset: usize,
     bsp_nodes_len: usize,
@@ -247,6 +474,10 @@ struct PackedMapData {
     texture_table_len: usize,
     texture_data_offset: usize,
     texture_data_len: usize,
+    texture_name_table_offset: usize,
+    texture_name_table_len: usize,
+    name_data_offset: usize,
+    name_data_len: usize,
 
     lightmap_cluster_table_offset: usize,
     lightmap_cluster_table_len: usize,
@@ -269,8 +500,71 @@ struct PackedMapData {
     displacement_byte_code_len: usize,
     displacement_display_lists_offset: usize,
     displacement_display_lists_len: usize,
-    displacement_references_offset: usize,
-    displacement_references_len: usize,
+
+    level_transition_table_offset: usize,
+    level_transition_table_len: usize,
+    landmark_table_offset: usize,
+    landmark_table_len: usize,
+    vertex_format_table_offset: usize,
+    vertex_format_table_len: usize,
+    cluster_lod_table_offset: usize,
+    cluster_lod_table_len: usize,
+    overlay_table_offset: usize,
+    overlay_table_len: usize,
+    overlay_byte_code_offset: usize,
+    overlay_byte_code_len: usize,
+    overlay_display_lists_offset: usize,
+    overlay_display_lists_len: usize,
+    animated_texture_table_offset: usize,
+    animated_texture_table_len: usize,
+    relocation_table_offset: usize,
+    relocation_table_len: usize,
+    prop_table_offset: usize,
+    prop_table_len: usize,
+    prop_byte_code_offset: usize,
+    prop_byte_code_len: usize,
+    prop_display_lists_offset: usize,
+    prop_display_lists_len: usize,
+    detail_sprite_type_table_offset: usize,
+    detail_sprite_type_table_len: usize,
+    detail_sprite_cluster_table_offset: usize,
+    detail_sprite_cluster_table_len: usize,
+    detail_sprites_offset: usize,
+    detail_sprites_len: usize,
+    fog_table_offset: usize,
+    fog_table_len: usize,
+    shared_texture_table_offset: usize,
+    shared_texture_table_len: usize,
+
+    /// The CRC-32 of each section's contents, in the order the offsets above are listed.
+    section_crc32s: [u32; SECTION_COUNT],
+}
+
+/// Why [`MapData::verify`] rejected a map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MapDataError {
+    /// The data is too short to hold the header.
+    Truncated,
+    /// A section extends past the end of the data.
+    SectionOutOfBounds { section: &'static str },
+    /// A section's contents don't match the checksum recorded in the header.
+    ChecksumMismatch {
+        section: &'static str,
+        expected: u32,
+        actual: u32,
+    },
+    /// A relocation names an unknown section or texture, or falls outside its display lists.
+    BadRelocation { index: usize },
+    /// A relocation targets a texture that isn't 32-byte aligned, which an image address can't
+    /// encode.
+    MisalignedTexture { texture_id: u16 },
+    /// A relocation targets a shared texture that wasn't provided.
+    MissingSharedTexture { texture_id: u16 },
+    /// An animated texture's frame isn't in the map's texture data.
+    BadTextureFrame { texture_id: u16 },
+    /// An animated texture names an unknown or shared texture, has no frames, or has frames past
+    /// the end of the texture data.
+    BadAnimatedTexture { index: usize },
 }
 
 pub struct MapData<Data> {
@@ -291,6 +585,75 @@ impl<Data: Deref<Target = [u8]>> MapData<Data> {
         unsafe { &*(self.data.as_ptr() as *const PackedMapData) }
     }
 
+    /// Checks every section against the checksums in the header, then checks that every animated
+    /// texture's frames are in the texture data. Call this before reading or patching anything,
+    /// since corrupt display lists tend to hang the GPU rather than fail cleanly.
+    pub fn verify(&self) -> Result<(), MapDataError> {
+        if self.data.len() < size_of::<PackedMapData>() {
+            return Err(MapDataError::Truncated);
+        }
+        let packed = self.packed();
+        for ((section, offset, size), &expected) in self
+            .section_extents()
+            .into_iter()
+            .zip(packed.section_crc32s.iter())
+        {
+            let data = offset
+                .checked_add(size)
+                .and_then(|end| self.data.get(offset..end))
+                .ok_or(MapDataError::SectionOutOfBounds { section })?;
+            let actual = crc32fast::hash(data);
+            if actual != expected {
+                return Err(MapDataError::ChecksumMismatch {
+                    section,
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        let texture_table = self.texture_table();
+        let texture_data_len = packed.texture_data_len;
+        for (index, entry) in self.animated_texture_table().iter().enumerate() {
+            let frames_fit = |texture: &TextureTableEntry| {
+                let Some(frame_len) = texture.end_offset.checked_sub(texture.start_offset) else {
+                    return false;
+                };
+                let end = (frame_len as usize)
+                    .checked_mul(entry.frame_count as usize)
+                    .and_then(|len| len.checked_add(texture.start_offset as usize));
+                matches!(end, Some(end) if end <= texture_data_len)
+            };
+            match texture_table.get(entry.texture_id as usize) {
+                Some(texture)
+                    if entry.frame_count != 0
+                        && texture.flags & TextureTableEntry::FLAG_SHARED == 0
+                        && frames_fit(texture) => {}
+                _ => return Err(MapDataError::BadAnimatedTexture { index }),
+            }
+        }
+        Ok(())
+    }
+
+    /// The name, offset, and size in bytes of each section, in header order.
+    fn section_extents(&self) -> [(&'static str, usize, usize); SECTION_COUNT] {
+        // The header starts with an offset and a length for each section.
+        let header = bytemuck::bytes_of(self.packed());
+        let words: &[usize] =
+            bytemuck::cast_slice(&header[..2 * SECTION_COUNT * size_of::<usize>()]);
+        let mut extents = [("", 0, 0); SECTION_COUNT];
+        for (index, (extent, &(name, element_size))) in
+            extents.iter_mut().zip(SECTION_LAYOUT.iter()).enumerate()
+        {
+            *extent = (
+                name,
+                words[2 * index],
+                words[2 * index + 1].saturating_mul(element_size),
+            );
+        }
+        extents
+    }
+
     unsafe fn cast_slice<T: Pod>(&self, offset: usize, len: usize) -> &[T] {
         let data = (self.data.as_ptr() as usize + offset) as *const T;
         slice::from_raw_parts(data, len)
@@ -346,26 +709,6 @@ impl<Data: Deref<Target = [u8]>> MapData<Data> {
         }
     }
 
-    pub fn cluster_geometry_references(&self) -> &[ClusterGeometryReferencesEntry] {
-        let packed = self.packed();
-        unsafe {
-            self.cast_slice(
-                packed.cluster_geometry_references_offset,
-                packed.cluster_geometry_references_len,
-            )
-        }
-    }
-
-    pub fn displacement_references(&self) -> &[DisplacementReferencesEntry] {
-        let packed = self.packed();
-        unsafe {
-            self.cast_slice(
-                packed.displacement_references_offset,
-                packed.displacement_references_len,
-            )
-        }
-    }
-
     pub fn bsp_nodes(&self) -> &[BspNode] {
         let packed = self.packed();
         unsafe { self.cast_slice(packed.bsp_nodes_offset, packed.bsp_nodes_len) }
@@ -407,6 +750,35 @@ impl<Data: Deref<Target = [u8]>> MapData<Data> {
         unsafe { self.cast_slice(packed.texture_data_offset, packed.texture_data_len) }
     }
 
+    pub fn texture_name_table(&self) -> &[NameTableEntry] {
+        let packed = self.packed();
+        unsafe {
+            self.cast_slice(
+                packed.texture_name_table_offset,
+                packed.texture_name_table_len,
+            )
+        }
+    }
+
+    pub fn name_data(&self) -> &[u8] {
+        let packed = self.packed();
+        unsafe { self.cast_slice(packed.name_data_offset, packed.name_data_len) }
+    }
+
+    /// Looks up the name a texture was packed from, for use in diagnostics. Returns `None` if the
+    /// map was packed without names or the entry is malformed.
+    pub fn texture_name(&self, texture_id: u16) -> Option<&str> {
+        self.name(self.texture_name_table().get(texture_id as usize)?)
+    }
+
+    /// Resolves a name table entry against the name data section. Returns `None` if the entry is
+    /// malformed.
+    pub fn name(&self, entry: &NameTableEntry) -> Option<&str> {
+        let start = entry.offset as usize;
+        let end = start.checked_add(entry.len as usize)?;
+        core::str::from_utf8(self.name_data().get(start..end)?).ok()
+    }
+
     pub fn lightmap_cluster_table(&self) -> &[ClusterLightmapTableEntry] {
         let packed = self.packed();
         unsafe {
@@ -501,6 +873,381 @@ impl<Data: Deref<Target = [u8]>> MapData<Data> {
             )
         }
     }
+
+    pub fn level_transition_table(&self) -> &[LevelTransitionTableEntry] {
+        let packed = self.packed();
+        unsafe {
+            self.cast_slice(
+                packed.level_transition_table_offset,
+                packed.level_transition_table_len,
+            )
+        }
+    }
+
+    pub fn landmark_table(&self) -> &[LandmarkTableEntry] {
+        let packed = self.packed();
+        unsafe { self.cast_slice(packed.landmark_table_offset, packed.landmark_table_len) }
+    }
+
+    pub fn find_landmark(&self, name: &str) -> Option<&LandmarkTableEntry> {
+        self.landmark_table()
+            .iter()
+            .find(|landmark| self.name(&landmark.name) == Some(name))
+    }
+
+    pub fn vertex_format_table(&self) -> &[VertexFormatDescriptor] {
+        let packed = self.packed();
+        unsafe {
+            self.cast_slice(
+                packed.vertex_format_table_offset,
+                packed.vertex_format_table_len,
+            )
+        }
+    }
+
+    pub fn vertex_format_descriptor(
+        &self,
+        vertex_format: VertexFormat,
+    ) -> Option<&VertexFormatDescriptor> {
+        self.vertex_format_table()
+            .iter()
+            .find(|descriptor| descriptor.vertex_format == vertex_format as u8)
+    }
+
+    pub fn cluster_lod_table(&self) -> &[ClusterLodTableEntry] {
+        let packed = self.packed();
+        unsafe {
+            self.cast_slice(
+                packed.cluster_lod_table_offset,
+                packed.cluster_lod_table_len,
+            )
+        }
+    }
+
+    pub fn overlay_table(&self) -> &[OverlayTableEntry] {
+        let packed = self.packed();
+        unsafe { self.cast_slice(packed.overlay_table_offset, packed.overlay_table_len) }
+    }
+
+    pub fn overlay_byte_code(&self) -> &[u32] {
+        let packed = self.packed();
+        unsafe {
+            self.cast_slice(
+                packed.overlay_byte_code_offset,
+                packed.overlay_byte_code_len,
+            )
+        }
+    }
+
+    pub fn overlay_display_lists(&self) -> &[u8] {
+        let packed = self.packed();
+        unsafe {
+            self.cast_slice(
+                packed.overlay_display_lists_offset,
+                packed.overlay_display_lists_len,
+            )
+        }
+    }
+
+    pub fn animated_texture_table(&self) -> &[AnimatedTextureTableEntry] {
+        let packed = self.packed();
+        unsafe {
+            self.cast_slice(
+                packed.animated_texture_table_offset,
+                packed.animated_texture_table_len,
+            )
+        }
+    }
+
+    pub fn relocation_table(&self) -> &[RelocationTableEntry] {
+        let packed = self.packed();
+        unsafe { self.cast_slice(packed.relocation_table_offset, packed.relocation_table_len) }
+    }
+
+    pub fn prop_table(&self) -> &[PropTableEntry] {
+        let packed = self.packed();
+        unsafe { self.cast_slice(packed.prop_table_offset, packed.prop_table_len) }
+    }
+
+    pub fn prop_byte_code(&self) -> &[u32] {
+        let packed = self.packed();
+        unsafe { self.cast_slice(packed.prop_byte_code_offset, packed.prop_byte_code_len) }
+    }
+
+    pub fn prop_display_lists(&self) -> &[u8] {
+        let packed = self.packed();
+        unsafe {
+            self.cast_slice(
+                packed.prop_display_lists_offset,
+                packed.prop_display_lists_len,
+            )
+        }
+    }
+
+    pub fn detail_sprite_type_table(&self) -> &[DetailSpriteTypeTableEntry] {
+        let packed = self.packed();
+        unsafe {
+            self.cast_slice(
+                packed.detail_sprite_type_table_offset,
+                packed.detail_sprite_type_table_len,
+            )
+        }
+    }
+
+    pub fn detail_sprite_cluster_table(&self) -> &[DetailSpriteClusterTableEntry] {
+        let packed = self.packed();
+        unsafe {
+            self.cast_slice(
+                packed.detail_sprite_cluster_table_offset,
+                packed.detail_sprite_cluster_table_len,
+            )
+        }
+    }
+
+    pub fn detail_sprites(&self) -> &[DetailSpriteEntry] {
+        let packed = self.packed();
+        unsafe { self.cast_slice(packed.detail_sprites_offset, packed.detail_sprites_len) }
+    }
+
+    pub fn fog_table(&self) -> &[FogTableEntry] {
+        let packed = self.packed();
+        unsafe { self.cast_slice(packed.fog_table_offset, packed.fog_table_len) }
+    }
+
+    /// Returns the fog of one of the `FogTableEntry` kinds, if the map has any.
+    pub fn fog(&self, kind: u8) -> Option<&FogTableEntry> {
+        self.fog_table().iter().find(|entry| entry.kind == kind)
+    }
+
+    pub fn shared_texture_table(&self) -> &[SharedTextureTableEntry] {
+        let packed = self.packed();
+        unsafe {
+            self.cast_slice(
+                packed.shared_texture_table_offset,
+                packed.shared_texture_table_len,
+            )
+        }
+    }
+
+    /// Returns the shared texture entry for a texture flagged `FLAG_SHARED`.
+    pub fn shared_texture(&self, texture_id: u16) -> Option<&SharedTextureTableEntry> {
+        let table = self.shared_texture_table();
+        table
+            .binary_search_by_key(&(texture_id as u32), |entry| entry.texture_id)
+            .ok()
+            .map(|index| &table[index])
+    }
+
+    /// Finds where the display lists reference a texture, so that
+    /// [`MapData::relocate_texture_frame`] doesn't have to search the relocation table every time
+    /// the texture changes frames.
+    pub fn texture_relocation_sites(
+        &self,
+        texture_id: u16,
+    ) -> Result<TextureRelocationSites, MapDataError> {
+        let packed = self.packed();
+        let mut positions = Vec::new();
+        for (index, entry) in self.relocation_table().iter().enumerate() {
+            if entry.texture_id == texture_id {
+                positions.push(relocation_site(packed, index, entry)?);
+            }
+        }
+        Ok(TextureRelocationSites {
+            texture_id,
+            positions,
+        })
+    }
+}
+
+impl<Data: DerefMut<Target = [u8]>> MapData<Data> {
+    /// Points every texture reference in the display lists at its image, as it sits in memory
+    /// now. Call this once, after [`MapData::verify`] (patching changes the checksummed bytes) and
+    /// before handing any display lists to the GPU. The caller is responsible for flushing the
+    /// display lists out of the CPU cache afterward.
+    ///
+    /// Fails on maps with shared textures. See [`MapData::relocate_with_shared_textures`].
+    pub fn relocate(&mut self) -> Result<(), MapDataError> {
+        self.relocate_with_shared_textures(|_| None)
+    }
+
+    /// Like [`MapData::relocate`], but textures flagged `FLAG_SHARED` are looked up with
+    /// `shared_texture_address`, which returns the address of the texture's image if it's loaded.
+    pub fn relocate_with_shared_textures(
+        &mut self,
+        shared_texture_address: impl Fn(&SharedTextureTableEntry) -> Option<usize>,
+    ) -> Result<(), MapDataError> {
+        let packed = *self.packed();
+        let texture_data_address = self.data.as_ptr() as usize + packed.texture_data_offset;
+        let texture_table = self.texture_table();
+        let mut patches = Vec::with_capacity(packed.relocation_table_len);
+        for (index, entry) in self.relocation_table().iter().enumerate() {
+            let position = relocation_site(&packed, index, entry)?;
+            let texture = texture_table
+                .get(entry.texture_id as usize)
+                .ok_or(MapDataError::BadRelocation { index })?;
+            let image_address = if texture.flags & TextureTableEntry::FLAG_SHARED != 0 {
+                self.shared_texture(entry.texture_id)
+                    .and_then(&shared_texture_address)
+                    .ok_or(MapDataError::MissingSharedTexture {
+                        texture_id: entry.texture_id,
+                    })?
+            } else {
+                texture_data_address + texture.start_offset as usize
+            };
+            if image_address & 31 != 0 {
+                return Err(MapDataError::MisalignedTexture {
+                    texture_id: entry.texture_id,
+                });
+            }
+            patches.push((position, image_address));
+        }
+
+        // Everything checked out, so nothing below can leave the display lists half patched.
+        for (position, image_address) in patches {
+            self.patch_image_address(position, image_address);
+        }
+        Ok(())
+    }
+
+    /// Points every reference to an animated texture at one of its frames, which starts
+    /// `frame_start_offset` bytes into the texture data (see
+    /// [`AnimatedTextureTableEntry::frame_start_offset`]). `sites` must come from
+    /// [`MapData::texture_relocation_sites`] on this map. `patched` is given each rewritten
+    /// register value so the caller can flush it out of the CPU cache.
+    ///
+    /// Nothing is patched unless the whole frame is in the map's texture data.
+    pub fn relocate_texture_frame(
+        &mut self,
+        sites: &TextureRelocationSites,
+        frame_start_offset: u32,
+        mut patched: impl FnMut(&[u8]),
+    ) -> Result<(), MapDataError> {
+        let texture_id = sites.texture_id;
+        let packed = *self.packed();
+        let frame_start_offset = frame_start_offset as usize;
+        let frame_fits = |texture: &TextureTableEntry| {
+            let Some(frame_len) = texture.end_offset.checked_sub(texture.start_offset) else {
+                return false;
+            };
+            matches!(
+                frame_start_offset.checked_add(frame_len as usize),
+                Some(end) if end <= packed.texture_data_len
+            )
+        };
+        match self.texture_table().get(texture_id as usize) {
+            Some(texture)
+                if texture.flags & TextureTableEntry::FLAG_SHARED == 0 && frame_fits(texture) => {}
+            _ => return Err(MapDataError::BadTextureFrame { texture_id }),
+        }
+        let image_address =
+            self.data.as_ptr() as usize + packed.texture_data_offset + frame_start_offset;
+        if image_address & 31 != 0 {
+            return Err(MapDataError::MisalignedTexture { texture_id });
+        }
+
+        for &position in &sites.positions {
+            self.patch_image_address(position, image_address);
+            patched(&self.data[position..position + 4]);
+        }
+        Ok(())
+    }
+
+    /// Replaces the image address in the BP register value at `position`, keeping the register
+    /// ID in its top byte.
+    fn patch_image_address(&mut self, position: usize, image_address: usize) {
+        let bytes = &mut self.data[position..position + 4];
+        let value = u32::from_be_bytes(bytes.try_into().unwrap());
+        let image_reg_value = ((image_address as u32) >> 5) & 0x00ffffff;
+        bytes.copy_from_slice(&(value & 0xff000000 | image_reg_value).to_be_bytes());
+    }
+}
+
+/// A packed map as written to disk, read on a host instead of in place on the console.
+///
+/// The header is decoded from big-endian, but the sections are left as the packer wrote them, so
+/// their contents are big-endian too.
+pub struct PackedMapFile<'a> {
+    sections: [&'a [u8]; SECTION_COUNT],
+}
+
+impl<'a> PackedMapFile<'a> {
+    /// An offset and a length for each section, then a checksum for each.
+    const HEADER_SIZE: usize = 12 * SECTION_COUNT;
+
+    /// Finds every section and checks it against its checksum.
+    pub fn new(data: &'a [u8]) -> Result<Self, MapDataError> {
+        if data.len() < Self::HEADER_SIZE {
+            return Err(MapDataError::Truncated);
+        }
+        let word =
+            |index: usize| u32::from_be_bytes(data[4 * index..4 * index + 4].try_into().unwrap());
+
+        let mut sections = [&data[..0]; SECTION_COUNT];
+        for (index, &(section, element_size)) in SECTION_LAYOUT.iter().enumerate() {
+            let offset = word(2 * index) as usize;
+            let size = (word(2 * index + 1) as usize).saturating_mul(element_size);
+            let bytes = offset
+                .checked_add(size)
+                .and_then(|end| data.get(offset..end))
+                .ok_or(MapDataError::SectionOutOfBounds { section })?;
+            let expected = word(2 * SECTION_COUNT + index);
+            let actual = crc32fast::hash(bytes);
+            if actual != expected {
+                return Err(MapDataError::ChecksumMismatch {
+                    section,
+                    expected,
+                    actual,
+                });
+            }
+            sections[index] = bytes;
+        }
+        Ok(Self { sections })
+    }
+
+    /// The contents of a section, named as in [`OwnedMapData`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if there's no section by that name.
+    pub fn section(&self, name: &str) -> &'a [u8] {
+        match SECTION_LAYOUT.iter().position(|&(n, _)| n == name) {
+            Some(index) => self.sections[index],
+            None => panic!("no section named {:?}", name),
+        }
+    }
+}
+
+/// Finds where a relocation's register value sits in the map data, checking that it's inside its
+/// display list section.
+fn relocation_site(
+    packed: &PackedMapData,
+    index: usize,
+    entry: &RelocationTableEntry,
+) -> Result<usize, MapDataError> {
+    let (section_offset, section_len) = match entry.display_lists {
+        RelocationTableEntry::CLUSTER_GEOMETRY => (
+            packed.cluster_geometry_display_lists_offset,
+            packed.cluster_geometry_display_lists_len,
+        ),
+        RelocationTableEntry::DISPLACEMENT => (
+            packed.displacement_display_lists_offset,
+            packed.displacement_display_lists_len,
+        ),
+        RelocationTableEntry::OVERLAY => (
+            packed.overlay_display_lists_offset,
+            packed.overlay_display_lists_len,
+        ),
+        RelocationTableEntry::PROP => (
+            packed.prop_display_lists_offset,
+            packed.prop_display_lists_len,
+        ),
+        _ => return Err(MapDataError::BadRelocation { index }),
+    };
+    let site = entry.display_list_offset as usize;
+    if !matches!(site.checked_add(4), Some(end) if end <= section_len) {
+        return Err(MapDataError::BadRelocation { index });
+    }
+    Ok(section_offset + site)
 }
 
 #[derive(Clone, Copy, Pod, Zeroable)]
@@ -533,39 +1280,35 @@ impl<W: Seek + Write> WriteTo<W> for ClusterGeometryTableEntry {
     }
 }
 
+/// Simplified geometry for drawing a cluster from far away, along with the bounds of its full
+/// geometry for choosing when to use it.
 #[derive(Clone, Copy, Pod, Zeroable)]
 #[repr(C)]
-pub struct ClusterGeometryReferencesEntry {
-    pub display_list_offset: u32,
-    pub texture_id: u16,
-    pub _padding: u16,
+pub struct ClusterLodTableEntry {
+    pub mins: [f32; 3],
+    pub maxs: [f32; 3],
+    pub geometry: ClusterGeometryTableEntry,
 }
 
-#[cfg(feature = "std")]
-impl<W: Seek + Write> WriteTo<W> for ClusterGeometryReferencesEntry {
-    fn write_to(&self, w: &mut W) -> io::Result<()> {
-        w.write_u32::<BigEndian>(self.display_list_offset)?;
-        w.write_u16::<BigEndian>(self.texture_id)?;
-        w.write_u16::<BigEndian>(self._padding)?;
-        Ok(())
+impl ClusterLodTableEntry {
+    /// Returns the squared distance from `pos` to the nearest point of the bounds.
+    pub fn distance_squared(&self, pos: [f32; 3]) -> f32 {
+        let mut sum = 0.0;
+        for ((min, max), x) in self.mins.iter().zip(&self.maxs).zip(pos) {
+            let d = (min - x).max(x - max).max(0.0);
+            sum += d * d;
+        }
+        sum
     }
 }
 
-#[derive(Clone, Copy, Pod, Zeroable)]
-#[repr(C)]
-pub struct DisplacementReferencesEntry {
-    pub display_list_offset: u32,
-    pub texture_id: u16,
-    pub _padding: u16,
-}
-
 #[cfg(feature = "std")]
-impl<W: Seek + Write> WriteTo<W> for DisplacementReferencesEntry {
+impl<W: Seek + Write> WriteTo<W> for ClusterLodTableEntry {
     fn write_to(&self, w: &mut W) -> io::Result<()> {
-        w.write_u32::<BigEndian>(self.display_list_offset)?;
-        w.write_u16::<BigEndian>(self.texture_id)?;
-        w.write_u16::<BigEndian>(self._padding)?;
-        Ok(())
+        for &x in self.mins.iter().chain(self.maxs.iter()) {
+            w.write_f32::<BigEndian>(x)?;
+        }
+        self.geometry.write_to(w)
     }
 }
 
@@ -620,6 +1363,9 @@ pub struct TextureTableEntry {
 impl TextureTableEntry {
     pub const FLAG_CLAMP_S: u8 = 0x01;
     pub const FLAG_CLAMP_T: u8 = 0x02;
+    /// The image isn't in `texture_data`. It's stored once for all maps, under the hash in its
+    /// `shared_texture_table` entry, and `start_offset` and `end_offset` are zero.
+    pub const FLAG_SHARED: u8 = 0x04;
 }
 
 #[cfg(feature = "std")]
@@ -637,6 +1383,53 @@ impl<W: Seek + Write> WriteTo<W> for TextureTableEntry {
     }
 }
 
+/// An animated texture. Frame `n` starts `n * (end_offset - start_offset)` bytes after the first
+/// frame's texture table entry's start offset, and shares its dimensions and format.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct AnimatedTextureTableEntry {
+    pub texture_id: u16,
+    pub frame_count: u16,
+    pub frames_per_second: f32,
+}
+
+impl AnimatedTextureTableEntry {
+    /// The byte offset of the frame showing `seconds` into the animation, for use in place of
+    /// the texture table entry's start offset. The entry must have passed [`MapData::verify`].
+    pub fn frame_start_offset(&self, texture_table: &[TextureTableEntry], seconds: f32) -> u32 {
+        let entry = &texture_table[self.texture_id as usize];
+        let frame = (seconds * self.frames_per_second) as u32 % self.frame_count as u32;
+        entry.start_offset + frame * (entry.end_offset - entry.start_offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Seek + Write> WriteTo<W> for AnimatedTextureTableEntry {
+    fn write_to(&self, w: &mut W) -> io::Result<()> {
+        w.write_u16::<BigEndian>(self.texture_id)?;
+        w.write_u16::<BigEndian>(self.frame_count)?;
+        w.write_u32::<BigEndian>(self.frames_per_second.to_bits())?;
+        Ok(())
+    }
+}
+
+/// Locates a UTF-8 string in the name data section.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct NameTableEntry {
+    pub offset: u32,
+    pub len: u32,
+}
+
+#[cfg(feature = "std")]
+impl<W: Seek + Write> WriteTo<W> for NameTableEntry {
+    fn write_to(&self, w: &mut W) -> io::Result<()> {
+        w.write_u32::<BigEndian>(self.offset)?;
+        w.write_u32::<BigEndian>(self.len)?;
+        Ok(())
+    }
+}
+
 #[derive(Clone, Copy, Pod, Zeroable)]
 #[repr(C)]
 pub struct CommonLightmapTableEntry {
@@ -734,3 +1527,653 @@ impl<W: Seek + Write> WriteTo<W> for DisplacementTableEntry {
         Ok(())
     }
 }
+
+/// Byte code ranges for one cluster's overlays, indexed by mode: 0 takes alpha from the base
+/// texture and 1 takes it from the aux texture.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct OverlayTableEntry {
+    pub byte_code_index_ranges: [[u32; 2]; 2],
+}
+
+impl OverlayTableEntry {
+    pub fn iter_display_lists<'a>(
+        &'a self,
+        overlay_byte_code: &'a [u32],
+        mode: usize,
+    ) -> impl Iterator<Item = BytecodeOp> + 'a {
+        let start = self.byte_code_index_ranges[mode][0] as usize;
+        let end = self.byte_code_index_ranges[mode][1] as usize;
+        BytecodeReader::new(&overlay_byte_code[start..end])
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Seek + Write> WriteTo<W> for OverlayTableEntry {
+    fn write_to(&self, w: &mut W) -> io::Result<()> {
+        for range in self.byte_code_index_ranges.iter() {
+            for &index in range {
+                w.write_u32::<BigEndian>(index)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Byte code ranges for one cluster's static props, indexed by mode like [`OverlayTableEntry`].
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct PropTableEntry {
+    pub byte_code_index_ranges: [[u32; 2]; 2],
+}
+
+impl PropTableEntry {
+    pub fn iter_display_lists<'a>(
+        &'a self,
+        prop_byte_code: &'a [u32],
+        mode: usize,
+    ) -> impl Iterator<Item = BytecodeOp> + 'a {
+        let start = self.byte_code_index_ranges[mode][0] as usize;
+        let end = self.byte_code_index_ranges[mode][1] as usize;
+        BytecodeReader::new(&prop_byte_code[start..end])
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Seek + Write> WriteTo<W> for PropTableEntry {
+    fn write_to(&self, w: &mut W) -> io::Result<()> {
+        for range in self.byte_code_index_ranges.iter() {
+            for &index in range {
+                w.write_u32::<BigEndian>(index)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One cell of the detail material, drawn as a point sprite.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct DetailSpriteTypeTableEntry {
+    /// The sprite's width and height in world units, before each sprite's scale.
+    pub size: [f32; 2],
+    /// How far the sprite's center sits above its origin, in world units, before each sprite's
+    /// scale.
+    pub center_height: f32,
+    /// The cell's corners in texture coordinates.
+    pub tex_upper_left: [f32; 2],
+    pub tex_lower_right: [f32; 2],
+    /// The detail material's textures. Every type in a map shares one material.
+    pub base_texture_id: u16,
+    /// The alpha texture, or `u16::MAX` if the base texture's alpha is used.
+    pub aux_texture_id: u16,
+}
+
+#[cfg(feature = "std")]
+impl<W: Seek + Write> WriteTo<W> for DetailSpriteTypeTableEntry {
+    fn write_to(&self, w: &mut W) -> io::Result<()> {
+        for &x in self.size.iter() {
+            w.write_f32::<BigEndian>(x)?;
+        }
+        w.write_f32::<BigEndian>(self.center_height)?;
+        for &x in self
+            .tex_upper_left
+            .iter()
+            .chain(self.tex_lower_right.iter())
+        {
+            w.write_f32::<BigEndian>(x)?;
+        }
+        w.write_u16::<BigEndian>(self.base_texture_id)?;
+        w.write_u16::<BigEndian>(self.aux_texture_id)?;
+        Ok(())
+    }
+}
+
+/// The range of `detail_sprites` drawn with one cluster.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct DetailSpriteClusterTableEntry {
+    pub sprite_index_range: [u32; 2],
+}
+
+#[cfg(feature = "std")]
+impl<W: Seek + Write> WriteTo<W> for DetailSpriteClusterTableEntry {
+    fn write_to(&self, w: &mut W) -> io::Result<()> {
+        for &index in self.sprite_index_range.iter() {
+            w.write_u32::<BigEndian>(index)?;
+        }
+        Ok(())
+    }
+}
+
+/// One detail sprite. The layout doubles as a GX vertex array: position at offset 0 and color at
+/// offset 16, with a stride of 20 bytes.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct DetailSpriteEntry {
+    pub position: [f32; 3],
+    pub scale: f32,
+    /// Lighting at the sprite's origin.
+    pub color: [u8; 3],
+    /// An index into `detail_sprite_type_table`.
+    pub sprite_type: u8,
+}
+
+#[cfg(feature = "std")]
+impl<W: Seek + Write> WriteTo<W> for DetailSpriteEntry {
+    fn write_to(&self, w: &mut W) -> io::Result<()> {
+        for &x in self.position.iter() {
+            w.write_f32::<BigEndian>(x)?;
+        }
+        w.write_f32::<BigEndian>(self.scale)?;
+        w.write_all(&self.color)?;
+        w.write_u8(self.sprite_type)?;
+        Ok(())
+    }
+}
+
+/// Linear distance fog, from Source's fog parameters.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct FogTableEntry {
+    /// Distance from the camera where fog begins.
+    pub start: f32,
+    /// Distance from the camera where fog reaches `max_density`.
+    pub end: f32,
+    /// The most fog that ever covers a surface, from 0 to 1.
+    pub max_density: f32,
+    /// sRGB.
+    pub color: [u8; 3],
+    /// What the fog applies to; one of the constants below.
+    pub kind: u8,
+}
+
+impl FogTableEntry {
+    /// Fog over the world geometry, from the map's fog controller.
+    pub const WORLD: u8 = 0;
+    /// Fog over the skybox, from the `sky_camera`.
+    pub const SKYBOX: u8 = 1;
+}
+
+#[cfg(feature = "std")]
+impl<W: Seek + Write> WriteTo<W> for FogTableEntry {
+    fn write_to(&self, w: &mut W) -> io::Result<()> {
+        w.write_f32::<BigEndian>(self.start)?;
+        w.write_f32::<BigEndian>(self.end)?;
+        w.write_f32::<BigEndian>(self.max_density)?;
+        w.write_all(&self.color)?;
+        w.write_u8(self.kind)?;
+        Ok(())
+    }
+}
+
+/// A texture stored outside the map, so that maps using the same image can share one copy.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct SharedTextureTableEntry {
+    pub texture_id: u32,
+    /// The image's size in bytes, a multiple of 32.
+    pub size: u32,
+    /// The image's CRC-32, which is much cheaper to check on the console than its hash.
+    pub crc32: u32,
+    /// The first 16 bytes of the image's SHA-256, which also names its file.
+    pub hash: [u8; 16],
+}
+
+/// The path of a shared texture's file, relative to the directory above `maps`.
+pub fn shared_texture_path(hash: &[u8; 16]) -> String {
+    let hex: String = hash.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("textures/{hex}.tex")
+}
+
+impl SharedTextureTableEntry {
+    /// Whether `data` is this texture's image, as far as its size and checksum can tell.
+    pub fn matches(&self, data: &[u8]) -> bool {
+        data.len() == self.size as usize && crc32fast::hash(data) == self.crc32
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Seek + Write> WriteTo<W> for SharedTextureTableEntry {
+    fn write_to(&self, w: &mut W) -> io::Result<()> {
+        w.write_u32::<BigEndian>(self.texture_id)?;
+        w.write_u32::<BigEndian>(self.size)?;
+        w.write_u32::<BigEndian>(self.crc32)?;
+        w.write_all(&self.hash)?;
+        Ok(())
+    }
+}
+
+/// A BP image address register write in one of the display list sections that must be patched
+/// with the address of a texture's image.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct RelocationTableEntry {
+    /// The offset of the register value, just past the BP opcode, within its section.
+    pub display_list_offset: u32,
+    pub texture_id: u16,
+    /// Which display list section the offset is into; one of the constants below.
+    pub display_lists: u8,
+    pub _padding: u8,
+}
+
+impl RelocationTableEntry {
+    pub const CLUSTER_GEOMETRY: u8 = 0;
+    pub const DISPLACEMENT: u8 = 1;
+    pub const OVERLAY: u8 = 2;
+    pub const PROP: u8 = 3;
+}
+
+/// Where the display lists reference one texture. See [`MapData::texture_relocation_sites`].
+pub struct TextureRelocationSites {
+    texture_id: u16,
+    /// Positions of the register values in the map data.
+    positions: Vec<usize>,
+}
+
+impl TextureRelocationSites {
+    pub fn texture_id(&self) -> u16 {
+        self.texture_id
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Seek + Write> WriteTo<W> for RelocationTableEntry {
+    fn write_to(&self, w: &mut W) -> io::Result<()> {
+        w.write_u32::<BigEndian>(self.display_list_offset)?;
+        w.write_u16::<BigEndian>(self.texture_id)?;
+        w.write_u8(self.display_lists)?;
+        w.write_u8(self._padding)?;
+        Ok(())
+    }
+}
+
+/// A volume that loads another map when the player enters it, from a `trigger_changelevel`.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct LevelTransitionTableEntry {
+    pub mins: [f32; 3],
+    pub maxs: [f32; 3],
+    pub map_name: NameTableEntry,
+    /// Names an entry in the landmark table of both this map and the destination map.
+    pub landmark_name: NameTableEntry,
+}
+
+impl LevelTransitionTableEntry {
+    pub fn contains(&self, pos: &[f32; 3]) -> bool {
+        (0..3).all(|i| self.mins[i] <= pos[i] && pos[i] <= self.maxs[i])
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Seek + Write> WriteTo<W> for LevelTransitionTableEntry {
+    fn write_to(&self, w: &mut W) -> io::Result<()> {
+        for &x in self.mins.iter().chain(self.maxs.iter()) {
+            w.write_u32::<BigEndian>(x.to_bits())?;
+        }
+        self.map_name.write_to(w)?;
+        self.landmark_name.write_to(w)?;
+        Ok(())
+    }
+}
+
+/// A shared reference point between two maps, from an `info_landmark`. The player's offset from
+/// the landmark is preserved across a level transition.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct LandmarkTableEntry {
+    pub origin: [f32; 3],
+    pub name: NameTableEntry,
+}
+
+#[cfg(feature = "std")]
+impl<W: Seek + Write> WriteTo<W> for LandmarkTableEntry {
+    fn write_to(&self, w: &mut W) -> io::Result<()> {
+        for &x in &self.origin {
+            w.write_u32::<BigEndian>(x.to_bits())?;
+        }
+        self.name.write_to(w)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    /// A map laid out in memory the way the loaders see it: a native header, then each section
+    /// 32-byte aligned, all at a 32-byte aligned address.
+    struct TestMap {
+        blocks: Vec<Block>,
+    }
+
+    #[derive(Clone, Copy, Pod, Zeroable)]
+    #[repr(C, align(32))]
+    struct Block([u8; 32]);
+
+    impl TestMap {
+        /// Packs the given sections, leaving the rest empty.
+        fn new(sections: &[(&str, &[u8], usize)]) -> Self {
+            let mut data = vec![0; size_of::<PackedMapData>()];
+            let names = section_names();
+            for &(name, bytes, len) in sections {
+                let index = names.iter().position(|&n| n == name).unwrap();
+                data.resize((data.len() + 31) & !31, 0);
+                let offset = data.len();
+                data.extend_from_slice(bytes);
+                write_usize(&mut data, 2 * index, offset);
+                write_usize(&mut data, 2 * index + 1, len);
+                let crc_position = 2 * SECTION_COUNT * size_of::<usize>() + 4 * index;
+                data[crc_position..crc_position + 4]
+                    .copy_from_slice(&crc32fast::hash(bytes).to_ne_bytes());
+            }
+
+            let mut blocks = vec![Block([0; 32]); (data.len() + 31) / 32];
+            bytemuck::cast_slice_mut::<_, u8>(&mut blocks)[..data.len()].copy_from_slice(&data);
+            Self { blocks }
+        }
+
+        fn bytes_mut(&mut self) -> &mut [u8] {
+            bytemuck::cast_slice_mut(&mut self.blocks)
+        }
+
+        fn map_data(&mut self) -> MapData<&mut [u8]> {
+            unsafe { MapData::new(self.bytes_mut()) }
+        }
+
+        fn section_offset(&mut self, name: &str) -> usize {
+            let extents = self.map_data().section_extents();
+            extents.iter().find(|(n, ..)| *n == name).unwrap().1
+        }
+    }
+
+    fn section_names() -> Vec<&'static str> {
+        let header = [Block([0; 32]); (size_of::<PackedMapData>() + 31) / 32];
+        let map_data = unsafe { MapData::new(bytemuck::cast_slice::<_, u8>(&header)) };
+        map_data
+            .section_extents()
+            .iter()
+            .map(|&(name, ..)| name)
+            .collect()
+    }
+
+    fn write_usize(data: &mut [u8], index: usize, value: usize) {
+        let position = index * size_of::<usize>();
+        data[position..position + size_of::<usize>()].copy_from_slice(&value.to_ne_bytes());
+    }
+
+    fn sample_map() -> TestMap {
+        TestMap::new(&[
+            ("position_data", &[1, 2, 3, 4, 5, 6], 6),
+            ("texture_data", &[7; 64], 64),
+            ("name_data", b"sample", 6),
+        ])
+    }
+
+    #[test]
+    fn verify_accepts_intact_map() {
+        assert_eq!(sample_map().map_data().verify(), Ok(()));
+    }
+
+    #[test]
+    fn verify_names_corrupt_section() {
+        for section in ["position_data", "texture_data", "name_data"] {
+            let mut map = sample_map();
+            let offset = map.section_offset(section);
+            map.bytes_mut()[offset] ^= 0x80;
+            assert!(
+                matches!(
+                    map.map_data().verify(),
+                    Err(MapDataError::ChecksumMismatch { section: s, .. }) if s == section
+                ),
+                "{section}",
+            );
+        }
+    }
+
+    #[test]
+    fn verify_names_section_out_of_bounds() {
+        let mut map = sample_map();
+        let index = section_names()
+            .iter()
+            .position(|&name| name == "texture_data")
+            .unwrap();
+        write_usize(map.bytes_mut(), 2 * index + 1, 1 << 20);
+        assert_eq!(
+            map.map_data().verify(),
+            Err(MapDataError::SectionOutOfBounds {
+                section: "texture_data"
+            }),
+        );
+    }
+
+    #[test]
+    fn verify_rejects_truncated_header() {
+        let mut map = sample_map();
+        let data = &map.bytes_mut()[..size_of::<PackedMapData>() - 1];
+        assert_eq!(
+            unsafe { MapData::new(data) }.verify(),
+            Err(MapDataError::Truncated)
+        );
+    }
+
+    /// Two 32-byte textures with room for two more frames, and a display list that binds each of
+    /// them once, texture 1 at offset 1 and texture 0 at offset 6.
+    fn relocation_map(relocations: &[RelocationTableEntry]) -> TestMap {
+        let texture = |start_offset| TextureTableEntry {
+            width: 8,
+            height: 8,
+            mip_count: 1,
+            flags: 0,
+            format: 14,
+            _padding: 0,
+            start_offset,
+            end_offset: start_offset + 32,
+        };
+        let texture_table = [texture(0), texture(32)];
+        let display_lists = [0x61, 0x94, 0, 0, 0, 0x61, 0x95, 0, 0, 0];
+        TestMap::new(&[
+            (
+                "cluster_geometry_display_lists",
+                &display_lists,
+                display_lists.len(),
+            ),
+            ("texture_table", bytemuck::cast_slice(&texture_table), 2),
+            ("texture_data", &[0; 128], 128),
+            (
+                "relocation_table",
+                bytemuck::cast_slice(relocations),
+                relocations.len(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn verify_checks_animated_textures() {
+        let texture_table = [TextureTableEntry {
+            width: 8,
+            height: 8,
+            mip_count: 1,
+            flags: 0,
+            format: 14,
+            _padding: 0,
+            start_offset: 32,
+            end_offset: 64,
+        }];
+        let animation = |texture_id, frame_count| AnimatedTextureTableEntry {
+            texture_id,
+            frame_count,
+            frames_per_second: 10.0,
+        };
+        let verify = |animated_texture_table: &[AnimatedTextureTableEntry]| {
+            TestMap::new(&[
+                ("texture_table", bytemuck::cast_slice(&texture_table), 1),
+                ("texture_data", &[0; 128], 128),
+                (
+                    "animated_texture_table",
+                    bytemuck::cast_slice(animated_texture_table),
+                    animated_texture_table.len(),
+                ),
+            ])
+            .map_data()
+            .verify()
+        };
+
+        // Three frames fill the texture data.
+        assert_eq!(verify(&[animation(0, 3)]), Ok(()));
+        for bad in [animation(0, 4), animation(0, 0), animation(1, 1)] {
+            assert_eq!(
+                verify(&[animation(0, 1), bad]),
+                Err(MapDataError::BadAnimatedTexture { index: 1 }),
+            );
+        }
+    }
+
+    fn relocation(display_list_offset: u32, texture_id: u16) -> RelocationTableEntry {
+        RelocationTableEntry {
+            display_list_offset,
+            texture_id,
+            display_lists: RelocationTableEntry::CLUSTER_GEOMETRY,
+            _padding: 0,
+        }
+    }
+
+    impl TestMap {
+        /// The register value written at `offset` into the cluster geometry display lists.
+        fn register_value(&mut self, offset: usize) -> u32 {
+            let position = self.section_offset("cluster_geometry_display_lists") + offset;
+            u32::from_be_bytes(self.bytes_mut()[position..position + 4].try_into().unwrap())
+        }
+
+        /// The register value that points at `offset` into the texture data.
+        fn image_reg_value(&mut self, register: u8, offset: usize) -> u32 {
+            let address =
+                self.bytes_mut().as_ptr() as usize + self.section_offset("texture_data") + offset;
+            (register as u32) << 24 | ((address as u32) >> 5) & 0x00ffffff
+        }
+    }
+
+    #[test]
+    fn relocate_points_references_at_images() {
+        let mut map = relocation_map(&[relocation(1, 1), relocation(6, 0)]);
+        assert_eq!(map.map_data().verify(), Ok(()));
+        assert_eq!(map.map_data().relocate(), Ok(()));
+        let expected = map.image_reg_value(0x94, 32);
+        assert_eq!(map.register_value(1), expected);
+        let expected = map.image_reg_value(0x95, 0);
+        assert_eq!(map.register_value(6), expected);
+    }
+
+    #[test]
+    fn relocate_patches_nothing_unless_every_reference_checks_out() {
+        for (relocations, index) in [
+            // Past the end of the display lists.
+            ([relocation(1, 1), relocation(7, 0)], 1),
+            // An undefined texture.
+            ([relocation(1, 1), relocation(6, 2)], 1),
+            // An unknown display list section.
+            (
+                [
+                    RelocationTableEntry {
+                        display_lists: 4,
+                        ..relocation(1, 1)
+                    },
+                    relocation(6, 0),
+                ],
+                0,
+            ),
+        ] {
+            let mut map = relocation_map(&relocations);
+            assert_eq!(
+                map.map_data().relocate(),
+                Err(MapDataError::BadRelocation { index })
+            );
+            assert_eq!(map.register_value(1), 0x94000000);
+            assert_eq!(map.register_value(6), 0x95000000);
+        }
+    }
+
+    #[test]
+    fn relocate_texture_frame_moves_only_that_texture() {
+        let mut map = relocation_map(&[relocation(1, 1), relocation(6, 0)]);
+        map.map_data().relocate().unwrap();
+        let sites = map.map_data().texture_relocation_sites(1).unwrap();
+        let mut patched = Vec::new();
+        assert_eq!(
+            map.map_data()
+                .relocate_texture_frame(&sites, 64, |bytes| patched.push(bytes.to_vec())),
+            Ok(())
+        );
+
+        let expected = map.image_reg_value(0x94, 64);
+        assert_eq!(map.register_value(1), expected);
+        assert_eq!(patched, [expected.to_be_bytes()]);
+        let expected = map.image_reg_value(0x95, 0);
+        assert_eq!(map.register_value(6), expected);
+    }
+
+    #[test]
+    fn relocate_texture_frame_checks_whole_frame() {
+        let mut map = relocation_map(&[relocation(1, 1)]);
+        let sites = map.map_data().texture_relocation_sites(1).unwrap();
+        // The last frame that fits.
+        assert_eq!(
+            map.map_data().relocate_texture_frame(&sites, 96, |_| ()),
+            Ok(())
+        );
+        for frame_start_offset in [112, 128, u32::MAX - 31] {
+            assert_eq!(
+                map.map_data()
+                    .relocate_texture_frame(&sites, frame_start_offset, |_| ()),
+                Err(MapDataError::BadTextureFrame { texture_id: 1 }),
+                "{frame_start_offset}",
+            );
+        }
+    }
+
+    #[test]
+    fn texture_relocation_sites_rejects_bad_relocation() {
+        let mut map = relocation_map(&[relocation(1, 1), relocation(9, 1)]);
+        assert!(matches!(
+            map.map_data().texture_relocation_sites(1),
+            Err(MapDataError::BadRelocation { index: 1 })
+        ));
+        assert!(map.map_data().texture_relocation_sites(0).is_ok());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn packed_map_file_reads_written_sections() {
+        let owned = OwnedMapData {
+            position_data: vec![1, 2, 3],
+            texture_data: vec![4; 32],
+            relocation_table: vec![relocation(6, 1)],
+            ..Default::default()
+        };
+        let mut data = Cursor::new(Vec::new());
+        owned.write_to(&mut data).unwrap();
+        let mut data = data.into_inner();
+
+        let file = PackedMapFile::new(&data).unwrap();
+        assert_eq!(file.section("position_data"), [1, 2, 3]);
+        assert_eq!(file.section("texture_data"), [4; 32]);
+        assert_eq!(file.section("relocation_table"), [0, 0, 0, 6, 0, 1, 0, 0]);
+        assert_eq!(file.section("name_data"), []);
+
+        let position = data.windows(32).position(|w| w == [4; 32]).unwrap();
+        data[position] ^= 1;
+        assert!(matches!(
+            PackedMapFile::new(&data),
+            Err(MapDataError::ChecksumMismatch {
+                section: "texture_data",
+                ..
+            })
+        ));
+        assert_eq!(
+            PackedMapFile::new(&data[..100]).err(),
+            Some(MapDataError::Truncated)
+        );
+    }
+}
@@ -6,22 +6,29 @@ use alloc::vec::Vec;
 use byteorder::WriteBytesExt;
 
 use crate::hashable_float::HashableMat3x4;
-use crate::shader::Shader;
+use crate::shader::{Shader, ShaderBaseAlpha, ShaderEnvMapMask};
 
 /// Uniquely identifies a graphics processor state needed to render a batch.
+///
+/// The shader is whatever identifies a TEV configuration to the target. The optional parts are
+/// left alone when unset, for state that the display lists set themselves.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub struct PipelineState {
-    pub shader: Shader,
+pub struct PipelineState<S = Shader> {
+    pub shader: S,
+
+    pub blend: BlendMode,
+
+    pub depth: DepthMode,
 
     /// Reflection matrix. Required for env map shaders.
-    pub reflection_matrix: HashableMat3x4,
+    pub reflection_matrix: Option<HashableMat3x4>,
 
     /// Which vertex attributes are enabled and their input types.
-    pub vertex_desc: VertexDesc,
+    pub vertex_desc: Option<VertexDesc>,
 
     /// Texture ID to bind to GX_TEXTURE1. Typically used for a base color, sometimes with an alpha
     /// channel for opacity or an env map mask.
-    pub base_texture_id: u16,
+    pub base_texture_id: Option<u16>,
 
     /// Texture ID to bind to GX_TEXTURE2. Typically used for opacity and/or an env map mask.
     pub aux_texture_id: Option<u16>,
@@ -33,6 +40,284 @@ pub struct PipelineState {
     pub env_map_tint: Option<[u8; 3]>,
 }
 
+impl<S> PipelineState<S> {
+    /// A state that sets only the shader, blending, and depth test.
+    pub fn new(shader: S, blend: BlendMode, depth: DepthMode) -> Self {
+        Self {
+            shader,
+            blend,
+            depth,
+            reflection_matrix: None,
+            vertex_desc: None,
+            base_texture_id: None,
+            aux_texture_id: None,
+            env_texture_id: None,
+            env_map_tint: None,
+        }
+    }
+}
+
+impl PipelineState<Shader> {
+    /// Checks that every texture and constant the shader reads is present.
+    pub fn validate(&self) -> Result<(), PipelineStateError> {
+        if let Shader::LightmappedGeneric {
+            base_alpha,
+            env_map,
+        } = self.shader
+        {
+            let needs_aux = base_alpha == ShaderBaseAlpha::AuxTextureAlpha
+                || env_map.map(|env_map| env_map.mask)
+                    == Some(ShaderEnvMapMask::AuxTextureIntensity);
+            if needs_aux && self.aux_texture_id.is_none() {
+                return Err(PipelineStateError::MissingAuxTexture);
+            }
+            if env_map.is_some() {
+                if self.env_texture_id.is_none() {
+                    return Err(PipelineStateError::MissingEnvTexture);
+                }
+                if self.env_map_tint.is_none() {
+                    return Err(PipelineStateError::MissingEnvMapTint);
+                }
+                if self.reflection_matrix.is_none() {
+                    return Err(PipelineStateError::MissingReflectionMatrix);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<S: PartialEq> PipelineState<S> {
+    /// Returns the state groups that must be submitted to get from `other` to `self`.
+    pub fn diff(&self, other: &Self) -> StateGroups {
+        StateGroups {
+            shader: self.shader != other.shader,
+            blend: self.blend != other.blend,
+            depth: self.depth != other.depth,
+            reflection_matrix: self.reflection_matrix != other.reflection_matrix,
+            vertex_desc: self.vertex_desc != other.vertex_desc,
+            base_texture: self.base_texture_id != other.base_texture_id,
+            aux_texture: self.aux_texture_id != other.aux_texture_id,
+            env_texture: self.env_texture_id != other.env_texture_id,
+            env_map_tint: self.env_map_tint != other.env_map_tint,
+        }
+    }
+
+    /// Submits the selected state groups. Unset optional state is skipped.
+    pub fn apply(&self, groups: StateGroups, target: &mut impl ApplyPipelineState<S>) {
+        if groups.shader {
+            target.apply_shader(&self.shader);
+        }
+        if groups.blend {
+            target.apply_blend(self.blend);
+        }
+        if groups.depth {
+            target.apply_depth(self.depth);
+        }
+        if groups.reflection_matrix {
+            if let Some(matrix) = &self.reflection_matrix {
+                target.apply_reflection_matrix(matrix);
+            }
+        }
+        if groups.vertex_desc {
+            if let Some(vertex_desc) = &self.vertex_desc {
+                target.apply_vertex_desc(vertex_desc);
+            }
+        }
+        if groups.base_texture {
+            if let Some(texture_id) = self.base_texture_id {
+                target.bind_texture(TextureSlot::Base, texture_id);
+            }
+        }
+        if groups.aux_texture {
+            if let Some(texture_id) = self.aux_texture_id {
+                target.bind_texture(TextureSlot::Aux, texture_id);
+            }
+        }
+        if groups.env_texture {
+            if let Some(texture_id) = self.env_texture_id {
+                target.bind_texture(TextureSlot::Env, texture_id);
+            }
+        }
+        if groups.env_map_tint {
+            if let Some(tint) = self.env_map_tint {
+                target.apply_env_map_tint(tint);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PipelineStateError {
+    MissingAuxTexture,
+    MissingEnvTexture,
+    MissingEnvMapTint,
+    MissingReflectionMatrix,
+}
+
+/// How a batch's color combines with the frame buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BlendMode {
+    /// Replaces the frame buffer.
+    Opaque,
+    /// Source alpha over inverse source alpha.
+    Alpha,
+    /// Adds to the frame buffer.
+    Additive,
+}
+
+/// How a batch tests and writes depth.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DepthMode {
+    /// `None` turns the depth test off.
+    pub compare: Option<DepthCompare>,
+    pub write: bool,
+}
+
+impl DepthMode {
+    /// The usual test for opaque geometry.
+    pub const OPAQUE: Self = Self {
+        compare: Some(DepthCompare::LessEqual),
+        write: true,
+    };
+    /// Tests against opaque geometry without occluding anything drawn after.
+    pub const TRANSLUCENT: Self = Self {
+        compare: Some(DepthCompare::LessEqual),
+        write: false,
+    };
+    /// Draws over everything.
+    pub const DISABLED: Self = Self {
+        compare: None,
+        write: false,
+    };
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DepthCompare {
+    LessEqual,
+    Equal,
+}
+
+/// Independently submittable groups of GX state.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StateGroups {
+    pub shader: bool,
+    pub blend: bool,
+    pub depth: bool,
+    pub reflection_matrix: bool,
+    pub vertex_desc: bool,
+    pub base_texture: bool,
+    pub aux_texture: bool,
+    pub env_texture: bool,
+    pub env_map_tint: bool,
+}
+
+impl StateGroups {
+    pub const ALL: Self = Self {
+        shader: true,
+        blend: true,
+        depth: true,
+        reflection_matrix: true,
+        vertex_desc: true,
+        base_texture: true,
+        aux_texture: true,
+        env_texture: true,
+        env_map_tint: true,
+    };
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The groups in either `self` or `other`.
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            shader: self.shader || other.shader,
+            blend: self.blend || other.blend,
+            depth: self.depth || other.depth,
+            reflection_matrix: self.reflection_matrix || other.reflection_matrix,
+            vertex_desc: self.vertex_desc || other.vertex_desc,
+            base_texture: self.base_texture || other.base_texture,
+            aux_texture: self.aux_texture || other.aux_texture,
+            env_texture: self.env_texture || other.env_texture,
+            env_map_tint: self.env_map_tint || other.env_map_tint,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextureSlot {
+    /// GX_TEXMAP1.
+    Base,
+    /// GX_TEXMAP2.
+    Aux,
+    /// GX_TEXMAP3.
+    Env,
+}
+
+/// Submits pipeline state to the graphics processor. Implemented by the runtime.
+pub trait ApplyPipelineState<S = Shader> {
+    fn apply_shader(&mut self, shader: &S);
+    fn apply_blend(&mut self, blend: BlendMode);
+    fn apply_depth(&mut self, depth: DepthMode);
+    fn apply_reflection_matrix(&mut self, matrix: &HashableMat3x4);
+    fn apply_vertex_desc(&mut self, vertex_desc: &VertexDesc);
+    fn bind_texture(&mut self, slot: TextureSlot, texture_id: u16);
+    fn apply_env_map_tint(&mut self, tint: [u8; 3]);
+}
+
+/// Remembers the last submitted state so that consecutive batches only submit what changed.
+pub struct PipelineStateTracker<S = Shader> {
+    current: Option<PipelineState<S>>,
+    /// Groups that something other than the tracker may have changed since they were submitted.
+    stale: StateGroups,
+}
+
+impl<S> Default for PipelineStateTracker<S> {
+    fn default() -> Self {
+        Self {
+            current: None,
+            stale: StateGroups::default(),
+        }
+    }
+}
+
+impl<S: Clone + PartialEq> PipelineStateTracker<S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Submits whatever differs between `state` and the last submitted state, returning what was
+    /// submitted.
+    pub fn submit(
+        &mut self,
+        state: &PipelineState<S>,
+        target: &mut impl ApplyPipelineState<S>,
+    ) -> StateGroups {
+        let groups = match &self.current {
+            Some(current) => state.diff(current).union(self.stale),
+            None => StateGroups::ALL,
+        };
+        if !groups.is_empty() {
+            state.apply(groups, target);
+            self.current = Some(state.clone());
+            self.stale = StateGroups::default();
+        }
+        groups
+    }
+
+    /// Forgets the last submitted state. Call this after anything else touches GX state.
+    pub fn invalidate(&mut self) {
+        self.current = None;
+    }
+
+    /// Forgets the last submitted state of some groups, so that the next submission sends them
+    /// again. Call this after anything else touches them.
+    pub fn invalidate_groups(&mut self, groups: StateGroups) {
+        self.stale = self.stale.union(groups);
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[repr(u8)]
 pub enum VertexAttribute {
@@ -42,6 +327,7 @@ pub enum VertexAttribute {
     Clr1 = 12,
     Tex0 = 13,
     Tex1 = 14,
+    Tex2 = 15,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -72,6 +358,10 @@ impl VertexDesc {
         }
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = (VertexAttribute, VertexInput)> + '_ {
+        self.desc.iter().copied()
+    }
+
     #[cfg(feature = "std")]
     pub fn write_to(&self, w: &mut impl Write) -> io::Result<()> {
         for &(attribute, input) in &self.desc {
@@ -81,3 +371,175 @@ impl VertexDesc {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use crate::shader::ShaderEnvMap;
+
+    #[derive(Debug, PartialEq)]
+    enum Call {
+        Shader(Shader),
+        Blend(BlendMode),
+        Depth(DepthMode),
+        ReflectionMatrix,
+        VertexDesc(VertexDesc),
+        Texture(TextureSlot, u16),
+        EnvMapTint([u8; 3]),
+    }
+
+    #[derive(Default)]
+    struct Recorder(Vec<Call>);
+
+    impl ApplyPipelineState for Recorder {
+        fn apply_shader(&mut self, shader: &Shader) {
+            self.0.push(Call::Shader(*shader));
+        }
+
+        fn apply_blend(&mut self, blend: BlendMode) {
+            self.0.push(Call::Blend(blend));
+        }
+
+        fn apply_depth(&mut self, depth: DepthMode) {
+            self.0.push(Call::Depth(depth));
+        }
+
+        fn apply_reflection_matrix(&mut self, _matrix: &HashableMat3x4) {
+            self.0.push(Call::ReflectionMatrix);
+        }
+
+        fn apply_vertex_desc(&mut self, vertex_desc: &VertexDesc) {
+            self.0.push(Call::VertexDesc(vertex_desc.clone()));
+        }
+
+        fn bind_texture(&mut self, slot: TextureSlot, texture_id: u16) {
+            self.0.push(Call::Texture(slot, texture_id));
+        }
+
+        fn apply_env_map_tint(&mut self, tint: [u8; 3]) {
+            self.0.push(Call::EnvMapTint(tint));
+        }
+    }
+
+    const LIGHTMAPPED: Shader = Shader::LightmappedGeneric {
+        base_alpha: ShaderBaseAlpha::BaseTextureAlpha,
+        env_map: None,
+    };
+
+    fn textured(base_texture_id: u16) -> PipelineState {
+        let mut vertex_desc = VertexDesc::new();
+        vertex_desc.add(VertexAttribute::Pos, VertexInput::Index16);
+        PipelineState {
+            vertex_desc: Some(vertex_desc),
+            base_texture_id: Some(base_texture_id),
+            ..PipelineState::new(LIGHTMAPPED, BlendMode::Opaque, DepthMode::OPAQUE)
+        }
+    }
+
+    #[test]
+    fn diff_of_equal_states_is_empty() {
+        assert!(textured(1).diff(&textured(1)).is_empty());
+    }
+
+    #[test]
+    fn diff_names_changed_groups() {
+        let mut state = textured(2);
+        state.blend = BlendMode::Alpha;
+        assert_eq!(
+            state.diff(&textured(1)),
+            StateGroups {
+                blend: true,
+                base_texture: true,
+                ..StateGroups::default()
+            },
+        );
+    }
+
+    #[test]
+    fn validate_requires_what_the_shader_reads() {
+        assert_eq!(textured(1).validate(), Ok(()));
+
+        let mut state = textured(1);
+        state.shader = Shader::LightmappedGeneric {
+            base_alpha: ShaderBaseAlpha::AuxTextureAlpha,
+            env_map: None,
+        };
+        assert_eq!(state.validate(), Err(PipelineStateError::MissingAuxTexture));
+        state.aux_texture_id = Some(2);
+        assert_eq!(state.validate(), Ok(()));
+
+        let mut state = textured(1);
+        state.shader = Shader::LightmappedGeneric {
+            base_alpha: ShaderBaseAlpha::BaseTextureAlpha,
+            env_map: Some(ShaderEnvMap {
+                mask: ShaderEnvMapMask::None,
+            }),
+        };
+        assert_eq!(state.validate(), Err(PipelineStateError::MissingEnvTexture));
+        state.env_texture_id = Some(3);
+        assert_eq!(state.validate(), Err(PipelineStateError::MissingEnvMapTint));
+        state.env_map_tint = Some([255, 255, 255]);
+        assert_eq!(
+            state.validate(),
+            Err(PipelineStateError::MissingReflectionMatrix),
+        );
+    }
+
+    #[test]
+    fn submit_applies_everything_first_then_only_changes() {
+        let mut tracker = PipelineStateTracker::new();
+        let mut recorder = Recorder::default();
+
+        assert_eq!(
+            tracker.submit(&textured(1), &mut recorder),
+            StateGroups::ALL
+        );
+        let mut vertex_desc = VertexDesc::new();
+        vertex_desc.add(VertexAttribute::Pos, VertexInput::Index16);
+        assert_eq!(
+            recorder.0,
+            vec![
+                Call::Shader(LIGHTMAPPED),
+                Call::Blend(BlendMode::Opaque),
+                Call::Depth(DepthMode::OPAQUE),
+                Call::VertexDesc(vertex_desc),
+                Call::Texture(TextureSlot::Base, 1),
+            ],
+        );
+
+        recorder.0.clear();
+        assert!(tracker.submit(&textured(1), &mut recorder).is_empty());
+        assert_eq!(recorder.0, vec![]);
+
+        tracker.submit(&textured(2), &mut recorder);
+        assert_eq!(recorder.0, vec![Call::Texture(TextureSlot::Base, 2)]);
+    }
+
+    #[test]
+    fn submit_resends_invalidated_state() {
+        let mut tracker = PipelineStateTracker::new();
+        let mut recorder = Recorder::default();
+        tracker.submit(&textured(1), &mut recorder);
+
+        recorder.0.clear();
+        tracker.invalidate_groups(StateGroups {
+            depth: true,
+            ..StateGroups::default()
+        });
+        tracker.submit(&textured(1), &mut recorder);
+        assert_eq!(recorder.0, vec![Call::Depth(DepthMode::OPAQUE)]);
+
+        recorder.0.clear();
+        tracker.submit(&textured(1), &mut recorder);
+        assert_eq!(recorder.0, vec![]);
+
+        tracker.invalidate();
+        assert_eq!(
+            tracker.submit(&textured(1), &mut recorder),
+            StateGroups::ALL
+        );
+        assert_eq!(recorder.0.len(), 5);
+    }
+}
@@ -1,3 +1,7 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::Write;
+
 /// Uniquely identifies a GX TEV configuration.
 #[allow(non_camel_case_types)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -95,3 +99,949 @@ pub enum ShaderEnvMapMask {
     BaseTextureAlpha,
     AuxTextureIntensity,
 }
+
+impl Shader {
+    /// Describes the TEV configuration that draws this shader.
+    ///
+    /// Env maps aren't drawn yet, so every env map variant shares the program of the variant
+    /// without one.
+    pub const fn program(self) -> &'static TevProgram {
+        match self {
+            Self::LightmappedGeneric {
+                base_alpha: ShaderBaseAlpha::BaseTextureAlpha,
+                ..
+            } => &LIGHTMAPPED_PROGRAM,
+            Self::LightmappedGeneric {
+                base_alpha: ShaderBaseAlpha::AuxTextureAlpha,
+                ..
+            } => &LIGHTMAPPED_BAAA_PROGRAM,
+            Self::UnlitGeneric => &UNLIT_GENERIC_PROGRAM,
+            Self::WorldVertexTransition => &WORLD_VERTEX_TRANSITION_PROGRAM,
+        }
+    }
+}
+
+pub const LIGHTMAPPED_PROGRAM: TevProgram = TevProgram {
+    stages: &[
+        // Sample the lightmap.
+        TevStageDesc::color_only(TevStageColor::just(TevColorIn::TexColor)).with_tex(0, 0),
+        // Sample the base map and multiply it by the lightmap.
+        TevStageDesc::new(
+            TevStageColor::mul(TevColorIn::PrevColor, TevColorIn::TexColor)
+                // Scale to allow the lightmap to over-brighten to some degree.
+                .with_scale(TevScale::K2),
+            TevStageAlpha::just(TevAlphaIn::TexAlpha),
+        )
+        .with_tex(1, 1),
+    ],
+    tex_gens: &[
+        // Lightmap coord.
+        TexGenDesc::tex(0),
+        // Base map coord.
+        TexGenDesc::tex(1),
+    ],
+    num_chans: 0,
+};
+
+/// LightmappedGeneric, base alpha packed as aux alpha.
+pub const LIGHTMAPPED_BAAA_PROGRAM: TevProgram = TevProgram {
+    stages: &[
+        // Sample the lightmap.
+        TevStageDesc::color_only(TevStageColor::just(TevColorIn::TexColor)).with_tex(0, 0),
+        // Sample the base map and multiply it by the lightmap.
+        TevStageDesc::new(
+            TevStageColor::mul(TevColorIn::PrevColor, TevColorIn::TexColor)
+                // Scale to allow the lightmap to over-brighten to some degree.
+                .with_scale(TevScale::K2),
+            TevStageAlpha::just(TevAlphaIn::TexAlpha),
+        )
+        .with_tex(1, 1),
+        // Sample the aux map for alpha.
+        TevStageDesc::new(
+            TevStageColor::just(TevColorIn::PrevColor),
+            TevStageAlpha::just(TevAlphaIn::TexAlpha),
+        )
+        .with_tex(1, 2),
+    ],
+    tex_gens: &[
+        // Lightmap coord.
+        TexGenDesc::tex(0),
+        // Base map coord.
+        TexGenDesc::tex(1),
+    ],
+    num_chans: 0,
+};
+
+pub const UNLIT_GENERIC_PROGRAM: TevProgram = TevProgram {
+    stages: &[TevStageDesc::color_only(TevStageColor::just(TevColorIn::TexColor)).with_tex(0, 1)],
+    tex_gens: &[
+        // Texture coord.
+        TexGenDesc::tex(0),
+    ],
+    num_chans: 0,
+};
+
+pub const WORLD_VERTEX_TRANSITION_PROGRAM: TevProgram = TevProgram {
+    stages: &[
+        // Sample the first base map.
+        TevStageDesc::color_only(TevStageColor::just(TevColorIn::TexColor)).with_tex(1, 1),
+        // Sample the second base map and blend between them by the rasterized alpha.
+        TevStageDesc::color_only(TevStageColor::mix(
+            TevColorIn::PrevColor,
+            TevColorIn::TexColor,
+            TevColorIn::RasColor,
+        ))
+        .with_tex(2, 2)
+        .with_channel(TevChannel::Color0),
+        // Sample the lightmap and multiply it in.
+        TevStageDesc::color_only(
+            TevStageColor::mul(TevColorIn::PrevColor, TevColorIn::TexColor)
+                // Scale to allow the lightmap to over-brighten to some degree.
+                .with_scale(TevScale::K2),
+        )
+        .with_tex(0, 0),
+    ],
+    tex_gens: &[
+        // Lightmap coord.
+        TexGenDesc::tex(0),
+        // Texture coord 1.
+        TexGenDesc::tex(1),
+        // Texture coord 2.
+        TexGenDesc::tex(2),
+    ],
+    num_chans: 1,
+};
+
+/// A TEV configuration described independently of any graphics API.
+///
+/// The GameCube runtime compiles this to GX calls. The PC side compiles it to GLSL with
+/// [`TevProgram::glsl_vertex`] and [`TevProgram::glsl_fragment`].
+///
+/// Texture maps follow the pipeline state's slots: 0 is the lightmap, 1 the base texture, 2 the aux
+/// texture, and 3 the env map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TevProgram {
+    pub stages: &'static [TevStageDesc],
+    pub tex_gens: &'static [TexGenDesc],
+    /// The number of rasterized color channels, taken from vertex colors.
+    pub num_chans: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TevProgramError {
+    TooManyStages,
+    TooManyTexGens,
+    TooManyChans,
+    TexCoordOutOfRange { stage: usize },
+    TexMapOutOfRange { stage: usize },
+    ChannelOutOfRange { stage: usize },
+    TexGenSourceOutOfRange { tex_gen: usize },
+    TexMtxOutOfRange { tex_gen: usize },
+}
+
+impl TevProgram {
+    /// Checks the program against GX's limits, and that every texture coordinate and color
+    /// channel a stage reads is generated.
+    pub fn validate(&self) -> Result<(), TevProgramError> {
+        if self.stages.len() > 16 {
+            return Err(TevProgramError::TooManyStages);
+        }
+        if self.tex_gens.len() > 8 {
+            return Err(TevProgramError::TooManyTexGens);
+        }
+        if self.num_chans > 2 {
+            return Err(TevProgramError::TooManyChans);
+        }
+        for (stage, desc) in self.stages.iter().enumerate() {
+            if let Some(tex) = desc.tex {
+                if tex.coord as usize >= self.tex_gens.len() {
+                    return Err(TevProgramError::TexCoordOutOfRange { stage });
+                }
+                if tex.map >= 8 {
+                    return Err(TevProgramError::TexMapOutOfRange { stage });
+                }
+            }
+            if matches!(desc.channel.chan_index(), Some(chan) if chan >= self.num_chans) {
+                return Err(TevProgramError::ChannelOutOfRange { stage });
+            }
+        }
+        for (tex_gen, desc) in self.tex_gens.iter().enumerate() {
+            if matches!(desc.src, TexGenSource::Tex(index) if index >= 8) {
+                return Err(TevProgramError::TexGenSourceOutOfRange { tex_gen });
+            }
+            if matches!(desc.tex_mtx, Some(tex_mtx) if tex_mtx >= 10) {
+                return Err(TevProgramError::TexMtxOutOfRange { tex_gen });
+            }
+        }
+        Ok(())
+    }
+
+    /// Generates a GLSL 3.30 vertex shader.
+    ///
+    /// Attributes are `position`, `normal`, `tex0` through `tex7`, and `color0` and `color1`, as
+    /// the program reads them. Uniforms are `mvp_matrix` and, if any texgen uses one, `tex_mtx`,
+    /// an array of ten 4x4 matrices holding the GX texture matrices in their top rows.
+    pub fn glsl_vertex(&self) -> String {
+        let mut s = String::new();
+        s.push_str("#version 330\n\nuniform mat4 mvp_matrix;\n");
+        if self.tex_gens.iter().any(|desc| desc.tex_mtx.is_some()) {
+            s.push_str("uniform mat4 tex_mtx[10];\n");
+        }
+
+        s.push_str("\nin vec3 position;\n");
+        if self
+            .tex_gens
+            .iter()
+            .any(|desc| desc.src == TexGenSource::Normal)
+        {
+            s.push_str("in vec3 normal;\n");
+        }
+        for index in 0..8 {
+            if self
+                .tex_gens
+                .iter()
+                .any(|desc| desc.src == TexGenSource::Tex(index))
+            {
+                writeln!(s, "in vec2 tex{index};").unwrap();
+            }
+        }
+        for chan in 0..self.num_chans {
+            writeln!(s, "in vec4 color{chan};").unwrap();
+        }
+
+        s.push('\n');
+        for index in 0..self.tex_gens.len() {
+            writeln!(s, "out vec3 tex_coord{index};").unwrap();
+        }
+        for chan in 0..self.num_chans {
+            writeln!(s, "out vec4 ras_color{chan};").unwrap();
+        }
+
+        s.push_str("\nvoid main() {\n    gl_Position = mvp_matrix * vec4(position, 1.0);\n");
+        for (index, desc) in self.tex_gens.iter().enumerate() {
+            let mut src = match desc.src {
+                TexGenSource::Position => "vec4(position, 1.0)".to_string(),
+                TexGenSource::Normal => "vec4(normal, 1.0)".to_string(),
+                TexGenSource::Tex(index) => format!("vec4(tex{index}, 1.0, 1.0)"),
+            };
+            if let Some(tex_mtx) = desc.tex_mtx {
+                src = format!("(transpose(tex_mtx[{tex_mtx}]) * {src})");
+            }
+            match desc.projection {
+                TexGenProjection::St => writeln!(s, "    tex_coord{index} = vec3({src}.xy, 1.0);"),
+                TexGenProjection::Stq => writeln!(s, "    tex_coord{index} = {src}.xyz;"),
+            }
+            .unwrap();
+        }
+        for chan in 0..self.num_chans {
+            writeln!(s, "    ras_color{chan} = color{chan};").unwrap();
+        }
+        s.push_str("}\n");
+        s
+    }
+
+    /// Generates a GLSL 3.30 fragment shader to pair with [`TevProgram::glsl_vertex`].
+    ///
+    /// Texture maps are bound to `tex_map0` through `tex_map7`, the konstant colors to `konst`,
+    /// and the initial values of REG0 through REG2 to `tev_reg`. The output is `rendered_color`.
+    pub fn glsl_fragment(&self) -> String {
+        let mut s = String::new();
+        s.push_str("#version 330\n\n");
+        for map in 0..8 {
+            if self
+                .stages
+                .iter()
+                .any(|desc| matches!(desc.tex, Some(tex) if tex.map == map))
+            {
+                writeln!(s, "uniform sampler2D tex_map{map};").unwrap();
+            }
+        }
+        s.push_str("uniform vec4 konst[4];\nuniform vec4 tev_reg[3];\n\n");
+        for index in 0..self.tex_gens.len() {
+            writeln!(s, "in vec3 tex_coord{index};").unwrap();
+        }
+        for chan in 0..self.num_chans {
+            writeln!(s, "in vec4 ras_color{chan};").unwrap();
+        }
+        s.push_str(concat!(
+            "\nout vec4 rendered_color;\n",
+            "\n",
+            "float tev_r8(vec3 x) { return round(x.r * 255.0); }\n",
+            "float tev_gr16(vec3 x) { return dot(round(x.rg * 255.0), vec2(256.0, 1.0)); }\n",
+            "float tev_bgr24(vec3 x) {\n",
+            "    return dot(round(x * 255.0), vec3(1.0, 256.0, 65536.0));\n",
+            "}\n",
+            "\n",
+            "void main() {\n",
+            "    vec4 prev = vec4(0.0);\n",
+            "    vec4 reg0 = tev_reg[0];\n",
+            "    vec4 reg1 = tev_reg[1];\n",
+            "    vec4 reg2 = tev_reg[2];\n",
+        ));
+
+        for (index, desc) in self.stages.iter().enumerate() {
+            writeln!(s, "\n    // Stage {index}.\n    {{").unwrap();
+            match desc.tex {
+                Some(TevTex { coord, map }) => writeln!(
+                    s,
+                    "        vec4 tex = textureProj(tex_map{map}, tex_coord{coord});",
+                ),
+                None => writeln!(s, "        vec4 tex = vec4(0.0);"),
+            }
+            .unwrap();
+            match desc.channel.chan_index() {
+                Some(chan) => writeln!(s, "        vec4 ras = ras_color{chan};"),
+                None => writeln!(s, "        vec4 ras = vec4(0.0);"),
+            }
+            .unwrap();
+            writeln!(
+                s,
+                "        vec3 konst_color = {};\n        float konst_alpha = {};",
+                desc.color
+                    .konst_sel
+                    .unwrap_or(TevColorKonst::Constant1)
+                    .glsl(),
+                desc.alpha
+                    .konst_sel
+                    .unwrap_or(TevAlphaKonst::Constant1)
+                    .glsl(),
+            )
+            .unwrap();
+            for (name, input) in ["ca", "cb", "cc", "cd"].iter().zip(desc.color.inputs) {
+                writeln!(s, "        vec3 {name} = {};", input.glsl()).unwrap();
+            }
+            for (name, input) in ["aa", "ab", "ac", "ad"].iter().zip(desc.alpha.inputs) {
+                writeln!(s, "        float {name} = {};", input.glsl()).unwrap();
+            }
+            writeln!(
+                s,
+                "        {}.rgb = {};\n        {}.a = {};\n    }}",
+                desc.color.dst.glsl(),
+                combiner_glsl(&desc.color, "vec3", "c"),
+                desc.alpha.dst.glsl(),
+                combiner_glsl(&desc.alpha, "float", "a"),
+            )
+            .unwrap();
+        }
+
+        s.push_str("\n    rendered_color = prev;\n}\n");
+        s
+    }
+}
+
+/// Builds the expression for one TEV combiner over the inputs named `{prefix}a` through
+/// `{prefix}d`.
+fn combiner_glsl<Input: ComponentIn, Konst: ComponentKonst>(
+    component: &TevStageComponent<Input, Konst>,
+    ty: &str,
+    prefix: &str,
+) -> String {
+    let [a, b, c, d] = ["a", "b", "c", "d"].map(|name| format!("{prefix}{name}"));
+    let compare =
+        |lhs: String, rhs: String, op: &str| format!("{d} + ({lhs} {op} {rhs} ? {c} : {ty}(0.0))");
+    // Packed comparisons always read the red, green, and blue channels of the color inputs, even
+    // in the alpha combiner.
+    let packed = |func: &str, op: &str| compare(format!("{func}(ca)"), format!("{func}(cb)"), op);
+    let per_channel = |op: &str| {
+        if ty == "float" {
+            compare(
+                format!("round({a} * 255.0)"),
+                format!("round({b} * 255.0)"),
+                op,
+            )
+        } else {
+            let func = if op == ">" { "greaterThan" } else { "equal" };
+            format!("{d} + {c} * vec3({func}(round({a} * 255.0), round({b} * 255.0)))",)
+        }
+    };
+
+    let value = match component.op {
+        TevOp::Add | TevOp::Sub => {
+            let sign = if component.op == TevOp::Add { '+' } else { '-' };
+            let bias = match component.bias {
+                TevBias::Zero => "",
+                TevBias::AddHalf => " + 0.5",
+                TevBias::SubHalf => " - 0.5",
+            };
+            let scale = match component.scale {
+                TevScale::K1 => "1.0",
+                TevScale::K2 => "2.0",
+                TevScale::K4 => "4.0",
+                TevScale::K1_2 => "0.5",
+            };
+            format!("({d} {sign} mix({a}, {b}, {c}){bias}) * {scale}")
+        }
+        TevOp::CompR8Gt => packed("tev_r8", ">"),
+        TevOp::CompR8Eq => packed("tev_r8", "=="),
+        TevOp::CompGr16Gt => packed("tev_gr16", ">"),
+        TevOp::CompGr16Eq => packed("tev_gr16", "=="),
+        TevOp::CompBgr24Gt => packed("tev_bgr24", ">"),
+        TevOp::CompBgr24Eq => packed("tev_bgr24", "=="),
+        TevOp::CompRgb8OrA8Gt => per_channel(">"),
+        TevOp::CompRgb8OrA8Eq => per_channel("=="),
+    };
+    if component.clamp {
+        format!("clamp({value}, 0.0, 1.0)")
+    } else {
+        // TEV registers hold signed 11-bit values, with 255 as 1.0.
+        format!("clamp({value}, -1024.0 / 255.0, 1023.0 / 255.0)")
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TevStageDesc {
+    pub color: TevStageColor,
+    pub alpha: TevStageAlpha,
+    pub tex: Option<TevTex>,
+    pub channel: TevChannel,
+}
+
+impl TevStageDesc {
+    pub const fn pass() -> Self {
+        Self {
+            color: TevStageColor::pass(),
+            alpha: TevStageAlpha::pass(),
+            tex: None,
+            channel: TevChannel::Null,
+        }
+    }
+
+    pub const fn new(color: TevStageColor, alpha: TevStageAlpha) -> Self {
+        Self {
+            color,
+            alpha,
+            ..Self::pass()
+        }
+    }
+
+    pub const fn color_only(color: TevStageColor) -> Self {
+        Self {
+            color,
+            ..Self::pass()
+        }
+    }
+
+    pub const fn alpha_only(alpha: TevStageAlpha) -> Self {
+        Self {
+            alpha,
+            ..Self::pass()
+        }
+    }
+
+    pub const fn with_tex(self, coord: u8, map: u8) -> Self {
+        Self {
+            tex: Some(TevTex { coord, map }),
+            ..self
+        }
+    }
+
+    pub const fn with_channel(self, channel: TevChannel) -> Self {
+        Self { channel, ..self }
+    }
+}
+
+/// A texture sampled by a TEV stage: which generated coordinate and which texture map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TevTex {
+    pub coord: u8,
+    pub map: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TexGenDesc {
+    pub src: TexGenSource,
+    pub projection: TexGenProjection,
+    /// A texture matrix from 0 to 9, or `None` for the identity.
+    pub tex_mtx: Option<u8>,
+}
+
+impl TexGenDesc {
+    /// Passes a vertex texture coordinate through untransformed.
+    pub const fn tex(index: u8) -> Self {
+        Self {
+            src: TexGenSource::Tex(index),
+            projection: TexGenProjection::St,
+            tex_mtx: None,
+        }
+    }
+
+    pub const fn with_src(self, src: TexGenSource) -> Self {
+        Self { src, ..self }
+    }
+
+    pub const fn with_projection(self, projection: TexGenProjection) -> Self {
+        Self { projection, ..self }
+    }
+
+    pub const fn with_tex_mtx(self, tex_mtx: u8) -> Self {
+        Self {
+            tex_mtx: Some(tex_mtx),
+            ..self
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TexGenSource {
+    Position,
+    Normal,
+    /// A vertex texture coordinate, from 0 to 7.
+    Tex(u8),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TexGenProjection {
+    /// A 2x4 matrix, giving s and t.
+    St,
+    /// A 3x4 matrix, giving s, t, and q. Sampling divides by q.
+    Stq,
+}
+
+pub trait ComponentIn: Copy {
+    const ZERO: Self;
+    const PREV: Self;
+}
+
+impl ComponentIn for TevColorIn {
+    const ZERO: Self = Self::Constant0;
+    const PREV: Self = Self::PrevColor;
+}
+
+impl ComponentIn for TevAlphaIn {
+    const ZERO: Self = Self::Constant0;
+    const PREV: Self = Self::PrevAlpha;
+}
+
+pub trait ComponentKonst: Copy {}
+
+impl ComponentKonst for TevColorKonst {}
+
+impl ComponentKonst for TevAlphaKonst {}
+
+pub type TevStageColor = TevStageComponent<TevColorIn, TevColorKonst>;
+pub type TevStageAlpha = TevStageComponent<TevAlphaIn, TevAlphaKonst>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TevStageComponent<Input: ComponentIn, Konst: ComponentKonst> {
+    pub inputs: [Input; 4],
+    pub op: TevOp,
+    pub bias: TevBias,
+    pub scale: TevScale,
+    pub clamp: bool,
+    pub dst: TevReg,
+    pub konst_sel: Option<Konst>,
+}
+
+impl<Input: ComponentIn, Konst: ComponentKonst> TevStageComponent<Input, Konst> {
+    pub const fn zero() -> Self {
+        Self {
+            inputs: [Input::ZERO; 4],
+            op: TevOp::Add,
+            bias: TevBias::Zero,
+            scale: TevScale::K1,
+            clamp: true,
+            dst: TevReg::Prev,
+            konst_sel: None,
+        }
+    }
+
+    pub const fn pass() -> Self {
+        Self::just(Input::PREV)
+    }
+
+    pub const fn just(input_10bit: Input) -> Self {
+        Self {
+            inputs: [Input::ZERO, Input::ZERO, Input::ZERO, input_10bit],
+            ..Self::zero()
+        }
+    }
+
+    pub const fn add(a_10bit: Input, b: Input) -> Self {
+        Self {
+            inputs: [b, Input::ZERO, Input::ZERO, a_10bit],
+            ..Self::zero()
+        }
+    }
+
+    pub const fn sub(a_10bit: Input, b: Input) -> Self {
+        Self {
+            inputs: [b, Input::ZERO, Input::ZERO, a_10bit],
+            op: TevOp::Sub,
+            ..Self::zero()
+        }
+    }
+
+    pub const fn mul(a: Input, b: Input) -> Self {
+        Self {
+            inputs: [Input::ZERO, a, b, Input::ZERO],
+            ..Self::zero()
+        }
+    }
+
+    /// Computes a + b * c.
+    pub const fn add_mul(a_10bit: Input, b: Input, c: Input) -> Self {
+        Self {
+            inputs: [Input::ZERO, b, c, a_10bit],
+            ..Self::zero()
+        }
+    }
+
+    /// Computes a - b * c.
+    pub const fn sub_mul(a_10bit: Input, b: Input, c: Input) -> Self {
+        Self {
+            inputs: [Input::ZERO, b, c, a_10bit],
+            op: TevOp::Sub,
+            ..Self::zero()
+        }
+    }
+
+    /// Computes (1 - c) * a + c * b.
+    pub const fn mix(a: Input, b: Input, c: Input) -> Self {
+        Self {
+            inputs: [a, b, c, Input::ZERO],
+            ..Self::zero()
+        }
+    }
+
+    /// Computes (a.r > b.r ? c : 0).
+    pub const fn comp_r8_gt(a: Input, b: Input, c: Input) -> Self {
+        Self::comp_r8_gt_add(a, b, c, Input::ZERO)
+    }
+
+    /// Computes (a.r > b.r ? c : 0) + d.
+    pub const fn comp_r8_gt_add(a: Input, b: Input, c: Input, d_10bit: Input) -> Self {
+        Self {
+            inputs: [a, b, c, d_10bit],
+            op: TevOp::CompR8Gt,
+            ..Self::zero()
+        }
+    }
+
+    pub const fn with_bias(self, bias: TevBias) -> Self {
+        Self { bias, ..self }
+    }
+
+    pub const fn with_scale(self, scale: TevScale) -> Self {
+        Self { scale, ..self }
+    }
+
+    pub const fn with_clamp(self, clamp: bool) -> Self {
+        Self { clamp, ..self }
+    }
+
+    pub const fn with_dst(self, dst: TevReg) -> Self {
+        Self { dst, ..self }
+    }
+
+    pub const fn with_konst_sel(self, konst_sel: Option<Konst>) -> Self {
+        Self { konst_sel, ..self }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum TevColorIn {
+    PrevColor = 0,
+    PrevAlpha = 1,
+    Reg0Color = 2,
+    Reg0Alpha = 3,
+    Reg1Color = 4,
+    Reg1Alpha = 5,
+    Reg2Color = 6,
+    Reg2Alpha = 7,
+    TexColor = 8,
+    TexAlpha = 9,
+    RasColor = 10,
+    RasAlpha = 11,
+    Constant1 = 12,
+    Constant1_2 = 13,
+    Konst = 14,
+    Constant0 = 15,
+}
+
+impl TevColorIn {
+    fn glsl(self) -> &'static str {
+        match self {
+            Self::PrevColor => "prev.rgb",
+            Self::PrevAlpha => "vec3(prev.a)",
+            Self::Reg0Color => "reg0.rgb",
+            Self::Reg0Alpha => "vec3(reg0.a)",
+            Self::Reg1Color => "reg1.rgb",
+            Self::Reg1Alpha => "vec3(reg1.a)",
+            Self::Reg2Color => "reg2.rgb",
+            Self::Reg2Alpha => "vec3(reg2.a)",
+            Self::TexColor => "tex.rgb",
+            Self::TexAlpha => "vec3(tex.a)",
+            Self::RasColor => "ras.rgb",
+            Self::RasAlpha => "vec3(ras.a)",
+            Self::Constant1 => "vec3(1.0)",
+            Self::Constant1_2 => "vec3(0.5)",
+            Self::Konst => "konst_color",
+            Self::Constant0 => "vec3(0.0)",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum TevAlphaIn {
+    PrevAlpha = 0,
+    Reg0Alpha = 1,
+    Reg1Alpha = 2,
+    Reg2Alpha = 3,
+    TexAlpha = 4,
+    RasAlpha = 5,
+    Konst = 6,
+    Constant0 = 7,
+}
+
+impl TevAlphaIn {
+    fn glsl(self) -> &'static str {
+        match self {
+            Self::PrevAlpha => "prev.a",
+            Self::Reg0Alpha => "reg0.a",
+            Self::Reg1Alpha => "reg1.a",
+            Self::Reg2Alpha => "reg2.a",
+            Self::TexAlpha => "tex.a",
+            Self::RasAlpha => "ras.a",
+            Self::Konst => "konst_alpha",
+            Self::Constant0 => "0.0",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum TevOp {
+    Add = 0,
+    Sub = 1,
+    CompR8Gt = 8,
+    CompR8Eq = 9,
+    CompGr16Gt = 10,
+    CompGr16Eq = 11,
+    CompBgr24Gt = 12,
+    CompBgr24Eq = 13,
+    CompRgb8OrA8Gt = 14,
+    CompRgb8OrA8Eq = 15,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum TevBias {
+    Zero = 0,
+    AddHalf = 1,
+    SubHalf = 2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum TevScale {
+    K1 = 0,
+    K2 = 1,
+    K4 = 2,
+    K1_2 = 3,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum TevReg {
+    Prev = 0,
+    Reg0 = 1,
+    Reg1 = 2,
+    Reg2 = 3,
+}
+
+impl TevReg {
+    fn glsl(self) -> &'static str {
+        match self {
+            Self::Prev => "prev",
+            Self::Reg0 => "reg0",
+            Self::Reg1 => "reg1",
+            Self::Reg2 => "reg2",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum TevColorKonst {
+    Constant1 = 0x00,
+    Constant7_8 = 0x01,
+    Constant3_4 = 0x02,
+    Constant5_8 = 0x03,
+    Constant1_2 = 0x04,
+    Constant3_8 = 0x05,
+    Constant1_4 = 0x06,
+    Constant1_8 = 0x07,
+    K0Rgb = 0x0c,
+    K1Rgb = 0x0d,
+    K2Rgb = 0x0e,
+    K3Rgb = 0x0f,
+    K0R = 0x10,
+    K1R = 0x11,
+    K2R = 0x12,
+    K3R = 0x13,
+    K0G = 0x14,
+    K1G = 0x15,
+    K2G = 0x16,
+    K3G = 0x17,
+    K0B = 0x18,
+    K1B = 0x19,
+    K2B = 0x1a,
+    K3B = 0x1b,
+    K0A = 0x1c,
+    K1A = 0x1d,
+    K2A = 0x1e,
+    K3A = 0x1f,
+}
+
+impl TevColorKonst {
+    fn glsl(self) -> String {
+        let value = self as u8;
+        match value {
+            0x00..=0x07 => format!("vec3({})", konst_fraction(value)),
+            0x0c..=0x0f => format!("konst[{}].rgb", value - 0x0c),
+            _ => format!("vec3({})", konst_channel(value)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum TevAlphaKonst {
+    Constant1 = 0x00,
+    Constant7_8 = 0x01,
+    Constant3_4 = 0x02,
+    Constant5_8 = 0x03,
+    Constant1_2 = 0x04,
+    Constant3_8 = 0x05,
+    Constant1_4 = 0x06,
+    Constant1_8 = 0x07,
+    K0R = 0x10,
+    K1R = 0x11,
+    K2R = 0x12,
+    K3R = 0x13,
+    K0G = 0x14,
+    K1G = 0x15,
+    K2G = 0x16,
+    K3G = 0x17,
+    K0B = 0x18,
+    K1B = 0x19,
+    K2B = 0x1a,
+    K3B = 0x1b,
+    K0A = 0x1c,
+    K1A = 0x1d,
+    K2A = 0x1e,
+    K3A = 0x1f,
+}
+
+impl TevAlphaKonst {
+    fn glsl(self) -> String {
+        let value = self as u8;
+        match value {
+            0x00..=0x07 => konst_fraction(value).to_string(),
+            _ => konst_channel(value),
+        }
+    }
+}
+
+/// The fixed konstant selections 0 through 7 count down from 1 in eighths.
+fn konst_fraction(value: u8) -> &'static str {
+    [
+        "1.0", "0.875", "0.75", "0.625", "0.5", "0.375", "0.25", "0.125",
+    ][value as usize]
+}
+
+/// The single-channel konstant selections from 0x10 cycle through the four konstant colors, then
+/// the four channels.
+fn konst_channel(value: u8) -> String {
+    let index = value - 0x10;
+    format!(
+        "konst[{}].{}",
+        index % 4,
+        ['r', 'g', 'b', 'a'][index as usize / 4]
+    )
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum TevChannel {
+    Color0 = 0,
+    Color1 = 1,
+    Alpha0 = 2,
+    Alpha1 = 3,
+    Color0A0 = 4,
+    Color1A1 = 5,
+    Zero = 6,
+    AlphaBump = 7,
+    AlphaBumpN = 8,
+    Null = 0xff,
+}
+
+impl TevChannel {
+    /// Returns which rasterized color channel this reads, if any.
+    pub const fn chan_index(self) -> Option<u8> {
+        match self {
+            Self::Color0 | Self::Alpha0 | Self::Color0A0 => Some(0),
+            Self::Color1 | Self::Alpha1 | Self::Color1A1 => Some(1),
+            Self::Zero | Self::AlphaBump | Self::AlphaBumpN | Self::Null => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHADERS: [Shader; 4] = [
+        Shader::LightmappedGeneric {
+            base_alpha: ShaderBaseAlpha::BaseTextureAlpha,
+            env_map: None,
+        },
+        Shader::LightmappedGeneric {
+            base_alpha: ShaderBaseAlpha::AuxTextureAlpha,
+            env_map: Some(ShaderEnvMap {
+                mask: ShaderEnvMapMask::AuxTextureIntensity,
+            }),
+        },
+        Shader::UnlitGeneric,
+        Shader::WorldVertexTransition,
+    ];
+
+    #[test]
+    fn material_programs_validate() {
+        for shader in SHADERS {
+            assert_eq!(shader.program().validate(), Ok(()), "{shader:?}");
+        }
+    }
+
+    #[test]
+    fn validate_rejects_missing_tex_gen() {
+        const PROGRAM: TevProgram = TevProgram {
+            stages: &[
+                TevStageDesc::color_only(TevStageColor::just(TevColorIn::TexColor)).with_tex(1, 0),
+            ],
+            tex_gens: &[TexGenDesc::tex(0)],
+            num_chans: 0,
+        };
+        assert_eq!(
+            PROGRAM.validate(),
+            Err(TevProgramError::TexCoordOutOfRange { stage: 0 }),
+        );
+    }
+
+    #[test]
+    fn glsl_declares_what_stages_read() {
+        let program = Shader::WorldVertexTransition.program();
+        let vertex = program.glsl_vertex();
+        let fragment = program.glsl_fragment();
+
+        assert!(vertex.contains("in vec4 color0;"));
+        assert!(vertex.contains("tex_coord2 = vec3(vec4(tex2, 1.0, 1.0).xy, 1.0);"));
+        for map in 0..3 {
+            assert!(fragment.contains(&format!("uniform sampler2D tex_map{map};")));
+        }
+        assert!(!fragment.contains("tex_map3"));
+        assert!(fragment.contains("prev.rgb = clamp((cd + mix(ca, cb, cc)) * 2.0, 0.0, 1.0);"));
+    }
+}
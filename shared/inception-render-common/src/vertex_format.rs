@@ -1,16 +1,293 @@
-#[derive(Clone, Copy, Debug)]
+#[cfg(feature = "std")]
+use std::io::{self, Seek, Write};
+
+use bytemuck::{Pod, Zeroable};
+
+#[cfg(feature = "std")]
+use crate::map_data::WriteTo;
+use crate::pipeline_state::{VertexAttribute, VertexInput};
+
+/// Component counts, matching the GX_POS_*, GX_NRM_*, GX_CLR_*, and GX_TEX_* constants.
+pub const POS_XY: u8 = 0;
+pub const POS_XYZ: u8 = 1;
+pub const NRM_XYZ: u8 = 0;
+pub const CLR_RGB: u8 = 0;
+pub const TEX_ST: u8 = 1;
+
+/// Component types, matching the GX_U8 ... GX_F32 and GX_RGB8 constants.
+pub const U8: u8 = 0;
+pub const S8: u8 = 1;
+pub const U16: u8 = 2;
+pub const S16: u8 = 3;
+pub const F32: u8 = 4;
+pub const RGB8: u8 = 1;
+
+/// Every vertex layout the packer emits or the runtime draws with. The discriminant is also the
+/// GX vertex format slot (GX_VTXFMT0 ... GX_VTXFMT7) the layout is loaded into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
 pub enum VertexFormat {
-    /// GX_VTXFMT0
-    /// - GX_VA_POS, GX_POS_XYZ, GX_F32
-    /// - GX_VA_NRM, GX_NRM_XYZ, GX_S8
-    /// - GX_VA_TEX0, GX_TEX_ST, GX_U16, frac=15
-    /// - GX_VA_TEX1, GX_TEX_ST, GX_S16, frac=8
+    /// World brush faces. Position, normal, and base texture coordinates are indexed; lightmap
+    /// coordinates are direct.
     Brush = 0,
 
-    /// GX_VTXFMT1
-    /// - GX_VA_POS,  GX_POS_XYZ, GX_F32
-    /// - GX_VA_CLR0, GX_CLR_RGB, GX_RGB8
-    /// - GX_VA_TEX0, GX_TEX_ST,  GX_F32
+    /// Displacement quads. Position, vertex color (the blend alpha), and both base texture
+    /// coordinates are indexed; lightmap coordinates are direct.
     Displacement = 1,
+
+    /// Skybox faces, drawn in immediate mode.
+    Skybox = 2,
+
+    /// Screen-space colored quads for the debug overlay.
+    DebugColor = 3,
+
+    /// Screen-space textured quads for the debug overlay.
+    DebugTexture = 4,
+
+    /// Overlays, decals, and static props. Every attribute is direct: position, baked lighting
+    /// as a vertex color, and base texture coordinates.
+    Overlay = 5,
+
+    /// Screen-space glyph quads. Texture coordinates are in 64ths of the font atlas.
+    Text = 6,
+
+    /// Studio model meshes. Every attribute is direct and floating point: position, normal, and
+    /// base texture coordinates.
+    Model = 7,
+}
+
+impl VertexFormat {
+    pub const fn descriptor(self) -> VertexFormatDescriptor {
+        use VertexAttribute::*;
+        use VertexInput::*;
+
+        let attr = VertexAttributeDescriptor::new;
+        match self {
+            Self::Brush => VertexFormatDescriptor::new(
+                self,
+                &[
+                    attr(Pos, Index16, POS_XYZ, F32, 0),
+                    attr(Nrm, Index16, NRM_XYZ, S8, 0),
+                    attr(Tex0, Direct, TEX_ST, U16, 15),
+                    attr(Tex1, Index16, TEX_ST, U16, 8),
+                ],
+            ),
+            Self::Displacement => VertexFormatDescriptor::new(
+                self,
+                &[
+                    attr(Pos, Index16, POS_XYZ, F32, 0),
+                    attr(Clr0, Index16, CLR_RGB, RGB8, 0),
+                    attr(Tex0, Direct, TEX_ST, U16, 15),
+                    attr(Tex1, Index16, TEX_ST, U16, 8),
+                    attr(Tex2, Index16, TEX_ST, U16, 8),
+                ],
+            ),
+            Self::Skybox => VertexFormatDescriptor::new(
+                self,
+                &[
+                    attr(Pos, Direct, POS_XYZ, S8, 0),
+                    attr(Tex0, Direct, TEX_ST, U8, 0),
+                ],
+            ),
+            Self::DebugColor => VertexFormatDescriptor::new(
+                self,
+                &[
+                    attr(Pos, Direct, POS_XY, U16, 0),
+                    attr(Clr0, Direct, CLR_RGB, RGB8, 0),
+                ],
+            ),
+            Self::DebugTexture => VertexFormatDescriptor::new(
+                self,
+                &[
+                    attr(Pos, Direct, POS_XY, U16, 0),
+                    attr(Tex0, Direct, TEX_ST, U8, 0),
+                ],
+            ),
+            Self::Overlay => VertexFormatDescriptor::new(
+                self,
+                &[
+                    attr(Pos, Direct, POS_XYZ, F32, 0),
+                    attr(Clr0, Direct, CLR_RGB, RGB8, 0),
+                    attr(Tex0, Direct, TEX_ST, F32, 0),
+                ],
+            ),
+            Self::Text => VertexFormatDescriptor::new(
+                self,
+                &[
+                    attr(Pos, Direct, POS_XY, U16, 0),
+                    attr(Tex0, Direct, TEX_ST, U8, 6),
+                ],
+            ),
+            Self::Model => VertexFormatDescriptor::new(
+                self,
+                &[
+                    attr(Pos, Direct, POS_XYZ, F32, 0),
+                    attr(Nrm, Direct, NRM_XYZ, F32, 0),
+                    attr(Tex0, Direct, TEX_ST, F32, 0),
+                ],
+            ),
+        }
+    }
+}
+
+/// One GX_SetVtxDesc/GX_SetVtxAttrFmt pair. Fields hold raw GX enum values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct VertexAttributeDescriptor {
+    pub attribute: u8,
+    pub input: u8,
+    pub component_count: u8,
+    pub component_type: u8,
+    pub frac: u8,
+}
+
+impl VertexAttributeDescriptor {
+    pub const fn new(
+        attribute: VertexAttribute,
+        input: VertexInput,
+        component_count: u8,
+        component_type: u8,
+        frac: u8,
+    ) -> Self {
+        Self {
+            attribute: attribute as u8,
+            input: input as u8,
+            component_count,
+            component_type,
+            frac,
+        }
+    }
+
+    /// The number of bytes this attribute occupies in each vertex of a display list.
+    pub const fn size(&self) -> usize {
+        const INDEX8: u8 = VertexInput::Index8 as u8;
+        const INDEX16: u8 = VertexInput::Index16 as u8;
+        const POS: u8 = VertexAttribute::Pos as u8;
+        const NRM: u8 = VertexAttribute::Nrm as u8;
+        const CLR0: u8 = VertexAttribute::Clr0 as u8;
+        const CLR1: u8 = VertexAttribute::Clr1 as u8;
+
+        match self.input {
+            INDEX8 => return 1,
+            INDEX16 => return 2,
+            _ => (),
+        }
+        if matches!(self.attribute, CLR0 | CLR1) {
+            // Color types are whole-vertex formats rather than per-component types.
+            return match self.component_type {
+                0 | 3 => 2, // GX_RGB565, GX_RGBA4
+                1 | 4 => 3, // GX_RGB8, GX_RGBA6
+                _ => 4,     // GX_RGBX8, GX_RGBA8
+            };
+        }
+        let components = match self.attribute {
+            POS => 2 + self.component_count as usize,
+            NRM => 3,
+            _ => 1 + self.component_count as usize,
+        };
+        let component_size = match self.component_type {
+            U8 | S8 => 1,
+            U16 | S16 => 2,
+            _ => 4,
+        };
+        components * component_size
+    }
+}
+
+pub const MAX_VERTEX_ATTRIBUTES: usize = 6;
+
+/// A complete vertex layout, compact enough to store in map data so the runtime configures GX
+/// exactly the way the packer laid out the vertices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct VertexFormatDescriptor {
+    pub vertex_format: u8,
+    pub attribute_count: u8,
+    pub attributes: [VertexAttributeDescriptor; MAX_VERTEX_ATTRIBUTES],
+}
+
+impl VertexFormatDescriptor {
+    const fn new(vertex_format: VertexFormat, attributes: &[VertexAttributeDescriptor]) -> Self {
+        assert!(attributes.len() <= MAX_VERTEX_ATTRIBUTES);
+        let mut result = Self {
+            vertex_format: vertex_format as u8,
+            attribute_count: attributes.len() as u8,
+            attributes: [VertexAttributeDescriptor {
+                attribute: 0,
+                input: 0,
+                component_count: 0,
+                component_type: 0,
+                frac: 0,
+            }; MAX_VERTEX_ATTRIBUTES],
+        };
+        let mut index = 0;
+        while index < attributes.len() {
+            result.attributes[index] = attributes[index];
+            index += 1;
+        }
+        result
+    }
+
+    pub fn attributes(&self) -> &[VertexAttributeDescriptor] {
+        &self.attributes[..self.attribute_count as usize]
+    }
+
+    /// The number of bytes each vertex occupies in a display list.
+    pub const fn vertex_size(&self) -> usize {
+        let mut size = 0;
+        let mut index = 0;
+        while index < self.attribute_count as usize {
+            size += self.attributes[index].size();
+            index += 1;
+        }
+        size
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Seek + Write> WriteTo<W> for VertexFormatDescriptor {
+    fn write_to(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(bytemuck::bytes_of(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attribute_sizes() {
+        use VertexAttribute::*;
+        use VertexInput::*;
+
+        let attr = VertexAttributeDescriptor::new;
+        assert_eq!(attr(Pos, Index8, POS_XYZ, F32, 0).size(), 1);
+        assert_eq!(attr(Tex1, Index16, TEX_ST, U16, 8).size(), 2);
+        assert_eq!(attr(Pos, Direct, POS_XY, U16, 0).size(), 4);
+        assert_eq!(attr(Pos, Direct, POS_XYZ, S8, 0).size(), 3);
+        assert_eq!(attr(Pos, Direct, POS_XYZ, F32, 0).size(), 12);
+        assert_eq!(attr(Nrm, Direct, NRM_XYZ, S8, 0).size(), 3);
+        assert_eq!(attr(Nrm, Direct, NRM_XYZ, S16, 0).size(), 6);
+        assert_eq!(attr(Clr0, Direct, 0, 0, 0).size(), 2); // GX_RGB565
+        assert_eq!(attr(Clr0, Direct, CLR_RGB, RGB8, 0).size(), 3);
+        assert_eq!(attr(Clr1, Direct, 1, 5, 0).size(), 4); // GX_RGBA8
+        assert_eq!(attr(Tex0, Direct, TEX_ST, U8, 6).size(), 2);
+        assert_eq!(attr(Tex0, Direct, TEX_ST, F32, 0).size(), 8);
+    }
+
+    #[test]
+    fn vertex_sizes() {
+        for (format, size) in [
+            (VertexFormat::Brush, 10),
+            (VertexFormat::Displacement, 12),
+            (VertexFormat::Skybox, 5),
+            (VertexFormat::DebugColor, 7),
+            (VertexFormat::DebugTexture, 6),
+            (VertexFormat::Overlay, 23),
+            (VertexFormat::Text, 6),
+            (VertexFormat::Model, 32),
+        ] {
+            assert_eq!(format.descriptor().vertex_size(), size, "{:?}", format);
+        }
+    }
 }
@@ -7,7 +7,18 @@ use no_std_io::{NetError, Read, Write, WriteExt};
 
 use crate::buffer::Buffer;
 
+pub use crate::crc32::{crc32, Crc32};
+pub use crate::list::{list, EntryKind, ListEntry, Listing, Timestamp};
+#[cfg(feature = "tls")]
+pub use crate::tls::{
+    CipherSuite, NoVerify, TlsConfig, TlsStream, TlsVerifier, RECORD_READ_BUFFER_SIZE,
+};
+
 mod buffer;
+mod crc32;
+pub mod list;
+#[cfg(feature = "tls")]
+mod tls;
 
 pub struct FtpClient<S> {
     stream: S,
@@ -34,7 +45,9 @@ impl<S: Read + Write> FtpClient<S> {
         self.read_response()
     }
 
-    fn read_response(&mut self) -> Result<FtpResponse, NetError> {
+    /// Reads the next response without sending a command, such as the one that reports how a
+    /// transfer ended.
+    pub fn read_response(&mut self) -> Result<FtpResponse, NetError> {
         // Read until the response is complete.
         loop {
             self.response_buffer
@@ -248,6 +261,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_restart() {
+        let mut parser = FtpResponseParser::new();
+        assert_eq!(
+            parser.parse(b"350 Restart position accepted (1048576).\r\n"),
+            (42, Some(FtpResponse::Code(350))),
+        );
+    }
+
     #[test]
     fn parse_size() {
         let mut parser = FtpResponseParser::new();
@@ -0,0 +1,425 @@
+//! Directory listings, as sent over the data connection in response to `MLSD` or `LIST`.
+//!
+//! `MLSD` (RFC 3659) has a machine-readable format and is preferred. `LIST` has no specified
+//! format at all, so its lines are parsed heuristically, recognizing the Unix `ls -l` style that
+//! nearly every server uses and the DOS style that the rest use.
+
+/// What a listed name refers to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntryKind {
+    File,
+    Dir,
+    Link,
+    Other,
+}
+
+/// A modification time, in UTC for `MLSD` and in the server's local time for `LIST`.
+///
+/// Orders chronologically, except that times without a year sort before all others.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Timestamp {
+    /// Absent when a `LIST` line gives a time of day instead, which Unix-style servers do for
+    /// files modified in the last six months.
+    pub year: Option<u16>,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+/// One line of a directory listing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ListEntry<'a> {
+    pub name: &'a str,
+    pub kind: EntryKind,
+    pub size: Option<u64>,
+    pub modified: Option<Timestamp>,
+}
+
+/// Parses a listing received in response to `MLSD` or `LIST`, skipping lines that don't name an
+/// entry (such as `total` lines and the directory's own `.` and `..`).
+pub fn list(data: &[u8]) -> Listing<'_> {
+    Listing { data }
+}
+
+/// An iterator over the entries of a directory listing. See [`list`].
+pub struct Listing<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for Listing<'a> {
+    type Item = ListEntry<'a>;
+
+    fn next(&mut self) -> Option<ListEntry<'a>> {
+        while !self.data.is_empty() {
+            let (line, rest) = match self.data.iter().position(|&b| b == b'\n') {
+                Some(n) => (&self.data[..n], &self.data[n + 1..]),
+                None => (self.data, &[][..]),
+            };
+            self.data = rest;
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            let Ok(line) = core::str::from_utf8(line) else {
+                continue;
+            };
+            if let Some(entry) = parse_mlsd_line(line).or_else(|| parse_list_line(line)) {
+                if entry.name != "." && entry.name != ".." {
+                    return Some(entry);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Parses one line of an `MLSD` listing: `fact=value;fact=value; name`.
+///
+/// Entries for the listed directory itself and its parent (`type=cdir` and `type=pdir`) are
+/// rejected.
+pub fn parse_mlsd_line(line: &str) -> Option<ListEntry<'_>> {
+    let (facts, name) = line.split_once(' ')?;
+    if name.is_empty() || !facts.contains('=') {
+        return None;
+    }
+
+    let mut entry = ListEntry {
+        name,
+        kind: EntryKind::Other,
+        size: None,
+        modified: None,
+    };
+    for fact in facts.split(';').filter(|fact| !fact.is_empty()) {
+        let (key, value) = fact.split_once('=')?;
+        if key.eq_ignore_ascii_case("type") {
+            entry.kind = if value.eq_ignore_ascii_case("file") {
+                EntryKind::File
+            } else if value.eq_ignore_ascii_case("dir") {
+                EntryKind::Dir
+            } else if value.eq_ignore_ascii_case("cdir") || value.eq_ignore_ascii_case("pdir") {
+                return None;
+            } else if matches!(value.get(..8), Some(prefix) if prefix.eq_ignore_ascii_case("os.unix="))
+            {
+                // A common extension, e.g. `type=OS.unix=slink:/target`.
+                if value[8..].starts_with("slink") || value[8..].starts_with("symlink") {
+                    EntryKind::Link
+                } else {
+                    EntryKind::Other
+                }
+            } else {
+                EntryKind::Other
+            };
+        } else if key.eq_ignore_ascii_case("size") {
+            entry.size = Some(value.parse().ok()?);
+        } else if key.eq_ignore_ascii_case("modify") {
+            entry.modified = Some(parse_mlsd_time(value)?);
+        }
+    }
+    Some(entry)
+}
+
+/// Parses `YYYYMMDDHHMMSS`, ignoring any fraction of a second.
+fn parse_mlsd_time(value: &str) -> Option<Timestamp> {
+    let value = value.split_once('.').map_or(value, |(whole, _)| whole);
+    if value.len() != 14 {
+        return None;
+    }
+    Some(Timestamp {
+        year: Some(parse_digits(&value[0..4])?),
+        month: parse_digits(&value[4..6])?,
+        day: parse_digits(&value[6..8])?,
+        hour: parse_digits(&value[8..10])?,
+        minute: parse_digits(&value[10..12])?,
+        second: parse_digits(&value[12..14])?,
+    })
+}
+
+/// Parses one line of a `LIST` listing in either of the common formats:
+///
+/// ```text
+/// -rw-r--r--   1 owner    group        1234 Oct 15 12:34 name
+/// drwxr-xr-x   2 owner    group        4096 Oct 15  2025 name
+/// 10-15-26  12:34PM                 1234 name
+/// 10-15-26  12:34PM       <DIR>          name
+/// ```
+pub fn parse_list_line(line: &str) -> Option<ListEntry<'_>> {
+    parse_unix_list_line(line).or_else(|| parse_dos_list_line(line))
+}
+
+fn parse_unix_list_line(line: &str) -> Option<ListEntry<'_>> {
+    let (mode, rest) = next_field(line)?;
+    if mode.len() < 10 {
+        return None;
+    }
+    let kind = match mode.as_bytes()[0] {
+        b'-' => EntryKind::File,
+        b'd' => EntryKind::Dir,
+        b'l' => EntryKind::Link,
+        _ => EntryKind::Other,
+    };
+
+    // The link count, owner, and group are each optional depending on the server, and an owner or
+    // group can be named like a month, so find the size as the first number followed by a whole
+    // date.
+    let mut fields = rest;
+    let (size, modified, rest) = loop {
+        let (field, rest) = next_field(fields)?;
+        fields = rest;
+        if let Some(size) = parse_digits(field) {
+            if let Some((modified, rest)) = parse_unix_date(rest) {
+                break (size, modified, rest);
+            }
+        }
+    };
+    let name = rest.trim_start_matches(' ');
+    let name = match kind {
+        EntryKind::Link => name.split_once(" -> ").map_or(name, |(name, _)| name),
+        _ => name,
+    };
+    if name.is_empty() {
+        return None;
+    }
+
+    Some(ListEntry {
+        name,
+        kind,
+        size: Some(size),
+        modified: Some(modified),
+    })
+}
+
+/// Parses `Oct 15 12:34` or `Oct 15  2025` from the start of `fields`, returning the rest.
+fn parse_unix_date(fields: &str) -> Option<(Timestamp, &str)> {
+    let (month, rest) = next_field(fields)?;
+    let (day, rest) = next_field(rest)?;
+    let (year_or_time, rest) = next_field(rest)?;
+    let month = parse_month(month)?;
+    let day = parse_digits(day).filter(|day| (1..=31).contains(day))?;
+    let (year, hour, minute) = match year_or_time.split_once(':') {
+        Some((hour, minute)) => (None, parse_digits(hour)?, parse_digits(minute)?),
+        None => (Some(parse_digits(year_or_time)?), 0, 0),
+    };
+    Some((
+        Timestamp {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second: 0,
+        },
+        rest,
+    ))
+}
+
+fn parse_dos_list_line(line: &str) -> Option<ListEntry<'_>> {
+    let (date, rest) = next_field(line)?;
+    let (time, rest) = next_field(rest)?;
+    let (size_or_dir, rest) = next_field(rest)?;
+    let name = rest.trim_start_matches(' ');
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut date = date.split('-');
+    let month = parse_digits(date.next()?)?;
+    let day = parse_digits(date.next()?)?;
+    let year: u16 = parse_digits(date.next()?)?;
+    // Two-digit years are assumed to be in 1970..2070.
+    let year = match year {
+        0..=69 => 2000 + year,
+        70..=99 => 1900 + year,
+        year => year,
+    };
+
+    let (time, pm) = if let Some(time) = time.strip_suffix("PM") {
+        (time, true)
+    } else {
+        (time.strip_suffix("AM")?, false)
+    };
+    let (hour, minute) = time.split_once(':')?;
+    let hour: u8 = parse_digits(hour)?;
+    let hour = hour % 12 + if pm { 12 } else { 0 };
+
+    let (kind, size) = if size_or_dir == "<DIR>" {
+        (EntryKind::Dir, None)
+    } else {
+        (EntryKind::File, Some(size_or_dir.parse().ok()?))
+    };
+    Some(ListEntry {
+        name,
+        kind,
+        size,
+        modified: Some(Timestamp {
+            year: Some(year),
+            month,
+            day,
+            hour,
+            minute: parse_digits(minute)?,
+            second: 0,
+        }),
+    })
+}
+
+/// Splits off the first space-separated field, skipping leading spaces.
+fn next_field(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim_start_matches(' ');
+    match s.split_once(' ') {
+        Some((field, rest)) => Some((field, rest)),
+        None if !s.is_empty() => Some((s, "")),
+        None => None,
+    }
+}
+
+fn parse_month(s: &str) -> Option<u8> {
+    const MONTHS: [&str; 12] = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ];
+    MONTHS
+        .iter()
+        .position(|month| s.eq_ignore_ascii_case(month))
+        .map(|index| index as u8 + 1)
+}
+
+/// Parses a field made only of ASCII digits. Unlike `str::parse`, this rejects a leading `+`.
+fn parse_digits<T: core::str::FromStr>(s: &str) -> Option<T> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{list, parse_list_line, parse_mlsd_line, EntryKind, ListEntry, Timestamp};
+
+    fn timestamp(year: Option<u16>, month: u8, day: u8, hour: u8, minute: u8) -> Timestamp {
+        Timestamp {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second: 0,
+        }
+    }
+
+    #[test]
+    fn mlsd_file() {
+        assert_eq!(
+            parse_mlsd_line(
+                "type=file;Size=1234;modify=20261015123456.789;perm=r; d1_trainstation_01.dat"
+            ),
+            Some(ListEntry {
+                name: "d1_trainstation_01.dat",
+                kind: EntryKind::File,
+                size: Some(1234),
+                modified: Some(Timestamp {
+                    second: 56,
+                    ..timestamp(Some(2026), 10, 15, 12, 34)
+                }),
+            }),
+        );
+    }
+
+    #[test]
+    fn mlsd_skips_cdir_and_pdir() {
+        assert_eq!(parse_mlsd_line("type=cdir;modify=20261015123456; ."), None);
+        assert_eq!(parse_mlsd_line("type=pdir;modify=20261015123456; .."), None);
+        assert_eq!(
+            parse_mlsd_line("type=dir; name with spaces").map(|entry| (entry.name, entry.kind)),
+            Some(("name with spaces", EntryKind::Dir)),
+        );
+    }
+
+    #[test]
+    fn mlsd_multibyte_type() {
+        assert_eq!(
+            parse_mlsd_line("type=aaaaaaaé; name").map(|entry| entry.kind),
+            Some(EntryKind::Other),
+        );
+        assert_eq!(
+            parse_mlsd_line("type=OS.unix=slink:/target; name").map(|entry| entry.kind),
+            Some(EntryKind::Link),
+        );
+    }
+
+    #[test]
+    fn unix_list() {
+        assert_eq!(
+            parse_list_line("-rw-r--r--   1 owner    group     1234 Oct 15 12:34 name with spaces"),
+            Some(ListEntry {
+                name: "name with spaces",
+                kind: EntryKind::File,
+                size: Some(1234),
+                modified: Some(timestamp(None, 10, 15, 12, 34)),
+            }),
+        );
+        assert_eq!(
+            parse_list_line("drwxr-xr-x 2 0 0 4096 Jan  2  2025 maps"),
+            Some(ListEntry {
+                name: "maps",
+                kind: EntryKind::Dir,
+                size: Some(4096),
+                modified: Some(timestamp(Some(2025), 1, 2, 0, 0)),
+            }),
+        );
+        assert_eq!(
+            parse_list_line("lrwxrwxrwx 1 owner group 8 Mar 3 01:02 latest.dat -> c0a0.dat")
+                .map(|entry| (entry.name, entry.kind)),
+            Some(("latest.dat", EntryKind::Link)),
+        );
+    }
+
+    #[test]
+    fn unix_list_owner_named_like_a_month() {
+        assert_eq!(
+            parse_list_line("-rw-r--r-- 1 may jan 1234 Oct 15 12:34 c0a0.dat"),
+            Some(ListEntry {
+                name: "c0a0.dat",
+                kind: EntryKind::File,
+                size: Some(1234),
+                modified: Some(timestamp(None, 10, 15, 12, 34)),
+            }),
+        );
+        assert_eq!(
+            parse_list_line("-rw-r--r-- 1 0 may 5 Oct 15  2025 c0a0.dat")
+                .map(|entry| (entry.size, entry.modified)),
+            Some((Some(5), Some(timestamp(Some(2025), 10, 15, 0, 0)))),
+        );
+    }
+
+    #[test]
+    fn dos_list() {
+        assert_eq!(
+            parse_list_line("10-15-26  12:34PM                 1234 c0a0.dat"),
+            Some(ListEntry {
+                name: "c0a0.dat",
+                kind: EntryKind::File,
+                size: Some(1234),
+                modified: Some(timestamp(Some(2026), 10, 15, 12, 34)),
+            }),
+        );
+        assert_eq!(
+            parse_list_line("01-02-99  12:05AM       <DIR>          maps")
+                .map(|entry| (entry.kind, entry.modified)),
+            Some((EntryKind::Dir, Some(timestamp(Some(1999), 1, 2, 0, 5)))),
+        );
+    }
+
+    #[test]
+    fn listing_skips_noise() {
+        let data = b"total 8\r\n\
+            drwxr-xr-x 2 0 0 4096 Oct 15 12:34 .\r\n\
+            drwxr-xr-x 2 0 0 4096 Oct 15 12:34 ..\r\n\
+            -rw-r--r-- 1 0 0 1234 Oct 15 12:34 c0a0.dat\r\n\
+            type=file;size=5678; c0a1.dat\r\n";
+        let names: std::vec::Vec<_> = list(data).map(|entry| entry.name).collect();
+        assert_eq!(names, ["c0a0.dat", "c0a1.dat"]);
+    }
+
+    #[test]
+    fn timestamps_order_chronologically() {
+        assert!(timestamp(Some(2026), 1, 1, 0, 0) > timestamp(Some(2025), 12, 31, 23, 59));
+        assert!(timestamp(None, 12, 31, 23, 59) < timestamp(Some(2025), 1, 1, 0, 0));
+    }
+}
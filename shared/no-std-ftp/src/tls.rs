@@ -0,0 +1,243 @@
+//! Explicit FTPS (RFC 4217), enabled by the `tls` feature.
+//!
+//! Only the control connection is encrypted, which keeps the credentials off the wire. Data
+//! connections stay in the clear (`PROT C`) so callers can keep reading them as plain streams.
+
+use core::cell::RefCell;
+
+use embedded_tls::blocking::{Aes128GcmSha256, TlsConnection, TlsContext, TlsError};
+use no_std_io::{NetError, Read, Write};
+use rand_core::{CryptoRng, RngCore};
+
+use crate::buffer::Buffer;
+use crate::{FtpClient, FtpResponse, FtpResponseParser};
+
+pub use embedded_tls::blocking::{NoVerify, TlsConfig, TlsVerifier};
+
+/// The only cipher suite offered.
+pub type CipherSuite = Aes128GcmSha256;
+
+/// Enough to hold any TLS record the server may send. The write buffer only ever holds one
+/// command, so it can be much smaller.
+pub const RECORD_READ_BUFFER_SIZE: usize = 16640;
+
+impl<S: Read + Write> FtpClient<S> {
+    /// Upgrades the control connection with `AUTH TLS`. Call this right after connecting, before
+    /// logging in.
+    ///
+    /// `V` decides whether to trust the server's certificate. [`NoVerify`] trusts anything, which
+    /// still defeats passive sniffing but not an active attacker.
+    pub fn auth_tls<'a, 'v, R, V>(
+        mut self,
+        record_read_buf: &'a mut [u8],
+        record_write_buf: &'a mut [u8],
+        config: &'v TlsConfig<'v, CipherSuite>,
+        rng: &'v mut R,
+    ) -> Result<FtpClient<TlsStream<'a, S>>, NetError>
+    where
+        R: CryptoRng + RngCore,
+        V: TlsVerifier<'v, CipherSuite>,
+    {
+        match self.send(b"AUTH TLS\r\n")? {
+            FtpResponse::Code(234) => (), // Security data exchange complete.
+            resp => return Err(unexpected("AUTH TLS", resp)),
+        }
+        if !self.response_buffer.get().is_empty() {
+            // Anything the server sent before the handshake could have been injected.
+            return Err(NetError::Unexpected {
+                function: "AUTH TLS",
+                ret: -1,
+            });
+        }
+
+        let mut connection = TlsConnection::new(
+            StreamAdapter(self.stream),
+            record_read_buf,
+            record_write_buf,
+        );
+        connection
+            .open::<R, V>(TlsContext::new(config, rng))
+            .map_err(tls_error("TLS handshake"))?;
+
+        let mut client = FtpClient {
+            stream: TlsStream {
+                connection: RefCell::new(connection),
+            },
+            response_buffer: Buffer::new(),
+            response_parser: FtpResponseParser::new(),
+        };
+        match client.send(b"PBSZ 0\r\n")? {
+            FtpResponse::Code(200) => (),
+            resp => return Err(unexpected("PBSZ", resp)),
+        }
+        match client.send(b"PROT C\r\n")? {
+            FtpResponse::Code(200) => (),
+            resp => return Err(unexpected("PROT", resp)),
+        }
+        Ok(client)
+    }
+}
+
+fn unexpected(function: &'static str, resp: FtpResponse) -> NetError {
+    NetError::Unexpected {
+        function,
+        ret: match resp {
+            FtpResponse::Code(code) => code as i32,
+            _ => -1,
+        },
+    }
+}
+
+fn tls_error(function: &'static str) -> impl FnOnce(TlsError) -> NetError {
+    move |e| match e {
+        TlsError::Io(_) | TlsError::ConnectionClosed => NetError::Disconnected,
+        _ => NetError::Unexpected { function, ret: -1 },
+    }
+}
+
+/// A TLS session over an underlying stream.
+pub struct TlsStream<'a, S: Read + Write> {
+    connection: RefCell<TlsConnection<'a, StreamAdapter<S>, CipherSuite>>,
+}
+
+impl<'a, S: Read + Write> Read for TlsStream<'a, S> {
+    fn read(&self, buf: &mut [u8]) -> Result<usize, NetError> {
+        match self.connection.borrow_mut().read(buf) {
+            Ok(n) => Ok(n),
+            Err(TlsError::ConnectionClosed) => Ok(0),
+            Err(e) => Err(tls_error("TLS read")(e)),
+        }
+    }
+}
+
+impl<'a, S: Read + Write> Write for TlsStream<'a, S> {
+    /// Sends `buf` in a record of its own. Commands are small and each one is answered before the
+    /// next, so there's nothing to gain by holding them back.
+    fn write(&self, buf: &[u8]) -> Result<usize, NetError> {
+        let mut connection = self.connection.borrow_mut();
+        let n = connection.write(buf).map_err(tls_error("TLS write"))?;
+        connection.flush().map_err(tls_error("TLS write"))?;
+        Ok(n)
+    }
+}
+
+/// Presents a stream to `embedded-tls`.
+struct StreamAdapter<S>(S);
+
+/// The underlying stream failed. `embedded-tls` keeps only an error's kind, so there's no point
+/// carrying the `NetError` along; the TLS layer reports it as a disconnection either way.
+#[derive(Debug)]
+struct StreamError;
+
+impl embedded_io::Error for StreamError {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        embedded_io::ErrorKind::Other
+    }
+}
+
+impl<S> embedded_io::Io for StreamAdapter<S> {
+    type Error = StreamError;
+}
+
+impl<S: Read> embedded_io::blocking::Read for StreamAdapter<S> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, StreamError> {
+        self.0.read(buf).map_err(|_| StreamError)
+    }
+}
+
+impl<S: Write> embedded_io::blocking::Write for StreamAdapter<S> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, StreamError> {
+        self.0.write(buf).map_err(|_| StreamError)
+    }
+
+    fn flush(&mut self) -> Result<(), StreamError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::RefCell;
+    use std::rc::Rc;
+    use std::vec::Vec;
+
+    use no_std_io::{NetError, Read, Write};
+    use rand_core::{CryptoRng, RngCore};
+
+    use super::{NoVerify, TlsConfig, RECORD_READ_BUFFER_SIZE};
+    use crate::FtpClient;
+
+    /// Plays back canned replies and records what the client sent.
+    struct ScriptedStream {
+        replies: RefCell<&'static [u8]>,
+        sent: Rc<RefCell<Vec<u8>>>,
+    }
+
+    impl Read for ScriptedStream {
+        fn read(&self, buf: &mut [u8]) -> Result<usize, NetError> {
+            let mut replies = self.replies.borrow_mut();
+            let n = buf.len().min(replies.len());
+            buf[..n].copy_from_slice(&replies[..n]);
+            *replies = &replies[n..];
+            Ok(n)
+        }
+    }
+
+    impl Write for ScriptedStream {
+        fn write(&self, buf: &[u8]) -> Result<usize, NetError> {
+            self.sent.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+    }
+
+    struct CountingRng(u64);
+
+    impl RngCore for CountingRng {
+        fn next_u32(&mut self) -> u32 {
+            self.next_u64() as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0 += 1;
+            self.0
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            rand_core::impls::fill_bytes_via_next(self, dest)
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl CryptoRng for CountingRng {}
+
+    #[test]
+    fn auth_tls_refused() {
+        let sent = Rc::default();
+        let stream = ScriptedStream {
+            replies: RefCell::new(b"220 (fake ftpd)\r\n534 TLS not available\r\n"),
+            sent: Rc::clone(&sent),
+        };
+        let client = FtpClient::new(stream).unwrap();
+        let mut read_buf = [0; RECORD_READ_BUFFER_SIZE];
+        let mut write_buf = [0; 1024];
+        let config = TlsConfig::new();
+        let result = client.auth_tls::<_, NoVerify>(
+            &mut read_buf,
+            &mut write_buf,
+            &config,
+            &mut CountingRng(0),
+        );
+        assert!(matches!(
+            result,
+            Err(NetError::Unexpected {
+                function: "AUTH TLS",
+                ret: 534,
+            }),
+        ));
+        assert_eq!(&*sent.borrow(), b"AUTH TLS\r\n");
+    }
+}
@@ -1,5 +1,7 @@
 #![no_std]
 
+use core::cell::Cell;
+
 #[derive(Debug)]
 pub enum NetError {
     Disconnected,
@@ -14,6 +16,13 @@ pub trait Write {
     fn write(&self, buf: &[u8]) -> Result<usize, NetError>;
 }
 
+/// A stream whose read position can be moved.
+pub trait Seek {
+    /// Moves the position to `pos` bytes from the start of the stream. Seeking past the end is not
+    /// an error, but reads from there return zero bytes.
+    fn seek(&self, pos: usize) -> Result<(), NetError>;
+}
+
 pub trait ReadExt: Read {
     fn read_all(&self, mut buf: &mut [u8]) -> Result<(), NetError> {
         while buf.len() > 0 {
@@ -22,6 +31,31 @@ pub trait ReadExt: Read {
         }
         Ok(())
     }
+
+    /// Reads from `self` until it ends, then from `next`. Like `std::io::Read::chain`.
+    fn chain<R: Read>(self, next: R) -> Chain<Self, R>
+    where
+        Self: Sized,
+    {
+        Chain {
+            first: self,
+            second: next,
+            first_done: Cell::new(false),
+        }
+    }
+
+    /// Skips `offset` bytes, then reads at most `len` bytes. With an offset of zero, this is like
+    /// `std::io::Read::take`.
+    fn window(self, offset: usize, len: usize) -> Window<Self>
+    where
+        Self: Sized,
+    {
+        Window {
+            inner: self,
+            skip: Cell::new(offset),
+            remaining: Cell::new(len),
+        }
+    }
 }
 
 impl<T: Read> ReadExt for T {}
@@ -37,3 +71,200 @@ pub trait WriteExt: Write {
 }
 
 impl<T: Write> WriteExt for T {}
+
+pub trait SeekExt: Read + Seek {
+    /// Fills `buf` with the bytes starting at `offset`, leaving the position just past them. Fails
+    /// with [`NetError::Disconnected`] if the stream ends first.
+    fn read_at(&self, offset: usize, mut buf: &mut [u8]) -> Result<(), NetError> {
+        self.seek(offset)?;
+        while !buf.is_empty() {
+            match self.read(buf)? {
+                0 => return Err(NetError::Disconnected),
+                n => buf = &mut buf[n..],
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Read + Seek> SeekExt for T {}
+
+impl<T: Read + ?Sized> Read for &T {
+    fn read(&self, buf: &mut [u8]) -> Result<usize, NetError> {
+        (**self).read(buf)
+    }
+}
+
+impl<T: Seek + ?Sized> Seek for &T {
+    fn seek(&self, pos: usize) -> Result<(), NetError> {
+        (**self).seek(pos)
+    }
+}
+
+/// Returned by [`ReadExt::chain`].
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+    first_done: Cell<bool>,
+}
+
+impl<A, B> Chain<A, B> {
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A: Read, B: Read> Read for Chain<A, B> {
+    fn read(&self, buf: &mut [u8]) -> Result<usize, NetError> {
+        if !self.first_done.get() {
+            match self.first.read(buf)? {
+                0 if !buf.is_empty() => self.first_done.set(true),
+                n => return Ok(n),
+            }
+        }
+        self.second.read(buf)
+    }
+}
+
+/// Returned by [`ReadExt::window`].
+pub struct Window<R> {
+    inner: R,
+    /// Bytes still to be discarded before the window begins.
+    skip: Cell<usize>,
+    /// Bytes left in the window.
+    remaining: Cell<usize>,
+}
+
+impl<R> Window<R> {
+    /// Bytes left to read before the window ends.
+    pub fn remaining(&self) -> usize {
+        self.remaining.get()
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for Window<R> {
+    fn read(&self, buf: &mut [u8]) -> Result<usize, NetError> {
+        let len = buf.len().min(self.remaining.get());
+        if len == 0 {
+            return Ok(0);
+        }
+
+        // Skip by reading into the caller's buffer, which is about to be overwritten anyway.
+        while self.skip.get() > 0 {
+            let n = self.skip.get().min(buf.len());
+            match self.inner.read(&mut buf[..n])? {
+                0 => return Ok(0),
+                n => self.skip.set(self.skip.get() - n),
+            }
+        }
+
+        let n = self.inner.read(&mut buf[..len])?;
+        self.remaining.set(self.remaining.get() - n);
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use super::{NetError, Read, ReadExt, Seek, SeekExt};
+
+    /// Yields at most `chunk` bytes per read, like a socket.
+    struct SliceReader<'a> {
+        data: &'a [u8],
+        pos: Cell<usize>,
+        chunk: usize,
+    }
+
+    impl<'a> SliceReader<'a> {
+        fn new(data: &'a [u8], chunk: usize) -> Self {
+            Self {
+                data,
+                pos: Cell::new(0),
+                chunk,
+            }
+        }
+    }
+
+    impl Read for SliceReader<'_> {
+        fn read(&self, buf: &mut [u8]) -> Result<usize, NetError> {
+            let rest = &self.data[self.pos.get()..];
+            let n = buf.len().min(rest.len()).min(self.chunk);
+            buf[..n].copy_from_slice(&rest[..n]);
+            self.pos.set(self.pos.get() + n);
+            Ok(n)
+        }
+    }
+
+    impl Seek for SliceReader<'_> {
+        fn seek(&self, pos: usize) -> Result<(), NetError> {
+            self.pos.set(pos.min(self.data.len()));
+            Ok(())
+        }
+    }
+
+    /// Reads until end of stream into `buf`, returning the number of bytes read.
+    fn read_to_end(r: &impl Read, buf: &mut [u8]) -> usize {
+        let mut len = 0;
+        loop {
+            match r.read(&mut buf[len..]).unwrap() {
+                0 => return len,
+                n => len += n,
+            }
+        }
+    }
+
+    #[test]
+    fn chain() {
+        let r = SliceReader::new(b"abc", 2).chain(SliceReader::new(b"defg", 3));
+        let mut buf = [0; 16];
+        let len = read_to_end(&r, &mut buf);
+        assert_eq!(&buf[..len], b"abcdefg");
+    }
+
+    #[test]
+    fn window() {
+        let r = SliceReader::new(b"0123456789", 3).window(2, 5);
+        let mut buf = [0; 16];
+        let len = read_to_end(&r, &mut buf);
+        assert_eq!(&buf[..len], b"23456");
+        assert_eq!(r.remaining(), 0);
+    }
+
+    #[test]
+    fn window_past_end() {
+        let r = SliceReader::new(b"0123", 3).window(2, 5);
+        let mut buf = [0; 16];
+        let len = read_to_end(&r, &mut buf);
+        assert_eq!(&buf[..len], b"23");
+    }
+
+    #[test]
+    fn consecutive_windows_share_a_stream() {
+        let stream = SliceReader::new(b"headerbody", 4);
+        let mut buf = [0; 16];
+        let len = read_to_end(&(&stream).window(0, 6), &mut buf);
+        assert_eq!(&buf[..len], b"header");
+        let len = read_to_end(&(&stream).window(0, 4), &mut buf);
+        assert_eq!(&buf[..len], b"body");
+    }
+
+    #[test]
+    fn read_at() {
+        let r = SliceReader::new(b"0123456789", 3);
+        let mut buf = [0; 4];
+        r.read_at(5, &mut buf).unwrap();
+        assert_eq!(&buf, b"5678");
+        r.read_at(1, &mut buf).unwrap();
+        assert_eq!(&buf, b"1234");
+        assert!(matches!(
+            r.read_at(8, &mut buf),
+            Err(NetError::Disconnected)
+        ));
+    }
+}
@@ -1,6 +1,14 @@
 pub struct RecursiveIter<F: Frame> {
     context: F::Context,
     stack: Vec<F>,
+    depth_limit: Option<DepthLimit<F::Item>>,
+    max_depth_reached: usize,
+}
+
+struct DepthLimit<T> {
+    max_depth: usize,
+    /// Taken when the limit is hit.
+    exceeded: Option<T>,
 }
 
 pub struct Yield<T>(pub T);
@@ -83,8 +91,31 @@ impl<F: Frame> RecursiveIter<F> {
         Self {
             context,
             stack: vec![initial_frame],
+            depth_limit: None,
+            max_depth_reached: 1,
         }
     }
+
+    /// Limits the stack to `max_depth` frames, counting the initial frame. A call that would go
+    /// deeper abandons the whole iteration: `exceeded` is yielded in place of anything that call
+    /// would have produced, and then the iterator ends.
+    pub fn with_max_depth(mut self, max_depth: usize, exceeded: F::Item) -> Self {
+        self.depth_limit = Some(DepthLimit {
+            max_depth,
+            exceeded: Some(exceeded),
+        });
+        self
+    }
+
+    /// The number of frames currently on the stack.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// The most frames that have been on the stack at once.
+    pub fn max_depth_reached(&self) -> usize {
+        self.max_depth_reached
+    }
 }
 
 impl<F: Frame> Iterator for RecursiveIter<F> {
@@ -99,7 +130,14 @@ impl<F: Frame> Iterator for RecursiveIter<F> {
                         self.stack.pop();
                     }
                     if let Some(frame) = result.call {
+                        if let Some(limit) = &mut self.depth_limit {
+                            if self.stack.len() >= limit.max_depth {
+                                self.stack.clear();
+                                return limit.exceeded.take();
+                            }
+                        }
                         self.stack.push(frame);
+                        self.max_depth_reached = self.max_depth_reached.max(self.stack.len());
                     }
                     if let Some(item) = result.yield_ {
                         return Some(item);
@@ -110,3 +148,72 @@ impl<F: Frame> Iterator for RecursiveIter<F> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Call, EvalResult, Frame, RecursiveIter, Yield};
+
+    /// Recurses `n` times in tail position, then yields zero.
+    struct Countdown(u32);
+
+    impl Frame for Countdown {
+        type Item = Option<u32>;
+        type Context = ();
+
+        fn eval(&mut self, _: &mut ()) -> EvalResult<Self> {
+            match self.0 {
+                0 => Yield(Some(0)).with_return(true),
+                n => {
+                    self.0 = 0;
+                    Call(Countdown(n - 1)).with_return(true)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unlimited() {
+        let mut iter = RecursiveIter::new((), Countdown(9));
+        assert_eq!(iter.next(), Some(Some(0)));
+        assert_eq!(iter.next(), None);
+        // Each frame returns as it calls, so the stack never grows.
+        assert_eq!(iter.max_depth_reached(), 1);
+    }
+
+    /// Recurses `n` levels deep without yielding anything.
+    struct Descend(u32);
+
+    impl Frame for Descend {
+        type Item = Option<u32>;
+        type Context = ();
+
+        fn eval(&mut self, _: &mut ()) -> EvalResult<Self> {
+            match self.0 {
+                0 => EvalResult::return_(),
+                n => {
+                    self.0 = 0;
+                    Call(Descend(n - 1)).with_return(false)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn tracks_max_depth() {
+        let mut iter = RecursiveIter::new((), Descend(4));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.max_depth_reached(), 5);
+        assert_eq!(iter.depth(), 0);
+    }
+
+    #[test]
+    fn stops_at_max_depth() {
+        let mut iter = RecursiveIter::new((), Descend(4)).with_max_depth(5, None);
+        assert_eq!(iter.next(), None);
+
+        let mut iter = RecursiveIter::new((), Descend(1000)).with_max_depth(5, None);
+        assert_eq!(iter.next(), Some(None));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.max_depth_reached(), 5);
+    }
+}
@@ -1,5 +1,5 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::{self, Seek, SeekFrom, Write};
 use std::ops::{Deref, DerefMut};
 
@@ -121,3 +121,154 @@ impl<W> DerefMut for RelocationWriter<W> {
         &mut self.inner
     }
 }
+
+/// Writes named sections through a [`RelocationWriter`].
+///
+/// Each section is padded to its alignment and defines three symbols: `<name>_start` and
+/// `<name>_end` hold the positions of its first byte and one past its last byte, and `<name>_len`
+/// holds its length in bytes. Data written between sections belongs to none of them.
+pub struct SectionBuilder<W> {
+    writer: RelocationWriter<W>,
+    names: HashSet<Cow<'static, str>>,
+    open: Option<OpenSection>,
+}
+
+struct OpenSection {
+    name: Cow<'static, str>,
+    start: u64,
+}
+
+impl<W: Seek + Write> SectionBuilder<W> {
+    pub fn new(writer: RelocationWriter<W>) -> Self {
+        Self {
+            writer,
+            names: Default::default(),
+            open: None,
+        }
+    }
+
+    /// Pads to `align` bytes and starts a section.
+    ///
+    /// # Panics
+    ///
+    /// Panics if another section is open, a section by this name already exists, or `align` is
+    /// zero.
+    pub fn begin_section(&mut self, name: Cow<'static, str>, align: u64) -> io::Result<()> {
+        if let Some(open) = &self.open {
+            panic!(
+                "Section {:?} begun while section {:?} is open",
+                name, open.name,
+            );
+        }
+        if !self.names.insert(name.clone()) {
+            panic!("Duplicate section {:?}", name);
+        }
+        assert_ne!(align, 0);
+
+        while self.writer.stream_position()? % align != 0 {
+            self.writer.write_u8(0)?;
+        }
+        let start = self.writer.stream_position()?;
+        self.writer
+            .define_symbol(Cow::Owned(format!("{}_start", name)), start);
+        self.open = Some(OpenSection { name, start });
+        Ok(())
+    }
+
+    /// Ends the open section, defining its `_end` and `_len` symbols.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no section is open.
+    pub fn end_section(&mut self) -> io::Result<()> {
+        let OpenSection { name, start } = self.open.take().expect("No section is open");
+        let end = self.writer.stream_position()?;
+        self.writer
+            .define_symbol(Cow::Owned(format!("{}_end", name)), end);
+        self.writer
+            .define_symbol(Cow::Owned(format!("{}_len", name)), end - start);
+        Ok(())
+    }
+
+    /// Writes a whole section with `f`.
+    pub fn section(
+        &mut self,
+        name: Cow<'static, str>,
+        align: u64,
+        f: impl FnOnce(&mut RelocationWriter<W>) -> io::Result<()>,
+    ) -> io::Result<()> {
+        self.begin_section(name, align)?;
+        f(&mut self.writer)?;
+        self.end_section()
+    }
+
+    /// # Panics
+    ///
+    /// Panics if a section is still open, or under the same conditions as
+    /// [`RelocationWriter::finish`].
+    pub fn finish(self) -> io::Result<W> {
+        if let Some(open) = self.open {
+            panic!("Section {:?} was never ended", open.name);
+        }
+        self.writer.finish()
+    }
+}
+
+impl<W> Deref for SectionBuilder<W> {
+    type Target = RelocationWriter<W>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.writer
+    }
+}
+
+impl<W> DerefMut for SectionBuilder<W> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::io::{Cursor, Write};
+
+    use crate::{PointerFormat, RelocationWriter, SectionBuilder};
+
+    #[test]
+    fn sections_define_offset_symbols() {
+        let mut w = SectionBuilder::new(RelocationWriter::new(Cursor::new(Vec::new())));
+        w.write_pointer(PointerFormat::BigEndianU32, Cow::Borrowed("a_start"))
+            .unwrap();
+        w.write_pointer(PointerFormat::BigEndianU32, Cow::Borrowed("b_start"))
+            .unwrap();
+        w.write_pointer(PointerFormat::BigEndianU32, Cow::Borrowed("b_end"))
+            .unwrap();
+        w.write_pointer(PointerFormat::BigEndianU32, Cow::Borrowed("b_len"))
+            .unwrap();
+        w.section(Cow::Borrowed("a"), 1, |w| w.write_all(&[1, 2, 3]))
+            .unwrap();
+        w.section(Cow::Borrowed("b"), 8, |w| w.write_all(&[4, 5]))
+            .unwrap();
+
+        assert_eq!(
+            w.finish().unwrap().into_inner(),
+            [
+                0, 0, 0, 16, // a_start
+                0, 0, 0, 24, // b_start
+                0, 0, 0, 26, // b_end
+                0, 0, 0, 2, // b_len
+                1, 2, 3, 0, 0, 0, 0, 0, // a, padded
+                4, 5, // b
+            ],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate section")]
+    fn duplicate_section_panics() {
+        let mut w = SectionBuilder::new(RelocationWriter::new(Cursor::new(Vec::new())));
+        w.section(Cow::Borrowed("a"), 1, |_| Ok(())).unwrap();
+        w.section(Cow::Borrowed("a"), 1, |_| Ok(())).unwrap();
+    }
+}
@@ -1,14 +1,18 @@
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
 use anyhow::{anyhow, Context, Result};
+#[cfg(feature = "threads")]
+use rayon::prelude::*;
 
 use crate::asset::vmt::Vmt;
 use crate::asset::vtf::Vtf;
 use crate::file::FileLoader;
 use crate::vpk::path::VpkPath;
 
+pub mod dependency_graph;
+pub mod skybox;
 pub mod vmt;
 pub mod vtf;
 
@@ -16,11 +20,70 @@ pub trait Asset: Sized {
     fn from_data(loader: &AssetLoader, path: &VpkPath, data: Vec<u8>) -> Result<Rc<Self>>;
 }
 
+/// Loads and caches materials and textures.
+///
+/// By default every asset stays cached for the loader's lifetime. A budget set with
+/// [`AssetLoader::with_budget`] bounds the total size of cached asset files, evicting the least
+/// recently used assets that aren't pinned. Evicted assets stay alive as long as something else
+/// holds them, and are loaded again the next time they're requested.
 pub struct AssetLoader<'a> {
     material_loader: Rc<dyn FileLoader + 'a>,
     texture_loader: Rc<dyn FileLoader + 'a>,
-    material_assets: RefCell<HashMap<VpkPath, Option<Rc<Vmt>>>>,
-    texture_assets: RefCell<HashMap<VpkPath, Option<Rc<Vtf>>>>,
+    material_assets: RefCell<AssetCache<Vmt>>,
+    texture_assets: RefCell<AssetCache<Vtf>>,
+    pinned: RefCell<HashSet<VpkPath>>,
+    budget: Option<usize>,
+    cached_bytes: Cell<usize>,
+    clock: Cell<u64>,
+}
+
+struct AssetCache<T> {
+    entries: HashMap<VpkPath, CacheEntry<T>>,
+}
+
+struct CacheEntry<T> {
+    /// `None` while the asset is loading, to catch a recursive load.
+    asset: Option<Rc<T>>,
+    /// Size of the file the asset was loaded from.
+    size: usize,
+    last_used: u64,
+}
+
+impl<T> AssetCache<T> {
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Returns the last use time and path of the least recently used entry that may be evicted.
+    fn eviction_candidate(&self, pinned: &HashSet<VpkPath>) -> Option<(u64, VpkPath)> {
+        self.entries
+            .iter()
+            .filter(|(path, entry)| entry.asset.is_some() && !pinned.contains(*path))
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(path, entry)| (entry.last_used, path.clone()))
+    }
+
+    /// Removes an entry and returns its size.
+    fn remove(&mut self, path: &VpkPath) -> usize {
+        self.entries.remove(path).map_or(0, |entry| entry.size)
+    }
+
+    /// Removes every loaded entry matching a predicate and returns their total size. Entries that
+    /// are still loading are kept.
+    fn remove_loaded(&mut self, mut predicate: impl FnMut(&VpkPath) -> bool) -> usize {
+        let mut removed = 0;
+        self.entries.retain(|path, entry| {
+            if entry.asset.is_some() && predicate(path) {
+                removed += entry.size;
+                false
+            } else {
+                true
+            }
+        });
+        removed
+    }
 }
 
 impl<'a> AssetLoader<'a> {
@@ -31,52 +94,114 @@ impl<'a> AssetLoader<'a> {
         Self {
             material_loader,
             texture_loader,
-            material_assets: RefCell::new(HashMap::new()),
-            texture_assets: RefCell::new(HashMap::new()),
+            material_assets: RefCell::new(AssetCache::new()),
+            texture_assets: RefCell::new(AssetCache::new()),
+            pinned: RefCell::new(HashSet::new()),
+            budget: None,
+            cached_bytes: Cell::new(0),
+            clock: Cell::new(0),
         }
     }
 
+    /// Limits the total file size of cached materials and textures to `bytes`. Pinned assets
+    /// count against the budget but are never evicted, so they may push the total over it.
+    pub fn with_budget(mut self, bytes: usize) -> Self {
+        self.budget = Some(bytes);
+        self
+    }
+
+    /// Total file size of the cached materials and textures.
+    pub fn cached_bytes(&self) -> usize {
+        self.cached_bytes.get()
+    }
+
+    fn tick(&self) -> u64 {
+        let now = self.clock.get() + 1;
+        self.clock.set(now);
+        now
+    }
+
     fn get<T: Asset>(
         &self,
         loader: &(dyn FileLoader + 'a),
-        assets: &RefCell<HashMap<VpkPath, Option<Rc<T>>>>,
+        assets: &RefCell<AssetCache<T>>,
         path: &VpkPath,
     ) -> Result<Rc<T>> {
-        if assets.borrow().contains_key(path) {
-            Ok(Rc::clone(
-                assets
-                    .borrow()
-                    .get(path)
-                    .unwrap()
+        if let Some(entry) = assets.borrow_mut().entries.get_mut(path) {
+            entry.last_used = self.tick();
+            return Ok(Rc::clone(
+                entry
+                    .asset
                     .as_ref()
                     .unwrap_or_else(|| panic!("Recursive load of asset {}", path)),
-            ))
-        } else {
-            // Poison this entry to catch a recursive load of this asset.
-            assets.borrow_mut().insert(path.clone(), None);
-            drop(assets);
-
-            match (|| {
-                Ok(T::from_data(
-                    self,
-                    path,
-                    loader
-                        .load_file(path)?
-                        .ok_or_else(|| anyhow!("file not found: {}", path))?,
-                )
-                .with_context(|| format!("Error creating asset from data for {}", path))?)
-            })() {
-                Ok(asset) => {
-                    // Replace the poison entry with the loaded asset.
-                    *assets.borrow_mut().get_mut(path).unwrap() = Some(Rc::clone(&asset));
-                    Ok(asset)
-                }
-                Err(e) => {
-                    // Remove the poison entry.
-                    assets.borrow_mut().remove(path);
-                    Err(e)
+            ));
+        }
+
+        // Poison this entry to catch a recursive load of this asset.
+        assets.borrow_mut().entries.insert(
+            path.clone(),
+            CacheEntry {
+                asset: None,
+                size: 0,
+                last_used: self.tick(),
+            },
+        );
+
+        match (|| {
+            let data = loader
+                .load_file(path)?
+                .ok_or_else(|| anyhow!("file not found: {}", path))?;
+            let size = data.len();
+            let asset = T::from_data(self, path, data)
+                .with_context(|| format!("Error creating asset from data for {}", path))?;
+            Ok((asset, size))
+        })() {
+            Ok((asset, size)) => {
+                // Replace the poison entry with the loaded asset.
+                {
+                    let mut assets = assets.borrow_mut();
+                    let entry = assets.entries.get_mut(path).unwrap();
+                    entry.asset = Some(Rc::clone(&asset));
+                    entry.size = size;
+                    entry.last_used = self.tick();
                 }
+                self.cached_bytes.set(self.cached_bytes.get() + size);
+                self.evict();
+                Ok(asset)
             }
+            Err(e) => {
+                // Remove the poison entry.
+                assets.borrow_mut().remove(path);
+                Err(e)
+            }
+        }
+    }
+
+    /// Evicts least recently used assets until the cache fits in its budget or nothing else can
+    /// be evicted.
+    fn evict(&self) {
+        let budget = match self.budget {
+            Some(budget) => budget,
+            None => return,
+        };
+        let pinned = self.pinned.borrow();
+        let mut materials = self.material_assets.borrow_mut();
+        let mut textures = self.texture_assets.borrow_mut();
+        while self.cached_bytes.get() > budget {
+            let removed = match (
+                materials.eviction_candidate(&pinned),
+                textures.eviction_candidate(&pinned),
+            ) {
+                (Some((material_used, material_path)), Some((texture_used, _)))
+                    if material_used < texture_used =>
+                {
+                    materials.remove(&material_path)
+                }
+                (_, Some((_, texture_path))) => textures.remove(&texture_path),
+                (Some((_, material_path)), None) => materials.remove(&material_path),
+                (None, None) => break,
+            };
+            self.cached_bytes.set(self.cached_bytes.get() - removed);
         }
     }
 
@@ -95,4 +220,168 @@ impl<'a> AssetLoader<'a> {
     pub fn get_texture(&self, path: &VpkPath) -> Result<Rc<Vtf>> {
         self.get(&*self.texture_loader, &self.texture_assets, path)
     }
+
+    /// Loads every texture in `paths` that isn't already cached, so that later calls to
+    /// [`AssetLoader::get_texture`] for them are cache hits. Files are read on the calling thread.
+    /// With the `threads` feature they're decoded in parallel, and this returns once all of them
+    /// are done.
+    ///
+    /// Prefetched textures are subject to the budget like any other, so prefetching more than fits
+    /// just evicts the earliest ones again.
+    pub fn prefetch_textures<'p>(
+        &self,
+        paths: impl IntoIterator<Item = &'p VpkPath>,
+    ) -> Result<()> {
+        let mut files = Vec::new();
+        let mut seen = HashSet::new();
+        for path in paths {
+            if self.texture_assets.borrow().entries.contains_key(path) || !seen.insert(path) {
+                continue;
+            }
+            let data = self
+                .texture_loader
+                .load_file(path)?
+                .ok_or_else(|| anyhow!("file not found: {}", path))?;
+            files.push((path, data));
+        }
+
+        #[cfg(feature = "threads")]
+        let files = files.into_par_iter();
+        #[cfg(not(feature = "threads"))]
+        let files = files.into_iter();
+        let textures: Vec<_> = files
+            .map(|(path, data)| (path, data.len(), Vtf::parse(path, &data)))
+            .collect();
+
+        for (path, size, texture) in textures {
+            let texture =
+                texture.with_context(|| format!("Error creating asset from data for {}", path))?;
+            self.texture_assets.borrow_mut().entries.insert(
+                path.clone(),
+                CacheEntry {
+                    asset: Some(Rc::new(texture)),
+                    size,
+                    last_used: self.tick(),
+                },
+            );
+            self.cached_bytes.set(self.cached_bytes.get() + size);
+        }
+        self.evict();
+        Ok(())
+    }
+
+    /// Forgets every loaded material so the next request for each one reads it from its file
+    /// again. Loaded textures are kept.
+    pub fn invalidate_materials(&self) {
+        let removed = self.material_assets.borrow_mut().remove_loaded(|_| true);
+        self.cached_bytes.set(self.cached_bytes.get() - removed);
+    }
+
+    /// Forgets every loaded material and texture that isn't pinned.
+    pub fn clear(&self) {
+        let pinned = self.pinned.borrow();
+        let removed = self
+            .material_assets
+            .borrow_mut()
+            .remove_loaded(|path| !pinned.contains(path))
+            + self
+                .texture_assets
+                .borrow_mut()
+                .remove_loaded(|path| !pinned.contains(path));
+        self.cached_bytes.set(self.cached_bytes.get() - removed);
+    }
+
+    /// Keeps the material or texture at `path` cached once it's loaded, regardless of the budget
+    /// or calls to [`AssetLoader::clear`].
+    pub fn pin(&self, path: &VpkPath) {
+        self.pinned.borrow_mut().insert(path.clone());
+    }
+
+    /// Makes the asset at `path` evictable again.
+    pub fn unpin(&self, path: &VpkPath) {
+        self.pinned.borrow_mut().remove(path);
+        self.evict();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    use anyhow::Result;
+
+    use crate::asset::AssetLoader;
+    use crate::file::FileLoader;
+    use crate::vpk::path::VpkPath;
+
+    struct MapLoader(HashMap<VpkPath, &'static str>);
+
+    impl FileLoader for MapLoader {
+        fn load_file(&self, path: &VpkPath) -> Result<Option<Vec<u8>>> {
+            Ok(self.0.get(path).map(|data| data.as_bytes().to_vec()))
+        }
+    }
+
+    fn material(name: &str) -> VpkPath {
+        VpkPath::new_with_prefix_and_extension(name, "materials", "vmt")
+    }
+
+    /// A loader with three ten-byte materials named `a`, `b`, and `c`.
+    fn loader() -> AssetLoader<'static> {
+        let materials = MapLoader(
+            ["a", "b", "c"]
+                .into_iter()
+                .map(|name| (material(name), "Foo {    }"))
+                .collect(),
+        );
+        AssetLoader::new(Rc::new(materials), Rc::new(MapLoader(HashMap::new())))
+    }
+
+    fn is_cached(loader: &AssetLoader, name: &str) -> bool {
+        loader
+            .material_assets
+            .borrow()
+            .entries
+            .contains_key(&material(name))
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let loader = loader().with_budget(20);
+        let a = loader.get_material(&material("a")).unwrap();
+        loader.get_material(&material("b")).unwrap();
+        assert!(Rc::ptr_eq(
+            &a,
+            &loader.get_material(&material("a")).unwrap()
+        ));
+        loader.get_material(&material("c")).unwrap();
+
+        assert!(is_cached(&loader, "a"));
+        assert!(!is_cached(&loader, "b"));
+        assert!(is_cached(&loader, "c"));
+        assert_eq!(loader.cached_bytes(), 20);
+    }
+
+    #[test]
+    fn pinned_assets_survive_eviction_and_clear() {
+        let loader = loader().with_budget(10);
+        loader.pin(&material("a"));
+        loader.get_material(&material("a")).unwrap();
+        loader.get_material(&material("b")).unwrap();
+        assert!(is_cached(&loader, "a"));
+        assert!(!is_cached(&loader, "b"));
+
+        loader.unpin(&material("a"));
+        loader.get_material(&material("b")).unwrap();
+        assert!(!is_cached(&loader, "a"));
+        assert!(is_cached(&loader, "b"));
+
+        loader.pin(&material("c"));
+        loader.get_material(&material("c")).unwrap();
+        loader.clear();
+        assert!(!is_cached(&loader, "b"));
+        assert!(is_cached(&loader, "c"));
+        assert_eq!(loader.cached_bytes(), 10);
+    }
 }
@@ -0,0 +1,264 @@
+use std::collections::btree_map::Entry as MapEntry;
+use std::collections::BTreeMap;
+use std::str::from_utf8;
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::asset::vmt::{parse_vmt_path, parse_vtf_path};
+use crate::asset::AssetLoader;
+use crate::bsp::Bsp;
+use crate::properties::{Entry, KeyValue, Object};
+use crate::vpk::path::VpkPath;
+
+/// Material parameters whose values name a texture.
+const TEXTURE_PARAMS: &[&str] = &[
+    "$ambientoccltexture",
+    "$basetexture",
+    "$basetexture2",
+    "$blendmodulatetexture",
+    "$bumpmap",
+    "$bumpmap2",
+    "$decal",
+    "$detail",
+    "$detail2",
+    "$envmap",
+    "$envmapmask",
+    "$envmapmask2",
+    "$lightwarptexture",
+    "$normalmap",
+    "$phongexponenttexture",
+    "$selfillummask",
+    "$tintmasktexture",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssetKind {
+    Material,
+    Texture,
+}
+
+#[derive(Debug)]
+pub struct DependencyNode {
+    kind: AssetKind,
+    size: Option<usize>,
+    dependencies: Vec<VpkPath>,
+}
+
+impl DependencyNode {
+    pub fn kind(&self) -> AssetKind {
+        self.kind
+    }
+
+    /// The file's size in bytes, or `None` if it couldn't be found.
+    pub fn size(&self) -> Option<usize> {
+        self.size
+    }
+
+    pub fn is_missing(&self) -> bool {
+        self.size.is_none()
+    }
+
+    /// Materials and textures this asset references directly, in the order they appear.
+    pub fn dependencies(&self) -> &[VpkPath] {
+        &self.dependencies
+    }
+}
+
+/// Every material and texture a map references, found by walking its VMTs without building
+/// shaders from them.
+///
+/// Missing files are recorded as nodes rather than reported as errors so that a single walk can
+/// list all of them.
+#[derive(Debug)]
+pub struct DependencyGraph {
+    roots: Vec<VpkPath>,
+    nodes: BTreeMap<VpkPath, DependencyNode>,
+}
+
+impl DependencyGraph {
+    pub fn from_bsp(bsp: Bsp, loader: &AssetLoader) -> Result<Self> {
+        let mut roots = Vec::new();
+        for tex_data in bsp.tex_datas() {
+            let path = VpkPath::new_with_prefix_and_extension(
+                bsp.tex_data_strings()
+                    .get(tex_data.name_string_table_id as usize),
+                "materials",
+                "vmt",
+            );
+            if !roots.contains(&path) {
+                roots.push(path);
+            }
+        }
+        Self::from_materials(roots, loader)
+    }
+
+    pub fn from_materials(roots: Vec<VpkPath>, loader: &AssetLoader) -> Result<Self> {
+        let mut nodes = BTreeMap::new();
+        let mut queue: Vec<_> = roots
+            .iter()
+            .rev()
+            .map(|path| (path.clone(), AssetKind::Material))
+            .collect();
+        while let Some((path, kind)) = queue.pop() {
+            let vacant = match nodes.entry(path) {
+                MapEntry::Vacant(vacant) => vacant,
+                MapEntry::Occupied(_) => continue,
+            };
+            let file_loader = match kind {
+                AssetKind::Material => loader.material_loader(),
+                AssetKind::Texture => loader.texture_loader(),
+            };
+            let data = file_loader.load_file(vacant.key())?;
+
+            let mut dependencies = Vec::new();
+            if let (AssetKind::Material, Some(data)) = (kind, data.as_ref()) {
+                collect_material_dependencies(data, &mut dependencies)
+                    .with_context(|| format!("Walking material {}", vacant.key()))?;
+            }
+            queue.extend(dependencies.iter().rev().cloned());
+
+            vacant.insert(DependencyNode {
+                kind,
+                size: data.map(|data| data.len()),
+                dependencies: dependencies.into_iter().map(|(path, _)| path).collect(),
+            });
+        }
+
+        Ok(Self { roots, nodes })
+    }
+
+    /// The materials the graph was built from.
+    pub fn roots(&self) -> &[VpkPath] {
+        &self.roots
+    }
+
+    pub fn get(&self, path: &VpkPath) -> Option<&DependencyNode> {
+        self.nodes.get(path)
+    }
+
+    /// All nodes, ordered by path.
+    pub fn iter(&self) -> impl Iterator<Item = (&VpkPath, &DependencyNode)> {
+        self.nodes.iter()
+    }
+
+    pub fn missing(&self) -> impl Iterator<Item = &VpkPath> {
+        self.nodes
+            .iter()
+            .filter(|(_, node)| node.is_missing())
+            .map(|(path, _)| path)
+    }
+
+    /// The combined size of every file that was found.
+    pub fn total_size(&self) -> usize {
+        self.nodes.values().filter_map(|node| node.size).sum()
+    }
+}
+
+fn collect_material_dependencies(
+    data: &[u8],
+    dependencies: &mut Vec<(VpkPath, AssetKind)>,
+) -> Result<()> {
+    let root = crate::properties::vmt(from_utf8(data)?).map_err(|e| anyhow!("{}", e))?;
+    collect_entries(&root.entries, dependencies)
+}
+
+fn collect_entries(entries: &[Entry], dependencies: &mut Vec<(VpkPath, AssetKind)>) -> Result<()> {
+    for entry in entries {
+        match entry {
+            Entry::KeyValue(KeyValue { key, value }) => {
+                let key = key.to_ascii_lowercase();
+                let dependency = if key == "include" {
+                    Some((parse_vmt_path(value)?, AssetKind::Material))
+                } else if TEXTURE_PARAMS.contains(&key.as_str()) && !value.starts_with("_rt_") {
+                    parse_vtf_path(value)
+                        .with_context(|| key.clone())?
+                        .map(|path| (path, AssetKind::Texture))
+                } else {
+                    None
+                };
+                if let Some(dependency) = dependency {
+                    if !dependencies.iter().any(|(path, _)| *path == dependency.0) {
+                        dependencies.push(dependency);
+                    }
+                }
+            }
+            Entry::Object(Object { name, entries }) => {
+                let name = name.to_ascii_lowercase();
+                // Mirror the shader builders: patch blocks, the targeted dxlevel's fallback, and
+                // conditionals that match it are all treated as if inlined.
+                if name == "replace"
+                    || name == "insert"
+                    || name.ends_with("_dx6")
+                    || name.starts_with('<')
+                {
+                    collect_entries(entries, dependencies)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    use anyhow::Result;
+
+    use crate::asset::AssetLoader;
+    use crate::file::FileLoader;
+    use crate::vpk::path::VpkPath;
+
+    use super::{AssetKind, DependencyGraph};
+
+    struct MapLoader(HashMap<VpkPath, &'static str>);
+
+    impl FileLoader for MapLoader {
+        fn load_file(&self, path: &VpkPath) -> Result<Option<Vec<u8>>> {
+            Ok(self.0.get(path).map(|data| data.as_bytes().to_vec()))
+        }
+    }
+
+    fn material(name: &str) -> VpkPath {
+        VpkPath::new_with_prefix_and_extension(name, "materials", "vmt")
+    }
+
+    fn texture(name: &str) -> VpkPath {
+        VpkPath::new_with_prefix_and_extension(name, "materials", "vtf")
+    }
+
+    #[test]
+    fn walks_patches_and_fallbacks() {
+        let patch = "patch { include materials/floor.vmt replace { $envmap env_cubemap } }";
+        let base = "LightmappedGeneric {
+                    $basetexture floor
+                    $envmap env_cubemap
+                    LightmappedGeneric_DX9 { $bumpmap floor_normal }
+                    LightmappedGeneric_DX6 { $detail detail/noise }
+                    Proxies { AnimatedTexture { animatedtextureframenumvar $frame } }
+                }";
+        let materials = MapLoader(HashMap::from([
+            (material("maps/test/floor"), patch),
+            (material("floor"), base),
+        ]));
+        let textures = MapLoader(HashMap::from([(texture("floor"), "vtf")]));
+        let loader = AssetLoader::new(Rc::new(materials), Rc::new(textures));
+
+        let graph =
+            DependencyGraph::from_materials(vec![material("maps/test/floor")], &loader).unwrap();
+
+        let root = graph.get(&material("maps/test/floor")).unwrap();
+        assert_eq!(root.kind(), AssetKind::Material);
+        assert_eq!(root.dependencies(), &[material("floor")]);
+        assert_eq!(
+            graph.get(&material("floor")).unwrap().dependencies(),
+            &[texture("floor"), texture("detail/noise")],
+        );
+        assert_eq!(
+            graph.missing().collect::<Vec<_>>(),
+            vec![&texture("detail/noise")],
+        );
+        assert_eq!(graph.total_size(), patch.len() + base.len() + 3);
+    }
+}
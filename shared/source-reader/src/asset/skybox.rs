@@ -0,0 +1,163 @@
+use std::rc::Rc;
+
+use anyhow::{bail, Context, Result};
+
+use crate::asset::vmt::{Shader, Sky, UnlitGeneric, Vmt};
+use crate::asset::vtf::Vtf;
+use crate::asset::AssetLoader;
+use crate::vpk::path::VpkPath;
+
+/// One face of a 2D skybox, named by the suffix Source gives its material.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SkyboxFace {
+    Right,
+    Left,
+    Back,
+    Front,
+    Up,
+    Down,
+}
+
+impl SkyboxFace {
+    pub const ALL: [Self; 6] = [
+        Self::Right,
+        Self::Left,
+        Self::Back,
+        Self::Front,
+        Self::Up,
+        Self::Down,
+    ];
+
+    /// The faces the viewers draw, in the order the packer emits their textures. The bottom face
+    /// is always hidden below the world, and many skies don't ship one.
+    pub const DRAWN: [Self; 5] = [Self::Right, Self::Left, Self::Back, Self::Front, Self::Up];
+
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Self::Right => "rt",
+            Self::Left => "lf",
+            Self::Back => "bk",
+            Self::Front => "ft",
+            Self::Up => "up",
+            Self::Down => "dn",
+        }
+    }
+
+    /// The face's corners on a cube of half-width 1 in world space, wound to match texture
+    /// coordinates (0, 0), (1, 0), (1, 1), (0, 1).
+    pub fn corners(self) -> [[i8; 3]; 4] {
+        match self {
+            Self::Right => [[1, 1, 1], [1, -1, 1], [1, -1, -1], [1, 1, -1]],
+            Self::Left => [[-1, -1, 1], [-1, 1, 1], [-1, 1, -1], [-1, -1, -1]],
+            Self::Back => [[-1, 1, 1], [1, 1, 1], [1, 1, -1], [-1, 1, -1]],
+            Self::Front => [[1, -1, 1], [-1, -1, 1], [-1, -1, -1], [1, -1, -1]],
+            Self::Up => [[-1, 1, 1], [-1, -1, 1], [1, -1, 1], [1, 1, 1]],
+            Self::Down => [[-1, -1, -1], [-1, 1, -1], [1, 1, -1], [1, -1, -1]],
+        }
+    }
+
+    /// Path of this face's material for the worldspawn `skyname`.
+    pub fn material_path(self, skyname: &str) -> VpkPath {
+        VpkPath::new_with_prefix_and_extension(
+            &format!("{}{}", skyname, self.suffix()),
+            "materials/skybox",
+            "vmt",
+        )
+    }
+}
+
+pub struct SkyboxFaceAssets {
+    pub face: SkyboxFace,
+    pub material: Rc<Vmt>,
+    pub texture_path: VpkPath,
+    pub texture: Rc<Vtf>,
+}
+
+/// The materials and textures of a 2D skybox.
+pub struct Skybox {
+    faces: Vec<SkyboxFaceAssets>,
+}
+
+impl Skybox {
+    /// Loads every face of the skybox named by the worldspawn `skyname`. The faces in
+    /// [`SkyboxFace::DRAWN`] are required and the bottom face is loaded if present.
+    pub fn load(asset_loader: &AssetLoader, skyname: &str) -> Result<Self> {
+        let mut faces = Vec::new();
+        for face in SkyboxFace::ALL {
+            match Self::load_face(asset_loader, skyname, face) {
+                Ok(assets) => faces.push(assets),
+                Err(_) if face == SkyboxFace::Down => (),
+                Err(e) => {
+                    return Err(e)
+                        .with_context(|| format!("Loading skybox {:?} face {:?}", skyname, face))
+                }
+            }
+        }
+        Ok(Self { faces })
+    }
+
+    fn load_face(
+        asset_loader: &AssetLoader,
+        skyname: &str,
+        face: SkyboxFace,
+    ) -> Result<SkyboxFaceAssets> {
+        let material = asset_loader.get_material(&face.material_path(skyname))?;
+        let texture_path = match material.shader() {
+            Shader::UnlitGeneric(UnlitGeneric {
+                base_texture_path, ..
+            }) => base_texture_path.clone(),
+
+            Shader::Sky(Sky { base_texture_path }) => base_texture_path.clone(),
+
+            shader => bail!(
+                "Unexpected skybox shader {:?} in {}",
+                shader.name(),
+                material.path(),
+            ),
+        };
+        let texture = asset_loader.get_texture(&texture_path)?;
+        Ok(SkyboxFaceAssets {
+            face,
+            material,
+            texture_path,
+            texture,
+        })
+    }
+
+    pub fn face(&self, face: SkyboxFace) -> Option<&SkyboxFaceAssets> {
+        self.faces.iter().find(|assets| assets.face == face)
+    }
+
+    pub fn faces(&self) -> &[SkyboxFaceAssets] {
+        &self.faces
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SkyboxFace;
+
+    #[test]
+    fn faces_lie_on_their_cube_sides() {
+        for face in SkyboxFace::ALL {
+            let corners = face.corners();
+            let axis = (0..3)
+                .find(|&axis| corners.iter().all(|c| c[axis] == corners[0][axis]))
+                .unwrap();
+            let expected = match face {
+                SkyboxFace::Right | SkyboxFace::Left => 0,
+                SkyboxFace::Back | SkyboxFace::Front => 1,
+                SkyboxFace::Up | SkyboxFace::Down => 2,
+            };
+            assert_eq!(axis, expected, "{face:?}");
+        }
+    }
+
+    #[test]
+    fn material_path() {
+        assert_eq!(
+            SkyboxFace::Up.material_path("sky_day01_01").to_string(),
+            "materials/skybox/sky_day01_01up.vmt",
+        );
+    }
+}
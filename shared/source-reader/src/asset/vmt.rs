@@ -48,7 +48,7 @@ fn parse_vector_or_f32(s: &str) -> Result<f32> {
     }
 }
 
-fn parse_vtf_path(s: &str) -> Result<Option<VpkPath>> {
+pub(crate) fn parse_vtf_path(s: &str) -> Result<Option<VpkPath>> {
     match s {
         "env_cubemap" => Ok(None),
         _ => Ok(Some(VpkPath::new_with_prefix_and_extension(
@@ -59,7 +59,7 @@ fn parse_vtf_path(s: &str) -> Result<Option<VpkPath>> {
     }
 }
 
-fn parse_vmt_path(s: &str) -> Result<VpkPath> {
+pub(crate) fn parse_vmt_path(s: &str) -> Result<VpkPath> {
     Ok(VpkPath::new_with_prefix_and_extension(
         s,
         "materials",
@@ -74,6 +74,52 @@ fn parse_texture_transform(s: &str) -> Result<Mat2x3> {
     }
 }
 
+/// Scans a `Proxies` object for an `AnimatedTexture` proxy driving `$basetexture` and returns its
+/// frame rate. Other proxies are reported and ignored.
+fn parse_base_texture_frame_rate(
+    material_path: &VpkPath,
+    entries: Vec<Entry>,
+) -> Result<Option<f32>> {
+    let mut frame_rate = None;
+    for entry in entries {
+        match entry {
+            Entry::Object(Object { name, entries })
+                if name.eq_ignore_ascii_case("animatedtexture") =>
+            {
+                let mut var = None;
+                let mut rate = None;
+                for entry in entries {
+                    if let Entry::KeyValue(KeyValue { key, value }) = entry {
+                        match key.to_ascii_lowercase().as_str() {
+                            "animatedtexturevar" => var = Some(value.to_ascii_lowercase()),
+                            "animatedtextureframerate" => {
+                                rate = Some(parse_f32(value).context("animatedtextureframerate")?)
+                            }
+                            _ => (),
+                        }
+                    }
+                }
+                match (var.as_deref(), rate) {
+                    (Some("$basetexture"), Some(rate)) => frame_rate = Some(rate),
+                    _ => eprintln!(
+                        "WARNING: Unimplemented AnimatedTexture proxy for {:?} in {}",
+                        var, material_path,
+                    ),
+                }
+            }
+            Entry::Object(Object { name, .. }) => eprintln!(
+                "WARNING: Unimplemented material proxy {} in {}",
+                name, material_path,
+            ),
+            Entry::KeyValue(KeyValue { key, .. }) => eprintln!(
+                "WARNING: Unexpected material proxies key {} in {}",
+                key, material_path,
+            ),
+        }
+    }
+    Ok(frame_rate)
+}
+
 pub struct Vmt {
     path: VpkPath,
     shader: Shader,
@@ -88,6 +134,21 @@ impl Vmt {
         &self.shader
     }
 
+    /// Frames per second if the base texture is animated by an `AnimatedTexture` proxy.
+    pub fn base_texture_frame_rate(&self) -> Option<f32> {
+        match self.shader {
+            Shader::LightmappedGeneric(LightmappedGeneric {
+                base_texture_frame_rate,
+                ..
+            })
+            | Shader::UnlitGeneric(UnlitGeneric {
+                base_texture_frame_rate,
+                ..
+            }) => base_texture_frame_rate,
+            _ => None,
+        }
+    }
+
     pub fn texture_transform(&self) -> Mat2x3 {
         match self.shader {
             // TODO: Everywhere else, too.
@@ -212,6 +273,7 @@ struct LightmappedGenericBuilder {
     alpha_test_reference: f32,
     base_alpha_env_map_mask: bool,
     base_texture_path: Option<VpkPath>,
+    base_texture_frame_rate: Option<f32>,
     bump_map_path: Option<VpkPath>,
     decal_path: Option<VpkPath>,
     detail_path: Option<VpkPath>,
@@ -236,6 +298,7 @@ impl Default for LightmappedGenericBuilder {
             alpha_test: false,
             base_alpha_env_map_mask: false,
             base_texture_path: None,
+            base_texture_frame_rate: None,
             bump_map_path: None,
             decal_path: None,
             detail_path: None,
@@ -313,10 +376,8 @@ impl<'a> ShaderBuilder<'a> for LightmappedGenericBuilder {
             },
             Entry::Object(Object { name, entries }) => match name.to_ascii_lowercase().as_str() {
                 "proxies" => {
-                    eprintln!(
-                        "WARNING: Unimplemented material proxies in {}",
-                        material_path,
-                    );
+                    self.base_texture_frame_rate =
+                        parse_base_texture_frame_rate(material_path, entries)?
                 }
 
                 // Fallbacks above the targeted dxlevel. Safe to completely ignore.
@@ -398,6 +459,7 @@ impl<'a> ShaderBuilder<'a> for LightmappedGenericBuilder {
                 Some(x) => x,
                 None => bail!("LightmappedGeneric $basetexture was unset"),
             },
+            base_texture_frame_rate: self.base_texture_frame_rate,
             bump_map_path: self.bump_map_path.clone(),
             decal_path: self.decal_path.clone(),
             detail_path: self.detail_path.clone(),
@@ -419,6 +481,7 @@ impl<'a> ShaderBuilder<'a> for LightmappedGenericBuilder {
 
 struct UnlitGenericBuilder {
     base_texture_path: Option<VpkPath>,
+    base_texture_frame_rate: Option<f32>,
     self_illum: bool,
 }
 
@@ -426,6 +489,7 @@ impl Default for UnlitGenericBuilder {
     fn default() -> Self {
         Self {
             base_texture_path: None,
+            base_texture_frame_rate: None,
             self_illum: false,
         }
     }
@@ -445,14 +509,16 @@ impl<'a> ShaderBuilder<'a> for UnlitGenericBuilder {
                     key, material_path,
                 ),
             },
-            Entry::Object(Object { name, entries: _ }) => {
-                match name.to_ascii_lowercase().as_str() {
-                    name => eprintln!(
-                        "WARNING: Unexpected UnlitGeneric object {} in {}",
-                        name, material_path,
-                    ),
+            Entry::Object(Object { name, entries }) => match name.to_ascii_lowercase().as_str() {
+                "proxies" => {
+                    self.base_texture_frame_rate =
+                        parse_base_texture_frame_rate(material_path, entries)?
                 }
-            }
+                name => eprintln!(
+                    "WARNING: Unexpected UnlitGeneric object {} in {}",
+                    name, material_path,
+                ),
+            },
         }
         Ok(())
     }
@@ -463,6 +529,7 @@ impl<'a> ShaderBuilder<'a> for UnlitGenericBuilder {
                 Some(x) => x,
                 None => bail!("UnlitGeneric $basetexture was unset"),
             },
+            base_texture_frame_rate: self.base_texture_frame_rate,
             self_illum: self.self_illum,
         }))
     }
@@ -471,6 +538,8 @@ impl<'a> ShaderBuilder<'a> for UnlitGenericBuilder {
 #[derive(Debug)]
 pub struct UnlitGeneric {
     pub base_texture_path: VpkPath,
+    /// Set if an `AnimatedTexture` proxy cycles through the base texture's frames.
+    pub base_texture_frame_rate: Option<f32>,
     pub self_illum: bool,
 }
 
@@ -663,6 +732,8 @@ pub struct LightmappedGeneric {
     pub alpha_test: bool,
     pub base_alpha_env_map_mask: bool,
     pub base_texture_path: VpkPath,
+    /// Set if an `AnimatedTexture` proxy cycles through the base texture's frames.
+    pub base_texture_frame_rate: Option<f32>,
     pub bump_map_path: Option<VpkPath>,
     pub decal_path: Option<VpkPath>,
     pub detail_path: Option<VpkPath>,
@@ -710,7 +781,10 @@ impl<'a> ShaderBuilder<'a> for PatchBuilder<'a> {
             Some(include_path) => include_path,
             None => bail!("patch material without include parameter"),
         };
-        let data = loader.material_loader().load_file(&include_path)?.unwrap();
+        let data = match loader.material_loader().load_file(&include_path)? {
+            Some(data) => data,
+            None => bail!("patch include not found: {}", include_path),
+        };
         let mut builder = create_shader_builder(&include_path, &data)?;
 
         for entry in self.entries {
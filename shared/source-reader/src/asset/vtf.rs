@@ -14,8 +14,8 @@ pub struct Vtf {
     flags: u32,
     format: TextureFormat,
     face_count: usize,
-    /// `mips[mip_level][face_index]`
-    mips: Vec<Vec<TextureBuf>>,
+    /// `frames[frame_index][mip_level][face_index]`
+    frames: Vec<Vec<Vec<TextureBuf>>>,
 }
 
 #[derive(Clone, Copy)]
@@ -61,14 +61,30 @@ impl Vtf {
         self.face_count
     }
 
-    /// `mips()[mip_level][face_index]`
+    /// The number of animation frames. Every frame has the same dimensions and mip count.
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// The first frame's mips. `mips()[mip_level][face_index]`
     pub fn mips(&self) -> &[Vec<TextureBuf>] {
-        &self.mips
+        &self.frames[0]
+    }
+
+    /// `frame_mips(frame)[mip_level][face_index]`
+    pub fn frame_mips(&self, frame: usize) -> &[Vec<TextureBuf>] {
+        &self.frames[frame]
     }
 
+    /// Iterates over the first frame's mips, face by face.
     pub fn iter_face_mips(&self) -> impl Iterator<Item = VtfFaceMip> {
+        self.iter_frame_face_mips(0)
+    }
+
+    pub fn iter_frame_face_mips(&self, frame: usize) -> impl Iterator<Item = VtfFaceMip> {
         FaceMipIter {
-            vtf: self,
+            mips: &self.frames[frame],
+            face_count: self.face_count,
             face: 0,
             mip_level: 0,
         }
@@ -77,7 +93,15 @@ impl Vtf {
 
 impl Asset for Vtf {
     fn from_data(_loader: &AssetLoader, path: &VpkPath, data: Vec<u8>) -> Result<Rc<Self>> {
-        let mut r = &*data;
+        Ok(Rc::new(Self::parse(path, &data)?))
+    }
+}
+
+impl Vtf {
+    /// Parses a VTF file. Unlike [`Asset::from_data`], this needs no loader, so it can run on any
+    /// thread.
+    pub(crate) fn parse(path: &VpkPath, data: &[u8]) -> Result<Self> {
+        let mut r = data;
         let signature = r.read_u32::<LittleEndian>()?;
         assert_eq!(signature, 0x00465456);
         let major_version = r.read_u32::<LittleEndian>()?;
@@ -87,7 +111,7 @@ impl Asset for Vtf {
         let width = r.read_u16::<LittleEndian>()? as usize;
         let height = r.read_u16::<LittleEndian>()? as usize;
         let flags = r.read_u32::<LittleEndian>()?;
-        let _frames = r.read_u16::<LittleEndian>()?;
+        let frame_count = r.read_u16::<LittleEndian>()?.max(1) as usize;
         let first_frame = r.read_u16::<LittleEndian>()?;
         r = &r[4..];
         let _reflectivity_r = f32::from_bits(r.read_u32::<LittleEndian>()?);
@@ -118,7 +142,7 @@ impl Asset for Vtf {
         };
 
         let low_res_bpp = Self::bits_per_pixel_for_format(low_res_image_format);
-        let (format, mips) = match low_res_bpp {
+        let (format, frames) = match low_res_bpp {
             Some(low_res_bpp) => {
                 let high_res_offset = header_size as usize
                     + (low_res_image_width as usize * low_res_image_height as usize * low_res_bpp)
@@ -139,10 +163,11 @@ impl Asset for Vtf {
                 };
                 (
                     format,
-                    build_mips(
+                    build_frames(
                         format,
                         high_res_data,
                         mipmap_count,
+                        frame_count,
                         face_count,
                         width,
                         height,
@@ -152,47 +177,60 @@ impl Asset for Vtf {
             _ => bail!("unexpected low res image format: {}", low_res_image_format),
         };
 
-        Ok(Rc::new(Vtf {
+        Ok(Vtf {
             path: path.to_owned(),
             width,
             height,
             flags,
             format,
             face_count,
-            mips,
-        }))
+            frames,
+        })
     }
 }
 
-fn build_mips(
+/// Splits the high res image data, which is stored smallest mip first and then by frame and face,
+/// into `frames[frame_index][mip_level][face_index]`.
+fn build_frames(
     format: TextureFormat,
     mut data: &[u8],
     mipmap_count: usize,
+    frame_count: usize,
     face_count: usize,
     width: usize,
     height: usize,
-) -> Vec<Vec<TextureBuf>> {
-    let mut mips = Vec::new();
+) -> Vec<Vec<Vec<TextureBuf>>> {
+    let mut frames = vec![Vec::new(); frame_count];
     for index in 0..mipmap_count {
         let mip_level = mipmap_count - 1 - index;
         let mip_width = (width >> mip_level).max(1);
         let mip_height = (height >> mip_level).max(1);
         let size = format.metrics().encoded_size(mip_width, mip_height);
 
-        let mut faces = Vec::new();
-        for _ in 0..face_count {
-            faces
-                .push(TextureBuf::new(format, mip_width, mip_height, data[..size].to_vec()).into());
-            data = &data[size..];
+        for mips in &mut frames {
+            let mut faces = Vec::new();
+            for _ in 0..face_count {
+                faces.push(TextureBuf::new(
+                    format,
+                    mip_width,
+                    mip_height,
+                    data[..size].to_vec(),
+                ));
+                data = &data[size..];
+            }
+            mips.push(faces);
         }
-        mips.push(faces);
     }
-    mips.reverse();
-    mips
+    for mips in &mut frames {
+        mips.reverse();
+    }
+    frames
 }
 
 struct FaceMipIter<'a> {
-    vtf: &'a Vtf,
+    /// `mips[mip_level][face_index]`
+    mips: &'a [Vec<TextureBuf>],
+    face_count: usize,
     // Valid until the iterator has ended, then forever out of range.
     face: usize,
     // Always valid.
@@ -203,16 +241,16 @@ impl<'a> Iterator for FaceMipIter<'a> {
     type Item = VtfFaceMip<'a>;
 
     fn next(&mut self) -> Option<VtfFaceMip<'a>> {
-        if (self.face as usize) < self.vtf.face_count {
+        if (self.face as usize) < self.face_count {
             // Prepare the result.
             let result = VtfFaceMip {
                 face: self.face,
                 mip_level: self.mip_level,
-                texture: &self.vtf.mips[self.mip_level as usize][self.face as usize],
+                texture: &self.mips[self.mip_level as usize][self.face as usize],
             };
 
             // Advance the counters.
-            if ((self.mip_level + 1) as usize) < self.vtf.mips.len() {
+            if ((self.mip_level + 1) as usize) < self.mips.len() {
                 self.mip_level += 1;
             } else {
                 self.mip_level = 0;
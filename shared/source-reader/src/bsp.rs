@@ -1,22 +1,37 @@
 use std::collections::HashMap;
 use std::ffi::CStr;
-use std::io::Cursor;
+use std::io::{Cursor, Read};
 use std::mem::size_of;
 use std::num::NonZeroUsize;
 use std::str;
 
+use anyhow::{bail, Context, Result};
 use byteorder::{LittleEndian, ReadBytesExt};
-use nalgebra_glm::{vec3, Vec3};
+use nalgebra_glm::{mat3, vec3, Mat3, Vec3};
 use recursive_iter::*;
 use zip::ZipArchive;
 
 use fully_occupied::{extract, extract_slice, extract_slice_unchecked, FullyOccupied};
+pub use visibility::{ClusterIndex, VisibilityBitmap};
 
+use crate::model::phy::{self, CollisionSolid};
 use crate::properties;
 
 #[derive(Clone, Copy)]
 pub struct Bsp<'a>(&'a [u8]);
 
+/// An index into `Bsp::faces()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FaceIndex(pub usize);
+
+/// An index into `Bsp::leaves()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LeafIndex(pub usize);
+
+/// An index into `Bsp::nodes()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeIndex(pub usize);
+
 impl<'a> Bsp<'a> {
     pub fn new(data: &'a [u8]) -> Self {
         Self(data)
@@ -60,6 +75,10 @@ impl<'a> Bsp<'a> {
         extract_slice(self.header().lumps[5].data(self.0))
     }
 
+    pub fn node(self, index: NodeIndex) -> &'a Node {
+        &self.nodes()[index.0]
+    }
+
     pub fn tex_infos(self) -> &'a [TexInfo] {
         extract_slice(self.header().lumps[6].data(self.0))
     }
@@ -75,6 +94,10 @@ impl<'a> Bsp<'a> {
         })
     }
 
+    pub fn face(self, index: FaceIndex) -> &'a Face {
+        &self.faces()[index.0]
+    }
+
     pub fn lighting(self) -> Lighting<'a> {
         let ldr_lighting_lump = &self.header().lumps[8];
         let hdr_lighting_lump = &self.header().lumps[53];
@@ -101,6 +124,10 @@ impl<'a> Bsp<'a> {
         }
     }
 
+    pub fn leaf(self, index: LeafIndex) -> &'a dyn Leaf {
+        self.leaves().get(index.0)
+    }
+
     pub fn edges(self) -> &'a [Edge] {
         extract_slice(self.header().lumps[12].data(self.0))
     }
@@ -109,6 +136,10 @@ impl<'a> Bsp<'a> {
         extract_slice(self.header().lumps[13].data(self.0))
     }
 
+    pub fn models(self) -> &'a [Model] {
+        extract_slice(self.header().lumps[14].data(self.0))
+    }
+
     pub fn leaf_faces(self) -> &'a [u16] {
         extract_slice(self.header().lumps[16].data(self.0))
     }
@@ -117,10 +148,258 @@ impl<'a> Bsp<'a> {
         extract_slice(self.header().lumps[26].data(self.0))
     }
 
+    /// Parses the physics collision lump. Entries are in lump order, which normally follows model
+    /// index.
+    pub fn phys_models(self) -> Result<Vec<PhysModel>> {
+        let data = self.header().lumps[29].data(self.0);
+        let mut offset = 0;
+        let mut models = Vec::new();
+        loop {
+            let header: &PhysModelHeader = extract(
+                data.get(offset..)
+                    .context("physics lump is missing its terminator")?,
+            );
+            if header.model_index == -1 {
+                break;
+            }
+            offset += size_of::<PhysModelHeader>();
+            let solid_data = data
+                .get(offset..offset + header.data_size as usize)
+                .context("physics model data is out of bounds")?;
+            offset += header.data_size as usize;
+            let key_data = data
+                .get(offset..offset + header.key_data_size as usize)
+                .context("physics model key data is out of bounds")?;
+            offset += header.key_data_size as usize;
+
+            let (solids, _) = phy::parse_solids(solid_data, header.solid_count as usize)
+                .with_context(|| format!("parsing physics model {}", header.model_index))?;
+            models.push(PhysModel {
+                model_index: header.model_index,
+                solids,
+                key_values: phy::parse_key_values(key_data),
+            });
+        }
+        Ok(models)
+    }
+
+    /// Finds a game lump by its ID, such as `b"sprp"`, and returns its version and data.
+    pub fn game_lump(self, id: &[u8; 4]) -> Option<(u16, &'a [u8])> {
+        let mut r = Cursor::new(self.header().lumps[35].data(self.0));
+        let count = r.read_i32::<LittleEndian>().ok()?;
+        for _ in 0..count {
+            // IDs are multi-character constants, so they read as little-endian integers.
+            let lump_id = r.read_u32::<LittleEndian>().ok()?.to_be_bytes();
+            let _flags = r.read_u16::<LittleEndian>().ok()?;
+            let version = r.read_u16::<LittleEndian>().ok()?;
+            let fileofs = r.read_i32::<LittleEndian>().ok()? as usize;
+            let filelen = r.read_i32::<LittleEndian>().ok()? as usize;
+            if lump_id == *id {
+                return Some((version, self.0.get(fileofs..fileofs + filelen)?));
+            }
+        }
+        None
+    }
+
+    /// Parses the static prop game lump. Maps without one have no static props.
+    pub fn static_props(self) -> Result<StaticProps> {
+        let (version, data) = match self.game_lump(b"sprp") {
+            Some(lump) => lump,
+            None => return Ok(StaticProps::default()),
+        };
+        let stride = match version {
+            4 => 56,
+            5 => 60,
+            6 => 64,
+            7 | 8 => 68,
+            9 => 72,
+            10 => 76,
+            _ => bail!("unsupported static prop lump version {}", version),
+        };
+
+        let mut r = Cursor::new(data);
+        let mut model_names = Vec::new();
+        for _ in 0..r.read_i32::<LittleEndian>()? {
+            let mut name = [0; 128];
+            r.read_exact(&mut name)?;
+            let len = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+            model_names.push(str::from_utf8(&name[..len])?.to_string());
+        }
+        let mut leaves = Vec::new();
+        for _ in 0..r.read_i32::<LittleEndian>()? {
+            leaves.push(r.read_u16::<LittleEndian>()?);
+        }
+        let mut props = Vec::new();
+        for _ in 0..r.read_i32::<LittleEndian>()? {
+            let mut entry = vec![0; stride];
+            r.read_exact(&mut entry)
+                .context("static prop lump is truncated")?;
+            // Every version shares the version 4 layout up front.
+            let mut r = &entry[..];
+            let read_vec3 = |r: &mut &[u8]| -> Result<[f32; 3]> {
+                Ok([
+                    r.read_f32::<LittleEndian>()?,
+                    r.read_f32::<LittleEndian>()?,
+                    r.read_f32::<LittleEndian>()?,
+                ])
+            };
+            props.push(StaticProp {
+                origin: Vec3::from(read_vec3(&mut r)?),
+                angles: read_vec3(&mut r)?,
+                prop_type: r.read_u16::<LittleEndian>()?,
+                first_leaf: r.read_u16::<LittleEndian>()?,
+                leaf_count: r.read_u16::<LittleEndian>()?,
+                solid: r.read_u8()?,
+                flags: r.read_u8()?,
+                skin: r.read_i32::<LittleEndian>()?,
+                fade_min_dist: r.read_f32::<LittleEndian>()?,
+                fade_max_dist: r.read_f32::<LittleEndian>()?,
+                lighting_origin: Vec3::from(read_vec3(&mut r)?),
+            });
+        }
+        Ok(StaticProps {
+            model_names,
+            leaves,
+            props,
+        })
+    }
+
+    /// Parses the detail prop game lump, which VBSP fills by scattering the detail types from the
+    /// game's `detail.vbsp` over displacements and faces with a `%detailtype`. Maps without one
+    /// have no detail props.
+    pub fn detail_props(self) -> Result<DetailProps> {
+        let (version, data) = match self.game_lump(b"dprp") {
+            Some(lump) => lump,
+            None => return Ok(DetailProps::default()),
+        };
+        if version != 4 {
+            bail!("unsupported detail prop lump version {}", version);
+        }
+
+        let mut r = Cursor::new(data);
+        let read_vec2 = |r: &mut Cursor<&[u8]>| -> Result<[f32; 2]> {
+            Ok([r.read_f32::<LittleEndian>()?, r.read_f32::<LittleEndian>()?])
+        };
+        let read_vec3 = |r: &mut Cursor<&[u8]>| -> Result<[f32; 3]> {
+            Ok([
+                r.read_f32::<LittleEndian>()?,
+                r.read_f32::<LittleEndian>()?,
+                r.read_f32::<LittleEndian>()?,
+            ])
+        };
+
+        let mut model_names = Vec::new();
+        for _ in 0..r.read_i32::<LittleEndian>()? {
+            let mut name = [0; 128];
+            r.read_exact(&mut name)?;
+            let len = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+            model_names.push(str::from_utf8(&name[..len])?.to_string());
+        }
+        let mut sprites = Vec::new();
+        for _ in 0..r.read_i32::<LittleEndian>()? {
+            sprites.push(DetailSprite {
+                upper_left: read_vec2(&mut r)?,
+                lower_right: read_vec2(&mut r)?,
+                tex_upper_left: read_vec2(&mut r)?,
+                tex_lower_right: read_vec2(&mut r)?,
+            });
+        }
+        let mut props = Vec::new();
+        for _ in 0..r.read_i32::<LittleEndian>()? {
+            let origin = Vec3::from(read_vec3(&mut r)?);
+            let angles = read_vec3(&mut r)?;
+            let detail_model = r.read_u16::<LittleEndian>()?;
+            let leaf = LeafIndex(r.read_u16::<LittleEndian>()? as usize);
+            let mut lighting = [0; 4];
+            r.read_exact(&mut lighting)?;
+            let _light_styles = r.read_u32::<LittleEndian>()?;
+            let _light_style_count = r.read_u8()?;
+            let sway_amount = r.read_u8()?;
+            let shape_angle = r.read_u8()?;
+            let shape_size = r.read_u8()?;
+            let orientation = r.read_u8()?;
+            r.read_exact(&mut [0; 3])?;
+            let prop_type = match r.read_u8()? {
+                0 => DetailPropType::Model,
+                1 => DetailPropType::Sprite,
+                2 => DetailPropType::ShapeCross,
+                3 => DetailPropType::ShapeTri,
+                prop_type => bail!("unknown detail prop type {}", prop_type),
+            };
+            r.read_exact(&mut [0; 3])?;
+            let scale = r.read_f32::<LittleEndian>()?;
+            props.push(DetailProp {
+                origin,
+                angles,
+                detail_model,
+                leaf,
+                lighting: ColorRgbExp32 {
+                    r: lighting[0],
+                    g: lighting[1],
+                    b: lighting[2],
+                    exponent: lighting[3] as i8,
+                },
+                sway_amount,
+                shape_angle,
+                shape_size,
+                orientation,
+                prop_type,
+                scale,
+            });
+        }
+        Ok(DetailProps {
+            model_names,
+            sprites,
+            props,
+        })
+    }
+
+    /// Map-wide settings from the worldspawn entity, plus the fog from the first
+    /// `env_fog_controller` and the `sky_camera`, since Source keeps fog there rather than on the
+    /// worldspawn.
+    pub fn map_settings(self) -> MapSettings {
+        let entities = self.entities();
+        let find = |classname: &str| {
+            entities
+                .iter()
+                .find(|entity| entity.get("classname").map(String::as_str) == Some(classname))
+        };
+        let empty = HashMap::new();
+        let worldspawn = find("worldspawn").unwrap_or(&empty);
+        let fog = |classname| {
+            find(classname)
+                .filter(|entity| entity.get("fogenable").map(String::as_str) == Some("1"))
+                .map(|entity| Fog {
+                    color: entity
+                        .get("fogcolor")
+                        .and_then(|value| parse_color(value))
+                        .unwrap_or([255; 3]),
+                    start: parse_or(entity, "fogstart", 500.0),
+                    end: parse_or(entity, "fogend", 2000.0),
+                    max_density: parse_or(entity, "fogmaxdensity", 1.0),
+                    far_z: Some(parse_or(entity, "farz", -1.0)).filter(|&far_z| far_z > 0.0),
+                })
+        };
+        MapSettings {
+            skyname: worldspawn.get("skyname").cloned(),
+            detail_material: worldspawn
+                .get("detailmaterial")
+                .cloned()
+                .unwrap_or_else(|| "detail/detailsprites".to_string()),
+            max_prop_screen_width: parse_or(worldspawn, "maxpropscreenwidth", -1.0),
+            fog: fog("env_fog_controller"),
+            skybox_fog: fog("sky_camera"),
+        }
+    }
+
     pub fn disp_verts(self) -> &'a [DispVert] {
         extract_slice(self.header().lumps[33].data(self.0))
     }
 
+    pub fn cubemap_samples(self) -> &'a [CubemapSample] {
+        extract_slice(self.header().lumps[42].data(self.0))
+    }
+
     pub fn pak_file(self) -> ZipArchive<Cursor<&'a [u8]>> {
         ZipArchive::new(Cursor::new(self.header().lumps[40].data(self.0))).unwrap()
     }
@@ -131,25 +410,70 @@ impl<'a> Bsp<'a> {
         TexDataStrings { table, data }
     }
 
+    /// The map's `info_overlay` entities, which the compiler removes from the entity lump.
+    pub fn overlays(self) -> &'a [Overlay] {
+        extract_slice(self.header().lumps[45].data(self.0))
+    }
+
     pub fn disp_tris(self) -> &'a [DispTri] {
         extract_slice(self.header().lumps[48].data(self.0))
     }
 
-    pub fn iter_worldspawn_leaves(self) -> impl Iterator<Item = &'a dyn Leaf> {
-        self.enumerate_leaves_from_node(&self.nodes()[0])
+    /// Parallel to `leaves()`. Empty for version 19 maps, which store a single ambient cube in
+    /// each leaf instead.
+    pub fn leaf_ambient_indices(self) -> &'a [LeafAmbientIndex] {
+        extract_slice(self.ldr_or_hdr_lump_data(52, 51))
+    }
+
+    pub fn leaf_ambient_lighting(self) -> &'a [LeafAmbientLighting] {
+        extract_slice(self.ldr_or_hdr_lump_data(56, 55))
+    }
+
+    /// Returns the ambient lighting samples within a leaf, or an empty slice if it has none.
+    pub fn leaf_ambient_samples(self, leaf: LeafIndex) -> &'a [LeafAmbientLighting] {
+        match self.leaf_ambient_indices().get(leaf.0) {
+            Some(index) => {
+                let start = index.first_ambient_sample as usize;
+                let end = start + index.ambient_sample_count as usize;
+                &self.leaf_ambient_lighting()[start..end]
+            }
+            None => &[],
+        }
+    }
+
+    /// Picks a lump the same way `lighting()` does: LDR if the map has LDR lighting, else HDR.
+    fn ldr_or_hdr_lump_data(self, ldr_index: usize, hdr_index: usize) -> &'a [u8] {
+        if self.header().lumps[8].filelen == 0 {
+            self.header().lumps[hdr_index].data(self.0)
+        } else {
+            self.header().lumps[ldr_index].data(self.0)
+        }
     }
 
-    pub fn enumerate_leaves_from_node(self, node: &'a Node) -> impl Iterator<Item = &'a dyn Leaf> {
+    pub fn iter_worldspawn_leaves(self) -> impl Iterator<Item = LeafIndex> + 'a {
+        self.enumerate_leaves_from_node(NodeIndex(0))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the tree is deeper than it has nodes, which means a malformed map has a cycle.
+    pub fn enumerate_leaves_from_node(
+        self,
+        node: NodeIndex,
+    ) -> impl Iterator<Item = LeafIndex> + 'a {
         RecursiveIter::new(
             self,
             LeavesIterFrame {
-                node: node,
+                node: self.node(node),
                 child_index: 0,
             },
         )
+        .with_max_depth(self.nodes().len(), None)
+        .map(|leaf| leaf.expect("BSP tree is deeper than its node count"))
     }
 
-    pub fn iter_faces_from_leaf(self, leaf: &'a dyn Leaf) -> impl Iterator<Item = &'a Face> {
+    pub fn iter_faces_from_leaf(self, leaf: LeafIndex) -> impl Iterator<Item = FaceIndex> + 'a {
+        let leaf = self.leaf(leaf);
         let leaf_face_index = leaf.first_leaf_face() as usize;
         LeafFacesIter {
             bsp: self,
@@ -160,6 +484,143 @@ impl<'a> Bsp<'a> {
         }
     }
 
+    pub fn iter_faces_from_model(self, model: &Model) -> impl Iterator<Item = FaceIndex> {
+        let start = model.first_face as usize;
+        (start..start + model.num_faces as usize).map(FaceIndex)
+    }
+
+    /// Walks the world's BSP tree down to the leaf containing `point`.
+    pub fn leaf_for_point(self, point: Vec3) -> LeafIndex {
+        let mut index = self.models()[0].head_node;
+        while index >= 0 {
+            let node = &self.nodes()[index as usize];
+            let plane = &self.planes()[node.planenum as usize];
+            let side = Vec3::from(plane.normal).dot(&point) < plane.dist;
+            index = node.children[side as usize];
+        }
+        LeafIndex((-1 - index) as usize)
+    }
+
+    /// Brush entities that are drawn where they stand, ignoring any movement they're capable of.
+    pub fn static_brush_entities(self) -> Vec<StaticBrushEntity> {
+        self.entities()
+            .iter()
+            .filter(|entity| {
+                entity.get("classname").map_or(false, |classname| {
+                    STATIC_BRUSH_ENTITY_CLASSES.contains(&classname.as_str())
+                }) && entity.get("StartDisabled").map(String::as_str) != Some("1")
+            })
+            .filter_map(|entity| {
+                let model_index = entity.get("model")?.strip_prefix('*')?.parse().ok()?;
+                let origin = match entity.get("origin") {
+                    Some(origin) => parse_vec3(origin)?,
+                    None => Vec3::zeros(),
+                };
+                Some(StaticBrushEntity {
+                    model_index,
+                    origin,
+                })
+            })
+            .collect()
+    }
+
+    /// Every `ambient_generic` with a sound to play. Sounds attached to another entity by
+    /// `SourceEntityName` are placed at that entity's origin. Like every entity property, sound
+    /// names come back lowercased.
+    pub fn ambient_sounds(self) -> Vec<AmbientSound> {
+        let entities = self.entities();
+        let origins_by_name = entity_origins_by_name(&entities);
+        entities
+            .iter()
+            .filter(|entity| entity.get("classname").map(String::as_str) == Some("ambient_generic"))
+            .filter_map(|entity| {
+                let sound_name = entity.get("message").filter(|name| !name.is_empty())?;
+                let origin = match entity
+                    .get("sourceentityname")
+                    .and_then(|name| origins_by_name.get(name.as_str()))
+                {
+                    Some(&origin) => origin,
+                    None => parse_vec3(entity.get("origin")?)?,
+                };
+                let spawn_flags = parse_or(entity, "spawnflags", 0u32);
+                Some(AmbientSound {
+                    name: entity.get("targetname").cloned(),
+                    sound_name: sound_name.clone(),
+                    origin,
+                    // Hammer's volume runs from 0 to 10.
+                    volume: parse_or(entity, "health", 10.0f32).clamp(0.0, 10.0) / 10.0,
+                    pitch: parse_or(entity, "pitch", 100.0),
+                    radius: parse_or(entity, "radius", 1250.0),
+                    play_everywhere: spawn_flags & AmbientSound::FLAG_PLAY_EVERYWHERE != 0,
+                    start_silent: spawn_flags & AmbientSound::FLAG_START_SILENT != 0,
+                    looped: spawn_flags & AmbientSound::FLAG_NOT_LOOPED == 0,
+                })
+            })
+            .collect()
+    }
+
+    /// Every `env_soundscape` and `env_soundscape_triggerable`. The soundscape names refer to
+    /// entries in the game's soundscape scripts, which aren't part of the map.
+    pub fn soundscapes(self) -> Vec<Soundscape> {
+        let entities = self.entities();
+        let origins_by_name = entity_origins_by_name(&entities);
+        entities
+            .iter()
+            .filter(|entity| {
+                entity.get("classname").map_or(false, |classname| {
+                    SOUNDSCAPE_ENTITY_CLASSES.contains(&classname.as_str())
+                })
+            })
+            .filter_map(|entity| {
+                Some(Soundscape {
+                    name: entity.get("targetname").cloned(),
+                    soundscape: entity.get("soundscape")?.clone(),
+                    origin: parse_vec3(entity.get("origin")?)?,
+                    radius: parse_or(entity, "radius", 128.0),
+                    start_disabled: entity.get("startdisabled").map(String::as_str) == Some("1"),
+                    // Unset or dangling positions stay `None` so script indices still line up.
+                    positions: (0..Soundscape::MAX_POSITIONS)
+                        .map(|index| {
+                            entity
+                                .get(&format!("position{index}"))
+                                .and_then(|name| origins_by_name.get(name.as_str()))
+                                .copied()
+                        })
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+
+    /// Yields each face of every static brush entity with the cluster that contains its center
+    /// and the entity's origin, which must be added to the face's vertex positions. Faces outside
+    /// of every cluster are skipped.
+    pub fn iter_static_brush_entity_faces(
+        self,
+    ) -> impl Iterator<Item = (i16, FaceIndex, Vec3)> + 'a {
+        self.static_brush_entities()
+            .into_iter()
+            .flat_map(move |entity| {
+                self.iter_faces_from_model(&self.models()[entity.model_index])
+                    .map(move |face| (face, entity.origin))
+            })
+            .filter_map(move |(face, origin)| {
+                let mut sum = Vec3::zeros();
+                let mut count = 0;
+                for index in self.iter_vertex_indices_from_face(self.face(face)) {
+                    sum += self.vertices()[index];
+                    count += 1;
+                }
+                if count == 0 {
+                    return None;
+                }
+                let cluster = self
+                    .leaf(self.leaf_for_point(sum / count as f32 + origin))
+                    .cluster();
+                (cluster != -1).then_some((cluster, face, origin))
+            })
+    }
+
     pub fn iter_vertex_indices_from_face(self, face: &'a Face) -> impl Iterator<Item = usize> + 'a {
         let surf_edge_index = face.first_edge as usize;
         FaceVertexIndicesIter {
@@ -178,7 +639,8 @@ struct LeavesIterFrame<'a> {
 }
 
 impl<'a> Frame for LeavesIterFrame<'a> {
-    type Item = &'a dyn Leaf;
+    /// `None` only when the depth limit is exceeded.
+    type Item = Option<LeafIndex>;
     type Context = Bsp<'a>;
 
     fn eval(&mut self, bsp: &mut Bsp<'a>) -> EvalResult<Self> {
@@ -192,7 +654,7 @@ impl<'a> Frame for LeavesIterFrame<'a> {
             .with_return(self.child_index == 2)
         } else {
             self.child_index += 1;
-            Yield(bsp.leaves().get((-child) as usize)).with_return(self.child_index == 2)
+            Yield(Some(LeafIndex((-child) as usize))).with_return(self.child_index == 2)
         }
     }
 }
@@ -204,13 +666,13 @@ struct LeafFacesIter<'a> {
 }
 
 impl<'a> Iterator for LeafFacesIter<'a> {
-    type Item = &'a Face;
+    type Item = FaceIndex;
 
-    fn next(&mut self) -> Option<&'a Face> {
+    fn next(&mut self) -> Option<FaceIndex> {
         if self.leaf_face_index < self.end {
             let face_index = self.bsp.leaf_faces()[self.leaf_face_index] as usize;
             self.leaf_face_index += 1;
-            Some(&self.bsp.faces()[face_index])
+            Some(FaceIndex(face_index))
         } else {
             None
         }
@@ -267,7 +729,7 @@ impl<'a> Iterator for FaceVertexIndicesIter<'a> {
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, FullyOccupied)]
 pub struct Header {
     pub ident: i32,
     pub version: i32,
@@ -275,10 +737,8 @@ pub struct Header {
     pub map_revision: i32,
 }
 
-unsafe impl FullyOccupied for Header {}
-
 #[repr(C)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, FullyOccupied)]
 pub struct Lump {
     pub fileofs: i32,
     pub filelen: i32,
@@ -293,15 +753,15 @@ impl Lump {
 }
 
 #[repr(C)]
+#[derive(FullyOccupied)]
 pub struct Plane {
     pub normal: [f32; 3],
     pub dist: f32,
     pub type_: i32,
 }
 
-unsafe impl FullyOccupied for Plane {}
-
 #[repr(C)]
+#[derive(FullyOccupied)]
 pub struct TexData {
     pub reflectivity: [f32; 3],
     pub name_string_table_id: i32,
@@ -311,8 +771,6 @@ pub struct TexData {
     pub view_height: i32,
 }
 
-unsafe impl FullyOccupied for TexData {}
-
 #[derive(Clone, Copy)]
 pub struct Visibility<'a> {
     data: &'a [u8],
@@ -329,10 +787,7 @@ impl<'a> Visibility<'a> {
         let pvs_byte_ofs = (&self.data[8 * index.0 + 4..])
             .read_i32::<LittleEndian>()
             .unwrap() as usize;
-        VisibilityBitmap {
-            data: &self.data[pvs_byte_ofs..],
-            num_clusters,
-        }
+        VisibilityBitmap::new(&self.data[pvs_byte_ofs..], num_clusters)
     }
 
     pub fn iter_clusters(self) -> impl Iterator<Item = VisibilityBitmap<'a>> {
@@ -342,96 +797,8 @@ impl<'a> Visibility<'a> {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct ClusterIndex(pub usize);
-
-#[derive(Clone, Copy)]
-pub struct VisibilityBitmap<'a> {
-    data: &'a [u8],
-    num_clusters: usize,
-}
-
-impl<'a> VisibilityBitmap<'a> {
-    pub fn iter_visible_clusters(self) -> impl Iterator<Item = ClusterIndex> + 'a {
-        VisibilityBitmapIter {
-            data: self.data,
-            cluster_index: 0,
-            num_clusters: self.num_clusters,
-            current_byte: 0,
-            current_bit: 0,
-        }
-    }
-
-    pub fn find_data(self) -> &'a [u8] {
-        // Length is unknown. Walk the data to find out.
-        let mut offset = 0;
-        let mut cluster_index = 0;
-        loop {
-            // Exit on reaching the end of the bitstream.
-            if cluster_index >= self.num_clusters {
-                return &self.data[..offset];
-            }
-
-            // Read a byte, which is either a skip instruction or another eight bits to scan.
-            let b = self.data[offset];
-            offset += 1;
-            if b == 0 {
-                let run_len = self.data[offset] as usize;
-                offset += 1;
-                cluster_index += 8 * run_len;
-            } else {
-                cluster_index += 8;
-            }
-        }
-    }
-}
-
-pub struct VisibilityBitmapIter<'a> {
-    data: &'a [u8],
-    cluster_index: usize,
-    num_clusters: usize,
-    current_byte: u8,
-    current_bit: u8,
-}
-
-impl<'a> Iterator for VisibilityBitmapIter<'a> {
-    type Item = ClusterIndex;
-
-    fn next(&mut self) -> Option<ClusterIndex> {
-        loop {
-            // Exit on reaching the end of the bitstream.
-            if self.cluster_index >= self.num_clusters {
-                return None;
-            }
-
-            // Scan bits until exhausted, yielding any that are set.
-            while self.current_bit != 0 {
-                let visible = (self.current_byte & self.current_bit) != 0;
-                self.current_bit <<= 1;
-                let cluster_index = self.cluster_index;
-                self.cluster_index += 1;
-                if visible {
-                    return Some(ClusterIndex(cluster_index));
-                }
-            }
-
-            // Read another byte, which is either a skip instruction or another eight bits to scan.
-            match self.data.read_u8().unwrap() {
-                0 => {
-                    let run_len = self.data.read_u8().unwrap() as usize;
-                    self.cluster_index += 8 * run_len;
-                }
-                x => {
-                    self.current_byte = x;
-                    self.current_bit = 1;
-                }
-            }
-        }
-    }
-}
-
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, FullyOccupied)]
 pub struct Node {
     pub planenum: i32,
     pub children: [i32; 2],
@@ -443,10 +810,8 @@ pub struct Node {
     pub padding: i16,
 }
 
-unsafe impl FullyOccupied for Node {}
-
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, FullyOccupied)]
 pub struct TexInfo {
     pub texture_vecs: [[f32; 4]; 2],
     pub lightmap_vecs: [[f32; 4]; 2],
@@ -454,10 +819,8 @@ pub struct TexInfo {
     pub tex_data: i32,
 }
 
-unsafe impl FullyOccupied for TexInfo {}
-
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, FullyOccupied)]
 pub struct Face {
     pub plane_num: u16,
     pub side: u8,
@@ -478,7 +841,151 @@ pub struct Face {
     pub smoothing_groups: u32,
 }
 
-unsafe impl FullyOccupied for Face {}
+/// Brush entity classes drawn by `Bsp::static_brush_entities`.
+pub const STATIC_BRUSH_ENTITY_CLASSES: &[&str] = &[
+    "func_breakable",
+    "func_brush",
+    "func_button",
+    "func_door",
+    "func_door_rotating",
+    "func_illusionary",
+    "func_movelinear",
+    "func_rotating",
+    "func_wall",
+];
+
+/// A brush entity drawn in place using one of the map's brush models.
+#[derive(Clone, Copy, Debug)]
+pub struct StaticBrushEntity {
+    pub model_index: usize,
+    pub origin: Vec3,
+}
+
+/// Map-wide settings returned by `Bsp::map_settings`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapSettings {
+    /// The name prefix of the 2D skybox's materials.
+    pub skyname: Option<String>,
+    /// The material detail sprites are cut from.
+    pub detail_material: String,
+    /// Props narrower than this many pixels on screen are faded out. -1 leaves them all.
+    pub max_prop_screen_width: f32,
+    /// `None` if the map has no fog controller or its fog starts disabled.
+    pub fog: Option<Fog>,
+    /// Fog over the 3D skybox, from the `sky_camera`. Distances are in skybox units.
+    pub skybox_fog: Option<Fog>,
+}
+
+/// Linear distance fog, from an `env_fog_controller` or `sky_camera`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fog {
+    /// sRGB.
+    pub color: [u8; 3],
+    /// Distance from the camera where fog begins.
+    pub start: f32,
+    /// Distance from the camera where fog reaches `max_density`.
+    pub end: f32,
+    pub max_density: f32,
+    /// A far clip plane meant to hide the world past the fog, if set.
+    pub far_z: Option<f32>,
+}
+
+/// Entity classes returned by `Bsp::soundscapes`.
+pub const SOUNDSCAPE_ENTITY_CLASSES: &[&str] = &["env_soundscape", "env_soundscape_triggerable"];
+
+/// A sound placed in the map by an `ambient_generic` entity.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AmbientSound {
+    pub name: Option<String>,
+    /// Either a path under `sound/` or the name of an entry in the game's sound scripts.
+    pub sound_name: String,
+    pub origin: Vec3,
+    /// From 0 to 1.
+    pub volume: f32,
+    /// A percentage, where 100 plays the sound unmodified.
+    pub pitch: f32,
+    /// The distance at which the sound becomes inaudible.
+    pub radius: f32,
+    pub play_everywhere: bool,
+    pub start_silent: bool,
+    pub looped: bool,
+}
+
+impl AmbientSound {
+    pub const FLAG_PLAY_EVERYWHERE: u32 = 1 << 0;
+    pub const FLAG_START_SILENT: u32 = 1 << 4;
+    pub const FLAG_NOT_LOOPED: u32 = 1 << 5;
+}
+
+/// A soundscape selected by the game while the player is within `radius` of `origin` and can see
+/// it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Soundscape {
+    pub name: Option<String>,
+    /// The name of an entry in the game's soundscape scripts.
+    pub soundscape: String,
+    pub origin: Vec3,
+    pub radius: f32,
+    pub start_disabled: bool,
+    /// Where the script's positional sounds play, by position index.
+    pub positions: Vec<Option<Vec3>>,
+}
+
+impl Soundscape {
+    pub const MAX_POSITIONS: usize = 8;
+}
+
+/// Parses an entity vector property like `"1 -2.5 3"`.
+fn parse_vec3(value: &str) -> Option<Vec3> {
+    let mut components = value.split_ascii_whitespace().map(str::parse);
+    match (components.next(), components.next(), components.next()) {
+        (Some(Ok(x)), Some(Ok(y)), Some(Ok(z))) => Some(vec3(x, y, z)),
+        _ => None,
+    }
+}
+
+/// Parses an entity color property like `"255 128 0"`.
+fn parse_color(value: &str) -> Option<[u8; 3]> {
+    let mut components = value.split_ascii_whitespace().map(str::parse);
+    match (components.next(), components.next(), components.next()) {
+        (Some(Ok(r)), Some(Ok(g)), Some(Ok(b))) => Some([r, g, b]),
+        _ => None,
+    }
+}
+
+/// Parses a numeric entity property, falling back to Hammer's default when it's missing or
+/// malformed.
+fn parse_or<T: str::FromStr>(entity: &HashMap<String, String>, key: &str, default: T) -> T {
+    entity
+        .get(key)
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Maps the `targetname` of each entity with an origin to that origin.
+fn entity_origins_by_name(entities: &[HashMap<String, String>]) -> HashMap<&str, Vec3> {
+    entities
+        .iter()
+        .filter_map(|entity| {
+            Some((
+                entity.get("targetname")?.as_str(),
+                parse_vec3(entity.get("origin")?)?,
+            ))
+        })
+        .collect()
+}
+
+/// A brush model. Model 0 is the world; entities refer to the rest by the name `*<index>`.
+#[repr(C)]
+#[derive(Debug, FullyOccupied)]
+pub struct Model {
+    pub mins: [f32; 3],
+    pub maxs: [f32; 3],
+    pub origin: [f32; 3],
+    pub head_node: i32,
+    pub first_face: i32,
+    pub num_faces: i32,
+}
 
 pub struct Lighting<'a> {
     data: &'a [u8],
@@ -562,7 +1069,7 @@ impl<'a> Iterator for LeafSliceIter<'a> {
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, FullyOccupied)]
 pub struct ShortLeaf {
     pub contents: i32,
     pub cluster: i16,
@@ -574,10 +1081,9 @@ pub struct ShortLeaf {
     pub first_leaf_brush: u16,
     pub num_leaf_brushes: u16,
     pub leaf_water_data_id: i16,
+    pub padding: i16,
 }
 
-unsafe impl FullyOccupied for ShortLeaf {}
-
 impl Leaf for ShortLeaf {
     fn contents(&self) -> i32 {
         self.contents
@@ -621,7 +1127,7 @@ impl Leaf for ShortLeaf {
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, FullyOccupied)]
 pub struct LongLeaf {
     pub contents: i32,
     pub cluster: i16,
@@ -637,8 +1143,6 @@ pub struct LongLeaf {
     pub padding: i16,
 }
 
-unsafe impl FullyOccupied for LongLeaf {}
-
 impl Leaf for LongLeaf {
     fn contents(&self) -> i32 {
         self.contents
@@ -682,15 +1186,13 @@ impl Leaf for LongLeaf {
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, FullyOccupied)]
 pub struct Edge {
     pub v: [u16; 2],
 }
 
-unsafe impl FullyOccupied for Edge {}
-
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, FullyOccupied)]
 pub struct DispInfo {
     pub start_position: [f32; 3],
     pub disp_vert_start: i32,
@@ -700,6 +1202,7 @@ pub struct DispInfo {
     pub smoothing_angle: f32,
     pub contents: i32,
     pub map_face: u16,
+    pub padding: u16,
     pub lightmap_alpha_start: i32,
     pub lightmap_sample_position_start: i32,
     pub edge_neighbors: [DispNeighbor; 4],
@@ -717,29 +1220,24 @@ impl DispInfo {
     }
 }
 
-unsafe impl FullyOccupied for DispInfo {}
-
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, FullyOccupied)]
 pub struct DispNeighbor {
     pub sub_neighbors: [DispSubNeighbor; 2],
 }
 
-unsafe impl FullyOccupied for DispNeighbor {}
-
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, FullyOccupied)]
 pub struct DispSubNeighbor {
     pub i_neighbor: u16,
     pub neighbor_orientation: NeighborOrientation,
     pub span: NeighborSpan,
     pub neighbor_span: NeighborSpan,
+    pub padding: u8,
 }
 
-unsafe impl FullyOccupied for DispSubNeighbor {}
-
 #[repr(transparent)]
-#[derive(Debug)]
+#[derive(Debug, FullyOccupied)]
 pub struct NeighborOrientation(u8);
 
 impl NeighborOrientation {
@@ -750,7 +1248,7 @@ impl NeighborOrientation {
 }
 
 #[repr(transparent)]
-#[derive(Debug)]
+#[derive(Debug, FullyOccupied)]
 pub struct NeighborSpan(u8);
 
 impl NeighborSpan {
@@ -760,32 +1258,169 @@ impl NeighborSpan {
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, FullyOccupied)]
 pub struct DispCornerNeighbors {
     pub neighbors: [u16; 4],
     pub neighbor_count: u8,
+    pub padding: u8,
 }
 
-unsafe impl FullyOccupied for DispCornerNeighbors {}
-
 #[repr(C)]
+#[derive(Debug, FullyOccupied)]
+struct PhysModelHeader {
+    model_index: i32,
+    data_size: i32,
+    key_data_size: i32,
+    solid_count: i32,
+}
+
+/// The collision solids for one brush model.
+pub struct PhysModel {
+    pub model_index: i32,
+    pub solids: Vec<CollisionSolid>,
+    /// Per-solid properties as Valve key-value text.
+    pub key_values: String,
+}
+
+/// The contents of the static prop game lump.
+#[derive(Debug, Default)]
+pub struct StaticProps {
+    /// Model paths, such as `models/props_c17/bench01a.mdl`.
+    pub model_names: Vec<String>,
+    /// Leaf indices, referenced by ranges in each prop.
+    pub leaves: Vec<u16>,
+    pub props: Vec<StaticProp>,
+}
+
+impl StaticProps {
+    pub fn model_name(&self, prop: &StaticProp) -> &str {
+        &self.model_names[prop.prop_type as usize]
+    }
+
+    /// The leaves the prop touches.
+    pub fn leaves(&self, prop: &StaticProp) -> impl Iterator<Item = LeafIndex> + '_ {
+        let start = prop.first_leaf as usize;
+        self.leaves[start..start + prop.leaf_count as usize]
+            .iter()
+            .map(|&leaf| LeafIndex(leaf as usize))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct StaticProp {
+    pub origin: Vec3,
+    /// Pitch, yaw, and roll, in degrees.
+    pub angles: [f32; 3],
+    /// An index into `StaticProps::model_names`.
+    pub prop_type: u16,
+    /// A range of `StaticProps::leaves`.
+    pub first_leaf: u16,
+    pub leaf_count: u16,
+    pub solid: u8,
+    pub flags: u8,
+    pub skin: i32,
+    pub fade_min_dist: f32,
+    pub fade_max_dist: f32,
+    /// Where to sample lighting for the whole prop.
+    pub lighting_origin: Vec3,
+}
+
+impl StaticProp {
+    /// Rotates model space into world space, following the engine's `AngleMatrix`.
+    pub fn rotation(&self) -> Mat3 {
+        let [pitch, yaw, roll] = self.angles.map(f32::to_radians);
+        let (sp, cp) = pitch.sin_cos();
+        let (sy, cy) = yaw.sin_cos();
+        let (sr, cr) = roll.sin_cos();
+        mat3(
+            cp * cy,
+            sr * sp * cy - cr * sy,
+            cr * sp * cy + sr * sy,
+            cp * sy,
+            sr * sp * sy + cr * cy,
+            cr * sp * sy - sr * cy,
+            -sp,
+            sr * cp,
+            cr * cp,
+        )
+    }
+
+    /// Transforms a model space position into world space.
+    pub fn transform_point(&self, position: Vec3) -> Vec3 {
+        self.rotation() * position + self.origin
+    }
+}
+
+/// The contents of the detail prop game lump.
+#[derive(Debug, Default)]
+pub struct DetailProps {
+    /// Model paths for [`DetailPropType::Model`] props.
+    pub model_names: Vec<String>,
+    /// Rectangles of the detail material, for every other type of prop.
+    pub sprites: Vec<DetailSprite>,
+    pub props: Vec<DetailProp>,
+}
+
+/// One cell of the detail material.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DetailSprite {
+    /// The corners of the sprite's quad, in world units relative to the prop's origin. X runs to
+    /// the right and Y runs up.
+    pub upper_left: [f32; 2],
+    pub lower_right: [f32; 2],
+    /// The corners of the sprite's cell in the detail material's texture coordinates.
+    pub tex_upper_left: [f32; 2],
+    pub tex_lower_right: [f32; 2],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DetailPropType {
+    Model,
+    /// A single camera-facing quad.
+    Sprite,
+    /// Two sprites crossing at right angles.
+    ShapeCross,
+    /// Three sprites leaning out from the prop's origin.
+    ShapeTri,
+}
+
 #[derive(Debug)]
+pub struct DetailProp {
+    pub origin: Vec3,
+    /// Pitch, yaw, and roll, in degrees.
+    pub angles: [f32; 3],
+    /// An index into `DetailProps::model_names` for models, or into `DetailProps::sprites` for
+    /// every other type.
+    pub detail_model: u16,
+    /// The leaf containing the prop's origin.
+    pub leaf: LeafIndex,
+    /// Lighting sampled at the prop's origin by VRAD.
+    pub lighting: ColorRgbExp32,
+    pub sway_amount: u8,
+    pub shape_angle: u8,
+    pub shape_size: u8,
+    /// 0 keeps the prop's angles, 1 faces the camera, and 2 turns only about the Z axis to face
+    /// the camera.
+    pub orientation: u8,
+    pub prop_type: DetailPropType,
+    /// Scales the sprite's quad.
+    pub scale: f32,
+}
+
+#[repr(C)]
+#[derive(Debug, FullyOccupied)]
 pub struct DispVert {
     pub vec: [f32; 3],
     pub dist: f32,
     pub alpha: f32,
 }
 
-unsafe impl FullyOccupied for DispVert {}
-
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, FullyOccupied)]
 pub struct DispTri {
     pub tags: u16,
 }
 
-unsafe impl FullyOccupied for DispTri {}
-
 #[derive(Clone, Copy)]
 pub struct TexDataStrings<'a> {
     table: &'a [i32],
@@ -810,10 +1445,104 @@ impl<'a> TexDataStrings<'a> {
     }
 }
 
+/// Incoming light from the +X, -X, +Y, -Y, +Z, and -Z directions.
 pub type CompressedLightCube = [ColorRgbExp32; 6];
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, FullyOccupied)]
+pub struct LeafAmbientIndex {
+    pub ambient_sample_count: u16,
+    pub first_ambient_sample: u16,
+}
+
+#[repr(C)]
+#[derive(Debug, FullyOccupied)]
+pub struct LeafAmbientLighting {
+    pub cube: CompressedLightCube,
+    /// Position within the leaf's bounding box, in 1/255ths.
+    pub x: u8,
+    pub y: u8,
+    pub z: u8,
+    pub pad: u8,
+}
+
+impl LeafAmbientLighting {
+    pub fn position(&self, leaf: &dyn Leaf) -> [f32; 3] {
+        let (mins, maxs) = (leaf.mins(), leaf.maxs());
+        let lerp = |i: usize, t: u8| {
+            mins[i] as f32 + (maxs[i] as f32 - mins[i] as f32) * (t as f32 / 255.0)
+        };
+        [lerp(0, self.x), lerp(1, self.y), lerp(2, self.z)]
+    }
+}
+
+/// A texture projected onto a set of faces, from an `info_overlay`.
+#[repr(C)]
+#[derive(Debug, FullyOccupied)]
+pub struct Overlay {
+    pub id: i32,
+    pub tex_info: i16,
+    /// Face count in the low 14 bits and render order in the high 2 bits.
+    pub face_count_and_render_order: u16,
+    faces: [i32; 64],
+    pub u: [f32; 2],
+    pub v: [f32; 2],
+    /// Corners in the overlay's plane, relative to `origin`. The compiler stores the overlay's U
+    /// basis vector in the unused Z components of the first three, and a flag in the fourth.
+    pub uv_points: [[f32; 3]; 4],
+    pub origin: [f32; 3],
+    pub basis_normal: [f32; 3],
+}
+
+impl Overlay {
+    pub fn faces(&self) -> &[i32] {
+        &self.faces[..(self.face_count_and_render_order & 0x3fff) as usize]
+    }
+
+    pub fn render_order(&self) -> u16 {
+        self.face_count_and_render_order >> 14
+    }
+
+    /// Returns the U, V, and normal basis vectors.
+    pub fn basis(&self) -> [Vec3; 3] {
+        let normal = Vec3::from(self.basis_normal);
+        let u = vec3(
+            self.uv_points[0][2],
+            self.uv_points[1][2],
+            self.uv_points[2][2],
+        );
+        let mut v = normal.cross(&u);
+        if self.uv_points[3][2] == 1.0 {
+            v = -v;
+        }
+        [u, v, normal]
+    }
+
+    /// Returns the world space corners along with their texture coordinates.
+    pub fn corners(&self) -> [(Vec3, [f32; 2]); 4] {
+        let [u, v, _] = self.basis();
+        let origin = Vec3::from(self.origin);
+        let corner = |i: usize| origin + u * self.uv_points[i][0] + v * self.uv_points[i][1];
+        [
+            (corner(0), [self.u[0], self.v[0]]),
+            (corner(1), [self.u[0], self.v[1]]),
+            (corner(2), [self.u[1], self.v[1]]),
+            (corner(3), [self.u[1], self.v[0]]),
+        ]
+    }
+}
+
+/// An `env_cubemap` that was built into the map.
+#[repr(C)]
+#[derive(Debug, FullyOccupied)]
+pub struct CubemapSample {
+    pub origin: [i32; 3],
+    /// Log2 of the edge length of the cubemap texture, plus one. Zero selects the default size.
+    pub size: i32,
+}
+
+#[repr(C)]
+#[derive(Debug, FullyOccupied)]
 pub struct ColorRgbExp32 {
     pub r: u8,
     pub g: u8,
@@ -821,8 +1550,6 @@ pub struct ColorRgbExp32 {
     pub exponent: i8,
 }
 
-unsafe impl FullyOccupied for ColorRgbExp32 {}
-
 impl ColorRgbExp32 {
     const SCALE: f32 = 0.5;
 
@@ -855,14 +1582,20 @@ impl ColorRgbExp32 {
     }
 
     pub fn to_srgb8(&self) -> [u8; 3] {
-        let map = |x| {
-            let linear =
-                (x as f32 * (self.exponent as f32).exp2() / 255.0 * Self::SCALE).clamp(0.0, 1.0);
-            let srgb = Self::linear_to_srgb(linear);
-            (srgb * 255.0 + 0.5) as u8
-        };
+        Self::linear_to_srgb8(self.to_linear())
+    }
+
+    /// Returns the sample in linear units where 1.0 is full brightness, without clamping, so that
+    /// several light styles' samples can be weighted and summed.
+    pub fn to_linear(&self) -> [f32; 3] {
+        let map = |x| x as f32 * (self.exponent as f32).exp2() / 255.0 * Self::SCALE;
         [map(self.r), map(self.g), map(self.b)]
     }
+
+    /// Clamps and encodes a value from [`Self::to_linear`].
+    pub fn linear_to_srgb8(linear: [f32; 3]) -> [u8; 3] {
+        linear.map(|x| (Self::linear_to_srgb(x.clamp(0.0, 1.0)) * 255.0 + 0.5) as u8)
+    }
 }
 
 #[cfg(test)]
@@ -872,8 +1605,8 @@ mod size_tests {
     use crate::bsp::{DispTri, DispVert};
 
     use super::{
-        DispCornerNeighbors, DispInfo, DispNeighbor, DispSubNeighbor, Face, LongLeaf, Node,
-        ShortLeaf, TexInfo,
+        CubemapSample, DispCornerNeighbors, DispInfo, DispNeighbor, DispSubNeighbor, Face,
+        LeafAmbientIndex, LeafAmbientLighting, LongLeaf, Node, Overlay, ShortLeaf, TexInfo,
     };
 
     #[test]
@@ -918,4 +1651,362 @@ mod size_tests {
     fn disp_tri_size() {
         assert_eq!(size_of::<DispTri>(), 2);
     }
+
+    #[test]
+    fn leaf_ambient_size() {
+        assert_eq!(size_of::<LeafAmbientIndex>(), 4);
+        assert_eq!(size_of::<LeafAmbientLighting>(), 28);
+    }
+
+    #[test]
+    fn cubemap_sample_size() {
+        assert_eq!(size_of::<CubemapSample>(), 16);
+    }
+
+    #[test]
+    fn overlay_size() {
+        assert_eq!(size_of::<Overlay>(), 352);
+    }
+}
+
+#[cfg(test)]
+mod static_prop_tests {
+    use std::mem::size_of;
+
+    use byteorder::{LittleEndian, WriteBytesExt};
+    use nalgebra_glm::vec3;
+
+    use super::{Bsp, Header, LeafIndex};
+
+    pub(super) fn build_bsp(id: &[u8; 4], version: u16, game_lump: &[u8]) -> Vec<u8> {
+        let header_size = size_of::<Header>();
+        let mut data = vec![0; header_size];
+        (&mut data[4..8]).write_i32::<LittleEndian>(20).unwrap();
+
+        // Point lump 35 at a game lump directory holding only the given game lump.
+        let directory_size = 4 + 16;
+        let lump_35 = 8 + 35 * 16;
+        (&mut data[lump_35..lump_35 + 4])
+            .write_i32::<LittleEndian>(header_size as i32)
+            .unwrap();
+        (&mut data[lump_35 + 4..lump_35 + 8])
+            .write_i32::<LittleEndian>(directory_size)
+            .unwrap();
+        data.write_i32::<LittleEndian>(1).unwrap();
+        data.write_u32::<LittleEndian>(u32::from_be_bytes(*id))
+            .unwrap();
+        data.write_u16::<LittleEndian>(0).unwrap();
+        data.write_u16::<LittleEndian>(version).unwrap();
+        data.write_i32::<LittleEndian>(header_size as i32 + directory_size)
+            .unwrap();
+        data.write_i32::<LittleEndian>(game_lump.len() as i32)
+            .unwrap();
+        data.extend_from_slice(game_lump);
+        data
+    }
+
+    #[test]
+    fn parse_version_4_static_props() {
+        let mut sprp = Vec::new();
+        sprp.write_i32::<LittleEndian>(1).unwrap();
+        let mut name = [0; 128];
+        name[..20].copy_from_slice(b"models/props/box.mdl");
+        sprp.extend_from_slice(&name);
+        sprp.write_i32::<LittleEndian>(2).unwrap();
+        sprp.write_u16::<LittleEndian>(7).unwrap();
+        sprp.write_u16::<LittleEndian>(9).unwrap();
+        sprp.write_i32::<LittleEndian>(1).unwrap();
+        for x in [1.0, 2.0, 3.0, 0.0, 90.0, 0.0] {
+            sprp.write_f32::<LittleEndian>(x).unwrap();
+        }
+        sprp.write_u16::<LittleEndian>(0).unwrap(); // prop_type
+        sprp.write_u16::<LittleEndian>(0).unwrap(); // first_leaf
+        sprp.write_u16::<LittleEndian>(2).unwrap(); // leaf_count
+        sprp.extend_from_slice(&[6, 0]); // solid, flags
+        sprp.write_i32::<LittleEndian>(0).unwrap(); // skin
+        for x in [0.0, 0.0, 1.0, 2.0, 4.0] {
+            sprp.write_f32::<LittleEndian>(x).unwrap();
+        }
+
+        let data = build_bsp(b"sprp", 4, &sprp);
+        let static_props = Bsp::new(&data).static_props().unwrap();
+        assert_eq!(static_props.props.len(), 1);
+        let prop = &static_props.props[0];
+        assert_eq!(static_props.model_name(prop), "models/props/box.mdl");
+        assert_eq!(
+            static_props.leaves(prop).collect::<Vec<_>>(),
+            [LeafIndex(7), LeafIndex(9)],
+        );
+        assert_eq!(prop.lighting_origin, vec3(1.0, 2.0, 4.0));
+
+        // A yaw of 90 degrees turns +X toward +Y.
+        approx::assert_relative_eq!(
+            prop.transform_point(vec3(1.0, 0.0, 0.0)),
+            vec3(1.0, 3.0, 3.0),
+            epsilon = 1e-5,
+        );
+    }
+
+    #[test]
+    fn missing_static_prop_lump() {
+        let data = vec![0; size_of::<Header>()];
+        assert!(Bsp::new(&data).static_props().unwrap().props.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod detail_prop_tests {
+    use std::mem::size_of;
+
+    use byteorder::{LittleEndian, WriteBytesExt};
+    use nalgebra_glm::vec3;
+
+    use super::static_prop_tests::build_bsp;
+    use super::{Bsp, DetailPropType, DetailSprite, Header, LeafIndex};
+
+    #[test]
+    fn parse_version_4_detail_props() {
+        let mut dprp = Vec::new();
+        dprp.write_i32::<LittleEndian>(0).unwrap();
+        dprp.write_i32::<LittleEndian>(1).unwrap();
+        for x in [-8.0, 16.0, 8.0, 0.0, 0.0, 0.5, 0.25, 0.75] {
+            dprp.write_f32::<LittleEndian>(x).unwrap();
+        }
+        dprp.write_i32::<LittleEndian>(1).unwrap();
+        for x in [1.0, 2.0, 3.0, 0.0, 45.0, 0.0] {
+            dprp.write_f32::<LittleEndian>(x).unwrap();
+        }
+        dprp.write_u16::<LittleEndian>(0).unwrap(); // detail_model
+        dprp.write_u16::<LittleEndian>(12).unwrap(); // leaf
+        dprp.extend_from_slice(&[128, 64, 32, 0xff]); // lighting
+        dprp.write_u32::<LittleEndian>(0).unwrap(); // light_styles
+        dprp.extend_from_slice(&[0, 5, 0, 0, 2, 0, 0, 0]); // through padding
+        dprp.extend_from_slice(&[1, 0, 0, 0]); // prop_type, padding
+        dprp.write_f32::<LittleEndian>(0.75).unwrap();
+
+        let data = build_bsp(b"dprp", 4, &dprp);
+        let detail_props = Bsp::new(&data).detail_props().unwrap();
+        assert!(detail_props.model_names.is_empty());
+        assert_eq!(
+            detail_props.sprites,
+            [DetailSprite {
+                upper_left: [-8.0, 16.0],
+                lower_right: [8.0, 0.0],
+                tex_upper_left: [0.0, 0.5],
+                tex_lower_right: [0.25, 0.75],
+            }],
+        );
+        assert_eq!(detail_props.props.len(), 1);
+        let prop = &detail_props.props[0];
+        assert_eq!(prop.origin, vec3(1.0, 2.0, 3.0));
+        assert_eq!(prop.leaf, LeafIndex(12));
+        assert_eq!(prop.lighting.exponent, -1);
+        assert_eq!(prop.sway_amount, 5);
+        assert_eq!(prop.orientation, 2);
+        assert_eq!(prop.prop_type, DetailPropType::Sprite);
+        assert_eq!(prop.scale, 0.75);
+    }
+
+    #[test]
+    fn missing_detail_prop_lump() {
+        let data = vec![0; size_of::<Header>()];
+        assert!(Bsp::new(&data).detail_props().unwrap().props.is_empty());
+    }
+
+    #[test]
+    fn unsupported_detail_prop_lump_version() {
+        let data = build_bsp(b"dprp", 5, &[0; 12]);
+        assert!(Bsp::new(&data).detail_props().is_err());
+    }
+}
+
+#[cfg(test)]
+mod sound_tests {
+    use std::mem::size_of;
+
+    use byteorder::{LittleEndian, WriteBytesExt};
+    use nalgebra_glm::vec3;
+
+    use super::{Bsp, Header};
+
+    pub(super) fn build_bsp(entities: &str) -> Vec<u8> {
+        let header_size = size_of::<Header>();
+        let mut data = vec![0; header_size];
+        (&mut data[8..12])
+            .write_i32::<LittleEndian>(header_size as i32)
+            .unwrap();
+        (&mut data[12..16])
+            .write_i32::<LittleEndian>(entities.len() as i32 + 1)
+            .unwrap();
+        data.extend_from_slice(entities.as_bytes());
+        data.push(0);
+        data
+    }
+
+    #[test]
+    fn ambient_sounds() {
+        let data = build_bsp(
+            r#"{
+"classname" "worldspawn"
+}
+{
+"classname" "ambient_generic"
+"targetname" "hum"
+"message" "ambient/machines/hum1.wav"
+"origin" "1 2 3"
+"health" "5"
+"radius" "512"
+"spawnflags" "16"
+}
+{
+"classname" "ambient_generic"
+"message" "Streetwar.d3_c17_13_horn"
+"origin" "0 0 0"
+"SourceEntityName" "horn"
+"spawnflags" "33"
+}
+{
+"classname" "ambient_generic"
+"origin" "0 0 0"
+}
+{
+"classname" "info_target"
+"targetname" "horn"
+"origin" "-4 5 -6"
+}
+"#,
+        );
+        let sounds = Bsp::new(&data).ambient_sounds();
+        assert_eq!(sounds.len(), 2);
+
+        assert_eq!(sounds[0].name.as_deref(), Some("hum"));
+        assert_eq!(sounds[0].sound_name, "ambient/machines/hum1.wav");
+        assert_eq!(sounds[0].origin, vec3(1.0, 2.0, 3.0));
+        assert_eq!(sounds[0].volume, 0.5);
+        assert_eq!(sounds[0].pitch, 100.0);
+        assert_eq!(sounds[0].radius, 512.0);
+        assert!(!sounds[0].play_everywhere);
+        assert!(sounds[0].start_silent);
+        assert!(sounds[0].looped);
+
+        assert_eq!(sounds[1].name, None);
+        assert_eq!(sounds[1].origin, vec3(-4.0, 5.0, -6.0));
+        assert_eq!(sounds[1].volume, 1.0);
+        assert!(sounds[1].play_everywhere);
+        assert!(!sounds[1].looped);
+    }
+
+    #[test]
+    fn soundscapes() {
+        let data = build_bsp(
+            r#"{
+"classname" "env_soundscape"
+"soundscape" "trainstation.plaza"
+"origin" "10 20 30"
+"radius" "1024"
+"position0" "speaker"
+"position2" "missing"
+}
+{
+"classname" "env_soundscape_triggerable"
+"soundscape" "trainstation.indoor"
+"origin" "0 0 0"
+"StartDisabled" "1"
+}
+{
+"classname" "info_target"
+"targetname" "speaker"
+"origin" "7 8 9"
+}
+"#,
+        );
+        let soundscapes = Bsp::new(&data).soundscapes();
+        assert_eq!(soundscapes.len(), 2);
+
+        assert_eq!(soundscapes[0].soundscape, "trainstation.plaza");
+        assert_eq!(soundscapes[0].origin, vec3(10.0, 20.0, 30.0));
+        assert_eq!(soundscapes[0].radius, 1024.0);
+        assert!(!soundscapes[0].start_disabled);
+        assert_eq!(soundscapes[0].positions.len(), 8);
+        assert_eq!(soundscapes[0].positions[0], Some(vec3(7.0, 8.0, 9.0)));
+        assert_eq!(soundscapes[0].positions[1], None);
+        assert_eq!(soundscapes[0].positions[2], None);
+
+        assert_eq!(soundscapes[1].soundscape, "trainstation.indoor");
+        assert_eq!(soundscapes[1].radius, 128.0);
+        assert!(soundscapes[1].start_disabled);
+    }
+}
+
+#[cfg(test)]
+mod map_settings_tests {
+    use super::sound_tests::build_bsp;
+    use super::{Bsp, Fog};
+
+    #[test]
+    fn reads_worldspawn_and_fog() {
+        let data = build_bsp(
+            r#"{
+"classname" "worldspawn"
+"skyname" "sky_day01_05"
+"maxpropscreenwidth" "320"
+}
+{
+"classname" "env_fog_controller"
+"fogenable" "1"
+"fogcolor" "90 100 110"
+"fogstart" "256"
+"fogend" "4096"
+"fogmaxdensity" ".8"
+"farz" "-1"
+}
+{
+"classname" "sky_camera"
+"fogenable" "1"
+"fogcolor" "200 210 220"
+"fogstart" "100"
+"fogend" "1500"
+}
+"#,
+        );
+        let settings = Bsp::new(&data).map_settings();
+        assert_eq!(settings.skyname.as_deref(), Some("sky_day01_05"));
+        assert_eq!(settings.detail_material, "detail/detailsprites");
+        assert_eq!(settings.max_prop_screen_width, 320.0);
+        assert_eq!(
+            settings.fog,
+            Some(Fog {
+                color: [90, 100, 110],
+                start: 256.0,
+                end: 4096.0,
+                max_density: 0.8,
+                far_z: None,
+            }),
+        );
+        assert_eq!(
+            settings.skybox_fog.map(|fog| (fog.color, fog.end)),
+            Some(([200, 210, 220], 1500.0)),
+        );
+    }
+
+    #[test]
+    fn disabled_fog_is_none() {
+        let data = build_bsp(
+            r#"{
+"classname" "worldspawn"
+"detailmaterial" "detail/detailsprites_coast"
+}
+{
+"classname" "env_fog_controller"
+"fogenable" "0"
+}
+"#,
+        );
+        let settings = Bsp::new(&data).map_settings();
+        assert_eq!(settings.skyname, None);
+        assert_eq!(settings.detail_material, "detail/detailsprites_coast");
+        assert_eq!(settings.max_prop_screen_width, -1.0);
+        assert_eq!(settings.fog, None);
+        assert_eq!(settings.skybox_fog, None);
+    }
 }
@@ -1,6 +1,6 @@
 use std::rc::Rc;
 
-use anyhow::{bail, Result};
+use anyhow::Result;
 
 use crate::vpk::path::VpkPath;
 
@@ -30,6 +30,6 @@ impl<'a> FileLoader for FallbackFileLoader<'a> {
                 None => (),
             }
         }
-        bail!("file not found: {}", path)
+        Ok(None)
     }
 }
@@ -1,5 +1,7 @@
+use nalgebra_glm::vec3;
+
 use crate::bsp::{Bsp, Face, TexInfo};
-use crate::lightmap::Lightmap;
+use crate::lightmap::{Lightmap, LuxelSpace};
 
 #[derive(Clone, Copy)]
 pub struct Vertex {
@@ -23,24 +25,12 @@ pub fn convert_vertex(
 
     let (lightmap_s, lightmap_t) = if let Some(lightmap) = lightmap {
         if let Some(lightmap_metadata) = lightmap.metadata_by_data_offset.get(&face.light_ofs) {
-            let patch_s = tex_info.lightmap_vecs[0][0] * vertex.x
-                + tex_info.lightmap_vecs[0][1] * vertex.y
-                + tex_info.lightmap_vecs[0][2] * vertex.z
-                + tex_info.lightmap_vecs[0][3]
-                - face.lightmap_texture_mins_in_luxels[0] as f32;
-            let patch_t = tex_info.lightmap_vecs[1][0] * vertex.x
-                + tex_info.lightmap_vecs[1][1] * vertex.y
-                + tex_info.lightmap_vecs[1][2] * vertex.z
-                + tex_info.lightmap_vecs[1][3]
-                - face.lightmap_texture_mins_in_luxels[1] as f32;
-            let (patch_s, patch_t) = if lightmap_metadata.is_flipped {
-                (patch_t, patch_s)
-            } else {
-                (patch_s, patch_t)
-            };
+            let luxel =
+                LuxelSpace::for_face(bsp, face).world_to_luxel(vec3(vertex.x, vertex.y, vertex.z));
+            let texel = lightmap_metadata.luxel_to_atlas(luxel);
             (
-                (patch_s + lightmap_metadata.luxel_offset[0] as f32 + 0.5) / lightmap.width as f32,
-                (patch_t + lightmap_metadata.luxel_offset[1] as f32 + 0.5) / lightmap.height as f32,
+                (texel.x + 0.5) / lightmap.width as f32,
+                (texel.y + 0.5) / lightmap.height as f32,
             )
         } else {
             (0.0, 0.0)
@@ -1,6 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use anyhow::Result;
+use nalgebra_glm::{mat3, vec2, vec3, Mat3, Vec2, Vec3};
 use texture_atlas::{PatchId, TextureAtlas};
 
 use crate::bsp::{Bsp, Face};
@@ -11,6 +12,81 @@ pub struct LightmapMetadata {
     pub is_flipped: bool,
 }
 
+impl LightmapMetadata {
+    /// Maps a face's luxel coordinates to texel coordinates in the atlas holding its patch.
+    pub fn luxel_to_atlas(&self, luxel: Vec2) -> Vec2 {
+        let luxel = if self.is_flipped {
+            vec2(luxel.y, luxel.x)
+        } else {
+            luxel
+        };
+        luxel + vec2(self.luxel_offset[0] as f32, self.luxel_offset[1] as f32)
+    }
+}
+
+/// A face's lightmap coordinate system. Luxel (0, 0) is the face's first lightmap sample and luxel
+/// (s, t) is `lightmap_texture_size_in_luxels` away from it, one unit per sample.
+#[derive(Clone, Copy, Debug)]
+pub struct LuxelSpace {
+    /// Rows map world space to luxel space when multiplied by `(x, y, z, 1)`.
+    world_to_luxel: [[f32; 4]; 2],
+    /// Maps `(s, t, plane distance)`, with the luxel space translation removed, back to a point
+    /// on the face's plane.
+    luxel_to_world: Mat3,
+}
+
+impl LuxelSpace {
+    pub fn for_face(bsp: Bsp, face: &Face) -> Self {
+        let tex_info = &bsp.tex_infos()[face.tex_info as usize];
+        let plane = &bsp.planes()[face.plane_num as usize];
+        Self::new(
+            tex_info.lightmap_vecs,
+            face.lightmap_texture_mins_in_luxels,
+            Vec3::from(plane.normal),
+            plane.dist,
+        )
+    }
+
+    fn new(lightmap_vecs: [[f32; 4]; 2], mins: [i32; 2], normal: Vec3, dist: f32) -> Self {
+        let mut world_to_luxel = lightmap_vecs;
+        for (row, min) in world_to_luxel.iter_mut().zip(mins) {
+            row[3] -= min as f32;
+        }
+        let [s, t] = lightmap_vecs;
+        let luxel_to_world = mat3(
+            s[0], s[1], s[2], //
+            t[0], t[1], t[2], //
+            normal.x, normal.y, normal.z,
+        )
+        .try_inverse()
+        .unwrap_or_else(Mat3::zeros);
+        // Fold the plane distance in now so the inverse only needs the luxel coordinates.
+        let luxel_to_world = luxel_to_world * Mat3::from_diagonal(&vec3(1.0, 1.0, dist));
+        Self {
+            world_to_luxel,
+            luxel_to_world,
+        }
+    }
+
+    pub fn world_to_luxel(&self, world: Vec3) -> Vec2 {
+        let [s, t] = self
+            .world_to_luxel
+            .map(|row| row[0] * world.x + row[1] * world.y + row[2] * world.z + row[3]);
+        vec2(s, t)
+    }
+
+    /// Returns the point on the face's plane at `luxel`. Degenerate lightmap axes, which should
+    /// not occur in a compiled map, map everything to the origin.
+    pub fn luxel_to_world(&self, luxel: Vec2) -> Vec3 {
+        self.luxel_to_world
+            * vec3(
+                luxel.x - self.world_to_luxel[0][3],
+                luxel.y - self.world_to_luxel[1][3],
+                1.0,
+            )
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct LightmapPatch {
     pub width: u8,
@@ -24,41 +100,87 @@ pub struct LightmapPatch {
 pub struct Lightmap {
     pub width: usize,
     pub height: usize,
-    pub metadata_by_data_offset: HashMap<i32, LightmapMetadata>,
+    pub metadata_by_data_offset: BTreeMap<i32, LightmapMetadata>,
+    /// Luxels covered by patches.
+    pub patch_luxels: usize,
+    /// Luxels claimed by patches once rounded out to whole 4x4 blocks.
+    pub block_aligned_luxels: usize,
+}
+
+impl Lightmap {
+    /// The fraction of the atlas covered by patches.
+    pub fn occupancy(&self) -> f32 {
+        self.patch_luxels as f32 / (self.width * self.height) as f32
+    }
+
+    pub fn flipped_patch_count(&self) -> usize {
+        self.metadata_by_data_offset
+            .values()
+            .filter(|metadata| metadata.is_flipped)
+            .count()
+    }
 }
 
-#[derive(Default)]
 struct LightmapBuilder {
     atlas: TextureAtlas,
     patch_ids_by_data_offset: HashMap<i32, PatchId>,
+    patch_luxels: usize,
+    block_aligned_luxels: usize,
+}
+
+impl Default for LightmapBuilder {
+    fn default() -> Self {
+        Self {
+            // Lightmap coordinates can be swapped per face, so patches are free to rotate.
+            atlas: TextureAtlas::new().with_rotation(),
+            patch_ids_by_data_offset: HashMap::new(),
+            patch_luxels: 0,
+            block_aligned_luxels: 0,
+        }
+    }
 }
 
 impl LightmapBuilder {
     fn build(self) -> Lightmap {
-        let (width, height, offsets_by_patch_id) = self.atlas.bake_smallest();
-        let metadata_by_data_offset: HashMap<i32, LightmapMetadata> = self
+        let baked = self.atlas.bake_smallest();
+        let metadata_by_data_offset: BTreeMap<i32, LightmapMetadata> = self
             .patch_ids_by_data_offset
             .into_iter()
             .map(|(data_offset, patch_id)| {
+                let placement = baked.placement(patch_id).unwrap();
                 (
                     data_offset,
                     LightmapMetadata {
-                        luxel_offset: offsets_by_patch_id[&patch_id],
-                        is_flipped: patch_id.is_flipped(),
+                        luxel_offset: [placement.x, placement.y],
+                        is_flipped: placement.rotated,
                     },
                 )
             })
             .collect();
 
         Lightmap {
-            width,
-            height,
+            width: baked.width(),
+            height: baked.height(),
             metadata_by_data_offset,
+            patch_luxels: self.patch_luxels,
+            block_aligned_luxels: self.block_aligned_luxels,
         }
     }
 }
 
-pub fn build_lightmaps(bsp: Bsp) -> Result<(HashMap<i16, Lightmap>, HashMap<u16, Lightmap>)> {
+/// Lays out a lightmap atlas for each cluster and each displacement.
+///
+/// The results are ordered so that consumers iterating over them produce stable output.
+pub fn build_lightmaps(bsp: Bsp) -> Result<(BTreeMap<i16, Lightmap>, BTreeMap<u16, Lightmap>)> {
+    build_grouped_lightmaps(bsp, |cluster| cluster)
+}
+
+/// Like [`build_lightmaps`], but clusters that `group` maps to the same cluster share one atlas,
+/// keyed by that cluster.
+pub fn build_grouped_lightmaps(
+    bsp: Bsp,
+    group: impl Fn(i16) -> i16,
+) -> Result<(BTreeMap<i16, Lightmap>, BTreeMap<u16, Lightmap>)> {
     fn process_face(face: &Face, lightmap_builder: &mut LightmapBuilder) {
         if face.light_ofs == -1 || face.tex_info == -1 {
             return;
@@ -71,6 +193,8 @@ pub fn build_lightmaps(bsp: Bsp) -> Result<(HashMap<i16, Lightmap>, HashMap<u16,
             // Allocate a patch in the lightmap texture atlas.
             let width = face.lightmap_texture_size_in_luxels[0] as usize + 1;
             let height = face.lightmap_texture_size_in_luxels[1] as usize + 1;
+            lightmap_builder.patch_luxels += width * height;
+            lightmap_builder.block_aligned_luxels += ((width + 3) & !3) * ((height + 3) & !3);
             lightmap_builder
                 .patch_ids_by_data_offset
                 .insert(face.light_ofs, lightmap_builder.atlas.insert(width, height));
@@ -78,18 +202,26 @@ pub fn build_lightmaps(bsp: Bsp) -> Result<(HashMap<i16, Lightmap>, HashMap<u16,
     }
 
     // Lay out an abstract texture atlas for all of the lightmap patches in the map.
-    let mut cluster_lightmap_builders: HashMap<i16, LightmapBuilder> = HashMap::new();
-    for leaf in bsp.iter_worldspawn_leaves() {
-        if leaf.cluster() == -1 {
+    let mut cluster_lightmap_builders: BTreeMap<i16, LightmapBuilder> = BTreeMap::new();
+    for leaf_index in bsp.iter_worldspawn_leaves() {
+        let cluster = bsp.leaf(leaf_index).cluster();
+        if cluster == -1 {
             continue;
         }
-        let lightmap_builder = cluster_lightmap_builders.entry(leaf.cluster()).or_default();
-        for face in bsp.iter_faces_from_leaf(leaf) {
-            process_face(face, lightmap_builder);
+        let lightmap_builder = cluster_lightmap_builders.entry(group(cluster)).or_default();
+        for face in bsp.iter_faces_from_leaf(leaf_index) {
+            process_face(bsp.face(face), lightmap_builder);
         }
     }
 
-    let mut displacement_lightmap_builders = HashMap::new();
+    for (cluster, face, _) in bsp.iter_static_brush_entity_faces() {
+        process_face(
+            bsp.face(face),
+            cluster_lightmap_builders.entry(group(cluster)).or_default(),
+        );
+    }
+
+    let mut displacement_lightmap_builders = BTreeMap::new();
     for disp_info in bsp.disp_infos() {
         let mut lightmap_builder = LightmapBuilder::default();
         process_face(
@@ -100,12 +232,12 @@ pub fn build_lightmaps(bsp: Bsp) -> Result<(HashMap<i16, Lightmap>, HashMap<u16,
     }
 
     // Bake texture atlases.
-    let cluster_lightmaps: HashMap<i16, Lightmap> = cluster_lightmap_builders
+    let cluster_lightmaps: BTreeMap<i16, Lightmap> = cluster_lightmap_builders
         .into_iter()
         .filter(|(_, builder)| !builder.patch_ids_by_data_offset.is_empty())
         .map(|(cluster, builder)| (cluster, builder.build()))
         .collect();
-    let displacement_lightmaps: HashMap<u16, Lightmap> = displacement_lightmap_builders
+    let displacement_lightmaps: BTreeMap<u16, Lightmap> = displacement_lightmap_builders
         .into_iter()
         .filter(|(_, builder)| !builder.patch_ids_by_data_offset.is_empty())
         .map(|(face_index, builder)| (face_index, builder.build()))
@@ -113,3 +245,56 @@ pub fn build_lightmaps(bsp: Bsp) -> Result<(HashMap<i16, Lightmap>, HashMap<u16,
 
     Ok((cluster_lightmaps, displacement_lightmaps))
 }
+
+#[cfg(test)]
+mod tests {
+    use nalgebra_glm::{vec2, vec3};
+
+    use super::{LightmapMetadata, LuxelSpace};
+
+    fn assert_close(a: &[f32], b: &[f32]) {
+        for (a, b) in a.iter().zip(b) {
+            assert!((a - b).abs() < 1e-3, "{a:?} != {b:?}");
+        }
+    }
+
+    #[test]
+    fn luxel_space_round_trip() {
+        // A floor at z = 64 with 16 world units per luxel and skewed axes.
+        let space = LuxelSpace::new(
+            [[0.0625, 0.0, 0.0, 3.5], [0.03125, 0.0625, 0.0, -2.0]],
+            [-10, 4],
+            vec3(0.0, 0.0, 1.0),
+            64.0,
+        );
+        let world = vec3(-96.0, 208.0, 64.0);
+        let luxel = space.world_to_luxel(world);
+        assert_close(luxel.as_slice(), &[7.5, 4.0]);
+        assert_close(space.luxel_to_world(luxel).as_slice(), world.as_slice());
+    }
+
+    #[test]
+    fn luxel_space_wall() {
+        // A wall facing -x at x = -32.
+        let space = LuxelSpace::new(
+            [[0.0, 0.0625, 0.0, 0.0], [0.0, 0.0, -0.0625, 0.0]],
+            [0, -4],
+            vec3(-1.0, 0.0, 0.0),
+            32.0,
+        );
+        let world = space.luxel_to_world(vec2(2.0, 3.0));
+        assert_close(world.as_slice(), &[-32.0, 32.0, 16.0]);
+    }
+
+    #[test]
+    fn luxel_to_atlas_flips() {
+        let metadata = LightmapMetadata {
+            luxel_offset: [8, 16],
+            is_flipped: true,
+        };
+        assert_close(
+            metadata.luxel_to_atlas(vec2(1.0, 2.0)).as_slice(),
+            &[10.0, 17.0],
+        );
+    }
+}
@@ -1,5 +1,40 @@
+use std::rc::Rc;
+
+use anyhow::Result;
+
+use crate::asset::vmt::Vmt;
+use crate::asset::AssetLoader;
+use crate::model::mdl::Mdl;
+use crate::vpk::path::VpkPath;
+
 #[cfg(feature = "glium")]
 pub mod glium;
 pub mod mdl;
+pub mod phy;
 pub mod vtx;
 pub mod vvd;
+
+/// Finds the material for one of the model's textures by searching its texture directories in
+/// order. Returns `None` if no directory has a matching VMT.
+pub fn find_material(
+    asset_loader: &AssetLoader,
+    mdl: Mdl,
+    texture_index: usize,
+) -> Result<Option<Rc<Vmt>>> {
+    let texture_name = mdl.textures()[texture_index].name(mdl);
+    for cd_texture in mdl.cd_textures() {
+        let material_path = VpkPath::new_with_prefix_and_extension(
+            &format!("{}{}", cd_texture.replace('\\', "/"), texture_name),
+            "materials",
+            "vmt",
+        );
+        if asset_loader
+            .material_loader()
+            .load_file(&material_path)?
+            .is_some()
+        {
+            return Ok(Some(asset_loader.get_material(&material_path)?));
+        }
+    }
+    Ok(None)
+}
@@ -5,10 +5,10 @@ use glium::{implement_vertex, Display, IndexBuffer};
 
 use crate::asset::vmt::Vmt;
 use crate::asset::AssetLoader;
+use crate::model::find_material;
 use crate::model::mdl::Mdl;
 use crate::model::vtx::Vtx;
 use crate::model::vvd::Vvd;
-use crate::vpk::path::VpkPath;
 
 #[cfg(feature = "glium")]
 #[derive(Clone, Copy)]
@@ -34,32 +34,38 @@ pub fn build_vertex_buffer(
 ) -> (Vec<Vertex>, Vec<Batch>) {
     const LOD: i32 = 0;
 
-    let mut materials = Vec::new();
-    for (i, texture) in mdl.textures().iter().enumerate() {
-        println!("Texture {}: {}", i, texture.name(mdl));
-        materials.push(
-            asset_loader
-                .get_material(&VpkPath::new_with_prefix_and_extension(
-                    texture.name(mdl),
-                    // TODO: Build this from the model path.
-                    "materials/models/police",
-                    "vmt",
-                ))
-                .ok(),
-        );
-    }
+    let materials: Vec<_> = (0..mdl.textures().len())
+        .map(|texture_index| {
+            let material = find_material(asset_loader, mdl, texture_index)
+                .ok()
+                .flatten();
+            if material.is_none() {
+                eprintln!(
+                    "WARNING: No material found for model texture {}",
+                    mdl.textures()[texture_index].name(mdl),
+                );
+            }
+            material
+        })
+        .collect();
 
     let mut vertex_data = Vec::new();
+    let mut push_vertex = |index| {
+        let vertex = vvd.vertex(index);
+        vertex_data.push(Vertex {
+            position: vertex.position,
+            normal: vertex.normal,
+            tex_coord: vertex.tex_coord,
+        });
+    };
+    if vvd.fixups().is_empty() {
+        // Without fixups, the vertices are already in LOD 0 order.
+        (0..vvd.header().num_lod_vertexes[0] as usize).for_each(&mut push_vertex);
+    }
     for fixup in vvd.fixups() {
         if fixup.lod >= LOD {
             for i in 0..fixup.num_vertexes as usize {
-                let src_index = fixup.source_vertex_id as usize + i;
-                let vertex = vvd.vertex(src_index);
-                vertex_data.push(Vertex {
-                    position: vertex.position,
-                    normal: vertex.normal,
-                    tex_coord: vertex.tex_coord,
-                });
+                push_vertex(fixup.source_vertex_id as usize + i);
             }
         }
     }
@@ -84,7 +90,8 @@ pub fn build_vertex_buffer(
                 let mdl_mesh = &mdl_model.meshes(mdl)[mesh_index];
                 // println!("MDL mesh: {mdl_mesh:?}");
 
-                let base_map = materials[mdl_mesh.material as usize].as_ref();
+                let base_map =
+                    materials[mdl.skin_texture_index(0, mdl_mesh.material as usize)].as_ref();
 
                 for strip_group in vtx_mesh.iter_strip_groups() {
                     // println!("VTX strip group: {strip_group:?}");
@@ -35,6 +35,40 @@ impl<'a> Mdl<'a> {
         cast_slice(bytes)
     }
 
+    pub fn attachments(self) -> &'a [Attachment] {
+        let header = self.header();
+        let bytes = &self.0[header.localattachmentindex as usize..]
+            [..header.numlocalattachments as usize * size_of::<Attachment>()];
+        cast_slice(bytes)
+    }
+
+    /// Directories to search for each texture's material, relative to `materials/`.
+    pub fn cd_textures(self) -> impl Iterator<Item = &'a str> + 'a {
+        let header = self.header();
+        let offsets: &[i32] = cast_slice(
+            &self.0[header.cdtextureindex as usize..][..header.numcdtextures as usize * 4],
+        );
+        offsets
+            .iter()
+            .map(move |&offset| c_str(&self.0[offset as usize..]))
+    }
+
+    /// Maps a mesh's material, which is a skin reference, to an index into `textures()` for the
+    /// given skin family. Out of range skins fall back to the default family.
+    pub fn skin_texture_index(self, skin: usize, skin_ref: usize) -> usize {
+        let header = self.header();
+        let skin = if skin < header.numskinfamilies as usize {
+            skin
+        } else {
+            0
+        };
+        let table: &[i16] = cast_slice(
+            &self.0[header.skinindex as usize..]
+                [..header.numskinfamilies as usize * header.numskinref as usize * 2],
+        );
+        table[skin * header.numskinref as usize + skin_ref] as usize
+    }
+
     fn offset_of<T>(self, t: &T) -> usize {
         let ptr = t as *const T as *const u8;
         let bounds = self.0.as_ptr_range();
@@ -159,6 +193,47 @@ pub struct Bone {
     pub unused: [i32; 8],
 }
 
+impl Bone {
+    pub fn name<'a>(&self, mdl: Mdl<'a>) -> &'a str {
+        c_str(&mdl.0[mdl.offset_of(self) + self.sznameindex as usize..])
+    }
+
+    /// Transforms a point from this bone's space to model space in the bind pose.
+    pub fn transform_to_model(&self, point: [f32; 3]) -> [f32; 3] {
+        // `pose_to_bone` is a row-major rigid transform, so its inverse applies the transposed
+        // rotation after undoing the translation.
+        let m = &self.pose_to_bone;
+        let p = [point[0] - m[3], point[1] - m[7], point[2] - m[11]];
+        [
+            m[0] * p[0] + m[4] * p[1] + m[8] * p[2],
+            m[1] * p[0] + m[5] * p[1] + m[9] * p[2],
+            m[2] * p[0] + m[6] * p[1] + m[10] * p[2],
+        ]
+    }
+}
+
+#[derive(Clone, Copy, Debug, Zeroable, Pod)]
+#[repr(C)]
+pub struct Attachment {
+    pub sznameindex: i32,
+    pub flags: u32,
+    pub localbone: i32,
+    /// Row-major transform from attachment space to the local bone's space.
+    pub local: [f32; 12],
+    pub unused: [i32; 8],
+}
+
+impl Attachment {
+    pub fn name<'a>(&self, mdl: Mdl<'a>) -> &'a str {
+        c_str(&mdl.0[mdl.offset_of(self) + self.sznameindex as usize..])
+    }
+
+    /// The attachment's origin in its local bone's space.
+    pub fn origin(&self) -> [f32; 3] {
+        [self.local[3], self.local[7], self.local[11]]
+    }
+}
+
 #[derive(Clone, Copy, Debug, Zeroable, Pod)]
 #[repr(C)]
 pub struct Texture {
@@ -173,12 +248,15 @@ pub struct Texture {
 
 impl Texture {
     pub fn name<'a>(&self, mdl: Mdl<'a>) -> &'a str {
-        let bytes = &mdl.0[mdl.offset_of(self) + self.sznameindex as usize..];
-        let null_index = bytes.iter().copied().position(|b| b == 0).unwrap();
-        std::str::from_utf8(&bytes[..null_index]).unwrap()
+        c_str(&mdl.0[mdl.offset_of(self) + self.sznameindex as usize..])
     }
 }
 
+fn c_str(bytes: &[u8]) -> &str {
+    let null_index = bytes.iter().copied().position(|b| b == 0).unwrap();
+    std::str::from_utf8(&bytes[..null_index]).unwrap()
+}
+
 #[derive(Clone, Copy, Debug, Zeroable, Pod)]
 #[repr(C)]
 pub struct BodyPart {
@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::mem::size_of;
+
+use anyhow::{bail, Context, Result};
+use bytemuck::{pod_read_unaligned, Pod, Zeroable};
+use nalgebra_glm::{max2, min2, vec3, Vec3};
+
+const METERS_TO_INCHES: f32 = 1.0 / 0.0254;
+
+/// A parsed .phy file: the collision model for an MDL.
+pub struct Phy {
+    pub header: Header,
+    pub solids: Vec<CollisionSolid>,
+    /// Per-solid properties (mass, surface material, ...) as Valve key-value text.
+    pub key_values: String,
+}
+
+impl Phy {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let header: Header = read(data, 0).context("reading phy header")?;
+        let body = data
+            .get(header.size as usize..)
+            .context("phy header size is out of bounds")?;
+        let (solids, rest) = parse_solids(body, header.solid_count as usize)?;
+        Ok(Self {
+            header,
+            solids,
+            key_values: parse_key_values(rest),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Zeroable, Pod)]
+#[repr(C)]
+pub struct Header {
+    pub size: i32,
+    pub id: i32,
+    pub solid_count: i32,
+    pub checksum: i32,
+}
+
+/// One rigid collision shape, made of convex pieces.
+#[derive(Clone, Debug, Default)]
+pub struct CollisionSolid {
+    pub pieces: Vec<ConvexPiece>,
+}
+
+impl CollisionSolid {
+    /// Returns the axis-aligned bounds of every vertex, or `None` if the solid is empty.
+    pub fn bounds(&self) -> Option<(Vec3, Vec3)> {
+        self.pieces.iter().filter_map(ConvexPiece::bounds).reduce(
+            |(mins_a, maxs_a), (mins_b, maxs_b)| (min2(&mins_a, &mins_b), max2(&maxs_a, &maxs_b)),
+        )
+    }
+}
+
+/// A convex hull. Vertices are in Source units, relative to the model origin.
+#[derive(Clone, Debug, Default)]
+pub struct ConvexPiece {
+    pub vertices: Vec<Vec3>,
+    /// Indices into `vertices`.
+    pub triangles: Vec<[u16; 3]>,
+}
+
+impl ConvexPiece {
+    pub fn bounds(&self) -> Option<(Vec3, Vec3)> {
+        let (first, rest) = self.vertices.split_first()?;
+        Some(rest.iter().fold((*first, *first), |(mins, maxs), v| {
+            (min2(&mins, v), max2(&maxs, v))
+        }))
+    }
+}
+
+/// Parses `solid_count` size-prefixed solids, as found in .phy files and the BSP physics lump.
+/// Returns the solids and whatever data follows them.
+pub fn parse_solids(data: &[u8], solid_count: usize) -> Result<(Vec<CollisionSolid>, &[u8])> {
+    let mut solids = Vec::with_capacity(solid_count);
+    let mut offset = 0;
+    for index in 0..solid_count {
+        let size: i32 =
+            read(data, offset).with_context(|| format!("reading solid {index} size"))?;
+        offset += size_of::<i32>();
+        let solid_data = data
+            .get(offset..offset + size as usize)
+            .with_context(|| format!("solid {index} is out of bounds"))?;
+        solids.push(parse_solid(solid_data).with_context(|| format!("parsing solid {index}"))?);
+        offset += size as usize;
+    }
+    Ok((solids, &data[offset..]))
+}
+
+pub(crate) fn parse_key_values(data: &[u8]) -> String {
+    let end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+    String::from_utf8_lossy(&data[..end]).into_owned()
+}
+
+fn parse_solid(data: &[u8]) -> Result<CollisionSolid> {
+    let surface = if data.get(..4) == Some(b"VPHY") {
+        let header: SurfaceHeader = read(data, 0)?;
+        if header.model_type != 0 {
+            bail!("unsupported collision model type {}", header.model_type);
+        }
+        &data[size_of::<SurfaceHeader>()..]
+    } else {
+        // Legacy solids have no header.
+        data
+    };
+    parse_compact_surface(surface)
+}
+
+fn parse_compact_surface(data: &[u8]) -> Result<CollisionSolid> {
+    let surface: CompactSurface = read(data, 0).context("reading compact surface")?;
+
+    // Ledges are packed back to back, followed by their shared points and then the ledge tree.
+    let mut end = (surface.offset_ledgetree_root as usize).min(data.len());
+    let mut offset = size_of::<CompactSurface>();
+    let mut pieces = Vec::new();
+    while offset < end {
+        let ledge: CompactLedge = read(data, offset).context("reading ledge")?;
+        let ledge_size = ledge.size();
+        if ledge_size == 0 {
+            bail!("zero-sized ledge at offset {offset}");
+        }
+        let points_offset = offset
+            .checked_add_signed(ledge.c_point_offset as isize)
+            .context("ledge point offset is out of bounds")?;
+        end = end.min(points_offset);
+
+        let mut piece = ConvexPiece::default();
+        let mut vertex_indices = HashMap::new();
+        for triangle_index in 0..ledge.n_triangles as usize {
+            let triangle: CompactTriangle = read(
+                data,
+                offset + size_of::<CompactLedge>() + triangle_index * size_of::<CompactTriangle>(),
+            )
+            .context("reading triangle")?;
+            let mut indices = [0; 3];
+            for (index, edge) in indices.iter_mut().zip(triangle.edges) {
+                let point_index = edge & 0xffff;
+                *index = match vertex_indices.get(&point_index) {
+                    Some(&index) => index,
+                    None => {
+                        let point: [f32; 4] = read(data, points_offset + point_index as usize * 16)
+                            .context("reading point")?;
+                        let index = piece.vertices.len() as u16;
+                        piece.vertices.push(ivp_to_source(point));
+                        vertex_indices.insert(point_index, index);
+                        index
+                    }
+                };
+            }
+            piece.triangles.push(indices);
+        }
+        pieces.push(piece);
+        offset += ledge_size;
+    }
+    Ok(CollisionSolid { pieces })
+}
+
+/// Converts from IVP's meters with Y down to Source's inches with Z up.
+fn ivp_to_source(point: [f32; 4]) -> Vec3 {
+    vec3(point[0], point[2], -point[1]) * METERS_TO_INCHES
+}
+
+fn read<T: Pod>(data: &[u8], offset: usize) -> Result<T> {
+    match data.get(offset..offset + size_of::<T>()) {
+        Some(bytes) => Ok(pod_read_unaligned(bytes)),
+        None => bail!(
+            "read of {} bytes at offset {offset} is out of bounds",
+            size_of::<T>()
+        ),
+    }
+}
+
+#[derive(Clone, Copy, Debug, Zeroable, Pod)]
+#[repr(C)]
+struct SurfaceHeader {
+    vphysics_id: [u8; 4],
+    version: u16,
+    model_type: i16,
+    surface_size: i32,
+    drag_axis_areas: [f32; 3],
+    axis_map_size: i32,
+}
+
+#[derive(Clone, Copy, Debug, Zeroable, Pod)]
+#[repr(C)]
+struct CompactSurface {
+    mass_center: [f32; 3],
+    rotation_inertia: [f32; 3],
+    upper_limit_radius: f32,
+    /// max_deviation:8, byte_size:24
+    max_deviation_and_byte_size: u32,
+    offset_ledgetree_root: i32,
+    dummy: [i32; 3],
+}
+
+#[derive(Clone, Copy, Debug, Zeroable, Pod)]
+#[repr(C)]
+struct CompactLedge {
+    /// Relative to the start of this ledge.
+    c_point_offset: i32,
+    client_data: i32,
+    /// has_children_flag:2, is_compact_flag:2, dummy:4, size_div_16:24
+    flags: u32,
+    n_triangles: i16,
+    for_future_use: i16,
+}
+
+impl CompactLedge {
+    fn size(&self) -> usize {
+        (self.flags >> 8) as usize * 16
+    }
+}
+
+#[derive(Clone, Copy, Debug, Zeroable, Pod)]
+#[repr(C)]
+struct CompactTriangle {
+    /// tri_index:12, pierce_index:12, material_index:7, is_virtual:1
+    flags: u32,
+    /// start_point_index:16, opposite_index:15, is_virtual:1
+    edges: [u32; 3],
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::size_of;
+
+    use bytemuck::bytes_of;
+
+    use super::*;
+
+    #[test]
+    fn struct_sizes() {
+        assert_eq!(size_of::<SurfaceHeader>(), 28);
+        assert_eq!(size_of::<CompactSurface>(), 48);
+        assert_eq!(size_of::<CompactLedge>(), 16);
+        assert_eq!(size_of::<CompactTriangle>(), 16);
+    }
+
+    #[test]
+    fn parse_single_triangle() {
+        let ledge_offset = size_of::<CompactSurface>();
+        let points_offset = ledge_offset + size_of::<CompactLedge>() + size_of::<CompactTriangle>();
+        let points: [[f32; 4]; 3] = [
+            [0.0, 0.0, 0.0, 0.0],
+            [0.0254, 0.0, 0.0, 0.0],
+            [0.0, -0.0254, 0.0, 0.0],
+        ];
+        let surface = CompactSurface {
+            offset_ledgetree_root: (points_offset + 3 * 16) as i32,
+            ..Zeroable::zeroed()
+        };
+        let ledge = CompactLedge {
+            c_point_offset: (points_offset - ledge_offset) as i32,
+            flags: 2 << 8,
+            n_triangles: 1,
+            ..Zeroable::zeroed()
+        };
+        let triangle = CompactTriangle {
+            flags: 0,
+            edges: [0, 1, 2],
+        };
+
+        let mut data = Vec::new();
+        data.extend_from_slice(bytes_of(&surface));
+        data.extend_from_slice(bytes_of(&ledge));
+        data.extend_from_slice(bytes_of(&triangle));
+        for point in &points {
+            data.extend_from_slice(bytes_of(point));
+        }
+
+        let solid = parse_solid(&data).unwrap();
+        assert_eq!(solid.pieces.len(), 1);
+        assert_eq!(solid.pieces[0].triangles, [[0, 1, 2]]);
+        let (mins, maxs) = solid.bounds().unwrap();
+        approx::assert_relative_eq!(mins, vec3(0.0, 0.0, 0.0), epsilon = 1e-4);
+        approx::assert_relative_eq!(maxs, vec3(1.0, 0.0, 1.0), epsilon = 1e-4);
+    }
+}
@@ -23,6 +23,14 @@ impl<'a> Vtx<'a> {
         bytemuck::cast_slice(bytes)
     }
 
+    /// One list per LOD, naming the materials that LOD swaps in for cheaper ones.
+    pub fn material_replacement_lists(self) -> &'a [MaterialReplacementListHeader] {
+        let header = self.header();
+        let bytes = &self.0[header.material_replacement_list_offset as usize..]
+            [..header.num_lods as usize * size_of::<MaterialReplacementListHeader>()];
+        bytemuck::cast_slice(bytes)
+    }
+
     fn offset_of<T>(self, t: &T) -> usize {
         let ptr = t as *const T as *const u8;
         let bounds = self.0.as_ptr_range();
@@ -74,6 +82,33 @@ impl ModelHeader {
             [..self.num_lods as usize * size_of::<ModelLodHeader>()];
         bytemuck::cast_slice(bytes)
     }
+
+    /// Picks a LOD, returning its index along with it. Returns `None` if the model has no LODs.
+    pub fn select_lod<'a>(
+        &self,
+        vtx: Vtx<'a>,
+        selection: LodSelection,
+    ) -> Option<(usize, &'a ModelLodHeader)> {
+        let lods = self.lods(vtx);
+        let index = match selection {
+            LodSelection::Index(index) => index.min(lods.len().checked_sub(1)?),
+            // A negative switch point marks the shadow LOD, which is never drawn by distance.
+            LodSelection::Distance(distance) => lods
+                .iter()
+                .rposition(|lod| lod.switch_point >= 0.0 && lod.switch_point <= distance)
+                .or_else(|| (!lods.is_empty()).then_some(0))?,
+        };
+        Some((index, &lods[index]))
+    }
+}
+
+/// How [`ModelHeader::select_lod`] picks a LOD.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LodSelection {
+    /// This LOD, or the coarsest one if the model doesn't have that many.
+    Index(usize),
+    /// The coarsest LOD whose switch point has been passed at this distance.
+    Distance(f32),
 }
 
 #[derive(Clone, Copy, Debug, Zeroable, Pod)]
@@ -193,8 +228,8 @@ impl<'a> StripGroupHeader<'a> {
             .unwrap()
     }
 
-    fn flags(self) -> u8 {
-        self.vtx[self.offset + 24]
+    pub fn flags(self) -> StripGroupHeaderFlags {
+        StripGroupHeaderFlags(self.vtx[self.offset + 24])
     }
 
     pub fn vert(self, index: usize) -> Vertex<'a> {
@@ -254,6 +289,56 @@ impl<'a> Debug for StripGroupHeader<'a> {
     }
 }
 
+/// A flag's name for `Debug` output and the predicate that tests for it.
+type NamedFlag<T> = (&'static str, fn(T) -> bool);
+
+#[derive(Clone, Copy)]
+pub struct StripGroupHeaderFlags(pub u8);
+
+impl StripGroupHeaderFlags {
+    pub fn is_flexed(self) -> bool {
+        (self.0 & 1) != 0
+    }
+
+    pub fn is_hwskinned(self) -> bool {
+        (self.0 & 2) != 0
+    }
+
+    pub fn is_delta_flexed(self) -> bool {
+        (self.0 & 4) != 0
+    }
+
+    pub fn suppress_hw_morph(self) -> bool {
+        (self.0 & 8) != 0
+    }
+}
+
+impl Debug for StripGroupHeaderFlags {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let flags: &[NamedFlag<Self>] = &[
+            ("IS_FLEXED", |x| x.is_flexed()),
+            ("IS_HWSKINNED", |x| x.is_hwskinned()),
+            ("IS_DELTA_FLEXED", |x| x.is_delta_flexed()),
+            ("SUPPRESS_HW_MORPH", |x| x.suppress_hw_morph()),
+            ("UNKNOWN_BITS", |x| (x.0 & !15) != 0),
+        ];
+        let mut any = false;
+        for &(name, predicate) in flags {
+            if predicate(*self) {
+                if any {
+                    write!(f, " | ")?;
+                }
+                any = true;
+                write!(f, "{}", name)?;
+            }
+        }
+        if !any {
+            write!(f, "0")?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct Vertex<'a> {
     vtx: &'a [u8],
@@ -397,7 +482,7 @@ impl StripHeaderFlags {
 
 impl Debug for StripHeaderFlags {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        let flags: &[(&str, fn(Self) -> bool)] = &[
+        let flags: &[NamedFlag<Self>] = &[
             ("IS_TRILIST", |x| x.is_trilist()),
             ("IS_TRISTRIP", |x| x.is_tristrip()),
             ("UNKNOWN_BITS", |x| (x.0 & !3) != 0),
@@ -450,3 +535,156 @@ impl<'a> Debug for BoneStateChangeHeader<'a> {
             .finish()
     }
 }
+
+#[derive(Clone, Copy, Debug, Zeroable, Pod)]
+#[repr(C)]
+pub struct MaterialReplacementListHeader {
+    pub num_replacements: i32,
+    pub replacement_offset: i32,
+}
+
+impl MaterialReplacementListHeader {
+    pub fn iter_replacements<'a>(
+        &self,
+        vtx: Vtx<'a>,
+    ) -> impl Iterator<Item = MaterialReplacementHeader<'a>> + 'a {
+        let replacement_offset = vtx.offset_of(self) + self.replacement_offset as usize;
+        (0..(self.num_replacements as usize)).map(move |index| MaterialReplacementHeader {
+            vtx: vtx.0,
+            offset: replacement_offset + index * MaterialReplacementHeader::SIZE,
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct MaterialReplacementHeader<'a> {
+    vtx: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> MaterialReplacementHeader<'a> {
+    const SIZE: usize = 6;
+
+    /// The index of the MDL texture being replaced.
+    pub fn material_id(self) -> i16 {
+        (&self.vtx[self.offset..][..2])
+            .read_i16::<LittleEndian>()
+            .unwrap()
+    }
+
+    pub fn replacement_material_name_offset(self) -> i32 {
+        (&self.vtx[self.offset + 2..][..4])
+            .read_i32::<LittleEndian>()
+            .unwrap()
+    }
+
+    pub fn replacement_material_name(self) -> &'a str {
+        let bytes = &self.vtx[self.offset + self.replacement_material_name_offset() as usize..];
+        let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        std::str::from_utf8(&bytes[..len]).unwrap()
+    }
+}
+
+impl<'a> Debug for MaterialReplacementHeader<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("MaterialReplacementHeader")
+            .field("offset", &self.offset)
+            .field("material_id", &self.material_id())
+            .field(
+                "replacement_material_name",
+                &self.replacement_material_name(),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::{bytes_of, Zeroable};
+
+    use super::*;
+
+    /// Builds a VTX with one body part holding one model, with LODs switching at `switch_points`,
+    /// and one material replacement per LOD.
+    fn build_vtx(switch_points: &[f32]) -> Vec<u8> {
+        let num_lods = switch_points.len();
+        let body_part_offset = size_of::<Header>();
+        let model_offset = body_part_offset + size_of::<BodyPart>();
+        let lod_offset = model_offset + size_of::<ModelHeader>();
+        let list_offset = lod_offset + num_lods * size_of::<ModelLodHeader>();
+        let replacement_offset =
+            list_offset + num_lods * size_of::<MaterialReplacementListHeader>();
+        let name_offset = replacement_offset + num_lods * MaterialReplacementHeader::SIZE;
+
+        let mut data = Vec::new();
+        data.extend_from_slice(bytes_of(&Header {
+            version: 7,
+            num_lods: num_lods as i32,
+            material_replacement_list_offset: list_offset as i32,
+            num_body_parts: 1,
+            body_part_offset: body_part_offset as i32,
+            ..Zeroable::zeroed()
+        }));
+        data.extend_from_slice(bytes_of(&BodyPart {
+            num_models: 1,
+            model_offset: (model_offset - body_part_offset) as i32,
+        }));
+        data.extend_from_slice(bytes_of(&ModelHeader {
+            num_lods: num_lods as i32,
+            lod_offset: (lod_offset - model_offset) as i32,
+        }));
+        for &switch_point in switch_points {
+            data.extend_from_slice(bytes_of(&ModelLodHeader {
+                switch_point,
+                ..Zeroable::zeroed()
+            }));
+        }
+        for lod in 0..num_lods {
+            let offset = list_offset + lod * size_of::<MaterialReplacementListHeader>();
+            data.extend_from_slice(bytes_of(&MaterialReplacementListHeader {
+                num_replacements: 1,
+                replacement_offset: (replacement_offset + lod * MaterialReplacementHeader::SIZE
+                    - offset) as i32,
+            }));
+        }
+        for lod in 0..num_lods {
+            let offset = replacement_offset + lod * MaterialReplacementHeader::SIZE;
+            data.extend_from_slice(&(lod as i16).to_le_bytes());
+            data.extend_from_slice(&((name_offset + 6 * lod - offset) as i32).to_le_bytes());
+        }
+        for lod in 0..num_lods {
+            data.extend_from_slice(format!("lod_{}\0", lod).as_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn select_lod_by_distance_skips_shadow_lod() {
+        let data = build_vtx(&[0.0, 100.0, 300.0, -1.0]);
+        let vtx = Vtx::new(&data);
+        let model = &vtx.body_parts()[0].models(vtx)[0];
+
+        let select = |selection| model.select_lod(vtx, selection).unwrap().0;
+        assert_eq!(select(LodSelection::Distance(0.0)), 0);
+        assert_eq!(select(LodSelection::Distance(99.0)), 0);
+        assert_eq!(select(LodSelection::Distance(100.0)), 1);
+        assert_eq!(select(LodSelection::Distance(1000.0)), 2);
+        assert_eq!(select(LodSelection::Index(3)), 3);
+        assert_eq!(select(LodSelection::Index(10)), 3);
+    }
+
+    #[test]
+    fn material_replacements() {
+        let data = build_vtx(&[0.0, 100.0]);
+        let vtx = Vtx::new(&data);
+
+        let lists = vtx.material_replacement_lists();
+        assert_eq!(lists.len(), 2);
+        let replacements: Vec<_> = lists
+            .iter()
+            .flat_map(|list| list.iter_replacements(vtx))
+            .map(|r| (r.material_id(), r.replacement_material_name()))
+            .collect();
+        assert_eq!(replacements, [(0, "lod_0"), (1, "lod_1")]);
+    }
+}
@@ -11,7 +11,7 @@ use fully_occupied::{extract, FullyOccupied};
 use memmap::Mmap;
 use try_insert_ext::EntryInsertExt;
 
-use crate::file::canonical_path::CanonicalPathBuf;
+use crate::file::canonical_path::{CanonicalPath, CanonicalPathBuf};
 use crate::file::FileLoader;
 use crate::vpk::path::VpkPath;
 
@@ -128,6 +128,31 @@ impl Vpk {
         })
     }
 
+    /// Lists every file in the archive with the given extension, in sorted order.
+    pub fn paths_with_extension(&self, extension: &str) -> Vec<VpkPath> {
+        let entries_by_parent_file_stem = match CanonicalPath::from_str(extension)
+            .and_then(|extension| self.entries_by_extension_parent_file_stem.get(extension))
+        {
+            Some(entries) => entries,
+            None => return Vec::new(),
+        };
+        let mut paths: Vec<VpkPath> = entries_by_parent_file_stem
+            .iter()
+            .flat_map(|(parent, entries_by_file_stem)| {
+                // The directory tree spells the root directory as a single space.
+                let prefix = match parent.as_str() {
+                    " " => "",
+                    parent => parent,
+                };
+                entries_by_file_stem.keys().map(move |file_stem| {
+                    VpkPath::new_with_prefix_and_extension(file_stem.as_str(), prefix, extension)
+                })
+            })
+            .collect();
+        paths.sort();
+        paths
+    }
+
     fn get_archive<'a>(
         archives: &'a mut HashMap<u16, Mmap>,
         path: &mut PathBuf,
@@ -181,16 +206,14 @@ impl FileLoader for Vpk {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, FullyOccupied)]
 #[repr(C)]
 struct HeaderV1 {
     signature: u32,
     version: u32,
 }
 
-unsafe impl FullyOccupied for HeaderV1 {}
-
-#[derive(Debug)]
+#[derive(Debug, FullyOccupied)]
 #[repr(C)]
 struct HeaderV2 {
     signature: u32,
@@ -202,8 +225,6 @@ struct HeaderV2 {
     signature_section_size: u32,
 }
 
-unsafe impl FullyOccupied for HeaderV2 {}
-
 struct DirectoryEntry {
     _crc: u32,
     preload_offset: usize,
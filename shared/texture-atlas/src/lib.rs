@@ -50,22 +50,39 @@ impl RgbU8Image {
 #[derive(Default)]
 pub struct TextureAtlas {
     patches: Vec<(usize, usize)>,
+    allow_rotation: bool,
 }
 
-#[derive(Clone, Copy, Hash, PartialEq, Eq)]
-pub struct PatchId(isize);
+/// Identifies a patch inserted into an atlas.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct PatchId(usize);
 
-impl PatchId {
-    fn new(index: usize, width: usize, height: usize) -> Self {
-        if height > width {
-            Self(-(index as isize) - 1)
+/// Where a patch ended up in a baked atlas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Placement {
+    pub x: usize,
+    pub y: usize,
+    /// The patch was turned 90 degrees, so its rows run down the atlas and its columns across.
+    pub rotated: bool,
+}
+
+impl Placement {
+    /// The patch's width and height as laid out in the atlas.
+    pub fn oriented_size(&self, width: usize, height: usize) -> (usize, usize) {
+        if self.rotated {
+            (height, width)
         } else {
-            Self(index as isize)
+            (width, height)
         }
     }
 
-    pub fn is_flipped(self) -> bool {
-        self.0 < 0
+    /// Maps a texel within the patch to its position in the atlas.
+    pub fn to_atlas(&self, x: usize, y: usize) -> (usize, usize) {
+        if self.rotated {
+            (self.x + y, self.y + x)
+        } else {
+            (self.x + x, self.y + y)
+        }
     }
 }
 
@@ -74,97 +91,57 @@ impl TextureAtlas {
         Self::default()
     }
 
+    /// Lets patches be turned 90 degrees when that fits them into a smaller open space.
+    pub fn with_rotation(mut self) -> Self {
+        self.allow_rotation = true;
+        self
+    }
+
     pub fn is_empty(&self) -> bool {
         self.patches.is_empty()
     }
 
     pub fn insert(&mut self, width: usize, height: usize) -> PatchId {
-        let id = PatchId::new(self.patches.len(), width, height);
+        let id = PatchId(self.patches.len());
         self.patches.push((width, height));
         id
     }
 
-    pub fn bake(self, width: usize, height: usize) -> Result<HashMap<PatchId, [usize; 2]>, Self> {
-        let mut open = vec![(0, 0, width, height)];
+    pub fn bake(self, width: usize, height: usize) -> Result<BakedAtlas, Self> {
+        let mut baked = BakedAtlas {
+            width,
+            height,
+            placements_by_patch_id: HashMap::new(),
+            open: vec![(0, 0, width, height)],
+            next_index: self.patches.len(),
+            allow_rotation: self.allow_rotation,
+        };
 
-        let mut offsets_by_patch_id = HashMap::new();
         let mut patches: Vec<(PatchId, (usize, usize))> = self
             .patches
             .iter()
             .enumerate()
-            .map(|(index, &(patch_width, patch_height))| {
-                (
-                    PatchId::new(index, patch_width, patch_height),
-                    (patch_width, patch_height),
-                )
-            })
+            .map(|(index, &size)| (PatchId(index), size))
             .collect();
         patches.sort_by_key(|&(_, (patch_width, patch_height))| patch_width * patch_height);
-        'for_each_patch: while let Some((patch_id, (patch_width, patch_height))) = patches.pop() {
-            let (oriented_patch_width, oriented_patch_height) = if patch_id.is_flipped() {
-                (patch_height, patch_width)
-            } else {
-                (patch_width, patch_height)
-            };
-            if oriented_patch_width > width || oriented_patch_height > height {
-                return Err(self);
-            }
-
-            // Consider smaller open spaces first.
-            //open.sort_by_key(|&(_, _, width, height)| width * height);
-            open.sort_by(|&(_, _, wa, ha), &(_, _, wb, hb)| wa.cmp(&wb).then_with(|| ha.cmp(&hb)));
-
-            for (open_index, (open_x0, open_y0, open_width, open_height)) in
-                open.iter().copied().enumerate()
-            {
-                if open_width < oriented_patch_width || open_height < oriented_patch_height {
-                    continue;
-                }
-
-                // Found a sufficiently sized open space. Place this patch there.
-                offsets_by_patch_id.insert(patch_id, [open_x0, open_y0]);
-
-                // Remove the open space that was just used and add any leftover areas.
-                open.remove(open_index);
-                // Reserve entire S3TC/DXT1/BC1 blocks to keep lightmaps from popping horribly.
-                let used_width = (oriented_patch_width + 3) & !3;
-                let used_height = (oriented_patch_height + 3) & !3;
-                if used_width < open_width {
-                    // There is unused space to the right of the placed patch. Limit this open space
-                    // to the patch's height, leaving the full width available for the next check.
-                    open.push((
-                        open_x0 + used_width,
-                        open_y0,
-                        open_width - used_width,
-                        used_height,
-                    ));
-                }
-                if used_height < open_height {
-                    // There is unused space below the placed patch. Claim the entire width, which
-                    // was left open just above.
-                    open.push((
-                        open_x0,
-                        open_y0 + used_height,
-                        open_width,
-                        open_height - used_height,
-                    ));
+        while let Some((patch_id, (patch_width, patch_height))) = patches.pop() {
+            match baked.place(patch_width, patch_height) {
+                Some(placement) => {
+                    baked.placements_by_patch_id.insert(patch_id, placement);
                 }
-
-                // Successfully placed this patch. Move on to the next patch.
-                continue 'for_each_patch;
+                None => return Err(self),
             }
-            return Err(self);
         }
 
-        Ok(offsets_by_patch_id)
+        Ok(baked)
     }
 
-    pub fn bake_smallest(mut self) -> (usize, usize, HashMap<PatchId, [usize; 2]>) {
+    pub fn bake_smallest(mut self) -> BakedAtlas {
         let mut width = 1;
         let mut height = 1;
         loop {
             match self.bake(width, height) {
-                Ok(result) => return (width, height, result),
+                Ok(baked) => return baked,
                 Err(recovered) => self = recovered,
             }
 
@@ -180,3 +157,182 @@ impl TextureAtlas {
         }
     }
 }
+
+/// An atlas with its patches placed. Space left over from baking stays available for patches
+/// inserted afterward.
+pub struct BakedAtlas {
+    width: usize,
+    height: usize,
+    placements_by_patch_id: HashMap<PatchId, Placement>,
+    /// Open spaces as `(x0, y0, width, height)`.
+    open: Vec<(usize, usize, usize, usize)>,
+    next_index: usize,
+    allow_rotation: bool,
+}
+
+impl BakedAtlas {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn placement(&self, patch_id: PatchId) -> Option<Placement> {
+        self.placements_by_patch_id.get(&patch_id).copied()
+    }
+
+    pub fn placements(&self) -> &HashMap<PatchId, Placement> {
+        &self.placements_by_patch_id
+    }
+
+    /// Places a patch in the remaining open space, or returns `None` if it doesn't fit. Existing
+    /// patches never move.
+    pub fn insert(&mut self, width: usize, height: usize) -> Option<(PatchId, Placement)> {
+        let patch_id = PatchId(self.next_index);
+        let placement = self.place(width, height)?;
+        self.next_index += 1;
+        self.placements_by_patch_id.insert(patch_id, placement);
+        Some((patch_id, placement))
+    }
+
+    /// Claims open space for a patch and returns where it went.
+    fn place(&mut self, patch_width: usize, patch_height: usize) -> Option<Placement> {
+        // Consider smaller open spaces first.
+        self.open
+            .sort_by(|&(_, _, wa, ha), &(_, _, wb, hb)| wa.cmp(&wb).then_with(|| ha.cmp(&hb)));
+
+        let fit = |rotated: bool| {
+            let (width, height) = if rotated {
+                (patch_height, patch_width)
+            } else {
+                (patch_width, patch_height)
+            };
+            self.open
+                .iter()
+                .position(|&(_, _, open_width, open_height)| {
+                    open_width >= width && open_height >= height
+                })
+                .map(|open_index| (open_index, rotated))
+        };
+        // With rotation allowed, take whichever orientation fits the smaller open space. Ties keep
+        // the patch upright.
+        let (open_index, rotated) = match (fit(false), self.allow_rotation.then(|| fit(true))) {
+            (Some(upright), Some(Some(rotated))) => {
+                let area = |(open_index, _): (usize, bool)| {
+                    let (_, _, width, height) = self.open[open_index];
+                    width * height
+                };
+                if area(rotated) < area(upright) {
+                    rotated
+                } else {
+                    upright
+                }
+            }
+            (Some(upright), _) => upright,
+            (None, Some(rotated)) => rotated?,
+            (None, None) => return None,
+        };
+        let (oriented_patch_width, oriented_patch_height) = if rotated {
+            (patch_height, patch_width)
+        } else {
+            (patch_width, patch_height)
+        };
+
+        // Found a sufficiently sized open space. Remove it and add any leftover areas.
+        let (open_x0, open_y0, open_width, open_height) = self.open.remove(open_index);
+        // Reserve entire S3TC/DXT1/BC1 blocks to keep lightmaps from popping horribly.
+        let used_width = (oriented_patch_width + 3) & !3;
+        let used_height = (oriented_patch_height + 3) & !3;
+        if used_width < open_width {
+            // There is unused space to the right of the placed patch. Limit this open space to
+            // the patch's height, leaving the full width available for the next check.
+            self.open.push((
+                open_x0 + used_width,
+                open_y0,
+                open_width - used_width,
+                used_height,
+            ));
+        }
+        if used_height < open_height {
+            // There is unused space below the placed patch. Claim the entire width, which was
+            // left open just above.
+            self.open.push((
+                open_x0,
+                open_y0 + used_height,
+                open_width,
+                open_height - used_height,
+            ));
+        }
+
+        Some(Placement {
+            x: open_x0,
+            y: open_y0,
+            rotated,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Placement, TextureAtlas};
+
+    #[test]
+    fn insert_after_bake_uses_leftover_space() {
+        let mut atlas = TextureAtlas::new();
+        let first = atlas.insert(8, 8);
+        let mut baked = atlas.bake(16, 8).ok().unwrap();
+        assert_eq!(
+            baked.placement(first),
+            Some(Placement {
+                x: 0,
+                y: 0,
+                rotated: false,
+            }),
+        );
+
+        let (second, placement) = baked.insert(4, 4).unwrap();
+        assert!(second != first);
+        assert_eq!((placement.x, placement.y), (8, 0));
+        assert_eq!(baked.placement(second), Some(placement));
+    }
+
+    #[test]
+    fn insert_after_bake_fails_when_full() {
+        let mut atlas = TextureAtlas::new();
+        atlas.insert(8, 8);
+        let mut baked = atlas.bake(8, 8).ok().unwrap();
+        assert!(baked.insert(1, 1).is_none());
+        assert_eq!(baked.placements().len(), 1);
+    }
+
+    #[test]
+    fn rotates_only_when_allowed() {
+        let mut atlas = TextureAtlas::new();
+        atlas.insert(16, 4);
+        assert!(atlas.bake(4, 16).is_err());
+
+        let mut atlas = TextureAtlas::new().with_rotation();
+        let patch = atlas.insert(16, 4);
+        let baked = atlas.bake(4, 16).ok().unwrap();
+        let placement = baked.placement(patch).unwrap();
+        assert!(placement.rotated);
+        assert_eq!(placement.oriented_size(16, 4), (4, 16));
+        assert_eq!(placement.to_atlas(15, 1), (1, 15));
+    }
+
+    #[test]
+    fn rotation_fills_leftover_strips() {
+        // Two tall patches side by side leave a strip along the bottom that only fits the long
+        // patch lying down.
+        let mut atlas = TextureAtlas::new().with_rotation();
+        atlas.insert(8, 12);
+        atlas.insert(8, 12);
+        let long = atlas.insert(4, 16);
+        let baked = atlas.bake(16, 16).ok().unwrap();
+        let placement = baked.placement(long).unwrap();
+        assert!(placement.rotated);
+        assert_eq!((placement.x, placement.y), (0, 12));
+    }
+}
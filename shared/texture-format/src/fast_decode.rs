@@ -0,0 +1,237 @@
+//! Whole-texture decoding to RGBA8 for DXT1-style formats.
+//!
+//! The general transcoding path decodes one texel at a time, which re-derives a block's palette for
+//! each of its sixteen texels. These paths decode each block once and write whole rows. With the
+//! `std` feature, RGB565 endpoints are expanded with SSE2 on x86_64 and NEON on aarch64.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::codec::dxt1::Dxt1;
+use crate::codec::dxt_common::blend;
+use crate::codec::gx_tf_cmpr::{permute_dxt1_for_gamecube, GxTfCmpr};
+use crate::codec::Codec;
+
+/// Decodes DXT1 blocks, stored in row major order.
+pub(crate) fn dxt1_to_rgba8(width: usize, height: usize, data: &[u8]) -> Vec<u8> {
+    let blocks_wide = Dxt1::METRICS.blocks_wide(width);
+    decode(width, height, |block_x, block_y| {
+        let offset = 8 * (blocks_wide * block_y + block_x);
+        data[offset..offset + 8].try_into().unwrap()
+    })
+}
+
+/// Decodes GX_TF_CMPR tiles, each of which holds four permuted DXT1 blocks.
+pub(crate) fn gx_tf_cmpr_to_rgba8(width: usize, height: usize, data: &[u8]) -> Vec<u8> {
+    let tiles_wide = GxTfCmpr::METRICS.blocks_wide(width);
+    decode(width, height, |block_x, block_y| {
+        let offset =
+            32 * (tiles_wide * (block_y / 2) + block_x / 2) + 8 * (2 * (block_y % 2) + block_x % 2);
+        permute_dxt1_for_gamecube(data[offset..offset + 8].try_into().unwrap())
+    })
+}
+
+fn decode(width: usize, height: usize, block_at: impl Fn(usize, usize) -> [u8; 8]) -> Vec<u8> {
+    let blocks_wide = Dxt1::METRICS.blocks_wide(width);
+    let blocks_high = Dxt1::METRICS.blocks_high(height);
+    let mut dst = vec![0; 4 * width * height];
+
+    let mut blocks = Vec::with_capacity(blocks_wide);
+    let mut endpoints = Vec::with_capacity(2 * blocks_wide);
+    let mut endpoints_rgba = vec![[0; 4]; 2 * blocks_wide];
+    for block_y in 0..blocks_high {
+        // Expand every endpoint in the row of blocks at once.
+        blocks.clear();
+        endpoints.clear();
+        for block_x in 0..blocks_wide {
+            let block = block_at(block_x, block_y);
+            endpoints.push(u16::from_le_bytes([block[0], block[1]]));
+            endpoints.push(u16::from_le_bytes([block[2], block[3]]));
+            blocks.push(block);
+        }
+        rgb565_to_rgba8(&endpoints, &mut endpoints_rgba);
+
+        for (block_x, block) in blocks.iter().enumerate() {
+            let palette = palette(
+                endpoints[2 * block_x],
+                endpoints[2 * block_x + 1],
+                endpoints_rgba[2 * block_x],
+                endpoints_rgba[2 * block_x + 1],
+            );
+            let indices = u32::from_le_bytes(block[4..].try_into().unwrap());
+
+            let x0 = 4 * block_x;
+            let columns = (width - x0).min(4);
+            for fine_y in 0..(height - 4 * block_y).min(4) {
+                let row_offset = 4 * (width * (4 * block_y + fine_y) + x0);
+                let row = &mut dst[row_offset..row_offset + 4 * columns];
+                for (fine_x, texel) in row.chunks_exact_mut(4).enumerate() {
+                    let index = (indices >> (2 * (4 * fine_y + fine_x))) & 3;
+                    texel.copy_from_slice(&palette[index as usize]);
+                }
+            }
+        }
+    }
+    dst
+}
+
+fn palette(color_a: u16, color_b: u16, a: [u8; 4], b: [u8; 4]) -> [[u8; 4]; 4] {
+    let mix = |a_mul, b_mul, div| {
+        [
+            blend(a[0], b[0], a_mul, b_mul, div),
+            blend(a[1], b[1], a_mul, b_mul, div),
+            blend(a[2], b[2], a_mul, b_mul, div),
+            255,
+        ]
+    };
+    if color_a > color_b {
+        [a, b, mix(2, 1, 3), mix(1, 2, 3)]
+    } else {
+        [a, b, mix(1, 1, 2), [0; 4]]
+    }
+}
+
+/// Expands RGB565 colors to opaque RGBA8, replicating high bits into the new low bits.
+pub(crate) fn rgb565_to_rgba8(src: &[u16], dst: &mut [[u8; 4]]) {
+    assert_eq!(src.len(), dst.len());
+
+    // Do as much as possible eight at a time and leave the rest for the scalar loop.
+    #[cfg(all(feature = "std", any(target_arch = "x86_64", target_arch = "aarch64")))]
+    let (src, dst) = {
+        let simd_len = src.len() / 8 * 8;
+        for (src, dst) in src.chunks_exact(8).zip(dst.chunks_exact_mut(8)) {
+            simd::rgb565_to_rgba8_x8(src.try_into().unwrap(), dst.try_into().unwrap());
+        }
+        (&src[simd_len..], &mut dst[simd_len..])
+    };
+
+    for (&color, dst) in src.iter().zip(dst) {
+        *dst = rgb565_to_rgba8_scalar(color);
+    }
+}
+
+fn rgb565_to_rgba8_scalar(rgb565: u16) -> [u8; 4] {
+    let extend5 = |x| (x << 3) | (x >> 2);
+    let extend6 = |x| (x << 2) | (x >> 4);
+    [
+        extend5(((rgb565 >> 11) & 0x1f) as u8),
+        extend6(((rgb565 >> 5) & 0x3f) as u8),
+        extend5((rgb565 & 0x1f) as u8),
+        255,
+    ]
+}
+
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+mod simd {
+    use core::arch::x86_64::*;
+
+    pub fn rgb565_to_rgba8_x8(src: &[u16; 8], dst: &mut [[u8; 4]; 8]) {
+        // SAFETY: SSE2 is part of the x86_64 baseline, and both pointers cover 16 and 32 bytes.
+        unsafe {
+            let v = _mm_loadu_si128(src.as_ptr() as *const __m128i);
+            let mask5 = _mm_set1_epi16(0x1f);
+            let mask6 = _mm_set1_epi16(0x3f);
+
+            let r = _mm_and_si128(_mm_srli_epi16(v, 11), mask5);
+            let g = _mm_and_si128(_mm_srli_epi16(v, 5), mask6);
+            let b = _mm_and_si128(v, mask5);
+            let r = _mm_or_si128(_mm_slli_epi16(r, 3), _mm_srli_epi16(r, 2));
+            let g = _mm_or_si128(_mm_slli_epi16(g, 2), _mm_srli_epi16(g, 4));
+            let b = _mm_or_si128(_mm_slli_epi16(b, 3), _mm_srli_epi16(b, 2));
+
+            // Pair up channels within 16-bit lanes, then interleave the pairs into 32-bit texels.
+            let rg = _mm_or_si128(r, _mm_slli_epi16(g, 8));
+            let ba = _mm_or_si128(b, _mm_set1_epi16(0xff00u16 as i16));
+            let dst = dst.as_mut_ptr() as *mut __m128i;
+            _mm_storeu_si128(dst, _mm_unpacklo_epi16(rg, ba));
+            _mm_storeu_si128(dst.add(1), _mm_unpackhi_epi16(rg, ba));
+        }
+    }
+}
+
+#[cfg(all(feature = "std", target_arch = "aarch64"))]
+mod simd {
+    use core::arch::aarch64::*;
+
+    pub fn rgb565_to_rgba8_x8(src: &[u16; 8], dst: &mut [[u8; 4]; 8]) {
+        // SAFETY: NEON is part of the aarch64 baseline, and both pointers cover 16 and 32 bytes.
+        unsafe {
+            let v = vld1q_u16(src.as_ptr());
+            let mask5 = vdupq_n_u16(0x1f);
+            let mask6 = vdupq_n_u16(0x3f);
+
+            let r = vandq_u16(vshrq_n_u16(v, 11), mask5);
+            let g = vandq_u16(vshrq_n_u16(v, 5), mask6);
+            let b = vandq_u16(v, mask5);
+            let r = vorrq_u16(vshlq_n_u16(r, 3), vshrq_n_u16(r, 2));
+            let g = vorrq_u16(vshlq_n_u16(g, 2), vshrq_n_u16(g, 4));
+            let b = vorrq_u16(vshlq_n_u16(b, 3), vshrq_n_u16(b, 2));
+
+            vst4_u8(
+                dst.as_mut_ptr() as *mut u8,
+                uint8x8x4_t(vmovn_u16(r), vmovn_u16(g), vmovn_u16(b), vdup_n_u8(255)),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::{rgb565_to_rgba8, rgb565_to_rgba8_scalar};
+    use crate::{TextureBuf, TextureFormat};
+
+    /// Deterministic filler bytes.
+    fn noise(len: usize) -> Vec<u8> {
+        let mut state = 0x12345678u32;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+                (state >> 24) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rgb565_matches_scalar() {
+        let src: Vec<u16> = (0..=u16::MAX).collect();
+        let mut dst = alloc::vec![[0; 4]; src.len()];
+        rgb565_to_rgba8(&src, &mut dst);
+        for (&color, &rgba) in src.iter().zip(&dst) {
+            assert_eq!(rgba, rgb565_to_rgba8_scalar(color), "{color:#06x}");
+        }
+    }
+
+    fn assert_matches_texel_decode(format: TextureFormat, width: usize, height: usize) {
+        let metrics = format.metrics();
+        let src = TextureBuf::new(
+            format,
+            width,
+            height,
+            noise(metrics.encoded_size(width, height)),
+        );
+        let decoded = TextureBuf::transcode(src.as_slice(), TextureFormat::Rgba8);
+        for y in 0..height {
+            for x in 0..width {
+                assert_eq!(
+                    decoded.get_texel(x, y),
+                    src.get_texel(x, y),
+                    "{format:?} at ({x}, {y})",
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn dxt1_matches_texel_decode() {
+        assert_matches_texel_decode(TextureFormat::Dxt1, 64, 32);
+        assert_matches_texel_decode(TextureFormat::Dxt1, 13, 7);
+    }
+
+    #[test]
+    fn gx_tf_cmpr_matches_texel_decode() {
+        assert_matches_texel_decode(TextureFormat::GxTfCmpr, 64, 32);
+        assert_matches_texel_decode(TextureFormat::GxTfCmpr, 21, 11);
+    }
+}
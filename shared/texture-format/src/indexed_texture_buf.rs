@@ -0,0 +1,278 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::texture_format::BlockMetrics;
+use crate::{TextureBuf, TextureFormat, TextureSlice, Tlut, TlutFormat};
+
+/// A GX color-indexed texture format. Texels are indices into a [`Tlut`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IndexFormat {
+    C4,
+    C8,
+}
+
+impl IndexFormat {
+    pub fn metrics(self) -> BlockMetrics {
+        match self {
+            Self::C4 => BlockMetrics {
+                block_width: 8,
+                block_height: 8,
+                encoded_block_size: 32,
+            },
+            Self::C8 => BlockMetrics {
+                block_width: 8,
+                block_height: 4,
+                encoded_block_size: 32,
+            },
+        }
+    }
+
+    /// The most palette entries a texture of this format can index.
+    pub fn max_colors(self) -> usize {
+        match self {
+            Self::C4 => 16,
+            Self::C8 => 256,
+        }
+    }
+}
+
+/// A color-indexed texture. It is decoded with a separately held [`Tlut`].
+#[derive(Clone)]
+pub struct IndexedTextureBuf {
+    format: IndexFormat,
+    width: usize,
+    height: usize,
+    physical_width: usize,
+    physical_height: usize,
+    data: Vec<u8>,
+}
+
+impl IndexedTextureBuf {
+    pub fn new(format: IndexFormat, width: usize, height: usize, data: Vec<u8>) -> Self {
+        let metrics = format.metrics();
+        let physical_width = metrics.physical_width(width);
+        let physical_height = metrics.physical_height(height);
+
+        let expected_size = metrics.encoded_size(width, height);
+        if data.len() != expected_size {
+            panic!(
+                "Data size mismatch: format={:?} logical={}x{} physical={}x{} expected={} actual={}",
+                format,
+                width,
+                height,
+                physical_width,
+                physical_height,
+                expected_size,
+                data.len(),
+            );
+        }
+
+        Self {
+            format,
+            width,
+            height,
+            physical_width,
+            physical_height,
+            data,
+        }
+    }
+
+    /// Quantizes `src` to a new palette by median cut and indexes it.
+    pub fn encode(
+        src: TextureSlice,
+        format: IndexFormat,
+        tlut_format: TlutFormat,
+        dither: bool,
+    ) -> (Self, Tlut) {
+        let tlut = Tlut::quantize(src, tlut_format, format.max_colors());
+        (Self::encode_with_tlut(src, format, &tlut, dither), tlut)
+    }
+
+    /// Indexes `src` against an existing palette, picking the nearest entry for each texel.
+    ///
+    /// With `dither` set, each texel's error is diffused to its unvisited neighbors
+    /// (Floyd-Steinberg).
+    pub fn encode_with_tlut(
+        src: TextureSlice,
+        format: IndexFormat,
+        tlut: &Tlut,
+        dither: bool,
+    ) -> Self {
+        assert!(tlut.len() <= format.max_colors());
+        let width = src.width();
+        let height = src.height();
+
+        let mut result = Self::new(
+            format,
+            width,
+            height,
+            vec![0; format.metrics().encoded_size(width, height)],
+        );
+        // Accumulated error for this row and the next, with a texel of padding on each side.
+        let mut error = [vec![[0; 4]; width + 2], vec![[0; 4]; width + 2]];
+        for y in 0..height {
+            for x in 0..width {
+                let texel = src.get_texel(x, y);
+                let target: [i32; 4] =
+                    [0, 1, 2, 3].map(|c| texel[c] as i32 + error[0][x + 1][c] / 16);
+                let index = tlut.nearest(target.map(|x| x.clamp(0, 255)));
+                result.set_index(x, y, index);
+
+                if dither {
+                    let actual = tlut.get_color(index);
+                    for c in 0..4 {
+                        let e = target[c].clamp(0, 255) - actual[c] as i32;
+                        error[0][x + 2][c] += 7 * e;
+                        error[1][x][c] += 3 * e;
+                        error[1][x + 1][c] += 5 * e;
+                        error[1][x + 2][c] += e;
+                    }
+                }
+            }
+            error.swap(0, 1);
+            error[1].fill([0; 4]);
+        }
+        result
+    }
+
+    pub fn format(&self) -> IndexFormat {
+        self.format
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn physical_width(&self) -> usize {
+        self.physical_width
+    }
+
+    pub fn physical_height(&self) -> usize {
+        self.physical_height
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    pub fn get_index(&self, x: usize, y: usize) -> u8 {
+        let (offset, shift) = self.texel_location(x, y);
+        match self.format {
+            IndexFormat::C4 => (self.data[offset] >> shift) & 0xf,
+            IndexFormat::C8 => self.data[offset],
+        }
+    }
+
+    fn set_index(&mut self, x: usize, y: usize, index: u8) {
+        let (offset, shift) = self.texel_location(x, y);
+        match self.format {
+            IndexFormat::C4 => {
+                self.data[offset] = (self.data[offset] & !(0xf << shift)) | (index << shift)
+            }
+            IndexFormat::C8 => self.data[offset] = index,
+        }
+    }
+
+    /// Returns the byte offset of a texel and, for C4, the shift of its nibble.
+    fn texel_location(&self, x: usize, y: usize) -> (usize, u32) {
+        let metrics = self.format.metrics();
+        let blocks_wide = self.physical_width / metrics.block_width;
+        let block = blocks_wide * (y / metrics.block_height) + x / metrics.block_width;
+        let fine = metrics.block_width * (y % metrics.block_height) + x % metrics.block_width;
+        match self.format {
+            // Two texels per byte, the left one in the high nibble.
+            IndexFormat::C4 => (32 * block + fine / 2, if fine % 2 == 0 { 4 } else { 0 }),
+            IndexFormat::C8 => (32 * block + fine, 0),
+        }
+    }
+
+    /// Fetches the texel at the given location and looks it up in `tlut`.
+    pub fn get_texel(&self, tlut: &Tlut, x: usize, y: usize) -> [u8; 4] {
+        tlut.get_color(self.get_index(x, y))
+    }
+
+    /// Decodes the whole texture to RGBA8 through `tlut`.
+    pub fn decode(&self, tlut: &Tlut) -> TextureBuf {
+        let mut data = Vec::with_capacity(4 * self.width * self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                data.extend_from_slice(&self.get_texel(tlut, x, y));
+            }
+        }
+        TextureBuf::new(TextureFormat::Rgba8, self.width, self.height, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::{IndexFormat, IndexedTextureBuf};
+    use crate::{TextureBuf, TextureFormat, Tlut, TlutFormat};
+
+    #[test]
+    fn c4_layout() {
+        let texture = IndexedTextureBuf::new(IndexFormat::C4, 16, 8, (0..64).collect());
+        assert_eq!(texture.get_index(0, 0), 0x0);
+        assert_eq!(texture.get_index(1, 0), 0x0);
+        assert_eq!(texture.get_index(2, 0), 0x0);
+        assert_eq!(texture.get_index(3, 0), 0x1);
+        assert_eq!(texture.get_index(0, 1), 0x0);
+        assert_eq!(texture.get_index(1, 1), 0x4);
+        assert_eq!(texture.get_index(9, 0), 0x0);
+        assert_eq!(texture.get_index(11, 0), 0x1);
+        assert_eq!(texture.get_index(15, 7), 0xf);
+    }
+
+    #[test]
+    fn c8_round_trip() {
+        let colors = [
+            [0x00, 0x00, 0x00, 0xff],
+            [0xff, 0x00, 0x00, 0xff],
+            [0x00, 0x00, 0xff, 0x6d],
+        ];
+        let src: Vec<u8> = (0..10 * 5).flat_map(|i| colors[i % colors.len()]).collect();
+        let src = TextureBuf::new(TextureFormat::Rgba8, 10, 5, src);
+
+        let (texture, tlut) =
+            IndexedTextureBuf::encode(src.as_slice(), IndexFormat::C8, TlutFormat::Rgb5a3, false);
+        assert_eq!(tlut.len(), 3);
+        assert_eq!(texture.data().len(), 2 * 2 * 32);
+        let decoded = texture.decode(&tlut);
+        for y in 0..5 {
+            for x in 0..10 {
+                assert_eq!(decoded.get_texel(x, y), src.get_texel(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn dither_mixes_entries() {
+        let src = TextureBuf::new(TextureFormat::Rgba8, 8, 8, [0x80; 4 * 64].to_vec());
+        let tlut = Tlut::from_rgba(
+            TlutFormat::Ia8,
+            &[[0, 0, 0, 0x80], [0xff, 0xff, 0xff, 0x80]],
+        );
+
+        let flat =
+            IndexedTextureBuf::encode_with_tlut(src.as_slice(), IndexFormat::C4, &tlut, false);
+        let dithered =
+            IndexedTextureBuf::encode_with_tlut(src.as_slice(), IndexFormat::C4, &tlut, true);
+        let count_ones = |texture: &IndexedTextureBuf| {
+            (0..8)
+                .flat_map(|y| (0..8).map(move |x| (x, y)))
+                .filter(|&(x, y)| texture.get_index(x, y) == 1)
+                .count()
+        };
+        assert!(count_ones(&flat) == 0 || count_ones(&flat) == 64);
+        assert!((24..=40).contains(&count_ones(&dithered)));
+    }
+}
@@ -1,12 +1,19 @@
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 extern crate alloc;
 
 mod codec;
+mod fast_decode;
+mod indexed_texture_buf;
+mod streaming_encoder;
 mod texture_buf;
 mod texture_format;
 mod texture_slice;
+mod tlut;
 
+pub use crate::indexed_texture_buf::{IndexFormat, IndexedTextureBuf};
+pub use crate::streaming_encoder::StreamingEncoder;
 pub use crate::texture_buf::TextureBuf;
 pub use crate::texture_format::{BlockMetrics, TextureFormat};
 pub use crate::texture_slice::TextureSlice;
+pub use crate::tlut::{Tlut, TlutFormat};
@@ -0,0 +1,154 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::codec::DynCodec;
+use crate::texture_format::BlockMetrics;
+use crate::{TextureFormat, TextureSlice};
+
+/// Encodes a texture from RGBA scanlines, handing each row of blocks to a callback as soon as its
+/// scanlines are in.
+///
+/// Only one row of blocks is buffered, so neither the whole source image nor the whole encoded
+/// texture needs to be in memory at once. The output is identical to [`crate::TextureBuf`]'s
+/// general transcoding path, including the transparent black padding past the logical edges.
+pub struct StreamingEncoder<F: FnMut(&[u8])> {
+    codec: &'static dyn DynCodec,
+    metrics: BlockMetrics,
+    width: usize,
+    height: usize,
+    /// RGBA texels of the block row being gathered, one physical scanline after another.
+    scanlines: Vec<u8>,
+    scanlines_pushed: usize,
+    /// Scratch space for the block being encoded.
+    block_texels: Vec<u8>,
+    encoded_row: Vec<u8>,
+    emit: F,
+}
+
+impl<F: FnMut(&[u8])> StreamingEncoder<F> {
+    pub fn new(format: TextureFormat, width: usize, height: usize, emit: F) -> Self {
+        let metrics = format.metrics();
+        let physical_width = metrics.physical_width(width);
+        Self {
+            codec: format.dyn_codec(),
+            metrics,
+            width,
+            height,
+            scanlines: vec![0; 4 * physical_width * metrics.block_height],
+            scanlines_pushed: 0,
+            block_texels: Vec::with_capacity(4 * metrics.block_width * metrics.block_height),
+            encoded_row: Vec::with_capacity(
+                metrics.encoded_block_size * metrics.blocks_wide(width),
+            ),
+            emit,
+        }
+    }
+
+    /// Encodes all of `src`, reading it one scanline at a time.
+    pub fn encode_slice(format: TextureFormat, src: TextureSlice, emit: F) {
+        let mut encoder = Self::new(format, src.width(), src.height(), emit);
+        let mut scanline = Vec::with_capacity(4 * src.width());
+        for y in 0..src.height() {
+            scanline.clear();
+            for x in 0..src.width() {
+                scanline.extend_from_slice(&src.get_texel(x, y));
+            }
+            encoder.push_scanline(&scanline);
+        }
+        encoder.finish();
+    }
+
+    /// Adds the next scanline of `4 * width` RGBA bytes, emitting a block row if this completes
+    /// one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scanline is the wrong length or if all `height` scanlines have already been
+    /// pushed.
+    pub fn push_scanline(&mut self, rgba: &[u8]) {
+        assert_eq!(rgba.len(), 4 * self.width);
+        assert!(self.scanlines_pushed < self.height);
+
+        let fine_y = self.scanlines_pushed % self.metrics.block_height;
+        let stride = self.scanlines.len() / self.metrics.block_height;
+        self.scanlines[stride * fine_y..][..rgba.len()].copy_from_slice(rgba);
+        self.scanlines_pushed += 1;
+
+        if fine_y + 1 == self.metrics.block_height || self.scanlines_pushed == self.height {
+            self.emit_block_row(fine_y + 1);
+        }
+    }
+
+    /// Checks that every scanline was pushed. The last block row has already been emitted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer than `height` scanlines were pushed.
+    pub fn finish(self) {
+        assert_eq!(self.scanlines_pushed, self.height);
+    }
+
+    fn emit_block_row(&mut self, scanline_count: usize) {
+        // Pad a partial final block row with transparent black.
+        let stride = self.scanlines.len() / self.metrics.block_height;
+        self.scanlines[stride * scanline_count..].fill(0);
+
+        let block_stride = 4 * self.metrics.block_width;
+        self.encoded_row.clear();
+        for coarse_x in 0..self.metrics.blocks_wide(self.width) {
+            self.block_texels.clear();
+            for fine_y in 0..self.metrics.block_height {
+                let offset = stride * fine_y + block_stride * coarse_x;
+                self.block_texels
+                    .extend_from_slice(&self.scanlines[offset..offset + block_stride]);
+            }
+            self.codec
+                .encode_block(&self.block_texels, &mut self.encoded_row);
+        }
+        (self.emit)(&self.encoded_row);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{StreamingEncoder, TextureBuf, TextureFormat};
+
+    fn rgba8_gradient(width: usize, height: usize) -> TextureBuf {
+        let mut data = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                data.extend_from_slice(&[16 * x as u8, 16 * y as u8, (x * y) as u8, 255]);
+            }
+        }
+        TextureBuf::new(TextureFormat::Rgba8, width, height, data)
+    }
+
+    #[test]
+    fn matches_transcode() {
+        // Neither dimension is a multiple of the block size, so padding is exercised too.
+        let src = rgba8_gradient(13, 11);
+        for format in [
+            TextureFormat::GxTfCmpr,
+            TextureFormat::GxTfRgba8,
+            TextureFormat::GxTfI8,
+        ] {
+            let mut rows = Vec::new();
+            StreamingEncoder::encode_slice(format, src.as_slice(), |row| rows.push(row.to_vec()));
+            assert_eq!(rows.len(), format.metrics().blocks_high(11));
+            assert_eq!(
+                rows.concat(),
+                TextureBuf::transcode(src.as_slice(), format).data(),
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn finish_checks_height() {
+        let mut encoder = StreamingEncoder::new(TextureFormat::GxTfCmpr, 8, 8, |_| ());
+        encoder.push_scanline(&[0; 32]);
+        encoder.finish();
+    }
+}
@@ -13,6 +13,7 @@ use crate::codec::rgb8::Rgb8;
 use crate::codec::rgba16f::Rgba16f;
 use crate::codec::rgba8::Rgba8;
 use crate::codec::Codec;
+use crate::fast_decode::{dxt1_to_rgba8, gx_tf_cmpr_to_rgba8};
 use crate::{TextureFormat, TextureSlice};
 
 #[derive(Clone)]
@@ -137,6 +138,20 @@ impl TextureBuf {
                 encode_dxt5_to_gx_tf_cmpr(src.width(), src.height(), src_data)
             }
 
+            // Fast paths for decoding whole DXT1-style textures.
+            (TextureFormat::Dxt1, TextureFormat::Rgba8, Some(src_data)) => Self::new(
+                format,
+                src.width(),
+                src.height(),
+                dxt1_to_rgba8(src.width(), src.height(), src_data),
+            ),
+            (TextureFormat::GxTfCmpr, TextureFormat::Rgba8, Some(src_data)) => Self::new(
+                format,
+                src.width(),
+                src.height(),
+                gx_tf_cmpr_to_rgba8(src.width(), src.height(), src_data),
+            ),
+
             // General case.
             (_, _, _) => Self::transcode_dispatch_src(src, format),
         }
@@ -1,6 +1,21 @@
 use core::ops::Range;
 
-use crate::TextureFormat;
+use alloc::vec::Vec;
+
+use crate::codec::bgr8::Bgr8;
+use crate::codec::bgra8::Bgra8;
+use crate::codec::bgrx8::Bgrx8;
+use crate::codec::dxt1::Dxt1;
+use crate::codec::dxt5::Dxt5;
+use crate::codec::gx_tf_cmpr::GxTfCmpr;
+use crate::codec::gx_tf_i8::GxTfI8;
+use crate::codec::gx_tf_ia8::GxTfIa8;
+use crate::codec::gx_tf_rgba8::GxTfRgba8;
+use crate::codec::rgb8::Rgb8;
+use crate::codec::rgba16f::Rgba16f;
+use crate::codec::rgba8::Rgba8;
+use crate::codec::Codec;
+use crate::{TextureBuf, TextureFormat};
 
 #[derive(Clone, Copy)]
 pub struct TextureSlice<'a> {
@@ -48,6 +63,19 @@ impl<'a> TextureSlice<'a> {
         None
     }
 
+    /// Narrows this slice to the given ranges, which are relative to this slice.
+    pub fn subslice(self, x_range: Range<usize>, y_range: Range<usize>) -> Self {
+        assert!(x_range.start <= x_range.end && x_range.end <= self.width());
+        assert!(y_range.start <= y_range.end && y_range.end <= self.height());
+        Self {
+            x0: self.x0 + x_range.start,
+            y0: self.y0 + y_range.start,
+            x1: self.x0 + x_range.end,
+            y1: self.y0 + y_range.end,
+            ..self
+        }
+    }
+
     pub fn get_texel(self, x: usize, y: usize) -> [u8; 4] {
         assert!(x < self.width() && y < self.height());
         self.format.dyn_codec().get_texel(
@@ -58,4 +86,195 @@ impl<'a> TextureSlice<'a> {
             y + self.y0,
         )
     }
+
+    /// Writes this slice into `dst` with its upper-left corner at (`dst_x`, `dst_y`), converting
+    /// to `dst`'s format one block at a time.
+    ///
+    /// Destination blocks that this slice only partly covers are decoded, merged, and re-encoded,
+    /// so their other texels survive, though lossy formats may shift them slightly. When the
+    /// formats match and the source is block aligned, fully covered blocks are copied verbatim.
+    ///
+    /// # Panics
+    ///
+    /// Panics if (`dst_x`, `dst_y`) is not on a block boundary of `dst`'s format or if the slice
+    /// does not fit within `dst`.
+    pub fn blit_into(self, dst: &mut TextureBuf, dst_x: usize, dst_y: usize) {
+        let metrics = dst.format.metrics();
+        assert_eq!(dst_x % metrics.block_width, 0);
+        assert_eq!(dst_y % metrics.block_height, 0);
+        assert!(dst_x + self.width() <= dst.width && dst_y + self.height() <= dst.height);
+
+        match self.format {
+            TextureFormat::Bgr8 => blit_dispatch_dst::<Bgr8>(self, dst, dst_x, dst_y),
+            TextureFormat::Bgra8 => blit_dispatch_dst::<Bgra8>(self, dst, dst_x, dst_y),
+            TextureFormat::Bgrx8 => blit_dispatch_dst::<Bgrx8>(self, dst, dst_x, dst_y),
+            TextureFormat::Dxt1 => blit_dispatch_dst::<Dxt1>(self, dst, dst_x, dst_y),
+            TextureFormat::Dxt5 => blit_dispatch_dst::<Dxt5>(self, dst, dst_x, dst_y),
+            TextureFormat::GxTfCmpr => blit_dispatch_dst::<GxTfCmpr>(self, dst, dst_x, dst_y),
+            TextureFormat::GxTfI8 => blit_dispatch_dst::<GxTfI8>(self, dst, dst_x, dst_y),
+            TextureFormat::GxTfIa8 => blit_dispatch_dst::<GxTfIa8>(self, dst, dst_x, dst_y),
+            TextureFormat::GxTfRgba8 => blit_dispatch_dst::<GxTfRgba8>(self, dst, dst_x, dst_y),
+            TextureFormat::Rgb8 => blit_dispatch_dst::<Rgb8>(self, dst, dst_x, dst_y),
+            TextureFormat::Rgba16f => blit_dispatch_dst::<Rgba16f>(self, dst, dst_x, dst_y),
+            TextureFormat::Rgba8 => blit_dispatch_dst::<Rgba8>(self, dst, dst_x, dst_y),
+        }
+    }
+}
+
+fn blit_dispatch_dst<C: Codec>(
+    src: TextureSlice,
+    dst: &mut TextureBuf,
+    dst_x: usize,
+    dst_y: usize,
+) {
+    assert_eq!(src.format, C::FORMAT);
+    match dst.format {
+        TextureFormat::Bgr8 => blit_static::<C, Bgr8>(src, dst, dst_x, dst_y),
+        TextureFormat::Bgra8 => blit_static::<C, Bgra8>(src, dst, dst_x, dst_y),
+        TextureFormat::Bgrx8 => blit_static::<C, Bgrx8>(src, dst, dst_x, dst_y),
+        TextureFormat::Dxt1 => blit_static::<C, Dxt1>(src, dst, dst_x, dst_y),
+        TextureFormat::Dxt5 => blit_static::<C, Dxt5>(src, dst, dst_x, dst_y),
+        TextureFormat::GxTfCmpr => blit_static::<C, GxTfCmpr>(src, dst, dst_x, dst_y),
+        TextureFormat::GxTfI8 => blit_static::<C, GxTfI8>(src, dst, dst_x, dst_y),
+        TextureFormat::GxTfIa8 => blit_static::<C, GxTfIa8>(src, dst, dst_x, dst_y),
+        TextureFormat::GxTfRgba8 => blit_static::<C, GxTfRgba8>(src, dst, dst_x, dst_y),
+        TextureFormat::Rgb8 => blit_static::<C, Rgb8>(src, dst, dst_x, dst_y),
+        TextureFormat::Rgba16f => blit_static::<C, Rgba16f>(src, dst, dst_x, dst_y),
+        TextureFormat::Rgba8 => blit_static::<C, Rgba8>(src, dst, dst_x, dst_y),
+    }
+}
+
+fn blit_static<C: Codec, D: Codec>(
+    src: TextureSlice,
+    dst: &mut TextureBuf,
+    dst_x: usize,
+    dst_y: usize,
+) {
+    assert_eq!(dst.format, D::FORMAT);
+    let metrics = D::METRICS;
+    let width = src.width();
+    let height = src.height();
+    let copy_blocks = C::FORMAT == D::FORMAT
+        && src.x0 % metrics.block_width == 0
+        && src.y0 % metrics.block_height == 0;
+    let src_blocks_wide = metrics.blocks_wide(src.physical_width);
+    let dst_blocks_wide = metrics.blocks_wide(dst.physical_width);
+
+    let mut texels = Vec::with_capacity(4 * metrics.block_width * metrics.block_height);
+    for coarse_y in 0..metrics.blocks_high(height) {
+        for coarse_x in 0..metrics.blocks_wide(width) {
+            let x0 = metrics.block_width * coarse_x;
+            let y0 = metrics.block_height * coarse_y;
+            let dst_block_index = dst_blocks_wide * ((dst_y + y0) / metrics.block_height)
+                + (dst_x + x0) / metrics.block_width;
+            let dst_range = metrics.encoded_block_size * dst_block_index
+                ..metrics.encoded_block_size * (dst_block_index + 1);
+
+            if copy_blocks
+                && x0 + metrics.block_width <= width
+                && y0 + metrics.block_height <= height
+            {
+                let src_block_index = src_blocks_wide * ((src.y0 + y0) / metrics.block_height)
+                    + (src.x0 + x0) / metrics.block_width;
+                dst.data[dst_range].copy_from_slice(
+                    &src.data[metrics.encoded_block_size * src_block_index..]
+                        [..metrics.encoded_block_size],
+                );
+                continue;
+            }
+
+            // Gather RGBA texels, falling back to the destination's own texels where the source
+            // doesn't reach.
+            texels.clear();
+            for fine_y in 0..metrics.block_height {
+                for fine_x in 0..metrics.block_width {
+                    let x = x0 + fine_x;
+                    let y = y0 + fine_y;
+                    let rgba = if x < width && y < height {
+                        C::get_texel(
+                            src.physical_width,
+                            src.physical_height,
+                            src.data,
+                            x + src.x0,
+                            y + src.y0,
+                        )
+                    } else {
+                        D::get_texel(
+                            dst.physical_width,
+                            dst.physical_height,
+                            &dst.data,
+                            x + dst_x,
+                            y + dst_y,
+                        )
+                    };
+                    texels.extend_from_slice(&rgba);
+                }
+            }
+
+            // Encode and store the block.
+            dst.data[dst_range].copy_from_slice(D::encode_block(&texels).as_ref());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::{TextureBuf, TextureFormat};
+
+    fn rgba8_gradient(width: usize, height: usize) -> TextureBuf {
+        let mut data = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                data.extend_from_slice(&[x as u8, y as u8, 0, 255]);
+            }
+        }
+        TextureBuf::new(TextureFormat::Rgba8, width, height, data)
+    }
+
+    #[test]
+    fn blit_into_converts_and_preserves_partial_blocks() {
+        let src = rgba8_gradient(6, 6);
+        let mut dst = TextureBuf::new(TextureFormat::GxTfRgba8, 8, 8, vec![0x55; 4 * 64]);
+
+        src.as_slice()
+            .subslice(1..4, 2..5)
+            .blit_into(&mut dst, 4, 0);
+
+        for y in 0..8 {
+            for x in 0..8 {
+                let expected = if (4..7).contains(&x) && y < 3 {
+                    [x as u8 - 3, y as u8 + 2, 0, 255]
+                } else {
+                    [0x55; 4]
+                };
+                assert_eq!(dst.get_texel(x, y), expected, "texel ({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn blit_into_copies_aligned_blocks() {
+        let src = TextureBuf::transcode(rgba8_gradient(8, 8).as_slice(), TextureFormat::GxTfRgba8);
+        let mut dst = TextureBuf::new(TextureFormat::GxTfRgba8, 8, 8, vec![0; 4 * 64]);
+
+        src.as_slice()
+            .subslice(4..8, 4..8)
+            .blit_into(&mut dst, 0, 4);
+
+        assert_eq!(dst.get_texel(0, 4), [4, 4, 0, 255]);
+        assert_eq!(dst.get_texel(3, 7), [7, 7, 0, 255]);
+        assert_eq!(dst.get_texel(4, 4), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn blit_into_rejects_unaligned_destination() {
+        let src = rgba8_gradient(4, 4);
+        let mut dst = TextureBuf::new(TextureFormat::GxTfRgba8, 8, 8, vec![0; 4 * 64]);
+
+        src.as_slice().blit_into(&mut dst, 2, 0);
+    }
 }
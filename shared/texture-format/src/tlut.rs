@@ -0,0 +1,311 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::TextureSlice;
+
+/// The entry format of a GX texture lookup table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TlutFormat {
+    Ia8,
+    Rgb565,
+    Rgb5a3,
+}
+
+impl TlutFormat {
+    /// Converts an RGBA color to a table entry, rounding to the nearest representable color.
+    pub fn encode(self, [r, g, b, a]: [u8; 4]) -> u16 {
+        match self {
+            Self::Ia8 => {
+                let i = ((r as u16 + g as u16 + b as u16) / 3) as u8;
+                u16::from_be_bytes([a, i])
+            }
+            Self::Rgb565 => {
+                ((quantize(r, 5) as u16) << 11)
+                    | ((quantize(g, 6) as u16) << 5)
+                    | quantize(b, 5) as u16
+            }
+            Self::Rgb5a3 => {
+                if quantize(a, 3) == 7 {
+                    0x8000
+                        | ((quantize(r, 5) as u16) << 10)
+                        | ((quantize(g, 5) as u16) << 5)
+                        | quantize(b, 5) as u16
+                } else {
+                    ((quantize(a, 3) as u16) << 12)
+                        | ((quantize(r, 4) as u16) << 8)
+                        | ((quantize(g, 4) as u16) << 4)
+                        | quantize(b, 4) as u16
+                }
+            }
+        }
+    }
+
+    /// Converts a table entry to an RGBA color.
+    pub fn decode(self, entry: u16) -> [u8; 4] {
+        match self {
+            Self::Ia8 => {
+                let [a, i] = entry.to_be_bytes();
+                [i, i, i, a]
+            }
+            Self::Rgb565 => [
+                expand(entry >> 11, 5),
+                expand(entry >> 5, 6),
+                expand(entry, 5),
+                0xff,
+            ],
+            Self::Rgb5a3 => {
+                if entry & 0x8000 != 0 {
+                    [
+                        expand(entry >> 10, 5),
+                        expand(entry >> 5, 5),
+                        expand(entry, 5),
+                        0xff,
+                    ]
+                } else {
+                    [
+                        expand(entry >> 8, 4),
+                        expand(entry >> 4, 4),
+                        expand(entry, 4),
+                        expand(entry >> 12, 3),
+                    ]
+                }
+            }
+        }
+    }
+}
+
+/// Rounds an 8-bit channel to the nearest `bits`-bit value.
+fn quantize(x: u8, bits: u32) -> u8 {
+    let max = (1 << bits) - 1;
+    ((x as u32 * max + 127) / 255) as u8
+}
+
+/// Widens the low `bits` bits of `x` to 8 bits by repeating them.
+fn expand(x: u16, bits: u32) -> u8 {
+    let x = (x & ((1 << bits) - 1)) as u8;
+    match bits {
+        3 => (x << 5) | (x << 2) | (x >> 1),
+        4 => (x << 4) | x,
+        5 => (x << 3) | (x >> 2),
+        6 => (x << 2) | (x >> 4),
+        _ => unreachable!(),
+    }
+}
+
+/// A texture lookup table: the palette for a C4 or C8 texture.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tlut {
+    format: TlutFormat,
+    entries: Vec<u16>,
+}
+
+impl Tlut {
+    pub fn new(format: TlutFormat, entries: Vec<u16>) -> Self {
+        assert!(!entries.is_empty() && entries.len() <= 256);
+        Self { format, entries }
+    }
+
+    pub fn from_rgba(format: TlutFormat, colors: &[[u8; 4]]) -> Self {
+        Self::new(
+            format,
+            colors.iter().map(|&color| format.encode(color)).collect(),
+        )
+    }
+
+    /// Builds a palette of at most `max_colors` entries for the texels of `src` by median cut.
+    pub fn quantize(src: TextureSlice, format: TlutFormat, max_colors: usize) -> Self {
+        assert!((1..=256).contains(&max_colors));
+
+        // Quantize to the table format up front so colors that would collapse to the same entry
+        // are counted together.
+        let mut histogram: Vec<([u8; 4], u32)> = Vec::new();
+        for y in 0..src.height() {
+            for x in 0..src.width() {
+                let color = format.decode(format.encode(src.get_texel(x, y)));
+                histogram.push((color, 1));
+            }
+        }
+        histogram.sort_unstable_by_key(|&(color, _)| color);
+        histogram.dedup_by(|(color, count), (prev_color, prev_count)| {
+            if color == prev_color {
+                *prev_count += *count;
+                true
+            } else {
+                false
+            }
+        });
+        if histogram.is_empty() {
+            return Self::new(format, vec![0]);
+        }
+
+        let mut boxes = vec![ColorBox::new(histogram)];
+        while boxes.len() < max_colors {
+            // Split the box that spans the widest range of any channel.
+            let (index, channel, _) = match boxes
+                .iter()
+                .enumerate()
+                .filter(|(_, b)| b.colors.len() > 1)
+                .map(|(index, b)| {
+                    let (channel, range) = b.widest_channel();
+                    (index, channel, range)
+                })
+                .max_by_key(|&(_, _, range)| range)
+            {
+                Some(split) => split,
+                None => break,
+            };
+            let upper = boxes[index].split(channel);
+            boxes.push(upper);
+        }
+
+        Self::from_rgba(
+            format,
+            &boxes.iter().map(ColorBox::average).collect::<Vec<_>>(),
+        )
+    }
+
+    pub fn format(&self) -> TlutFormat {
+        self.format
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[u16] {
+        &self.entries
+    }
+
+    pub fn get_color(&self, index: u8) -> [u8; 4] {
+        self.format.decode(self.entries[index as usize])
+    }
+
+    /// Returns the index of the entry closest to `color`.
+    pub fn nearest(&self, color: [i32; 4]) -> u8 {
+        let mut best = (0, i32::MAX);
+        for (index, &entry) in self.entries.iter().enumerate() {
+            let entry = self.format.decode(entry);
+            let distance = (0..4)
+                .map(|channel| {
+                    let d = color[channel] - entry[channel] as i32;
+                    d * d
+                })
+                .sum();
+            if distance < best.1 {
+                best = (index as u8, distance);
+            }
+        }
+        best.0
+    }
+
+    /// Serializes the table as GX loads it: big-endian entries, padded to a multiple of 16
+    /// entries so it fills whole 32-byte lines.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![0; 2 * ((self.entries.len() + 15) & !15)];
+        for (dst, &entry) in data.chunks_exact_mut(2).zip(&self.entries) {
+            dst.copy_from_slice(&entry.to_be_bytes());
+        }
+        data
+    }
+}
+
+/// A group of histogram entries in median cut.
+struct ColorBox {
+    colors: Vec<([u8; 4], u32)>,
+}
+
+impl ColorBox {
+    fn new(colors: Vec<([u8; 4], u32)>) -> Self {
+        Self { colors }
+    }
+
+    fn widest_channel(&self) -> (usize, u8) {
+        (0..4)
+            .map(|channel| {
+                let min = self.colors.iter().map(|(c, _)| c[channel]).min().unwrap();
+                let max = self.colors.iter().map(|(c, _)| c[channel]).max().unwrap();
+                (channel, max - min)
+            })
+            .max_by_key(|&(_, range)| range)
+            .unwrap()
+    }
+
+    /// Splits at the weighted median along `channel`, keeping the lower half and returning the
+    /// upper half. Both halves are non-empty.
+    fn split(&mut self, channel: usize) -> Self {
+        self.colors
+            .sort_unstable_by_key(|(color, _)| color[channel]);
+        let total: u32 = self.colors.iter().map(|&(_, count)| count).sum();
+        let mut seen = 0;
+        let mut at = self.colors.len() - 1;
+        for (index, &(_, count)) in self.colors.iter().enumerate() {
+            seen += count;
+            if 2 * seen >= total {
+                at = index + 1;
+                break;
+            }
+        }
+        Self::new(self.colors.split_off(at.clamp(1, self.colors.len() - 1)))
+    }
+
+    fn average(&self) -> [u8; 4] {
+        let total: u32 = self.colors.iter().map(|&(_, count)| count).sum();
+        [0, 1, 2, 3].map(|channel| {
+            let sum: u32 = self
+                .colors
+                .iter()
+                .map(|&(color, count)| color[channel] as u32 * count)
+                .sum();
+            ((sum + total / 2) / total) as u8
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Tlut, TlutFormat};
+    use crate::{TextureBuf, TextureFormat};
+
+    #[test]
+    fn round_trip_entries() {
+        for format in [TlutFormat::Ia8, TlutFormat::Rgb565, TlutFormat::Rgb5a3] {
+            for entry in [0x0000, 0x1234, 0x7fff, 0x8000, 0xabcd, 0xffff] {
+                let entry = format.encode(format.decode(entry));
+                assert_eq!(format.encode(format.decode(entry)), entry, "{format:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn decode_rgb5a3() {
+        assert_eq!(TlutFormat::Rgb5a3.decode(0xfc00), [0xff, 0x00, 0x00, 0xff]);
+        assert_eq!(TlutFormat::Rgb5a3.decode(0x40f0), [0x00, 0xff, 0x00, 0x92]);
+    }
+
+    #[test]
+    fn quantize_keeps_distinct_colors() {
+        let colors = [
+            [0xff, 0x00, 0x00, 0xff],
+            [0x00, 0xff, 0x00, 0xff],
+            [0x00, 0x00, 0xff, 0xff],
+            [0xff, 0xff, 0xff, 0xff],
+        ];
+        let texture = TextureBuf::new(
+            TextureFormat::Rgba8,
+            4,
+            2,
+            colors.iter().chain(&colors).flatten().copied().collect(),
+        );
+
+        let tlut = Tlut::quantize(texture.as_slice(), TlutFormat::Rgb565, 16);
+        assert_eq!(tlut.len(), 4);
+        for color in colors {
+            let index = tlut.nearest(color.map(i32::from));
+            assert_eq!(tlut.get_color(index), color);
+        }
+    }
+}
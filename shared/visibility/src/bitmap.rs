@@ -0,0 +1,194 @@
+use crate::ClusterIndex;
+
+/// One cluster's compressed PVS.
+///
+/// The compressed length isn't stored anywhere, so the data may extend past the end of the
+/// bitmap. Decoding stops at whichever comes first: the last cluster or the end of the data.
+#[derive(Clone, Copy)]
+pub struct VisibilityBitmap<'a> {
+    data: &'a [u8],
+    num_clusters: usize,
+}
+
+impl<'a> VisibilityBitmap<'a> {
+    pub fn new(data: &'a [u8], num_clusters: usize) -> Self {
+        Self { data, num_clusters }
+    }
+
+    pub fn num_clusters(self) -> usize {
+        self.num_clusters
+    }
+
+    pub fn iter_visible_clusters(self) -> VisibleClusters<'a> {
+        VisibleClusters {
+            data: self.data,
+            cluster_index: 0,
+            num_clusters: self.num_clusters,
+            current_byte: 0,
+            current_bit: 0,
+        }
+    }
+
+    pub fn is_visible(self, cluster: ClusterIndex) -> bool {
+        self.iter_visible_clusters()
+            .take_while(|&visible| visible <= cluster)
+            .any(|visible| visible == cluster)
+    }
+
+    /// Returns the prefix of the data that encodes this bitmap.
+    pub fn find_data(self) -> &'a [u8] {
+        let mut offset = 0;
+        let mut cluster_index = 0;
+        while cluster_index < self.num_clusters && offset < self.data.len() {
+            // Read a byte, which is either a skip instruction or another eight bits to scan.
+            let b = self.data[offset];
+            offset += 1;
+            if b == 0 {
+                let run_len = self.data.get(offset).copied().unwrap_or(0) as usize;
+                offset = (offset + 1).min(self.data.len());
+                cluster_index += 8 * run_len;
+            } else {
+                cluster_index += 8;
+            }
+        }
+        &self.data[..offset]
+    }
+}
+
+/// Iterates over the clusters set in a [`VisibilityBitmap`], in increasing order.
+pub struct VisibleClusters<'a> {
+    data: &'a [u8],
+    cluster_index: usize,
+    num_clusters: usize,
+    current_byte: u8,
+    current_bit: u8,
+}
+
+impl<'a> Iterator for VisibleClusters<'a> {
+    type Item = ClusterIndex;
+
+    fn next(&mut self) -> Option<ClusterIndex> {
+        loop {
+            // Scan bits until exhausted, yielding any that are set.
+            while self.current_bit != 0 {
+                if self.cluster_index >= self.num_clusters {
+                    return None;
+                }
+                let visible = (self.current_byte & self.current_bit) != 0;
+                self.current_bit <<= 1;
+                let cluster_index = self.cluster_index;
+                self.cluster_index += 1;
+                if visible {
+                    return Some(ClusterIndex(cluster_index));
+                }
+            }
+
+            // Exit on reaching the end of the bitstream.
+            if self.cluster_index >= self.num_clusters {
+                return None;
+            }
+
+            // Read another byte, which is either a skip instruction or another eight bits to scan.
+            let (&b, rest) = self.data.split_first()?;
+            self.data = rest;
+            match b {
+                0 => {
+                    let (&run_len, rest) = self.data.split_first()?;
+                    self.data = rest;
+                    self.cluster_index += 8 * run_len as usize;
+                }
+                x => {
+                    self.current_byte = x;
+                    self.current_bit = 1;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::{ClusterIndex, VisibilityBitmap};
+
+    /// A direct port of the decompression loop in the Source SDK's `CM_DecompressVis`, used as a
+    /// reference.
+    fn reference_decompress(data: &[u8], num_clusters: usize) -> Vec<bool> {
+        let row_bytes = (num_clusters + 7) / 8;
+        let mut out = Vec::new();
+        let mut data = data.iter().copied();
+        while out.len() < row_bytes {
+            let b = data.next().unwrap();
+            if b != 0 {
+                out.push(b);
+            } else {
+                let count = data.next().unwrap();
+                out.extend(core::iter::repeat(0).take(count as usize));
+            }
+        }
+        (0..num_clusters)
+            .map(|i| out[i / 8] & (1 << (i % 8)) != 0)
+            .collect()
+    }
+
+    const REFERENCE_PVS: &[(&[u8], usize)] = &[
+        (&[0xff], 8),
+        (&[0x01, 0x00, 0x02, 0x80], 32),
+        (&[0x00, 0x03, 0x41, 0x00, 0x01, 0x12], 37),
+        (&[0x2a, 0x00, 0x00, 0x55], 13),
+        (&[0x00, 0x04], 30),
+    ];
+
+    #[test]
+    fn matches_reference_decompression() {
+        for &(data, num_clusters) in REFERENCE_PVS {
+            let expected: Vec<_> = reference_decompress(data, num_clusters)
+                .into_iter()
+                .enumerate()
+                .filter(|&(_, visible)| visible)
+                .map(|(i, _)| ClusterIndex(i))
+                .collect();
+            let bitmap = VisibilityBitmap::new(data, num_clusters);
+            assert_eq!(
+                bitmap.iter_visible_clusters().collect::<Vec<_>>(),
+                expected,
+                "{:02x?}",
+                data,
+            );
+            for cluster in 0..num_clusters {
+                assert_eq!(
+                    bitmap.is_visible(ClusterIndex(cluster)),
+                    expected.contains(&ClusterIndex(cluster)),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn ignores_bits_past_the_last_cluster() {
+        let bitmap = VisibilityBitmap::new(&[0xff], 3);
+        assert_eq!(
+            bitmap.iter_visible_clusters().collect::<Vec<_>>(),
+            vec![ClusterIndex(0), ClusterIndex(1), ClusterIndex(2)],
+        );
+    }
+
+    #[test]
+    fn find_data_stops_at_the_end_of_the_bitmap() {
+        let data = [0x00, 0x01, 0x10, 0xaa, 0xbb];
+        assert_eq!(VisibilityBitmap::new(&data, 16).find_data(), &data[..3]);
+    }
+
+    #[test]
+    fn truncated_data_is_not_an_error() {
+        let data = [0x01, 0x00];
+        let bitmap = VisibilityBitmap::new(&data, 64);
+        assert_eq!(
+            bitmap.iter_visible_clusters().collect::<Vec<_>>(),
+            vec![ClusterIndex(0)],
+        );
+        assert_eq!(bitmap.find_data(), &data[..]);
+    }
+}
@@ -0,0 +1,19 @@
+//! Potentially visible set (PVS) data shared between the packer and the runtime.
+//!
+//! Each cluster's PVS is a bitmap over all clusters, compressed with the run-length scheme Source
+//! uses: a zero byte is followed by a count of zero bytes it stands for, and any other byte holds
+//! eight clusters' bits, least significant first.
+
+#![no_std]
+#![deny(unsafe_op_in_unsafe_fn)]
+
+extern crate alloc;
+
+mod bitmap;
+mod packed;
+
+pub use crate::bitmap::{VisibilityBitmap, VisibleClusters};
+pub use crate::packed::{pack, PackedVisibility};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ClusterIndex(pub usize);
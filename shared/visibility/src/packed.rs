@@ -0,0 +1,111 @@
+use alloc::vec::Vec;
+use core::iter::ExactSizeIterator;
+
+use crate::{ClusterIndex, VisibilityBitmap};
+
+/// Every cluster's PVS in the packed map format: a big-endian u32 cluster count, then a
+/// big-endian u32 byte offset for each cluster's bitmap, then the bitmaps themselves.
+#[derive(Clone, Copy)]
+pub struct PackedVisibility<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> PackedVisibility<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Returns the number of clusters, or zero if the data is too short to hold a count.
+    pub fn num_clusters(self) -> usize {
+        read_u32(self.data, 0).unwrap_or(0) as usize
+    }
+
+    /// Returns the PVS for the given cluster, or `None` if the cluster or its offset is out of
+    /// range.
+    pub fn get_cluster(self, index: ClusterIndex) -> Option<VisibilityBitmap<'a>> {
+        let num_clusters = self.num_clusters();
+        if index.0 >= num_clusters {
+            return None;
+        }
+        let offset = read_u32(self.data, 4 * index.0 + 4)? as usize;
+        Some(VisibilityBitmap::new(
+            self.data.get(offset..)?,
+            num_clusters,
+        ))
+    }
+
+    pub fn iter_clusters(self) -> impl Iterator<Item = VisibilityBitmap<'a>> {
+        (0..self.num_clusters()).filter_map(move |index| self.get_cluster(ClusterIndex(index)))
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        data.get(offset..offset + 4)?.try_into().unwrap(),
+    ))
+}
+
+/// Packs compressed bitmaps, one per cluster in order, into the format [`PackedVisibility`] reads.
+pub fn pack<'b>(bitmaps: impl ExactSizeIterator<Item = &'b [u8]> + Clone) -> Vec<u8> {
+    let num_clusters = bitmaps.len();
+    let mut data = Vec::new();
+    data.extend_from_slice(&(num_clusters as u32).to_be_bytes());
+
+    // Build the index.
+    let mut offset = 4 * num_clusters as u32 + 4;
+    for bitmap in bitmaps.clone() {
+        data.extend_from_slice(&offset.to_be_bytes());
+        offset += bitmap.len() as u32;
+    }
+
+    // Append all bitmaps.
+    for bitmap in bitmaps {
+        data.extend_from_slice(bitmap);
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{pack, ClusterIndex, PackedVisibility};
+
+    #[test]
+    fn round_trip() {
+        let bitmaps: [&[u8]; 3] = [&[0x05], &[0x00, 0x01], &[0x02]];
+        let data = pack(bitmaps.iter().copied());
+        let visibility = PackedVisibility::new(&data);
+
+        assert_eq!(visibility.num_clusters(), 3);
+        let visible: Vec<Vec<_>> = visibility
+            .iter_clusters()
+            .map(|bitmap| bitmap.iter_visible_clusters().collect())
+            .collect();
+        assert_eq!(
+            visible,
+            [
+                &[ClusterIndex(0), ClusterIndex(2)][..],
+                &[][..],
+                &[ClusterIndex(1)][..],
+            ],
+        );
+        for (bitmap, expected) in visibility.iter_clusters().zip(bitmaps) {
+            assert_eq!(bitmap.find_data(), expected);
+        }
+    }
+
+    #[test]
+    fn out_of_range_clusters() {
+        let data = pack([&[0x01][..]].into_iter());
+        let visibility = PackedVisibility::new(&data);
+        assert!(visibility.get_cluster(ClusterIndex(0)).is_some());
+        assert!(visibility.get_cluster(ClusterIndex(1)).is_none());
+
+        // A count that promises more offsets than the data holds.
+        let truncated = PackedVisibility::new(&[0, 0, 0, 2, 0, 0, 0, 8]);
+        assert!(truncated.get_cluster(ClusterIndex(0)).is_some());
+        assert!(truncated.get_cluster(ClusterIndex(1)).is_none());
+        assert_eq!(PackedVisibility::new(&[]).num_clusters(), 0);
+    }
+}